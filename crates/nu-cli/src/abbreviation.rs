@@ -0,0 +1,237 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use reedline::{
+    EditCommand, EditMode, Highlighter, PromptEditMode, PromptViMode, ReedlineEvent,
+    ReedlineRawEvent, StyledText,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The line editor's buffer and cursor as of its last repaint.
+///
+/// An [`EditMode`] doesn't get to see the buffer, but every repaint highlights it, so
+/// [`AbbreviationHighlighter`] records it here for [`AbbreviationEditMode`]. The REPL also reads
+/// it to keep a line discarded with Ctrl+C, which the line editor clears without repainting.
+#[derive(Clone, Default)]
+pub(crate) struct LineSnapshot(Arc<Mutex<Snapshot>>);
+
+#[derive(Default)]
+struct Snapshot {
+    line: String,
+    cursor: usize,
+    /// Whether no event was parsed since the buffer was recorded. Reedline repaints once for
+    /// all the events that are ready, so otherwise the buffer might be out of date.
+    current: bool,
+}
+
+impl LineSnapshot {
+    fn record(&self, line: &str, cursor: usize) {
+        if let Ok(mut snapshot) = self.0.lock() {
+            snapshot.line.clear();
+            snapshot.line.push_str(line);
+            snapshot.cursor = cursor;
+            snapshot.current = true;
+        }
+    }
+
+    /// The buffer as of the last repaint, even if events were parsed since.
+    pub(crate) fn line(&self) -> String {
+        self.0
+            .lock()
+            .map(|snapshot| snapshot.line.clone())
+            .unwrap_or_default()
+    }
+
+    /// The buffer and cursor, if they're up to date. Either way, they aren't after this event.
+    fn take_current(&self) -> Option<(String, usize)> {
+        let mut snapshot = self.0.lock().ok()?;
+        let current = std::mem::take(&mut snapshot.current);
+        current.then(|| (snapshot.line.clone(), snapshot.cursor))
+    }
+}
+
+/// Wraps the highlighter of the line editor to record its buffer in a [`LineSnapshot`].
+pub(crate) struct AbbreviationHighlighter {
+    pub highlighter: Box<dyn Highlighter>,
+    pub snapshot: LineSnapshot,
+}
+
+impl Highlighter for AbbreviationHighlighter {
+    fn highlight(&self, line: &str, cursor: usize) -> StyledText {
+        self.snapshot.record(line, cursor);
+        self.highlighter.highlight(line, cursor)
+    }
+}
+
+/// Wraps the edit mode of the line editor to expand abbreviations when space is pressed, with
+/// the edits for the expansion and the space, so that the line editor never has to return.
+pub(crate) struct AbbreviationEditMode {
+    pub edit_mode: Box<dyn EditMode>,
+    pub abbreviations: HashMap<String, String>,
+    pub snapshot: LineSnapshot,
+}
+
+impl AbbreviationEditMode {
+    fn expand(&self) -> Option<ReedlineEvent> {
+        let (line, cursor) = self.snapshot.take_current()?;
+        let (line, cursor) = expand_abbreviation(&line, cursor, &self.abbreviations)?;
+        Some(ReedlineEvent::Edit(vec![
+            EditCommand::Clear,
+            EditCommand::InsertString(line),
+            EditCommand::MoveToPosition {
+                position: cursor,
+                select: false,
+            },
+            EditCommand::InsertChar(' '),
+        ]))
+    }
+}
+
+impl EditMode for AbbreviationEditMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = Event::from(event);
+        let expansion = match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(' '),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            }) if !matches!(self.edit_mode(), PromptEditMode::Vi(PromptViMode::Normal)) => {
+                self.expand()
+            }
+            _ => {
+                self.snapshot.take_current();
+                None
+            }
+        };
+        match expansion {
+            Some(expansion) => expansion,
+            None => ReedlineRawEvent::try_from(event).map_or(ReedlineEvent::None, |event| {
+                self.edit_mode.parse_event(event)
+            }),
+        }
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.edit_mode.edit_mode()
+    }
+}
+
+/// Expand the abbreviation right before the cursor, if it's in command position.
+///
+/// Returns the new buffer and cursor position.
+fn expand_abbreviation(
+    buffer: &str,
+    cursor_pos: usize,
+    abbreviations: &HashMap<String, String>,
+) -> Option<(String, usize)> {
+    let before = buffer.get(..cursor_pos)?;
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace() || matches!(c, '|' | ';' | '(' | '{'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let expansion = abbreviations.get(&before[start..])?;
+    let preceding = before[..start].trim_end();
+    if !(preceding.is_empty() || preceding.ends_with(['|', ';', '(', '{'])) {
+        return None;
+    }
+    Some((
+        format!("{}{expansion}{}", &before[..start], &buffer[cursor_pos..]),
+        start + expansion.len(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nu_highlight::NoOpHighlighter;
+    use reedline::Emacs;
+
+    fn abbreviations() -> HashMap<String, String> {
+        HashMap::from([("gc".to_string(), "git commit -m".to_string())])
+    }
+
+    fn space() -> ReedlineRawEvent {
+        ReedlineRawEvent::try_from(Event::Key(KeyEvent::new(
+            KeyCode::Char(' '),
+            KeyModifiers::NONE,
+        )))
+        .expect("key press event")
+    }
+
+    fn edit_mode(snapshot: &LineSnapshot) -> AbbreviationEditMode {
+        AbbreviationEditMode {
+            edit_mode: Box::new(Emacs::default()),
+            abbreviations: abbreviations(),
+            snapshot: snapshot.clone(),
+        }
+    }
+
+    #[test]
+    fn expands_abbreviations_in_command_position() {
+        let abbreviations = abbreviations();
+        assert_eq!(
+            expand_abbreviation("gc", 2, &abbreviations),
+            Some(("git commit -m".to_string(), 13))
+        );
+        assert_eq!(
+            expand_abbreviation("ls | gc", 7, &abbreviations),
+            Some(("ls | git commit -m".to_string(), 18))
+        );
+        assert_eq!(expand_abbreviation("echo gc", 7, &abbreviations), None);
+        assert_eq!(expand_abbreviation("gcc", 3, &abbreviations), None);
+    }
+
+    #[test]
+    fn space_expands_the_highlighted_line() {
+        let snapshot = LineSnapshot::default();
+        let highlighter = AbbreviationHighlighter {
+            highlighter: Box::<NoOpHighlighter>::default(),
+            snapshot: snapshot.clone(),
+        };
+        let mut edit_mode = edit_mode(&snapshot);
+
+        highlighter.highlight("ls | gc", 7);
+        assert_eq!(
+            edit_mode.parse_event(space()),
+            ReedlineEvent::Edit(vec![
+                EditCommand::Clear,
+                EditCommand::InsertString("ls | git commit -m".into()),
+                EditCommand::MoveToPosition {
+                    position: 18,
+                    select: false,
+                },
+                EditCommand::InsertChar(' '),
+            ])
+        );
+    }
+
+    #[test]
+    fn cleared_line_is_recorded() {
+        let snapshot = LineSnapshot::default();
+        let highlighter = AbbreviationHighlighter {
+            highlighter: Box::<NoOpHighlighter>::default(),
+            snapshot: snapshot.clone(),
+        };
+
+        highlighter.highlight("ls -la", 6);
+        highlighter.highlight("", 0);
+        assert_eq!(snapshot.line(), "");
+    }
+
+    #[test]
+    fn space_is_inserted_when_the_line_is_out_of_date() {
+        let snapshot = LineSnapshot::default();
+        let mut edit_mode = edit_mode(&snapshot);
+
+        snapshot.record("gc", 2);
+        edit_mode.parse_event(space());
+        // The line wasn't highlighted again since the last space
+        assert_eq!(
+            edit_mode.parse_event(space()),
+            ReedlineEvent::Edit(vec![EditCommand::InsertChar(' ')])
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct Abbr;
+
+impl Command for Abbr {
+    fn name(&self) -> &str {
+        "abbr"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .category(Category::Platform)
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+    }
+
+    fn description(&self) -> &str {
+        "List the abbreviations expanded in the line editor."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Abbreviations are expanded in place when space is pressed after them in command position, so the full command is what ends up in the history. They're kept in `$env.config.abbreviations`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["abbreviation", "alias", "expand"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "List the abbreviations",
+            example: "abbr",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let config = stack.get_config(engine_state);
+        let mut abbreviations = config.abbreviations.iter().collect::<Vec<_>>();
+        abbreviations.sort();
+        let rows = abbreviations
+            .into_iter()
+            .map(|(name, expansion)| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(name, head),
+                        "expansion" => Value::string(expansion, head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
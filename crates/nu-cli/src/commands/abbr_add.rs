@@ -0,0 +1,69 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AbbrAdd;
+
+impl Command for AbbrAdd {
+    fn name(&self) -> &str {
+        "abbr add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .category(Category::Platform)
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("name", SyntaxShape::String, "The word to expand.")
+            .required(
+                "expansion",
+                SyntaxShape::String,
+                "The text to replace the word with.",
+            )
+    }
+
+    fn description(&self) -> &str {
+        "Add an abbreviation that is expanded in the line editor."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"This adds the abbreviation to `$env.config.abbreviations` for the current session. To keep it, add it to your config instead."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["abbreviation", "alias", "expand"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Expand `gc` to `git commit -m` when space is pressed after it",
+            example: "abbr add gc 'git commit -m'",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let expansion: String = call.req(engine_state, stack, 1)?;
+
+        if name.item.is_empty() || name.item.contains(char::is_whitespace) {
+            return Err(ShellError::InvalidValue {
+                valid: "a single word".into(),
+                actual: format!("'{}'", name.item),
+                span: name.span,
+            });
+        }
+
+        let mut config = (*stack.get_config(engine_state)).clone();
+        config.abbreviations.insert(name.item, expansion);
+        stack.add_env_var("config".into(), config.into_value(head));
+        stack.update_config(engine_state)?;
+
+        Ok(PipelineData::empty())
+    }
+}
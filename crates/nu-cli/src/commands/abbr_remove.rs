@@ -0,0 +1,59 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AbbrRemove;
+
+impl Command for AbbrRemove {
+    fn name(&self) -> &str {
+        "abbr remove"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .category(Category::Platform)
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("name", SyntaxShape::String, "The abbreviation to remove.")
+    }
+
+    fn description(&self) -> &str {
+        "Remove an abbreviation from the line editor."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["abbreviation", "alias", "expand"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Stop expanding `gc`",
+            example: "abbr remove gc",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+
+        let mut config = (*stack.get_config(engine_state)).clone();
+        if config.abbreviations.remove(&name.item).is_none() {
+            return Err(ShellError::GenericError {
+                error: format!("Abbreviation not found: {}", name.item),
+                msg: "no abbreviation with this name".into(),
+                span: Some(name.span),
+                help: Some("use `abbr` to list the abbreviations".into()),
+                inner: vec![],
+            });
+        }
+        stack.add_env_var("config".into(), config.into_value(head));
+        stack.update_config(engine_state)?;
+
+        Ok(PipelineData::empty())
+    }
+}
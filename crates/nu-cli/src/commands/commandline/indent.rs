@@ -0,0 +1,228 @@
+use nu_engine::command_prelude::*;
+
+/// The indentation for each level of nesting.
+const INDENT: &str = "    ";
+
+#[derive(Clone)]
+pub struct CommandlineIndent;
+
+impl Command for CommandlineIndent {
+    fn name(&self) -> &str {
+        "commandline indent"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .switch(
+                "newline",
+                "Insert a newline at the cursor, indented to match the blocks open before it.",
+                Some('n'),
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Re-indent the current command line input buffer."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Each line is indented by four spaces for every block, list, record, or parenthesized \
+        expression it is in, and one more level when the line before it ends with a pipe. Lines \
+        in multiline strings are left as they are."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "multiline", "format"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "$env.config.keybindings ++= [{ name: indented_newline, modifier: alt, keycode: enter, mode: [emacs vi_insert], event: { send: executehostcommand, cmd: 'commandline indent --newline' } }]",
+                description: "Insert an indented newline with Alt+Enter",
+                result: None,
+            },
+            Example {
+                example: "$env.config.keybindings ++= [{ name: reindent, modifier: alt, keycode: char_i, mode: [emacs vi_normal vi_insert], event: { send: executehostcommand, cmd: 'commandline indent' } }]",
+                description: "Re-indent a pasted block with Alt+I",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let newline = call.has_flag(engine_state, stack, "newline")?;
+        let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+        let cursor_pos = repl.cursor_pos.min(repl.buffer.len());
+        let (before, after) = repl.buffer.split_at(cursor_pos);
+
+        let (buffer, cursor_pos) = if newline {
+            let inserted = format!("\n{}", indentation_after(before));
+            let after = after.trim_start_matches([' ', '\t']);
+            (
+                format!("{before}{inserted}{after}"),
+                cursor_pos + inserted.len(),
+            )
+        } else {
+            // Keep the cursor on the same text of its line
+            let line = before.matches('\n').count();
+            let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+            let reindented = reindent(&repl.buffer);
+            let old_line = repl.buffer.split('\n').nth(line).unwrap_or_default();
+            let new_line = reindented.split('\n').nth(line).unwrap_or_default();
+            let line_start = reindented
+                .split('\n')
+                .take(line)
+                .map(|line| line.len() + 1)
+                .sum::<usize>();
+            let old_indent = old_line.len() - old_line.trim_start().len();
+            let new_indent = new_line.len() - new_line.trim_start().len();
+            let column = (new_indent + column.saturating_sub(old_indent)).min(new_line.len());
+            (reindented, line_start + column)
+        };
+
+        repl.buffer = buffer;
+        repl.cursor_pos = cursor_pos;
+
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+}
+
+/// How deeply nested the end of some text is.
+#[derive(Debug, Default, Clone, Copy)]
+struct Nesting {
+    /// The number of open blocks, lists, records, and parentheses.
+    depth: usize,
+    /// The quote of the string the text ends in, if any.
+    quote: Option<char>,
+    /// Whether the text ends with a pipe, so the pipeline continues on the next line.
+    continues: bool,
+}
+
+impl Nesting {
+    fn indentation(&self) -> String {
+        INDENT.repeat(self.depth + usize::from(self.continues))
+    }
+}
+
+/// Scan a line of code that starts with the given nesting, and return the nesting at its end.
+fn scan_line(line: &str, mut nesting: Nesting) -> Nesting {
+    let mut chars = line.chars();
+    let mut after_whitespace = true;
+    let mut in_parameters = false;
+    let mut last = None;
+    while let Some(c) = chars.next() {
+        if let Some(quote) = nesting.quote {
+            if c == '\\' && quote == '"' {
+                chars.next();
+            } else if c == quote {
+                nesting.quote = None;
+                last = Some(c);
+            }
+            after_whitespace = false;
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => nesting.quote = Some(c),
+            '#' if after_whitespace => break,
+            '{' | '(' | '[' => nesting.depth += 1,
+            '}' | ')' | ']' => nesting.depth = nesting.depth.saturating_sub(1),
+            // The parameters of a closure, like `{|x| ... }`, are not a pipe
+            '|' if last == Some('{') => in_parameters = true,
+            '|' if in_parameters => {
+                in_parameters = false;
+                after_whitespace = false;
+                last = Some(')');
+                continue;
+            }
+            _ => {}
+        }
+        after_whitespace = c.is_whitespace();
+        if !after_whitespace {
+            last = Some(c);
+        }
+    }
+    nesting.continues = nesting.quote.is_none() && last == Some('|');
+    nesting
+}
+
+/// The indentation for a new line after `text`.
+fn indentation_after(text: &str) -> String {
+    let nesting = text
+        .split('\n')
+        .fold(Nesting::default(), |nesting, line| scan_line(line, nesting));
+    if nesting.quote.is_some() {
+        String::new()
+    } else {
+        nesting.indentation()
+    }
+}
+
+/// Re-indent each line of `text` by how deeply it's nested.
+fn reindent(text: &str) -> String {
+    let mut nesting = Nesting::default();
+    let mut lines = vec![];
+    for line in text.split('\n') {
+        if nesting.quote.is_some() {
+            lines.push(line.to_string());
+        } else {
+            let trimmed = line.trim_start();
+            let closing = trimmed
+                .chars()
+                .take_while(|c| matches!(c, '}' | ')' | ']'))
+                .count();
+            let level = Nesting {
+                depth: nesting.depth.saturating_sub(closing),
+                ..nesting
+            };
+            if trimmed.is_empty() {
+                lines.push(String::new());
+            } else {
+                lines.push(format!("{}{trimmed}", level.indentation()));
+            }
+        }
+        nesting = scan_line(line, nesting);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_after_open_blocks() {
+        assert_eq!("", indentation_after("ls"));
+        assert_eq!(INDENT, indentation_after("ls | each {|file|"));
+        assert_eq!(INDENT.repeat(2), indentation_after("do {\n    [1, 2,"));
+        assert_eq!(INDENT, indentation_after("ls |"));
+        assert_eq!("", indentation_after("echo '{"));
+        assert_eq!("", indentation_after("ls # {"));
+    }
+
+    #[test]
+    fn reindents_pasted_blocks() {
+        let pasted = "ls\n| each {|file|\nif $file.size > 1kb {\n$file.name\n}\n}";
+        let expected =
+            "ls\n| each {|file|\n    if $file.size > 1kb {\n        $file.name\n    }\n}";
+        assert_eq!(expected, reindent(pasted));
+    }
+
+    #[test]
+    fn reindent_keeps_multiline_strings() {
+        let text = "let s = \"\n  keep\n\"\n  ls";
+        assert_eq!("let s = \"\n  keep\n\"\nls", reindent(text));
+    }
+
+    #[test]
+    fn reindent_indents_pipeline_continuations() {
+        assert_eq!("ls |\n    length", reindent("ls |\nlength"));
+    }
+}
@@ -1,9 +1,15 @@
 mod commandline_;
 mod edit;
 mod get_cursor;
+mod indent;
+mod restore;
 mod set_cursor;
+mod surround;
 
 pub use commandline_::Commandline;
 pub use edit::CommandlineEdit;
 pub use get_cursor::CommandlineGetCursor;
+pub use indent::CommandlineIndent;
+pub use restore::CommandlineRestore;
 pub use set_cursor::CommandlineSetCursor;
+pub use surround::CommandlineSurround;
@@ -0,0 +1,131 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::engine::ReplState;
+
+#[derive(Clone)]
+pub struct CommandlineRestore;
+
+impl Command for CommandlineRestore {
+    fn name(&self) -> &str {
+        "commandline restore"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::List(Box::new(Type::String))),
+            ])
+            .switch(
+                "list",
+                "List the discarded command lines, from the most recent, instead of restoring one.",
+                Some('l'),
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Bring back a command line that was discarded with Ctrl+C."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The most recently discarded command line replaces the current one. Running it again \
+        brings back the one before it, and a replaced command line that wasn't empty is kept to \
+        be restored later, so no input is lost by cycling through them. The last 50 discarded \
+        command lines of the session are kept. Edits to the current command line can be undone \
+        and redone with the `undo` and `redo` edit events, but that history only lasts until the \
+        command line is run or discarded: it isn't restored with the command line, and isn't \
+        kept between sessions."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "undo", "recover", "kill ring"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "$env.config.keybindings ++= [{ name: restore_commandline, modifier: alt, keycode: char_z, mode: [emacs vi_normal vi_insert], event: { send: executehostcommand, cmd: 'commandline restore' } }]",
+                description: "Bring back a command line discarded with Ctrl+C by pressing Alt+Z",
+                result: None,
+            },
+            Example {
+                example: "commandline restore --list",
+                description: "List the discarded command lines",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+
+        if call.has_flag(engine_state, stack, "list")? {
+            let discarded = repl
+                .discarded
+                .iter()
+                .rev()
+                .map(|buffer| Value::string(buffer, head))
+                .collect();
+            return Ok(Value::list(discarded, head).into_pipeline_data());
+        }
+
+        restore(&mut repl);
+        Ok(Value::nothing(head).into_pipeline_data())
+    }
+}
+
+/// Replace the buffer with the most recently discarded one, keeping the replaced buffer as the
+/// oldest, so that restoring repeatedly cycles through all of them.
+fn restore(repl: &mut ReplState) {
+    let Some(restored) = repl.discarded.pop_back() else {
+        return;
+    };
+    let replaced = std::mem::replace(&mut repl.buffer, restored);
+    if !replaced.trim().is_empty() {
+        repl.discarded.push_front(replaced);
+    }
+    repl.cursor_pos = repl.buffer.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_most_recent_first() {
+        let mut repl = ReplState::default();
+        repl.discard("ls".into());
+        repl.discard("cd ..".into());
+
+        restore(&mut repl);
+        assert_eq!("cd ..", repl.buffer);
+        assert_eq!(5, repl.cursor_pos);
+
+        restore(&mut repl);
+        assert_eq!("ls", repl.buffer);
+
+        // The replaced command lines are kept, so restoring cycles through them
+        restore(&mut repl);
+        assert_eq!("cd ..", repl.buffer);
+    }
+
+    #[test]
+    fn keeps_discarded_buffers_bounded() {
+        let mut repl = ReplState::default();
+        repl.discard("   ".into());
+        assert!(repl.discarded.is_empty());
+
+        for i in 0..ReplState::MAX_DISCARDED + 10 {
+            repl.discard(format!("echo {i}"));
+        }
+        assert_eq!(ReplState::MAX_DISCARDED, repl.discarded.len());
+        assert_eq!(Some(&"echo 10".to_string()), repl.discarded.front());
+    }
+}
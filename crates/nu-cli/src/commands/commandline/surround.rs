@@ -0,0 +1,256 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct CommandlineSurround;
+
+impl Command for CommandlineSurround {
+    fn name(&self) -> &str {
+        "commandline surround"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .switch(
+                "delete",
+                "Delete the innermost pair around the cursor, instead of adding one.",
+                Some('d'),
+            )
+            .named(
+                "change",
+                SyntaxShape::String,
+                "Change the innermost pair around the cursor to this one, instead of adding one.",
+                Some('c'),
+            )
+            .required(
+                "pair",
+                SyntaxShape::String,
+                "The quote or bracket to surround with, like `\"` or `(`.",
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Add, change, or delete the quotes or brackets surrounding the cursor."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Like vim-surround: without flags, the word under the cursor is surrounded by the pair. \
+        Either bracket of a pair can be given. Bind this to keys in `$env.config.keybindings` to \
+        use it while editing, together with the vi text objects like `ci(`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "vi", "quote", "bracket"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"$env.config.keybindings ++= [{ name: quote_word, modifier: alt, keycode: 'char_"', mode: [emacs vi_normal vi_insert], event: { send: executehostcommand, cmd: 'commandline surround "\""' } }]"#,
+                description: "Quote the word under the cursor with Alt+\"",
+                result: None,
+            },
+            Example {
+                example: "commandline surround --change '[' '('",
+                description: "Change the parentheses around the cursor to square brackets",
+                result: None,
+            },
+            Example {
+                example: "commandline surround --delete '\"'",
+                description: "Delete the quotes around the cursor",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let pair: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let delete = call.has_flag(engine_state, stack, "delete")?;
+        let change: Option<Spanned<String>> = call.get_flag(engine_state, stack, "change")?;
+
+        let pair = parse_pair(&pair)?;
+        let change = change.map(|change| parse_pair(&change)).transpose()?;
+
+        let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+        let cursor_pos = repl.cursor_pos.min(repl.buffer.len());
+        let edited = if delete || change.is_some() {
+            find_pair(&repl.buffer, cursor_pos, pair).map(|(open, close)| {
+                let (new_open, new_close) = change
+                    .map(|(open, close)| (open.to_string(), close.to_string()))
+                    .unwrap_or_default();
+                replace_pair(&repl.buffer, cursor_pos, open, close, &new_open, &new_close)
+            })
+        } else {
+            let (start, end) = word_at(&repl.buffer, cursor_pos);
+            let (open, close) = pair;
+            let buffer = format!(
+                "{}{open}{}{close}{}",
+                &repl.buffer[..start],
+                &repl.buffer[start..end],
+                &repl.buffer[end..]
+            );
+            Some((buffer, cursor_pos + open.len_utf8()))
+        };
+
+        if let Some((buffer, cursor_pos)) = edited {
+            repl.buffer = buffer;
+            repl.cursor_pos = cursor_pos;
+        }
+
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+}
+
+/// Get the opening and closing characters of a pair from either of them.
+fn parse_pair(pair: &Spanned<String>) -> Result<(char, char), ShellError> {
+    let mut chars = pair.item.chars();
+    match (chars.next(), chars.next()) {
+        (Some('(' | ')'), None) => Ok(('(', ')')),
+        (Some('[' | ']'), None) => Ok(('[', ']')),
+        (Some('{' | '}'), None) => Ok(('{', '}')),
+        (Some('<' | '>'), None) => Ok(('<', '>')),
+        (Some(c @ ('"' | '\'' | '`')), None) => Ok((c, c)),
+        _ => Err(ShellError::InvalidValue {
+            valid: "a quote or bracket".into(),
+            actual: format!("'{}'", pair.item),
+            span: pair.span,
+        }),
+    }
+}
+
+/// The start and end of the word under the cursor, or right before it.
+fn word_at(buffer: &str, cursor_pos: usize) -> (usize, usize) {
+    let start = buffer[..cursor_pos]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let end = buffer[cursor_pos..]
+        .find(char::is_whitespace)
+        .map_or(buffer.len(), |i| cursor_pos + i);
+    (start, end)
+}
+
+/// Find the positions of the innermost `pair` around the cursor.
+fn find_pair(
+    buffer: &str,
+    cursor_pos: usize,
+    (open, close): (char, char),
+) -> Option<(usize, usize)> {
+    if open == close {
+        // Quotes can't be nested, so take the nearest ones on either side of the cursor
+        let start = buffer[..cursor_pos]
+            .rfind(open)
+            .or_else(|| buffer[cursor_pos..].starts_with(open).then_some(cursor_pos))?;
+        let end = start + open.len_utf8() + buffer[start + open.len_utf8()..].find(close)?;
+        return (end >= cursor_pos).then_some((start, end));
+    }
+
+    let mut depth = 0usize;
+    let mut start = None;
+    // A bracket under the cursor counts as being inside it, like in vim
+    let scan_end = if buffer[cursor_pos..].starts_with(open) {
+        cursor_pos + open.len_utf8()
+    } else {
+        cursor_pos
+    };
+    for (i, c) in buffer[..scan_end].char_indices().rev() {
+        if c == close {
+            depth += 1;
+        } else if c == open {
+            if depth == 0 {
+                start = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let start = start?;
+    let mut depth = 0usize;
+    for (i, c) in buffer[start + open.len_utf8()..].char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some((start, start + open.len_utf8() + i));
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+/// Replace the characters at `open` and `close` with new ones, keeping the cursor on the same
+/// text.
+fn replace_pair(
+    buffer: &str,
+    cursor_pos: usize,
+    open: usize,
+    close: usize,
+    new_open: &str,
+    new_close: &str,
+) -> (String, usize) {
+    let open_len = buffer[open..].chars().next().map_or(0, char::len_utf8);
+    let close_len = buffer[close..].chars().next().map_or(0, char::len_utf8);
+    let edited = format!(
+        "{}{new_open}{}{new_close}{}",
+        &buffer[..open],
+        &buffer[open + open_len..close],
+        &buffer[close + close_len..]
+    );
+    let cursor_pos = if cursor_pos <= open {
+        cursor_pos
+    } else if cursor_pos <= close {
+        (cursor_pos - open_len + new_open.len()).max(open)
+    } else {
+        cursor_pos - open_len - close_len + new_open.len() + new_close.len()
+    };
+    (edited, cursor_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_innermost_brackets() {
+        let buffer = "each {|x| ($x + 1) }";
+        let cursor = buffer.find('+').unwrap_or_default();
+        assert_eq!(Some((10, 17)), find_pair(buffer, cursor, ('(', ')')));
+        assert_eq!(Some((5, 19)), find_pair(buffer, cursor, ('{', '}')));
+        assert_eq!(None, find_pair(buffer, cursor, ('[', ']')));
+    }
+
+    #[test]
+    fn finds_quotes() {
+        let buffer = r#"echo "hello world""#;
+        assert_eq!(Some((5, 17)), find_pair(buffer, 9, ('"', '"')));
+        assert_eq!(None, find_pair(buffer, 2, ('"', '"')));
+    }
+
+    #[test]
+    fn changes_and_deletes_pairs() {
+        let buffer = "echo (ls)";
+        assert_eq!(
+            ("echo [ls]".to_string(), 7),
+            replace_pair(buffer, 7, 5, 8, "[", "]")
+        );
+        assert_eq!(
+            ("echo ls".to_string(), 6),
+            replace_pair(buffer, 7, 5, 8, "", "")
+        );
+    }
+
+    #[test]
+    fn surrounds_word() {
+        assert_eq!((5, 10), word_at("echo hello world", 7));
+        assert_eq!((5, 10), word_at("echo hello world", 10));
+    }
+}
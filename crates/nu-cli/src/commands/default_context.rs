@@ -12,11 +12,20 @@ pub fn add_cli_context(mut engine_state: EngineState) -> EngineState {
         }
 
         bind_command! {
+            Abbr,
+            AbbrAdd,
+            AbbrRemove,
             Commandline,
             CommandlineEdit,
             CommandlineGetCursor,
+            CommandlineIndent,
+            CommandlineRestore,
             CommandlineSetCursor,
+            CommandlineSurround,
+            EnvDeny,
+            EnvTrust,
             History,
+            HistoryEdit,
             Keybindings,
             KeybindingsDefault,
             KeybindingsList,
@@ -26,6 +35,7 @@ pub fn add_cli_context(mut engine_state: EngineState) -> EngineState {
         #[cfg(feature = "sqlite")]
         bind_command! {
             HistoryImport,
+            HistoryPrune,
             HistorySession
         };
 
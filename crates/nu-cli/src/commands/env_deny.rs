@@ -0,0 +1,70 @@
+use super::env_trust::env_file_arg;
+use crate::directory_env::{LOADED_ENV_FILE, TrustStore};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+
+#[derive(Clone)]
+pub struct EnvDeny;
+
+impl Command for EnvDeny {
+    fn name(&self) -> &str {
+        "env deny"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .category(Category::Env)
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The environment file, or its directory (default: the one of the current directory).",
+            )
+    }
+
+    fn description(&self) -> &str {
+        "Stop loading a directory's environment file, and stop asking to trust it."
+    }
+
+    fn extra_description(&self) -> &str {
+        "If the file is loaded, it's unloaded before the next prompt. Use `env trust` to load it again."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["direnv", "block", "overlay", ".nu-env"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Unload the environment file of the current directory, and never load it",
+            example: "env deny",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let file = env_file_arg(engine_state, stack, call)?;
+
+        let mut store = TrustStore::load();
+        store.deny(&file);
+        store.save().map_err(|err| IoError::new(err, head, None))?;
+
+        let loaded = stack
+            .get_env_var(engine_state, LOADED_ENV_FILE)
+            .and_then(|loaded| loaded.as_str().ok())
+            .is_some_and(|loaded| file.as_os_str() == loaded);
+        if loaded {
+            // Makes the REPL unload the overlay, as the loaded file no longer matches
+            stack.remove_env_var(engine_state, LOADED_ENV_FILE);
+        }
+
+        Ok(PipelineData::empty())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::directory_env::{ENV_FILE_NAMES, TrustStore, find_env_file};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct EnvTrust;
+
+impl Command for EnvTrust {
+    fn name(&self) -> &str {
+        "env trust"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .category(Category::Env)
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The environment file, or its directory (default: the one of the current directory).",
+            )
+    }
+
+    fn description(&self) -> &str {
+        "Trust a directory's environment file, so it's loaded when entering the directory."
+    }
+
+    fn extra_description(&self) -> &str {
+        "When `$env.config.directory_env` is enabled, the `.nu-env` or `nu.env.nu` file of the \
+        current directory or its closest parent is loaded as the `nu-env` overlay, and unloaded \
+        again when leaving the directory. Files are only loaded once trusted, and have to be \
+        trusted again after they change."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["direnv", "allow", "overlay", ".nu-env"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Trust the environment file of the current directory",
+            example: "env trust",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let file = env_file_arg(engine_state, stack, call)?;
+
+        let mut store = TrustStore::load();
+        store
+            .trust(&file)
+            .map_err(|err| IoError::new(err, head, file.clone()))?;
+        store.save().map_err(|err| IoError::new(err, head, None))?;
+
+        Ok(PipelineData::empty())
+    }
+}
+
+/// The environment file given as the first argument, or the one of the current directory.
+pub(crate) fn env_file_arg(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<PathBuf, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?;
+    let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+
+    let file = match &path {
+        Some(path) => {
+            let path = nu_path::expand_path_with(&path.item, &cwd, true);
+            if path.is_dir() {
+                ENV_FILE_NAMES
+                    .iter()
+                    .map(|name| path.join(name))
+                    .find(|file| file.is_file())
+            } else {
+                path.is_file().then_some(path)
+            }
+        }
+        None => find_env_file(cwd.as_std_path()),
+    };
+
+    file.ok_or_else(|| ShellError::GenericError {
+        error: "No environment file found".into(),
+        msg: format!("there is no {} here", ENV_FILE_NAMES.join(" or ")),
+        span: Some(path.map_or(call.head, |path| path.span)),
+        help: None,
+        inner: vec![],
+    })
+}
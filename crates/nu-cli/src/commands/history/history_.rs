@@ -30,6 +30,11 @@ impl Command for History {
                 "Show long format with timestamps and additional details.",
                 Some('l'),
             )
+            .switch(
+                "cwd",
+                "Only show commands run in the current directory (SQLite history only).",
+                None,
+            )
             .category(Category::History)
     }
 
@@ -50,6 +55,11 @@ impl Command for History {
                 description: "Show last 5 commands with full details",
                 result: None,
             },
+            Example {
+                example: "history --cwd | last 5",
+                description: "Show the last 5 commands you ran in the current directory",
+                result: None,
+            },
             Example {
                 example: "history | where command =~ cargo | get command",
                 description: "Search all the commands from history that contains 'cargo'",
@@ -83,6 +93,21 @@ impl Command for History {
 
         #[cfg_attr(not(feature = "sqlite"), allow(unused_variables))]
         let long = call.has_flag(engine_state, stack, "long")?;
+        #[cfg_attr(not(feature = "sqlite"), allow(unused_variables))]
+        let cwd = if call.has_flag(engine_state, stack, "cwd")? {
+            if history.file_format == HistoryFileFormat::Plaintext {
+                return Err(ShellError::GenericError {
+                    error: "Can't filter plaintext history by directory".into(),
+                    msg: "plaintext history doesn't record where commands were run".into(),
+                    span: Some(head),
+                    help: Some(r#"set $env.config.history.file_format = "sqlite""#.into()),
+                    inner: vec![],
+                });
+            }
+            Some(engine_state.cwd(Some(stack))?)
+        } else {
+            None
+        };
 
         let signals = engine_state.signals().clone();
         let history_reader: Option<Box<dyn ReedlineHistory>> = match history.file_format {
@@ -166,6 +191,10 @@ impl Command for History {
                             .to_string(),
                     );
                 }
+                if let Some(cwd) = cwd {
+                    table = table
+                        .with_where("cwd = ?".into(), vec![cwd.to_string_lossy().into_owned()]);
+                }
                 table = table
                     // Keep sqlite history output deterministic and append-ordered unless
                     // the user explicitly requests a different sort.
@@ -0,0 +1,176 @@
+use nu_cmd_base::util::get_editor;
+use nu_engine::{command_prelude::*, env_to_strings};
+use nu_protocol::{HistoryFileFormat, shell_error::io::IoError};
+#[cfg(feature = "sqlite")]
+use reedline::SqliteBackedHistory;
+use reedline::{FileBackedHistory, History as ReedlineHistory, SearchDirection, SearchQuery};
+use std::env::temp_dir;
+
+#[derive(Clone)]
+pub struct HistoryEdit;
+
+impl Command for HistoryEdit {
+    fn name(&self) -> &str {
+        "history edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit recent commands in your editor, then run them."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The commands are opened in `$env.config.buffer_editor`, or `$env.VISUAL` or `$env.EDITOR` if it's not set. Once the editor exits, the saved commands are put on the command line and run, unless they were deleted. Like `fc` in other shells, previous `history edit` commands are skipped."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("history edit")
+            .category(Category::History)
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "count",
+                SyntaxShape::Int,
+                "How many of the most recent commands to edit (default: 1).",
+            )
+            .switch(
+                "no-run",
+                "Put the edited commands on the command line, without running them.",
+                Some('n'),
+            )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "history edit",
+                description: "Edit the previous command, then run it",
+                result: None,
+            },
+            Example {
+                example: "history edit 3 --no-run",
+                description: "Edit the last three commands, and put them on the command line",
+                result: None,
+            },
+            Example {
+                example: "$env.config.keybindings ++= [{ name: history_edit, modifier: alt, keycode: char_e, mode: [emacs vi_normal vi_insert], event: { send: executehostcommand, cmd: 'history edit' } }]",
+                description: "Edit and rerun the previous command with Alt+E",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let count: Option<Spanned<i64>> = call.opt(engine_state, stack, 0)?;
+        let no_run = call.has_flag(engine_state, stack, "no-run")?;
+
+        let count = match count {
+            Some(count) if count.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: count.span });
+            }
+            Some(count) => count.item as usize,
+            None => 1,
+        };
+
+        let Some(history) = engine_state.history_config() else {
+            return Ok(PipelineData::empty());
+        };
+        let Some(history_path) = history.file_path() else {
+            return Err(ShellError::ConfigDirNotFound { span: head });
+        };
+
+        let history_reader: Box<dyn ReedlineHistory> = match history.file_format {
+            #[cfg(feature = "sqlite")]
+            HistoryFileFormat::Sqlite => Box::new(
+                SqliteBackedHistory::with_file(history_path, None, None).map_err(|err| {
+                    ShellError::GenericError {
+                        error: "Could not open history".into(),
+                        msg: err.to_string(),
+                        span: Some(head),
+                        help: None,
+                        inner: vec![],
+                    }
+                })?,
+            ),
+            #[cfg(not(feature = "sqlite"))]
+            HistoryFileFormat::Sqlite => {
+                return Err(ShellError::GenericError {
+                    error: "Could not open history reader".into(),
+                    msg: "SQLite is not supported".to_string(),
+                    span: Some(head),
+                    help: "Compile Nushell with `sqlite` feature".to_string().into(),
+                    inner: vec![],
+                });
+            }
+            HistoryFileFormat::Plaintext => Box::new(
+                FileBackedHistory::with_file(history.max_size as usize, history_path).map_err(
+                    |err| ShellError::GenericError {
+                        error: "Could not open history".into(),
+                        msg: err.to_string(),
+                        span: Some(head),
+                        help: None,
+                        inner: vec![],
+                    },
+                )?,
+            ),
+        };
+
+        let entries = history_reader
+            .search(SearchQuery::everything(SearchDirection::Backward, None))
+            .map_err(|err| ShellError::GenericError {
+                error: "Could not read history".into(),
+                msg: err.to_string(),
+                span: Some(head),
+                help: None,
+                inner: vec![],
+            })?;
+        let mut commands = entries
+            .into_iter()
+            .map(|entry| entry.command_line)
+            .filter(|command| !command.trim_start().starts_with("history edit"))
+            .take(count)
+            .collect::<Vec<_>>();
+        commands.reverse();
+
+        let (editor, editor_args) = get_editor(engine_state, stack, head)?;
+        let file = temp_dir().join(format!("{}.nu", uuid::Uuid::new_v4()));
+        std::fs::write(&file, commands.join("\n") + "\n")
+            .map_err(|err| IoError::new(err, head, file.clone()))?;
+
+        let status = std::process::Command::new(editor)
+            .args(editor_args)
+            .arg(&file)
+            .current_dir(engine_state.cwd(Some(stack))?)
+            .envs(env_to_strings(engine_state, stack)?)
+            .status();
+        let edited = std::fs::read_to_string(&file);
+        let _ = std::fs::remove_file(&file);
+
+        let status = status.map_err(|err| IoError::new(err, head, None))?;
+        if !status.success() {
+            return Err(ShellError::GenericError {
+                error: "Editor failed".into(),
+                msg: format!("the editor exited with {status}"),
+                span: Some(head),
+                help: Some("the commands were not run".into()),
+                inner: vec![],
+            });
+        }
+        let edited = edited.map_err(|err| IoError::new(err, head, file))?;
+        let edited = edited.trim_end();
+
+        if !edited.is_empty() {
+            let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+            repl.buffer = edited.to_string();
+            repl.cursor_pos = repl.buffer.len();
+            repl.accept = !no_run;
+        }
+
+        Ok(Value::nothing(head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,301 @@
+use std::path::Path;
+
+use nu_engine::command_prelude::*;
+use nu_protocol::HistoryFileFormat;
+use rusqlite::{Connection, params};
+
+#[derive(Clone)]
+pub struct HistoryPrune;
+
+impl Command for HistoryPrune {
+    fn name(&self) -> &str {
+        "history prune"
+    }
+
+    fn description(&self) -> &str {
+        "Remove old, unwanted, or duplicate entries from the command line history."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Only SQLite history is supported. Entries are removed by each given policy, and the number of removed entries is returned.
+
+Patterns are matched against the whole command line, with `*` matching any text and `?` matching a single character. Matching is case-sensitive."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("history prune")
+            .category(Category::History)
+            .input_output_types(vec![(Type::Nothing, Type::Int)])
+            .named(
+                "older-than",
+                SyntaxShape::Duration,
+                "Remove entries run longer ago than this.",
+                Some('o'),
+            )
+            .named(
+                "keep",
+                SyntaxShape::Int,
+                "Only keep this many of the most recent entries.",
+                Some('k'),
+            )
+            .named(
+                "matching",
+                SyntaxShape::String,
+                "Remove entries with a command line matching this pattern.",
+                Some('m'),
+            )
+            .switch(
+                "dedup",
+                "Collapse exact duplicate commands, keeping the most recent entry.",
+                Some('d'),
+            )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "history prune --older-than 52wk",
+                description: "Remove entries older than a year",
+                result: None,
+            },
+            Example {
+                example: "history prune --dedup --keep 100_000",
+                description: "Collapse duplicate commands, and keep only the 100,000 most recent entries",
+                result: None,
+            },
+            Example {
+                example: "history prune --matching '* --password *'",
+                description: "Remove commands that had a password on the command line",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let older_than: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "older-than")?;
+        let keep: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "keep")?;
+        let matching: Option<String> = call.get_flag(engine_state, stack, "matching")?;
+        let dedup = call.has_flag(engine_state, stack, "dedup")?;
+
+        if older_than.is_none() && keep.is_none() && matching.is_none() && !dedup {
+            return Err(ShellError::MissingParameter {
+                param_name: "--older-than, --keep, --matching, or --dedup".into(),
+                span: head,
+            });
+        }
+
+        for value in [&older_than, &keep].into_iter().flatten() {
+            if value.item < 0 {
+                return Err(ShellError::NeedsPositiveValue { span: value.span });
+            }
+        }
+
+        let Some(history) = engine_state.history_config() else {
+            return Ok(Value::int(0, head).into_pipeline_data());
+        };
+        if history.file_format != HistoryFileFormat::Sqlite {
+            return Err(ShellError::GenericError {
+                error: "Can't prune plaintext history".into(),
+                msg: "history prune only supports SQLite history".into(),
+                span: Some(head),
+                help: Some(r#"set $env.config.history.file_format = "sqlite""#.into()),
+                inner: vec![],
+            });
+        }
+        let Some(history_path) = history.file_path() else {
+            return Err(ShellError::ConfigDirNotFound { span: head });
+        };
+
+        let policies = PrunePolicies {
+            older_than: older_than.map(|older_than| older_than.item),
+            keep: keep.map(|keep| keep.item),
+            matching,
+            dedup,
+        };
+        let now = chrono::Utc::now().timestamp_millis();
+        let removed = Connection::open(&history_path)
+            .and_then(|mut conn| prune(&mut conn, &policies, now))
+            .map_err(|err| ShellError::GenericError {
+                error: "Failed to prune history".into(),
+                msg: err.to_string(),
+                span: Some(head),
+                help: None,
+                inner: vec![],
+            })?;
+
+        Ok(Value::int(removed as i64, head).into_pipeline_data())
+    }
+}
+
+/// The ways `history prune` removes entries, one for each of its flags.
+#[derive(Default)]
+struct PrunePolicies {
+    /// In nanoseconds, like a duration
+    older_than: Option<i64>,
+    keep: Option<i64>,
+    matching: Option<String>,
+    dedup: bool,
+}
+
+/// Remove the entries of the history that any of `policies` applies to, as of `now` in
+/// milliseconds, and return how many were removed.
+fn prune(conn: &mut Connection, policies: &PrunePolicies, now: i64) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut removed = 0;
+    if let Some(older_than) = policies.older_than {
+        let cutoff = now - older_than / 1_000_000;
+        removed += tx.execute(
+            "DELETE FROM history WHERE start_timestamp < ?1",
+            params![cutoff],
+        )?;
+    }
+    if let Some(pattern) = &policies.matching {
+        removed += tx.execute(
+            "DELETE FROM history WHERE command_line GLOB ?1",
+            params![pattern],
+        )?;
+    }
+    if policies.dedup {
+        removed += tx.execute(
+            "DELETE FROM history WHERE id NOT IN \
+            (SELECT MAX(id) FROM history GROUP BY command_line)",
+            [],
+        )?;
+    }
+    // Applied last, so that the most recent entries left by the other policies are kept
+    if let Some(keep) = policies.keep {
+        removed += tx.execute(
+            "DELETE FROM history WHERE id NOT IN \
+            (SELECT id FROM history ORDER BY id DESC LIMIT ?1)",
+            params![keep],
+        )?;
+    }
+    tx.commit()?;
+    Ok(removed)
+}
+
+/// Remove the older entries of `command_line` from the history, so that only the most recent
+/// one is kept. Used for `$env.config.history.ignore_duplicates`.
+pub(crate) fn collapse_duplicates(history_path: &Path, command_line: &str) -> rusqlite::Result<()> {
+    let conn = Connection::open(history_path)?;
+    conn.execute(
+        "DELETE FROM history WHERE command_line = ?1 AND id < \
+        (SELECT MAX(id) FROM history WHERE command_line = ?1)",
+        params![command_line],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with(path: &Path, commands: &[&str]) -> rusqlite::Result<Connection> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE history (id INTEGER PRIMARY KEY, command_line TEXT NOT NULL, \
+            start_timestamp INTEGER)",
+            [],
+        )?;
+        // Each entry is run a second after the one before it
+        for (i, command) in commands.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO history (command_line, start_timestamp) VALUES (?1, ?2)",
+                params![command, i as i64 * 1000],
+            )?;
+        }
+        Ok(conn)
+    }
+
+    fn commands(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+        conn.prepare("SELECT command_line FROM history ORDER BY id")?
+            .query_map([], |row| row.get(0))?
+            .collect()
+    }
+
+    #[test]
+    fn collapse_duplicates_keeps_most_recent() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.sqlite3");
+        let conn = history_with(&path, &["ls", "cd", "ls", "pwd", "ls"])?;
+
+        collapse_duplicates(&path, "ls")?;
+
+        assert_eq!(vec!["cd", "pwd", "ls"], commands(&conn)?);
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_older_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = history_with(&dir.path().join("history.sqlite3"), &["a", "b", "c", "d"])?;
+        let policies = PrunePolicies {
+            older_than: Some(1_500_000_000),
+            ..Default::default()
+        };
+
+        assert_eq!(2, prune(&mut conn, &policies, 3000)?);
+        assert_eq!(vec!["c", "d"], commands(&conn)?);
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_matching_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = history_with(
+            &dir.path().join("history.sqlite3"),
+            &["ls", "login --password hunter2", "cd", "login --password=x"],
+        )?;
+        let policies = PrunePolicies {
+            matching: Some("* --password*".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(2, prune(&mut conn, &policies, 0)?);
+        assert_eq!(vec!["ls", "cd"], commands(&conn)?);
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_duplicate_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = history_with(
+            &dir.path().join("history.sqlite3"),
+            &["ls", "cd", "ls", "pwd", "cd"],
+        )?;
+        let policies = PrunePolicies {
+            dedup: true,
+            ..Default::default()
+        };
+
+        assert_eq!(2, prune(&mut conn, &policies, 0)?);
+        assert_eq!(vec!["ls", "pwd", "cd"], commands(&conn)?);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_most_recent_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = history_with(
+            &dir.path().join("history.sqlite3"),
+            &["ls", "ls", "cd", "pwd"],
+        )?;
+        // Duplicates are collapsed first, so that they don't take up the entries that are kept
+        let policies = PrunePolicies {
+            keep: Some(2),
+            dedup: true,
+            ..Default::default()
+        };
+
+        assert_eq!(2, prune(&mut conn, &policies, 0)?);
+        assert_eq!(vec!["cd", "pwd"], commands(&conn)?);
+        Ok(())
+    }
+}
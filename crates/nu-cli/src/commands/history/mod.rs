@@ -1,15 +1,23 @@
 mod fields;
 mod history_;
+mod history_edit;
 
 pub use history_::History;
+pub use history_edit::HistoryEdit;
 
 // if more history formats are added, will need to reconsider this
 #[cfg(feature = "sqlite")]
 mod history_import;
 #[cfg(feature = "sqlite")]
+mod history_prune;
+#[cfg(feature = "sqlite")]
 mod history_session;
 
 #[cfg(feature = "sqlite")]
 pub use history_import::HistoryImport;
 #[cfg(feature = "sqlite")]
+pub use history_prune::HistoryPrune;
+#[cfg(feature = "sqlite")]
+pub(crate) use history_prune::collapse_duplicates;
+#[cfg(feature = "sqlite")]
 pub use history_session::HistorySession;
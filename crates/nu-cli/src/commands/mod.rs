@@ -1,12 +1,25 @@
+mod abbr;
+mod abbr_add;
+mod abbr_remove;
 mod commandline;
 mod default_context;
+mod env_deny;
+mod env_trust;
 mod history;
 mod keybindings;
 mod keybindings_default;
 mod keybindings_list;
 mod keybindings_listen;
 
-pub use commandline::{Commandline, CommandlineEdit, CommandlineGetCursor, CommandlineSetCursor};
+pub use abbr::Abbr;
+pub use abbr_add::AbbrAdd;
+pub use abbr_remove::AbbrRemove;
+pub use commandline::{
+    Commandline, CommandlineEdit, CommandlineGetCursor, CommandlineIndent, CommandlineRestore,
+    CommandlineSetCursor, CommandlineSurround,
+};
+pub use env_deny::EnvDeny;
+pub use env_trust::EnvTrust;
 pub use history::*;
 pub use keybindings::Keybindings;
 pub use keybindings_default::KeybindingsDefault;
@@ -43,6 +43,7 @@ impl<'a> Completer for ArgValueCompletion<'a> {
             call: self.call,
             strip: self.strip,
             pos: self.pos,
+            prefix: prefix.as_ref(),
         };
         match decl.get_dynamic_completion(
             working_set.permanent_state,
@@ -600,16 +600,12 @@ impl NuCompleter {
                         }
 
                         // resort to external completer set in config
-                        let completion = self
-                            .engine_state
-                            .get_config()
-                            .completions
-                            .external
-                            .completer
-                            .as_ref()
-                            .map(|closure| {
-                                CommandWideCompletion::closure(closure, element_expression, strip)
-                            });
+                        let completion = CommandWideCompletion::external(
+                            working_set,
+                            &self.engine_state.get_config().completions.external,
+                            element_expression,
+                            strip,
+                        );
 
                         if let Some(mut completion) = completion {
                             let ctx = Context::new(working_set, span, b"", offset);
@@ -712,14 +708,12 @@ impl NuCompleter {
             Some(CommandWideCompleter::Command(decl_id)) => {
                 CommandWideCompletion::command(ctx.working_set, decl_id, element_expression, strip)
             }
-            Some(CommandWideCompleter::External) => self
-                .engine_state
-                .get_config()
-                .completions
-                .external
-                .completer
-                .as_ref()
-                .map(|closure| CommandWideCompletion::closure(closure, element_expression, strip)),
+            Some(CommandWideCompleter::External) => CommandWideCompletion::external(
+                ctx.working_set,
+                &self.engine_state.get_config().completions.external,
+                element_expression,
+                strip,
+            ),
             None => None,
         };
 
@@ -3,15 +3,19 @@ use nu_color_config::{color_record_to_nustyle, lookup_ansi_color_style};
 use nu_engine::{compile, eval_call};
 use nu_parser::flatten_expression;
 use nu_protocol::{
-    BlockId, DeclId, GetSpan, IntoSpanned, PipelineData, Record, ShellError, Span, Spanned,
-    SuggestionKind, Type, Value, VarId,
+    BlockId, DeclId, ExternalCompleterConfig, GetSpan, IntoSpanned, PipelineData, Record,
+    ShellError, Signals, Span, Spanned, SuggestionKind, Type, Value, VarId,
     ast::{Argument, Call, Expr, Expression},
     debugger::WithoutDebug,
-    engine::{Closure, EngineState, Stack, StateWorkingSet},
+    engine::{CompletionCacheKey, EngineState, Stack, StateWorkingSet},
 };
 use nu_utils::{SharedCow, strip_ansi_string_unlikely};
 use reedline::Suggestion;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, atomic::AtomicBool, mpsc},
+    time::Duration,
+};
 
 use super::completion_options::NuMatcher;
 
@@ -319,6 +323,8 @@ pub struct CommandWideCompletion<'e> {
     captures: Vec<(VarId, Value)>,
     expression: &'e Expression,
     strip: bool,
+    cache_duration: Duration,
+    timeout: Option<Duration>,
     pub need_fallback: bool,
 }
 
@@ -338,18 +344,35 @@ impl<'a> CommandWideCompletion<'a> {
             captures: vec![],
             expression,
             strip,
+            cache_duration: Duration::ZERO,
+            timeout: None,
             need_fallback: false,
         })
     }
 
-    pub fn closure(closure: &'a Closure, expression: &'a Expression, strip: bool) -> Self {
-        Self {
+    /// The external completer configured for the command line of `expression`, if any.
+    pub fn external(
+        working_set: &StateWorkingSet<'_>,
+        config: &ExternalCompleterConfig,
+        expression: &'a Expression,
+        strip: bool,
+    ) -> Option<Self> {
+        let words = get_command_arguments(working_set, expression)
+            .item
+            .into_iter()
+            .map(|word| word.item)
+            .collect::<Vec<_>>();
+        let closure = config.completer_for(&words)?;
+
+        Some(Self {
             block_id: closure.block_id,
             captures: closure.captures.clone(),
             expression,
             strip,
+            cache_duration: config.cache_duration,
+            timeout: config.timeout,
             need_fallback: false,
-        }
+        })
     }
 }
 
@@ -377,6 +400,26 @@ impl<'a> Completer for CommandWideCompletion<'a> {
             new_span = Span::new(span.start, span.end.saturating_sub(1));
         }
 
+        let cache_key = (!self.cache_duration.is_zero()).then(|| CompletionCacheKey {
+            block_id: self.block_id,
+            args: args.iter().map(|arg| arg.item.clone()).collect(),
+            cwd: working_set
+                .permanent_state
+                .cwd(Some(stack))
+                .ok()
+                .map(|cwd| cwd.into_std_path_buf()),
+        });
+
+        let command_span = working_set.get_span(self.expression.span_id);
+        let completion_cache = working_set.permanent_state.completion_cache.clone();
+        let cached = cache_key.as_ref().and_then(|key| {
+            let cache = completion_cache.lock().ok()?;
+            cache.get(key, self.cache_duration)
+        });
+        if let Some(value) = cached {
+            return self.convert_results(offset, new_span, Ok(value), command_span);
+        }
+
         let mut block = working_set.get_block(self.block_id).clone();
 
         // LSP completion where custom def is parsed but not compiled
@@ -406,17 +449,69 @@ impl<'a> Completer for CommandWideCompletion<'a> {
         let mut engine_state = working_set.permanent_state.clone();
         let _ = engine_state.merge_delta(working_set.delta.clone());
 
-        let result = nu_engine::eval_block::<WithoutDebug>(
-            &engine_state,
-            &mut callee_stack,
-            &block,
-            PipelineData::empty(),
-        )
-        .map(|p| p.body);
+        let cache_duration = self.cache_duration;
+        let eval = move |engine_state: EngineState| {
+            let result = nu_engine::eval_block::<WithoutDebug>(
+                &engine_state,
+                &mut callee_stack,
+                &block,
+                PipelineData::empty(),
+            )
+            .and_then(|p| p.body.into_value(new_span));
 
-        let command_span = working_set.get_span(self.expression.span_id);
+            if let (Ok(value), Some(key)) = (&result, cache_key)
+                && !engine_state.signals().interrupted()
+                && let Ok(mut cache) = completion_cache.lock()
+            {
+                cache.insert(key, value.clone(), cache_duration);
+            }
+            result
+        };
+
+        let result = match self.timeout {
+            // Run the completer in the background, so that a slow completer can't block the line
+            // editor. If it doesn't finish in time, it's interrupted.
+            Some(timeout) => {
+                let signals = Signals::new(Arc::new(AtomicBool::new(false)));
+                engine_state.set_signals(signals.clone());
+                let (tx, rx) = mpsc::channel();
+                let spawned = std::thread::Builder::new()
+                    .name("external completer".into())
+                    .spawn(move || {
+                        let _ = tx.send(eval(engine_state));
+                    });
+                match spawned.map(|_| rx.recv_timeout(timeout)) {
+                    Ok(Ok(result)) => result,
+                    Ok(Err(_)) => {
+                        log::debug!("external completer timed out after {timeout:?}");
+                        signals.trigger();
+                        self.need_fallback = true;
+                        return vec![];
+                    }
+                    Err(err) => {
+                        log::error!("failed to spawn external completer thread: {err}");
+                        self.need_fallback = true;
+                        return vec![];
+                    }
+                }
+            }
+            None => eval(engine_state),
+        };
+
+        self.convert_results(offset, new_span, result, command_span)
+    }
+}
+
+impl CommandWideCompletion<'_> {
+    fn convert_results(
+        &mut self,
+        offset: usize,
+        span: Span,
+        result: Result<Value, ShellError>,
+        command_span: Span,
+    ) -> Vec<SemanticSuggestion> {
         if let Some(results) =
-            convert_whole_command_completion_results(offset, new_span, result, command_span)
+            convert_whole_command_completion_results(offset, span, result, command_span)
         {
             results
         } else {
@@ -431,10 +526,10 @@ impl<'a> Completer for CommandWideCompletion<'a> {
 fn convert_whole_command_completion_results(
     offset: usize,
     span: Span,
-    result: Result<PipelineData, nu_protocol::ShellError>,
+    result: Result<Value, nu_protocol::ShellError>,
     command_span: Span,
 ) -> Option<Vec<SemanticSuggestion>> {
-    let value = match result.and_then(|pipeline_data| pipeline_data.into_value(span)) {
+    let value = match result {
         Ok(value) => value,
         Err(err) => {
             log::error!(
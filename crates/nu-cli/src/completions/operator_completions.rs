@@ -166,7 +166,9 @@ fn ops_by_value(value: &Value, mutable: bool) -> Vec<OperatorItem> {
         Value::Binary { .. } => valid_binary_ops(),
         Value::Bool { .. } => valid_bool_ops(),
         Value::Date { .. } => number_comparison_ops(),
-        Value::Filesize { .. } | Value::Duration { .. } => valid_value_with_unit_ops(),
+        Value::Filesize { .. } | Value::Duration { .. } | Value::Rate { .. } => {
+            valid_value_with_unit_ops()
+        }
         Value::Range { .. } | Value::Record { .. } => collection_comparison_ops(),
         Value::List { .. } => valid_list_ops(),
         _ => all_ops_for_immutable(),
@@ -176,7 +178,8 @@ fn ops_by_value(value: &Value, mutable: bool) -> Vec<OperatorItem> {
             Value::Int { .. }
             | Value::Float { .. }
             | Value::Filesize { .. }
-            | Value::Duration { .. } => numeric_assignment_ops(),
+            | Value::Duration { .. }
+            | Value::Rate { .. } => numeric_assignment_ops(),
             Value::String { .. } | Value::Binary { .. } | Value::List { .. } => {
                 concat_assignment_ops()
             }
@@ -224,7 +227,7 @@ impl Completer for OperatorCompletion<'_> {
             Type::Binary => valid_binary_ops(),
             Type::Bool => valid_bool_ops(),
             Type::Date => number_comparison_ops(),
-            Type::Filesize | Type::Duration => valid_value_with_unit_ops(),
+            Type::Filesize | Type::Duration | Type::Rate => valid_value_with_unit_ops(),
             Type::Record(_) | Type::Range => collection_comparison_ops(),
             Type::List(_) | Type::Table(_) => valid_list_ops(),
             // Unknown type, resort to evaluated values
@@ -250,7 +253,7 @@ impl Completer for OperatorCompletion<'_> {
         if needs_assignment_ops && is_expression_mutable(&self.left_hand_side.expr, working_set) {
             possible_operations.extend(match &self.left_hand_side.ty {
                 Type::Int | Type::Float | Type::Number => numeric_assignment_ops(),
-                Type::Filesize | Type::Duration => numeric_assignment_ops(),
+                Type::Filesize | Type::Duration | Type::Rate => numeric_assignment_ops(),
                 Type::String | Type::Binary | Type::List(_) => concat_assignment_ops(),
                 Type::Any => all_assignment_ops(),
                 _ => vec![operator_to_item(ast::Assignment::Assign)],
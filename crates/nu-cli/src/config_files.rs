@@ -345,6 +345,7 @@ pub fn migrate_old_plugin_file(engine_state: &EngineState) -> bool {
             name: identity.name().to_owned(),
             filename: identity.filename().to_owned(),
             shell: identity.shell().map(|p| p.to_owned()),
+            address: identity.address().map(|a| a.to_owned()),
             data: PluginRegistryItemData::Valid {
                 metadata: Default::default(),
                 commands,
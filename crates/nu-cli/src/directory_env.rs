@@ -0,0 +1,382 @@
+use nu_cmd_base::hook::eval_hook;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+    report_shell_error,
+};
+use nu_utils::escape_quote_string;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The names of the environment files, in order of preference.
+pub(crate) const ENV_FILE_NAMES: [&str; 2] = [".nu-env", "nu.env.nu"];
+/// The overlay the environment file of the current directory is loaded into.
+const OVERLAY_NAME: &str = "nu-env";
+/// The environment variable with the path of the loaded environment file, kept in its overlay.
+pub(crate) const LOADED_ENV_FILE: &str = "NU_ENV_FILE";
+/// The file the trusted and denied environment files are stored in, in the config directory.
+const TRUST_FILE: &str = "env-trust.txt";
+
+/// Find the environment file of `dir`, or of the closest of its parents that has one.
+pub(crate) fn find_env_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().find_map(|dir| {
+        ENV_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Trust {
+    Trusted,
+    Denied,
+    Unknown,
+}
+
+/// The environment files that may or may not be loaded.
+///
+/// Trusted files are stored with a hash of their contents, so they have to be trusted again once
+/// they change.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct TrustStore {
+    trusted: HashMap<PathBuf, String>,
+    denied: HashSet<PathBuf>,
+}
+
+impl TrustStore {
+    fn path() -> Option<PathBuf> {
+        nu_path::nu_config_dir().map(|dir| dir.join(TRUST_FILE).into_std_path_buf())
+    }
+
+    /// Read the store from the config directory, or start an empty one.
+    pub(crate) fn load() -> Self {
+        Self::path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| io::Error::other("no config directory"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_text())
+    }
+
+    /// Each line is either `trust <hash> <path>` or `deny <path>`.
+    fn parse(text: &str) -> Self {
+        let mut store = Self::default();
+        for line in text.lines() {
+            match line.split_once(' ') {
+                Some(("trust", rest)) => {
+                    if let Some((hash, path)) = rest.split_once(' ') {
+                        store.trusted.insert(path.into(), hash.into());
+                    }
+                }
+                Some(("deny", path)) => {
+                    store.denied.insert(path.into());
+                }
+                _ => {}
+            }
+        }
+        store
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = self
+            .trusted
+            .iter()
+            .map(|(path, hash)| format!("trust {hash} {}", path.display()))
+            .chain(
+                self.denied
+                    .iter()
+                    .map(|path| format!("deny {}", path.display())),
+            )
+            .collect::<Vec<_>>();
+        lines.sort();
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+
+    pub(crate) fn check(&self, file: &Path) -> Trust {
+        if self.denied.contains(file) {
+            Trust::Denied
+        } else if self
+            .trusted
+            .get(file)
+            .is_some_and(|hash| hash_file(file).is_ok_and(|current| *hash == current))
+        {
+            Trust::Trusted
+        } else {
+            Trust::Unknown
+        }
+    }
+
+    pub(crate) fn trust(&mut self, file: &Path) -> io::Result<()> {
+        let hash = hash_file(file)?;
+        self.denied.remove(file);
+        self.trusted.insert(file.to_path_buf(), hash);
+        Ok(())
+    }
+
+    pub(crate) fn deny(&mut self, file: &Path) {
+        self.trusted.remove(file);
+        self.denied.insert(file.to_path_buf());
+    }
+}
+
+fn hash_file(file: &Path) -> io::Result<String> {
+    let contents = std::fs::read(file)?;
+    Ok(format!("{:x}", Sha256::digest(contents)))
+}
+
+/// What the REPL remembers about environment files between prompts.
+///
+/// The trust store is kept in memory, and only read again once `env trust` or `env deny` changed
+/// it, so that a denied file doesn't mean reading the store before every prompt.
+pub(crate) struct DirectoryEnv {
+    trust_file: Option<PathBuf>,
+    store: TrustStore,
+    store_modified: Option<SystemTime>,
+    /// Files that aren't trusted yet, that the user was already told about
+    announced: HashSet<PathBuf>,
+}
+
+impl DirectoryEnv {
+    pub(crate) fn new() -> Self {
+        Self::with_trust_file(TrustStore::path())
+    }
+
+    fn with_trust_file(trust_file: Option<PathBuf>) -> Self {
+        Self {
+            trust_file,
+            store: TrustStore::default(),
+            store_modified: None,
+            announced: HashSet::new(),
+        }
+    }
+
+    fn check(&mut self, file: &Path) -> Trust {
+        let Some(trust_file) = &self.trust_file else {
+            return Trust::Unknown;
+        };
+        let modified = std::fs::metadata(trust_file)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified != self.store_modified {
+            self.store = TrustStore::load_from(trust_file);
+            self.store_modified = modified;
+        }
+        self.store.check(file)
+    }
+}
+
+/// Load the environment file of the current directory into an overlay, after unloading the one
+/// of the directory that was left.
+///
+/// Files that aren't trusted yet aren't loaded. Instead, the user is told once how to trust them
+/// with `env trust`, so that nothing reads from the terminal while the REPL owns it.
+///
+/// Used for `$env.config.directory_env`.
+pub(crate) fn update_directory_env(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    directory_env: &mut DirectoryEnv,
+) {
+    let Ok(cwd) = engine_state.cwd(Some(stack)) else {
+        return;
+    };
+    let env_file = find_env_file(cwd.as_std_path());
+    let loaded = stack
+        .get_env_var(engine_state, LOADED_ENV_FILE)
+        .and_then(|file| file.as_str().ok())
+        .map(PathBuf::from);
+    if env_file == loaded {
+        return;
+    }
+
+    let mut code = String::new();
+    if stack.is_overlay_active(OVERLAY_NAME) {
+        code.push_str(&format!(
+            "overlay hide {OVERLAY_NAME} --keep-env [PWD OLDPWD]\n"
+        ));
+    }
+    if let Some(file) = env_file {
+        match directory_env.check(&file) {
+            Trust::Trusted => {
+                let file = escape_quote_string(&file.to_string_lossy());
+                code.push_str(&format!(
+                    "overlay use --reload {file} as {OVERLAY_NAME}\n$env.{LOADED_ENV_FILE} = {file}\n"
+                ));
+            }
+            Trust::Denied => (),
+            Trust::Unknown => {
+                if directory_env.announced.insert(file.clone()) {
+                    eprintln!(
+                        "{} can change your environment, and isn't loaded until you trust it. \
+                        Run `env trust` to load it, or `env deny` to not be told about it again.",
+                        file.display()
+                    );
+                }
+            }
+        }
+    }
+    if code.is_empty() {
+        return;
+    }
+
+    if let Err(err) = eval_hook(
+        engine_state,
+        stack,
+        None,
+        vec![],
+        &Value::string(code, Span::unknown()),
+        "directory env",
+    ) {
+        report_shell_error(Some(stack), engine_state, &err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_env_file_in_parents() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let nested = dir.path().join("a").join("b");
+        std::fs::create_dir_all(&nested)?;
+        assert_eq!(None, find_env_file(&nested));
+
+        std::fs::write(dir.path().join("nu.env.nu"), "")?;
+        assert_eq!(Some(dir.path().join("nu.env.nu")), find_env_file(&nested));
+
+        std::fs::write(nested.join(".nu-env"), "")?;
+        assert_eq!(Some(nested.join(".nu-env")), find_env_file(&nested));
+        Ok(())
+    }
+
+    #[test]
+    fn trust_is_revoked_when_file_changes() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join(".nu-env");
+        std::fs::write(&file, "export-env { $env.FOO = 1 }")?;
+
+        let mut store = TrustStore::default();
+        assert_eq!(Trust::Unknown, store.check(&file));
+        store.trust(&file)?;
+        assert_eq!(Trust::Trusted, store.check(&file));
+
+        std::fs::write(&file, "export-env { $env.FOO = 2 }")?;
+        assert_eq!(Trust::Unknown, store.check(&file));
+
+        store.deny(&file);
+        assert_eq!(Trust::Denied, store.check(&file));
+        Ok(())
+    }
+
+    #[test]
+    fn trust_store_roundtrips() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("my project").join(".nu-env");
+        std::fs::create_dir_all(dir.path().join("my project"))?;
+        std::fs::write(&file, "")?;
+
+        let mut store = TrustStore::default();
+        store.trust(&file)?;
+        store.deny(Path::new("/tmp/.nu-env"));
+        assert_eq!(store, TrustStore::parse(&store.to_text()));
+        Ok(())
+    }
+
+    fn env_var(engine_state: &EngineState, stack: &Stack, name: &str) -> Option<String> {
+        stack
+            .get_env_var(engine_state, name)
+            .and_then(|value| value.as_str().ok())
+            .map(String::from)
+    }
+
+    fn cd(stack: &mut Stack, dir: &Path) {
+        stack.add_env_var(
+            "PWD".into(),
+            Value::test_string(dir.to_string_lossy().into_owned()),
+        );
+    }
+
+    #[test]
+    fn trusted_file_is_loaded_and_unloaded() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let project = dir.path().join("project");
+        std::fs::create_dir(&project)?;
+        let file = project.join(".nu-env");
+        std::fs::write(&file, "export-env { $env.FOO = 'bar' }")?;
+
+        let trust_file = dir.path().join(TRUST_FILE);
+        let mut store = TrustStore::default();
+        store.trust(&file)?;
+        std::fs::write(&trust_file, store.to_text())?;
+
+        let mut engine_state = nu_cmd_lang::create_default_context();
+        let mut stack = Stack::new();
+        let mut directory_env = DirectoryEnv::with_trust_file(Some(trust_file));
+
+        cd(&mut stack, &project);
+        update_directory_env(&mut engine_state, &mut stack, &mut directory_env);
+        assert_eq!(Some("bar".into()), env_var(&engine_state, &stack, "FOO"));
+        assert_eq!(
+            Some(file.to_string_lossy().into_owned()),
+            env_var(&engine_state, &stack, LOADED_ENV_FILE)
+        );
+
+        cd(&mut stack, dir.path());
+        update_directory_env(&mut engine_state, &mut stack, &mut directory_env);
+        assert_eq!(None, env_var(&engine_state, &stack, "FOO"));
+        assert_eq!(None, env_var(&engine_state, &stack, LOADED_ENV_FILE));
+        Ok(())
+    }
+
+    #[test]
+    fn untrusted_file_is_not_loaded() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join(".nu-env");
+        std::fs::write(&file, "export-env { $env.FOO = 'bar' }")?;
+
+        let mut engine_state = nu_cmd_lang::create_default_context();
+        let mut stack = Stack::new();
+        let mut directory_env = DirectoryEnv::with_trust_file(Some(dir.path().join(TRUST_FILE)));
+
+        cd(&mut stack, dir.path());
+        update_directory_env(&mut engine_state, &mut stack, &mut directory_env);
+        assert_eq!(None, env_var(&engine_state, &stack, "FOO"));
+        assert!(directory_env.announced.contains(&file));
+        Ok(())
+    }
+
+    #[test]
+    fn trust_store_is_reloaded_after_it_changes() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join(".nu-env");
+        std::fs::write(&file, "")?;
+        let trust_file = dir.path().join(TRUST_FILE);
+        let mut directory_env = DirectoryEnv::with_trust_file(Some(trust_file.clone()));
+
+        assert_eq!(Trust::Unknown, directory_env.check(&file));
+
+        let mut store = TrustStore::default();
+        store.deny(&file);
+        std::fs::write(&trust_file, store.to_text())?;
+        assert_eq!(Trust::Denied, directory_env.check(&file));
+        Ok(())
+    }
+}
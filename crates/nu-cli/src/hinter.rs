@@ -0,0 +1,178 @@
+use nu_ansi_term::Style;
+use reedline::{Hinter, History, HistoryItem, SearchQuery};
+use std::collections::HashMap;
+
+/// How many of the most recent matching history entries are ranked for a hint.
+const RANKED_ENTRIES: i64 = 500;
+
+/// A hinter that suggests the history entry starting with the current line that ranks highest by
+/// recency, frequency, the current directory, and how often it succeeded.
+///
+/// Used for `$env.config.hint_ranking = "ranked"`.
+#[derive(Default)]
+pub struct RankedHinter {
+    style: Style,
+    current_hint: String,
+}
+
+impl RankedHinter {
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Hinter for RankedHinter {
+    fn handle(
+        &mut self,
+        line: &str,
+        _pos: usize,
+        history: &dyn History,
+        use_ansi_coloring: bool,
+        cwd: &str,
+    ) -> String {
+        self.current_hint = if line.is_empty() {
+            String::new()
+        } else {
+            let mut query = SearchQuery::last_with_prefix(line.to_string(), history.session());
+            query.limit = Some(RANKED_ENTRIES);
+            history
+                .search(query)
+                .ok()
+                .and_then(|mut entries| {
+                    // Entries that are exactly the current line have nothing to hint
+                    entries.retain(|entry| entry.command_line.len() > line.len());
+                    best_entry(&entries, cwd)
+                        .and_then(|command_line| command_line.get(line.len()..))
+                        .map(str::to_string)
+                })
+                .unwrap_or_default()
+        };
+
+        if use_ansi_coloring && !self.current_hint.is_empty() {
+            self.style.paint(&self.current_hint).to_string()
+        } else {
+            self.current_hint.clone()
+        }
+    }
+
+    fn complete_hint(&self) -> String {
+        self.current_hint.clone()
+    }
+
+    fn next_hint_token(&self) -> String {
+        let mut reached_content = false;
+        self.current_hint
+            .chars()
+            .take_while(|c| {
+                if c.is_whitespace() {
+                    !reached_content
+                } else {
+                    reached_content = true;
+                    true
+                }
+            })
+            .collect()
+    }
+}
+
+/// Pick the best command line out of `entries`, which are ordered from the most recent.
+///
+/// Each run of a command adds to its score, less so the longer ago it was, and twice as much if
+/// it was run in `cwd`. The score is then scaled down by how often the command failed.
+fn best_entry<'a>(entries: &'a [HistoryItem], cwd: &str) -> Option<&'a str> {
+    #[derive(Default)]
+    struct Score {
+        frecency: f64,
+        succeeded: u32,
+        failed: u32,
+        // Ties are broken in favor of the most recent command
+        most_recent: usize,
+    }
+
+    let mut scores: HashMap<&str, Score> = HashMap::new();
+    for (age, entry) in entries.iter().enumerate() {
+        let score = scores
+            .entry(entry.command_line.as_str())
+            .or_insert_with(|| Score {
+                most_recent: age,
+                ..Default::default()
+            });
+        let weight = if entry.cwd.as_deref() == Some(cwd) {
+            2.0
+        } else {
+            1.0
+        };
+        score.frecency += weight / (1.0 + age as f64 / 10.0);
+        match entry.exit_status {
+            Some(0) => score.succeeded += 1,
+            Some(_) => score.failed += 1,
+            None => {}
+        }
+    }
+
+    scores
+        .into_iter()
+        .map(|(command_line, score)| {
+            let known = score.succeeded + score.failed;
+            let success_rate = if known > 0 {
+                score.succeeded as f64 / known as f64
+            } else {
+                1.0
+            };
+            let rank = score.frecency * (0.2 + 0.8 * success_rate);
+            (command_line, rank, score.most_recent)
+        })
+        .max_by(|(_, a, a_recent), (_, b, b_recent)| {
+            a.total_cmp(b).then_with(|| b_recent.cmp(a_recent))
+        })
+        .map(|(command_line, _, _)| command_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(command_line: &str, cwd: &str, exit_status: i64) -> HistoryItem {
+        HistoryItem {
+            cwd: Some(cwd.into()),
+            exit_status: Some(exit_status),
+            ..HistoryItem::from_command_line(command_line)
+        }
+    }
+
+    #[test]
+    fn prefers_frequent_commands() {
+        let entries = [
+            entry("cargo build", "/a", 0),
+            entry("cargo test", "/a", 0),
+            entry("cargo test", "/a", 0),
+        ];
+        assert_eq!(Some("cargo test"), best_entry(&entries, "/a"));
+    }
+
+    #[test]
+    fn prefers_commands_from_cwd() {
+        let entries = [entry("make", "/b", 0), entry("make install", "/a", 0)];
+        assert_eq!(Some("make install"), best_entry(&entries, "/a"));
+    }
+
+    #[test]
+    fn prefers_successful_commands() {
+        let entries = [
+            entry("git pish", "/a", 1),
+            entry("git push", "/a", 0),
+            entry("git pish", "/a", 1),
+        ];
+        assert_eq!(Some("git push"), best_entry(&entries, "/a"));
+    }
+
+    #[test]
+    fn prefers_recent_commands() {
+        let entries = [
+            HistoryItem::from_command_line("ls -a"),
+            HistoryItem::from_command_line("ls -l"),
+        ];
+        assert_eq!(Some("ls -a"), best_entry(&entries, "/a"));
+    }
+}
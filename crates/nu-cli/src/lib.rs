@@ -1,9 +1,12 @@
 #![doc = include_str!("../README.md")]
+mod abbreviation;
 mod commands;
 mod completions;
 mod config_files;
+mod directory_env;
 mod eval_cmds;
 mod eval_file;
+mod hinter;
 mod menus;
 mod nu_highlight;
 mod print;
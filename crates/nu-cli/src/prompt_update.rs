@@ -31,14 +31,15 @@ pub(crate) const TRANSIENT_PROMPT_MULTILINE_INDICATOR: &str =
 // These escape sequences are used by the shell to mark command execution boundaries.
 // Note: A/B/P markers for prompts are now handled by reedline.
 
-// Command execution markers (C = pre-exec, D = post-exec with exit code)
+// Command execution markers (C = pre-exec, D = post-exec, followed by `;` and the exit code if a
+// command ran)
 pub(crate) const PRE_EXECUTION_MARKER: &str = "\x1b]133;C\x1b\\";
-pub(crate) const POST_EXECUTION_MARKER_PREFIX: &str = "\x1b]133;D;";
+pub(crate) const POST_EXECUTION_MARKER_PREFIX: &str = "\x1b]133;D";
 pub(crate) const POST_EXECUTION_MARKER_SUFFIX: &str = "\x1b\\";
 
 // VS Code specific markers (OSC 633)
 pub(crate) const VSCODE_PRE_EXECUTION_MARKER: &str = "\x1b]633;C\x1b\\";
-pub(crate) const VSCODE_POST_EXECUTION_MARKER_PREFIX: &str = "\x1b]633;D;";
+pub(crate) const VSCODE_POST_EXECUTION_MARKER_PREFIX: &str = "\x1b]633;D";
 pub(crate) const VSCODE_POST_EXECUTION_MARKER_SUFFIX: &str = "\x1b\\";
 pub(crate) const VSCODE_COMMANDLINE_MARKER_PREFIX: &str = "\x1b]633;E;";
 pub(crate) const VSCODE_COMMANDLINE_MARKER_SUFFIX: &str = "\x1b\\";
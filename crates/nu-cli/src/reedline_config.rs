@@ -5,10 +5,10 @@ use nu_color_config::{color_record_to_nustyle, lookup_ansi_color_style};
 use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_protocol::{
-    Config, EditBindings, FromValue, ParsedKeybinding, ParsedMenu, PipelineData, Record,
+    BlockId, Config, EditBindings, FromValue, ParsedKeybinding, ParsedMenu, PipelineData, Record,
     ShellError, Span, Type, Value,
     debugger::WithoutDebug,
-    engine::{EngineState, Stack, StateWorkingSet},
+    engine::{Closure, EngineState, Stack, StateWorkingSet},
     extract_value,
 };
 use reedline::{
@@ -912,10 +912,48 @@ fn add_parsed_keybinding(
     Ok(())
 }
 
+/// Widgets are run through [`ReedlineEvent::ExecuteHostCommand`] with this prefix and the block id
+/// of their closure. The prefix can't start a valid command line, so the REPL can tell the two
+/// apart.
+const WIDGET_COMMAND_PREFIX: &str = "\0widget:";
+
+/// Whether `command`, the text of an [`ReedlineEvent::ExecuteHostCommand`], runs a widget.
+pub(crate) fn is_widget_command(command: &str) -> bool {
+    command.starts_with(WIDGET_COMMAND_PREFIX)
+}
+
+/// Get the closure of the widget run by `command`, the text of an
+/// [`ReedlineEvent::ExecuteHostCommand`], if it runs a widget from `$env.config.keybindings`.
+pub(crate) fn widget_closure(command: &str, config: &Config) -> Option<Closure> {
+    let block_id = command.strip_prefix(WIDGET_COMMAND_PREFIX)?.parse().ok()?;
+    let block_id = BlockId::new(block_id);
+    config
+        .keybindings
+        .iter()
+        .find_map(|keybinding| find_widget(&keybinding.event, block_id))
+}
+
+fn find_widget(event: &Value, block_id: BlockId) -> Option<Closure> {
+    match event {
+        Value::Record { val: record, .. } => {
+            if let Some(Value::Closure { val, .. }) = record.get("widget") {
+                (val.block_id == block_id).then(|| val.as_ref().clone())
+            } else {
+                record
+                    .get("until")
+                    .and_then(|until| find_widget(until, block_id))
+            }
+        }
+        Value::List { vals, .. } => vals.iter().find_map(|event| find_widget(event, block_id)),
+        _ => None,
+    }
+}
+
 enum EventType<'config> {
     Send(&'config Value),
     Edit(&'config Value),
     Until(&'config Value),
+    Widget(&'config Value),
 }
 
 impl<'config> EventType<'config> {
@@ -924,8 +962,9 @@ impl<'config> EventType<'config> {
             .map(Self::Send)
             .or_else(|_| extract_value("edit", record, span).map(Self::Edit))
             .or_else(|_| extract_value("until", record, span).map(Self::Until))
+            .or_else(|_| extract_value("widget", record, span).map(Self::Widget))
             .map_err(|_| ShellError::MissingRequiredColumn {
-                column: "'send', 'edit', or 'until'",
+                column: "'send', 'edit', 'until', or 'widget'",
                 span,
             })
     }
@@ -976,6 +1015,13 @@ fn parse_event(value: &Value, config: &Config) -> Result<Option<ReedlineEvent>,
                     span: v.span(),
                 }),
             },
+            EventType::Widget(value) => {
+                let closure = value.as_closure()?;
+                Ok(Some(ReedlineEvent::ExecuteHostCommand(format!(
+                    "{WIDGET_COMMAND_PREFIX}{}",
+                    closure.block_id.get()
+                ))))
+            }
         },
         Value::List { vals, .. } => {
             let events = vals
@@ -1695,6 +1741,43 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_widget_event() {
+        let closure = Closure {
+            block_id: BlockId::new(42),
+            captures: vec![],
+        };
+        let event = record! {
+            "widget" => Value::test_closure(closure.clone()),
+        };
+
+        let span = Span::test_data();
+        let b = EventType::try_from_record(&event, span).unwrap();
+        assert!(matches!(b, EventType::Widget(_)));
+
+        let event = Value::test_record(event);
+        let mut config = Config::default();
+
+        let Some(ReedlineEvent::ExecuteHostCommand(command)) =
+            parse_event(&event, &config).unwrap()
+        else {
+            panic!("widget should run as a host command");
+        };
+        assert!(is_widget_command(&command));
+
+        config.keybindings.push(ParsedKeybinding {
+            name: None,
+            modifier: Value::test_string("alt"),
+            keycode: Value::test_string("char_s"),
+            event,
+            mode: Value::test_string("emacs"),
+        });
+        assert_eq!(
+            widget_closure(&command, &config).map(|closure| closure.block_id),
+            Some(closure.block_id)
+        );
+    }
+
     #[test]
     fn test_error() {
         let event = record! {
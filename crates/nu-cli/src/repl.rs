@@ -7,10 +7,15 @@ use crate::prompt_update::{
 };
 use crate::{
     NuHighlighter, NuValidator, NushellPrompt,
+    abbreviation::{AbbreviationEditMode, AbbreviationHighlighter, LineSnapshot},
     completions::NuCompleter,
+    directory_env::{self, DirectoryEnv},
+    hinter::RankedHinter,
     nu_highlight::NoOpHighlighter,
     prompt_update,
-    reedline_config::{KeybindingsMode, add_menus, create_keybindings},
+    reedline_config::{
+        KeybindingsMode, add_menus, create_keybindings, is_widget_command, widget_closure,
+    },
     util::eval_source,
 };
 use crossterm::cursor::SetCursorStyle;
@@ -18,17 +23,18 @@ use log::{error, trace, warn};
 use miette::{ErrReport, IntoDiagnostic, Result};
 use nu_cmd_base::util::get_editor;
 use nu_color_config::StyleComputer;
-#[allow(deprecated)]
-use nu_engine::env_to_strings;
 use nu_engine::exit::cleanup_exit;
+#[allow(deprecated)]
+use nu_engine::{ClosureEvalOnce, env_to_strings};
 use nu_parser::{lex, parse, trim_quotes_str};
 use nu_protocol::shell_error::io::IoError;
 use nu_protocol::{BannerKind, shell_error};
 use nu_protocol::{
-    Config, HistoryConfig, HistoryFileFormat, PipelineData, ShellError, Span, Spanned, Value,
+    Config, HintRanking, HistoryConfig, HistoryFileFormat, NotifyConfig, NotifyMethod,
+    PipelineData, ShellError, Span, Spanned, Type, Value,
     config::NuCursorShape,
-    engine::{EngineState, Stack, StateWorkingSet},
-    report_shell_error,
+    engine::{Closure, EngineState, Stack, StateWorkingSet},
+    format_duration, report_shell_error,
 };
 use nu_utils::{
     filesystem::{PermissionResult, have_permission},
@@ -37,9 +43,9 @@ use nu_utils::{
 #[cfg(feature = "sqlite")]
 use reedline::SqliteBackedHistory;
 use reedline::{
-    CursorConfig, CwdAwareHinter, DefaultCompleter, EditCommand, Emacs, FileBackedHistory,
-    HistorySessionId, MouseClickMode, Osc133ClickEventsMarkers, Osc633Markers, Reedline,
-    SemanticPromptMarkers, Vi,
+    CursorConfig, CwdAwareHinter, DefaultCompleter, EditCommand, EditMode, Emacs,
+    FileBackedHistory, Hinter, HistorySessionId, MouseClickMode, Osc133ClickEventsMarkers,
+    Osc633Markers, Reedline, SemanticPromptMarkers, Vi,
 };
 use std::sync::atomic::Ordering;
 use std::{
@@ -52,6 +58,7 @@ use std::{
     time::{Duration, Instant},
 };
 use sysinfo::System;
+use unicode_segmentation::UnicodeSegmentation;
 
 fn semantic_markers_from_config(
     config: &Config,
@@ -84,6 +91,7 @@ pub fn evaluate_repl(
     let use_color = config.use_ansi_coloring.get(engine_state);
 
     let mut entry_num = 0;
+    let mut directory_env = DirectoryEnv::new();
 
     // Let's grab the shell_integration configs
     let shell_integration_osc2 = config.shell_integration.osc2;
@@ -148,6 +156,9 @@ pub fn evaluate_repl(
         );
     }
 
+    #[cfg(feature = "plugin")]
+    nu_plugin_engine::start_eager_plugins(engine_state, &mut unique_stack);
+
     engine_state.set_startup_time(entire_start_time.elapsed().as_nanos() as i64);
 
     // Regenerate the $nu constant to contain the startup time and any other potential updates
@@ -205,6 +216,7 @@ pub fn evaluate_repl(
                 use_color,
                 entry_num: &mut entry_num,
                 hostname: hostname.as_deref(),
+                directory_env: &mut directory_env,
             });
 
             // pass the most recent version of the line_editor back
@@ -312,6 +324,7 @@ struct LoopContext<'a> {
     use_color: bool,
     entry_num: &'a mut usize,
     hostname: Option<&'a str>,
+    directory_env: &'a mut DirectoryEnv,
 }
 
 /// Perform one iteration of the REPL loop
@@ -331,6 +344,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         use_color,
         entry_num,
         hostname,
+        directory_env,
     } = ctx;
 
     let mut start_time = std::time::Instant::now();
@@ -341,6 +355,13 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     }
     perf!("merge env", start_time, use_color);
 
+    #[cfg(feature = "plugin")]
+    if engine_state.get_config().plugin_hot_reload {
+        start_time = std::time::Instant::now();
+        nu_plugin_engine::reload_changed_plugins(engine_state, &mut stack);
+        perf!("reload changed plugins", start_time, use_color);
+    }
+
     start_time = std::time::Instant::now();
     engine_state.reset_signals();
     perf!("reset signals", start_time, use_color);
@@ -357,6 +378,12 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     }
     perf!("env-change hook", start_time, use_color);
 
+    if engine_state.get_config().directory_env {
+        start_time = std::time::Instant::now();
+        directory_env::update_directory_env(engine_state, &mut stack, directory_env);
+        perf!("directory env", start_time, use_color);
+    }
+
     start_time = std::time::Instant::now();
     // Next, right before we start our prompt and take input from the user, fire the "pre_prompt" hook
     if let Err(err) = hook::eval_hooks(
@@ -391,15 +418,21 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         .get_env_var("TERM_PROGRAM")
         .and_then(|v| v.as_str().ok())
         == Some("vscode");
+    // The highlighter records the buffer for the edit mode to expand abbreviations, and to keep
+    // it when it's discarded with Ctrl+C
+    let line_snapshot = LineSnapshot::default();
     let mut line_editor = line_editor
         .use_kitty_keyboard_enhancement(config.use_kitty_protocol)
         // try to enable bracketed paste
         // It doesn't work on windows system: https://github.com/crossterm-rs/crossterm/issues/737
         .use_bracketed_paste(cfg!(not(target_os = "windows")) && config.bracketed_paste)
-        .with_highlighter(Box::new(NuHighlighter {
-            engine_state: engine_reference.clone(),
-            // STACK-REFERENCE 1
-            stack: stack_arc.clone(),
+        .with_highlighter(Box::new(AbbreviationHighlighter {
+            highlighter: Box::new(NuHighlighter {
+                engine_state: engine_reference.clone(),
+                // STACK-REFERENCE 1
+                stack: stack_arc.clone(),
+            }),
+            snapshot: line_snapshot.clone(),
         }))
         .with_validator(Box::new(NuValidator {
             engine_state: engine_reference.clone(),
@@ -441,11 +474,16 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
 
     start_time = std::time::Instant::now();
     line_editor = if config.use_ansi_coloring.get(engine_state) && config.show_hints {
-        line_editor.with_hinter(Box::new({
+        line_editor.with_hinter({
             // As of Nov 2022, "hints" color_config closures only get `null` passed in.
             let style = style_computer.compute("hints", &Value::nothing(Span::unknown()));
-            CwdAwareHinter::default().with_style(style)
-        }))
+            match config.hint_ranking {
+                HintRanking::Recent => {
+                    Box::new(CwdAwareHinter::default().with_style(style)) as Box<dyn Hinter>
+                }
+                HintRanking::Ranked => Box::new(RankedHinter::default().with_style(style)),
+            }
+        })
     } else {
         line_editor.disable_hints()
     };
@@ -492,7 +530,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
 
     start_time = std::time::Instant::now();
     // Changing the line editor based on the found keybindings
-    line_editor = setup_keybindings(engine_state, line_editor);
+    line_editor = setup_keybindings(engine_state, line_editor, line_snapshot.clone());
 
     perf!("keybindings", start_time, use_color);
 
@@ -544,6 +582,12 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
 
     let line_editor_input_time = std::time::Instant::now();
     match input {
+        Ok(Signal::Success(repl_cmd_line_text)) if is_widget_command(&repl_cmd_line_text) => {
+            if let Some(closure) = widget_closure(&repl_cmd_line_text, engine_state.get_config()) {
+                run_widget(closure, engine_state, &mut stack, &line_editor);
+            }
+            line_editor = flush_engine_state_repl_buffer(engine_state, line_editor);
+        }
         Ok(Signal::Success(repl_cmd_line_text)) => {
             let history_supports_meta = match engine_state.history_config().map(|h| h.file_format) {
                 #[cfg(feature = "sqlite")]
@@ -641,6 +685,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                             use_color,
                             shell_integration_osc633,
                             shell_integration_osc133,
+                            true,
                         );
                     }
                     ReplOperation::RunCommand(cmd) => {
@@ -660,10 +705,21 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                             use_color,
                             shell_integration_osc633,
                             shell_integration_osc133,
+                            true,
+                        );
+                    }
+                    // as the name implies, we do nothing in this case, other than telling the
+                    // terminal that nothing ran
+                    ReplOperation::DoNothing => {
+                        run_finaliziation_ansi_sequence(
+                            &stack,
+                            engine_state,
+                            use_color,
+                            shell_integration_osc633,
+                            shell_integration_osc133,
+                            false,
                         );
                     }
-                    // as the name implies, we do nothing in this case
-                    ReplOperation::DoNothing => {}
                 },
                 Err(ref e) => error!("Error parsing operation: {e}"),
             }
@@ -674,6 +730,21 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 Value::string(format!("{}", cmd_duration.as_millis()), Span::unknown()),
             );
 
+            let exit_code = stack
+                .get_env_var(engine_state, "LAST_EXIT_CODE")
+                .and_then(|e| e.as_int().ok())
+                .unwrap_or(0);
+            let config = stack.get_config(engine_state);
+            run_command_duration_hook(
+                engine_state,
+                &mut stack,
+                &config.hooks.command_duration,
+                &repl_cmd_line_text,
+                cmd_duration,
+                exit_code,
+            );
+            notify_long_command(&config.notify, &repl_cmd_line_text, cmd_duration, exit_code);
+
             if history_supports_meta
                 && let Err(e) = fill_in_result_related_history_metadata(
                     &repl_cmd_line_text,
@@ -686,6 +757,11 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 warn!("Could not fill in result related history metadata: {e}");
             }
 
+            #[cfg(feature = "sqlite")]
+            if history_supports_meta {
+                collapse_history_duplicates(&repl_cmd_line_text, engine_state);
+            }
+
             if shell_integration_osc2 {
                 run_shell_integration_osc2(None, engine_state, &mut stack, use_color);
             }
@@ -710,13 +786,20 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
             line_editor = flush_engine_state_repl_buffer(engine_state, line_editor);
         }
         Ok(Signal::CtrlC) => {
-            // `Reedline` clears the line content. New prompt is shown
+            // `Reedline` clears the line content. New prompt is shown, and the discarded line
+            // can be brought back with `commandline restore`
+            engine_state
+                .repl_state
+                .lock()
+                .expect("repl state mutex")
+                .discard(line_snapshot.line());
             run_finaliziation_ansi_sequence(
                 &stack,
                 engine_state,
                 use_color,
                 shell_integration_osc633,
                 shell_integration_osc133,
+                false,
             );
         }
         Ok(Signal::CtrlD) => {
@@ -728,6 +811,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 use_color,
                 shell_integration_osc633,
                 shell_integration_osc133,
+                false,
             );
 
             println!();
@@ -753,6 +837,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 use_color,
                 shell_integration_osc633,
                 shell_integration_osc133,
+                false,
             );
         }
     }
@@ -822,6 +907,125 @@ fn fill_in_result_related_history_metadata(
     Ok(())
 }
 
+///
+/// Run the `command_duration` hooks with the command that finished, its duration and exit code
+///
+fn run_command_duration_hook(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    hooks: &[Value],
+    command: &str,
+    duration: Duration,
+    exit_code: i64,
+) {
+    if hooks.is_empty() {
+        return;
+    }
+    let arguments = vec![
+        ("$command".into(), Value::string(command, Span::unknown())),
+        (
+            "$duration".into(),
+            Value::duration(
+                i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX),
+                Span::unknown(),
+            ),
+        ),
+        ("$exit_code".into(), Value::int(exit_code, Span::unknown())),
+    ];
+    if let Err(err) =
+        nu_cmd_base::hook::eval_hooks(engine_state, stack, arguments, hooks, "command_duration")
+    {
+        report_shell_error(None, engine_state, &err);
+    }
+}
+
+///
+/// Notify that a command finished, if it took at least as long as `notify.threshold`
+///
+fn notify_long_command(config: &NotifyConfig, command: &str, duration: Duration, exit_code: i64) {
+    let Some((title, body)) = long_command_notification(config, command, duration, exit_code)
+    else {
+        return;
+    };
+
+    match config.method {
+        NotifyMethod::Osc777 => run_ansi_sequence(&format!("\x1b]777;notify;{title};{body}\x1b\\")),
+        NotifyMethod::Osc9 => run_ansi_sequence(&format!("\x1b]9;{title}: {body}\x1b\\")),
+        NotifyMethod::Bell => run_ansi_sequence("\x07"),
+        NotifyMethod::NotifySend => {
+            match std::process::Command::new("notify-send")
+                .args([title, &body])
+                .spawn()
+            {
+                // Reap it without blocking the prompt, as notify-send can wait for the daemon
+                Ok(mut child) => {
+                    let reaper = std::thread::Builder::new()
+                        .name("notify-send".into())
+                        .spawn(move || {
+                            if let Err(e) = child.wait() {
+                                warn!("Could not wait for notify-send: {e}");
+                            }
+                        });
+                    if let Err(e) = reaper {
+                        warn!("Could not wait for notify-send: {e}");
+                    }
+                }
+                Err(e) => warn!("Could not run notify-send: {e}"),
+            }
+        }
+    }
+}
+
+///
+/// The title and body of the notification for a command, if it took at least as long as
+/// `notify.threshold`
+///
+fn long_command_notification(
+    config: &NotifyConfig,
+    command: &str,
+    duration: Duration,
+    exit_code: i64,
+) -> Option<(&'static str, String)> {
+    let threshold = config.threshold?;
+    let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+    if nanos < threshold {
+        return None;
+    }
+
+    let title = if exit_code == 0 {
+        "Command finished"
+    } else {
+        "Command failed"
+    };
+    // Only the first line of the command, without anything that could end the escape sequence
+    let command = command
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != ';')
+        .collect::<String>();
+    Some((title, format!("{command} ({})", format_duration(nanos))))
+}
+
+///
+/// Removes older entries of the command from the history, if `history.ignore_duplicates` is set
+///
+#[cfg(feature = "sqlite")]
+fn collapse_history_duplicates(s: &str, engine_state: &EngineState) {
+    let Some(history) = engine_state.history_config() else {
+        return;
+    };
+    if s.is_empty() || !history.ignore_duplicates {
+        return;
+    }
+    if let Some(path) = history.file_path()
+        && let Err(e) = crate::commands::collapse_duplicates(&path, s)
+    {
+        warn!("Could not collapse duplicate history entries: {e}");
+    }
+}
+
 /// The kinds of operations you can do in a single loop iteration of the REPL
 enum ReplOperation {
     /// "auto-cd": change directory by typing it in directly
@@ -1046,17 +1250,12 @@ fn run_shell_integration_osc2(
             path
         };
 
-        let title = match command_name {
-            Some(binary_name) => {
-                let split_binary_name = binary_name.split_whitespace().next();
-                if let Some(binary_name) = split_binary_name {
-                    format!("{maybe_abbrev_path}> {binary_name}")
-                } else {
-                    maybe_abbrev_path.to_string()
-                }
-            }
-            None => maybe_abbrev_path.to_string(),
+        let template = &engine_state.get_config().terminal_title;
+        let template = match command_name {
+            Some(_) => &template.command,
+            None => &template.prompt,
         };
+        let title = render_title(template, &maybe_abbrev_path, command_name);
 
         // Set window title too
         // https://tldp.org/HOWTO/Xterm-Title-3.html
@@ -1069,6 +1268,54 @@ fn run_shell_integration_osc2(
     }
 }
 
+/// Fill in the placeholders of a `$env.config.terminal_title` template.
+///
+/// Control characters are dropped, as they would end the escape sequence early.
+fn render_title(template: &str, cwd: &str, command_line: Option<&str>) -> String {
+    let dir = Path::new(cwd)
+        .file_name()
+        .map_or(cwd.to_string(), |name| name.to_string_lossy().into_owned());
+    let command_line = command_line.map(str::trim).unwrap_or_default();
+    let command = command_line.split_whitespace().next().unwrap_or_default();
+    let command_line = command_line.lines().next().unwrap_or_default();
+
+    let mut title = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        title.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = [
+            ("{cwd}", cwd),
+            ("{dir}", &dir),
+            ("{command}", command),
+            ("{commandline}", command_line),
+        ]
+        .into_iter()
+        .find(|(placeholder, _)| rest.starts_with(placeholder));
+        match value {
+            Some((placeholder, value)) => {
+                title.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                title.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    title.push_str(rest);
+    title.retain(|c| !c.is_control());
+    title
+}
+
+/// The characters that are percent-encoded in the OSC 7 `file://` URI.
+const OSC7_PATH_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'?');
+
 fn run_shell_integration_osc7(
     hostname: Option<&str>,
     engine_state: &EngineState,
@@ -1092,7 +1339,7 @@ fn run_shell_integration_osc7(
                 percent_encoding::CONTROLS
             ),
             if path.starts_with('/') { "" } else { "/" },
-            percent_encoding::utf8_percent_encode(&path, percent_encoding::CONTROLS)
+            percent_encoding::utf8_percent_encode(&path, OSC7_PATH_ENCODE_SET)
         ));
 
         perf!(
@@ -1164,6 +1411,76 @@ fn run_shell_integration_reset_application_mode() {
     run_ansi_sequence(RESET_APPLICATION_MODE);
 }
 
+///
+/// Run a line editor widget, a closure bound to a key with `{ widget: {|buffer, cursor| ... } }`.
+///
+/// The widget can return the new buffer as a string, or a record with the new `buffer`, the new
+/// `cursor` position, and whether to `accept` the line and run it. The result is put in the
+/// EngineState buffer, to be flushed back into the line editor.
+///
+fn run_widget(
+    closure: Closure,
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    line_editor: &Reedline,
+) {
+    let buffer = line_editor.current_buffer_contents().to_string();
+    let insertion_point = line_editor.current_insertion_point();
+    // Positions given to and taken from widgets count graphemes, like `commandline get-cursor`
+    let cursor = buffer
+        .grapheme_indices(true)
+        .take_while(|(i, _)| *i < insertion_point)
+        .count();
+
+    let result = ClosureEvalOnce::new(engine_state, stack, closure)
+        .add_arg(Value::string(buffer.clone(), Span::unknown()))
+        .add_arg(Value::int(cursor as i64, Span::unknown()))
+        .run_with_input(PipelineData::empty())
+        .and_then(|output| output.into_value(Span::unknown()))
+        .and_then(|value| match value {
+            Value::Nothing { .. } => Ok((buffer.clone(), Some(cursor as i64), false)),
+            Value::String { val, .. } => Ok((val, None, false)),
+            Value::Record { val, .. } => {
+                let new_buffer = match val.get("buffer") {
+                    Some(buffer) => buffer.as_str()?.to_string(),
+                    None => buffer.clone(),
+                };
+                let new_cursor = val.get("cursor").map(Value::as_int).transpose()?;
+                let accept = val
+                    .get("accept")
+                    .map(Value::as_bool)
+                    .transpose()?
+                    .unwrap_or(false);
+                Ok((new_buffer, new_cursor, accept))
+            }
+            value => Err(ShellError::RuntimeTypeMismatch {
+                expected: Type::custom("string, record, or nothing"),
+                actual: value.get_type(),
+                span: value.span(),
+            }),
+        });
+
+    let (buffer, cursor, accept) = match result {
+        Ok(result) => result,
+        Err(err) => {
+            report_shell_error(None, engine_state, &err);
+            (buffer, Some(cursor as i64), false)
+        }
+    };
+
+    let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+    repl.cursor_pos = match cursor {
+        Some(cursor) => buffer
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .nth(cursor.max(0) as usize)
+            .unwrap_or(buffer.len()),
+        None => buffer.len(),
+    };
+    repl.buffer = buffer;
+    repl.accept = accept;
+}
+
 ///
 /// Clear the screen and output anything remaining in the EngineState buffer.
 ///
@@ -1219,21 +1536,31 @@ fn setup_history(
 ///
 /// Setup Reedline keybindingds based on the provided config
 ///
-fn setup_keybindings(engine_state: &EngineState, line_editor: Reedline) -> Reedline {
-    match create_keybindings(engine_state.get_config()) {
-        Ok(keybindings) => match keybindings {
-            KeybindingsMode::Emacs(keybindings) => {
-                let edit_mode = Box::new(Emacs::new(keybindings));
-                line_editor.with_edit_mode(edit_mode)
-            }
-            KeybindingsMode::Vi {
-                insert_keybindings,
-                normal_keybindings,
-            } => {
-                let edit_mode = Box::new(Vi::new(insert_keybindings, normal_keybindings));
+fn setup_keybindings(
+    engine_state: &EngineState,
+    line_editor: Reedline,
+    line_snapshot: LineSnapshot,
+) -> Reedline {
+    let config = engine_state.get_config();
+    match create_keybindings(config) {
+        Ok(keybindings) => {
+            let edit_mode: Box<dyn EditMode> = match keybindings {
+                KeybindingsMode::Emacs(keybindings) => Box::new(Emacs::new(keybindings)),
+                KeybindingsMode::Vi {
+                    insert_keybindings,
+                    normal_keybindings,
+                } => Box::new(Vi::new(insert_keybindings, normal_keybindings)),
+            };
+            if config.abbreviations.is_empty() {
                 line_editor.with_edit_mode(edit_mode)
+            } else {
+                line_editor.with_edit_mode(Box::new(AbbreviationEditMode {
+                    edit_mode,
+                    abbreviations: config.abbreviations.clone(),
+                    snapshot: line_snapshot,
+                }))
             }
-        },
+        }
         Err(e) => {
             report_shell_error(None, engine_state, &e);
             line_editor
@@ -1323,15 +1650,23 @@ fn map_nucursorshape_to_cursorshape(shape: NuCursorShape) -> Option<SetCursorSty
     }
 }
 
+/// The marker for the end of a command, with its exit code if `command_ran`. Without one, the
+/// terminal knows that the prompt was left without running anything, like with Ctrl+C.
 fn get_command_finished_marker(
     stack: &Stack,
     engine_state: &EngineState,
     shell_integration_osc633: bool,
     shell_integration_osc133: bool,
+    command_ran: bool,
 ) -> String {
-    let exit_code = stack
-        .get_env_var(engine_state, "LAST_EXIT_CODE")
-        .and_then(|e| e.as_int().ok());
+    let exit_code = if command_ran {
+        let exit_code = stack
+            .get_env_var(engine_state, "LAST_EXIT_CODE")
+            .and_then(|e| e.as_int().ok());
+        format!(";{}", exit_code.unwrap_or(0))
+    } else {
+        String::new()
+    };
 
     if shell_integration_osc633 {
         if stack
@@ -1342,17 +1677,13 @@ fn get_command_finished_marker(
             // We're in vscode and we have osc633 enabled
             format!(
                 "{}{}{}",
-                VSCODE_POST_EXECUTION_MARKER_PREFIX,
-                exit_code.unwrap_or(0),
-                VSCODE_POST_EXECUTION_MARKER_SUFFIX
+                VSCODE_POST_EXECUTION_MARKER_PREFIX, exit_code, VSCODE_POST_EXECUTION_MARKER_SUFFIX
             )
         } else if shell_integration_osc133 {
             // If we're in VSCode but we don't find the env var, just return the regular markers
             format!(
                 "{}{}{}",
-                POST_EXECUTION_MARKER_PREFIX,
-                exit_code.unwrap_or(0),
-                POST_EXECUTION_MARKER_SUFFIX
+                POST_EXECUTION_MARKER_PREFIX, exit_code, POST_EXECUTION_MARKER_SUFFIX
             )
         } else {
             // We're not in vscode, so we don't need to do anything special
@@ -1361,9 +1692,7 @@ fn get_command_finished_marker(
     } else if shell_integration_osc133 {
         format!(
             "{}{}{}",
-            POST_EXECUTION_MARKER_PREFIX,
-            exit_code.unwrap_or(0),
-            POST_EXECUTION_MARKER_SUFFIX
+            POST_EXECUTION_MARKER_PREFIX, exit_code, POST_EXECUTION_MARKER_SUFFIX
         )
     } else {
         "\x1b[0m".to_string()
@@ -1384,6 +1713,7 @@ fn run_finaliziation_ansi_sequence(
     use_color: bool,
     shell_integration_osc633: bool,
     shell_integration_osc133: bool,
+    command_ran: bool,
 ) {
     if shell_integration_osc633 {
         // Only run osc633 if we are in vscode
@@ -1399,6 +1729,7 @@ fn run_finaliziation_ansi_sequence(
                 engine_state,
                 shell_integration_osc633,
                 shell_integration_osc133,
+                command_ran,
             ));
 
             perf!(
@@ -1414,6 +1745,7 @@ fn run_finaliziation_ansi_sequence(
                 engine_state,
                 shell_integration_osc633,
                 shell_integration_osc133,
+                command_ran,
             ));
 
             perf!(
@@ -1430,6 +1762,7 @@ fn run_finaliziation_ansi_sequence(
             engine_state,
             shell_integration_osc633,
             shell_integration_osc133,
+            command_ran,
         ));
 
         perf!(
@@ -1462,10 +1795,112 @@ fn looks_like_path(orig: &str) -> bool {
         || orig.ends_with(std::path::MAIN_SEPARATOR)
 }
 
+#[cfg(test)]
+mod title_tests {
+    use super::render_title;
+
+    #[test]
+    fn default_templates_match_previous_titles() {
+        assert_eq!("~/src", render_title("{cwd}", "~/src", None));
+        assert_eq!(
+            "~/src> cargo",
+            render_title("{cwd}> {command}", "~/src", Some("cargo build --release"))
+        );
+    }
+
+    #[test]
+    fn title_placeholders_are_filled_in() {
+        assert_eq!(
+            "src: ls -la | sort-by size [{unknown}]",
+            render_title(
+                "{dir}: {commandline} [{unknown}]",
+                "~/src",
+                Some("  ls -la | sort-by size\nfirst")
+            )
+        );
+        assert_eq!("/", render_title("{dir}", "/", None));
+        assert_eq!("~/{dir}", render_title("{cwd}", "~/{dir}", None));
+    }
+
+    #[test]
+    fn title_drops_control_characters() {
+        assert_eq!(
+            "~> echo]0;x",
+            render_title("{cwd}> {commandline}", "~", Some("echo\x1b]0;x\x07"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod command_duration_tests {
+    use super::{long_command_notification, run_command_duration_hook};
+    use nu_protocol::{NotifyConfig, Value, engine::Stack};
+    use std::time::Duration;
+
+    fn notify_after(threshold: Option<Duration>) -> NotifyConfig {
+        NotifyConfig {
+            threshold: threshold.map(|threshold| threshold.as_nanos() as i64),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn notifies_only_at_the_threshold() {
+        let config = notify_after(Some(Duration::from_secs(10)));
+        assert_eq!(
+            None,
+            long_command_notification(&config, "sleep 9sec", Duration::from_secs(9), 0)
+        );
+        assert_eq!(
+            Some(("Command finished", "sleep 10sec (10sec)".to_string())),
+            long_command_notification(&config, "sleep 10sec", Duration::from_secs(10), 0)
+        );
+        assert_eq!(
+            Some(("Command failed", "make exit 1 (1min)".to_string())),
+            long_command_notification(&config, "make; exit 1", Duration::from_secs(60), 1)
+        );
+    }
+
+    #[test]
+    fn does_not_notify_without_a_threshold() {
+        let config = notify_after(None);
+        assert_eq!(
+            None,
+            long_command_notification(&config, "cargo build", Duration::from_secs(3600), 0)
+        );
+    }
+
+    #[test]
+    fn command_duration_hook_gets_the_command_duration_and_exit_code() {
+        let mut engine_state = nu_cmd_lang::create_default_context();
+        let mut stack = Stack::new();
+        let hook = Value::test_string("$env.HOOK_ARGS = $\"($command) ($duration) ($exit_code)\"");
+
+        run_command_duration_hook(
+            &mut engine_state,
+            &mut stack,
+            &[hook],
+            "sleep 2sec",
+            Duration::from_secs(2),
+            1,
+        );
+
+        assert_eq!(
+            Some("sleep 2sec 2sec 1"),
+            stack
+                .get_env_var(&engine_state, "HOOK_ARGS")
+                .and_then(|value| value.as_str().ok())
+        );
+    }
+}
+
 #[cfg(test)]
 mod semantic_marker_tests {
-    use super::semantic_markers_from_config;
-    use nu_protocol::Config;
+    use super::{get_command_finished_marker, semantic_markers_from_config};
+    use nu_protocol::{
+        Config, Span, Value,
+        engine::{EngineState, Stack},
+    };
     use reedline::PromptKind;
 
     #[test]
@@ -1497,6 +1932,22 @@ mod semantic_marker_tests {
         );
     }
 
+    #[test]
+    fn command_finished_marker_has_exit_code_only_if_command_ran() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var("LAST_EXIT_CODE".into(), Value::int(2, Span::test_data()));
+
+        assert_eq!(
+            "\x1b]133;D;2\x1b\\",
+            get_command_finished_marker(&stack, &engine_state, false, true, true)
+        );
+        assert_eq!(
+            "\x1b]133;D\x1b\\",
+            get_command_finished_marker(&stack, &engine_state, false, true, false)
+        );
+    }
+
     #[test]
     fn semantic_markers_none_when_disabled() {
         let mut config = Config::default();
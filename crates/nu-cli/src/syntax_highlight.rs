@@ -1,6 +1,6 @@
 use log::trace;
 use nu_ansi_term::Style;
-use nu_color_config::{get_matching_brackets_style, get_shape_color};
+use nu_color_config::{StyleComputer, get_matching_brackets_style};
 use nu_engine::env;
 use nu_parser::{FlatShape, flatten_block, parse};
 use nu_protocol::{
@@ -42,6 +42,7 @@ pub(crate) fn highlight_syntax(
 
     let config = stack.get_config(engine_state);
     let highlight_resolved_externals = config.highlight_resolved_externals;
+    let style_computer = StyleComputer::from_config(engine_state, stack);
     let mut working_set = StateWorkingSet::new(engine_state);
     let block = parse(&mut working_set, None, line.as_bytes(), false);
     // TODO: Traverse::flat_map based highlighting?
@@ -86,9 +87,8 @@ pub(crate) fn highlight_syntax(
             line[(span.start - global_span_offset)..(span.end - global_span_offset)].to_string();
 
         let mut add_colored_token = |shape: &FlatShape, text: String| {
-            result
-                .text
-                .push((get_shape_color(shape.as_str(), &config), text));
+            let style = style_computer.compute_shape(shape.as_str(), &text);
+            result.text.push((style, text));
         };
 
         match flat_shape {
@@ -138,7 +138,7 @@ pub(crate) fn highlight_syntax(
                     let start = part.start - span.start;
                     let end = part.end - span.start;
                     let text = next_token[start..end].to_string();
-                    let mut style = get_shape_color(flat_shape.as_str(), &config);
+                    let mut style = style_computer.compute_shape(flat_shape.as_str(), &text);
                     if highlight {
                         style = get_matching_brackets_style(style, &config);
                     }
@@ -140,6 +140,22 @@ fn custom_completer_with_options(
     NuCompleter::new(Arc::new(engine), Arc::new(stack))
 }
 
+/// Custom completers are run by the AST evaluator, which caches the results of `@memo` commands too
+#[test]
+fn memoized_custom_completer() {
+    let (_, _, mut engine, mut stack) = new_engine();
+    let command = r#"
+        @memo
+        def comp [] { [(random uuid)] }
+        def my-command [arg: string@comp] {}"#;
+    assert!(support::merge_input(command.as_bytes(), &mut engine, &mut stack).is_ok());
+    let mut completer = NuCompleter::new(Arc::new(engine), Arc::new(stack));
+
+    let first = completer.complete("my-command ", 11);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first, completer.complete("my-command ", 11));
+}
+
 #[fixture]
 fn custom_completer() -> NuCompleter {
     // Create a new engine
@@ -192,6 +208,9 @@ fn fuzzy_alpha_sort_completer() -> NuCompleter {
 #[case::dynamic_short_flag_value("fake-cmd arg0:0 -f ", None, vec!["flag:0", "flag:1", "flag:2"])]
 #[case::dynamic_1st_positional("fake-cmd -f flag:0 ", None, vec!["arg0:0"])]
 #[case::dynamic_2nd_positional("fake-cmd -f flag:0 foo --unknown ", None, vec!["arg1:0", "arg1:1"])]
+#[case::builtin_flag_value("into binary --endian l", None, vec!["little"])]
+#[case::builtin_positional_value("ansi green_b", None, vec!["green_bold"])]
+#[case::builtin_format_string("format date %d.", None, vec!["%d.%m.%Y"])]
 fn misc_command_argument_completions(
     mut completer: NuCompleter,
     #[case] input: &str,
@@ -1068,6 +1087,63 @@ fn external_completer_invalid() {
     assert!(suggestions.is_empty());
 }
 
+#[test]
+fn external_completers_by_command() {
+    let mut completer = external_completer(
+        r#"$env.config.completions.external = {
+            completer: {|spans| ['default'] }
+            completers: {
+                git: {|spans| ['git'] }
+                "git commit": {|spans| ['git commit'] }
+            }
+        }"#,
+        fs::fixtures().join("completions"),
+    );
+
+    let suggestions = completer.complete("git commit -", 12);
+    match_suggestions(&vec!["git commit"], &suggestions);
+    let suggestions = completer.complete("git push ", 9);
+    match_suggestions(&vec!["git"], &suggestions);
+    let suggestions = completer.complete("foo ", 4);
+    match_suggestions(&vec!["default"], &suggestions);
+}
+
+#[test]
+fn external_completer_cache_duration() {
+    let config = |cache_duration| {
+        format!(
+            "$env.config.completions.external.completer = {{|spans| [(random uuid)] }}
+            $env.config.completions.external.cache_duration = {cache_duration}"
+        )
+    };
+
+    let mut completer = external_completer(&config("1min"), fs::fixtures().join("completions"));
+    let first = completer.complete("foo ", 4);
+    assert_eq!(first, completer.complete("foo ", 4));
+    // The cache is for a command line
+    assert_ne!(first, completer.complete("foo bar ", 8));
+
+    let mut completer = external_completer(&config("0sec"), fs::fixtures().join("completions"));
+    let first = completer.complete("foo ", 4);
+    assert_ne!(first, completer.complete("foo ", 4));
+}
+
+/// Fallback to file completions when external completer takes longer than the timeout
+#[test]
+fn external_completer_timeout() {
+    let mut completer = external_completer(
+        "$env.config.completions.external.completer = {|spans| sleep 1min; ['slow'] }
+        $env.config.completions.external.timeout = 10ms",
+        fs::fixtures().join("completions"),
+    );
+
+    let start = std::time::Instant::now();
+    let suggestions = completer.complete("foo test", 8);
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+    let expected = [folder("test_a"), file("test_a_symlink"), folder("test_b")];
+    match_suggestions_by_string(&expected, &suggestions);
+}
+
 #[test]
 fn command_wide_completion_external() {
     let mut completer = custom_completer();
@@ -2801,12 +2877,16 @@ fn run_external_completion_within_pwd(
     pwd: AbsolutePathBuf,
 ) -> Vec<Suggestion> {
     let completer = format!("$env.config.completions.external.completer = {completer}");
+    external_completer(&completer, pwd).complete(input, input.len())
+}
 
+/// A completer with the external completion config set by `config`
+fn external_completer(config: &str, pwd: AbsolutePathBuf) -> NuCompleter {
     // Create a new engine
     let (_, _, mut engine_state, mut stack) = new_engine_helper(pwd);
     let (block, delta) = {
         let mut working_set = StateWorkingSet::new(&engine_state);
-        let block = parse(&mut working_set, None, completer.as_bytes(), false);
+        let block = parse(&mut working_set, None, config.as_bytes(), false);
         assert!(working_set.parse_errors.is_empty());
 
         (block, working_set.render())
@@ -2823,9 +2903,7 @@ fn run_external_completion_within_pwd(
     assert!(engine_state.merge_env(&mut stack).is_ok());
 
     // Instantiate a new completer
-    let mut completer = NuCompleter::new(Arc::new(engine_state), Arc::new(stack));
-
-    completer.complete(input, input.len())
+    NuCompleter::new(Arc::new(engine_state), Arc::new(stack))
 }
 
 fn run_external_completion(completer: &str, input: &str) -> Vec<Suggestion> {
@@ -0,0 +1,111 @@
+use nu_cmd_base::WrapCall;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrMemo;
+
+impl Command for AttrMemo {
+    fn name(&self) -> &str {
+        "attr memo"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr memo")
+            .input_output_type(Type::Nothing, Type::record())
+            .named(
+                "ttl",
+                SyntaxShape::Duration,
+                "How long a cached result is reused for (default: the rest of the session).",
+                Some('t'),
+            )
+            .switch(
+                "input",
+                "Cache results by pipeline input too, not only by arguments.",
+                Some('i'),
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for caching the results of a custom command."
+    }
+
+    fn extra_description(&self) -> &str {
+        "\
+            Calls to the command with the same arguments return the result of the first call, \
+            without running the command again. Arguments are compared by value, after default \
+            values are filled in. With --input, the pipeline input must be the same too, and it \
+            is collected before the command runs.\n\
+            \n\
+            Only use this with commands that don't have side effects, like changing the \
+            environment, since those are skipped when a cached result is returned. \
+            Results are collected into a value before they're cached, and errors aren't cached.\
+        "
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let call = WrapCall::Eval(engine_state, stack, call);
+        Ok(memo_record(call)?.into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let call = WrapCall::ConstEval(working_set, call);
+        Ok(memo_record(call)?.into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Cache the latest release of a repository for an hour.",
+                example: r###"@memo --ttl 1hr
+    def latest-release [repo: string] {
+        http get $"https://api.github.com/repos/($repo)/releases/latest" | get tag_name
+    }"###,
+                result: None,
+            },
+            Example {
+                description: "Cache parsing a file by its contents.",
+                example: r###"@memo --input
+    def parse-log []: string -> table { lines | parse "{level}: {message}" }"###,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn memo_record(call: WrapCall) -> Result<Value, ShellError> {
+    let (call, ttl): (_, Option<Value>) = call.get_flag("ttl")?;
+    let (call, input) = call.has_flag::<bool>("input")?;
+
+    let mut record = Record::new();
+    if let Some(ttl) = ttl {
+        if let Value::Duration { val, .. } = ttl
+            && val < 0
+        {
+            return Err(ShellError::IncorrectValue {
+                msg: "The ttl can't be negative".into(),
+                val_span: ttl.span(),
+                call_span: call.head(),
+            });
+        }
+        record.push("ttl", ttl);
+    }
+    record.push("input", Value::bool(input, call.head()));
+
+    Ok(Value::record(record, call.head()))
+}
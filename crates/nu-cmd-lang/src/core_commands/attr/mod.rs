@@ -3,6 +3,7 @@ mod category;
 mod complete;
 mod deprecated;
 mod example;
+mod memo;
 mod search_terms;
 
 pub use attr_::Attr;
@@ -10,4 +11,5 @@ pub use category::AttrCategory;
 pub use complete::{AttrComplete, AttrCompleteExternal};
 pub use deprecated::AttrDeprecated;
 pub use example::AttrExample;
+pub use memo::AttrMemo;
 pub use search_terms::AttrSearchTerms;
@@ -0,0 +1,94 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::engine::Closure;
+
+#[derive(Clone)]
+pub struct Defer;
+
+impl Command for Defer {
+    fn name(&self) -> &str {
+        "defer"
+    }
+
+    fn description(&self) -> &str {
+        "Run a block when the surrounding command, closure or script finishes."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The deferred block always runs, whether the surrounding code finishes normally, with
+`return`, with an error, or because it was interrupted with Ctrl-C. Deferred blocks run in
+the reverse order that they were registered in, once the output of the surrounding code
+has been collected. An error from a deferred block is only reported if the surrounding
+code didn't fail with an error of its own.
+
+Changes to the environment in a deferred block are kept, so it can restore variables or
+the working directory that the surrounding code changed.
+
+Blocks like the ones of `if`, `for` or `try` don't count as the surrounding code, so
+a `defer` inside of them waits until the whole command, closure or script is done."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("defer")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "block",
+                SyntaxShape::Block,
+                "The block to run when the surrounding code finishes.",
+            )
+            .category(Category::Core)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cleanup", "finally", "scope", "exit"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let block: Closure = call.req(engine_state, stack, 0)?;
+        stack.deferred.push(block);
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Run a block after the rest of a closure.",
+                example: "do --env { defer { $env.STATE = 'done' }; $env.STATE = 'running' }; $env.STATE",
+                result: Some(Value::test_string("done")),
+            },
+            Example {
+                description: "Delete a temporary file, even if the command fails.",
+                example: r#"def process [] {
+    let tmp = mktemp
+    defer { rm $tmp }
+    ^curl -o $tmp https://example.com
+    open $tmp | lines | length
+}"#,
+                result: None,
+            },
+            Example {
+                description: "Restore the working directory of a command that changes it.",
+                example: r#"def --env in-tmp [] { let dir = $env.PWD; defer { cd $dir }; cd /tmp; ls }"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Do;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples_with_commands;
+
+        test_examples_with_commands(Defer {}, &[&Do {}])
+    }
+}
@@ -370,6 +370,12 @@ fn describe_value_inner(
             "rust_type" => Value::string(type_of(&val), head),
             "value" => value,
         }),
+        Value::Rate { val, .. } => Description::Record(record! {
+            "type" => Value::string("rate", head),
+            "detailed_type" => Value::string(value_type, head),
+            "rust_type" => Value::string(type_of(&val), head),
+            "value" => value,
+        }),
         Value::Date { val, .. } => Description::Record(record! {
             "type" => Value::string("datetime", head),
             "detailed_type" => Value::string(value_type, head),
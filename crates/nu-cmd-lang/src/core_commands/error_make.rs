@@ -18,6 +18,12 @@ impl Command for ErrorMake {
                 SyntaxShape::OneOf(vec![SyntaxShape::Record(vec![]), SyntaxShape::String]),
                 "The error to create.",
             )
+            .named(
+                "inner",
+                SyntaxShape::Any,
+                "The error that caused this one, like the error given to a `catch` closure.",
+                Some('i'),
+            )
             .switch("unspanned", "Remove the labels from the error.", Some('u'))
     }
 
@@ -36,6 +42,8 @@ impl Command for ErrorMake {
   * `url: string`
   * `inner: table<error_struct>`
   * `src: src_record`
+  * `data: any` - structured data for code that catches the error, available
+    as the `data` field of the error record in `catch`
 
 The `error_label` should contain the following keys:
 
@@ -61,7 +69,14 @@ Errors can also be chained using `try {} catch {}`, allowing for related errors
 to be printed out more easily. The code block for `catch` passes a record of the
 `try` block's error into the catch block, which can be used in `error make`
 either as the input or as an argument. These will be added as `inner` errors to
-the most recent `error make`."
+the most recent `error make`.
+
+To make an error that was caused by another one, pass the cause to `--inner`.
+It's shown as the cause of the new error, and is kept as it is, including its
+code and data, so that code catching the new error can still inspect it.
+
+Codes are how scripts tell errors apart in `catch` (see `help try`), so errors
+raised by modules should have a code of their own, like `mymodule::not_found`."
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -111,6 +126,20 @@ the most recent `error make`."
     } catch {|err|
         print 'We got an error that will be chained!'
         error make {msg: "bar" inner: [$err]}
+    }"#,
+                result: None,
+            },
+            Example {
+                description: "Create an error with a custom code and structured data.",
+                example: r#"error make {msg: "user not found" code: "users::not_found" data: {id: 42}}"#,
+                result: None,
+            },
+            Example {
+                description: "Raise an error caused by a caught one.",
+                example: r#"try {
+        open config.toml
+    } catch {|err|
+        error make --inner $err {msg: "could not load the configuration" code: "app::config"}
     }"#,
                 result: None,
             },
@@ -130,6 +159,11 @@ the most recent `error make`."
             Err(e) => return Err(e),
         };
         let show_labels: bool = !call.has_flag(engine_state, stack, "unspanned")?;
+        // The `--inner` error goes first, since that's the one shown as the cause
+        let cause = call
+            .get_flag(engine_state, stack, "inner")?
+            .map(cause_value)
+            .transpose()?;
 
         let inners = match ErrorInfo::from_value(input.into_value(call.head)?) {
             Ok(v) => vec![v.into_value(call.head)],
@@ -139,7 +173,7 @@ the most recent `error make`."
         Err(match (inners, value) {
             (inner, Value::String { val, .. }) => ErrorInfo {
                 msg: val,
-                inner,
+                inner: cause.into_iter().chain(inner).collect(),
                 ..ErrorInfo::default()
             }
             .labeled(call.head, show_labels),
@@ -150,7 +184,7 @@ the most recent `error make`."
                 },
             ) => {
                 let mut ei = ErrorInfo::from_value((*val).clone().into_value(internal_span))?;
-                ei.inner = [ei.inner, inner].concat();
+                ei.inner = cause.into_iter().chain(ei.inner).chain(inner).collect();
 
                 ei.labeled(internal_span, show_labels)
             }
@@ -160,6 +194,21 @@ the most recent `error make`."
     }
 }
 
+/// Turn the `--inner` error, which can be an error value or an `error_struct` like the record
+/// given to `catch`, into an `inner` entry.
+fn cause_value(value: Value) -> Result<Value, ShellError> {
+    let span = value.span();
+    match value {
+        Value::Error { error, .. } => Ok(ErrorInfo {
+            msg: error.to_string(),
+            raw: Some(Value::error(*error, span)),
+            ..ErrorInfo::default()
+        }
+        .into_value(span)),
+        value => ErrorInfo::from_value(value).map(|info| info.into_value(span)),
+    }
+}
+
 #[derive(Debug, Clone, IntoValue, FromValue)]
 struct ErrorInfo {
     msg: String,
@@ -173,6 +222,7 @@ struct ErrorInfo {
     inner: Vec<Value>,
     raw: Option<Value>,
     src: Option<ErrorSource>,
+    data: Option<Value>,
 }
 
 impl Default for ErrorInfo {
@@ -187,6 +237,7 @@ impl Default for ErrorInfo {
             inner: Vec::default(),
             raw: None,
             src: None,
+            data: None,
         }
     }
 }
@@ -261,6 +312,7 @@ impl ErrorInfo {
                 url: ei.url,
                 help: ei.help,
                 inner: inner.into(),
+                data: ei.data,
             }
             .into(),
             // Error error with a raw error value somewhere
@@ -0,0 +1,130 @@
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::{PipelineIterator, engine::Closure};
+
+#[derive(Clone)]
+pub struct Lazy;
+
+impl Command for Lazy {
+    fn name(&self) -> &str {
+        "lazy"
+    }
+
+    fn description(&self) -> &str {
+        "Defer running a closure until its output is read."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure runs when the first row of the returned stream is read, with the
+pipeline input as its input. If the stream is never read, for example because an
+earlier `first` already got enough rows or the stream is dropped, the closure
+never runs at all.
+
+Assigning the stream to a variable reads it, so to define a lazy dataset that can
+be used more than once, wrap it in a custom command:
+    def big-files [] { lazy { ls **/* | where size > 100mb } }"#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("lazy")
+            .input_output_types(vec![(Type::Any, Type::list(Type::Any))])
+            .required(
+                "closure",
+                SyntaxShape::Closure(None),
+                "The closure to run once its output is read.",
+            )
+            .category(Category::Core)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["defer", "delay", "thunk"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let closure = ClosureEval::new(engine_state, stack, closure);
+
+        let stream = LazyStream::Pending {
+            closure,
+            input,
+            head,
+        };
+        Ok(ListStream::new(stream, head, engine_state.signals().clone()).into())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Run a closure once its output is read.",
+                example: "lazy { [1 2 3] } | collect",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_int(2),
+                    Value::test_int(3),
+                ])),
+            },
+            Example {
+                description: "Pass the pipeline input to the deferred closure.",
+                example: "'abc' | lazy { $in + 'def' } | collect",
+                result: Some(Value::test_list(vec![Value::test_string("abcdef")])),
+            },
+            Example {
+                description: "Define a dataset that is only loaded when it's used.",
+                example: "def big-files [] { lazy { ls **/* | where size > 100mb } }; big-files | first 5",
+                result: None,
+            },
+        ]
+    }
+}
+
+enum LazyStream {
+    Pending {
+        closure: ClosureEval,
+        input: PipelineData,
+        head: Span,
+    },
+    Running(PipelineIterator),
+    Done,
+}
+
+impl Iterator for LazyStream {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        *self = match std::mem::replace(self, Self::Done) {
+            Self::Pending {
+                mut closure,
+                input,
+                head,
+            } => match closure.run_with_input(input) {
+                Ok(output) => Self::Running(output.into_iter()),
+                Err(err) => return Some(Value::error(err, head)),
+            },
+            stream => stream,
+        };
+
+        match self {
+            Self::Running(values) => values.next(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Collect;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples_with_commands;
+
+        test_examples_with_commands(Lazy {}, &[&Collect {}])
+    }
+}
@@ -5,6 +5,7 @@ mod collect;
 mod const_;
 mod continue_;
 mod def;
+mod defer;
 mod describe;
 mod do_;
 mod echo;
@@ -23,6 +24,7 @@ mod hide;
 mod hide_env;
 mod if_;
 mod ignore;
+mod lazy;
 mod let_;
 mod loop_;
 mod match_;
@@ -30,6 +32,7 @@ mod module;
 mod mut_;
 pub(crate) mod overlay;
 mod return_;
+mod run_command;
 mod scope;
 mod try_;
 mod use_;
@@ -43,6 +46,7 @@ pub use collect::Collect;
 pub use const_::Const;
 pub use continue_::Continue;
 pub use def::Def;
+pub use defer::Defer;
 pub use describe::Describe;
 pub use do_::Do;
 pub use echo::Echo;
@@ -61,6 +65,7 @@ pub use hide::Hide;
 pub use hide_env::HideEnv;
 pub use if_::If;
 pub use ignore::Ignore;
+pub use lazy::Lazy;
 pub use let_::Let;
 pub use loop_::Loop;
 pub use match_::Match;
@@ -68,6 +73,7 @@ pub use module::Module;
 pub use mut_::Mut;
 pub use overlay::*;
 pub use return_::Return;
+pub use run_command::RunCommand;
 pub use scope::*;
 pub use try_::Try;
 pub use use_::Use;
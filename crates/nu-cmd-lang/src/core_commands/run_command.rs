@@ -0,0 +1,318 @@
+use nu_engine::{command_prelude::*, eval_decl_call};
+use nu_protocol::{
+    debugger::{WithDebug, WithoutDebug},
+    engine::StateWorkingSet,
+    eval_const::eval_constant,
+    ir,
+};
+
+#[derive(Clone)]
+pub struct RunCommand;
+
+impl Command for RunCommand {
+    fn name(&self) -> &str {
+        "run-command"
+    }
+
+    fn description(&self) -> &str {
+        "Run a command by name, providing it with the pipeline input."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The command is looked up when `run-command` runs, so its name can come from a variable, like
+a key of a dispatch table. Parser keywords like `def` or `let` can't be run this way.
+
+The arguments are checked against the command's signature like they would be in a direct call.
+Strings that start with `-` are passed as flags when the command has a matching flag, so
+`run-command greet --loud Bob` runs `greet --loud Bob`. A flag's value is the next argument, or
+follows `=`, like `--name=Bob`."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("run-command")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .required(
+                "command",
+                SyntaxShape::String,
+                "The name of the command to run.",
+            )
+            .rest(
+                "args",
+                SyntaxShape::Any,
+                "Arguments and flags for the command.",
+            )
+            .allows_unknown_args()
+            .category(Category::Core)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["dispatch", "dynamic", "call", "invoke"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
+
+        // Overlays hidden at runtime are only hidden in the stack, so the command is looked up
+        // without them, like a direct call in the same scope would be
+        let hidden_overlays = engine_state
+            .active_overlay_names(&[])
+            .filter(|overlay| {
+                !stack
+                    .active_overlays
+                    .iter()
+                    .any(|active| active.as_bytes() == *overlay)
+            })
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>();
+        let decl_id = engine_state
+            .find_decl(name.item.as_bytes(), &hidden_overlays)
+            .ok_or(ShellError::CommandNotFound { span: name.span })?;
+        let decl = engine_state.get_decl(decl_id);
+
+        if decl.is_keyword() || decl.is_alias() {
+            return Err(ShellError::IncorrectValue {
+                msg: format!(
+                    "`{}` is a parser keyword or alias, which can't be run by name",
+                    name.item
+                ),
+                val_span: name.span,
+                call_span: head,
+            });
+        }
+
+        // The arguments are pushed onto the stack as they're checked, and have to be removed from
+        // it afterwards, even if they turn out to be invalid
+        let args_base = stack.arguments.get_base();
+        let mut builder = ir::Call::build(decl_id, name.span);
+        let result = build_call(
+            engine_state,
+            &mut builder,
+            stack,
+            &decl.signature(),
+            &name,
+            args,
+            head,
+        )
+        .and_then(|()| {
+            let call = builder.finish();
+            if engine_state.is_debugging() {
+                eval_decl_call::<WithDebug>(engine_state, stack, &call, input)
+            } else {
+                eval_decl_call::<WithoutDebug>(engine_state, stack, &call, input)
+            }
+        });
+        stack.arguments.leave_frame(args_base);
+
+        result
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Run a command by name.",
+                example: "let cmd = 'echo'; run-command $cmd hello",
+                result: Some(Value::test_string("hello")),
+            },
+            Example {
+                description: "Pass arguments and flags to the command.",
+                example: "'hello world' | run-command 'str replace' --all o '0'",
+                result: None,
+            },
+            Example {
+                description: "Dispatch subcommands of a script from a table of command names.",
+                example: r#"def "main add" [a: int, b: int] { $a + $b }
+    def "main sub" [a: int, b: int] { $a - $b }
+    def main [op: string, ...args: int] { run-command $"main ($op)" ...$args }"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Push the arguments of a call to the command with `signature` onto the stack, checking them
+/// against it.
+fn build_call(
+    engine_state: &EngineState,
+    builder: &mut ir::CallBuilder,
+    stack: &mut Stack,
+    signature: &Signature,
+    name: &Spanned<String>,
+    args: Vec<Value>,
+    head: Span,
+) -> Result<(), ShellError> {
+    let mut args = args.into_iter();
+    let mut positional = 0;
+
+    while let Some(arg) = args.next() {
+        let span = arg.span();
+
+        if let Some((flag_name, inline_value)) = arg.as_str().ok().and_then(split_flag) {
+            let flag = match flag_name {
+                FlagName::Long(long) => signature.get_long_flag(long),
+                FlagName::Short(short) => signature.get_short_flag(short),
+            };
+
+            match flag {
+                Some(flag) => {
+                    let short = flag.short.map(String::from).unwrap_or_default();
+                    match &flag.arg {
+                        None if inline_value.is_some() => {
+                            return Err(ShellError::IncorrectValue {
+                                msg: format!(
+                                    "--{} is a switch, which doesn't take a value",
+                                    flag.long
+                                ),
+                                val_span: span,
+                                call_span: head,
+                            });
+                        }
+                        None => {
+                            builder.add_flag(stack, &flag.long, short, span);
+                        }
+                        Some(shape) => {
+                            let value = match inline_value {
+                                Some(value) => {
+                                    parse_inline_value(engine_state, value, shape, span)?
+                                }
+                                None => {
+                                    args.next().ok_or_else(|| ShellError::MissingParameter {
+                                        param_name: format!("value for --{}", flag.long),
+                                        span,
+                                    })?
+                                }
+                            };
+                            check_argument(&value, shape)?;
+                            builder.add_named(stack, &flag.long, short, span, value);
+                        }
+                    }
+                    continue;
+                }
+                None if !signature.allows_unknown_args => {
+                    return Err(ShellError::IncorrectValue {
+                        msg: format!("`{}` doesn't have this flag", name.item),
+                        val_span: span,
+                        call_span: head,
+                    });
+                }
+                None => (),
+            }
+        }
+
+        match signature.get_positional(positional) {
+            Some(param) => check_argument(&arg, &param.shape)?,
+            None if signature.allows_unknown_args => (),
+            None => {
+                return Err(ShellError::IncorrectValue {
+                    msg: format!(
+                        "`{}` takes at most {} positional arguments",
+                        name.item,
+                        signature.num_positionals()
+                    ),
+                    val_span: span,
+                    call_span: head,
+                });
+            }
+        }
+        builder.add_positional(stack, span, arg);
+        positional += 1;
+    }
+
+    if let Some(missing) = signature.required_positional.get(positional) {
+        return Err(ShellError::MissingParameter {
+            param_name: missing.name.clone(),
+            span: head,
+        });
+    }
+
+    Ok(())
+}
+
+enum FlagName<'a> {
+    Long(&'a str),
+    Short(char),
+}
+
+/// Split a flag like `--name=value` or `-n` into its name and value, if the string is one.
+fn split_flag(arg: &str) -> Option<(FlagName<'_>, Option<&str>)> {
+    let (flag, value) = match arg.split_once('=') {
+        Some((flag, value)) => (flag, Some(value)),
+        None => (arg, None),
+    };
+
+    if let Some(long) = flag.strip_prefix("--") {
+        (!long.is_empty()).then_some((FlagName::Long(long), value))
+    } else if let Some(short) = flag.strip_prefix('-') {
+        let mut chars = short.chars();
+        match (chars.next(), chars.next()) {
+            // Negative numbers aren't flags
+            (Some(short), None) if !short.is_ascii_digit() => Some((FlagName::Short(short), value)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Parse the value of a flag given inline, like `--count=3`, with the flag's shape, like the
+/// parser would in a direct call. Values of flags that take strings are kept as they are.
+fn parse_inline_value(
+    engine_state: &EngineState,
+    value: &str,
+    shape: &SyntaxShape,
+    span: Span,
+) -> Result<Value, ShellError> {
+    if matches!(shape.to_type(), Type::String | Type::Glob) {
+        return Ok(Value::string(value, span));
+    }
+
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let file_id = working_set.add_file("run-command".into(), value.as_bytes());
+    let file_span = working_set.get_span_for_file(file_id);
+    let expr = nu_parser::parse_value(&mut working_set, file_span, shape);
+    let cant_convert = |help: String| ShellError::CantConvert {
+        to_type: shape.to_type().to_string(),
+        from_type: format!("`{value}`"),
+        span,
+        help: Some(help),
+    };
+    if let Some(err) = working_set.parse_errors.first() {
+        return Err(cant_convert(err.to_string()));
+    }
+    eval_constant(&working_set, &expr)
+        .map(|value| value.with_span(span))
+        .map_err(|err| cant_convert(err.to_string()))
+}
+
+fn check_argument(value: &Value, shape: &SyntaxShape) -> Result<(), ShellError> {
+    let ty = shape.to_type();
+    if value.is_subtype_of(&ty) {
+        Ok(())
+    } else {
+        Err(ShellError::CantConvert {
+            to_type: ty.to_string(),
+            from_type: value.get_type().to_string(),
+            span: value.span(),
+            help: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(RunCommand {})
+    }
+}
@@ -61,7 +61,19 @@ impl Command for Try {
     }
 
     fn extra_description(&self) -> &str {
-        r#"This command is a parser keyword. For details, check:
+        r#"The catch closure is given a record describing the error, with these fields:
+  * `msg: string` - the error message
+  * `code: string` - the error code, like `nu::shell::division_by_zero`
+  * `debug: string` - the error in its debug representation
+  * `raw: error` - the error itself
+  * `rendered: string` - the error as it would be shown in the terminal
+  * `json: string` - the error serialized as JSON
+  * `exit_code: int` - the exit code, if the error came from an external command
+
+Error codes are stable, so to handle specific errors, match on `code` rather than on the message.
+Errors created with `error make` have the code `nu::shell::error`, unless they set their own.
+
+This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"#
     }
 
@@ -106,6 +118,11 @@ impl Command for Try {
                 example: "try { 1 / 0 } catch { |err| print $err.msg } finally { 'clean' }",
                 result: None,
             },
+            Example {
+                description: "Handle a specific kind of error by matching on its code.",
+                example: r#"try { 1 / 0 } catch { |err| match $err.code { "nu::shell::division_by_zero" => 0, _ => null } }"#,
+                result: Some(Value::test_int(0)),
+            },
         ]
     }
 }
@@ -116,8 +133,8 @@ mod test {
 
     #[test]
     fn test_examples() {
-        use crate::test_examples;
+        use crate::{Match, test_examples_with_commands};
 
-        test_examples(Try {})
+        test_examples_with_commands(Try {}, &[&Match {}])
     }
 }
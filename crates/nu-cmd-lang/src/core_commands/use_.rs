@@ -43,6 +43,12 @@ impl Command for Use {
         r#"See `help std` for the standard library module.
 See `help modules` to list all available modules.
 
+A module can also be imported from an http:// or https:// URL, once it's downloaded with
+`http fetch-module`. That keeps it in a cache directory, and records the SHA-256 checksum of its
+contents in a `nu-modules.lock` file in the current directory. The import uses the cached copy
+that matches the lockfile next to the importing script, or in the current directory, and fails
+if there is none. Parsing never downloads anything.
+
 This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"#
     }
@@ -198,6 +204,11 @@ This command is a parser keyword. For details, check:
                 example: r#"module spam { export def foo [] { "foo" }; export def 'foo bar' [] { "baz" } }; use spam ['foo', 'foo bar']; (foo) + (foo bar)"#,
                 result: Some(Value::test_string("foobaz")),
             },
+            Example {
+                description: "Import a module from a URL, after fetching it with `http fetch-module https://example.com/module.nu`.",
+                example: r#"use https://example.com/module.nu"#,
+                result: None,
+            },
         ]
     }
 }
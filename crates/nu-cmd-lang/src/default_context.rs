@@ -25,12 +25,14 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             AttrCompleteExternal,
             AttrDeprecated,
             AttrExample,
+            AttrMemo,
             AttrSearchTerms,
             Break,
             Collect,
             Const,
             Continue,
             Def,
+            Defer,
             Describe,
             Do,
             Echo,
@@ -49,6 +51,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             HideEnv,
             If,
             Ignore,
+            Lazy,
             Overlay,
             OverlayUse,
             OverlayList,
@@ -60,6 +63,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             Module,
             Mut,
             Return,
+            RunCommand,
             Scope,
             ScopeAliases,
             ScopeCommands,
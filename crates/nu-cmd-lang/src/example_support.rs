@@ -236,6 +236,9 @@ impl std::fmt::Debug for DebuggableValue<'_> {
                 let duration = std::time::Duration::from_nanos(*val as u64);
                 write!(f, "Duration({duration:?})")
             }
+            Value::Rate { val, .. } => {
+                write!(f, "Rate({val:?})")
+            }
             Value::Date { val, .. } => {
                 write!(f, "Date({val:?})")
             }
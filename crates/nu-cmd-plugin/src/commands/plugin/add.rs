@@ -1,10 +1,7 @@
-use crate::util::{get_plugin_dirs, modify_plugin_file};
+use crate::util::{get_plugin_dirs, get_plugin_registry_item, modify_plugin_file};
 use nu_engine::command_prelude::*;
-use nu_plugin_engine::{GetPlugin, PersistentPlugin};
-use nu_protocol::{
-    PluginGcConfig, PluginIdentity, PluginRegistryItem, RegisteredPlugin, shell_error::io::IoError,
-};
-use std::{path::PathBuf, sync::Arc};
+use nu_protocol::{PluginIdentity, shell_error::io::IoError};
+use std::path::PathBuf;
 
 #[derive(Clone)]
 pub struct PluginAdd;
@@ -30,6 +27,12 @@ impl Command for PluginAdd {
                 "Use an additional shell program (cmd, sh, python, etc.) to run the plugin.",
                 Some('s'),
             )
+            .named(
+                "address",
+                SyntaxShape::String,
+                "Connect to a plugin listening on this address (`tcp://<host>:<port>` or `unix://<path>`) instead of running it.",
+                None,
+            )
             .required(
                 "filename",
                 SyntaxShape::String,
@@ -50,6 +53,12 @@ that.
 Instead, it runs the plugin to get its command signatures, and then edits the
 plugin registry file (by default, `$nu.plugin-path`). The changes will be
 apparent the next time `nu` is next launched with that plugin registry file.
+
+With --address, the plugin is expected to be already running somewhere else,
+started with `--listen <address>`, and nushell connects to it instead of
+running it. The filename is then only used for the name of the plugin. The
+connection starts with the secret in `$env.NU_PLUGIN_SECRET`, which has to
+match the one the plugin was started with.
 "#
         .trim()
     }
@@ -70,6 +79,16 @@ apparent the next time `nu` is next launched with that plugin registry file.
                 description: "Run the `nu_plugin_polars` plugin from the current directory or $env.NU_PLUGIN_DIRS, and install its signatures to the \"polars.msgpackz\" plugin registry file.",
                 result: None,
             },
+            Example {
+                example: "plugin add nu_plugin_example.wasm",
+                description: "Add a plugin compiled to WebAssembly for WASI, which is run with `wasmtime`, or with the command line in `$env.NU_WASM_RUNTIME`.",
+                result: None,
+            },
+            Example {
+                example: "plugin add --address tcp://gpu-box:4000 nu_plugin_gpu",
+                description: "Add a plugin running on another host, started there with `NU_PLUGIN_SECRET=<secret> nu_plugin_gpu --listen tcp://0.0.0.0:4000 --allow-remote`.",
+                result: None,
+            },
         ]
     }
 
@@ -82,19 +101,25 @@ apparent the next time `nu` is next launched with that plugin registry file.
     ) -> Result<PipelineData, ShellError> {
         let filename: Spanned<String> = call.req(engine_state, stack, 0)?;
         let shell: Option<Spanned<String>> = call.get_flag(engine_state, stack, "shell")?;
+        let address: Option<String> = call.get_flag(engine_state, stack, "address")?;
         let cwd = engine_state.cwd(Some(stack))?;
 
-        // Check the current directory, or fall back to NU_PLUGIN_DIRS
-        let filename_expanded = nu_path::locate_in_dirs(&filename.item, &cwd, || {
-            get_plugin_dirs(engine_state, stack)
-        })
-        .map_err(|err| {
-            IoError::new(
-                err.not_found_as(NotFound::File),
-                filename.span,
-                PathBuf::from(filename.item),
-            )
-        })?;
+        let filename_expanded = if address.is_some() {
+            // Remote plugins aren't run from here, so the executable doesn't have to exist
+            nu_path::expand_path_with(&filename.item, &cwd, true)
+        } else {
+            // Check the current directory, or fall back to NU_PLUGIN_DIRS
+            nu_path::locate_in_dirs(&filename.item, &cwd, || {
+                get_plugin_dirs(engine_state, stack)
+            })
+            .map_err(|err| {
+                IoError::new(
+                    err.not_found_as(NotFound::File),
+                    filename.span,
+                    PathBuf::from(&filename.item),
+                )
+            })?
+        };
 
         let shell_expanded = shell
             .as_ref()
@@ -104,34 +129,22 @@ apparent the next time `nu` is next launched with that plugin registry file.
             .transpose()?;
 
         // Parse the plugin filename so it can be used to spawn the plugin
-        let identity = PluginIdentity::new(filename_expanded, shell_expanded).map_err(|_| {
-            ShellError::GenericError {
+        let identity = PluginIdentity::new(filename_expanded, shell_expanded)
+            .map_err(|_| ShellError::GenericError {
                 error: "Plugin filename is invalid".into(),
                 msg: "plugin executable files must start with `nu_plugin_`".into(),
                 span: Some(filename.span),
                 help: None,
                 inner: vec![],
-            }
-        })?;
+            })?
+            .with_address(address);
 
         let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
 
-        // Start the plugin manually, to get the freshest signatures and to not affect engine
-        // state. Provide a GC config that will stop it ASAP
-        let plugin = Arc::new(PersistentPlugin::new(
-            identity,
-            PluginGcConfig {
-                enabled: true,
-                stop_after: 0,
-            },
-        ));
-        let interface = plugin.clone().get_plugin(Some((engine_state, stack)))?;
-        let metadata = interface.get_metadata()?;
-        let commands = interface.get_signature()?;
+        let item = get_plugin_registry_item(engine_state, stack, identity, filename.span)?;
 
         modify_plugin_file(engine_state, stack, call.head, &custom_path, |contents| {
             // Update the file with the received metadata and signatures
-            let item = PluginRegistryItem::new(plugin.identity(), metadata, commands);
             contents.upsert_plugin(item);
             Ok(())
         })?;
@@ -1,6 +1,7 @@
 use itertools::{EitherOrBoth, Itertools};
 use nu_engine::command_prelude::*;
-use nu_protocol::{IntoValue, PluginRegistryItemData};
+use nu_plugin_engine::PersistentPlugin;
+use nu_protocol::{Filesize, IntoValue, PluginGcConfig, PluginGcConfigs, PluginRegistryItemData};
 
 use crate::util::read_plugin_file;
 
@@ -22,9 +23,35 @@ impl Command for PluginList {
                         ("version".into(), Type::String),
                         ("status".into(), Type::String),
                         ("pid".into(), Type::Int),
+                        ("memory".into(), Type::Filesize),
                         ("filename".into(), Type::String),
                         ("shell".into(), Type::String),
                         ("commands".into(), Type::List(Type::String.into())),
+                        (
+                            "gc".into(),
+                            Type::Record(
+                                [
+                                    ("enabled".into(), Type::Bool),
+                                    ("stop_after".into(), Type::Duration),
+                                    ("max_idle_memory".into(), Type::Filesize),
+                                    ("start".into(), Type::String),
+                                ]
+                                .into(),
+                            ),
+                        ),
+                        (
+                            "metrics".into(),
+                            Type::Record(
+                                [
+                                    ("calls".into(), Type::Int),
+                                    ("average_latency".into(), Type::Duration),
+                                    ("bytes_sent".into(), Type::Filesize),
+                                    ("bytes_received".into(), Type::Filesize),
+                                    ("uptime".into(), Type::Duration),
+                                ]
+                                .into(),
+                            ),
+                        ),
                     ]
                     .into(),
                 ),
@@ -45,6 +72,11 @@ impl Command for PluginList {
                 "Show info for plugins from the registry file only.",
                 Some('r'),
             )
+            .switch(
+                "metrics",
+                "Also show calls, latency, bytes transferred and uptime of plugins in the engine.",
+                Some('m'),
+            )
             .category(Category::Plugin)
     }
 
@@ -74,6 +106,16 @@ or the plugin has not been loaded yet, the values of `version`, `filename`,
 `shell`, and `commands` reflect the values in the engine and not the ones in
 the plugin registry file.
 
+The `memory` column contains the memory used by running plugins. The `gc`
+column contains the settings from `$env.config.plugin_gc` that apply to the
+plugin, which control when it is started and stopped.
+
+With `--metrics`, the `metrics` column contains how many calls were made to
+the plugin, the average time until the plugin responded to them, and how many
+bytes were sent to and received from it since it was loaded, as well as how
+long its process has been running. This can help to find plugins that are slow
+or leak memory.
+
 See also: `plugin use`
 "#
         .trim()
@@ -93,6 +135,7 @@ See also: `plugin use`
                     "version" => Value::test_string(env!("CARGO_PKG_VERSION")),
                     "status" => Value::test_string("running"),
                     "pid" => Value::test_int(106480),
+                    "memory" => Value::test_filesize(11_251_712),
                     "filename" => if cfg!(windows) {
                         Value::test_string(r"C:\nu\plugins\nu_plugin_inc.exe")
                     } else {
@@ -100,8 +143,19 @@ See also: `plugin use`
                     },
                     "shell" => Value::test_nothing(),
                     "commands" => Value::test_list(vec![Value::test_string("inc")]),
+                    "gc" => Value::test_record(record! {
+                        "enabled" => Value::test_bool(true),
+                        "stop_after" => Value::test_duration(10_000_000_000),
+                        "max_idle_memory" => Value::test_nothing(),
+                        "start" => Value::test_string("lazy"),
+                    }),
                 })])),
             },
+            Example {
+                example: "plugin list --metrics | select name memory metrics",
+                description: "Show how plugins in the engine have performed.",
+                result: None,
+            },
             Example {
                 example: "ps | where pid in (plugin list).pid",
                 description: "Get process information for running plugins.",
@@ -120,37 +174,87 @@ See also: `plugin use`
         let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
         let engine_mode = call.has_flag(engine_state, stack, "engine")?;
         let registry_mode = call.has_flag(engine_state, stack, "registry")?;
+        let show_metrics = call.has_flag(engine_state, stack, "metrics")?;
+        let gc = &stack.get_config(engine_state).plugin_gc;
 
         let plugins_info = match (engine_mode, registry_mode) {
             // --engine and --registry together is equivalent to the default.
             (false, false) | (true, true) => {
                 if engine_state.plugin_path.is_some() || custom_path.is_some() {
-                    let plugins_in_engine = get_plugins_in_engine(engine_state);
+                    let plugins_in_engine = get_plugins_in_engine(engine_state, gc);
                     let plugins_in_registry =
-                        get_plugins_in_registry(engine_state, stack, call.head, &custom_path)?;
+                        get_plugins_in_registry(engine_state, stack, call.head, &custom_path, gc)?;
                     merge_plugin_info(plugins_in_engine, plugins_in_registry)
                 } else {
                     // Don't produce error when running nu --no-config-file
-                    get_plugins_in_engine(engine_state)
+                    get_plugins_in_engine(engine_state, gc)
                 }
             }
-            (true, false) => get_plugins_in_engine(engine_state),
-            (false, true) => get_plugins_in_registry(engine_state, stack, call.head, &custom_path)?,
+            (true, false) => get_plugins_in_engine(engine_state, gc),
+            (false, true) => {
+                get_plugins_in_registry(engine_state, stack, call.head, &custom_path, gc)?
+            }
         };
 
-        Ok(plugins_info.into_value(call.head).into_pipeline_data())
+        let head = call.head;
+        let rows = plugins_info
+            .into_iter()
+            .map(|info| {
+                let metrics = show_metrics.then(|| get_metrics(engine_state, &info.name, head));
+                let mut row = info.into_value(head);
+                if let (Some(metrics), Value::Record { val, .. }) = (metrics, &mut row) {
+                    val.to_mut().push("metrics", metrics);
+                }
+                row
+            })
+            .collect();
+
+        Ok(Value::list(rows, head).into_pipeline_data())
     }
 }
 
+/// Get the metrics of a plugin in the engine, or `null` if it isn't loaded.
+fn get_metrics(engine_state: &EngineState, name: &str, span: Span) -> Value {
+    let Some(plugin) = engine_state
+        .plugins()
+        .iter()
+        .find(|plugin| plugin.identity().name() == name)
+        .and_then(|plugin| plugin.clone().as_any().downcast::<PersistentPlugin>().ok())
+    else {
+        return Value::nothing(span);
+    };
+
+    let metrics = plugin.metrics();
+    let duration = |duration: std::time::Duration| {
+        Value::duration(i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX), span)
+    };
+    let filesize = |bytes: u64| Value::filesize(i64::try_from(bytes).unwrap_or(i64::MAX), span);
+
+    Value::record(
+        record! {
+            "calls" => Value::int(i64::try_from(metrics.calls()).unwrap_or(i64::MAX), span),
+            "average_latency" => metrics
+                .average_latency()
+                .map_or_else(|| Value::nothing(span), duration),
+            "bytes_sent" => filesize(metrics.bytes_sent()),
+            "bytes_received" => filesize(metrics.bytes_received()),
+            "uptime" => plugin.uptime().map_or_else(|| Value::nothing(span), duration),
+        },
+        span,
+    )
+}
+
 #[derive(Debug, Clone, IntoValue, PartialOrd, Ord, PartialEq, Eq)]
 struct PluginInfo {
     name: String,
     version: Option<String>,
     status: PluginStatus,
     pid: Option<u32>,
+    memory: Option<Filesize>,
     filename: String,
     shell: Option<String>,
     commands: Vec<CommandInfo>,
+    gc: PluginGcConfig,
 }
 
 #[derive(Debug, Clone, IntoValue, PartialOrd, Ord, PartialEq, Eq)]
@@ -170,7 +274,7 @@ enum PluginStatus {
     Invalid,
 }
 
-fn get_plugins_in_engine(engine_state: &EngineState) -> Vec<PluginInfo> {
+fn get_plugins_in_engine(engine_state: &EngineState, gc: &PluginGcConfigs) -> Vec<PluginInfo> {
     // Group plugin decls by plugin identity
     let decls = engine_state.plugin_decls().into_group_map_by(|decl| {
         decl.plugin_identity()
@@ -203,6 +307,13 @@ fn get_plugins_in_engine(engine_state: &EngineState) -> Vec<PluginInfo> {
                     PluginStatus::Loaded
                 },
                 pid: plugin.pid(),
+                memory: plugin
+                    .clone()
+                    .as_any()
+                    .downcast::<PersistentPlugin>()
+                    .ok()
+                    .and_then(|plugin| plugin.memory())
+                    .and_then(|memory| Filesize::try_from(memory).ok()),
                 filename: plugin.identity().filename().to_string_lossy().into_owned(),
                 shell: plugin
                     .identity()
@@ -215,6 +326,7 @@ fn get_plugins_in_engine(engine_state: &EngineState) -> Vec<PluginInfo> {
                         description: desc.clone(),
                     })
                     .collect(),
+                gc: gc.get(plugin.identity().name()).clone(),
             }
         })
         .sorted()
@@ -226,6 +338,7 @@ fn get_plugins_in_registry(
     stack: &mut Stack,
     span: Span,
     custom_path: &Option<Spanned<String>>,
+    gc: &PluginGcConfigs,
 ) -> Result<Vec<PluginInfo>, ShellError> {
     let plugin_file_contents = read_plugin_file(engine_state, stack, span, custom_path)?;
 
@@ -234,10 +347,12 @@ fn get_plugins_in_registry(
         .into_iter()
         .map(|plugin| {
             let mut info = PluginInfo {
+                gc: gc.get(&plugin.name).clone(),
                 name: plugin.name,
                 version: None,
                 status: PluginStatus::Added,
                 pid: None,
+                memory: None,
                 filename: plugin.filename.to_string_lossy().into_owned(),
                 shell: plugin.shell.map(|path| path.to_string_lossy().into_owned()),
                 commands: vec![],
@@ -4,12 +4,14 @@ mod add;
 mod list;
 mod rm;
 mod stop;
+mod update;
 mod use_;
 
 pub use add::PluginAdd;
 pub use list::PluginList;
 pub use rm::PluginRm;
 pub use stop::PluginStop;
+pub use update::PluginUpdate;
 pub use use_::PluginUse;
 
 #[derive(Clone)]
@@ -72,6 +74,11 @@ time.
                 description: "Remove the installed signatures for the `inc` plugin.",
                 result: None,
             },
+            Example {
+                example: "plugin update",
+                description: "Update the installed signatures of all plugins from their executables.",
+                result: None,
+            },
         ]
     }
 }
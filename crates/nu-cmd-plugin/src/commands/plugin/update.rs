@@ -0,0 +1,195 @@
+use crate::util::{
+    canonicalize_possible_filename_arg, get_plugin_dirs, get_plugin_registry_item,
+    modify_plugin_file, read_plugin_file,
+};
+use nu_engine::command_prelude::*;
+use nu_protocol::{PluginIdentity, PluginRegistryItem, PluginRegistryItemData};
+
+#[derive(Clone)]
+pub struct PluginUpdate;
+
+impl Command for PluginUpdate {
+    fn name(&self) -> &str {
+        "plugin update"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("name".into(), Type::String),
+                        ("filename".into(), Type::String),
+                        ("old_version".into(), Type::String),
+                        ("new_version".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )
+            // This matches the option to `nu`
+            .named(
+                "plugin-config",
+                SyntaxShape::Filepath,
+                "Use a plugin registry file other than the one set in `$nu.plugin-path`.",
+                None,
+            )
+            .rest(
+                "names",
+                SyntaxShape::String,
+                "The names, or filenames, of the plugins to update. Default: all plugins.",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn description(&self) -> &str {
+        "Update plugins in the plugin registry file from their executables."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"
+Like `plugin add`, this runs each plugin to get its current version and
+command signatures, and saves them in the plugin registry file (by default,
+`$nu.plugin-path`). Plugins that require a newer version of Nushell are not
+updated.
+
+If the executable of a plugin was moved, it is looked for by its file name in
+the current directory and in $env.NU_PLUGIN_DIRS.
+
+This does not load the new plugin commands into the scope - see `plugin use`
+for that.
+"#
+        .trim()
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["upgrade", "refresh", "signature"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "plugin update",
+                description: "Update all plugins in the plugin registry file.",
+                result: None,
+            },
+            Example {
+                example: "plugin update polars; plugin use polars",
+                description: "Update the `polars` plugin, and load its new commands.",
+                result: None,
+            },
+            Example {
+                example: "plugin update --plugin-config polars.msgpackz polars",
+                description: "Update the `polars` plugin in the \"polars.msgpackz\" plugin registry file.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let names: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
+        let cwd = engine_state.cwd(Some(stack))?;
+
+        let registry = read_plugin_file(engine_state, stack, head, &custom_path)?;
+
+        let plugins = if names.is_empty() {
+            registry.plugins
+        } else {
+            names
+                .iter()
+                .map(|name| {
+                    let filename =
+                        canonicalize_possible_filename_arg(engine_state, stack, &name.item);
+                    registry
+                        .plugins
+                        .iter()
+                        .find(|p| p.name == name.item || p.filename == filename)
+                        .cloned()
+                        .ok_or_else(|| ShellError::GenericError {
+                            error: format!("Failed to update the `{}` plugin", name.item),
+                            msg: "couldn't find a plugin with this name in the registry file"
+                                .into(),
+                            span: Some(name.span),
+                            help: None,
+                            inner: vec![],
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut updated = vec![];
+        for plugin in plugins {
+            // Look for the executable by its file name if it isn't where it was added from. Remote
+            // plugins aren't run from their executable, so there's nothing to look for.
+            let filename = if plugin.address.is_some() || plugin.filename.exists() {
+                plugin.filename.clone()
+            } else {
+                plugin
+                    .filename
+                    .file_name()
+                    .and_then(|file_name| {
+                        nu_path::locate_in_dirs(file_name, &cwd, || {
+                            get_plugin_dirs(engine_state, stack)
+                        })
+                        .ok()
+                    })
+                    .unwrap_or_else(|| plugin.filename.clone())
+            };
+
+            let identity = PluginIdentity::new(filename, plugin.shell.clone())
+                .map_err(|_| ShellError::GenericError {
+                    error: "Invalid plugin filename in plugin registry file".into(),
+                    msg: format!("`{}` can't be updated", plugin.name),
+                    span: Some(head),
+                    help: Some(format!(
+                        "the filename is not a valid nushell plugin: {}",
+                        plugin.filename.display()
+                    )),
+                    inner: vec![],
+                })?
+                .with_address(plugin.address.clone());
+
+            let item = get_plugin_registry_item(engine_state, stack, identity, head)?;
+            updated.push((version(&plugin), item));
+        }
+
+        let rows = updated
+            .iter()
+            .map(|(old_version, item)| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(&item.name, head),
+                        "filename" => Value::string(item.filename.to_string_lossy(), head),
+                        "old_version" => old_version.clone().into_value(head),
+                        "new_version" => version(item).into_value(head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+
+        modify_plugin_file(engine_state, stack, head, &custom_path, |contents| {
+            for (_, item) in updated {
+                contents.upsert_plugin(item);
+            }
+            Ok(())
+        })?;
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+fn version(item: &PluginRegistryItem) -> Option<String> {
+    match &item.data {
+        PluginRegistryItemData::Valid { metadata, .. } => metadata.version.clone(),
+        PluginRegistryItemData::Invalid => None,
+    }
+}
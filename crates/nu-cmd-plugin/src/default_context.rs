@@ -17,6 +17,7 @@ pub fn add_plugin_command_context(mut engine_state: EngineState) -> EngineState
             PluginList,
             PluginRm,
             PluginStop,
+            PluginUpdate,
             PluginUse,
         );
 
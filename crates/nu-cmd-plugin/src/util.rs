@@ -1,6 +1,7 @@
 use nu_engine::command_prelude::*;
+use nu_plugin_engine::{GetPlugin, PersistentPlugin, check_plugin_compatibility};
 use nu_protocol::{
-    PluginRegistryFile,
+    PluginGcConfig, PluginIdentity, PluginRegistryFile, PluginRegistryItem, RegisteredPlugin,
     engine::StateWorkingSet,
     shell_error::{
         self,
@@ -10,6 +11,7 @@ use nu_protocol::{
 use std::{
     fs::{self, File},
     path::PathBuf,
+    sync::Arc,
 };
 
 fn get_plugin_registry_file_path(
@@ -113,6 +115,36 @@ pub(crate) fn modify_plugin_file(
     Ok(())
 }
 
+/// Run the plugin to get its current metadata and signatures for the plugin registry file, checking
+/// that it's compatible with this version of Nushell.
+pub(crate) fn get_plugin_registry_item(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    identity: PluginIdentity,
+    span: Span,
+) -> Result<PluginRegistryItem, ShellError> {
+    // Start the plugin manually, to get the freshest signatures and to not affect engine
+    // state. Provide a GC config that will stop it ASAP
+    let plugin = Arc::new(PersistentPlugin::new(
+        identity,
+        PluginGcConfig {
+            enabled: true,
+            stop_after: 0,
+            ..Default::default()
+        },
+    ));
+    let interface = plugin.clone().get_plugin(Some((engine_state, stack)))?;
+    let metadata = interface.get_metadata()?;
+    check_plugin_compatibility(plugin.identity(), &metadata, Some(span))?;
+    let commands = interface.get_signature()?;
+
+    Ok(PluginRegistryItem::new(
+        plugin.identity(),
+        metadata,
+        commands,
+    ))
+}
+
 pub(crate) fn canonicalize_possible_filename_arg(
     engine_state: &EngineState,
     stack: &Stack,
@@ -47,7 +47,7 @@ pub fn default_shape_color(shape: &str) -> Style {
 pub fn get_shape_color(shape: &str, conf: &Config) -> Style {
     match conf.color_config.get(shape) {
         Some(int_color) => {
-            // Shapes do not use color_config closures, currently.
+            // Closures are only run by the highlighter, with StyleComputer::compute_shape().
             match int_color {
                 Value::Record { .. } => color_record_to_nustyle(int_color),
                 Value::String { val, .. } => lookup_ansi_color_style(val),
@@ -1,4 +1,7 @@
-use crate::{TextStyle, color_record_to_nustyle, lookup_ansi_color_style, text_style::Alignment};
+use crate::{
+    TextStyle, color_record_to_nustyle, default_shape_color, lookup_ansi_color_style,
+    text_style::Alignment,
+};
 use nu_ansi_term::{Color, Style};
 use nu_engine::ClosureEvalOnce;
 use nu_protocol::{
@@ -82,6 +85,19 @@ impl<'a> StyleComputer<'a> {
         }
     }
 
+    // Computes the style of a token on the commandline, with the name of its shape (e.g.
+    // "shape_string"). Closures are given the text of the token. Unlike compute(), shapes
+    // without a style in color_config fall back to their default color.
+    pub fn compute_shape(&self, shape: &str, token: &str) -> Style {
+        match self.map.get(shape) {
+            Some(ComputableStyle::Static(style)) => *style,
+            Some(ComputableStyle::Closure(_, span)) => {
+                self.compute(shape, &Value::string(token, *span))
+            }
+            None => default_shape_color(shape),
+        }
+    }
+
     // Used only by the `table` command.
     pub fn style_primitive(&self, value: &Value) -> TextStyle {
         use Alignment::*;
@@ -91,6 +107,7 @@ impl<'a> StyleComputer<'a> {
             Value::Int { .. } => TextStyle::with_style(Right, s),
             Value::Filesize { .. } => TextStyle::with_style(Right, s),
             Value::Duration { .. } => TextStyle::with_style(Right, s),
+            Value::Rate { .. } => TextStyle::with_style(Right, s),
             Value::Date { .. } => TextStyle::with_style(Left, s),
             Value::Range { .. } => TextStyle::with_style(Left, s),
             Value::Float { .. } => TextStyle::with_style(Right, s),
@@ -207,6 +224,25 @@ fn test_computable_style_static() {
     );
 }
 
+#[test]
+fn test_computable_style_shape_fallback() {
+    let style = Style::default().italic();
+    let dummy_engine_state = EngineState::new();
+    let dummy_stack = Stack::new();
+    let style_computer = StyleComputer::new(
+        &dummy_engine_state,
+        &dummy_stack,
+        [("shape_flag".into(), ComputableStyle::Static(style))]
+            .into_iter()
+            .collect(),
+    );
+    assert_eq!(style_computer.compute_shape("shape_flag", "--force"), style);
+    assert_eq!(
+        style_computer.compute_shape("shape_int", "42"),
+        default_shape_color("shape_int")
+    );
+}
+
 // Because each closure currently runs in a separate environment, checks that the closures have run
 // must use the filesystem.
 #[test]
@@ -1,8 +1,9 @@
 use super::hashable_value::HashableValue;
 use itertools::Itertools;
 use nu_engine::command_prelude::*;
+use nu_protocol::{FloatRange, Range};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Bound};
 
 #[derive(Clone)]
 pub struct Histogram;
@@ -12,6 +13,17 @@ enum PercentageCalcMethod {
     Relative,
 }
 
+// How to group numbers into bins.
+enum Bins {
+    // This many bins of the same width, over the range or from the smallest to the largest number
+    Count {
+        count: usize,
+        range: Option<(f64, f64)>,
+    },
+    // The edges of the bins, in increasing order
+    Edges(Vec<Value>),
+}
+
 impl Command for Histogram {
     fn name(&self) -> &str {
         "histogram"
@@ -40,6 +52,21 @@ impl Command for Histogram {
                     )
                     .completion(Completion::new_list(&["normalize", "relative"])),
             )
+            .named(
+                "bins",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::Int,
+                    SyntaxShape::List(Box::new(SyntaxShape::Number)),
+                ]),
+                "Count numbers in this many bins of the same width, or in the bins between the numbers of a list of edges.",
+                Some('b'),
+            )
+            .named(
+                "range",
+                SyntaxShape::Range,
+                "The range of numbers to split into --bins, instead of the smallest to the largest number.",
+                Some('r'),
+            )
             .category(Category::Chart)
     }
 
@@ -47,6 +74,14 @@ impl Command for Histogram {
         "Creates a new table with a histogram based on the column name passed in."
     }
 
+    fn extra_description(&self) -> &str {
+        "Without --bins or --range, every distinct value gets its own row, sorted by count. With \
+them, the numbers are counted in bins that include their start and exclude their end, except the \
+last bin, which includes its end too. The rows are the bins in increasing order, with their bounds \
+in the start and end columns, and numbers outside of the bins are not counted. --range alone splits \
+it into 10 bins."
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -80,6 +115,33 @@ impl Command for Histogram {
                     }),
                 ])),
             },
+            Example {
+                description: "Compute a histogram of numbers in 2 bins of the same width",
+                example: "[1 2 3 4 4] | histogram --bins 2 --range 0..4",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "start" =>      Value::test_float(0.0),
+                        "end" =>        Value::test_float(2.0),
+                        "count" =>      Value::test_int(1),
+                        "quantile" =>   Value::test_float(0.2),
+                        "percentage" => Value::test_string("20.00%"),
+                        "frequency" =>  Value::test_string("********************"),
+                    }),
+                    Value::test_record(record! {
+                        "start" =>      Value::test_float(2.0),
+                        "end" =>        Value::test_float(4.0),
+                        "count" =>      Value::test_int(4),
+                        "quantile" =>   Value::test_float(0.8),
+                        "percentage" => Value::test_string("80.00%"),
+                        "frequency" =>  Value::test_string("********************************************************************************"),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Compute a histogram of file sizes with explicit bin edges",
+                example: "ls | get size | into int | histogram --bins [0 1000 10000 100000]",
+                result: None,
+            },
             Example {
                 description: "Compute a histogram for a list of numbers, and percentage is based \
                               on the maximum value",
@@ -101,7 +163,8 @@ impl Command for Histogram {
         let frequency_name_arg = call.opt::<Spanned<String>>(engine_state, stack, 1)?;
         let frequency_column_name = match frequency_name_arg {
             Some(inner) => {
-                let forbidden_column_names = ["value", "count", "quantile", "percentage"];
+                let forbidden_column_names =
+                    ["value", "count", "quantile", "percentage", "start", "end"];
                 if forbidden_column_names.contains(&inner.item.as_str()) {
                     return Err(ShellError::TypeMismatch {
                         err_message: format!(
@@ -138,8 +201,23 @@ impl Command for Histogram {
         };
 
         let span = call.head;
+        let bins = bins(
+            call.get_flag(engine_state, stack, "bins")?,
+            call.get_flag(engine_state, stack, "range")?,
+        )?;
         let data_as_value = input.into_value(span)?;
         let value_span = data_as_value.span();
+        if let Some(bins) = bins {
+            return run_binned_histogram(
+                data_as_value.into_list()?,
+                column_name,
+                frequency_column_name,
+                calc_method,
+                bins,
+                span,
+                value_span,
+            );
+        }
         // `input` is not a list, here we can return an error.
         run_histogram(
             data_as_value.into_list()?,
@@ -233,6 +311,201 @@ fn run_histogram(
     ))
 }
 
+fn bins(bins: Option<Value>, range: Option<Spanned<Range>>) -> Result<Option<Bins>, ShellError> {
+    let range = match range {
+        Some(range) => {
+            let range_span = range.span;
+            let range = FloatRange::from(range.item);
+            match range.end() {
+                Bound::Included(end) | Bound::Excluded(end) if end > range.start() => {
+                    Some((range.start(), end))
+                }
+                end => {
+                    return Err(ShellError::InvalidRange {
+                        left_flank: range.start().to_string(),
+                        right_flank: match end {
+                            Bound::Included(end) | Bound::Excluded(end) => end.to_string(),
+                            Bound::Unbounded => "".into(),
+                        },
+                        span: range_span,
+                    });
+                }
+            }
+        }
+        None => None,
+    };
+
+    match bins {
+        None if range.is_none() => Ok(None),
+        None => Ok(Some(Bins::Count { count: 10, range })),
+        Some(Value::Int { val, internal_span }) => match usize::try_from(val) {
+            Ok(count) if count > 0 => Ok(Some(Bins::Count { count, range })),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "The number of bins has to be positive".into(),
+                val_span: internal_span,
+                call_span: internal_span,
+            }),
+        },
+        Some(Value::List {
+            vals,
+            internal_span,
+        }) => {
+            if let Some((start, end)) = range {
+                return Err(ShellError::IncompatibleParametersSingle {
+                    msg: format!(
+                        "--range {start}..{end} can only be used with a number of bins, not with bin edges"
+                    ),
+                    span: internal_span,
+                });
+            }
+            let increasing = vals
+                .iter()
+                .map(Value::coerce_float)
+                .collect::<Result<Vec<_>, _>>()?
+                .windows(2)
+                .all(|edges| edges[0] < edges[1]);
+            if vals.len() < 2 || !increasing {
+                return Err(ShellError::IncorrectValue {
+                    msg: "The bin edges have to be at least 2 increasing numbers".into(),
+                    val_span: internal_span,
+                    call_span: internal_span,
+                });
+            }
+            Ok(Some(Bins::Edges(vals)))
+        }
+        Some(other) => Err(ShellError::CantConvert {
+            to_type: "int or list<number>".into(),
+            from_type: other.get_type().to_string(),
+            span: other.span(),
+            help: None,
+        }),
+    }
+}
+
+fn run_binned_histogram(
+    values: Vec<Value>,
+    column_name: Option<Spanned<String>>,
+    freq_column: String,
+    calc_method: PercentageCalcMethod,
+    bins: Bins,
+    head_span: Span,
+    list_span: Span,
+) -> Result<PipelineData, ShellError> {
+    let mut numbers = vec![];
+    for v in values {
+        let v = match (v, &column_name) {
+            // Propagate existing errors.
+            (Value::Error { error, .. }, _) => return Err(*error),
+            (v, None) => v,
+            // Like without bins, skip the rows that are not records or don't have the column.
+            (Value::Record { val, .. }, Some(col)) => match val.get(&col.item) {
+                Some(v) => v.clone(),
+                None => continue,
+            },
+            (_, Some(_)) => continue,
+        };
+        let number = v.coerce_float().map_err(|_| ShellError::UnsupportedInput {
+            msg: "Only numbers can be counted in bins.".to_string(),
+            input: format!("input type: {}", v.get_type()),
+            msg_span: head_span,
+            input_span: v.span(),
+        })?;
+        numbers.push(number);
+    }
+
+    if numbers.is_empty()
+        && let Some(col) = &column_name
+    {
+        return Err(ShellError::CantFindColumn {
+            col_name: col.item.clone(),
+            span: Some(head_span),
+            src_span: list_span,
+        });
+    }
+
+    let edges = match bins {
+        Bins::Edges(edges) => edges,
+        Bins::Count { count, range } => {
+            let (start, end) = range.unwrap_or_else(|| {
+                numbers
+                    .iter()
+                    .filter(|n| n.is_finite())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &n| {
+                        (min.min(n), max.max(n))
+                    })
+            });
+            let (start, end) = if start.is_finite() && end > start {
+                (start, end)
+            } else if start.is_finite() {
+                // all the numbers are the same
+                (start - 0.5, start + 0.5)
+            } else {
+                // no numbers at all
+                (0.0, 1.0)
+            };
+            let width = (end - start) / count as f64;
+            (0..=count)
+                .map(|i| {
+                    let edge = if i == count {
+                        end
+                    } else {
+                        start + width * i as f64
+                    };
+                    Value::float(edge, head_span)
+                })
+                .collect()
+        }
+    };
+
+    let bounds = edges
+        .iter()
+        .map(Value::coerce_float)
+        .collect::<Result<Vec<_>, _>>()?;
+    let (first, last) = (bounds[0], bounds[bounds.len() - 1]);
+    let mut counts = vec![0_i64; bounds.len() - 1];
+    for n in numbers {
+        // NaN and infinities aren't in any bin, and NaN would compare false with every edge
+        if !n.is_finite() || n < first || n > last {
+            continue;
+        }
+        let bin = bounds.partition_point(|&edge| edge <= n).min(counts.len());
+        counts[bin - 1] += 1;
+    }
+
+    let total_cnt: i64 = counts.iter().sum();
+    let max_cnt = counts.iter().copied().max().unwrap_or(0);
+    const MAX_FREQ_COUNT: f64 = 100.0;
+    let result = edges
+        .iter()
+        .tuple_windows()
+        .zip(counts)
+        .map(|((start, end), count)| {
+            let quantile = match calc_method {
+                PercentageCalcMethod::Normalize if total_cnt > 0 => count as f64 / total_cnt as f64,
+                PercentageCalcMethod::Relative if max_cnt > 0 => count as f64 / max_cnt as f64,
+                _ => 0.0,
+            };
+
+            let percentage = format!("{:.2}%", quantile * 100_f64);
+            let freq = "*".repeat((MAX_FREQ_COUNT * quantile).floor() as usize);
+
+            Value::record(
+                record! {
+                    "start" => start.clone().with_span(head_span),
+                    "end" => end.clone().with_span(head_span),
+                    "count" => Value::int(count, head_span),
+                    "quantile" => Value::float(quantile, head_span),
+                    "percentage" => Value::string(percentage, head_span),
+                    freq_column.as_str() => Value::string(freq, head_span),
+                },
+                head_span,
+            )
+        })
+        .collect();
+
+    Ok(Value::list(result, head_span).into_pipeline_data())
+}
+
 fn histogram_impl(
     inputs: Vec<HashableValue>,
     value_column_name: &str,
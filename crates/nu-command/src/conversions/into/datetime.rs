@@ -1,8 +1,12 @@
-use crate::{generate_strftime_list, parse_date_from_string};
+use crate::{
+    COMMON_DATE_FORMATS, delocalize_date_names, generate_strftime_list, locale_from_name,
+    parse_date_from_string, parse_date_from_string_localized,
+};
 use chrono::{
-    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    DateTime, Datelike, FixedOffset, Local, Locale, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
     Timelike, Utc,
 };
+use chrono_tz::Tz;
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
 
@@ -24,6 +28,7 @@ const ALLOWED_COLUMNS: [&str; 10] = [
 struct Arguments {
     zone_options: Option<Spanned<Zone>>,
     format_options: Option<Spanned<DatetimeFormat>>,
+    locale: Option<Locale>,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -40,6 +45,7 @@ enum Zone {
     Local,
     East(u8),
     West(u8),
+    Named(Tz),
     Error, // we want Nushell to cast it instead of Rust
 }
 
@@ -61,7 +67,7 @@ impl Zone {
         match s.to_ascii_lowercase().as_str() {
             "utc" | "u" => Self::Utc,
             "local" | "l" => Self::Local,
-            _ => Self::Error,
+            _ => s.parse().map(Self::Named).unwrap_or(Self::Error),
         }
     }
 }
@@ -99,7 +105,7 @@ impl Command for IntoDatetime {
                     .short('z')
                     .arg(SyntaxShape::String)
                     .desc(
-                        "Specify timezone if the input is a Unix timestamp. Valid options: 'UTC' ('u') or 'LOCAL' ('l').",
+                        "Specify timezone if the input is a Unix timestamp. Valid options: 'UTC' ('u'), 'LOCAL' ('l') or a time zone name like 'Europe/Berlin'.",
                     )
                     .completion(Completion::new_list(Zone::OPTIONS)),
             )
@@ -109,11 +115,18 @@ impl Command for IntoDatetime {
                 "Specify timezone by offset from UTC if the input is a Unix timestamp, like '+8', '-4'.",
                 Some('o'),
             )
+            .param(
+                Flag::new("format")
+                    .short('f')
+                    .arg(SyntaxShape::String)
+                    .desc("Specify expected format of INPUT string to parse to datetime. Use --list to see options.")
+                    .completion(Completion::new_list(COMMON_DATE_FORMATS)),
+            )
             .named(
-                "format",
+                "locale",
                 SyntaxShape::String,
-                "Specify expected format of INPUT string to parse to datetime. Use --list to see options.",
-                Some('f'),
+                "The locale of the month and weekday names in the input, and of the order of the day and month, like de-DE.",
+                None,
             )
             .switch(
                 "list",
@@ -163,9 +176,15 @@ impl Command for IntoDatetime {
                     span: fmt.span,
                 });
 
+            let locale = match call.get_flag(engine_state, stack, "locale")? {
+                Some(name) => Some(locale_from_name(&name)?),
+                None => None,
+            };
+
             let args = Arguments {
                 zone_options,
                 format_options,
+                locale,
                 cell_paths,
             };
             operate(action, args, input, call.head, engine_state.signals())
@@ -226,6 +245,12 @@ impl Command for IntoDatetime {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "Convert a date with a French month name, and the day before the month.",
+                example: "'3 févr. 2021 14:30 +0000' | into datetime --locale fr-FR",
+                #[allow(clippy::inconsistent_digit_grouping)]
+                result: example_result_1(1612362600_000000000),
+            },
             Example {
                 description: "Convert nanosecond-precision unix timestamp to a datetime with \
                               offset from UTC.",
@@ -233,6 +258,13 @@ impl Command for IntoDatetime {
                 #[allow(clippy::inconsistent_digit_grouping)]
                 result: example_result_1(1614434140_123456789),
             },
+            Example {
+                description: "Convert nanosecond-precision unix timestamp to a datetime in a \
+                              named time zone.",
+                example: "1614434140123456789 | into datetime --timezone Europe/Berlin",
+                #[allow(clippy::inconsistent_digit_grouping)]
+                result: example_result_1(1614434140_123456789),
+            },
             Example {
                 description: "Convert standard (seconds) unix timestamp to a UTC datetime.",
                 example: "1614434140 | into datetime -f '%s'",
@@ -295,6 +327,10 @@ struct DatetimeFormat(String);
 fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     let timezone = &args.zone_options;
     let dateformat = &args.format_options;
+    let parse_date = |val: &str, span: Span| match args.locale {
+        Some(locale) => parse_date_from_string_localized(val, span, locale),
+        None => parse_date_from_string(val, span),
+    };
 
     // noop if the input is already a datetime
     if let Value::Date { .. } = input {
@@ -334,7 +370,7 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     if matches!(input, Value::String { .. }) && dateformat.is_none() {
         let span = input.span();
         if let Ok(input_val) = input.coerce_str()
-            && let Ok(date) = parse_date_from_string(&input_val, span)
+            && let Ok(date) = parse_date(&input_val, span)
         {
             return Value::date(date, span);
         }
@@ -402,6 +438,10 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
                         *span,
                     ),
                 },
+                Zone::Named(tz) => {
+                    let dt = tz.timestamp_nanos(ts);
+                    Value::date(dt.fixed_offset(), *span)
+                }
                 Zone::Error => Value::error(
                     // This is an argument error, not an input error
                     ShellError::TypeMismatch {
@@ -420,6 +460,11 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     let parse_as_string = |val: &str| {
         match dateformat {
             Some(dt_format) => {
+                // the format can only parse English month and weekday names
+                let val = &match args.locale {
+                    Some(locale) => delocalize_date_names(val, locale),
+                    None => val.to_string(),
+                };
                 // Handle custom format specifiers for compact formats
                 let format_str = dt_format
                     .item
@@ -458,6 +503,9 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
                                     *span,
                                 ),
                             },
+                            Zone::Named(tz) => {
+                                Value::date(dt.with_timezone(tz).fixed_offset(), *span)
+                            }
                             Zone::Error => Value::error(
                                 // This is an argument error, not an input error
                                 ShellError::TypeMismatch {
@@ -492,7 +540,7 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
             // Tries to automatically parse the date
             // (i.e. without a format string)
             // and assumes the system's local timezone if none is specified
-            None => match parse_date_from_string(val, span) {
+            None => match parse_date(val, span) {
                 Ok(date) => Value::date(date, span),
                 Err(err) => err,
             },
@@ -796,6 +844,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -816,6 +865,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -839,6 +889,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -859,6 +910,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -880,6 +932,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_int, &args, Span::test_data());
@@ -901,6 +954,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_int, &args, Span::test_data());
@@ -926,6 +980,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_int, &args, Span::test_data());
@@ -951,6 +1006,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_int, &args, Span::test_data());
@@ -961,6 +1017,32 @@ mod tests {
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn takes_timestamp_with_named_timezone() {
+        let date_str = Value::test_string("1614434140000000000");
+        let timezone_option = Some(Spanned {
+            item: Zone::from_string("Asia/Kolkata"),
+            span: Span::test_data(),
+        });
+        let args = Arguments {
+            zone_options: timezone_option,
+            format_options: None,
+            locale: None,
+            cell_paths: None,
+        };
+        let actual = action(&date_str, &args, Span::test_data());
+        let expected = Value::date(
+            DateTime::parse_from_rfc3339("2021-02-27T19:25:40+05:30").unwrap(),
+            Span::test_data(),
+        );
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual.as_date().unwrap().offset().local_minus_utc(),
+            5 * HOUR + 30 * 60
+        );
+    }
+
     #[test]
     fn takes_timestamp() {
         let date_str = Value::test_string("1614434140000000000");
@@ -971,6 +1053,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -991,6 +1074,7 @@ mod tests {
         let args = Arguments {
             zone_options: timezone_option,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let expected = Value::date(
@@ -1008,6 +1092,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: None,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
@@ -1030,6 +1115,7 @@ mod tests {
         let args = Arguments {
             zone_options: None,
             format_options: fmt_options,
+            locale: None,
             cell_paths: None,
         };
         let actual = action(&date_str, &args, Span::test_data());
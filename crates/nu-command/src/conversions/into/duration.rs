@@ -26,6 +26,44 @@ const ALLOWED_COLUMNS: [&str; 9] = [
 ];
 const ALLOWED_SIGNS: [&str; 2] = ["+", "-"];
 
+// The units that can be written right after their number in strings like `1h30m` or `2 days 4 hours`.
+const HUMAN_DURATION_UNITS: [(&str, i64); 34] = [
+    ("ns", 1),
+    ("nanosecond", 1),
+    ("nanoseconds", 1),
+    ("us", NS_PER_US),
+    ("\u{00B5}s", NS_PER_US), // micro sign
+    ("\u{03BC}s", NS_PER_US), // mu symbol
+    ("microsecond", NS_PER_US),
+    ("microseconds", NS_PER_US),
+    ("ms", NS_PER_MS),
+    ("millisecond", NS_PER_MS),
+    ("milliseconds", NS_PER_MS),
+    ("s", NS_PER_SEC),
+    ("sec", NS_PER_SEC),
+    ("secs", NS_PER_SEC),
+    ("second", NS_PER_SEC),
+    ("seconds", NS_PER_SEC),
+    ("m", NS_PER_MINUTE),
+    ("min", NS_PER_MINUTE),
+    ("mins", NS_PER_MINUTE),
+    ("minute", NS_PER_MINUTE),
+    ("minutes", NS_PER_MINUTE),
+    ("h", NS_PER_HOUR),
+    ("hr", NS_PER_HOUR),
+    ("hrs", NS_PER_HOUR),
+    ("hour", NS_PER_HOUR),
+    ("hours", NS_PER_HOUR),
+    ("d", NS_PER_DAY),
+    ("day", NS_PER_DAY),
+    ("days", NS_PER_DAY),
+    ("w", NS_PER_WEEK),
+    ("wk", NS_PER_WEEK),
+    ("wks", NS_PER_WEEK),
+    ("week", NS_PER_WEEK),
+    ("weeks", NS_PER_WEEK),
+];
+
 #[derive(Clone, Debug)]
 struct Arguments {
     unit: Option<Spanned<Unit>>,
@@ -80,7 +118,12 @@ impl Command for IntoDuration {
     }
 
     fn extra_description(&self) -> &str {
-        "Max duration value is i64::MAX nanoseconds; max duration time unit is wk (weeks)."
+        r#"Max duration value is i64::MAX nanoseconds; max duration time unit is wk (weeks).
+
+Besides the duration literals of nushell, strings can use the short and long names of the units,
+with or without spaces in between, like "1h30m", "2d 4h" or "1 hour, 30 minutes", as well as
+ISO 8601 durations like "P1DT2H". Years and months don't have a fixed length, so ISO 8601
+durations with them aren't supported."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -138,6 +181,16 @@ impl Command for IntoDuration {
                     (((((/* 1 * */24) + 2) * 60) + 3) * 60 + 4) * NS_PER_SEC,
                 )),
             },
+            Example {
+                description: "Convert compact duration string to duration value.",
+                example: "'1h30m' | into duration",
+                result: Some(Value::test_duration(90 * NS_PER_MINUTE)),
+            },
+            Example {
+                description: "Convert ISO 8601 duration string to duration value.",
+                example: "'P1DT2H' | into duration",
+                result: Some(Value::test_duration(NS_PER_DAY + 2 * NS_PER_HOUR)),
+            },
             Example {
                 description: "Convert table of duration strings to table of duration values.",
                 example: "[[value]; ['1sec'] ['2min'] ['3hr'] ['4day'] ['5wk']] | into duration value",
@@ -209,17 +262,105 @@ fn split_whitespace_indices(s: &str, span: Span) -> impl Iterator<Item = (&str,
     })
 }
 
-fn compound_to_duration(s: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn compound_to_duration(s: &str, span: Span) -> Result<i64, ShellError> {
     let mut duration_ns: i64 = 0;
 
     for (substring, substring_span) in split_whitespace_indices(s, span) {
-        let sub_ns = string_to_duration(substring, substring_span)?;
-        duration_ns += sub_ns;
+        match string_to_duration(substring, substring_span) {
+            Ok(sub_ns) => duration_ns += sub_ns,
+            Err(error) => {
+                return iso8601_to_duration(s.trim())
+                    .or_else(|| human_to_duration(s))
+                    .ok_or(error);
+            }
+        }
     }
 
     Ok(duration_ns)
 }
 
+// Parses strings like `1h30m`, `2d 4h` or `1 hour, 30 minutes`.
+fn human_to_duration(s: &str) -> Option<i64> {
+    let s = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect::<String>();
+    let (sign, mut rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s.as_str()),
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut duration_ns: i64 = 0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .unwrap_or(rest.len());
+        let unit_len = rest[number_len..]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len() - number_len);
+        let unit = rest[number_len..number_len + unit_len].to_lowercase();
+        let (_, factor) = HUMAN_DURATION_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)?;
+        let number = rest[..number_len].replace('_', "");
+        duration_ns = duration_ns.checked_add(scale_to_ns(&number, *factor)?)?;
+        rest = &rest[number_len + unit_len..];
+    }
+
+    Some(sign * duration_ns)
+}
+
+// Parses ISO 8601 durations like `P1DT2H30M` or `PT0.5S`, without years and months.
+fn iso8601_to_duration(s: &str) -> Option<i64> {
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s),
+    };
+    let s = s.strip_prefix(['P', 'p'])?;
+    let (date, time) = match s.split_once(['T', 't']) {
+        Some((date, time)) if !time.is_empty() => (date, time),
+        Some(_) => return None,
+        None if !s.is_empty() => (s, ""),
+        None => return None,
+    };
+
+    let mut duration_ns: i64 = 0;
+    for (part, designators) in [
+        (date, &[('W', NS_PER_WEEK), ('D', NS_PER_DAY)][..]),
+        (
+            time,
+            &[('H', NS_PER_HOUR), ('M', NS_PER_MINUTE), ('S', NS_PER_SEC)][..],
+        ),
+    ] {
+        // the designators have to be in this order, and each can only be used once
+        let mut designators = designators.iter();
+        let mut rest = part;
+        while !rest.is_empty() {
+            let number_len = rest.find(|c: char| c.is_ascii_alphabetic())?;
+            let designator = rest[number_len..].chars().next()?.to_ascii_uppercase();
+            let (_, factor) = designators.find(|(d, _)| *d == designator)?;
+            let number = rest[..number_len].replace(',', ".");
+            duration_ns = duration_ns.checked_add(scale_to_ns(&number, *factor)?)?;
+            rest = &rest[number_len + 1..];
+        }
+    }
+
+    Some(sign * duration_ns)
+}
+
+fn scale_to_ns(number: &str, factor: i64) -> Option<i64> {
+    if !number.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    match number.parse::<i64>() {
+        Ok(number) => number.checked_mul(factor),
+        Err(_) => Some((number.parse::<f64>().ok()? * factor as f64).round() as i64),
+    }
+}
+
 fn string_to_duration(s: &str, span: Span) -> Result<i64, ShellError> {
     if let Some(Ok(expression)) = parse_unit_value(
         s.as_bytes(),
@@ -454,6 +595,16 @@ mod test {
     #[case("3wk", 3 * NS_PER_WEEK)]
     #[case("86hr 26ns", 86 * 3600 * NS_PER_SEC + 26)] // compound duration string
     #[case("14ns 3hr 17sec", 14 + 3 * NS_PER_HOUR + 17 * NS_PER_SEC)] // compound string with units in random order
+    #[case("1h30m", 90 * NS_PER_MINUTE)] // units right after each other
+    #[case("2d 4h", 2 * NS_PER_DAY + 4 * NS_PER_HOUR)]
+    #[case("1 hour, 30 minutes", 90 * NS_PER_MINUTE)]
+    #[case("1.5h", 90 * NS_PER_MINUTE)]
+    #[case("-1m30s", -90 * NS_PER_SEC)]
+    #[case("2s500ms", 2500 * NS_PER_MS)]
+    #[case("P1DT2H", NS_PER_DAY + 2 * NS_PER_HOUR)] // ISO 8601
+    #[case("PT0.5S", 500 * NS_PER_MS)]
+    #[case("P2W", 2 * NS_PER_WEEK)]
+    #[case("-PT1M30S", -90 * NS_PER_SEC)]
 
     fn turns_string_to_duration(#[case] phrase: &str, #[case] expected_duration_val: i64) {
         let args = Arguments {
@@ -139,7 +139,7 @@ fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
     }
 }
 
-fn i64_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn i64_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
     // Get the Locale so we know what the thousands separator is
     let locale = get_system_locale();
 
@@ -25,3 +25,7 @@ pub use int::IntoInt;
 pub use record::IntoRecord;
 pub use string::IntoString;
 pub use value::IntoValue;
+
+pub(crate) use duration::compound_to_duration;
+pub(crate) use filesize::i64_from_string as filesize_from_string;
+pub(crate) use int::int_from_string;
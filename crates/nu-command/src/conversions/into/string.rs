@@ -38,6 +38,7 @@ impl Command for IntoString {
                 (Type::Filesize, Type::String),
                 (Type::Date, Type::String),
                 (Type::Duration, Type::String),
+                (Type::Rate, Type::String),
                 (Type::CellPath, Type::String),
                 (Type::Range, Type::String),
                 (
@@ -233,6 +234,7 @@ fn action(input: &Value, args: &Arguments, span: Span) -> Value {
             }
         }
         Value::Duration { val: _, .. } => Value::string(input.to_expanded_string("", config), span),
+        Value::Rate { .. } => Value::string(input.to_expanded_string("", config), span),
         Value::Nothing { .. } => Value::string("".to_string(), span),
         Value::Record { .. } => Value::error(
             // Watch out for CantConvert's argument order
@@ -378,6 +378,7 @@ fn nu_value_to_sqlite_type(val: &Value, dst_span: Span) -> Result<&'static str,
         Type::Date => Ok("DATETIME"),
         Type::Duration => Ok("BIGINT"),
         Type::Filesize => Ok("INTEGER"),
+        Type::Rate => Ok("INTEGER"),
         Type::List(_) | Type::Record(_) | Type::Table(_) => Ok("JSONB"),
 
         // [NOTE] On null values, we just assume TEXT. This could end up
@@ -447,6 +447,7 @@ pub fn value_to_sql(
         Value::Float { val, .. } => Ok(Box::new(val)),
         Value::Filesize { val, .. } => Ok(Box::new(val.get())),
         Value::Duration { val, .. } => Ok(Box::new(val)),
+        Value::Rate { val, .. } => Ok(Box::new(val.get())),
         Value::Date { val, .. } => Ok(Box::new(val)),
         Value::String { val, .. } => Ok(Box::new(val)),
         Value::Binary { val, .. } => Ok(Box::new(val)),
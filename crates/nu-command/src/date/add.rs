@@ -0,0 +1,170 @@
+use super::parser::TimeZoneArg;
+use crate::date::utils::parse_date_from_string;
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone};
+use nu_engine::command_prelude::*;
+
+const NS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+
+#[derive(Clone)]
+pub struct DateAdd;
+
+impl Command for DateAdd {
+    fn name(&self) -> &str {
+        "date add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("date add")
+            .input_output_types(vec![(Type::Date, Type::Date), (Type::String, Type::Date)])
+            .allow_variants_without_examples(true)
+            .required("duration", SyntaxShape::Duration, "The duration to add.")
+            .named(
+                "timezone",
+                SyntaxShape::String,
+                "The time zone whose clocks to follow, instead of the local one.",
+                Some('z'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Add a duration to a date, following the clocks of a time zone across daylight saving time changes."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Whole days of the duration are added to the calendar date, so the time of day stays the \
+         same even when a day is 23 or 25 hours long. The rest of the duration is added as elapsed \
+         time. The result is in the given time zone.
+
+Adding a duration with `+` instead always adds elapsed time, and keeps the offset of the date."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["plus", "dst", "daylight saving", "calendar", "tz"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let duration: i64 = call.req(engine_state, stack, 0)?;
+        let tz = match call.get_flag::<Spanned<String>>(engine_state, stack, "timezone")? {
+            Some(timezone) => {
+                TimeZoneArg::parse(&timezone.item).map_err(|_| ShellError::TypeMismatch {
+                    err_message: String::from("invalid time zone"),
+                    span: timezone.span,
+                })?
+            }
+            None => TimeZoneArg::Local,
+        };
+
+        // This doesn't match explicit nulls
+        if let PipelineData::Empty = input {
+            return Err(ShellError::PipelineEmpty { dst_span: head });
+        }
+        input.map(
+            move |value| helper(value, head, duration, &tz),
+            engine_state.signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let example_result = |day, hour, offset| {
+            FixedOffset::east_opt(offset * 3600)
+                .and_then(|offset| offset.with_ymd_and_hms(2024, 3, day, hour, 0, 0).single())
+                .map(|dt| Value::date(dt, Span::test_data()))
+        };
+
+        vec![
+            Example {
+                description: "Add a day across the start of daylight saving time in Berlin, keeping the time of day.",
+                example: r#""2024-03-30 12:00:00 +01:00" | date add 1day --timezone Europe/Berlin"#,
+                result: example_result(31, 12, 2),
+            },
+            Example {
+                description: "Add elapsed time across the start of daylight saving time in Berlin.",
+                example: r#""2024-03-31 01:00:00 +01:00" | date add 2hr --timezone Europe/Berlin"#,
+                result: example_result(31, 4, 2),
+            },
+            Example {
+                description: "Get the same time of day a week from now.",
+                example: "date now | date add 1wk",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn helper(value: Value, head: Span, duration: i64, tz: &TimeZoneArg) -> Value {
+    let val_span = value.span();
+    let dt = match value {
+        Value::Date { val, .. } => val,
+        Value::String { val, .. } => match parse_date_from_string(&val, val_span) {
+            Ok(dt) => dt,
+            Err(e) => return e,
+        },
+        Value::Error { .. } => return value,
+        _ => {
+            return Value::error(
+                ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "date, string (that represents datetime)".into(),
+                    wrong_type: value.get_type().to_string(),
+                    dst_span: head,
+                    src_span: val_span,
+                },
+                head,
+            );
+        }
+    };
+
+    match add_duration(dt, duration, tz) {
+        Some(dt) => Value::date(dt, head),
+        None => Value::error(
+            ShellError::GenericError {
+                error: "Date out of range".into(),
+                msg: "adding the duration goes past the supported dates".into(),
+                span: Some(head),
+                help: None,
+                inner: vec![],
+            },
+            head,
+        ),
+    }
+}
+
+fn add_duration(
+    dt: DateTime<FixedOffset>,
+    duration: i64,
+    tz: &TimeZoneArg,
+) -> Option<DateTime<FixedOffset>> {
+    let dt = tz.convert(&dt);
+    let naive = dt
+        .naive_local()
+        .checked_add_signed(TimeDelta::days(duration / NS_PER_DAY))?;
+
+    // A time that is skipped by turning the clocks forward is moved forward too, like a clock
+    // that isn't set to the new time yet would show
+    let dt = match tz.at_local_time(&naive) {
+        Some(dt) => dt,
+        None => tz.convert(&dt.offset().from_local_datetime(&naive).single()?),
+    };
+
+    dt.checked_add_signed(TimeDelta::nanoseconds(duration % NS_PER_DAY))
+        .map(|dt| tz.convert(&dt))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(DateAdd {})
+    }
+}
@@ -1,3 +1,4 @@
+mod add;
 mod date_;
 mod from_human;
 mod humanize;
@@ -6,11 +7,18 @@ mod now;
 mod parser;
 mod to_timezone;
 mod utils;
+mod with_timezone;
 
+pub use add::DateAdd;
 pub use date_::Date;
 pub use from_human::DateFromHuman;
 pub use humanize::DateHumanize;
 pub use list_timezone::DateListTimezones;
 pub use now::DateNow;
+pub(crate) use parser::TimeZoneArg;
 pub use to_timezone::DateToTimezone;
-pub(crate) use utils::{generate_strftime_list, parse_date_from_string};
+pub(crate) use utils::{
+    COMMON_DATE_FORMATS, TIMEZONES, delocalize_date_names, generate_strftime_list,
+    locale_from_name, parse_date_from_string, parse_date_from_string_localized,
+};
+pub use with_timezone::DateWithTimezone;
@@ -1,6 +1,6 @@
 // Modified from chrono::format::scan
 
-use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone};
 use chrono_tz::Tz;
 use titlecase::titlecase;
 
@@ -16,26 +16,63 @@ pub enum ParseErrorKind {
     TooShort,
 }
 
+/// A time zone given by name like `Europe/Berlin`, by offset like `+0500`, or as `local`.
+///
+/// Unlike the fixed offset of a date, a named time zone knows when daylight saving time applies.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TimeZoneArg {
+    Fixed(FixedOffset),
+    Local,
+    Named(Tz),
+}
+
+impl TimeZoneArg {
+    pub(crate) fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        match timezone_offset_internal(s, true, true) {
+            Ok(offset) => FixedOffset::east_opt(offset)
+                .map(Self::Fixed)
+                .ok_or(ParseErrorKind::OutOfRange),
+            Err(ParseErrorKind::Invalid) if s.eq_ignore_ascii_case("local") => Ok(Self::Local),
+            Err(ParseErrorKind::Invalid) => parse_timezone_internal(s).map(Self::Named),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The same point in time, with the offset this time zone has at that time.
+    pub(crate) fn convert(&self, dt: &DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Fixed(offset) => dt.with_timezone(offset),
+            Self::Local => dt.with_timezone(&Local).fixed_offset(),
+            Self::Named(tz) => dt.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    /// The date with the wall-clock time `naive` in this time zone.
+    ///
+    /// When the clocks are turned back, a wall-clock time happens twice and the earlier one is
+    /// used. When they are turned forward, some times don't happen at all, which is `None`.
+    pub(crate) fn at_local_time(&self, naive: &NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Self::Fixed(offset) => offset.from_local_datetime(naive).earliest(),
+            Self::Local => Local
+                .from_local_datetime(naive)
+                .earliest()
+                .map(|dt| dt.fixed_offset()),
+            Self::Named(tz) => tz
+                .from_local_datetime(naive)
+                .earliest()
+                .map(|dt| dt.fixed_offset()),
+        }
+    }
+}
+
 pub fn datetime_in_timezone(
     dt: &DateTime<FixedOffset>,
     s: &str,
 ) -> Result<DateTime<FixedOffset>, ParseErrorKind> {
-    match timezone_offset_internal(s, true, true) {
-        Ok(offset) => match FixedOffset::east_opt(offset) {
-            Some(offset) => Ok(dt.with_timezone(&offset)),
-            None => Err(ParseErrorKind::OutOfRange),
-        },
-        Err(ParseErrorKind::Invalid) => {
-            if s.eq_ignore_ascii_case("local") {
-                Ok(dt.with_timezone(Local::now().offset()))
-            } else {
-                let tz: Tz = parse_timezone_internal(s)?;
-                let offset = tz.offset_from_utc_datetime(&dt.naive_utc()).fix();
-                Ok(dt.with_timezone(&offset))
-            }
-        }
-        Err(e) => Err(e),
-    }
+    // The offset of the local time zone and named ones depends on the date because of daylight
+    // saving time, so the offset they have now can't be used
+    TimeZoneArg::parse(s).map(|tz| tz.convert(dt))
 }
 
 fn parse_timezone_internal(s: &str) -> Result<Tz, ParseErrorKind> {
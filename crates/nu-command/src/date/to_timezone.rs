@@ -1,13 +1,8 @@
-use std::sync::LazyLock;
-
 use super::parser::datetime_in_timezone;
-use crate::date::utils::parse_date_from_string;
+use crate::date::utils::{TIMEZONES, parse_date_from_string};
 use chrono::{DateTime, FixedOffset, Local, LocalResult, TimeZone};
 use nu_engine::command_prelude::*;
 
-static TIMEZONES: LazyLock<Vec<&'static str>> =
-    LazyLock::new(|| chrono_tz::TZ_VARIANTS.iter().map(|tz| tz.name()).collect());
-
 #[derive(Clone)]
 pub struct DateToTimezone;
 
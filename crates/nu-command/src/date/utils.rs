@@ -1,32 +1,36 @@
-use chrono::{DateTime, FixedOffset, Local, LocalResult, TimeZone};
-use nu_protocol::{ShellError, Span, Value, record};
+use chrono::{
+    DateTime, FixedOffset, Local, LocalResult, Locale, NaiveDate, NaiveDateTime, TimeZone,
+};
+use dtparse::{Parser, ParserInfo};
+use nu_protocol::{ShellError, Span, Spanned, Value, record};
+use std::{collections::HashMap, sync::LazyLock};
+
+/// The names of all supported time zones, to complete time zone arguments with.
+pub(crate) static TIMEZONES: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| chrono_tz::TZ_VARIANTS.iter().map(|tz| tz.name()).collect());
+
+/// Commonly used format strings, to complete format string arguments with.
+pub(crate) const COMMON_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%:z",
+    "%+",
+    "%d/%m/%Y",
+    "%m/%d/%Y",
+    "%d.%m.%Y",
+    "%H:%M:%S",
+    "%H:%M",
+    "%c",
+    "%s",
+    "%A, %B %d, %Y",
+];
 
 pub(crate) fn parse_date_from_string(
     input: &str,
     span: Span,
 ) -> Result<DateTime<FixedOffset>, Value> {
     match dtparse::parse(input) {
-        Ok((native_dt, fixed_offset)) => {
-            let offset = match fixed_offset {
-                Some(offset) => offset,
-                None => *Local
-                    .from_local_datetime(&native_dt)
-                    .single()
-                    .unwrap_or_default()
-                    .offset(),
-            };
-            match offset.from_local_datetime(&native_dt) {
-                LocalResult::Single(d) => Ok(d),
-                LocalResult::Ambiguous(d, _) => Ok(d),
-                LocalResult::None => Err(Value::error(
-                    ShellError::DatetimeParseError {
-                        msg: input.into(),
-                        span,
-                    },
-                    span,
-                )),
-            }
-        }
+        Ok((native_dt, fixed_offset)) => with_offset(input, native_dt, fixed_offset, span),
         Err(_) => Err(Value::error(
             ShellError::DatetimeParseError {
                 msg: input.into(),
@@ -37,6 +41,181 @@ pub(crate) fn parse_date_from_string(
     }
 }
 
+/// Like [`parse_date_from_string`], but with the month and weekday names of `locale` and the
+/// order that it writes the day, month and year in.
+pub(crate) fn parse_date_from_string_localized(
+    input: &str,
+    span: Span,
+    locale: Locale,
+) -> Result<DateTime<FixedOffset>, Value> {
+    let mut info = ParserInfo::default();
+    // the parser splits a dot off an abbreviation like `févr.`
+    for (month, name, _) in month_names(locale) {
+        info.months
+            .insert(name.trim_end_matches('.').to_string(), month);
+    }
+    for (weekday, name, _) in weekday_names(locale) {
+        info.weekday
+            .insert(name.trim_end_matches('.').to_string(), weekday);
+    }
+    let (dayfirst, yearfirst) = date_order(locale);
+    info.dayfirst = dayfirst;
+    info.yearfirst = yearfirst;
+
+    let parsed = Parser::new(info).parse(
+        input,
+        None,
+        None,
+        false,
+        false,
+        None,
+        false,
+        &HashMap::new(),
+    );
+    match parsed {
+        Ok((native_dt, fixed_offset, _)) => with_offset(input, native_dt, fixed_offset, span),
+        Err(_) => Err(Value::error(
+            ShellError::DatetimeParseError {
+                msg: input.into(),
+                span,
+            },
+            span,
+        )),
+    }
+}
+
+/// Uses the offset of the local timezone when the parsed date didn't have one.
+fn with_offset(
+    input: &str,
+    native_dt: NaiveDateTime,
+    fixed_offset: Option<FixedOffset>,
+    span: Span,
+) -> Result<DateTime<FixedOffset>, Value> {
+    let offset = match fixed_offset {
+        Some(offset) => offset,
+        None => *Local
+            .from_local_datetime(&native_dt)
+            .single()
+            .unwrap_or_default()
+            .offset(),
+    };
+    match offset.from_local_datetime(&native_dt) {
+        LocalResult::Single(d) => Ok(d),
+        LocalResult::Ambiguous(d, _) => Ok(d),
+        LocalResult::None => Err(Value::error(
+            ShellError::DatetimeParseError {
+                msg: input.into(),
+                span,
+            },
+            span,
+        )),
+    }
+}
+
+/// Parses a locale name like `de-DE`, `de_DE` or `de_DE.UTF-8`.
+pub(crate) fn locale_from_name(name: &Spanned<String>) -> Result<Locale, ShellError> {
+    let locale = name
+        .item
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .replace('-', "_");
+    Locale::try_from(locale.as_str()).map_err(|_| ShellError::IncorrectValue {
+        msg: format!(
+            "Unknown locale `{}`, expected a name like de_DE or pt-BR",
+            name.item
+        ),
+        val_span: name.span,
+        call_span: name.span,
+    })
+}
+
+/// Replaces the month and weekday names of `locale` in `input` with the English ones, which are
+/// the only ones that a format string can parse.
+pub(crate) fn delocalize_date_names(input: &str, locale: Locale) -> String {
+    let mut names: Vec<(String, String)> = month_names(locale)
+        .chain(weekday_names(locale))
+        .map(|(_, localized, english)| (localized, english))
+        .collect();
+    // abbreviations with a dot, like `févr.`, have to match with the dot before they match without it
+    names.sort_by_key(|(localized, _)| std::cmp::Reverse(localized.len()));
+
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find(char::is_alphabetic) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let word = rest[..end].to_lowercase();
+        let with_dot = rest[end..].starts_with('.');
+        let replacement = names.iter().find_map(|(localized, english)| {
+            if with_dot && localized.strip_suffix('.') == Some(word.as_str()) {
+                Some((english, end + 1))
+            } else if *localized == word {
+                Some((english, end))
+            } else {
+                None
+            }
+        });
+        match replacement {
+            Some((english, len)) => {
+                output.push_str(english);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The full and abbreviated month names of `locale` in lowercase, with their index and the
+/// English name of the same length.
+fn month_names(locale: Locale) -> impl Iterator<Item = (usize, String, String)> {
+    let months = (1..=12).filter_map(|month| NaiveDate::from_ymd_opt(2001, month, 1));
+    localized_names(months, ["%B", "%b"], locale)
+}
+
+/// Like [`month_names`], for the days of the week, starting with Monday.
+fn weekday_names(locale: Locale) -> impl Iterator<Item = (usize, String, String)> {
+    // the first of January 2001 was a Monday
+    let days = (1..=7).filter_map(|day| NaiveDate::from_ymd_opt(2001, 1, day));
+    localized_names(days, ["%A", "%a"], locale)
+}
+
+fn localized_names(
+    dates: impl Iterator<Item = NaiveDate>,
+    formats: [&'static str; 2],
+    locale: Locale,
+) -> impl Iterator<Item = (usize, String, String)> {
+    dates.enumerate().flat_map(move |(index, date)| {
+        formats.map(|format| {
+            let localized = date.format_localized(format, locale).to_string();
+            (
+                index,
+                localized.to_lowercase(),
+                date.format(format).to_string(),
+            )
+        })
+    })
+}
+
+/// Whether `locale` writes the day before the month, and the year before both, in dates.
+fn date_order(locale: Locale) -> (bool, bool) {
+    let Some(date) = NaiveDate::from_ymd_opt(1999, 11, 22) else {
+        return (false, false);
+    };
+    let formatted = date.format_localized("%x", locale).to_string();
+    let position = |part: &str| formatted.find(part).unwrap_or(usize::MAX);
+    let (year, month, day) = (position("99"), position("11"), position("22"));
+    (day < month, year < month.min(day))
+}
+
 /// Generates a table containing available datetime format specifiers
 ///
 /// # Arguments
@@ -0,0 +1,137 @@
+use super::parser::TimeZoneArg;
+use crate::date::utils::parse_date_from_string;
+use chrono::{DateTime, FixedOffset, TimeZone};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct DateWithTimezone;
+
+impl Command for DateWithTimezone {
+    fn name(&self) -> &str {
+        "date with-timezone"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("date with-timezone")
+            .input_output_types(vec![(Type::Date, Type::Date), (Type::String, Type::Date)])
+            .allow_variants_without_examples(true)
+            .required("time zone", SyntaxShape::String, "Time zone description.")
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Keep the date and time of day of a date, but put it in a given time zone."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Unlike 'date to-timezone', this changes the point in time that the date refers to. \
+         The offset is the one the time zone has at that date, so daylight saving time is taken \
+         into account. A time that is skipped when the clocks are turned forward is an error.
+
+Use 'date list-timezone' to list all supported time zones."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["tz", "replace", "set", "local", "dst", "wall clock"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let timezone: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let tz = TimeZoneArg::parse(&timezone.item).map_err(|_| ShellError::TypeMismatch {
+            err_message: String::from("invalid time zone"),
+            span: timezone.span,
+        })?;
+
+        // This doesn't match explicit nulls
+        if let PipelineData::Empty = input {
+            return Err(ShellError::PipelineEmpty { dst_span: head });
+        }
+        input.map(
+            move |value| helper(value, head, &tz),
+            engine_state.signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let example_result = |hour, offset| {
+            FixedOffset::east_opt(offset * 3600)
+                .and_then(|offset| offset.with_ymd_and_hms(2024, 7, 1, hour, 0, 0).single())
+                .map(|dt| Value::date(dt, Span::test_data()))
+        };
+
+        vec![
+            Example {
+                description: "Put a time in UTC+05:00, keeping the time of day.",
+                example: r#""2024-07-01 12:00:00 +02:00" | date with-timezone "+0500""#,
+                result: example_result(12, 5),
+            },
+            Example {
+                description: "Put a time in Berlin, where daylight saving time applies in summer.",
+                example: r#""2024-07-01 12:00:00 +00:00" | into datetime | date with-timezone Europe/Berlin"#,
+                result: example_result(12, 2),
+            },
+            Example {
+                description: "Put a time in the local time zone.",
+                example: r#""2024-07-01 12:00:00 +00:00" | date with-timezone local"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn helper(value: Value, head: Span, tz: &TimeZoneArg) -> Value {
+    let val_span = value.span();
+    match value {
+        Value::Date { val, .. } => with_timezone(val, tz, head),
+        Value::String { val, .. } => match parse_date_from_string(&val, val_span) {
+            Ok(dt) => with_timezone(dt, tz, head),
+            Err(e) => e,
+        },
+        Value::Error { .. } => value,
+        _ => Value::error(
+            ShellError::OnlySupportsThisInputType {
+                exp_input_type: "date, string (that represents datetime)".into(),
+                wrong_type: value.get_type().to_string(),
+                dst_span: head,
+                src_span: val_span,
+            },
+            head,
+        ),
+    }
+}
+
+fn with_timezone(dt: DateTime<FixedOffset>, tz: &TimeZoneArg, span: Span) -> Value {
+    match tz.at_local_time(&dt.naive_local()) {
+        Some(dt) => Value::date(dt, span),
+        None => Value::error(
+            ShellError::IncorrectValue {
+                msg: format!(
+                    "{} doesn't exist in this time zone, because the clocks are turned forward then",
+                    dt.naive_local()
+                ),
+                val_span: span,
+                call_span: span,
+            },
+            span,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(DateWithTimezone {})
+    }
+}
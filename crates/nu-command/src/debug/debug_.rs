@@ -124,6 +124,7 @@ impl ValueExt for Value {
             Value::Glob { val, .. } => Ok(val.to_string()),
             Value::Filesize { val, .. } => Ok(val.get().to_string()),
             Value::Duration { val, .. } => Ok(val.to_string()),
+            Value::Rate { val, .. } => Ok(val.get().to_string()),
             Value::Date { val, .. } => Ok(val.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)),
             Value::Range { val, .. } => Ok(val.to_string()),
             Value::Record { val, .. } => Ok(format!(
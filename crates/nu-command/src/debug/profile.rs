@@ -1,6 +1,6 @@
 use nu_engine::{ClosureEvalOnce, command_prelude::*};
 use nu_protocol::{
-    debugger::{DurationMode, Profiler, ProfilerOptions},
+    debugger::{DurationMode, Profiler, ProfilerOptions, ReportMode},
     engine::Closure,
 };
 
@@ -36,13 +36,28 @@ impl Command for DebugProfile {
                 "Report instruction duration as duration values rather than milliseconds.",
                 Some('d'),
             )
+            .switch(
+                "cpu-time",
+                "Collect the CPU time spent by the evaluating thread.",
+                Some('c'),
+            )
+            .switch(
+                "tree",
+                "Report a tree of source spans with their total wall and CPU time.",
+                Some('t'),
+            )
+            .switch(
+                "folded",
+                "Report folded stacks for flamegraph tools.",
+                Some('f'),
+            )
             .named(
                 "max-depth",
                 SyntaxShape::Int,
                 "How many blocks/closures deep to step into (default 2).",
                 Some('m'),
             )
-            .input_output_types(vec![(Type::Any, Type::table())])
+            .input_output_types(vec![(Type::Any, Type::Any)])
             .category(Category::Debug)
     }
 
@@ -68,6 +83,8 @@ The output can be heavily customized. By default, the following columns are incl
 - (optional) span        : Span associated with the instruction. Can be viewed via the `view span`
                            command. Enabled with the --spans flag.
 - (optional) output      : The output value of the instruction. Enabled with the --values flag.
+- (optional) cpu         : CPU time spent by the evaluating thread. Enabled with the --cpu-time
+                           flag. Empty on platforms where it can't be measured.
 
 To illustrate the depth and IDs, consider `debug profile { do { if true { echo 'spam' } } }`. A unique ID is generated each time an instruction is executed, and there are two levels of depth:
 
@@ -94,6 +111,14 @@ at `if true { 'spam' }`. The id is used to identify each element. The parent_id
 instructions inside the block are being executed because of `do` (5), which in turn was spawned from
 the root `debug profile { ... }`.
 
+The --tree flag reports the same data as nested records instead. Instructions sharing a span are
+merged into one node, with `count` telling how many there were, so every node stands for a piece of
+source code with the total wall and CPU time spent in it. The callee's nodes are in `children`.
+
+The --folded flag reports one line per call stack, in the folded format understood by flamegraph
+tools like `inferno-flamegraph` or `flamegraph.pl`. Frames are named after their source and the
+number at the end of each line is the time spent in that frame itself, in nanoseconds.
+
 For a better understanding of how instructions map to source code, see the `view ir` command.
 
 Note: In some cases, the ordering of pipeline elements might not be intuitive. For example,
@@ -114,6 +139,9 @@ confusing the id/parent_id hierarchy. The --expr flag is helpful for investigati
         let collect_values = call.has_flag(engine_state, stack, "values")?;
         let collect_lines = call.has_flag(engine_state, stack, "lines")?;
         let duration_values = call.has_flag(engine_state, stack, "duration-values")?;
+        let collect_cpu_time = call.has_flag(engine_state, stack, "cpu-time")?;
+        let tree = call.has_flag(engine_state, stack, "tree")?;
+        let folded = call.has_flag(engine_state, stack, "folded")?;
         let max_depth = call
             .get_flag(engine_state, stack, "max-depth")?
             .unwrap_or(2);
@@ -122,6 +150,19 @@ confusing the id/parent_id hierarchy. The --expr flag is helpful for investigati
             true => DurationMode::Value,
             false => DurationMode::Milliseconds,
         };
+        let report_mode = match (tree, folded) {
+            (true, true) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "tree".into(),
+                    left_span: call.get_flag_span(stack, "tree").unwrap_or(call.head),
+                    right_message: "folded".into(),
+                    right_span: call.get_flag_span(stack, "folded").unwrap_or(call.head),
+                });
+            }
+            (true, false) => ReportMode::Tree,
+            (false, true) => ReportMode::Folded,
+            (false, false) => ReportMode::Table,
+        };
         let profiler = Profiler::new(
             ProfilerOptions {
                 max_depth,
@@ -132,7 +173,9 @@ confusing the id/parent_id hierarchy. The --expr flag is helpful for investigati
                 collect_exprs: false,
                 collect_instructions: true,
                 collect_lines,
+                collect_cpu_time: collect_cpu_time || tree,
                 duration_mode,
+                report_mode,
             },
             call.span(),
         );
@@ -176,6 +219,16 @@ confusing the id/parent_id hierarchy. The --expr flag is helpful for investigati
                 example: "debug profile { source $nu.config-path } --max-depth 4",
                 result: None,
             },
+            Example {
+                description: "Show where config evaluation spends its time, as a tree.",
+                example: "debug profile --tree { source $nu.config-path }",
+                result: None,
+            },
+            Example {
+                description: "Render a flamegraph of config evaluation with inferno.",
+                example: "debug profile --folded --max-depth 10 { source $nu.config-path } | inferno-flamegraph | save flamegraph.svg",
+                result: None,
+            },
         ]
     }
 }
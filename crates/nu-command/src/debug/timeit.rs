@@ -1,4 +1,4 @@
-use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_engine::{ClosureEval, ClosureEvalOnce, command_prelude::*};
 use nu_protocol::engine::Closure;
 use web_time::Instant;
 
@@ -17,13 +17,27 @@ impl Command for TimeIt {
     fn extra_description(&self) -> &str {
         "Any pipeline input given to this command is passed to the closure. Note that streaming inputs may affect timing results, and it is recommended to add a `collect` command before this if the input is a stream.
 
-This command will bubble up any errors encountered when running the closure. The return pipeline of the closure is collected into a value and then discarded if `--output` is not set."
+This command will bubble up any errors encountered when running the closure. The return pipeline of the closure is collected into a value and then discarded if `--output` is not set.
+
+With `--runs` or `--warmup`, the closure is run several times and a record of statistics about the measured runs is returned instead of a single duration. Warmup runs are not measured. The input is collected once and passed to every run, and `--output` includes the output of the last run."
     }
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("timeit")
             .required("command", SyntaxShape::Closure(None), "The closure to run.")
             .switch("output", "Include the closure output.", Some('o'))
+            .named(
+                "runs",
+                SyntaxShape::Int,
+                "Number of measured runs to report statistics for (default 1).",
+                Some('r'),
+            )
+            .named(
+                "warmup",
+                SyntaxShape::Int,
+                "Number of unmeasured runs before the measured ones (default 0).",
+                Some('w'),
+            )
             .input_output_types(vec![
                 (Type::Any, Type::Duration),
                 (Type::Nothing, Type::Duration),
@@ -41,6 +55,10 @@ This command will bubble up any errors encountered when running the closure. The
                         ("output".into(), Type::Any),
                     ])),
                 ),
+                (Type::Any, stats_type(false)),
+                (Type::Nothing, stats_type(false)),
+                (Type::Any, stats_type(true)),
+                (Type::Nothing, stats_type(true)),
             ])
             .allow_variants_without_examples(true)
             .category(Category::Debug)
@@ -66,6 +84,56 @@ This command will bubble up any errors encountered when running the closure. The
 
         let include_output = call.has_flag(engine_state, stack, "output")?;
         let closure: Closure = call.req(engine_state, stack, 0)?;
+        let runs: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "runs")?;
+        let warmup: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "warmup")?;
+
+        if runs.is_some() || warmup.is_some() {
+            let runs = match runs {
+                Some(runs) if runs.item < 1 => {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "at least one run is needed".into(),
+                        val_span: runs.span,
+                        call_span: call.head,
+                    });
+                }
+                Some(runs) => runs.item,
+                None => 1,
+            };
+            let warmup = match warmup {
+                Some(warmup) if warmup.item < 0 => {
+                    return Err(ShellError::NeedsPositiveValue { span: warmup.span });
+                }
+                Some(warmup) => warmup.item,
+                None => 0,
+            };
+
+            let input = input.into_value(call.head)?;
+            let mut closure = ClosureEval::new_preserve_out_dest(engine_state, stack, closure);
+
+            for _ in 0..warmup {
+                closure
+                    .run_with_value(input.clone())?
+                    .into_value(call.head)?;
+            }
+
+            let mut samples = Vec::with_capacity(runs as usize);
+            let mut closure_output = Value::nothing(call.head);
+            for _ in 0..runs {
+                let start_time = Instant::now();
+                closure_output = closure
+                    .run_with_value(input.clone())?
+                    .into_value(call.head)?;
+                samples.push(start_time.elapsed().as_nanos() as i64);
+            }
+
+            let mut stats = duration_stats(&mut samples, call.head);
+            if include_output {
+                stats.push("output", closure_output);
+            }
+
+            return Ok(Value::record(stats, call.head).into_pipeline_data());
+        }
+
         let closure = ClosureEvalOnce::new_preserve_out_dest(engine_state, stack, closure);
 
         // Get the start time after all other computation has been done.
@@ -119,10 +187,84 @@ This command will bubble up any errors encountered when running the closure. The
                 "time" => Value::test_duration(14328),
                 "output" => Value::test_string("example text")})),
             },
+            Example {
+                description: "Benchmark a closure over 20 runs, after 3 warmup runs.",
+                example: "timeit --runs 20 --warmup 3 { 1..1000 | math sum }",
+                result: None,
+            },
         ]
     }
 }
 
+fn stats_type(include_output: bool) -> Type {
+    let mut columns = vec![
+        ("runs".into(), Type::Int),
+        ("min".into(), Type::Duration),
+        ("median".into(), Type::Duration),
+        ("mean".into(), Type::Duration),
+        ("max".into(), Type::Duration),
+        ("stddev".into(), Type::Duration),
+    ];
+    if include_output {
+        columns.push(("output".into(), Type::Any));
+    }
+    Type::Record(columns.into())
+}
+
+/// Summarizes the durations (in nanoseconds) of the measured runs.
+///
+/// The standard deviation is the sample standard deviation, which is zero for a single run.
+fn duration_stats(samples: &mut [i64], span: Span) -> Record {
+    samples.sort_unstable();
+
+    let runs = samples.len();
+    let (min, max) = match (samples.first(), samples.last()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => (0, 0),
+    };
+    let median = match runs {
+        0 => 0,
+        n if n % 2 == 0 => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        n => samples[n / 2],
+    };
+    let mean = match runs {
+        0 => 0.0,
+        n => samples.iter().map(|&s| s as f64).sum::<f64>() / n as f64,
+    };
+    let stddev = match runs {
+        0 | 1 => 0.0,
+        n => {
+            let variance = samples
+                .iter()
+                .map(|&s| (s as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64;
+            variance.sqrt()
+        }
+    };
+
+    record! {
+        "runs" => Value::int(runs as i64, span),
+        "min" => Value::duration(min, span),
+        "median" => Value::duration(median, span),
+        "mean" => Value::duration(mean.round() as i64, span),
+        "max" => Value::duration(max, span),
+        "stddev" => Value::duration(stddev.round() as i64, span),
+    }
+}
+
+#[test]
+fn test_duration_stats() {
+    let mut samples = [40, 10, 30, 20];
+    let stats = duration_stats(&mut samples, Span::test_data());
+    assert_eq!(stats.get("runs"), Some(&Value::test_int(4)));
+    assert_eq!(stats.get("min"), Some(&Value::test_duration(10)));
+    assert_eq!(stats.get("median"), Some(&Value::test_duration(25)));
+    assert_eq!(stats.get("mean"), Some(&Value::test_duration(25)));
+    assert_eq!(stats.get("max"), Some(&Value::test_duration(40)));
+    assert_eq!(stats.get("stddev"), Some(&Value::test_duration(13)));
+}
+
 #[test]
 // Due to difficulty in observing side-effects from time closures,
 // checks that the closures have run correctly must use the filesystem.
@@ -32,6 +32,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Shuffle
         }
         bind_command! {
+            Aggregate,
             All,
             Any,
             Append,
@@ -68,12 +69,14 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Length,
             Lines,
             ParEach,
+            Pivot,
             ChunkBy,
             Prepend,
             Reduce,
             Reject,
             Rename,
             Reverse,
+            Rolling,
             Select,
             Skip,
             SkipUntil,
@@ -86,6 +89,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Transpose,
             Uniq,
             UniqBy,
+            Unpivot,
             Upsert,
             Update,
             Values,
@@ -221,6 +225,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             StrEndswith,
             StrEscapeRegex,
             StrExpand,
+            StrFuzzyMatch,
             StrJoin,
             StrReplace,
             StrIndexOf,
@@ -237,10 +242,19 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             FormatFilesize,
         };
 
+        #[cfg(feature = "uuid")]
+        bind_command! {
+            Uuid,
+            UuidParse,
+        };
+
         // FileSystem
         #[cfg(feature = "os")]
         bind_command! {
             Cd,
+            Chmod,
+            Flock,
+            Ln,
             Ls,
             UMkdir,
             Mktemp,
@@ -255,6 +269,32 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Watch,
         };
 
+        #[cfg(all(unix, feature = "os"))]
+        bind_command! {
+            Chown,
+            Xattr,
+            XattrGet,
+            XattrSet,
+        };
+
+        #[cfg(all(
+            feature = "os",
+            feature = "trash-support",
+            any(
+                target_os = "windows",
+                all(
+                    unix,
+                    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+                )
+            )
+        ))]
+        bind_command! {
+            Trash,
+            TrashEmpty,
+            TrashList,
+            TrashRestore,
+        };
+
         // Platform
         #[cfg(all(feature = "os", not(target_arch = "wasm32")))]
         if nu_experimental::NATIVE_CLIP.get() {
@@ -290,11 +330,13 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         // Date
         bind_command! {
             Date,
+            DateAdd,
             DateFromHuman,
             DateHumanize,
             DateListTimezones,
             DateNow,
             DateToTimezone,
+            DateWithTimezone,
         };
 
         // Shells
@@ -385,8 +427,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             MathMedian,
             MathMin,
             MathMode,
+            MathPercentile,
             MathProduct,
             MathRound,
+            MathSkew,
             MathSqrt,
             MathStddev,
             MathSum,
@@ -414,8 +458,15 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         // Network
         #[cfg(feature = "network")]
         bind_command! {
+            Dns,
+            DnsQuery,
+            Grpc,
+            GrpcCall,
             Http,
+            HttpAuth,
+            HttpAuthOauth2,
             HttpDelete,
+            HttpFetchModule,
             HttpGet,
             HttpHead,
             HttpPatch,
@@ -423,6 +474,11 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             HttpPut,
             HttpOptions,
             HttpPool,
+            HttpServe,
+            HttpSession,
+            Net,
+            NetConnect,
+            NetConnections,
             Port,
             VersionCheck,
         }
@@ -444,6 +500,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             RandomChars,
             RandomFloat,
             RandomInt,
+            RandomTable,
             RandomUuid,
             RandomBinary
         };
@@ -462,6 +519,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Hash,
             HashMd5::default(),
             HashSha256::default(),
+            HashBlake3::default(),
+            HashCrc32::default(),
+            HashXxh64::default(),
+            HashXxh3::default(),
         };
 
         // Experimental
@@ -507,6 +568,15 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             StorUpdate,
         };
 
+        // Sftp
+        #[cfg(feature = "sftp")]
+        bind_command! {
+            Sftp,
+            SftpGet,
+            SftpLs,
+            SftpPut,
+        };
+
         working_set.render()
     };
 
@@ -518,5 +588,11 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
     let table_decl_id = engine_state.find_decl("table".as_bytes(), &[]);
     engine_state.table_decl_id = table_decl_id;
 
+    // Let the parser find the modules fetched from URLs
+    #[cfg(feature = "network")]
+    {
+        engine_state.remote_modules = Some(std::sync::Arc::new(HttpModuleResolver));
+    }
+
     engine_state
 }
@@ -0,0 +1,399 @@
+use super::util::{target_paths, walk_paths};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use std::{fs, path::Path};
+
+#[derive(Clone)]
+pub struct Chmod;
+
+impl Command for Chmod {
+    fn name(&self) -> &str {
+        "chmod"
+    }
+
+    fn description(&self) -> &str {
+        "Change the permissions of files and directories."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The mode can be octal like 755, symbolic like u+x,go-w, a full mode like rwxr-xr-x as \
+         shown by `ls -l`, or a record with the `mode` or `readonly` column of `ls -l`. Without \
+         paths, the paths are read from the input, so the output of `ls` can be piped in. Like \
+         the system chmod, a mode that starts with `-`, like -x, can be given before the paths.
+
+On Windows, only the read-only attribute exists, which is set when the owner can't write."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["permissions", "mode", "readonly", "attrib"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("chmod")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::table(), Type::table()),
+                (Type::List(Box::new(Type::String)), Type::table()),
+            ])
+            .optional(
+                "mode",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::Record(vec![])]),
+                "The permissions to set.",
+            )
+            .rest(
+                "paths",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::String]),
+                "The paths to change the permissions of.",
+            )
+            .switch(
+                "recursive",
+                "Also change the permissions of everything inside directories.",
+                Some('R'),
+            )
+            // a mode like -x looks like a flag, and is passed as an unknown argument instead
+            .allows_unknown_args()
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mode: Value = call.req(engine_state, stack, 0)?;
+        let recursive = call.has_flag(engine_state, stack, "recursive")?;
+
+        let mode_span = mode.span();
+        let spec = match &mode {
+            Value::Record { val, .. } => match (val.get("mode"), val.get("readonly")) {
+                (Some(mode), _) => mode.coerce_string()?,
+                (None, Some(readonly)) if readonly.as_bool()? => "a-w".into(),
+                (None, Some(_)) => "u+w".into(),
+                (None, None) => {
+                    return Err(ShellError::CantFindColumn {
+                        col_name: "mode".into(),
+                        span: Some(head),
+                        src_span: mode_span,
+                    });
+                }
+            },
+            mode => mode.coerce_string()?,
+        };
+        // check the mode once up front, instead of failing on the first file
+        apply_mode(&spec, 0, false, 0).map_err(|msg| ShellError::IncorrectValue {
+            msg,
+            val_span: mode_span,
+            call_span: head,
+        })?;
+
+        #[cfg(unix)]
+        let umask = nu_system::get_umask();
+        #[cfg(not(unix))]
+        let umask = 0;
+
+        let mut changed = Vec::new();
+        for root in target_paths(engine_state, stack, call, 1, input)? {
+            walk_paths(
+                &root,
+                recursive,
+                head,
+                engine_state.signals(),
+                &mut |path| {
+                    let metadata = match fs::symlink_metadata(path) {
+                        // links have no permissions of their own, and aren't followed while
+                        // recursing
+                        Ok(metadata) if metadata.is_symlink() && path != root => {
+                            return Ok(());
+                        }
+                        _ => fs::metadata(path),
+                    }
+                    .map_err(|err| IoError::new(err, head, path.to_path_buf()))?;
+
+                    let current = mode_bits(&metadata);
+                    let new =
+                        apply_mode(&spec, current, metadata.is_dir(), umask).map_err(|msg| {
+                            ShellError::IncorrectValue {
+                                msg,
+                                val_span: mode_span,
+                                call_span: head,
+                            }
+                        })?;
+                    set_mode_bits(path, metadata.permissions(), new)
+                        .map_err(|err| IoError::new(err, head, path.to_path_buf()))?;
+                    changed.push(permission_record(path, new, head));
+                    Ok(())
+                },
+            )?;
+        }
+
+        Ok(Value::list(changed, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Make a script executable.",
+                example: "chmod +x build.nu",
+                result: None,
+            },
+            Example {
+                description: "Remove execute permissions, the same way as the system chmod.",
+                example: "chmod -x build.nu",
+                result: None,
+            },
+            Example {
+                description: "Let only the owner read and write a file.",
+                example: "chmod 600 secrets.toml",
+                result: None,
+            },
+            Example {
+                description: "Remove write access for the group and others from a whole directory.",
+                example: "chmod -R go-w project",
+                result: None,
+            },
+            Example {
+                description: "Give a file the same permissions as another one.",
+                example: "chmod (ls -l template.sh | first) new.sh",
+                result: None,
+            },
+            Example {
+                description: "Make all shell scripts in the current directory executable.",
+                example: "ls *.sh | chmod u+x",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn permission_record(path: &Path, mode: u32, span: Span) -> Value {
+    let mut record = record! {
+        "name" => Value::string(path.to_string_lossy(), span),
+        "readonly" => Value::bool(mode & 0o200 == 0, span),
+    };
+    #[cfg(unix)]
+    record.push(
+        "mode",
+        Value::string(umask::Mode::from(mode).to_string(), span),
+    );
+    Value::record(record, span)
+}
+
+#[cfg(unix)]
+fn mode_bits(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(unix)]
+fn set_mode_bits(path: &Path, mut permissions: fs::Permissions, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    permissions.set_mode(mode);
+    fs::set_permissions(path, permissions)
+}
+
+/// Windows only knows the read-only attribute, so files are treated as readable and writable by
+/// everyone unless they are read-only.
+#[cfg(not(unix))]
+fn mode_bits(metadata: &fs::Metadata) -> u32 {
+    match metadata.permissions().readonly() {
+        true => 0o444,
+        false => 0o666,
+    }
+}
+
+#[cfg(not(unix))]
+fn set_mode_bits(path: &Path, mut permissions: fs::Permissions, mode: u32) -> std::io::Result<()> {
+    permissions.set_readonly(mode & 0o200 == 0);
+    fs::set_permissions(path, permissions)
+}
+
+const USER: u8 = 1;
+const GROUP: u8 = 2;
+const OTHERS: u8 = 4;
+
+/// Applies `spec` to the mode `current`, the way `chmod` from coreutils does. Clauses without
+/// `u`, `g`, `o` or `a` leave the bits set in `umask` alone.
+fn apply_mode(spec: &str, current: u32, is_dir: bool, umask: u32) -> Result<u32, String> {
+    if !spec.is_empty() && spec.len() <= 4 && spec.chars().all(|c| c.is_digit(8)) {
+        return u32::from_str_radix(spec, 8).map_err(|err| err.to_string());
+    }
+    if let Some(mode) = parse_full_mode(spec) {
+        return Ok(mode);
+    }
+
+    let invalid = || format!("Invalid mode: {spec}.");
+    let mut mode = current;
+    for clause in spec.split(',') {
+        let mut chars = clause.chars().peekable();
+        let mut who = 0;
+        while let Some(c) = chars.next_if(|c| "ugoa".contains(*c)) {
+            who |= match c {
+                'u' => USER,
+                'g' => GROUP,
+                'o' => OTHERS,
+                _ => USER | GROUP | OTHERS,
+            };
+        }
+        let implicit = who == 0;
+        if implicit {
+            who = USER | GROUP | OTHERS;
+        }
+        let masked = if implicit { umask } else { 0 };
+
+        if chars.peek().is_none() {
+            return Err(invalid());
+        }
+        while let Some(op) = chars.next() {
+            if !"+-=".contains(op) {
+                return Err(invalid());
+            }
+            let mut rwx = 0;
+            let mut special = 0;
+            if let Some(class) = chars.next_if(|c| "ugo".contains(*c)) {
+                // copy the permissions of another class, like `g=u`
+                rwx = match class {
+                    'u' => (mode >> 6) & 0o7,
+                    'g' => (mode >> 3) & 0o7,
+                    _ => mode & 0o7,
+                };
+            } else {
+                while let Some(perm) = chars.next_if(|c| "rwxXst".contains(*c)) {
+                    match perm {
+                        'r' => rwx |= 0o4,
+                        'w' => rwx |= 0o2,
+                        'x' => rwx |= 0o1,
+                        'X' if is_dir || mode & 0o111 != 0 => rwx |= 0o1,
+                        's' => special |= special_bits(who) & 0o6000,
+                        't' => special |= special_bits(who) & 0o1000,
+                        _ => {}
+                    }
+                }
+            }
+
+            let bits = (spread(who, rwx) | special) & !masked;
+            let affected = (spread(who, 0o7) | special_bits(who)) & !masked;
+            mode = match op {
+                '+' => mode | bits,
+                '-' => mode & !bits,
+                _ => (mode & !affected) | bits,
+            };
+        }
+    }
+    Ok(mode)
+}
+
+/// Repeats the `rwx` bits for every class in `who`.
+fn spread(who: u8, rwx: u32) -> u32 {
+    let mut bits = 0;
+    if who & USER != 0 {
+        bits |= rwx << 6;
+    }
+    if who & GROUP != 0 {
+        bits |= rwx << 3;
+    }
+    if who & OTHERS != 0 {
+        bits |= rwx;
+    }
+    bits
+}
+
+/// The setuid, setgid and sticky bits that belong to the classes in `who`.
+fn special_bits(who: u8) -> u32 {
+    let mut bits = 0;
+    if who & USER != 0 {
+        bits |= 0o4000;
+    }
+    if who & GROUP != 0 {
+        bits |= 0o2000;
+    }
+    if who & OTHERS != 0 {
+        bits |= 0o1000;
+    }
+    bits
+}
+
+/// Parses a mode like `rwxr-x---` or `rwsr-xr-t`, as shown by `ls -l`.
+fn parse_full_mode(spec: &str) -> Option<u32> {
+    let chars: Vec<char> = spec.chars().collect();
+    if chars.len() != 9 {
+        return None;
+    }
+    let mut mode = 0;
+    for (class, triple) in chars.chunks(3).enumerate() {
+        let shift = 6 - 3 * class as u32;
+        let special = [0o4000, 0o2000, 0o1000][class];
+        let (special_set, special_unset) = if class == 2 { ('t', 'T') } else { ('s', 'S') };
+        match triple[0] {
+            'r' => mode |= 0o4 << shift,
+            '-' => {}
+            _ => return None,
+        }
+        match triple[1] {
+            'w' => mode |= 0o2 << shift,
+            '-' => {}
+            _ => return None,
+        }
+        match triple[2] {
+            'x' => mode |= 0o1 << shift,
+            c if c == special_set => mode |= (0o1 << shift) | special,
+            c if c == special_unset => mode |= special,
+            '-' => {}
+            _ => return None,
+        }
+    }
+    Some(mode)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Chmod {})
+    }
+
+    #[test]
+    fn test_octal_mode() {
+        assert_eq!(apply_mode("755", 0o600, false, 0o022), Ok(0o755));
+        assert_eq!(apply_mode("4750", 0, false, 0), Ok(0o4750));
+    }
+
+    #[test]
+    fn test_full_mode() {
+        assert_eq!(apply_mode("rwxr-x---", 0, false, 0), Ok(0o750));
+        assert_eq!(apply_mode("rwsr-xr-T", 0, false, 0), Ok(0o5754));
+        assert_eq!(parse_full_mode("rwxr-x--"), None);
+    }
+
+    #[test]
+    fn test_symbolic_mode() {
+        assert_eq!(apply_mode("u+x", 0o644, false, 0), Ok(0o744));
+        assert_eq!(apply_mode("go-w", 0o666, false, 0), Ok(0o644));
+        assert_eq!(apply_mode("u=rw,go=r", 0o777, false, 0), Ok(0o644));
+        assert_eq!(apply_mode("a+X", 0o644, true, 0), Ok(0o755));
+        assert_eq!(apply_mode("a+X", 0o644, false, 0), Ok(0o644));
+        assert_eq!(apply_mode("g=u", 0o640, false, 0), Ok(0o660));
+        assert_eq!(apply_mode("u+s,+t", 0o755, false, 0), Ok(0o5755));
+    }
+
+    #[test]
+    fn test_symbolic_mode_respects_umask() {
+        assert_eq!(apply_mode("+w", 0o444, false, 0o022), Ok(0o644));
+        assert_eq!(apply_mode("a+w", 0o444, false, 0o022), Ok(0o666));
+    }
+
+    #[test]
+    fn test_invalid_mode() {
+        assert!(apply_mode("u", 0, false, 0).is_err());
+        assert!(apply_mode("u*x", 0, false, 0).is_err());
+        assert!(apply_mode("999", 0, false, 0).is_err());
+    }
+}
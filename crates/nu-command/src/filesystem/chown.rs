@@ -0,0 +1,247 @@
+use super::util::{target_paths, walk_paths};
+use nix::unistd::{Gid, Group, Uid, User};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use nu_utils::filesystem::users;
+use std::{
+    fs,
+    os::unix::fs::{MetadataExt, chown, lchown},
+    path::Path,
+};
+
+#[derive(Clone)]
+pub struct Chown;
+
+impl Command for Chown {
+    fn name(&self) -> &str {
+        "chown"
+    }
+
+    fn description(&self) -> &str {
+        "Change the owner and group of files and directories."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The owner is given as user, user:group or :group, by name or id, or as a record with the \
+         `user` and `group` columns of `ls -l`. Without paths, the paths are read from the input, \
+         so the output of `ls` can be piped in."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["owner", "group", "permissions", "chgrp"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("chown")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::table(), Type::table()),
+                (Type::List(Box::new(Type::String)), Type::table()),
+            ])
+            .required(
+                "owner",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::Record(vec![])]),
+                "The new owner, group, or both.",
+            )
+            .rest(
+                "paths",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::String]),
+                "The paths to change the owner of.",
+            )
+            .switch(
+                "recursive",
+                "Also change the owner of everything inside directories.",
+                Some('R'),
+            )
+            .switch(
+                "no-dereference",
+                "Change the owner of symbolic links instead of their targets.",
+                None,
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let owner: Value = call.req(engine_state, stack, 0)?;
+        let recursive = call.has_flag(engine_state, stack, "recursive")?;
+        let no_dereference = call.has_flag(engine_state, stack, "no-dereference")?;
+
+        let (uid, gid) = parse_owner(&owner)?;
+
+        let mut changed = Vec::new();
+        for root in target_paths(engine_state, stack, call, 1, input)? {
+            walk_paths(
+                &root,
+                recursive,
+                head,
+                engine_state.signals(),
+                &mut |path| {
+                    // links found while recursing are never followed, like coreutils does
+                    let result = if no_dereference || path != root {
+                        lchown(path, uid, gid)
+                    } else {
+                        chown(path, uid, gid)
+                    };
+                    result.map_err(|err| IoError::new(err, head, path.to_path_buf()))?;
+                    changed.push(owner_record(path, head)?);
+                    Ok(())
+                },
+            )?;
+        }
+
+        Ok(Value::list(changed, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Give a file to another user.",
+                example: "chown alice notes.txt",
+                result: None,
+            },
+            Example {
+                description: "Change the owner and group of a directory and everything in it.",
+                example: "chown --recursive www-data:www-data /srv/site",
+                result: None,
+            },
+            Example {
+                description: "Change only the group.",
+                example: "chown :staff shared.txt",
+                result: None,
+            },
+            Example {
+                description: "Give a file the same owner and group as another one.",
+                example: "chown (ls -l template.sh | first) new.sh",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Reads the owner argument, returning `None` for the parts that stay unchanged.
+fn parse_owner(owner: &Value) -> Result<(Option<u32>, Option<u32>), ShellError> {
+    let span = owner.span();
+    let (user, group) = match owner {
+        Value::Record { val, .. } => {
+            let (user, group) = (val.get("user"), val.get("group"));
+            if user.is_none() && group.is_none() {
+                return Err(ShellError::CantFindColumn {
+                    col_name: "user".into(),
+                    span: None,
+                    src_span: span,
+                });
+            }
+            (
+                user.map(Value::coerce_string).transpose()?,
+                group.map(Value::coerce_string).transpose()?,
+            )
+        }
+        owner => {
+            let owner = owner.coerce_string()?;
+            match owner.split_once(':') {
+                Some((user, group)) => (
+                    (!user.is_empty()).then(|| user.to_string()),
+                    (!group.is_empty()).then(|| group.to_string()),
+                ),
+                None => (Some(owner), None),
+            }
+        }
+    };
+    if user.is_none() && group.is_none() {
+        return Err(ShellError::IncorrectValue {
+            msg: "Expected a user, a group, or both.".into(),
+            val_span: span,
+            call_span: span,
+        });
+    }
+
+    let uid = user
+        .map(|user| match user.parse::<u32>() {
+            Ok(uid) => Ok(uid),
+            Err(_) => match User::from_name(&user) {
+                Ok(Some(user)) => Ok(user.uid.as_raw()),
+                _ => Err(unknown("user", &user, span)),
+            },
+        })
+        .transpose()?;
+    let gid = group
+        .map(|group| match group.parse::<u32>() {
+            Ok(gid) => Ok(gid),
+            Err(_) => match Group::from_name(&group) {
+                Ok(Some(group)) => Ok(group.gid.as_raw()),
+                _ => Err(unknown("group", &group, span)),
+            },
+        })
+        .transpose()?;
+    Ok((uid, gid))
+}
+
+fn unknown(kind: &str, name: &str, span: Span) -> ShellError {
+    ShellError::IncorrectValue {
+        msg: format!("There is no {kind} named {name}."),
+        val_span: span,
+        call_span: span,
+    }
+}
+
+/// The owner of `path` the way `ls -l` shows it: by name, or by id if it has no name.
+fn owner_record(path: &Path, span: Span) -> Result<Value, ShellError> {
+    let metadata =
+        fs::symlink_metadata(path).map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+    let user = match users::get_user_by_uid(Uid::from_raw(metadata.uid())) {
+        Some(user) => Value::string(user.name, span),
+        None => Value::int(metadata.uid().into(), span),
+    };
+    let group = match users::get_group_by_gid(Gid::from_raw(metadata.gid())) {
+        Some(group) => Value::string(group.name, span),
+        None => Value::int(metadata.gid().into(), span),
+    };
+
+    Ok(Value::record(
+        record! {
+            "name" => Value::string(path.to_string_lossy(), span),
+            "user" => user,
+            "group" => group,
+        },
+        span,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Chown {})
+    }
+
+    #[test]
+    fn test_parse_owner_ids() {
+        let owner = |spec: &str| parse_owner(&Value::test_string(spec));
+
+        assert_eq!(owner("1000").ok(), Some((Some(1000), None)));
+        assert_eq!(owner("1000:100").ok(), Some((Some(1000), Some(100))));
+        assert_eq!(owner(":100").ok(), Some((None, Some(100))));
+        assert!(owner(":").is_err());
+    }
+
+    #[test]
+    fn test_parse_owner_record() {
+        let owner = Value::test_record(record! {
+            "user" => Value::test_int(0),
+            "group" => Value::test_int(0),
+        });
+
+        assert_eq!(parse_owner(&owner).ok(), Some((Some(0), Some(0))));
+    }
+}
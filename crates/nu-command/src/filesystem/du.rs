@@ -1,9 +1,10 @@
 use crate::{DirBuilder, DirInfo, FileInfo};
 use nu_engine::command_prelude::*;
 use nu_glob::{MatchOptions, Pattern};
-use nu_protocol::{NuGlob, PipelineMetadata, Signals};
+use nu_protocol::{Filesize, NuGlob, PipelineMetadata, Signals};
+use rayon::prelude::*;
 use serde::Deserialize;
-use std::path::Path;
+use std::{path::Path, sync::mpsc};
 
 #[derive(Clone)]
 pub struct Du;
@@ -19,6 +20,9 @@ pub struct DuArgs {
     max_depth: Option<Spanned<i64>>,
     #[serde(rename = "min-size")]
     min_size: Option<Spanned<i64>>,
+    threshold: Option<i64>,
+    #[serde(rename = "apparent-size")]
+    apparent_size: bool,
 }
 
 impl Command for Du {
@@ -30,6 +34,18 @@ impl Command for Du {
         "Find disk usage sizes of specified items."
     }
 
+    fn extra_description(&self) -> &str {
+        "The `apparent` column is the size of the contents of each item, and the `physical` \
+         column is the space it takes up on disk, which is usually larger because of block \
+         sizes, or smaller for sparse and compressed files.
+
+The --threshold option compares the space on disk, like the `du` of coreutils, unless \
+         --apparent-size is given.
+
+Directories are measured in parallel, and every item is output as soon as it's done, so the \
+         output isn't in any particular order."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build("du")
             .input_output_types(vec![(Type::Nothing, Type::table())])
@@ -67,6 +83,17 @@ impl Command for Du {
                 "Exclude files below this size.",
                 Some('m'),
             )
+            .named(
+                "threshold",
+                SyntaxShape::Filesize,
+                "Only output items that take up at least this much space, or at most this much if negative.",
+                Some('t'),
+            )
+            .switch(
+                "apparent-size",
+                "Compare the apparent size instead of the space on disk with --threshold.",
+                None,
+            )
             .switch("all", "Include hidden files if '*' is provided.", Some('a'))
             .category(Category::FileSystem)
     }
@@ -95,6 +122,10 @@ impl Command for Du {
                 span: min_size.span,
             });
         }
+        let threshold = call
+            .get_flag::<Filesize>(engine_state, stack, "threshold")?
+            .map(|size| size.get());
+        let apparent_size = call.has_flag(engine_state, stack, "apparent-size")?;
         let deref = call.has_flag(engine_state, stack, "deref")?;
         let long = call.has_flag(engine_state, stack, "long")?;
         let exclude = call.get_flag(engine_state, stack, "exclude")?;
@@ -118,6 +149,8 @@ impl Command for Du {
                     exclude,
                     max_depth,
                     min_size,
+                    threshold,
+                    apparent_size,
                 };
                 Ok(
                     du_for_one_pattern(args, &current_dir, tag, engine_state.signals().clone())?
@@ -142,6 +175,8 @@ impl Command for Du {
                         exclude: exclude.clone(),
                         max_depth,
                         min_size,
+                        threshold,
+                        apparent_size,
                     };
                     result_iters.push(du_for_one_pattern(
                         args,
@@ -168,11 +203,23 @@ impl Command for Du {
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
-        vec![Example {
-            description: "Disk usage of the current directory.",
-            example: "du",
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Disk usage of the current directory.",
+                example: "du",
+                result: None,
+            },
+            Example {
+                description: "Find the largest directories, looking at most two levels deep.",
+                example: "du --max-depth 2 --threshold 100mb | sort-by physical --reverse",
+                result: None,
+            },
+            Example {
+                description: "Find the files and directories that are empty or almost empty.",
+                example: "du --threshold -1kb",
+                result: None,
+            },
+        ]
     }
 }
 
@@ -227,24 +274,39 @@ fn du_for_one_pattern(
         deref,
         exclude,
         long,
+        threshold: args.threshold,
+        apparent_size: args.apparent_size,
     };
 
-    Ok(paths.filter_map(move |p| match p {
-        Ok(a) => {
-            if a.is_dir() {
-                match DirInfo::new(a, &params, max_depth, span, &signals) {
-                    Ok(v) => Some(Value::from(v)),
-                    Err(_) => None,
-                }
-            } else {
-                match FileInfo::new(a, deref, span, params.long) {
-                    Ok(v) => Some(Value::from(v)),
-                    Err(_) => None,
+    let (tx, rx) = mpsc::channel();
+    rayon::spawn(move || {
+        // sending only fails once the output isn't read anymore, which stops the walk
+        let _ = paths.par_bridge().try_for_each_with(tx, |tx, p| {
+            let value = match p {
+                Ok(a) => {
+                    let (apparent, physical, value) = if a.is_dir() {
+                        match DirInfo::new(a, &params, max_depth, span, &signals) {
+                            Ok(v) => (v.get_size(), v.get_blocks(), Value::from(v)),
+                            Err(_) => return Ok(()),
+                        }
+                    } else {
+                        match FileInfo::new(a, deref, span, params.long) {
+                            Ok(v) => (v.get_size(), v.get_blocks(), Value::from(v)),
+                            Err(_) => return Ok(()),
+                        }
+                    };
+                    if !params.within_threshold(apparent, physical) {
+                        return Ok(());
+                    }
+                    value
                 }
-            }
-        }
-        Err(e) => Some(Value::error(e, span)),
-    }))
+                Err(e) => Value::error(e, span),
+            };
+            tx.send(value)
+        });
+    });
+
+    Ok(rx.into_iter())
 }
 
 #[cfg(test)]
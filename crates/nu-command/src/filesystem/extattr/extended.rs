@@ -0,0 +1,209 @@
+//! The columns `ls --extended` adds to every entry.
+
+use nu_protocol::{Record, Span, Value};
+use std::{fs::Metadata, path::Path};
+
+/// Adds the extended attributes, ACL presence and immutability flags of `path` to `record`.
+/// Anything that can't be determined, for example because the filesystem doesn't support it, is
+/// left as null.
+pub(crate) fn push_columns(record: &mut Record, path: &Path, md: Option<&Metadata>, span: Span) {
+    // the attributes of the link itself, like `ls` shows the link and not its target
+    let names: Option<Vec<String>> = xattr::list(path).ok().map(|names| {
+        names
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect()
+    });
+
+    let xattrs = match &names {
+        Some(names) => Value::list(
+            names.iter().map(|name| Value::string(name, span)).collect(),
+            span,
+        ),
+        None => Value::nothing(span),
+    };
+    record.push("xattrs", xattrs);
+
+    let acl =
+        has_acl(path, names.as_deref()).map_or(Value::nothing(span), |acl| Value::bool(acl, span));
+    record.push("acl", acl);
+
+    let (immutable, append_only) = match md.and_then(|md| file_flags(path, md)) {
+        Some(flags) => (
+            Value::bool(flags.immutable, span),
+            Value::bool(flags.append_only, span),
+        ),
+        None => (Value::nothing(span), Value::nothing(span)),
+    };
+    record.push("immutable", immutable);
+    record.push("append_only", append_only);
+
+    #[cfg(target_os = "macos")]
+    {
+        let quarantine = names
+            .filter(|names| names.iter().any(|name| name == QUARANTINE))
+            .and_then(|_| xattr::get(path, QUARANTINE).ok().flatten())
+            .and_then(|value| parse_quarantine(&value, span))
+            .unwrap_or(Value::nothing(span));
+        record.push("quarantine", quarantine);
+    }
+}
+
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+struct FileFlags {
+    immutable: bool,
+    append_only: bool,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn has_acl(_path: &Path, names: Option<&[String]>) -> Option<bool> {
+    // POSIX ACLs beyond the permission bits are stored as extended attributes
+    names.map(|names| {
+        names
+            .iter()
+            .any(|name| name == "system.posix_acl_access" || name == "system.posix_acl_default")
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn file_flags(path: &Path, md: &Metadata) -> Option<FileFlags> {
+    use std::os::{fd::AsRawFd, unix::fs::OpenOptionsExt};
+
+    const FS_IMMUTABLE_FL: libc::c_long = 0x10;
+    const FS_APPEND_FL: libc::c_long = 0x20;
+
+    // opening a fifo or device could block or have side effects, and links have no flags
+    if !md.is_file() && !md.is_dir() {
+        return None;
+    }
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK | libc::O_NOFOLLOW)
+        .open(path)
+        .ok()?;
+
+    let mut flags: libc::c_long = 0;
+    // SAFETY: the descriptor is open for the duration of the call, and FS_IOC_GETFLAGS writes a
+    // single long to the pointer it is given.
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+    (result == 0).then_some(FileFlags {
+        immutable: flags & FS_IMMUTABLE_FL != 0,
+        append_only: flags & FS_APPEND_FL != 0,
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn has_acl(path: &Path, _names: Option<&[String]>) -> Option<bool> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    type AclT = *mut std::ffi::c_void;
+    type AclEntryT = *mut std::ffi::c_void;
+    const ACL_TYPE_EXTENDED: libc::c_int = 0x100;
+    const ACL_FIRST_ENTRY: libc::c_int = 0;
+
+    unsafe extern "C" {
+        fn acl_get_link_np(path: *const libc::c_char, acl_type: libc::c_int) -> AclT;
+        fn acl_get_entry(acl: AclT, entry_id: libc::c_int, entry: *mut AclEntryT) -> libc::c_int;
+        fn acl_free(obj: *mut std::ffi::c_void) -> libc::c_int;
+    }
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: the path is a valid C string, and the returned ACL is freed once it was inspected.
+    unsafe {
+        let acl = acl_get_link_np(path.as_ptr(), ACL_TYPE_EXTENDED);
+        if acl.is_null() {
+            // a file without an ACL is reported as ENOENT
+            return match std::io::Error::last_os_error().kind() {
+                std::io::ErrorKind::NotFound => Some(false),
+                _ => None,
+            };
+        }
+        let mut entry: AclEntryT = std::ptr::null_mut();
+        let has_entries = acl_get_entry(acl, ACL_FIRST_ENTRY, &mut entry) == 0;
+        acl_free(acl);
+        Some(has_entries)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn file_flags(_path: &Path, md: &Metadata) -> Option<FileFlags> {
+    use std::os::macos::fs::MetadataExt;
+
+    const UF_IMMUTABLE: u32 = 0x2;
+    const UF_APPEND: u32 = 0x4;
+    const SF_IMMUTABLE: u32 = 0x20000;
+    const SF_APPEND: u32 = 0x40000;
+
+    let flags = md.st_flags();
+    Some(FileFlags {
+        immutable: flags & (UF_IMMUTABLE | SF_IMMUTABLE) != 0,
+        append_only: flags & (UF_APPEND | SF_APPEND) != 0,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn has_acl(_path: &Path, _names: Option<&[String]>) -> Option<bool> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn file_flags(_path: &Path, _md: &Metadata) -> Option<FileFlags> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+const QUARANTINE: &str = "com.apple.quarantine";
+
+/// Parses the value of `com.apple.quarantine`, which looks like `flags;time;agent;event id`
+/// with the time as hexadecimal seconds since the Unix epoch.
+#[cfg(target_os = "macos")]
+fn parse_quarantine(value: &[u8], span: Span) -> Option<Value> {
+    let value = std::str::from_utf8(value).ok()?;
+    let mut fields = value.split(';');
+    let _flags = fields.next()?;
+    let time = fields
+        .next()
+        .and_then(|time| i64::from_str_radix(time, 16).ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map_or(Value::nothing(span), |time| {
+            Value::date(time.fixed_offset(), span)
+        });
+    let string_or_null = |field: Option<&str>| match field {
+        Some(field) if !field.is_empty() => Value::string(field, span),
+        _ => Value::nothing(span),
+    };
+
+    Some(Value::record(
+        nu_protocol::record! {
+            "agent" => string_or_null(fields.next()),
+            "time" => time,
+            "event_id" => string_or_null(fields.next()),
+        },
+        span,
+    ))
+}
+
+#[cfg(all(test, target_os = "macos"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_quarantine() {
+        let value = parse_quarantine(
+            b"0083;65a7c3f0;Safari;6F1B0D6C-7B8E-4D9A-9C5E-2F3A4B5C6D7E",
+            Span::test_data(),
+        )
+        .expect("valid quarantine value");
+
+        assert_eq!(
+            value.get_data_by_key("agent"),
+            Some(Value::test_string("Safari"))
+        );
+        assert_eq!(
+            value
+                .get_data_by_key("time")
+                .and_then(|time| time.as_date().ok())
+                .map(|time| time.timestamp()),
+            Some(0x65a7c3f0)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::io::IoError;
+
+#[derive(Clone)]
+pub struct XattrGet;
+
+impl Command for XattrGet {
+    fn name(&self) -> &str {
+        "xattr get"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("xattr get")
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The file to read the attributes of.",
+            )
+            .optional(
+                "name",
+                SyntaxShape::String,
+                "The attribute to get. Default: all of them, as a record.",
+            )
+            .switch(
+                "binary",
+                "Return values as binary, even if they are valid UTF-8.",
+                Some('b'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Get the extended attributes of a file."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Values are returned as strings if they are valid UTF-8, and as binary otherwise. \
+         Symbolic links are followed."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["attributes", "metadata", "getfattr"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let name: Option<Spanned<String>> = call.opt(engine_state, stack, 1)?;
+        let binary = call.has_flag(engine_state, stack, "binary")?;
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let full_path = expand_path_with(&path.item, &cwd, true);
+        let io_error = |err| IoError::new(err, path.span, full_path.clone());
+        let to_value = |value: Vec<u8>| match String::from_utf8(value) {
+            Ok(string) if !binary => Value::string(string, head),
+            Ok(string) => Value::binary(string.into_bytes(), head),
+            Err(err) => Value::binary(err.into_bytes(), head),
+        };
+
+        let value = match name {
+            Some(name) => {
+                let value = xattr::get_deref(&full_path, &name.item)
+                    .map_err(io_error)?
+                    .ok_or_else(|| ShellError::GenericError {
+                        error: "Extended attribute not found".into(),
+                        msg: format!("{} has no attribute {}", path.item, name.item),
+                        span: Some(name.span),
+                        help: None,
+                        inner: vec![],
+                    })?;
+                to_value(value)
+            }
+            None => {
+                let mut record = Record::new();
+                for name in xattr::list_deref(&full_path).map_err(io_error)? {
+                    // an attribute removed in the meantime is left out
+                    if let Some(value) = xattr::get_deref(&full_path, &name).map_err(io_error)? {
+                        record.push(name.to_string_lossy(), to_value(value));
+                    }
+                }
+                Value::record(record, head)
+            }
+        };
+
+        Ok(value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get all extended attributes of a file.",
+                example: "xattr get notes.txt",
+                result: None,
+            },
+            Example {
+                description: "Get a single attribute.",
+                example: "xattr get notes.txt user.checksum",
+                result: None,
+            },
+            Example {
+                description: "Find the files in a directory that have a given attribute.",
+                example: "ls | where { xattr get $in.name | columns | 'user.reviewed' in $in }",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(XattrGet {})
+    }
+}
@@ -0,0 +1,8 @@
+pub(crate) mod extended;
+mod get;
+mod set;
+mod xattr_;
+
+pub use get::XattrGet;
+pub use set::XattrSet;
+pub use xattr_::Xattr;
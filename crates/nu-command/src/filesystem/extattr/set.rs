@@ -0,0 +1,120 @@
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::io::IoError;
+
+#[derive(Clone)]
+pub struct XattrSet;
+
+impl Command for XattrSet {
+    fn name(&self) -> &str {
+        "xattr set"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("xattr set")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The file to set the attribute on.",
+            )
+            .required("name", SyntaxShape::String, "The name of the attribute.")
+            .optional(
+                "value",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::Binary]),
+                "The value to set. Required unless --remove is given.",
+            )
+            .switch("remove", "Remove the attribute instead.", Some('r'))
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Set or remove an extended attribute of a file."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Symbolic links are followed. On Linux, attributes set by users have to be in the `user.` \
+         namespace."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["attributes", "metadata", "setfattr"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let name: String = call.req(engine_state, stack, 1)?;
+        let value: Option<Value> = call.opt(engine_state, stack, 2)?;
+        let remove = call.has_flag(engine_state, stack, "remove")?;
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let full_path = expand_path_with(&path.item, &cwd, true);
+        let io_error = |err| IoError::new(err, path.span, full_path.clone());
+
+        match (value, remove) {
+            (None, true) => xattr::remove_deref(&full_path, &name).map_err(io_error)?,
+            (Some(value), false) => {
+                let bytes = match value {
+                    Value::Binary { val, .. } => val,
+                    value => value.coerce_into_string()?.into_bytes(),
+                };
+                xattr::set_deref(&full_path, &name, &bytes).map_err(io_error)?
+            }
+            (Some(value), true) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "a value can't be given".into(),
+                    left_span: value.span(),
+                    right_message: "when removing the attribute".into(),
+                    right_span: call.get_flag_span(stack, "remove").unwrap_or(head),
+                });
+            }
+            (None, false) => {
+                return Err(ShellError::MissingParameter {
+                    param_name: "value".into(),
+                    span: head,
+                });
+            }
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Mark a file as reviewed.",
+                example: "xattr set notes.txt user.reviewed yes",
+                result: None,
+            },
+            Example {
+                description: "Store a binary value.",
+                example: "xattr set image.png user.thumbnail-hash 0x[de ad be ef]",
+                result: None,
+            },
+            Example {
+                description: "Remove the quarantine attribute macOS adds to downloaded files.",
+                example: "xattr set --remove app.dmg com.apple.quarantine",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(XattrSet {})
+    }
+}
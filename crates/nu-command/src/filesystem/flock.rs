@@ -0,0 +1,178 @@
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_path::expand_path_with;
+use nu_protocol::{engine::Closure, shell_error::io::IoError};
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often a lock that is held by someone else is tried again.
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone)]
+pub struct Flock;
+
+impl Command for Flock {
+    fn name(&self) -> &str {
+        "flock"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure while holding an advisory lock on a file."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The lock is exclusive by default, so only one closure at a time can hold it. Shared locks \
+         can be held by any number of closures at once, but not at the same time as an exclusive \
+         lock. The file is created if it doesn't exist, and is otherwise left as it is.
+
+The locks are advisory: they only keep out other processes that lock the same file, like other \
+         `flock` calls, but not processes that simply open it.
+
+Any pipeline input is passed to the closure. The output of the closure is collected before the \
+         lock is released, so that nothing reads from it afterwards."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["lock", "mutex", "lockfile", "synchronize", "concurrent"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("flock")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .required("path", SyntaxShape::Filepath, "The file to lock.")
+            .required(
+                "closure",
+                SyntaxShape::Closure(None),
+                "The closure to run while holding the lock.",
+            )
+            .switch(
+                "shared",
+                "Take a shared lock instead of an exclusive one.",
+                Some('s'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "Give up if the lock can't be taken within this time, instead of waiting for as long as it takes.",
+                Some('t'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+        let shared = call.has_flag(engine_state, stack, "shared")?;
+        let timeout = match call.get_flag::<Value>(engine_state, stack, "timeout")? {
+            Some(value) => {
+                let nanos = value.as_duration()?;
+                if nanos < 0 {
+                    return Err(ShellError::NeedsPositiveValue { span: value.span() });
+                }
+                Some(Duration::from_nanos(nanos as u64))
+            }
+            None => None,
+        };
+
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let full_path = expand_path_with(&path.item, &cwd, true);
+        let io_error = |err: std::io::Error| IoError::new(err, path.span, full_path.clone());
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&full_path)
+            .map_err(io_error)?;
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        // trying again and again instead of blocking makes it possible to interrupt the wait
+        loop {
+            match try_lock(&file, shared) {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(err)) => return Err(io_error(err).into()),
+            }
+            let wait = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(timed_out(&full_path, call, stack, head));
+                    }
+                    RETRY_INTERVAL.min(remaining)
+                }
+                None => RETRY_INTERVAL,
+            };
+            thread::sleep(wait);
+            engine_state.signals().check(&head)?;
+        }
+
+        let result = ClosureEvalOnce::new(engine_state, stack, closure)
+            .run_with_input(input)
+            .and_then(|output| output.into_value(head));
+        // closing the file would release the lock as well, but this reports errors
+        file.unlock().map_err(io_error)?;
+
+        Ok(result?.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Increment a counter that other scripts update as well.",
+                example: "flock counter.lock { open counter.txt | into int | $in + 1 | save --force counter.txt }",
+                result: None,
+            },
+            Example {
+                description: "Read a state file while no one is writing to it, but allow other readers.",
+                example: "flock --shared state.json { open state.json }",
+                result: None,
+            },
+            Example {
+                description: "Give up if another job holds the lock for more than 5 seconds.",
+                example: "flock --timeout 5sec deploy.lock { ./deploy.nu }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn try_lock(file: &File, shared: bool) -> Result<(), TryLockError> {
+    if shared {
+        file.try_lock_shared()
+    } else {
+        file.try_lock()
+    }
+}
+
+fn timed_out(path: &Path, call: &Call, stack: &Stack, head: Span) -> ShellError {
+    ShellError::GenericError {
+        error: "Timed out waiting for the lock".into(),
+        msg: format!("{} is still locked by someone else", path.display()),
+        span: Some(call.get_flag_span(stack, "timeout").unwrap_or(head)),
+        help: None,
+        inner: vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Flock {})
+    }
+}
@@ -1,6 +1,11 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use nu_engine::command_prelude::*;
 use nu_protocol::{ListStream, Signals};
-use wax::{Glob as WaxGlob, WalkBehavior, WalkEntry};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use wax::{FileIterator, FilterTarget, Glob as WaxGlob, WalkBehavior, WalkEntry};
 
 #[derive(Clone)]
 pub struct Glob;
@@ -46,6 +51,11 @@ impl Command for Glob {
                 "Patterns to exclude from the search: `glob` will not walk the inside of directories matching the excluded patterns.",
                 Some('e'),
             )
+            .switch(
+                "gitignore",
+                "Leave out the paths ignored by .gitignore and .ignore files, like ripgrep does.",
+                Some('g'),
+            )
             .category(Category::FileSystem)
     }
 
@@ -114,6 +124,11 @@ impl Command for Glob {
                 example: r#"glob **/* --exclude [**/target/** **/.git/** */]"#,
                 result: None,
             },
+            Example {
+                description: "Search for the Rust files of a project, skipping ignored ones like the build output.",
+                example: r#"glob **/*.rs --gitignore"#,
+                result: None,
+            },
             Example {
                 description: "Search for files following symbolic links to their targets.",
                 example: r#"glob "**/*.txt" --follow-symlinks"#,
@@ -123,7 +138,9 @@ impl Command for Glob {
     }
 
     fn extra_description(&self) -> &str {
-        r#"For more glob pattern help, please refer to https://docs.rs/crate/wax/latest"#
+        r#"Matches are streamed as they are found, so `glob **/* | first 10` returns as soon as ten paths were found.
+
+For more glob pattern help, please refer to https://docs.rs/crate/wax/latest"#
     }
 
     fn run(
@@ -142,6 +159,7 @@ impl Command for Glob {
         let no_symlinks = call.has_flag(engine_state, stack, "no-symlink")?;
         let follow_symlinks = call.has_flag(engine_state, stack, "follow-symlinks")?;
         let paths_to_exclude: Option<Value> = call.get_flag(engine_state, stack, "exclude")?;
+        let gitignore = call.has_flag(engine_state, stack, "gitignore")?;
 
         let (not_patterns, not_pattern_span): (Vec<String>, Span) = match paths_to_exclude {
             None => (vec![], span),
@@ -233,6 +251,20 @@ impl Command for Glob {
             }
         };
 
+        // Ignored directories are pruned from the walk, so nothing in them is read
+        let mut ignore_filter = gitignore.then(|| IgnoreFilter::new(&path));
+        let prune_ignored = move |entry: &WalkEntry| {
+            let is_dir = entry.file_type().is_dir();
+            ignore_filter
+                .as_mut()?
+                .is_ignored(entry.path(), is_dir)
+                .then_some(if is_dir {
+                    FilterTarget::Tree
+                } else {
+                    FilterTarget::File
+                })
+        };
+
         let link_behavior = match follow_symlinks {
             true => wax::LinkBehavior::ReadTarget,
             false => wax::LinkBehavior::ReadFile,
@@ -257,6 +289,7 @@ impl Command for Glob {
                     help: None,
                     inner: vec![],
                 })?
+                .filter_tree(prune_ignored)
                 .flatten();
             glob_to_value(
                 engine_state.signals(),
@@ -276,6 +309,7 @@ impl Command for Glob {
                     },
                 )
                 .into_owned()
+                .filter_tree(prune_ignored)
                 .flatten();
             glob_to_value(
                 engine_state.signals(),
@@ -357,6 +391,83 @@ fn glob_to_value(
     ListStream::new(result, span, signals.clone())
 }
 
+/// Decides whether paths are ignored by the .gitignore and .ignore files of their directories,
+/// up to the root of the git repository they are in.
+struct IgnoreFilter {
+    /// The topmost directory whose ignore files apply.
+    top: PathBuf,
+    /// The ignore rules of every directory seen so far.
+    rules: HashMap<PathBuf, Gitignore>,
+    /// The user's global gitignore, set with `core.excludesFile`.
+    global: Gitignore,
+}
+
+impl IgnoreFilter {
+    fn new(root: &Path) -> Self {
+        let top = root
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .unwrap_or(root)
+            .to_path_buf();
+        let mut rules = HashMap::new();
+        // the repository's own excludes apply everywhere in it, like a top-level .gitignore
+        let mut builder = GitignoreBuilder::new(&top);
+        for file in [".git/info/exclude", ".gitignore", ".ignore"] {
+            // unreadable or missing files are treated as empty
+            let _ = builder.add(top.join(file));
+        }
+        rules.insert(
+            top.clone(),
+            builder.build().unwrap_or_else(|_| Gitignore::empty()),
+        );
+
+        Self {
+            top,
+            rules,
+            global: Gitignore::global().0,
+        }
+    }
+
+    fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.top) else {
+            return false;
+        };
+        if relative.components().any(|part| part.as_os_str() == ".git") {
+            return true;
+        }
+
+        // the rules of deeper directories take precedence, a whitelist (`!pattern`) included
+        let top = self.top.clone();
+        for dir in path.ancestors().skip(1) {
+            let rules = self.rules.entry(dir.to_path_buf()).or_insert_with(|| {
+                let mut builder = GitignoreBuilder::new(dir);
+                let _ = builder.add(dir.join(".gitignore"));
+                let _ = builder.add(dir.join(".ignore"));
+                builder.build().unwrap_or_else(|_| Gitignore::empty())
+            });
+            let matched = rules.matched_path_or_any_parents(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            } else if matched.is_whitelist() {
+                return false;
+            }
+            if dir == top {
+                break;
+            }
+        }
+
+        // the global rules aren't rooted anywhere, so check the parents one by one
+        let mut is_dir = is_dir;
+        for path in path.ancestors().take_while(|path| *path != top) {
+            if self.global.matched(path, is_dir).is_ignore() {
+                return true;
+            }
+            is_dir = true;
+        }
+        false
+    }
+}
+
 #[cfg(windows)]
 #[cfg(test)]
 mod windows_tests {
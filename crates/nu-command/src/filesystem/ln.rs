@@ -0,0 +1,298 @@
+use nu_engine::command_prelude::*;
+use nu_path::{expand_path_with, expand_tilde};
+use nu_protocol::shell_error::io::IoError;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone)]
+pub struct Ln;
+
+impl Command for Ln {
+    fn name(&self) -> &str {
+        "ln"
+    }
+
+    fn description(&self) -> &str {
+        "Create hard links, symbolic links or directory junctions."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Without a link path, the link is created in the current directory with the name of the \
+         target. If the link path is a directory, the link is created inside of it. To create \
+         many links at once, pipe in a table with `target` and `link` columns.
+
+Creating symbolic links on Windows requires either developer mode or administrator rights, \
+         which junctions don't need."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["link", "symlink", "hardlink", "junction", "mklink"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ln")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::table(), Type::Nothing),
+            ])
+            .optional(
+                "target",
+                SyntaxShape::String,
+                "The file or directory the link points to.",
+            )
+            .optional("link", SyntaxShape::Filepath, "The path of the link.")
+            .switch(
+                "symbolic",
+                "Create a symbolic link instead of a hard link.",
+                Some('s'),
+            )
+            .switch(
+                "relative",
+                "Point symbolic links to their target relative to the directory of the link.",
+                Some('r'),
+            )
+            .switch(
+                "junction",
+                "Create a directory junction instead of a symbolic link (Windows only).",
+                Some('j'),
+            )
+            .switch(
+                "force",
+                "Replace files that already exist at the link path.",
+                Some('f'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let target: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let link: Option<Spanned<String>> = call.opt(engine_state, stack, 1)?;
+        let options = LinkOptions {
+            symbolic: call.has_flag(engine_state, stack, "symbolic")?,
+            relative: call.has_flag(engine_state, stack, "relative")?,
+            junction: call.has_flag(engine_state, stack, "junction")?,
+            force: call.has_flag(engine_state, stack, "force")?,
+        };
+        if options.relative
+            && !options.symbolic
+            && let Some(span) = call.get_flag_span(stack, "relative")
+        {
+            return Err(ShellError::GenericError {
+                error: "Only symbolic links can be relative".into(),
+                msg: "hard links and junctions always point to an absolute path".into(),
+                span: Some(span),
+                help: Some("Add --symbolic to create a symbolic link.".into()),
+                inner: vec![],
+            });
+        }
+        if options.junction && !cfg!(windows) {
+            return Err(ShellError::GenericError {
+                error: "Junctions are not supported".into(),
+                msg: "junctions only exist on Windows".into(),
+                span: call.get_flag_span(stack, "junction"),
+                help: Some("Use --symbolic to create a symbolic link instead.".into()),
+                inner: vec![],
+            });
+        }
+
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        if let Some(target) = target {
+            create_link(&target, link.as_ref(), &options, &cwd)?;
+            return Ok(PipelineData::empty());
+        }
+
+        let mut created = false;
+        for row in input {
+            let row = row.unwrap_error()?;
+            let span = row.span();
+            let column = |name: &str| -> Result<Option<Spanned<String>>, ShellError> {
+                row.get_data_by_key(name)
+                    .filter(|value| !value.is_nothing())
+                    .map(|value| {
+                        let span = value.span();
+                        value
+                            .coerce_into_string()
+                            .map(|item| item.into_spanned(span))
+                    })
+                    .transpose()
+            };
+            let target = column("target")?.ok_or_else(|| ShellError::CantFindColumn {
+                col_name: "target".into(),
+                span: Some(head),
+                src_span: span,
+            })?;
+            create_link(&target, column("link")?.as_ref(), &options, &cwd)?;
+            created = true;
+        }
+        if !created {
+            return Err(ShellError::MissingParameter {
+                param_name: "target".into(),
+                span: head,
+            });
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Create a hard link to a file.",
+                example: "ln notes.txt notes-backup.txt",
+                result: None,
+            },
+            Example {
+                description: "Create a symbolic link in the current directory, named after its target.",
+                example: "ln --symbolic /etc/nginx/nginx.conf",
+                result: None,
+            },
+            Example {
+                description: "Link a config file into place, with a link that keeps working when both are moved together.",
+                example: "ln --symbolic --relative --force dotfiles/config.nu ~/.config/nushell/config.nu",
+                result: None,
+            },
+            Example {
+                description: "Create many symbolic links at once.",
+                example: "[[target link]; [bin/app.exe app.exe] [share/app docs]] | ln --symbolic",
+                result: None,
+            },
+            Example {
+                description: "Create a directory junction on Windows.",
+                example: r"ln --junction D:\projects C:\Users\me\projects",
+                result: None,
+            },
+        ]
+    }
+}
+
+struct LinkOptions {
+    symbolic: bool,
+    relative: bool,
+    junction: bool,
+    force: bool,
+}
+
+fn create_link(
+    target: &Spanned<String>,
+    link: Option<&Spanned<String>>,
+    options: &LinkOptions,
+    cwd: &Path,
+) -> Result<(), ShellError> {
+    let target_path = expand_path_with(&target.item, cwd, true);
+    let name_error = || ShellError::GenericError {
+        error: "Can't name the link after its target".into(),
+        msg: "the target has no file name".into(),
+        span: Some(target.span),
+        help: Some("Give the path of the link as well.".into()),
+        inner: vec![],
+    };
+
+    let (mut link_path, link_span) = match link {
+        Some(link) => (expand_path_with(&link.item, cwd, true), link.span),
+        None => (cwd.to_path_buf(), target.span),
+    };
+    // like coreutils, a link to a directory is created inside of it
+    if link_path.is_dir() {
+        link_path.push(target_path.file_name().ok_or_else(name_error)?);
+    }
+
+    // checked before anything is removed, as `--force` would otherwise delete the target
+    let target_entry = canonical_entry(&target_path);
+    if target_entry.is_some() && target_entry == canonical_entry(&link_path) {
+        return Err(ShellError::GenericError {
+            error: "Can't link a file to itself".into(),
+            msg: "the link would replace its own target".into(),
+            span: Some(link_span),
+            help: Some("Give a different path for the link.".into()),
+            inner: vec![],
+        });
+    }
+
+    let io_error = |err: std::io::Error| IoError::new(err, link_span, link_path.clone());
+    if options.force
+        && let Ok(metadata) = fs::symlink_metadata(&link_path)
+        && !metadata.is_dir()
+    {
+        fs::remove_file(&link_path).map_err(io_error)?;
+    }
+
+    if options.junction {
+        create_junction(&target_path, &link_path).map_err(io_error)?;
+        return Ok(());
+    }
+    if !options.symbolic {
+        return fs::hard_link(&target_path, &link_path)
+            .map_err(|err| IoError::new(err, target.span, target_path.clone()).into());
+    }
+
+    let contents = if options.relative {
+        let link_dir = link_path.parent().unwrap_or(cwd);
+        pathdiff::diff_paths(&target_path, link_dir).unwrap_or_else(|| target_path.clone())
+    } else {
+        // relative targets are kept as they are, and resolved against the link's directory
+        expand_tilde(&target.item)
+    };
+    create_symlink(&contents, &target_path, &link_path).map_err(io_error)?;
+    Ok(())
+}
+
+/// The canonical form of `path` without following it if it's a symlink itself, so that a link is
+/// told apart from what it points to.
+fn canonical_entry(path: &Path) -> Option<PathBuf> {
+    fs::symlink_metadata(path).ok()?;
+    match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => dir.canonicalize().ok().map(|dir| dir.join(name)),
+        _ => path.canonicalize().ok(),
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(contents: &Path, _target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(contents, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(contents: &Path, target: &Path, link: &Path) -> std::io::Result<()> {
+    // Windows needs to know whether a link points to a directory when creating it
+    if target.is_dir() {
+        std::os::windows::fs::symlink_dir(contents, link)
+    } else {
+        std::os::windows::fs::symlink_file(contents, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_symlink(_contents: &Path, _target: &Path, _link: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(windows)]
+fn create_junction(target: &Path, link: &Path) -> std::io::Result<()> {
+    junction::create(target, link)
+}
+
+#[cfg(not(windows))]
+fn create_junction(_target: &Path, _link: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Ln {})
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{DirBuilder, DirInfo};
 use chrono::{DateTime, Local, LocalResult, TimeZone, Utc};
+use filesize::file_real_size_fast;
 use nu_engine::{command_prelude::*, glob_from};
 use nu_glob::MatchOptions;
 use nu_path::{expand_path_with, expand_to_real_path};
@@ -136,9 +137,11 @@ struct Args {
     short_names: bool,
     full_paths: bool,
     du: bool,
+    extended: bool,
     directory: bool,
     use_mime_type: bool,
     use_threads: bool,
+    stream: bool,
     call_span: Span,
 }
 
@@ -178,6 +181,11 @@ impl Command for Ls {
                 "Display the apparent directory size (\"disk usage\") in place of the directory metadata size.",
                 Some('d'),
             )
+            .switch(
+                "extended",
+                "Add columns for extended attributes, ACL presence and immutability flags (Unix only).",
+                Some('x'),
+            )
             .switch(
                 "directory",
                 "List the specified directory itself instead of its contents.",
@@ -185,6 +193,7 @@ impl Command for Ls {
             )
             .switch("mime-type", "Show mime-type in type column instead of 'file' (based on filenames only; files' contents are not examined).", Some('m'))
             .switch("threads", "Use multiple threads to list contents. Output will be non-deterministic.", Some('t'))
+            .switch("stream", "Output entries as soon as they are read, in the order the directory lists them, instead of sorting them first.", None)
             .category(Category::FileSystem)
     }
 
@@ -200,9 +209,11 @@ impl Command for Ls {
         let short_names = call.has_flag(engine_state, stack, "short-names")?;
         let full_paths = call.has_flag(engine_state, stack, "full-paths")?;
         let du = call.has_flag(engine_state, stack, "du")?;
+        let extended = call.has_flag(engine_state, stack, "extended")?;
         let directory = call.has_flag(engine_state, stack, "directory")?;
         let use_mime_type = call.has_flag(engine_state, stack, "mime-type")?;
         let use_threads = call.has_flag(engine_state, stack, "threads")?;
+        let stream = call.has_flag(engine_state, stack, "stream")?;
         let call_span = call.head;
         let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
 
@@ -212,9 +223,11 @@ impl Command for Ls {
             short_names,
             full_paths,
             du,
+            extended,
             directory,
             use_mime_type,
             use_threads,
+            stream,
             call_span,
         };
 
@@ -315,6 +328,16 @@ impl Command for Ls {
                 example: "ls ...(glob **/*.{rs,toml})",
                 result: None,
             },
+            Example {
+                description: "Find sparse files, like VM images, and how much space they really take up.",
+                example: "ls -l | where sparse | select name size allocated",
+                result: None,
+            },
+            Example {
+                description: "Look at a few entries of a directory with millions of them, without waiting for all of them to be read.",
+                example: "ls --stream huge_directory | first 10",
+                result: None,
+            },
             Example {
                 description: "List given paths and show directories themselves.",
                 example: "['/path/to/directory' '/path/to/file'] | each {|| ls -D $in } | flatten",
@@ -354,11 +377,15 @@ fn ls_for_one_pattern(
         short_names,
         full_paths,
         du,
+        extended,
         directory,
         use_mime_type,
         use_threads,
+        stream,
         call_span,
     } = args;
+    // sorting needs every entry, so the first one can only be output after reading the last
+    let sort = !use_threads && !stream;
     let pattern_arg = {
         if let Some(path) = pattern_arg {
             // it makes no sense to list an empty string.
@@ -394,10 +421,7 @@ fn ls_for_one_pattern(
                 nu_path::expand_path_with(pat.item.as_ref(), &cwd, pat.item.is_expand());
             // Avoid checking and pushing "*" to the path when directory (do not show contents) flag is true
             if !directory && tmp_expanded.is_dir() {
-                if read_dir(tmp_expanded, p_tag, use_threads, signals.clone())?
-                    .next()
-                    .is_none()
-                {
+                if dir_is_empty(&tmp_expanded, p_tag)? {
                     return Ok(Value::test_nothing().into_pipeline_data());
                 }
                 just_read_dir = !(pat.item.is_expand() && nu_glob::is_glob(pat.item.as_ref()));
@@ -416,11 +440,12 @@ fn ls_for_one_pattern(
             // Avoid pushing "*" to the default path when directory (do not show contents) flag is true
             if directory {
                 (NuGlob::Expand(".".to_string()), false)
-            } else if read_dir(cwd.clone(), p_tag, use_threads, signals.clone())?
-                .next()
-                .is_none()
-            {
+            } else if dir_is_empty(&cwd, p_tag)? {
                 return Ok(Value::test_nothing().into_pipeline_data());
+            } else if stream {
+                // globbing sorts the entries as well, so the directory is read directly instead
+                just_read_dir = true;
+                (NuGlob::Expand(".".to_string()), false)
             } else {
                 (NuGlob::Expand("*".to_string()), false)
             }
@@ -435,7 +460,7 @@ fn ls_for_one_pattern(
         Box<dyn Iterator<Item = Result<LsEntry, ShellError>> + Send>,
     ) = if just_read_dir {
         let expanded = nu_path::expand_path_with(path.item.as_ref(), &cwd, path.item.is_expand());
-        let paths = read_dir(expanded.clone(), p_tag, use_threads, signals.clone())?;
+        let paths = read_dir(expanded.clone(), p_tag, sort, signals.clone())?;
         // just need to read the directory, so prefix is path itself.
         (Some(expanded), paths)
     } else {
@@ -575,6 +600,7 @@ fn ls_for_one_pattern(
                                     call_span,
                                     long,
                                     du,
+                                    extended,
                                     &signals_clone,
                                     use_mime_type,
                                     full_paths,
@@ -716,6 +742,7 @@ pub(crate) fn dir_entry_dict(
     span: Span,
     long: bool,
     du: bool,
+    extended: bool,
     signals: &Signals,
     use_mime_type: bool,
     full_symlink_target: bool,
@@ -816,6 +843,14 @@ pub(crate) fn dir_entry_dict(
         }
     }
 
+    let dir_info = match metadata {
+        Some(md) if du && md.is_dir() => {
+            let params = DirBuilder::new(Span::new(0, 2), None, false, None, false, None, true);
+            Some(DirInfo::new(filename, &params, None, span, signals)?)
+        }
+        _ => None,
+    };
+
     record.push(
         "size",
         if let Some(md) = metadata {
@@ -825,11 +860,8 @@ pub(crate) fn dir_entry_dict(
                 || file_type == "block device";
 
             if md.is_dir() {
-                if du {
-                    let params = DirBuilder::new(Span::new(0, 2), None, false, None, false);
-                    let dir_size = DirInfo::new(filename, &params, None, span, signals)?.get_size();
-
-                    Value::filesize(dir_size as i64, span)
+                if let Some(dir_info) = &dir_info {
+                    Value::filesize(dir_info.get_size() as i64, span)
                 } else {
                     let dir_size: u64 = md.len();
 
@@ -853,6 +885,29 @@ pub(crate) fn dir_entry_dict(
         },
     );
 
+    if long {
+        let allocated = match (metadata, &dir_info) {
+            (_, Some(dir_info)) => Some(dir_info.get_blocks()),
+            (Some(md), None) if md.is_file() || md.is_dir() => {
+                file_real_size_fast(filename, md).ok()
+            }
+            _ => None,
+        };
+        record.push(
+            "allocated",
+            allocated.map_or(Value::nothing(span), |allocated| {
+                Value::filesize(allocated as i64, span)
+            }),
+        );
+        record.push(
+            "sparse",
+            match metadata {
+                Some(md) if md.is_file() => Value::bool(is_sparse(md, allocated), span),
+                _ => Value::nothing(span),
+            },
+        );
+    }
+
     if let Some(md) = metadata {
         if long {
             record.push("created", {
@@ -894,9 +949,31 @@ pub(crate) fn dir_entry_dict(
         record.push("modified", Value::nothing(span));
     }
 
+    if extended {
+        #[cfg(unix)]
+        super::extattr::extended::push_columns(&mut record, filename, metadata, span);
+    }
+
     Ok(Value::record(record, span))
 }
 
+/// Whether a file has holes, so that less space is allocated for it than its size suggests.
+#[cfg(windows)]
+fn is_sparse(md: &Metadata, _allocated: Option<u64>) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    // https://docs.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants
+    const FILE_ATTRIBUTE_SPARSE_FILE: u32 = 0x200;
+    md.file_attributes() & FILE_ATTRIBUTE_SPARSE_FILE != 0
+}
+
+/// Whether a file has holes, so that less space is allocated for it than its size suggests.
+#[cfg(not(windows))]
+fn is_sparse(md: &Metadata, allocated: Option<u64>) -> bool {
+    // Unix has no flag for it, but a file can only take up less space than its size if it either
+    // has holes, or is compressed by the filesystem
+    allocated.is_some_and(|allocated| allocated < md.len())
+}
+
 // TODO: can we get away from local times in `ls`? internals might be cleaner if we worked in UTC
 // and left the conversion to local time to the display layer
 fn try_convert_to_local_date_time(t: SystemTime) -> Option<DateTime<Local>> {
@@ -943,8 +1020,8 @@ mod windows_helper {
     use std::os::windows::prelude::OsStrExt;
     use windows::Win32::Foundation::FILETIME;
     use windows::Win32::Storage::FileSystem::{
-        FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_REPARSE_POINT, FindClose,
-        FindFirstFileW, WIN32_FIND_DATAW,
+        FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_REPARSE_POINT,
+        FILE_ATTRIBUTE_SPARSE_FILE, FindClose, FindFirstFileW, WIN32_FIND_DATAW,
     };
     use windows::Win32::System::SystemServices::{
         IO_REPARSE_TAG_MOUNT_POINT, IO_REPARSE_TAG_SYMLINK,
@@ -1009,6 +1086,15 @@ mod windows_helper {
         record.push("size", Value::filesize(file_size as i64, span));
 
         if long {
+            record.push("allocated", Value::nothing(span));
+            record.push(
+                "sparse",
+                Value::bool(
+                    find_data.dwFileAttributes & FILE_ATTRIBUTE_SPARSE_FILE.0 != 0,
+                    span,
+                ),
+            );
+
             record.push("created", {
                 let mut val = Value::nothing(span);
                 let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftCreationTime);
@@ -1113,11 +1199,19 @@ mod windows_helper {
     }
 }
 
+fn dir_is_empty(path: &Path, span: Span) -> Result<bool, ShellError> {
+    Ok(path
+        .read_dir()
+        .map_err(|err| IoError::new(err, span, path.to_path_buf()))?
+        .next()
+        .is_none())
+}
+
 #[allow(clippy::type_complexity)]
 fn read_dir(
     f: PathBuf,
     span: Span,
-    use_threads: bool,
+    sort: bool,
     signals: Signals,
 ) -> Result<Box<dyn Iterator<Item = Result<LsEntry, ShellError>> + Send>, ShellError> {
     let signals_clone = signals.clone();
@@ -1130,7 +1224,7 @@ fn read_dir(
                 .map_err(|err| IoError::new(err, span, f.clone()))
                 .map_err(ShellError::from)
         });
-    if !use_threads {
+    if sort {
         let mut collected = items.collect::<Vec<_>>();
         signals.check(&span)?;
         collected.sort_by(|a, b| match (a, b) {
@@ -1,5 +1,6 @@
-use nu_engine::command_prelude::*;
-use std::path::PathBuf;
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::{engine::Closure, shell_error::io::IoError};
+use std::{io::ErrorKind, path::PathBuf};
 use uucore::{localized_help_template, translate};
 
 #[derive(Clone)]
@@ -14,6 +15,12 @@ impl Command for Mktemp {
         "Create temporary files or directories using uutils/coreutils mktemp."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --scoped, the path is passed to the given closure, and the file or directory is \
+         removed again once the closure is done, even if it failed or was interrupted. The \
+         output of the closure is returned instead of the path."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec![
             "create",
@@ -27,7 +34,11 @@ impl Command for Mktemp {
 
     fn signature(&self) -> Signature {
         Signature::build("mktemp")
-            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .input_output_types(vec![
+                (Type::Nothing, Type::String),
+                (Type::Nothing, Type::Any),
+            ])
+            .allow_variants_without_examples(true)
             .optional(
                 "template",
                 SyntaxShape::String,
@@ -38,6 +49,7 @@ impl Command for Mktemp {
             .switch("tmpdir", "Interpret TEMPLATE relative to the system temporary directory. It is implied if template is not provided.", Some('t'))
             .switch("directory", "Create a directory instead of a file.", Some('d'))
             .switch("dry", "Don't create a file and just return the path that would have been created.", None)
+            .named("scoped", SyntaxShape::Closure(Some(vec![SyntaxShape::String])), "Run this closure with the path, and remove the file or directory when it ends.", Some('s'))
             .category(Category::FileSystem)
     }
 
@@ -63,6 +75,11 @@ impl Command for Mktemp {
                 example: "mktemp -d",
                 result: Some(Value::test_string("/tmp/tmp.NMw9fJr8K0")),
             },
+            Example {
+                description: "Unpack an archive into a temporary directory that is removed afterwards.",
+                example: "mktemp --directory --scoped {|dir| ^tar -xf archive.tar -C $dir; ls $dir | length }",
+                result: None,
+            },
         ]
     }
 
@@ -84,6 +101,7 @@ impl Command for Mktemp {
             .map(|i: Spanned<String>| i.item);
         let directory = call.has_flag(engine_state, stack, "directory")?;
         let dry_run = call.has_flag(engine_state, stack, "dry")?;
+        let scoped: Option<Closure> = call.get_flag(engine_state, stack, "scoped")?;
         let suffix = call.get_flag(engine_state, stack, "suffix")?;
         let tmpdir = template.is_none() || call.has_flag(engine_state, stack, "tmpdir")?;
         let tmpdir_path = call
@@ -124,6 +142,41 @@ impl Command for Mktemp {
                 });
             }
         };
-        Ok(PipelineData::value(Value::string(res, span), None))
+
+        match scoped {
+            Some(closure) => run_scoped(engine_state, stack, closure, res, directory, span),
+            None => Ok(PipelineData::value(Value::string(res, span), None)),
+        }
+    }
+}
+
+/// Runs `closure` with the temporary `path`, and removes it again afterwards.
+fn run_scoped(
+    engine_state: &EngineState,
+    stack: &Stack,
+    closure: Closure,
+    path: String,
+    directory: bool,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    // the output is collected first, so that nothing reads from the path after it's removed
+    let result = ClosureEvalOnce::new(engine_state, stack, closure)
+        .add_arg(Value::string(&path, span))
+        .run_with_input(PipelineData::empty())
+        .and_then(|output| output.into_value(span));
+
+    let removed = if directory {
+        std::fs::remove_dir_all(&path)
+    } else {
+        std::fs::remove_file(&path)
+    };
+    match removed {
+        // the closure may have removed it already
+        Err(err) if err.kind() != ErrorKind::NotFound => {
+            // an error of the closure is more useful than the one caused by it
+            result?;
+            Err(IoError::new(err, span, PathBuf::from(path)).into())
+        }
+        _ => Ok(result?.into_pipeline_data()),
     }
 }
@@ -1,12 +1,31 @@
 mod cd;
+mod chmod;
+#[cfg(unix)]
+mod chown;
 mod du;
+#[cfg(unix)]
+mod extattr;
+mod flock;
 mod glob;
+mod ln;
 mod ls;
 mod mktemp;
 mod open;
 mod rm;
 mod save;
 mod start;
+// listing the trash isn't supported on macOS
+#[cfg(all(
+    feature = "trash-support",
+    any(
+        target_os = "windows",
+        all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+        )
+    )
+))]
+mod trash_;
 mod ucp;
 mod umkdir;
 mod umv;
@@ -16,13 +35,31 @@ mod watch;
 
 pub use self::open::Open;
 pub use cd::Cd;
+pub use chmod::Chmod;
+#[cfg(unix)]
+pub use chown::Chown;
 pub use du::Du;
+#[cfg(unix)]
+pub use extattr::{Xattr, XattrGet, XattrSet};
+pub use flock::Flock;
 pub use glob::Glob;
+pub use ln::Ln;
 pub use ls::Ls;
 pub use mktemp::Mktemp;
 pub use rm::Rm;
 pub use save::Save;
 pub use start::Start;
+#[cfg(all(
+    feature = "trash-support",
+    any(
+        target_os = "windows",
+        all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+        )
+    )
+))]
+pub use trash_::{Trash, TrashEmpty, TrashList, TrashRestore};
 pub use ucp::UCp;
 pub use umkdir::UMkdir;
 pub use umv::UMv;
@@ -1,7 +1,7 @@
 use nu_engine::{command_prelude::*, eval_call};
 use nu_path::is_windows_device_path;
 use nu_protocol::{
-    DataSource, NuGlob, PipelineMetadata, ast,
+    DataSource, DeclId, NuGlob, PipelineMetadata, ast,
     debugger::{WithDebug, WithoutDebug},
     shell_error::{self, io::IoError},
 };
@@ -29,7 +29,11 @@ impl Command for Open {
     }
 
     fn extra_description(&self) -> &str {
-        "Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope."
+        "Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope.
+
+URLs starting with http://, https://, s3:// or gs:// are downloaded as a stream. They are parsed based on the content type the server sends, or on their extension if the content type doesn't say more than that it's text or binary data.
+
+Objects in S3 are read with the credentials in $env.AWS_ACCESS_KEY_ID, $env.AWS_SECRET_ACCESS_KEY and $env.AWS_SESSION_TOKEN, from the region in $env.AWS_REGION, or from an S3 compatible service at $env.AWS_ENDPOINT_URL. Objects in Google Cloud Storage are read with the token in $env.GOOGLE_OAUTH_ACCESS_TOKEN, which `gcloud auth print-access-token` prints. Without credentials, only public objects can be read."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -101,6 +105,12 @@ impl Command for Open {
             path.item = path.item.strip_ansi_string_unlikely();
 
             let arg_span = path.span;
+
+            if is_remote_url(path.item.as_ref()) {
+                output.push(open_remote(engine_state, stack, call_span, &path, raw)?);
+                continue;
+            }
+
             // let path_no_whitespace = &path.item.trim_end_matches(|x| matches!(x, '\x09'..='\x0d'));
 
             let matches: Box<dyn Iterator<Item = Result<PathBuf, ShellError>> + Send> =
@@ -204,36 +214,19 @@ impl Command for Open {
                         Some(extract_extensions(path_str.as_str()))
                     };
 
-                    let converter = exts_opt.and_then(|exts| {
-                        exts.iter().find_map(|ext| {
-                            engine_state
-                                .find_decl(format!("from {ext}").as_bytes(), &[])
-                                .map(|id| (id, ext.to_string()))
-                        })
-                    });
+                    let converter = exts_opt.and_then(|exts| find_converter(engine_state, &exts));
 
                     match converter {
-                        Some((converter_id, ext)) => {
-                            let open_call = ast::Call {
-                                decl_id: converter_id,
-                                head: call_span,
-                                arguments: vec![],
-                                parser_info: HashMap::new(),
-                            };
-                            let command_output = if engine_state.is_debugging() {
-                                eval_call::<WithDebug>(engine_state, stack, &open_call, stream)
-                            } else {
-                                eval_call::<WithoutDebug>(engine_state, stack, &open_call, stream)
-                            };
-                            output.push(command_output.map_err(|inner| {
-                                    ShellError::GenericError{
-                                        error: format!("Error while parsing as {ext}"),
-                                        msg: format!("Could not parse '{}' with `from {}`", path.display(), ext),
-                                        span: Some(arg_span),
-                                        help: Some(format!("Check out `help from {}` or `help from` for more options or open raw data with `open --raw '{}'`", ext, path.display())),
-                                        inner: vec![inner],
-                                }
-                                })?);
+                        Some(converter) => {
+                            output.push(run_converter(
+                                engine_state,
+                                stack,
+                                call_span,
+                                converter,
+                                stream,
+                                &path.display().to_string(),
+                                arg_span,
+                            )?);
                         }
                         None => {
                             // If no converter was found, add content-type metadata
@@ -289,6 +282,16 @@ impl Command for Open {
                 example: "open myfile.txt --raw | decode utf-8",
                 result: None,
             },
+            Example {
+                description: "Open JSON from the web, which is parsed because of its content type.",
+                example: "open https://api.github.com/repos/nushell/nushell | get stargazers_count",
+                result: None,
+            },
+            Example {
+                description: "Open a CSV file from an S3 bucket, with the credentials from the environment.",
+                example: "with-env {AWS_REGION: eu-west-1} { open s3://my-bucket/reports/2024.csv }",
+                result: None,
+            },
             Example {
                 description: "Create a custom `from` parser to open newline-delimited JSON files with `open`.",
                 example: r#"def "from ndjson" [] { from json -o }; open myfile.ndjson"#,
@@ -308,6 +311,130 @@ impl Command for Open {
     }
 }
 
+/// The `from` command for the first of `exts` that has one, and that extension.
+fn find_converter(engine_state: &EngineState, exts: &[String]) -> Option<(DeclId, String)> {
+    exts.iter().find_map(|ext| {
+        engine_state
+            .find_decl(format!("from {ext}").as_bytes(), &[])
+            .map(|id| (id, ext.to_string()))
+    })
+}
+
+fn run_converter(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call_span: Span,
+    (converter_id, ext): (DeclId, String),
+    stream: PipelineData,
+    name: &str,
+    arg_span: Span,
+) -> Result<PipelineData, ShellError> {
+    let open_call = ast::Call {
+        decl_id: converter_id,
+        head: call_span,
+        arguments: vec![],
+        parser_info: HashMap::new(),
+    };
+    let command_output = if engine_state.is_debugging() {
+        eval_call::<WithDebug>(engine_state, stack, &open_call, stream)
+    } else {
+        eval_call::<WithoutDebug>(engine_state, stack, &open_call, stream)
+    };
+    command_output.map_err(|inner| ShellError::GenericError {
+        error: format!("Error while parsing as {ext}"),
+        msg: format!("Could not parse '{name}' with `from {ext}`"),
+        span: Some(arg_span),
+        help: Some(format!(
+            "Check out `help from {ext}` or `help from` for more options or open raw data with `open --raw '{name}'`"
+        )),
+        inner: vec![inner],
+    })
+}
+
+/// URLs with these schemes are downloaded instead of being treated as paths.
+const REMOTE_SCHEMES: &[&str] = &["http", "https", "s3", "gs"];
+
+fn is_remote_url(path: &str) -> bool {
+    path.split_once("://")
+        .is_some_and(|(scheme, _)| REMOTE_SCHEMES.contains(&scheme.to_lowercase().as_str()))
+}
+
+#[cfg(feature = "network")]
+fn open_remote(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call_span: Span,
+    url: &Spanned<NuGlob>,
+    raw: bool,
+) -> Result<PipelineData, ShellError> {
+    let url_str = url.item.as_ref();
+    let (stream, content_type) =
+        crate::network::fetch_remote(engine_state, stack, url_str, url.span, call_span)?;
+    if raw {
+        return Ok(stream);
+    }
+
+    // a specific content type is more reliable than the name, which URLs often don't even have
+    let file_name = url::Url::parse(url_str)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()
+                .and_then(|mut segments| segments.next_back().map(str::to_lowercase))
+        })
+        .unwrap_or_default();
+    let exts: Vec<String> = content_type
+        .as_deref()
+        .and_then(content_type_extension)
+        .into_iter()
+        .chain(extract_extensions(&file_name))
+        .collect();
+
+    match find_converter(engine_state, &exts) {
+        Some(converter) => run_converter(
+            engine_state,
+            stack,
+            call_span,
+            converter,
+            stream,
+            url_str,
+            url.span,
+        ),
+        None => Ok(stream),
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn open_remote(
+    _engine_state: &EngineState,
+    _stack: &mut Stack,
+    _call_span: Span,
+    url: &Spanned<NuGlob>,
+    _raw: bool,
+) -> Result<PipelineData, ShellError> {
+    Err(ShellError::GenericError {
+        error: "Can't open URLs".into(),
+        msg: "nushell was built without the network feature".into(),
+        span: Some(url.span),
+        help: Some("Download the file with another program first.".into()),
+        inner: vec![],
+    })
+}
+
+/// The extension of the `from` command for a content type, unless it's too generic to tell.
+#[cfg(feature = "network")]
+fn content_type_extension(content_type: &str) -> Option<String> {
+    let mime: mime::Mime = content_type.parse().ok()?;
+    if let Some(suffix) = mime.suffix() {
+        // like application/geo+json
+        return Some(suffix.as_str().to_string());
+    }
+    match (mime.type_().as_str(), mime.subtype().as_str()) {
+        ("text", "plain") | ("application" | "binary", "octet-stream") => None,
+        (_, "tab-separated-values") => Some("tsv".into()),
+        (_, subtype) => Some(subtype.trim_start_matches("x-").to_string()),
+    }
+}
+
 fn permission_denied(dir: impl AsRef<Path>) -> bool {
     match dir.as_ref().read_dir() {
         Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
@@ -352,7 +479,40 @@ fn detect_content_type(extension: &str) -> Option<String> {
 
 #[cfg(test)]
 mod test {
+    use super::*;
 
     #[test]
     fn test_content_type() {}
+
+    #[test]
+    fn test_is_remote_url() {
+        assert!(is_remote_url("https://example.com/data.json"));
+        assert!(is_remote_url("S3://bucket/data.csv"));
+        assert!(is_remote_url("gs://bucket/data.parquet"));
+        assert!(!is_remote_url("file://data.json"));
+        assert!(!is_remote_url("data/https.json"));
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn test_content_type_extension() {
+        assert_eq!(
+            content_type_extension("application/json; charset=utf-8").as_deref(),
+            Some("json")
+        );
+        assert_eq!(
+            content_type_extension("application/geo+json").as_deref(),
+            Some("json")
+        );
+        assert_eq!(
+            content_type_extension("application/x-yaml").as_deref(),
+            Some("yaml")
+        );
+        assert_eq!(
+            content_type_extension("text/tab-separated-values").as_deref(),
+            Some("tsv")
+        );
+        assert_eq!(content_type_extension("text/plain"), None);
+        assert_eq!(content_type_extension("binary/octet-stream"), None);
+    }
 }
@@ -0,0 +1,86 @@
+use super::item;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct TrashEmpty;
+
+impl Command for TrashEmpty {
+    fn name(&self) -> &str {
+        "trash empty"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("trash empty")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .named(
+                "older-than",
+                SyntaxShape::Duration,
+                "Only delete the items that have been in the trash for longer than this.",
+                Some('o'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Permanently delete the items in the trash."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["recycle bin", "deleted", "purge", "clean"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let older_than: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "older-than")?;
+
+        let mut items = item::list(head)?;
+        if let Some(older_than) = older_than {
+            if older_than.item < 0 {
+                return Err(ShellError::NeedsPositiveValue {
+                    span: older_than.span,
+                });
+            }
+            let cutoff = chrono::Utc::now().timestamp() - older_than.item / 1_000_000_000;
+            items.retain(|item| item.time_deleted < cutoff);
+        }
+
+        if !items.is_empty() {
+            trash::os_limited::purge_all(items).map_err(|err| item::trash_error(err, head))?;
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Delete everything in the trash.",
+                example: "trash empty",
+                result: None,
+            },
+            Example {
+                description: "Delete the items that were moved to the trash more than 30 days ago.",
+                example: "trash empty --older-than 30day",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(TrashEmpty {})
+    }
+}
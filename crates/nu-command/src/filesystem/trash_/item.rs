@@ -0,0 +1,46 @@
+use chrono::{DateTime, FixedOffset, Local};
+use nu_protocol::{ShellError, Span, Value, record};
+use trash::TrashItem;
+
+/// Lists the items in the trash, oldest first.
+pub(super) fn list(span: Span) -> Result<Vec<TrashItem>, ShellError> {
+    let mut items = trash::os_limited::list().map_err(|err| trash_error(err, span))?;
+    items.sort_by_key(|item| item.time_deleted);
+    Ok(items)
+}
+
+pub(super) fn trash_error(err: trash::Error, span: Span) -> ShellError {
+    match err {
+        trash::Error::RestoreCollision { path, .. } => ShellError::GenericError {
+            error: "Can't restore from the trash".into(),
+            msg: format!("{} already exists", path.display()),
+            span: Some(span),
+            help: Some("Move or remove the existing file first.".into()),
+            inner: vec![],
+        },
+        err => ShellError::GenericError {
+            error: "Can't access the trash".into(),
+            msg: err.to_string(),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        },
+    }
+}
+
+pub(super) fn deleted_at(item: &TrashItem) -> Option<DateTime<FixedOffset>> {
+    DateTime::from_timestamp(item.time_deleted, 0)
+        .map(|time| time.with_timezone(&Local).fixed_offset())
+}
+
+pub(super) fn to_value(item: &TrashItem, span: Span) -> Value {
+    Value::record(
+        record! {
+            "name" => Value::string(item.name.to_string_lossy(), span),
+            "original_path" => Value::string(item.original_path().to_string_lossy(), span),
+            "deleted" => deleted_at(item).map_or(Value::nothing(span), |time| Value::date(time, span)),
+            "id" => Value::string(item.id.to_string_lossy(), span),
+        },
+        span,
+    )
+}
@@ -0,0 +1,68 @@
+use super::item;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct TrashList;
+
+impl Command for TrashList {
+    fn name(&self) -> &str {
+        "trash list"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("trash list")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "List the items in the trash, oldest first."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["recycle bin", "deleted", "ls"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let items = item::list(head)?
+            .iter()
+            .map(|item| item::to_value(item, head))
+            .collect();
+
+        Ok(Value::list(items, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "List the items in the trash.",
+                example: "trash list",
+                result: None,
+            },
+            Example {
+                description: "Find the files that were deleted from the current directory today.",
+                example: "trash list | where ($it.original_path | path dirname) == $env.PWD and deleted > (date now) - 1day",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(TrashList {})
+    }
+}
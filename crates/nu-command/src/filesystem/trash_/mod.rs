@@ -0,0 +1,10 @@
+mod empty;
+mod item;
+mod list;
+mod restore;
+mod trash_;
+
+pub use empty::TrashEmpty;
+pub use list::TrashList;
+pub use restore::TrashRestore;
+pub use trash_::Trash;
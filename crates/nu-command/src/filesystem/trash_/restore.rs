@@ -0,0 +1,130 @@
+use super::item;
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use trash::TrashItem;
+
+#[derive(Clone)]
+pub struct TrashRestore;
+
+impl Command for TrashRestore {
+    fn name(&self) -> &str {
+        "trash restore"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("trash restore")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::table(), Type::Nothing),
+            ])
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "The original paths of the items to restore.",
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Move items from the trash back to where they were deleted from."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Items are either given by their original path, or piped in from `trash list`. If a path \
+         was deleted several times, its most recently deleted version is restored. Restoring \
+         fails if something already exists at the original path."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["recycle bin", "deleted", "undelete", "undo"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let cwd = engine_state.cwd(Some(stack))?;
+        let mut trashed = item::list(head)?;
+
+        let mut selected: Vec<TrashItem> = Vec::new();
+        for path in paths {
+            let full_path = expand_path_with(&path.item, &cwd, true);
+            // the list is sorted oldest first, so the last match is the latest version
+            let Some(index) = trashed
+                .iter()
+                .rposition(|item| item.original_path() == full_path)
+            else {
+                return Err(not_in_trash(&path.item, path.span));
+            };
+            selected.push(trashed.remove(index));
+        }
+
+        for value in input {
+            let value = value.unwrap_error()?;
+            let span = value.span();
+            let id = value
+                .get_data_by_key("id")
+                .ok_or_else(|| ShellError::CantFindColumn {
+                    col_name: "id".into(),
+                    span: Some(head),
+                    src_span: span,
+                })?;
+            let id = id.coerce_str()?;
+            let Some(index) = trashed
+                .iter()
+                .position(|item| item.id.to_string_lossy() == id)
+            else {
+                return Err(not_in_trash(&id, span));
+            };
+            selected.push(trashed.remove(index));
+        }
+
+        if !selected.is_empty() {
+            trash::os_limited::restore_all(selected).map_err(|err| item::trash_error(err, head))?;
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Restore a file that was deleted from the current directory.",
+                example: "trash restore notes.txt",
+                result: None,
+            },
+            Example {
+                description: "Restore everything that was deleted in the last hour.",
+                example: "trash list | where deleted > (date now) - 1hr | trash restore",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn not_in_trash(item: &str, span: Span) -> ShellError {
+    ShellError::GenericError {
+        error: "Not in the trash".into(),
+        msg: format!("{item} is not in the trash"),
+        span: Some(span),
+        help: Some("Use `trash list` to see what can be restored.".into()),
+        inner: vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(TrashRestore {})
+    }
+}
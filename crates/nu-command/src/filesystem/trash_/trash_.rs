@@ -0,0 +1,38 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Trash;
+
+impl Command for Trash {
+    fn name(&self) -> &str {
+        "trash"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("trash")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with the trash of the platform, where `rm --trash` moves files to."
+    }
+
+    fn extra_description(&self) -> &str {
+        "You must use one of the following subcommands. Using this command as-is will only produce this help message."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["recycle bin", "deleted", "rm"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
@@ -1,10 +1,15 @@
 use nu_engine::command_prelude::*;
 use nu_glob::MatchOptions;
 use nu_protocol::{
-    NuGlob,
+    NuGlob, Signals,
+    byte_stream::copy_with_signals,
     shell_error::{self, io::IoError},
 };
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
 use uu_cp::{BackupMode, CopyMode, CpError, UpdateMode};
 use uucore::{localized_help_template, translate};
 
@@ -16,6 +21,9 @@ const PATH_SEPARATOR: &str = "/";
 #[cfg(target_os = "windows")]
 const PATH_SEPARATOR: &str = "\\";
 
+const REFLINK_MODES: &[&str] = &["auto", "always", "never"];
+const VERIFY_ALGORITHMS: &[&str] = &["sha256"];
+
 #[derive(Clone)]
 pub struct UCp;
 
@@ -51,6 +59,25 @@ impl Command for UCp {
                 Some('u')
             )
             .switch("progress", "Display a progress bar.", Some('p'))
+            .param(
+                Flag::new("reflink")
+                    .arg(SyntaxShape::String)
+                    .desc(
+                        "Clone files with copy-on-write on filesystems that support it, like \
+                         btrfs, XFS and APFS. Default: 'auto', which falls back to a regular copy. \
+                         Valid options: 'auto', 'always', 'never'.",
+                    )
+                    .completion(Completion::new_list(REFLINK_MODES)),
+            )
+            .param(
+                Flag::new("verify")
+                    .arg(SyntaxShape::String)
+                    .desc(
+                        "Check that every copied file matches its source after copying. Valid \
+                         options: 'sha256'.",
+                    )
+                    .completion(Completion::new_list(VERIFY_ALGORITHMS)),
+            )
             .switch("no-clobber", "Do not overwrite an existing file.", Some('n'))
             .named(
                 "preserve",
@@ -109,6 +136,11 @@ impl Command for UCp {
                 example: "cp myfile ....",
                 result: None,
             },
+            Example {
+                description: "Copy a large directory with a progress bar, cloning the files if the filesystem allows it and checking the copies afterwards.",
+                example: "cp -r --progress --reflink always --verify sha256 dir_a dir_b",
+                result: None,
+            },
         ]
     }
 
@@ -151,10 +183,46 @@ impl Command for UCp {
         } else {
             uu_cp::OverwriteMode::Clobber(uu_cp::ClobberMode::Standard)
         };
-        #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
-        let reflink_mode = uu_cp::ReflinkMode::Auto;
-        #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
-        let reflink_mode = uu_cp::ReflinkMode::Never;
+        let reflink_mode = match call.get_flag::<Spanned<String>>(engine_state, stack, "reflink")? {
+            Some(mode) => match mode.item.as_str() {
+                "auto" => uu_cp::ReflinkMode::Auto,
+                "always" => uu_cp::ReflinkMode::Always,
+                "never" => uu_cp::ReflinkMode::Never,
+                _ => {
+                    return Err(ShellError::InvalidValue {
+                        valid: format!("one of {}", REFLINK_MODES.join(", ")),
+                        actual: mode.item,
+                        span: mode.span,
+                    });
+                }
+            },
+            #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+            None => uu_cp::ReflinkMode::Auto,
+            #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+            None => uu_cp::ReflinkMode::Never,
+        };
+
+        let verify: Option<Spanned<String>> = call.get_flag(engine_state, stack, "verify")?;
+        if let Some(algorithm) = &verify {
+            if !VERIFY_ALGORITHMS.contains(&algorithm.item.as_str()) {
+                return Err(ShellError::InvalidValue {
+                    valid: format!("one of {}", VERIFY_ALGORITHMS.join(", ")),
+                    actual: algorithm.item.clone(),
+                    span: algorithm.span,
+                });
+            }
+            // these skip files on purpose, so their destinations are expected to differ
+            for flag in ["update", "no-clobber", "interactive"] {
+                if let Some(span) = call.get_flag_span(stack, flag) {
+                    return Err(ShellError::IncompatibleParameters {
+                        left_message: "can't verify the copies".into(),
+                        left_span: algorithm.span,
+                        right_message: format!("when files may be skipped with --{flag}"),
+                        right_span: span,
+                    });
+                }
+            }
+        }
         let mut paths = call.rest::<Spanned<NuGlob>>(engine_state, stack, 0)?;
         if paths.is_empty() {
             return Err(ShellError::GenericError {
@@ -257,6 +325,8 @@ impl Command for UCp {
             }
         }
         let sources: Vec<PathBuf> = sources.into_iter().flat_map(|x| x.0).collect();
+        // whether the sources are copied into the target, or the target becomes the copy
+        let target_is_dir = target_path.is_dir();
 
         let attributes = make_attributes(preserve)?;
 
@@ -304,10 +374,113 @@ impl Command for UCp {
             // TODO: What should we do in place of set_exit_code?
             // uucore::error::set_exit_code(EXIT_ERR);
         }
+
+        if verify.is_some() {
+            verify_copies(
+                &sources,
+                &target_path,
+                target_is_dir,
+                recursive,
+                call.head,
+                engine_state.signals(),
+            )?;
+        }
+
         Ok(PipelineData::empty())
     }
 }
 
+/// Compares the SHA-256 checksum of every copied file with that of its source.
+pub(super) fn verify_copies(
+    sources: &[PathBuf],
+    target: &Path,
+    target_is_dir: bool,
+    recursive: bool,
+    span: Span,
+    signals: &Signals,
+) -> Result<(), ShellError> {
+    let mut files = Vec::new();
+    for source in sources {
+        let destination = match source.file_name() {
+            Some(name) if target_is_dir => target.join(name),
+            _ => target.to_path_buf(),
+        };
+        // without --recursive, links given on the command line are followed
+        collect_files(source, destination, !recursive, &mut files, span)?;
+    }
+
+    let mut mismatches = Vec::new();
+    for (source, destination) in files {
+        signals.check(&span)?;
+        if sha256_file(&source, span, signals)? != sha256_file(&destination, span, signals)? {
+            mismatches.push(ShellError::GenericError {
+                error: "Copy differs from its source".into(),
+                msg: format!(
+                    "{} differs from {}",
+                    destination.display(),
+                    source.display()
+                ),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            });
+        }
+    }
+
+    match mismatches.len() {
+        0 => Ok(()),
+        count => Err(ShellError::GenericError {
+            error: "Copy verification failed".into(),
+            msg: format!("{count} copied file(s) don't match their source"),
+            span: Some(span),
+            help: Some("The files may have changed while they were copied.".into()),
+            inner: mismatches,
+        }),
+    }
+}
+
+/// Lists the regular files below `source` along with where they were copied to. Links are
+/// copied as links, so there is nothing to verify for them.
+fn collect_files(
+    source: &Path,
+    destination: PathBuf,
+    follow_links: bool,
+    files: &mut Vec<(PathBuf, PathBuf)>,
+    span: Span,
+) -> Result<(), ShellError> {
+    let metadata = if follow_links {
+        fs::metadata(source)
+    } else {
+        fs::symlink_metadata(source)
+    }
+    .map_err(|err| IoError::new(err, span, source.to_path_buf()))?;
+
+    if metadata.is_dir() {
+        let entries =
+            fs::read_dir(source).map_err(|err| IoError::new(err, span, source.to_path_buf()))?;
+        for entry in entries {
+            let entry = entry.map_err(|err| IoError::new(err, span, source.to_path_buf()))?;
+            collect_files(
+                &entry.path(),
+                destination.join(entry.file_name()),
+                false,
+                files,
+                span,
+            )?;
+        }
+    } else if metadata.is_file() {
+        files.push((source.to_path_buf(), destination));
+    }
+    Ok(())
+}
+
+fn sha256_file(path: &Path, span: Span, signals: &Signals) -> Result<Vec<u8>, ShellError> {
+    let file = File::open(path).map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+    let mut hasher = Sha256::new();
+    copy_with_signals(file, &mut hasher, span, signals)?;
+    Ok(hasher.finalize().to_vec())
+}
+
 const ATTR_UNSET: uu_cp::Preserve = uu_cp::Preserve::No { explicit: true };
 const ATTR_SET: uu_cp::Preserve = uu_cp::Preserve::Yes { required: true };
 
@@ -2,10 +2,14 @@ use nu_engine::command_prelude::*;
 use nu_glob::MatchOptions;
 use nu_path::expand_path_with;
 use nu_protocol::{
-    NuGlob,
+    NuGlob, Signals,
     shell_error::{self, io::IoError},
 };
-use std::{ffi::OsString, path::PathBuf};
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
 use uu_mv::{BackupMode, UpdateMode};
 use uucore::{localized_help_template, translate};
 
@@ -21,6 +25,12 @@ impl Command for UMv {
         "Move files or directories using uutils/coreutils mv."
     }
 
+    fn extra_description(&self) -> &str {
+        "Moving to another filesystem copies the files, checks that the copies match the \
+         originals and only then deletes the originals. On Windows, and with --interactive, \
+         --no-clobber or --update, the copies aren't checked."
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -43,6 +53,11 @@ impl Command for UMv {
                 example: "mv *.txt my/subdirectory",
                 result: None,
             },
+            Example {
+                description: "Move a file, keeping a numbered backup of the file it replaces.",
+                example: "mv --backup config.toml ~/.config/app/config.toml",
+                result: None,
+            },
             Example {
                 description: r#"Move a file into the "my" directory two levels up in the directory tree."#,
                 example: "mv test.txt .../my/",
@@ -68,6 +83,11 @@ impl Command for UMv {
                 Some('u')
             )
             .switch("no-clobber", "Do not overwrite an existing file.", Some('n'))
+            .switch(
+                "backup",
+                "Keep a numbered backup (like 'file.~1~') of every file that is overwritten.",
+                Some('b'),
+            )
             .switch("all", "Move hidden files if '*' is provided.", Some('a'))
             .rest(
                 "paths",
@@ -100,11 +120,13 @@ impl Command for UMv {
         } else {
             uu_mv::OverwriteMode::Force
         };
-        let update = if call.has_flag(engine_state, stack, "update")? {
+        let update_flag = call.has_flag(engine_state, stack, "update")?;
+        let update = if update_flag {
             UpdateMode::IfOlder
         } else {
             UpdateMode::All
         };
+        let backup = call.has_flag(engine_state, stack, "backup")?;
 
         let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
         let mut paths = call.rest::<Spanned<NuGlob>>(engine_state, stack, 0)?;
@@ -187,14 +209,53 @@ impl Command for UMv {
                 }
             }
         }
-        let mut files: Vec<PathBuf> = files.into_iter().flat_map(|x| x.0).collect();
+        let files: Vec<PathBuf> = files.into_iter().flat_map(|x| x.0).collect();
 
-        // Add back the target after globbing
         let abs_target_path = expand_path_with(
             nu_utils::strip_ansi_string_unlikely(spanned_target.item.to_string()),
             &cwd,
             matches!(spanned_target.item, NuGlob::Expand(..)),
         );
+
+        // uu_mv also falls back to copying across filesystems, but deletes the originals without
+        // checking the copies. It's left to handle the cases where files may be skipped on purpose.
+        let target_is_dir = abs_target_path.is_dir();
+        // Checked before anything is copied or deleted, as several files would otherwise all be
+        // moved onto the same target
+        if files.len() > 1 && !target_is_dir {
+            return Err(ShellError::GenericError {
+                error: "Target is not a directory".into(),
+                msg: "moving several files needs an existing directory".into(),
+                span: Some(spanned_target.span),
+                help: None,
+                inner: Vec::new(),
+            });
+        }
+        let target_dir = match abs_target_path.parent() {
+            Some(parent) if !target_is_dir => parent,
+            _ => &abs_target_path,
+        };
+        let (cross_device, mut files): (Vec<PathBuf>, Vec<PathBuf>) =
+            files.into_iter().partition(|source| {
+                !interactive && !no_clobber && !update_flag && is_cross_device(source, target_dir)
+            });
+        for source in cross_device {
+            move_across_devices(
+                &source,
+                &abs_target_path,
+                target_is_dir,
+                progress,
+                verbose,
+                backup,
+                call.head,
+                engine_state.signals(),
+            )?;
+        }
+        if files.is_empty() {
+            return Ok(PipelineData::empty());
+        }
+
+        // Add back the target after globbing
         files.push(abs_target_path.clone());
         let files = files
             .into_iter()
@@ -205,7 +266,11 @@ impl Command for UMv {
             progress_bar: progress,
             verbose,
             suffix: String::from("~"),
-            backup: BackupMode::None,
+            backup: if backup {
+                BackupMode::Numbered
+            } else {
+                BackupMode::None
+            },
             update,
             target_dir: None,
             no_target_dir: false,
@@ -225,3 +290,96 @@ impl Command for UMv {
         Ok(PipelineData::empty())
     }
 }
+
+#[cfg(unix)]
+fn is_cross_device(source: &Path, target_dir: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::symlink_metadata(source), fs::metadata(target_dir)) {
+        (Ok(source), Ok(target)) => source.dev() != target.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_cross_device(_source: &Path, _target_dir: &Path) -> bool {
+    false
+}
+
+/// Moves `source` to another filesystem by copying it with all its attributes, checking the copy
+/// and only then deleting the original.
+#[allow(clippy::too_many_arguments)]
+fn move_across_devices(
+    source: &Path,
+    target: &Path,
+    target_is_dir: bool,
+    progress: bool,
+    verbose: bool,
+    backup: bool,
+    span: Span,
+    signals: &Signals,
+) -> Result<(), ShellError> {
+    // ownership can only be kept by root, so attributes are kept where possible
+    const KEEP: uu_cp::Preserve = uu_cp::Preserve::Yes { required: false };
+    let options = uu_cp::Options {
+        overwrite: uu_cp::OverwriteMode::Clobber(uu_cp::ClobberMode::Standard),
+        reflink_mode: uu_cp::ReflinkMode::Never,
+        recursive: true,
+        debug: false,
+        attributes: uu_cp::Attributes {
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "freebsd",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ))]
+            ownership: KEEP,
+            mode: KEEP,
+            timestamps: KEEP,
+            context: KEEP,
+            links: KEEP,
+            xattr: KEEP,
+        },
+        verbose,
+        dereference: false,
+        progress_bar: progress,
+        attributes_only: false,
+        backup: if backup {
+            uu_cp::BackupMode::Numbered
+        } else {
+            uu_cp::BackupMode::None
+        },
+        copy_contents: false,
+        cli_dereference: false,
+        copy_mode: uu_cp::CopyMode::Copy,
+        no_target_dir: false,
+        one_file_system: false,
+        parents: false,
+        sparse_mode: uu_cp::SparseMode::Auto,
+        strip_trailing_slashes: false,
+        backup_suffix: String::from("~"),
+        target_dir: None,
+        update: uu_cp::UpdateMode::All,
+        set_selinux_context: false,
+        context: None,
+    };
+
+    let sources = [source.to_path_buf()];
+    uu_cp::copy(&sources, target, &options).map_err(|error| ShellError::GenericError {
+        error: format!("{error}"),
+        msg: translate!(&error.to_string()),
+        span: Some(span),
+        help: None,
+        inner: Vec::new(),
+    })?;
+    super::ucp::verify_copies(&sources, target, target_is_dir, true, span, signals)?;
+
+    let removed = match fs::symlink_metadata(source) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(source),
+        _ => fs::remove_file(source),
+    };
+    removed.map_err(|err| IoError::new(err, span, source.to_path_buf()))?;
+    Ok(())
+}
@@ -4,9 +4,13 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode},
 };
+use nu_engine::command_prelude::*;
+use nu_protocol::{NuGlob, Signals, shell_error::io::IoError};
 use std::{
     error::Error,
+    fs,
     io::{self, Write},
+    path::{Path, PathBuf},
 };
 
 pub fn try_interaction(
@@ -94,3 +98,86 @@ fn get_interactive_confirmation(prompt: String) -> Result<bool, Box<dyn Error>>
         }
     }
 }
+
+/// Collects the paths a command like `chmod` works on: the globs given as its arguments starting
+/// at `first`, or else the paths piped into it, either as strings or as records with a `name`
+/// column like `ls` outputs.
+pub fn target_paths(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    first: usize,
+    input: PipelineData,
+) -> Result<Vec<PathBuf>, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+    let globs = call.rest::<Spanned<NuGlob>>(engine_state, stack, first)?;
+
+    let mut paths = Vec::new();
+    if globs.is_empty() {
+        for value in input {
+            let value = value.unwrap_error()?;
+            let span = value.span();
+            let name = match &value {
+                Value::Record { val, .. } => {
+                    val.get("name").ok_or_else(|| ShellError::CantFindColumn {
+                        col_name: "name".into(),
+                        span: Some(call.head),
+                        src_span: span,
+                    })?
+                }
+                value => value,
+            };
+            paths.push(nu_path::expand_path_with(
+                name.coerce_str()?.as_ref(),
+                &cwd,
+                true,
+            ));
+        }
+        if paths.is_empty() {
+            return Err(ShellError::MissingParameter {
+                param_name: "paths".into(),
+                span: call.head,
+            });
+        }
+        return Ok(paths);
+    }
+
+    for glob in globs {
+        let (_, matches) =
+            nu_engine::glob_from(&glob, &cwd, call.head, None, engine_state.signals().clone())?;
+        let count = paths.len();
+        for path in matches {
+            paths.push(path?);
+        }
+        if paths.len() == count {
+            return Err(ShellError::Io(IoError::new(
+                nu_protocol::shell_error::io::ErrorKind::FileNotFound,
+                glob.span,
+                PathBuf::from(glob.item.to_string()),
+            )));
+        }
+    }
+    Ok(paths)
+}
+
+/// Calls `f` with `path` and, if `recursive` is set and `path` is a directory, with everything
+/// below it. Symbolic links below `path` are passed to `f` but not followed.
+pub fn walk_paths(
+    path: &Path,
+    recursive: bool,
+    span: Span,
+    signals: &Signals,
+    f: &mut dyn FnMut(&Path) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    signals.check(&span)?;
+    f(path)?;
+    if recursive && fs::symlink_metadata(path).is_ok_and(|md| md.is_dir()) {
+        let entries =
+            fs::read_dir(path).map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+        for entry in entries {
+            let entry = entry.map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+            walk_paths(&entry.path(), recursive, span, signals, f)?;
+        }
+    }
+    Ok(())
+}
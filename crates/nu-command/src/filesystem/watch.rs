@@ -33,7 +33,10 @@ impl Command for Watch {
     }
 
     fn extra_description(&self) -> &str {
-        "When run without a closure, `watch` returns a stream of events instead."
+        "When run without a closure, `watch` returns a stream of events instead. Every event has an \
+         `operation`, a lowercase `kind` (create, modify, remove, rename or scan), the `path` it \
+         happened to and, for renames, the `new_path`. Include and exclude patterns are relative \
+         to the watched path."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -50,6 +53,7 @@ impl Command for Watch {
                         ("operation".into(), Type::String),
                         ("path".into(), Type::String),
                         ("new_path".into(), Type::String),
+                        ("kind".into(), Type::String),
                     ].into_boxed_slice())
                 ),
             ])
@@ -57,7 +61,7 @@ impl Command for Watch {
             .optional(
                 "closure",
                 SyntaxShape::Closure(Some(vec![SyntaxShape::String, SyntaxShape::String, SyntaxShape::String])),
-                "Some Nu code to run whenever a file changes. The closure will be passed `operation`, `path`, and `new_path` (for renames only) arguments in that order.",
+                "Some Nu code to run whenever a file changes. The closure will be passed `operation`, `path`, and `new_path` (for renames only) arguments in that order, and the event record as input.",
             )
             .named(
                 "debounce",
@@ -71,6 +75,23 @@ impl Command for Watch {
                 "Only report changes for files that match this glob pattern (default: all files)",
                 Some('g'),
             )
+            .named(
+                "include",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::List(Box::new(SyntaxShape::String))]),
+                "Only report changes for files that match one of these glob patterns, like '**/*.rs' (default: all files).",
+                Some('i'),
+            )
+            .named(
+                "exclude",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::List(Box::new(SyntaxShape::String))]),
+                "Don't report changes for files that match one of these glob patterns, like 'target/**'.",
+                Some('e'),
+            )
+            .switch(
+                "initial-scan",
+                "Report every existing file as a `Scan` event before watching for changes.",
+                None,
+            )
             .named(
                 "recursive",
                 SyntaxShape::Boolean,
@@ -112,22 +133,20 @@ impl Command for Watch {
             .get_flag(engine_state, stack, "debounce")?
             .unwrap_or(DEFAULT_WATCH_DEBOUNCE_DURATION);
 
-        let glob_flag: Option<Spanned<String>> = call.get_flag(engine_state, stack, "glob")?;
-        let glob_pattern = glob_flag
-            .map(|glob| {
-                let absolute_path = path.join(glob.item);
-                if verbose {
-                    eprintln!("Absolute glob path: {absolute_path:?}");
-                }
+        let initial_scan = call.has_flag(engine_state, stack, "initial-scan")?;
 
-                nu_glob::Pattern::new(&absolute_path.to_string_lossy()).map_err(|_| {
-                    ShellError::TypeMismatch {
-                        err_message: "Glob pattern is invalid".to_string(),
-                        span: glob.span,
-                    }
-                })
-            })
-            .transpose()?;
+        let mut include = Vec::new();
+        if let Some(glob) = call.get_flag::<Value>(engine_state, stack, "glob")? {
+            include.extend(parse_patterns(&path, glob, verbose)?);
+        }
+        if let Some(globs) = call.get_flag::<Value>(engine_state, stack, "include")? {
+            include.extend(parse_patterns(&path, globs, verbose)?);
+        }
+        let exclude = match call.get_flag::<Value>(engine_state, stack, "exclude")? {
+            Some(globs) => parse_patterns(&path, globs, verbose)?,
+            None => vec![],
+        };
+        let filter = PathFilter { include, exclude };
 
         let recursive_flag: Option<Spanned<bool>> =
             call.get_flag(engine_state, stack, "recursive")?;
@@ -169,22 +188,34 @@ impl Command for Watch {
             eprintln!("Now watching files at {path:?}. Press ctrl+c to abort.");
         }
 
-        let iter = WatchIterator::new(debouncer, rx, engine_state.signals().clone());
+        // the watcher is already running, so changes made while scanning aren't missed
+        let scanned = initial_scan.then(|| {
+            let mut events = Vec::new();
+            scan(
+                &path,
+                recursive_mode == RecursiveMode::Recursive,
+                &mut events,
+            );
+            Ok(events)
+        });
+        let iter = scanned.into_iter().chain(WatchIterator::new(
+            debouncer,
+            rx,
+            engine_state.signals().clone(),
+        ));
 
         if let Some(closure) = closure {
             let mut closure = ClosureEval::new(engine_state, stack, closure);
 
             for events in iter {
                 for event in events? {
-                    let matches_glob = match &glob_pattern {
-                        Some(glob) => glob.matches_path(&event.path),
-                        None => true,
-                    };
-                    if verbose && glob_pattern.is_some() {
-                        eprintln!("Matches glob: {matches_glob}");
+                    let matches = filter.matches_event(&event);
+                    if verbose && !filter.is_empty() {
+                        eprintln!("Matches filter: {matches}");
                     }
 
-                    if matches_glob {
+                    if matches {
+                        let record = WatchEventRecord::from(&event).into_value(head);
                         let result = closure
                             .add_arg(event.operation.into_value(head))
                             .add_arg(event.path.to_string_lossy().into_value(head))
@@ -195,7 +226,7 @@ impl Command for Watch {
                                     .map(Path::to_string_lossy)
                                     .into_value(head),
                             )
-                            .run_with_input(PipelineData::empty());
+                            .run_with_input(record.into_pipeline_data());
 
                         match result {
                             Ok(val) => val.print_table(engine_state, stack, false, false)?,
@@ -207,18 +238,14 @@ impl Command for Watch {
 
             Ok(PipelineData::empty())
         } else {
-            fn glob_filter(glob: Option<&nu_glob::Pattern>, path: &Path) -> bool {
-                let Some(glob) = glob else { return true };
-                glob.matches_path(path)
-            }
-
             let out = iter
                 .flat_map(|e| match e {
                     Ok(events) => Either::Right(events.into_iter().map(Ok)),
                     Err(err) => Either::Left(std::iter::once(Err(err))),
                 })
                 .filter_map(move |e| match e {
-                    Ok(ev) => glob_filter(glob_pattern.as_ref(), &ev.path)
+                    Ok(ev) => filter
+                        .matches_event(&ev)
                         .then(|| WatchEventRecord::from(&ev).into_value(head)),
                     Err(err) => Some(Value::error(err, head)),
                 })
@@ -249,6 +276,11 @@ impl Command for Watch {
     | save --append changes_in_bar.log"#,
                 result: None,
             },
+            Example {
+                description: "Report the Rust files that change, ignoring the build output, starting with the existing ones.",
+                example: r#"watch . --include '**/*.rs' --exclude ['target/**'] --initial-scan | each {|e| $"($e.kind) ($e.path)" }"#,
+                result: None,
+            },
             Example {
                 description: "Print file changes with a debounce time of 5 minutes.",
                 example: r#"watch /foo/bar --debounce 5min { |op, path| $"Registered ($op) on ($path)" | print }"#,
@@ -263,8 +295,93 @@ impl Command for Watch {
     }
 }
 
+fn parse_patterns(
+    root: &Path,
+    globs: Value,
+    verbose: bool,
+) -> Result<Vec<nu_glob::Pattern>, ShellError> {
+    let globs = match globs {
+        Value::List { vals, .. } => vals,
+        glob => vec![glob],
+    };
+    globs
+        .into_iter()
+        .map(|glob| {
+            let span = glob.span();
+            let absolute_path = root.join(glob.coerce_into_string()?);
+            if verbose {
+                eprintln!("Absolute glob path: {absolute_path:?}");
+            }
+
+            nu_glob::Pattern::new(&absolute_path.to_string_lossy()).map_err(|_| {
+                ShellError::TypeMismatch {
+                    err_message: "Glob pattern is invalid".to_string(),
+                    span,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Decides which paths events are reported for.
+struct PathFilter {
+    include: Vec<nu_glob::Pattern>,
+    exclude: Vec<nu_glob::Pattern>,
+}
+
+impl PathFilter {
+    fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|glob| glob.matches_path(path)))
+            && !self.exclude.iter().any(|glob| glob.matches_path(path))
+    }
+
+    /// A rename is reported if either of its paths matches.
+    fn matches_event(&self, event: &WatchEvent) -> bool {
+        self.matches(&event.path) || event.new_path.as_deref().is_some_and(|p| self.matches(p))
+    }
+}
+
+/// Lists the existing files under `path` as `Scan` events. Entries that can't be read are
+/// skipped.
+fn scan(path: &Path, recursive: bool, events: &mut Vec<WatchEvent>) {
+    if path.is_file() {
+        events.push(WatchEvent {
+            operation: "Scan",
+            kind: "scan",
+            path: path.to_path_buf(),
+            new_path: None,
+        });
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if recursive {
+                scan(&entry.path(), recursive, events);
+            }
+        } else {
+            events.push(WatchEvent {
+                operation: "Scan",
+                kind: "scan",
+                path: entry.path(),
+                new_path: None,
+            });
+        }
+    }
+}
+
 struct WatchEvent {
     operation: &'static str,
+    kind: &'static str,
     path: PathBuf,
     new_path: Option<PathBuf>,
 }
@@ -274,6 +391,7 @@ struct WatchEventRecord<'a> {
     operation: &'static str,
     path: Cow<'a, str>,
     new_path: Option<Cow<'a, str>>,
+    kind: &'static str,
 }
 
 impl<'a> From<&'a WatchEvent> for WatchEventRecord<'a> {
@@ -282,6 +400,7 @@ impl<'a> From<&'a WatchEvent> for WatchEventRecord<'a> {
             operation: value.operation,
             path: value.path.to_string_lossy(),
             new_path: value.new_path.as_deref().map(Path::to_string_lossy),
+            kind: value.kind,
         }
     }
 }
@@ -294,11 +413,13 @@ impl TryFrom<DebouncedEvent> for WatchEvent {
         match ev.event.kind {
             EventKind::Create(_) => ev.paths.pop().map(|p| WatchEvent {
                 operation: "Create",
+                kind: "create",
                 path: p,
                 new_path: None,
             }),
             EventKind::Remove(_) => ev.paths.pop().map(|p| WatchEvent {
                 operation: "Remove",
+                kind: "remove",
                 path: p,
                 new_path: None,
             }),
@@ -306,6 +427,7 @@ impl TryFrom<DebouncedEvent> for WatchEvent {
                 ModifyKind::Data(DataChange::Content | DataChange::Any) | ModifyKind::Any,
             ) => ev.paths.pop().map(|p| WatchEvent {
                 operation: "Write",
+                kind: "modify",
                 path: p,
                 new_path: None,
             }),
@@ -316,6 +438,7 @@ impl TryFrom<DebouncedEvent> for WatchEvent {
                 .next_array()
                 .map(|[from, to]| WatchEvent {
                     operation: "Rename",
+                    kind: "rename",
                     path: from,
                     new_path: Some(to),
                 }),
@@ -389,3 +512,42 @@ impl Iterator for WatchIterator {
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> PathFilter {
+        let root = Path::new("/project");
+        let patterns = |globs: &[&str]| {
+            let globs = globs.iter().map(|glob| Value::test_string(*glob)).collect();
+            parse_patterns(root, Value::test_list(globs), false).expect("valid patterns")
+        };
+        PathFilter {
+            include: patterns(include),
+            exclude: patterns(exclude),
+        }
+    }
+
+    #[test]
+    fn test_filter_include_and_exclude() {
+        let filter = filter(&["**/*.rs"], &["target/**"]);
+
+        assert!(filter.matches(Path::new("/project/src/main.rs")));
+        assert!(!filter.matches(Path::new("/project/Cargo.toml")));
+        assert!(!filter.matches(Path::new("/project/target/debug/build.rs")));
+    }
+
+    #[test]
+    fn test_filter_matches_either_rename_path() {
+        let filter = filter(&["**/*.rs"], &[]);
+        let event = WatchEvent {
+            operation: "Rename",
+            kind: "rename",
+            path: PathBuf::from("/project/src/main.rs.tmp"),
+            new_path: Some(PathBuf::from("/project/src/main.rs")),
+        };
+
+        assert!(filter.matches_event(&event));
+    }
+}
@@ -0,0 +1,168 @@
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::engine::Closure;
+
+#[derive(Clone)]
+pub struct Aggregate;
+
+impl Command for Aggregate {
+    fn name(&self) -> &str {
+        "aggregate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::table(), Type::table())])
+            .required(
+                "aggregations",
+                SyntaxShape::Record(vec![]),
+                "A record of the columns to add and the closures computing them from the items of each group.",
+            )
+            .category(Category::Filters)
+    }
+
+    fn description(&self) -> &str {
+        "Compute values for each group of a table made by group-by."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The input is a table with an "items" column, like the output of `group-by --to-table`, or a
+tree of such tables, like the output of `group-by --into-tree`, where the rows of the upper levels
+have a "groups" column instead. Every closure receives the items of a group as a list, both as its
+argument and as its input, and its result is added to the group's row under the name it has in the
+record. In a tree, the closures also run for every row of the upper levels, with the items of all
+the groups below that row."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["summarize", "rollup", "statistics", "group", "tree"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Sum the values of each group.",
+                example: "[[lang items]; [rs [[lines]; [10] [20]]] [rb [[lines]; [5]]]] | aggregate {total: {get lines | math sum}}",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "lang" => Value::test_string("rs"),
+                        "items" => Value::test_list(vec![
+                            Value::test_record(record! { "lines" => Value::test_int(10) }),
+                            Value::test_record(record! { "lines" => Value::test_int(20) }),
+                        ]),
+                        "total" => Value::test_int(30),
+                    }),
+                    Value::test_record(record! {
+                        "lang" => Value::test_string("rb"),
+                        "items" => Value::test_list(vec![
+                            Value::test_record(record! { "lines" => Value::test_int(5) }),
+                        ]),
+                        "total" => Value::test_int(5),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Count the cities and sum the population of every region and country.",
+                example: "open cities.csv | group-by region country --into-tree | aggregate {cities: {length}, population: {get population | math sum}}",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let aggregations: Record = call.req(engine_state, stack, 0)?;
+
+        let mut closures = aggregations
+            .into_iter()
+            .map(|(name, value)| {
+                let closure: Closure = FromValue::from_value(value)?;
+                Ok((name, ClosureEval::new(engine_state, stack, closure)))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        let metadata = input.metadata();
+        let rows = input.into_iter().collect::<Vec<_>>();
+        let (rows, _) = aggregate(rows, &mut closures, engine_state, head)?;
+
+        Ok(Value::list(rows, head).into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+/// Adds the aggregated columns to every row, and returns the rows along with all the items below them.
+fn aggregate(
+    rows: Vec<Value>,
+    closures: &mut [(String, ClosureEval)],
+    engine_state: &EngineState,
+    head: Span,
+) -> Result<(Vec<Value>, Vec<Value>), ShellError> {
+    let mut all_items = vec![];
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            engine_state.signals().check(&head)?;
+            let span = row.span();
+            let mut record = match row {
+                Value::Record { val, .. } => val.into_owned(),
+                Value::Error { error, .. } => return Err(*error),
+                other => {
+                    return Err(ShellError::OnlySupportsThisInputType {
+                        exp_input_type: "record".into(),
+                        wrong_type: other.get_type().to_string(),
+                        dst_span: head,
+                        src_span: span,
+                    });
+                }
+            };
+
+            let items = if let Some(groups) = record.get_mut("groups") {
+                let groups_span = groups.span();
+                let (rows, items) = aggregate(
+                    std::mem::take(groups).into_list()?,
+                    closures,
+                    engine_state,
+                    head,
+                )?;
+                *groups = Value::list(rows, groups_span);
+                items
+            } else if let Some(items) = record.get("items") {
+                items.clone().into_list()?
+            } else {
+                return Err(ShellError::CantFindColumn {
+                    col_name: "items".into(),
+                    span: Some(head),
+                    src_span: span,
+                });
+            };
+
+            for (name, closure) in closures.iter_mut() {
+                let value = closure
+                    .run_with_value(Value::list(items.clone(), span))?
+                    .into_value(head)?;
+                record.insert(name.clone(), value);
+            }
+
+            all_items.extend(items);
+            Ok(Value::record(record, span))
+        })
+        .collect::<Result<Vec<_>, ShellError>>()?;
+
+    Ok((rows, all_items))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Aggregate {})
+    }
+}
@@ -608,6 +608,7 @@ fn value_should_be_printed(
         | Value::Int { .. }
         | Value::Filesize { .. }
         | Value::Duration { .. }
+        | Value::Rate { .. }
         | Value::Date { .. }
         | Value::Range { .. }
         | Value::Float { .. }
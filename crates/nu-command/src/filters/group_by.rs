@@ -18,6 +18,11 @@ impl Command for GroupBy {
                 "Return a table with \"groups\" and \"items\" columns.",
                 None,
             )
+            .switch(
+                "into-tree",
+                "Return a table for each level of grouping, nested in the \"groups\" column of the level above it.",
+                None,
+            )
             .rest(
                 "grouper",
                 SyntaxShape::OneOf(vec![
@@ -39,7 +44,12 @@ impl Command for GroupBy {
     - if the input data is not a string, the grouper will convert the key to string but the values will remain in their original format. e.g. with bools, "true" and true would be in the same group (see example).
     - datetime is formatted based on your configuration setting. use `format date` to change the format.
     - filesize is formatted based on your configuration setting. use `format filesize` to change the format.
-    - some nushell values are not supported, such as closures."#
+    - some nushell values are not supported, such as closures.
+
+With --into-tree, every level of grouping is a table with a column named after its grouper. The
+rows of the last level have the grouped values in an "items" column, and the rows of the levels
+above it have the next level in a "groups" column. Use `aggregate` to compute values for each group
+of such a tree."#
     }
 
     fn run(
@@ -214,6 +224,50 @@ impl Command for GroupBy {
                     }),
                 ])),
             },
+            Example {
+                description: "Group items by multiple columns' values into a tree of tables.",
+                example: r#"[
+        [name, lang, year];
+        [andres, rb, "2019"],
+        [jt, rs, "2019"],
+        [storm, rs, "2021"]
+    ]
+    | group-by lang year --into-tree"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "lang" => Value::test_string("rb"),
+                        "groups" => Value::test_list(vec![Value::test_record(record! {
+                            "year" => Value::test_string("2019"),
+                            "items" => Value::test_list(vec![Value::test_record(record! {
+                                "name" => Value::test_string("andres"),
+                                "lang" => Value::test_string("rb"),
+                                "year" => Value::test_string("2019"),
+                            })]),
+                        })]),
+                    }),
+                    Value::test_record(record! {
+                        "lang" => Value::test_string("rs"),
+                        "groups" => Value::test_list(vec![
+                            Value::test_record(record! {
+                                "year" => Value::test_string("2019"),
+                                "items" => Value::test_list(vec![Value::test_record(record! {
+                                    "name" => Value::test_string("jt"),
+                                    "lang" => Value::test_string("rs"),
+                                    "year" => Value::test_string("2019"),
+                                })]),
+                            }),
+                            Value::test_record(record! {
+                                "year" => Value::test_string("2021"),
+                                "items" => Value::test_list(vec![Value::test_record(record! {
+                                    "name" => Value::test_string("storm"),
+                                    "lang" => Value::test_string("rs"),
+                                    "year" => Value::test_string("2021"),
+                                })]),
+                            }),
+                        ]),
+                    }),
+                ])),
+            },
             Example {
                 description: "Group items by column and delete the original.",
                 example: r#"[
@@ -258,11 +312,21 @@ pub fn group_by(
     let head = call.head;
     let groupers: Vec<Spanned<Grouper>> = call.rest(engine_state, stack, 0)?;
     let to_table = call.has_flag(engine_state, stack, "to-table")?;
+    let into_tree = call.has_flag(engine_state, stack, "into-tree")?;
     let config = &stack.get_config(engine_state);
 
+    if to_table && into_tree {
+        return Err(ShellError::IncompatibleParameters {
+            left_message: "can't be used with --into-tree".into(),
+            left_span: call.get_flag_span(stack, "to-table").unwrap_or(head),
+            right_message: "can't be used with --to-table".into(),
+            right_span: call.get_flag_span(stack, "into-tree").unwrap_or(head),
+        });
+    }
+
     let values: Vec<Value> = input.into_iter().collect();
     if values.is_empty() {
-        let val = if to_table {
+        let val = if to_table || into_tree {
             Value::list(Vec::new(), head)
         } else {
             Value::record(Record::new(), head)
@@ -284,6 +348,18 @@ pub fn group_by(
     let value = if to_table {
         let column_names = groupers_to_column_names(&groupers)?;
         grouped.into_table(&column_names, head)
+    } else if into_tree {
+        let column_names = groupers_to_column_names(&groupers)?;
+        if let Some(idx) = column_names.iter().position(|name| name == "groups") {
+            return Err(ShellError::GenericError {
+                error: "grouper arguments can't be named `groups` with --into-tree".into(),
+                msg: "here".into(),
+                span: groupers.get(idx).map(|grouper| grouper.span),
+                help: Some("instead of a cell-path, try using a closure: { get groups }".into()),
+                inner: vec![],
+            });
+        }
+        grouped.into_tree(&column_names, head)
     } else {
         grouped.into_record(head)
     };
@@ -518,6 +594,37 @@ impl Grouped {
         }
     }
 
+    /// Turns each level into a table, with the grouper's column from `column_names`.
+    fn into_tree(self, column_names: &[String], head: Span) -> Value {
+        let column = column_names
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "group".into());
+        let rows = match self.groups {
+            Tree::Leaf(leaf) => leaf
+                .into_iter()
+                .map(|(group, values)| {
+                    record! {
+                        column.clone() => group.into_value(head),
+                        "items" => values.into_value(head),
+                    }
+                    .into_value(head)
+                })
+                .collect::<Vec<_>>(),
+            Tree::Branch(branch) => branch
+                .into_iter()
+                .map(|(group, items)| {
+                    record! {
+                        column.clone() => group.into_value(head),
+                        "groups" => items.into_tree(column_names.get(1..).unwrap_or_default(), head),
+                    }
+                    .into_value(head)
+                })
+                .collect(),
+        };
+        rows.into_value(head)
+    }
+
     fn into_record(self, head: Span) -> Value {
         match self.groups {
             Tree::Leaf(leaf) => Value::record(
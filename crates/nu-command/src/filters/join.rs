@@ -2,22 +2,20 @@ use nu_engine::command_prelude::*;
 use nu_protocol::Config;
 use std::{
     cmp::max,
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
 };
 
 #[derive(Clone)]
 pub struct Join;
 
+#[derive(Clone, Copy, PartialEq)]
 enum JoinType {
     Inner,
     Left,
     Right,
     Outer,
-}
-
-enum IncludeInner {
-    No,
-    Yes,
+    Semi,
+    Anti,
 }
 
 #[derive(Debug, Default)]
@@ -40,13 +38,19 @@ impl Command for Join {
             )
             .required(
                 "left-on",
-                SyntaxShape::String,
-                "Name of column in input (left) table to join on.",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::List(Box::new(SyntaxShape::String)),
+                ]),
+                "Name of column in input (left) table to join on, or a list of names to join on several columns.",
             )
             .optional(
                 "right-on",
-                SyntaxShape::String,
-                "Name of column in right table to join on. Defaults to same column as left table.",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::List(Box::new(SyntaxShape::String)),
+                ]),
+                "Name of column in right table to join on, or a list of names. Defaults to same columns as left table.",
             )
             .named(
                 "prefix",
@@ -63,7 +67,17 @@ impl Command for Join {
             .switch("inner", "Inner join (default).", Some('i'))
             .switch("left", "Left-outer join.", Some('l'))
             .switch("right", "Right-outer join.", Some('r'))
-            .switch("outer", "Outer join.", Some('o'))
+            .switch("outer", "Full outer join.", Some('o'))
+            .switch(
+                "semi",
+                "Semi join: the rows of the input table that have a match in the right table.",
+                None,
+            )
+            .switch(
+                "anti",
+                "Anti join: the rows of the input table that have no match in the right table.",
+                None,
+            )
             .input_output_types(vec![(Type::table(), Type::table())])
             .category(Category::Filters)
     }
@@ -72,8 +86,14 @@ impl Command for Join {
         "Join two tables."
     }
 
+    fn extra_description(&self) -> &str {
+        "The right table is loaded into a hash table, and the input table is streamed through it, \
+so that the input can be much larger than the right table. A right join is the exception: it \
+collects the input to return the rows in the order of the right table."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["sql"]
+        vec!["sql", "hash join", "semi", "anti", "full outer"]
     }
 
     fn run(
@@ -83,8 +103,6 @@ impl Command for Join {
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let input = input.into_stream_or_original(engine_state);
-
         let metadata = input.metadata();
         let table_2: Value = call.req(engine_state, stack, 0)?;
         let l_on: Value = call.req(engine_state, stack, 1)?;
@@ -98,32 +116,68 @@ impl Command for Join {
             suffix: call.get_flag(engine_state, stack, "suffix")?,
         };
 
-        // FIXME: we should handle ListStreams properly instead of collecting
-        let collected_input = input.into_value(span)?;
-
-        match (&collected_input, &table_2, &l_on, &r_on) {
-            (
-                Value::List { vals: rows_1, .. },
-                Value::List { vals: rows_2, .. },
-                Value::String { val: l_on, .. },
-                Value::String { val: r_on, .. },
-            ) => {
-                let result = join(rows_1, rows_2, l_on, r_on, join_type, &rename, span);
-                Ok(PipelineData::value(result, metadata))
-            }
-            _ => Err(ShellError::UnsupportedInput {
-                msg: "(PipelineData<table>, table, string, string)".into(),
-                input: format!(
-                    "({:?}, {:?}, {:?} {:?})",
-                    collected_input,
-                    table_2.get_type(),
-                    l_on.get_type(),
-                    r_on.get_type(),
+        let l_on = join_columns(l_on)?;
+        let r_on = join_columns(r_on)?;
+        if l_on.len() != r_on.len() {
+            return Err(ShellError::IncompatibleParametersSingle {
+                msg: format!(
+                    "Joining {} columns of the input table on {} columns of the right table",
+                    l_on.len(),
+                    r_on.len()
                 ),
-                msg_span: span,
-                input_span: span,
-            }),
+                span,
+            });
         }
+        let shared_keys = l_on
+            .iter()
+            .zip(&r_on)
+            .filter(|(l, r)| l == r)
+            .map(|(l, _)| l.clone())
+            .collect();
+
+        let table_2_type = table_2.get_type();
+        let table_2 = match table_2 {
+            Value::List { vals, .. } => vals,
+            _ => return Err(unsupported_input(input, table_2_type, span)),
+        };
+        // Both list values and streams are accepted as the input table
+        let rows_1: Box<dyn Iterator<Item = Value> + Send> = match input {
+            PipelineData::Value(Value::List { vals, .. }, ..) => Box::new(vals.into_iter()),
+            input => match input.into_stream_or_original(engine_state) {
+                PipelineData::ListStream(stream, ..) => Box::new(stream.into_iter()),
+                input => return Err(unsupported_input(input, table_2_type, span)),
+            },
+        };
+
+        let joined = if join_type == JoinType::Right {
+            // The input table is the one loaded into the hash table
+            HashJoin::new(
+                Box::new(table_2.into_iter()),
+                r_on,
+                rows_1.collect(),
+                &l_on,
+                join_type,
+                shared_keys,
+                rename,
+                span,
+            )
+        } else {
+            HashJoin::new(
+                rows_1,
+                l_on,
+                table_2,
+                &r_on,
+                join_type,
+                shared_keys,
+                rename,
+                span,
+            )
+        };
+
+        Ok(PipelineData::list_stream(
+            ListStream::new(joined, span, engine_state.signals().clone()),
+            metadata,
+        ))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -154,274 +208,373 @@ impl Command for Join {
                     "r_x" => Value::test_int(20),
                 })])),
             },
+            Example {
+                description: "Join two tables on several columns",
+                example: "[{x: 1 y: 1 a: 10} {x: 1 y: 2 a: 20}] | join [{x: 1 y: 2 b: 30}] [x y]",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "x" => Value::test_int(1),
+                    "y" => Value::test_int(2),
+                    "a" => Value::test_int(20),
+                    "b" => Value::test_int(30),
+                })])),
+            },
+            Example {
+                description: "Keep the rows of the input table that have no match in the right table",
+                example: "[{id: 1} {id: 2} {id: 3}] | join --anti [{id: 2}] id",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! { "id" => Value::test_int(1) }),
+                    Value::test_record(record! { "id" => Value::test_int(3) }),
+                ])),
+            },
         ]
     }
 }
 
+fn unsupported_input(input: PipelineData, table_2_type: Type, span: Span) -> ShellError {
+    let collected_input = match input.into_value(span) {
+        Ok(value) => value,
+        Err(err) => return err,
+    };
+    ShellError::UnsupportedInput {
+        msg: "(PipelineData<table>, table, string, string)".into(),
+        input: format!("({collected_input:?}, {table_2_type:?})"),
+        msg_span: span,
+        input_span: span,
+    }
+}
+
 fn join_type(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
 ) -> Result<JoinType, nu_protocol::ShellError> {
-    match (
-        call.has_flag(engine_state, stack, "inner")?,
-        call.has_flag(engine_state, stack, "left")?,
-        call.has_flag(engine_state, stack, "right")?,
-        call.has_flag(engine_state, stack, "outer")?,
-    ) {
-        (_, false, false, false) => Ok(JoinType::Inner),
-        (false, true, false, false) => Ok(JoinType::Left),
-        (false, false, true, false) => Ok(JoinType::Right),
-        (false, false, false, true) => Ok(JoinType::Outer),
-        _ => Err(ShellError::UnsupportedInput {
-            msg: "Choose one of: --inner, --left, --right, --outer".into(),
-            input: "".into(),
-            msg_span: call.head,
-            input_span: call.head,
-        }),
+    let mut join_type = None;
+    for (flag, flag_type) in [
+        ("inner", JoinType::Inner),
+        ("left", JoinType::Left),
+        ("right", JoinType::Right),
+        ("outer", JoinType::Outer),
+        ("semi", JoinType::Semi),
+        ("anti", JoinType::Anti),
+    ] {
+        if call.has_flag(engine_state, stack, flag)? {
+            if join_type.is_some() {
+                return Err(ShellError::UnsupportedInput {
+                    msg: "Choose one of: --inner, --left, --right, --outer, --semi, --anti".into(),
+                    input: "".into(),
+                    msg_span: call.head,
+                    input_span: call.head,
+                });
+            }
+            join_type = Some(flag_type);
+        }
+    }
+    Ok(join_type.unwrap_or(JoinType::Inner))
+}
+
+// Turn a `left-on` or `right-on` argument into the names of the columns to join on.
+fn join_columns(on: Value) -> Result<Vec<String>, ShellError> {
+    let span = on.span();
+    let columns = match on {
+        Value::String { val, .. } => vec![val],
+        Value::List { vals, .. } => vals
+            .into_iter()
+            .map(Value::into_string)
+            .collect::<Result<_, _>>()?,
+        other => {
+            return Err(ShellError::UnsupportedInput {
+                msg: "string or list<string>".into(),
+                input: other.get_type().to_string(),
+                msg_span: span,
+                input_span: span,
+            });
+        }
+    };
+    if columns.is_empty() {
+        return Err(ShellError::IncorrectValue {
+            msg: "At least one column to join on is required".into(),
+            val_span: span,
+            call_span: span,
+        });
     }
+    Ok(columns)
 }
 
-fn join(
-    left: &[Value],
-    right: &[Value],
-    left_join_key: &str,
-    right_join_key: &str,
+// Hash join of the rows of `probe` against the rows of `build`, streaming the
+// joined rows as the rows of `probe` come in.
+//
+// Inner / Left / Outer / Semi / Anti Join
+// ---------------------------------------
+// `build` is the right table and `probe` the (streamed) left table.
+// Make look-up table from rows of `build`
+// For each row l of `probe`:
+//    If any matching rows in `build`:
+//        For each matching row r in `build`:
+//            Emit (l, r)                  (Semi: emit l once; Anti: nothing)
+//    Else if not InnerJoin or SemiJoin:
+//        Emit (l, null)                   (Anti: emit l)
+// If OuterJoin, for each row r of `build` that was never matched:
+//    Emit (null, r)
+//
+// Right Join
+// ----------
+// `build` is the left table and `probe` the right table, with the same procedure
+// as a Left Join except that the rows are merged the other way around, so that
+// the rows come out in the order of the right table.
+struct HashJoin {
+    probe: Box<dyn Iterator<Item = Value> + Send>,
+    probe_on: Vec<String>,
+    // Column names of the first row of `probe`, used for the rows of `build`
+    // that did not match any row in an outer join.
+    probe_columns: Option<Vec<String>>,
+    build: Vec<Value>,
+    build_columns: Vec<String>,
+    build_has_key: bool,
+    lookup: HashMap<Vec<String>, Vec<usize>>,
+    matched: Vec<bool>,
     join_type: JoinType,
-    rename: &RightColumnRename,
+    // Names of the join columns that are the same in both tables; they are only
+    // output once.
+    shared_keys: Vec<String>,
+    rename: RightColumnRename,
+    config: Config,
+    // Like a SQL join on a column that doesn't exist, a join outputs nothing
+    // unless at least one row of `probe` has the join columns. Unmatched rows
+    // are held back here until such a row comes in.
+    pending: Vec<Value>,
+    seen_key: bool,
+    output: VecDeque<Value>,
+    finished: bool,
     span: Span,
-) -> Value {
-    // Inner / Right Join
-    // ------------------
-    // Make look-up table from rows on left
-    // For each row r on right:
-    //    If any matching rows on left:
-    //        For each matching row l on left:
-    //            Emit (l, r)
-    //    Else if RightJoin:
-    //        Emit (null, r)
-
-    // Left Join
-    // ----------
-    // Make look-up table from rows on right
-    // For each row l on left:
-    //    If any matching rows on right:
-    //        For each matching row r on right:
-    //            Emit (l, r)
-    //    Else:
-    //        Emit (l, null)
-
-    // Outer Join
-    // ----------
-    // Perform Left Join procedure
-    // Perform Right Join procedure, but excluding rows in Inner Join
-
-    let config = Config::default();
-    let sep = ",";
-    let cap = max(left.len(), right.len());
-    let shared_join_key = if left_join_key == right_join_key {
-        Some(left_join_key)
-    } else {
-        None
-    };
+}
 
-    // For the "other" table, create a map from value in `on` column to a list of the
-    // rows having that value.
-    let mut result: Vec<Value> = Vec::new();
-    let is_outer = matches!(join_type, JoinType::Outer);
-    let (this, this_join_key, other, other_keys, join_type) = match join_type {
-        JoinType::Left | JoinType::Outer => (
-            left,
-            left_join_key,
-            lookup_table(right, right_join_key, sep, cap, &config),
-            column_names(right),
-            // For Outer we do a Left pass and a Right pass; this is the Left
-            // pass.
-            JoinType::Left,
-        ),
-        JoinType::Inner | JoinType::Right => (
-            right,
-            right_join_key,
-            lookup_table(left, left_join_key, sep, cap, &config),
-            column_names(left),
+impl HashJoin {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        probe: Box<dyn Iterator<Item = Value> + Send>,
+        probe_on: Vec<String>,
+        build: Vec<Value>,
+        build_on: &[String],
+        join_type: JoinType,
+        shared_keys: Vec<String>,
+        rename: RightColumnRename,
+        span: Span,
+    ) -> Self {
+        let config = Config::default();
+        let lookup = lookup_table(&build, build_on, &config);
+        let build_columns = column_names(&build);
+        let build_has_key = build.iter().any(|row| match row.as_record() {
+            Ok(record) => build_on.iter().all(|column| record.contains(column)),
+            Err(_) => false,
+        });
+        let matched = vec![false; build.len()];
+        Self {
+            probe,
+            probe_on,
+            probe_columns: None,
+            build,
+            build_columns,
+            build_has_key,
+            lookup,
+            matched,
             join_type,
-        ),
-    };
-    join_rows(
-        &mut result,
-        this,
-        this_join_key,
-        other,
-        other_keys,
-        shared_join_key,
-        &join_type,
-        IncludeInner::Yes,
-        sep,
-        &config,
-        rename,
-        span,
-    );
-    if is_outer {
-        let (this, this_join_key, other, other_names, join_type) = (
-            right,
-            right_join_key,
-            lookup_table(left, left_join_key, sep, cap, &config),
-            column_names(left),
-            JoinType::Right,
-        );
-        join_rows(
-            &mut result,
-            this,
-            this_join_key,
-            other,
-            other_names,
-            shared_join_key,
-            &join_type,
-            IncludeInner::No,
-            sep,
-            &config,
+            shared_keys,
             rename,
+            config,
+            pending: Vec::new(),
+            seen_key: false,
+            output: VecDeque::new(),
+            finished: false,
             span,
-        );
+        }
     }
-    Value::list(result, span)
-}
 
-// Join rows of `this` (a nushell table) to rows of `other` (a lookup-table
-// containing rows of a nushell table).
-#[allow(clippy::too_many_arguments)]
-fn join_rows(
-    result: &mut Vec<Value>,
-    this: &[Value],
-    this_join_key: &str,
-    other: HashMap<String, Vec<&Record>>,
-    other_keys: Vec<&String>,
-    shared_join_key: Option<&str>,
-    join_type: &JoinType,
-    include_inner: IncludeInner,
-    sep: &str,
-    config: &Config,
-    rename: &RightColumnRename,
-    span: Span,
-) {
-    if !this
-        .iter()
-        .any(|this_record| match this_record.as_record() {
-            Ok(record) => record.contains(this_join_key),
-            Err(_) => false,
-        })
-    {
-        // `this` table does not contain the join column; do nothing
-        return;
+    // Whether `probe` holds the rows of the left input table.
+    fn probe_is_left(&self) -> bool {
+        self.join_type != JoinType::Right
     }
-    for this_row in this {
-        if let Value::Record {
-            val: this_record, ..
-        } = this_row
-        {
-            if let Some(this_valkey) = this_record.get(this_join_key)
-                && let Some(other_rows) = other.get(&this_valkey.to_expanded_string(sep, config))
-            {
-                if let IncludeInner::Yes = include_inner {
-                    for other_record in other_rows {
-                        // `other` table contains rows matching `this` row on the join column
-                        let record = match join_type {
-                            JoinType::Inner | JoinType::Right => merge_records(
-                                other_record, // `other` (lookup) is the left input table
-                                this_record,
-                                shared_join_key,
-                                rename,
-                            ),
-                            JoinType::Left => merge_records(
-                                this_record, // `this` is the left input table
-                                other_record,
-                                shared_join_key,
-                                rename,
-                            ),
-                            _ => panic!("not implemented"),
-                        };
-                        result.push(Value::record(record, span))
+
+    fn merge(&self, probe_record: &Record, build_record: &Record) -> Value {
+        let record = if self.probe_is_left() {
+            merge_records(probe_record, build_record, &self.shared_keys, &self.rename)
+        } else {
+            merge_records(build_record, probe_record, &self.shared_keys, &self.rename)
+        };
+        Value::record(record, self.span)
+    }
+
+    fn probe_row(&mut self, row: Value) {
+        let record = match row {
+            Value::Record { val, .. } => val,
+            Value::Error { .. } => {
+                self.output.push_back(row);
+                return;
+            }
+            // rows that aren't records can't be joined
+            _ => return,
+        };
+        if self.probe_columns.is_none() {
+            self.probe_columns = Some(record.columns().cloned().collect());
+        }
+
+        let Some(key) = join_key(&record, &self.probe_on, &self.config) else {
+            self.unmatched(&record);
+            return;
+        };
+        if !self.seen_key {
+            self.seen_key = true;
+            self.output.extend(self.pending.drain(..));
+        }
+
+        let matches = self.lookup.get(&key).cloned().unwrap_or_default();
+        if matches.is_empty() {
+            self.unmatched(&record);
+            return;
+        }
+        match self.join_type {
+            JoinType::Semi => self
+                .output
+                .push_back(Value::record(record.into_owned(), self.span)),
+            JoinType::Anti => {}
+            _ => {
+                for idx in matches {
+                    self.matched[idx] = true;
+                    if let Value::Record {
+                        val: build_record, ..
+                    } = &self.build[idx]
+                    {
+                        let joined = self.merge(&record, build_record);
+                        self.output.push_back(joined);
                     }
                 }
-                continue;
             }
-            if !matches!(join_type, JoinType::Inner) {
-                // Either `this` row is missing a value for the join column or
-                // `other` table did not contain any rows matching
-                // `this` row on the join column; emit a single joined
-                // row with null values for columns not present
-                let other_record = other_keys
-                    .iter()
-                    .map(|&key| {
-                        let val = if Some(key.as_ref()) == shared_join_key {
-                            this_record
-                                .get(key)
-                                .cloned()
-                                .unwrap_or_else(|| Value::nothing(span))
-                        } else {
-                            Value::nothing(span)
-                        };
-
-                        (key.clone(), val)
-                    })
-                    .collect();
-
-                let record = match join_type {
-                    JoinType::Inner | JoinType::Right => {
-                        merge_records(&other_record, this_record, shared_join_key, rename)
-                    }
-                    JoinType::Left => {
-                        merge_records(this_record, &other_record, shared_join_key, rename)
-                    }
-                    _ => panic!("not implemented"),
-                };
+        }
+    }
 
-                result.push(Value::record(record, span))
+    // Either `record` is missing a value for a join column or no rows of `build`
+    // matched it on the join columns
+    fn unmatched(&mut self, record: &Record) {
+        let value = match self.join_type {
+            JoinType::Inner | JoinType::Semi => return,
+            JoinType::Anti => Value::record(record.clone(), self.span),
+            JoinType::Left | JoinType::Right | JoinType::Outer => {
+                // emit a single joined row with null values for columns not present
+                let nulls = null_record(&self.build_columns, record, &self.shared_keys, self.span);
+                self.merge(record, &nulls)
             }
         };
+        if self.seen_key {
+            self.output.push_back(value);
+        } else {
+            self.pending.push(value);
+        }
+    }
+
+    // Emit the rows of `build` that no row matched, for an outer join.
+    fn finish(&mut self) {
+        if self.join_type != JoinType::Outer || !self.build_has_key {
+            return;
+        }
+        let probe_columns = self.probe_columns.take().unwrap_or_default();
+        for (row, matched) in self.build.iter().zip(&self.matched) {
+            if let (Value::Record { val: record, .. }, false) = (row, matched) {
+                let nulls = null_record(&probe_columns, record, &self.shared_keys, self.span);
+                let joined = merge_records(&nulls, record, &self.shared_keys, &self.rename);
+                self.output.push_back(Value::record(joined, self.span));
+            }
+        }
     }
 }
 
+impl Iterator for HashJoin {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        loop {
+            if let Some(value) = self.output.pop_front() {
+                return Some(value);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.probe.next() {
+                Some(row) => self.probe_row(row),
+                None => {
+                    self.finished = true;
+                    self.finish();
+                }
+            }
+        }
+    }
+}
+
+// A record with null values for `columns`, except for the shared join columns
+// which take their values from `record`.
+fn null_record(columns: &[String], record: &Record, shared_keys: &[String], span: Span) -> Record {
+    columns
+        .iter()
+        .map(|column| {
+            let val = if shared_keys.contains(column) {
+                record
+                    .get(column)
+                    .cloned()
+                    .unwrap_or_else(|| Value::nothing(span))
+            } else {
+                Value::nothing(span)
+            };
+            (column.clone(), val)
+        })
+        .collect()
+}
+
 // Return column names (i.e. ordered keys from the first row; we assume that
 // these are the same for all rows).
-fn column_names(table: &[Value]) -> Vec<&String> {
+fn column_names(table: &[Value]) -> Vec<String> {
     table
         .iter()
         .find_map(|val| match val {
-            Value::Record { val, .. } => Some(val.columns().collect()),
+            Value::Record { val, .. } => Some(val.columns().cloned().collect()),
             _ => None,
         })
         .unwrap_or_default()
 }
 
-// Create a map from value in `on` column to a list of the rows having that
-// value.
-fn lookup_table<'a>(
-    rows: &'a [Value],
-    on: &str,
-    sep: &str,
-    cap: usize,
+// The values of the `on` columns of `record`, or `None` if it is missing any of them.
+fn join_key(record: &Record, on: &[String], config: &Config) -> Option<Vec<String>> {
+    on.iter()
+        .map(|column| {
+            record
+                .get(column)
+                .map(|val| val.to_expanded_string(",", config))
+        })
+        .collect()
+}
+
+// Create a map from the values in the `on` columns to the indices of the rows
+// having those values.
+fn lookup_table(
+    rows: &[Value],
+    on: &[String],
     config: &Config,
-) -> HashMap<String, Vec<&'a Record>> {
-    let mut map = HashMap::<String, Vec<&'a Record>>::with_capacity(cap);
-    for row in rows {
+) -> HashMap<Vec<String>, Vec<usize>> {
+    let mut map = HashMap::<Vec<String>, Vec<usize>>::with_capacity(rows.len());
+    for (idx, row) in rows.iter().enumerate() {
         if let Value::Record { val: record, .. } = row
-            && let Some(val) = record.get(on)
+            && let Some(key) = join_key(record, on, config)
         {
-            let valkey = val.to_expanded_string(sep, config);
-            map.entry(valkey).or_default().push(record);
+            map.entry(key).or_default().push(idx);
         };
     }
     map
 }
 
 // Merge `left` and `right` records, renaming keys in `right` where they clash
-// with keys in `left`. If `shared_key` is supplied then it is the name of a key
-// that should not be renamed (its values are guaranteed to be equal).
+// with keys in `left`. The `shared_keys` are the names of keys that should not
+// be renamed (their values are guaranteed to be equal).
 fn merge_records(
     left: &Record,
     right: &Record,
-    shared_key: Option<&str>,
+    shared_keys: &[String],
     rename: &RightColumnRename,
 ) -> Record {
     let cap = max(left.len(), right.len());
@@ -433,7 +586,7 @@ fn merge_records(
     }
 
     for (k, v) in right {
-        let k_shared = shared_key == Some(k.as_str());
+        let k_shared = shared_keys.contains(k);
         // Do not output shared join key twice
         if k_shared && seen.contains(k) {
             continue;
@@ -1,3 +1,4 @@
+mod aggregate;
 mod all;
 mod any;
 mod append;
@@ -30,11 +31,13 @@ mod lines;
 mod merge;
 mod move_;
 mod par_each;
+mod pivot;
 mod prepend;
 mod reduce;
 mod reject;
 mod rename;
 mod reverse;
+mod rolling;
 mod select;
 #[cfg(feature = "rand")]
 mod shuffle;
@@ -47,6 +50,7 @@ mod tee;
 mod transpose;
 mod uniq;
 mod uniq_by;
+mod unpivot;
 mod update;
 mod upsert;
 mod utils;
@@ -56,6 +60,7 @@ mod window;
 mod wrap;
 mod zip;
 
+pub use aggregate::Aggregate;
 pub use all::All;
 pub use any::Any;
 pub use append::Append;
@@ -89,11 +94,13 @@ pub use merge::Merge;
 pub use merge::MergeDeep;
 pub use move_::Move;
 pub use par_each::ParEach;
+pub use pivot::Pivot;
 pub use prepend::Prepend;
 pub use reduce::Reduce;
 pub use reject::Reject;
 pub use rename::Rename;
 pub use reverse::Reverse;
+pub use rolling::Rolling;
 pub use select::Select;
 #[cfg(feature = "rand")]
 pub use shuffle::Shuffle;
@@ -106,6 +113,7 @@ pub use tee::Tee;
 pub use transpose::Transpose;
 pub use uniq::*;
 pub use uniq_by::UniqBy;
+pub use unpivot::Unpivot;
 pub use update::Update;
 pub use upsert::Upsert;
 pub use values::Values;
@@ -1,7 +1,11 @@
 use super::utils::chain_error_with_input;
 use nu_engine::{ClosureEval, ClosureEvalOnce, command_prelude::*};
-use nu_protocol::{Signals, engine::Closure};
+use nu_protocol::{DeprecationEntry, DeprecationType, ReportMode, Signals, engine::Closure};
 use rayon::prelude::*;
+use std::{
+    collections::BTreeMap,
+    sync::mpsc::{self, Receiver, Sender},
+};
 
 #[derive(Clone)]
 pub struct ParEach;
@@ -26,14 +30,31 @@ impl Command for ParEach {
                 (Type::Any, Type::Any),
             ])
             .named(
-                "threads",
+                "max-threads",
                 SyntaxShape::Int,
-                "The number of threads to use.",
+                "The maximum number of threads to use.",
                 Some('t'),
             )
+            .switch(
+                "ordered",
+                "Stream the results in the same order as the input.",
+                Some('o'),
+            )
+            .named(
+                "buffer",
+                SyntaxShape::Int,
+                "The maximum number of rows to process ahead of the output (defaults to four per thread).",
+                Some('b'),
+            )
+            .named(
+                "threads",
+                SyntaxShape::Int,
+                "The number of threads to use (deprecated, use --max-threads).",
+                None,
+            )
             .switch(
                 "keep-order",
-                "Keep sequence of output same as the order of input.",
+                "Keep sequence of output same as the order of input (deprecated, use --ordered).",
                 Some('k'),
             )
             .required(
@@ -53,7 +74,7 @@ impl Command for ParEach {
                 result: None,
             },
             Example {
-                example: r#"[1 2 3] | par-each --keep-order {|e| $e * 2 }"#,
+                example: r#"[1 2 3] | par-each --ordered {|e| $e * 2 }"#,
                 description: "Multiplies each number, keeping an original order.",
                 result: Some(Value::test_list(vec![
                     Value::test_int(2),
@@ -61,6 +82,15 @@ impl Command for ParEach {
                     Value::test_int(6),
                 ])),
             },
+            Example {
+                example: r#"1..1000 | par-each --ordered --max-threads 4 --buffer 16 {|e| $e * 2 } | first 3"#,
+                description: "Use at most 4 threads, and never work on more than 16 rows ahead of the output.",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(2),
+                    Value::test_int(4),
+                    Value::test_int(6),
+                ])),
+            },
             Example {
                 example: r#"1..3 | enumerate | par-each {|p| update item ($p.item * 2)} | sort-by item | get item"#,
                 description: "Enumerate and sort-by can be used to reconstruct the original order.",
@@ -112,22 +142,34 @@ impl Command for ParEach {
 
         let head = call.head;
         let closure: Closure = call.req(engine_state, stack, 0)?;
-        let threads: Option<usize> = call.get_flag(engine_state, stack, "threads")?;
+        let threads: Option<usize> = match call.get_flag(engine_state, stack, "max-threads")? {
+            Some(threads) => Some(threads),
+            None => call.get_flag(engine_state, stack, "threads")?,
+        };
         let max_threads = threads.unwrap_or(0);
-        let keep_order = call.has_flag(engine_state, stack, "keep-order")?;
+        let ordered = call.has_flag(engine_state, stack, "ordered")?
+            || call.has_flag(engine_state, stack, "keep-order")?;
+        let buffer: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "buffer")?;
+        let buffer = buffer
+            .map(|buffer| match buffer.item {
+                1.. => Ok(buffer.item as usize),
+                _ => Err(ShellError::NeedsPositiveValue { span: buffer.span }),
+            })
+            .transpose()?;
+        // Ordered output and a bounded buffer both need the rows to be streamed through a window,
+        // otherwise all the results are collected at once, in whatever order they finish.
+        let windowed = ordered || buffer.is_some();
 
         let input = input.into_stream_or_original(engine_state);
         let metadata = input.metadata();
 
-        // A helper function sorts the output if needed
-        let apply_order = |mut vec: Vec<(usize, Value)>| {
-            if keep_order {
-                // It runs inside the rayon's thread pool so parallel sorting can be used.
-                // There are no identical indexes, so unstable sorting can be used.
-                vec.par_sort_unstable_by_key(|(index, _)| *index);
-            }
-
-            vec.into_iter().map(|(_, val)| val)
+        let window = |values: Box<dyn Iterator<Item = Value> + Send>, span: Span| {
+            let pool = create_pool(max_threads, head)?;
+            let buffer = buffer.unwrap_or(pool.current_num_threads() * 4);
+            let closure_eval = ClosureEval::new(engine_state, stack, closure.clone());
+            let stream = ParEachWindow::new(values, closure_eval, pool, buffer, ordered);
+            let stream = ListStream::new(stream, span, engine_state.signals().clone());
+            Ok::<_, ShellError>(PipelineData::list_stream(stream, None))
         };
 
         match input {
@@ -135,19 +177,24 @@ impl Command for ParEach {
             PipelineData::Value(value, ..) => {
                 let span = value.span();
                 match value {
+                    Value::List { vals, .. } if windowed => {
+                        window(Box::new(vals.into_iter()), span)
+                    }
                     Value::List { vals, .. } => Ok(create_pool(max_threads, head)?.install(|| {
-                        let par_iter = vals.into_par_iter().enumerate();
+                        let par_iter = vals.into_par_iter();
                         let mapped = parallel_closure_map(engine_state, stack, &closure, par_iter);
-                        apply_order(mapped.collect())
+                        mapped
+                            .collect::<Vec<_>>()
                             .into_pipeline_data(span, engine_state.signals().clone())
                     })),
+                    Value::Range { val, .. } if windowed => {
+                        window(Box::new(val.into_range_iter(span, Signals::empty())), span)
+                    }
                     Value::Range { val, .. } => Ok(create_pool(max_threads, head)?.install(|| {
-                        let par_iter = val
-                            .into_range_iter(span, Signals::empty())
-                            .enumerate()
-                            .par_bridge();
+                        let par_iter = val.into_range_iter(span, Signals::empty()).par_bridge();
                         let mapped = parallel_closure_map(engine_state, stack, &closure, par_iter);
-                        apply_order(mapped.collect())
+                        mapped
+                            .collect::<Vec<_>>()
                             .into_pipeline_data(span, engine_state.signals().clone())
                     })),
                     // This match allows non-iterables to be accepted,
@@ -157,28 +204,34 @@ impl Command for ParEach {
                     }
                 }
             }
+            PipelineData::ListStream(stream, ..) if windowed => {
+                window(Box::new(stream.into_iter()), head)
+            }
             PipelineData::ListStream(stream, ..) => {
                 Ok(create_pool(max_threads, head)?.install(|| {
-                    let par_iter = stream.into_iter().enumerate().par_bridge();
+                    let par_iter = stream.into_iter().par_bridge();
                     let mapped = parallel_closure_map(engine_state, stack, &closure, par_iter);
-
-                    apply_order(mapped.collect())
+                    mapped
+                        .collect::<Vec<_>>()
                         .into_pipeline_data(head, engine_state.signals().clone())
                 }))
             }
             PipelineData::ByteStream(stream, ..) => {
                 if let Some(chunks) = stream.chunks() {
-                    Ok(create_pool(max_threads, head)?.install(|| {
-                        let par_iter = chunks
-                            .enumerate()
-                            .map(move |(idx, val)| {
-                                (idx, val.unwrap_or_else(|err| Value::error(err, head)))
-                            })
-                            .par_bridge();
-                        let mapped = parallel_closure_map(engine_state, stack, &closure, par_iter);
-                        apply_order(mapped.collect())
-                            .into_pipeline_data(head, engine_state.signals().clone())
-                    }))
+                    let values =
+                        chunks.map(move |val| val.unwrap_or_else(|err| Value::error(err, head)));
+                    if windowed {
+                        window(Box::new(values), head)
+                    } else {
+                        Ok(create_pool(max_threads, head)?.install(|| {
+                            let par_iter = values.par_bridge();
+                            let mapped =
+                                parallel_closure_map(engine_state, stack, &closure, par_iter);
+                            mapped
+                                .collect::<Vec<_>>()
+                                .into_pipeline_data(head, engine_state.signals().clone())
+                        }))
+                    }
                 } else {
                     Ok(PipelineData::empty())
                 }
@@ -187,31 +240,130 @@ impl Command for ParEach {
         .and_then(|x| x.filter(|v| !v.is_nothing(), engine_state.signals()))
         .map(|data| data.set_metadata(metadata))
     }
+
+    fn deprecation_info(&self) -> Vec<DeprecationEntry> {
+        vec![
+            DeprecationEntry {
+                ty: DeprecationType::Flag("threads".into()),
+                report_mode: ReportMode::FirstUse,
+                since: Some("0.112.0".into()),
+                expected_removal: None,
+                help: Some("This flag has been renamed to `--max-threads (-t)`.".into()),
+            },
+            DeprecationEntry {
+                ty: DeprecationType::Flag("keep-order".into()),
+                report_mode: ReportMode::FirstUse,
+                since: Some("0.112.0".into()),
+                expected_removal: None,
+                help: Some(
+                    "This flag has been renamed to `--ordered (-o)`, which streams the results instead of collecting them."
+                        .into(),
+                ),
+            },
+        ]
+    }
+}
+
+fn run_closure(closure_eval: &mut ClosureEval, value: Value) -> Value {
+    let span = value.span();
+    let is_error = value.is_error();
+    closure_eval
+        .run_with_value(value)
+        .and_then(|data| data.into_value(span))
+        .unwrap_or_else(|err| Value::error(chain_error_with_input(err, is_error, span), span))
 }
 
 fn parallel_closure_map(
     engine_state: &EngineState,
     stack: &mut Stack,
     closure: &Closure,
-    input: impl ParallelIterator<Item = (usize, Value)>,
-) -> impl ParallelIterator<Item = (usize, Value)> {
+    input: impl ParallelIterator<Item = Value>,
+) -> impl ParallelIterator<Item = Value> {
     input.map_init(
         move || ClosureEval::new(engine_state, stack, closure.clone()),
-        |closure_eval, (index, value)| {
-            let span = value.span();
-            let is_error = value.is_error();
-            let value = closure_eval
-                .run_with_value(value)
-                .and_then(|data| data.into_value(span))
-                .unwrap_or_else(|err| {
-                    Value::error(chain_error_with_input(err, is_error, span), span)
-                });
-
-            (index, value)
-        },
+        run_closure,
     )
 }
 
+/// Runs the closure on at most `buffer` rows ahead of the output,
+/// yielding the results as they finish or in the order of the input.
+struct ParEachWindow {
+    input: Box<dyn Iterator<Item = Value> + Send>,
+    closure_eval: ClosureEval,
+    pool: rayon::ThreadPool,
+    buffer: usize,
+    ordered: bool,
+    sender: Sender<(usize, Value)>,
+    receiver: Receiver<(usize, Value)>,
+    /// The number of rows handed to the thread pool
+    started: usize,
+    /// The number of results yielded
+    finished: usize,
+    /// Results that finished before the rows preceding them, when the output is ordered
+    pending: BTreeMap<usize, Value>,
+}
+
+impl ParEachWindow {
+    fn new(
+        input: Box<dyn Iterator<Item = Value> + Send>,
+        closure_eval: ClosureEval,
+        pool: rayon::ThreadPool,
+        buffer: usize,
+        ordered: bool,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            input: Box::new(input.fuse()),
+            closure_eval,
+            pool,
+            buffer,
+            ordered,
+            sender,
+            receiver,
+            started: 0,
+            finished: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+}
+
+impl Iterator for ParEachWindow {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.started - self.finished < self.buffer
+            && let Some(value) = self.input.next()
+        {
+            let index = self.started;
+            let mut closure_eval = self.closure_eval.clone();
+            let sender = self.sender.clone();
+            self.pool.spawn_fifo(move || {
+                // The receiver is gone if the output is no longer being read.
+                let _ = sender.send((index, run_closure(&mut closure_eval, value)));
+            });
+            self.started += 1;
+        }
+
+        if self.finished == self.started {
+            return None;
+        }
+
+        let value = if self.ordered {
+            loop {
+                if let Some(value) = self.pending.remove(&self.finished) {
+                    break value;
+                }
+                let (index, value) = self.receiver.recv().ok()?;
+                self.pending.insert(index, value);
+            }
+        } else {
+            self.receiver.recv().ok()?.1
+        };
+        self.finished += 1;
+        Some(value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
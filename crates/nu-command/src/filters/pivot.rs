@@ -0,0 +1,290 @@
+use crate::math::reducers::{Reduce, reducer_for};
+use indexmap::IndexMap;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct Pivot;
+
+impl Command for Pivot {
+    fn name(&self) -> &str {
+        "pivot"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::table(), Type::table())])
+            .required_named(
+                "index",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::List(Box::new(SyntaxShape::String)),
+                ]),
+                "The column, or list of columns, whose values identify the rows of the output.",
+                Some('i'),
+            )
+            .required_named(
+                "columns",
+                SyntaxShape::String,
+                "The column whose values become the columns of the output.",
+                Some('c'),
+            )
+            .required_named(
+                "values",
+                SyntaxShape::String,
+                "The column whose values fill the cells of the output.",
+                Some('v'),
+            )
+            .named(
+                "agg",
+                SyntaxShape::String,
+                "How to combine the values that end up in the same cell: first (the default), last, sum, product, min, max, avg, count or list.",
+                Some('a'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn description(&self) -> &str {
+        "Reshape a long table into a wide one, aggregating the values that fall in the same cell."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The output has one row for each distinct value of the --index columns, and one column for
+each distinct value of the --columns column, in the order they first appear in the input. Cells
+without any value are null, or 0 and [] with `--agg count` and `--agg list`. Use `unpivot` to turn
+the output back into a long table."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["pivot table", "crosstab", "reshape", "wide", "spread"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Sum the sales of each region per month.",
+                example: "[[region month sales]; [eu jan 10] [eu feb 20] [na jan 5] [eu jan 7]] | pivot --index region --columns month --values sales --agg sum",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "region" => Value::test_string("eu"),
+                        "jan" => Value::test_int(17),
+                        "feb" => Value::test_int(20),
+                    }),
+                    Value::test_record(record! {
+                        "region" => Value::test_string("na"),
+                        "jan" => Value::test_int(5),
+                        "feb" => Value::test_nothing(),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Count the rows for each combination of several index columns.",
+                example: "[[year region product]; [2024 eu a] [2024 eu b] [2025 eu a] [2024 eu a]] | pivot --index [year region] --columns product --values product --agg count",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "year" => Value::test_int(2024),
+                        "region" => Value::test_string("eu"),
+                        "a" => Value::test_int(2),
+                        "b" => Value::test_int(1),
+                    }),
+                    Value::test_record(record! {
+                        "year" => Value::test_int(2025),
+                        "region" => Value::test_string("eu"),
+                        "a" => Value::test_int(1),
+                        "b" => Value::test_int(0),
+                    }),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let index: Value = call
+            .get_flag(engine_state, stack, "index")?
+            .ok_or_else(|| ShellError::MissingParameter {
+                param_name: "index".into(),
+                span: head,
+            })?;
+        let index = match index {
+            Value::String { val, .. } => vec![val],
+            Value::List { vals, .. } => vals
+                .into_iter()
+                .map(Value::into_string)
+                .collect::<Result<_, _>>()?,
+            other => {
+                return Err(ShellError::CantConvert {
+                    to_type: "string or list<string>".into(),
+                    from_type: other.get_type().to_string(),
+                    span: other.span(),
+                    help: None,
+                });
+            }
+        };
+        let columns: String = call
+            .get_flag(engine_state, stack, "columns")?
+            .ok_or_else(|| ShellError::MissingParameter {
+                param_name: "columns".into(),
+                span: head,
+            })?;
+        let values: String = call
+            .get_flag(engine_state, stack, "values")?
+            .ok_or_else(|| ShellError::MissingParameter {
+                param_name: "values".into(),
+                span: head,
+            })?;
+        let aggregation = match call.get_flag(engine_state, stack, "agg")? {
+            Some(name) => Aggregation::from_name(&name)?,
+            None => Aggregation::First,
+        };
+
+        let metadata = input.metadata();
+        let config = stack.get_config(engine_state);
+
+        // The values of each cell, by the index values of the row and the name of the column.
+        let mut rows: IndexMap<Vec<String>, (Vec<Value>, IndexMap<String, Vec<Value>>)> =
+            IndexMap::new();
+        let mut column_names: Vec<String> = Vec::new();
+
+        for row in input {
+            engine_state.signals().check(&head)?;
+            let span = row.span();
+            let record = match row {
+                Value::Record { val, .. } => val,
+                Value::Error { error, .. } => return Err(*error),
+                other => {
+                    return Err(ShellError::OnlySupportsThisInputType {
+                        exp_input_type: "record".into(),
+                        wrong_type: other.get_type().to_string(),
+                        dst_span: head,
+                        src_span: span,
+                    });
+                }
+            };
+
+            let get = |column: &String| {
+                record
+                    .get(column)
+                    .cloned()
+                    .ok_or_else(|| ShellError::CantFindColumn {
+                        col_name: column.clone(),
+                        span: Some(head),
+                        src_span: span,
+                    })
+            };
+            let index_values = index.iter().map(&get).collect::<Result<Vec<_>, _>>()?;
+            let column = get(&columns)?.to_abbreviated_string(&config);
+            let value = get(&values)?;
+
+            if !column_names.contains(&column) {
+                column_names.push(column.clone());
+            }
+            let key = index_values
+                .iter()
+                .map(|value| value.to_abbreviated_string(&config))
+                .collect();
+            rows.entry(key)
+                .or_insert_with(|| (index_values, IndexMap::new()))
+                .1
+                .entry(column)
+                .or_default()
+                .push(value);
+        }
+
+        let table = rows
+            .into_values()
+            .map(|(index_values, mut cells)| {
+                let mut record: Record = index.iter().cloned().zip(index_values).collect();
+                for column in &column_names {
+                    let cell = cells.swap_remove(column).unwrap_or_default();
+                    record.push(column.clone(), aggregation.apply(cell, head)?);
+                }
+                Ok(Value::record(record, head))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        Ok(Value::list(table, head).into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Aggregation {
+    First,
+    Last,
+    Sum,
+    Product,
+    Min,
+    Max,
+    Avg,
+    Count,
+    List,
+}
+
+impl Aggregation {
+    fn from_name(name: &Spanned<String>) -> Result<Self, ShellError> {
+        match name.item.as_str() {
+            "first" => Ok(Self::First),
+            "last" => Ok(Self::Last),
+            "sum" => Ok(Self::Sum),
+            "product" => Ok(Self::Product),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "avg" | "mean" => Ok(Self::Avg),
+            "count" => Ok(Self::Count),
+            "list" => Ok(Self::List),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "Expected one of first, last, sum, product, min, max, avg, count or list"
+                    .into(),
+                val_span: name.span,
+                call_span: name.span,
+            }),
+        }
+    }
+
+    fn apply(self, values: Vec<Value>, head: Span) -> Result<Value, ShellError> {
+        let reduce = match self {
+            Self::First => {
+                return Ok(values
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| Value::nothing(head)));
+            }
+            Self::Last => {
+                return Ok(values
+                    .into_iter()
+                    .last()
+                    .unwrap_or_else(|| Value::nothing(head)));
+            }
+            Self::Count => return Ok(Value::int(values.len() as i64, head)),
+            Self::List => return Ok(Value::list(values, head)),
+            _ if values.is_empty() => return Ok(Value::nothing(head)),
+            Self::Sum | Self::Avg => Reduce::Summation,
+            Self::Product => Reduce::Product,
+            Self::Min => Reduce::Minimum,
+            Self::Max => Reduce::Maximum,
+        };
+        let count = values.len() as i64;
+        let result = reducer_for(reduce)(Value::nothing(head), values, head, head)?;
+        match self {
+            Self::Avg => result.div(head, &Value::int(count, head), head),
+            _ => Ok(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Pivot {})
+    }
+}
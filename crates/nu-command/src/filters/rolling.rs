@@ -0,0 +1,414 @@
+use crate::math::reducers::{Reduce, reducer_for};
+use nu_engine::command_prelude::*;
+use nu_protocol::ast::CellPath;
+use std::{cmp::Ordering, collections::VecDeque};
+
+#[derive(Clone)]
+pub struct Rolling;
+
+impl Command for Rolling {
+    fn name(&self) -> &str {
+        "rolling"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![
+                (Type::table(), Type::table()),
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::Any)),
+                ),
+            ])
+            .required(
+                "operation",
+                SyntaxShape::String,
+                "The computation to run: sum, avg, min, max, lag, lead or rank.",
+            )
+            .optional(
+                "column",
+                SyntaxShape::CellPath,
+                "The column of the table to compute on. Without it, the computation runs on the input list itself.",
+            )
+            .named(
+                "size",
+                SyntaxShape::Int,
+                "The number of rows of the moving window of sum, avg, min and max. Without it, they are cumulative.",
+                Some('s'),
+            )
+            .switch(
+                "partial",
+                "Compute sum, avg, min and max over the first rows too, before the window is full, instead of returning null.",
+                Some('p'),
+            )
+            .named(
+                "offset",
+                SyntaxShape::Int,
+                "How many rows back (lag) or ahead (lead) to take the value from (default: 1).",
+                Some('n'),
+            )
+            .named(
+                "into",
+                SyntaxShape::String,
+                "The name of the column to put the results in (default: the column name followed by an underscore and the operation).",
+                Some('i'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn description(&self) -> &str {
+        "Compute moving or cumulative aggregates, lagged and leading values, or ranks along a list or a table column."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"For a table, the results are added as a new column to each row. For a list, the results
+replace the values. The rows are processed in the order they come in, so sort the table first,
+by time for example, to compute on a time series.
+
+sum, avg, min, max and lag stream their input. lead and rank need to collect it first. The rank of
+a value is one more than the number of values that are smaller than it, so equal values have the
+same rank."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "moving average",
+            "cumulative",
+            "running total",
+            "cumsum",
+            "shift",
+            "time series",
+            "smooth",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Compute the moving average of the last 2 values.",
+                example: "[1 3 5 7] | rolling avg --size 2",
+                result: Some(Value::test_list(vec![
+                    Value::test_nothing(),
+                    Value::test_float(2.0),
+                    Value::test_float(4.0),
+                    Value::test_float(6.0),
+                ])),
+            },
+            Example {
+                description: "Compute the running total of a column.",
+                example: "[[day sales]; [1 10] [2 20] [3 5]] | rolling sum sales --into total",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "day" => Value::test_int(1),
+                        "sales" => Value::test_int(10),
+                        "total" => Value::test_int(10),
+                    }),
+                    Value::test_record(record! {
+                        "day" => Value::test_int(2),
+                        "sales" => Value::test_int(20),
+                        "total" => Value::test_int(30),
+                    }),
+                    Value::test_record(record! {
+                        "day" => Value::test_int(3),
+                        "sales" => Value::test_int(5),
+                        "total" => Value::test_int(35),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Get the previous value of a column.",
+                example: "[[day sales]; [1 10] [2 20]] | rolling lag sales",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "day" => Value::test_int(1),
+                        "sales" => Value::test_int(10),
+                        "sales_lag" => Value::test_nothing(),
+                    }),
+                    Value::test_record(record! {
+                        "day" => Value::test_int(2),
+                        "sales" => Value::test_int(20),
+                        "sales_lag" => Value::test_int(10),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Rank values, with equal values sharing their rank.",
+                example: "[30 10 20 10] | rolling rank",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(4),
+                    Value::test_int(1),
+                    Value::test_int(3),
+                    Value::test_int(1),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let operation: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let operation = Operation::from_name(&operation)?;
+        let column: Option<CellPath> = call.opt(engine_state, stack, 1)?;
+        let size: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "size")?;
+        let partial = call.has_flag(engine_state, stack, "partial")?;
+        let offset: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "offset")?;
+        let into: Option<String> = call.get_flag(engine_state, stack, "into")?;
+
+        let size = size.map(positive).transpose()?;
+        let offset = offset.map(positive).transpose()?.unwrap_or(1);
+        let output = match (&column, into) {
+            (_, Some(into)) => Some(into),
+            (Some(column), None) => {
+                Some(format!("{}_{}", column.to_column_name(), operation.name()))
+            }
+            (None, None) => None,
+        };
+
+        let metadata = input.metadata();
+        let rows = input.into_iter();
+        let results: Box<dyn Iterator<Item = Value> + Send> = match operation {
+            Operation::Lead | Operation::Rank => {
+                let rows = rows.collect::<Vec<_>>();
+                let values = rows
+                    .iter()
+                    .map(|row| argument(row, column.as_ref(), head))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let results = if let Operation::Lead = operation {
+                    lead(values, offset, head)
+                } else {
+                    rank(&values, head)
+                };
+                Box::new(
+                    rows.into_iter()
+                        .zip(results)
+                        .map(move |(row, result)| attach(row, result, output.as_deref(), head)),
+                )
+            }
+            _ => {
+                let mut window = Window::new(operation, size, partial, offset);
+                Box::new(rows.map(move |row| {
+                    let result = argument(&row, column.as_ref(), head)
+                        .and_then(|value| window.push(value, head));
+                    match result {
+                        Ok(result) => attach(row, result, output.as_deref(), head),
+                        Err(error) => Value::error(error, head),
+                    }
+                }))
+            }
+        };
+
+        Ok(PipelineData::list_stream(
+            ListStream::new(results, head, engine_state.signals().clone()),
+            metadata,
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Lag,
+    Lead,
+    Rank,
+}
+
+impl Operation {
+    fn from_name(name: &Spanned<String>) -> Result<Self, ShellError> {
+        match name.item.as_str() {
+            "sum" => Ok(Self::Sum),
+            "avg" => Ok(Self::Avg),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "lag" => Ok(Self::Lag),
+            "lead" => Ok(Self::Lead),
+            "rank" => Ok(Self::Rank),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "Expected one of sum, avg, min, max, lag, lead or rank".into(),
+                val_span: name.span,
+                call_span: name.span,
+            }),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Lag => "lag",
+            Self::Lead => "lead",
+            Self::Rank => "rank",
+        }
+    }
+}
+
+fn positive(value: Spanned<i64>) -> Result<usize, ShellError> {
+    match usize::try_from(value.item) {
+        Ok(value) if value > 0 => Ok(value),
+        _ => Err(ShellError::IncorrectValue {
+            msg: "Expected a positive number".into(),
+            val_span: value.span,
+            call_span: value.span,
+        }),
+    }
+}
+
+// The value of `row` to compute on.
+fn argument(row: &Value, column: Option<&CellPath>, head: Span) -> Result<Value, ShellError> {
+    match (row, column) {
+        (Value::Error { error, .. }, _) => Err(*error.clone()),
+        (row, Some(column)) => row
+            .follow_cell_path(&column.members)
+            .map(|value| value.into_owned()),
+        (row, None) => Ok(row.clone().with_span(head)),
+    }
+}
+
+// Add `result` to `row` as the `output` column, or replace `row` with it.
+fn attach(row: Value, result: Value, output: Option<&str>, head: Span) -> Value {
+    let Some(output) = output else {
+        return result;
+    };
+    let span = row.span();
+    match row {
+        Value::Record { mut val, .. } => {
+            val.to_mut().insert(output, result);
+            Value::record(val.into_owned(), span)
+        }
+        Value::Error { .. } => row,
+        other => Value::error(
+            ShellError::OnlySupportsThisInputType {
+                exp_input_type: "record".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: span,
+            },
+            head,
+        ),
+    }
+}
+
+// The state of the streaming operations.
+struct Window {
+    operation: Operation,
+    // `None` for a cumulative aggregate
+    size: Option<usize>,
+    partial: bool,
+    offset: usize,
+    values: VecDeque<Value>,
+    // The running aggregate and count of a cumulative aggregate
+    acc: Option<Value>,
+    count: i64,
+}
+
+impl Window {
+    fn new(operation: Operation, size: Option<usize>, partial: bool, offset: usize) -> Self {
+        Self {
+            operation,
+            size,
+            partial,
+            offset,
+            values: VecDeque::new(),
+            acc: None,
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, value: Value, head: Span) -> Result<Value, ShellError> {
+        let reduce = match self.operation {
+            Operation::Lag => {
+                self.values.push_back(value);
+                if self.values.len() <= self.offset {
+                    return Ok(Value::nothing(head));
+                }
+                return Ok(self
+                    .values
+                    .pop_front()
+                    .unwrap_or_else(|| Value::nothing(head)));
+            }
+            Operation::Sum | Operation::Avg => Reduce::Summation,
+            Operation::Min => Reduce::Minimum,
+            Operation::Max => Reduce::Maximum,
+            Operation::Lead | Operation::Rank => return Ok(Value::nothing(head)),
+        };
+
+        let (result, count) = match self.size {
+            Some(size) => {
+                self.values.push_back(value);
+                if self.values.len() > size {
+                    self.values.pop_front();
+                }
+                if self.values.len() < size && !self.partial {
+                    return Ok(Value::nothing(head));
+                }
+                let values = self.values.iter().cloned().collect();
+                let result = reducer_for(reduce)(Value::nothing(head), values, head, head)?;
+                (result, self.values.len() as i64)
+            }
+            None => {
+                let values = match self.acc.take() {
+                    Some(acc) => vec![acc, value],
+                    None => vec![value],
+                };
+                let acc = reducer_for(reduce)(Value::nothing(head), values, head, head)?;
+                self.acc = Some(acc.clone());
+                self.count += 1;
+                (acc, self.count)
+            }
+        };
+
+        match self.operation {
+            Operation::Avg => result.div(head, &Value::int(count, head), head),
+            _ => Ok(result),
+        }
+    }
+}
+
+fn lead(values: Vec<Value>, offset: usize, head: Span) -> Vec<Value> {
+    let len = values.len();
+    values
+        .into_iter()
+        .skip(offset)
+        .chain(std::iter::repeat_with(|| Value::nothing(head)))
+        .take(len)
+        .collect()
+}
+
+fn rank(values: &[Value], head: Span) -> Vec<Value> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+
+    let mut ranks = vec![Value::nothing(head); values.len()];
+    let mut previous: Option<(usize, i64)> = None;
+    for (position, idx) in order.into_iter().enumerate() {
+        let rank = match previous {
+            Some((previous_idx, rank)) if values[previous_idx] == values[idx] => rank,
+            _ => position as i64 + 1,
+        };
+        ranks[idx] = Value::int(rank, head);
+        previous = Some((idx, rank));
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Rolling {})
+    }
+}
@@ -1,8 +1,16 @@
 use itertools::Itertools;
 use nu_engine::command_prelude::*;
-use nu_protocol::PipelineMetadata;
+use nu_protocol::{ListStream, PipelineMetadata};
 use nu_utils::IgnoreCaseExt;
-use std::collections::{HashMap, hash_map::IntoIter};
+use std::{
+    collections::{HashMap, HashSet, hash_map::DefaultHasher, hash_map::IntoIter},
+    hash::{Hash, Hasher},
+};
+
+/// The number of bits in the bloom filter of `--approximate`, which is 16 MiB.
+const BLOOM_FILTER_BITS: u64 = 1 << 27;
+/// The number of bits that each value sets in the bloom filter.
+const BLOOM_FILTER_HASHES: u64 = 7;
 
 #[derive(Clone)]
 pub struct Uniq;
@@ -38,6 +46,11 @@ impl Command for Uniq {
                 "Return the input values that occur once only.",
                 Some('u'),
             )
+            .switch(
+                "approximate",
+                "Remember the values that have been seen in a fixed amount of memory, at the cost of rarely dropping a distinct value.",
+                None,
+            )
             .category(Category::Filters)
     }
 
@@ -45,6 +58,10 @@ impl Command for Uniq {
         "Return the distinct values in the input."
     }
 
+    fn extra_description(&self) -> &str {
+        UNIQ_EXTRA_DESCRIPTION
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["distinct", "deduplicate", "count"]
     }
@@ -65,7 +82,7 @@ impl Command for Uniq {
             engine_state,
             stack,
             call,
-            input.into_iter().collect(),
+            input.into_iter(),
             mapper,
             metadata,
         )
@@ -99,6 +116,11 @@ impl Command for Uniq {
                     Value::test_string("goodbye"),
                 ])),
             },
+            Example {
+                description: "Deduplicate the lines of a huge log file in a fixed amount of memory.",
+                example: "open --raw huge.log | lines | uniq --approximate | save deduplicated.log",
+                result: None,
+            },
             Example {
                 description: "Return a table containing the distinct input values together with their counts.",
                 example: "[1 2 2] | uniq --count",
@@ -117,6 +139,12 @@ impl Command for Uniq {
     }
 }
 
+pub(crate) const UNIQ_EXTRA_DESCRIPTION: &str = "Unless --count, --repeated, --unique or --keep-last \
+     are used, the distinct values are streamed as soon as they are seen, and only a key for each \
+     of them is kept in memory. With --approximate, a bloom filter of 16 MiB is used instead of the \
+     keys, which drops about 1 in 100 distinct values as duplicates after 14 million distinct \
+     values, and fewer before.";
+
 pub struct ItemMapperState {
     pub item: Value,
     pub flag_ignore_case: bool,
@@ -234,8 +262,8 @@ pub fn uniq(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    input: Vec<Value>,
-    item_mapper: Box<dyn Fn(ItemMapperState) -> ValueCounter>,
+    input: impl Iterator<Item = Value> + Send + 'static,
+    item_mapper: Box<dyn Fn(ItemMapperState) -> ValueCounter + Send>,
     metadata: Option<PipelineMetadata>,
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
@@ -243,11 +271,45 @@ pub fn uniq(
     let flag_show_repeated = call.has_flag(engine_state, stack, "repeated")?;
     let flag_ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
     let flag_only_uniques = call.has_flag(engine_state, stack, "unique")?;
+    let flag_approximate = call.has_flag(engine_state, stack, "approximate")?;
 
     // for uniq-by command
     let flag_keep_last = call.has_flag(engine_state, stack, "keep-last")?;
 
     let signals = engine_state.signals().clone();
+
+    // the first occurrences can be passed on right away, everything else needs the whole input
+    if !(flag_show_count || flag_show_repeated || flag_only_uniques || flag_keep_last) {
+        let engine_state = engine_state.clone();
+        let mut seen = if flag_approximate {
+            SeenKeys::Approximate(vec![0; (BLOOM_FILTER_BITS / 64) as usize])
+        } else {
+            SeenKeys::Exact(HashSet::new())
+        };
+        let iter = input.enumerate().filter_map(move |(index, item)| {
+            let item = item_mapper(ItemMapperState {
+                item,
+                flag_ignore_case,
+                index,
+            });
+            match generate_key(&engine_state, &item) {
+                Ok(key) => seen.insert(key).then_some(item.val),
+                Err(err) => Some(Value::error(err, head)),
+            }
+        });
+        return Ok(PipelineData::list_stream(
+            ListStream::new(iter, head, signals),
+            metadata,
+        ));
+    }
+
+    if flag_approximate {
+        return Err(ShellError::IncompatibleParametersSingle {
+            msg: "--approximate can't be used with --count, --repeated, --unique or --keep-last"
+                .into(),
+            span: call.get_flag_span(stack, "approximate").unwrap_or(head),
+        });
+    }
     let uniq_values = input
         .into_iter()
         .enumerate()
@@ -307,6 +369,42 @@ pub fn uniq(
     Ok(Value::list(result, head).into_pipeline_data_with_metadata(metadata))
 }
 
+/// The keys of the values that have already been passed on.
+enum SeenKeys {
+    Exact(HashSet<String>),
+    /// A bloom filter, which can mistake a new key for one that has been seen.
+    Approximate(Vec<u64>),
+}
+
+impl SeenKeys {
+    /// Remembers `key`, and returns whether it is new.
+    fn insert(&mut self, key: String) -> bool {
+        match self {
+            SeenKeys::Exact(keys) => keys.insert(key),
+            SeenKeys::Approximate(bits) => {
+                // the positions are derived from two hashes, as described by Kirsch and Mitzenmacher
+                let hash = |seed: u8| {
+                    let mut hasher = DefaultHasher::new();
+                    seed.hash(&mut hasher);
+                    key.hash(&mut hasher);
+                    hasher.finish()
+                };
+                let (first, second) = (hash(0), hash(1));
+                let mut new = false;
+                for i in 0..BLOOM_FILTER_HASHES {
+                    let bit = first.wrapping_add(i.wrapping_mul(second)) % BLOOM_FILTER_BITS;
+                    let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+                    if bits[word] & mask == 0 {
+                        bits[word] |= mask;
+                        new = true;
+                    }
+                }
+                new
+            }
+        }
+    }
+}
+
 fn sort(iter: IntoIter<String, ValueCounter>) -> Vec<ValueCounter> {
     iter.map(|item| item.1)
         .sorted_by(|a, b| a.index.cmp(&b.index))
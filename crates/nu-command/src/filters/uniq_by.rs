@@ -44,6 +44,11 @@ impl Command for UniqBy {
                 "Return the input values that occur once only.",
                 Some('u'),
             )
+            .switch(
+                "approximate",
+                "Remember the values that have been seen in a fixed amount of memory, at the cost of rarely dropping a distinct value.",
+                None,
+            )
             .allow_variants_without_examples(true)
             .category(Category::Filters)
     }
@@ -52,6 +57,10 @@ impl Command for UniqBy {
         "Return the distinct values in the input by the given column(s)."
     }
 
+    fn extra_description(&self) -> &str {
+        crate::UNIQ_EXTRA_DESCRIPTION
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["distinct", "deduplicate"]
     }
@@ -74,17 +83,12 @@ impl Command for UniqBy {
 
         let metadata = input.metadata();
 
-        let vec: Vec<_> = input.into_iter().collect();
-        match validate(&vec, &columns, call.head) {
-            Ok(_) => {}
-            Err(err) => {
-                return Err(err);
-            }
-        }
+        let mut iter = input.into_iter().peekable();
+        validate(iter.peek(), &columns, call.head)?;
 
         let mapper = Box::new(item_mapper_by_col(columns));
 
-        uniq(engine_state, stack, call, vec, mapper, metadata)
+        uniq(engine_state, stack, call, iter, mapper, metadata)
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -129,8 +133,7 @@ impl Command for UniqBy {
     }
 }
 
-fn validate(vec: &[Value], columns: &[String], span: Span) -> Result<(), ShellError> {
-    let first = vec.first();
+fn validate(first: Option<&Value>, columns: &[String], span: Span) -> Result<(), ShellError> {
     if let Some(v) = first {
         let val_span = v.span();
         if let Value::Record { val: record, .. } = &v {
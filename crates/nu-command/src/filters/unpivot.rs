@@ -0,0 +1,219 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct Unpivot;
+
+impl Command for Unpivot {
+    fn name(&self) -> &str {
+        "unpivot"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::table(), Type::table())])
+            .named(
+                "index",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "The columns to keep in every output row. Defaults to the columns not given to --on.",
+                Some('i'),
+            )
+            .named(
+                "on",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "The columns to turn into rows. Defaults to the columns not given to --index.",
+                Some('o'),
+            )
+            .named(
+                "variable-name",
+                SyntaxShape::String,
+                "The name of the output column holding the names of the unpivoted columns (default: variable).",
+                Some('r'),
+            )
+            .named(
+                "value-name",
+                SyntaxShape::String,
+                "The name of the output column holding the values of the unpivoted columns (default: value).",
+                Some('l'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn description(&self) -> &str {
+        "Reshape a wide table into a long one, with a row for each of the unpivoted columns of every input row."
+    }
+
+    fn extra_description(&self) -> &str {
+        "This is the inverse of `pivot`. Rows that don't have one of the --index or --on columns get \
+         null for it. The input is streamed."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["melt", "reshape", "long", "gather", "pivot"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Turn a column for each month into a row for each month.",
+                example: "[[region jan feb]; [eu 17 20] [na 5 null]] | unpivot --index [region] --variable-name month --value-name sales",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "region" => Value::test_string("eu"),
+                        "month" => Value::test_string("jan"),
+                        "sales" => Value::test_int(17),
+                    }),
+                    Value::test_record(record! {
+                        "region" => Value::test_string("eu"),
+                        "month" => Value::test_string("feb"),
+                        "sales" => Value::test_int(20),
+                    }),
+                    Value::test_record(record! {
+                        "region" => Value::test_string("na"),
+                        "month" => Value::test_string("jan"),
+                        "sales" => Value::test_int(5),
+                    }),
+                    Value::test_record(record! {
+                        "region" => Value::test_string("na"),
+                        "month" => Value::test_string("feb"),
+                        "sales" => Value::test_nothing(),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Unpivot only some of the columns, keeping all the others.",
+                example: "[[id a b]; [1 10 20]] | unpivot --on [b]",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "id" => Value::test_int(1),
+                    "a" => Value::test_int(10),
+                    "variable" => Value::test_string("b"),
+                    "value" => Value::test_int(20),
+                })])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let index: Option<Vec<String>> = call.get_flag(engine_state, stack, "index")?;
+        let on: Option<Vec<String>> = call.get_flag(engine_state, stack, "on")?;
+        let variable_name: String = call
+            .get_flag(engine_state, stack, "variable-name")?
+            .unwrap_or_else(|| "variable".into());
+        let value_name: String = call
+            .get_flag(engine_state, stack, "value-name")?
+            .unwrap_or_else(|| "value".into());
+
+        if variable_name == value_name {
+            return Err(ShellError::IncompatibleParametersSingle {
+                msg: "The variable and value columns need different names".into(),
+                span: head,
+            });
+        }
+        if let Some(column) = index
+            .iter()
+            .flatten()
+            .find(|column| **column == variable_name || **column == value_name)
+        {
+            return Err(ShellError::IncompatibleParametersSingle {
+                msg: format!("The index column `{column}` has the name of an output column"),
+                span: head,
+            });
+        }
+
+        let metadata = input.metadata();
+        let rows = input.into_iter().flat_map(move |row| {
+            let span = row.span();
+            let record = match row {
+                Value::Record { val, .. } => val.into_owned(),
+                Value::Error { .. } => return vec![row],
+                other => {
+                    return vec![Value::error(
+                        ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "record".into(),
+                            wrong_type: other.get_type().to_string(),
+                            dst_span: head,
+                            src_span: span,
+                        },
+                        head,
+                    )];
+                }
+            };
+
+            let (kept, unpivoted): (Record, Vec<(String, Value)>) = match (&index, &on) {
+                (Some(index), Some(on)) => (
+                    select(&record, index, span),
+                    on.iter()
+                        .map(|column| (column.clone(), get(&record, column, span)))
+                        .collect(),
+                ),
+                (Some(index), None) => {
+                    let (kept, unpivoted) = record
+                        .into_iter()
+                        .partition::<Vec<_>, _>(|(column, _)| index.contains(column));
+                    // keep the order of --index, and null for its missing columns
+                    let kept = kept.into_iter().collect::<Record>();
+                    (select(&kept, index, span), unpivoted)
+                }
+                (None, Some(on)) => {
+                    let unpivoted = on
+                        .iter()
+                        .map(|column| (column.clone(), get(&record, column, span)))
+                        .collect();
+                    let kept = record
+                        .into_iter()
+                        .filter(|(column, _)| !on.contains(column))
+                        .collect();
+                    (kept, unpivoted)
+                }
+                (None, None) => (Record::new(), record.into_iter().collect()),
+            };
+
+            unpivoted
+                .into_iter()
+                .map(|(column, value)| {
+                    let mut row = kept.clone();
+                    row.insert(variable_name.clone(), Value::string(column, head));
+                    row.insert(value_name.clone(), value);
+                    Value::record(row, span)
+                })
+                .collect()
+        });
+
+        Ok(PipelineData::list_stream(
+            ListStream::new(rows, head, engine_state.signals().clone()),
+            metadata,
+        ))
+    }
+}
+
+fn get(record: &Record, column: &str, span: Span) -> Value {
+    record
+        .get(column)
+        .cloned()
+        .unwrap_or_else(|| Value::nothing(span))
+}
+
+fn select(record: &Record, columns: &[String], span: Span) -> Record {
+    columns
+        .iter()
+        .map(|column| (column.clone(), get(record, column, span)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Unpivot {})
+    }
+}
@@ -33,6 +33,7 @@ fn to_string_tagged_value(
         | Value::Binary { .. }
         | Value::Custom { .. }
         | Value::Filesize { .. }
+        | Value::Rate { .. }
         | Value::CellPath { .. }
         | Value::Float { .. } => Ok(v.clone().to_abbreviated_string(config)),
         Value::Date { val, .. } => Ok(val.to_string()),
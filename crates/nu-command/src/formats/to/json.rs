@@ -122,6 +122,7 @@ pub fn value_to_json_value(
         Value::Bool { val, .. } => nu_json::Value::Bool(*val),
         Value::Filesize { val, .. } => nu_json::Value::I64(val.get()),
         Value::Duration { val, .. } => nu_json::Value::I64(*val),
+        Value::Rate { val, .. } => nu_json::Value::I64(val.get()),
         Value::Date { val, .. } => nu_json::Value::String(val.to_string()),
         Value::Float { val, .. } => nu_json::Value::F64(*val),
         Value::Int { val, .. } => nu_json::Value::I64(*val),
@@ -190,6 +190,9 @@ pub(crate) fn write_value(
         Value::Duration { val, .. } => {
             mp::write_sint(out, *val).err_span(span)?;
         }
+        Value::Rate { val, .. } => {
+            mp::write_sint(out, val.get()).err_span(span)?;
+        }
         Value::Date { val, .. } => {
             if val.timestamp_subsec_nanos() == 0
                 && val.timestamp() >= 0
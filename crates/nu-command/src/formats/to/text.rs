@@ -168,6 +168,7 @@ fn local_into_string(
         Value::Float { val, .. } => ObviousFloat(val).to_string(),
         Value::Filesize { val, .. } => val.to_string(),
         Value::Duration { val, .. } => format_duration(val),
+        Value::Rate { val, .. } => val.to_string(),
         Value::Date { val, .. } => {
             format!(
                 "{} ({})",
@@ -105,6 +105,7 @@ pub fn value_to_yaml_value(
             serde_yaml::Value::Number(serde_yaml::Number::from(val.get()))
         }
         Value::Duration { val, .. } => serde_yaml::Value::String(val.to_string()),
+        Value::Rate { val, .. } => serde_yaml::Value::Number(serde_yaml::Number::from(val.get())),
         Value::Date { val, .. } => serde_yaml::Value::String(val.to_string()),
         Value::Range { .. } => serde_yaml::Value::Null,
         Value::Float { val, .. } => serde_yaml::Value::Number(serde_yaml::Number::from(*val)),
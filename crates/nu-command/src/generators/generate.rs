@@ -37,6 +37,15 @@ containing two optional keys: 'out' and 'next'. Each invocation, the 'out'
 value, if present, is added to the stream. If a 'next' key is present, it is
 used as the next argument to the closure, otherwise generation stops.
 
+To add several values to the stream in one invocation, return them as a list
+under the 'items' key. They are added after the 'out' value, if any. Returning
+'done: true' stops generation after this invocation, even if 'next' is present.
+
+Generation is lazy: the closure is only invoked again once every value from the
+previous invocation has been consumed. Commands like `take while` or `first`
+can therefore stop an endless generator, such as a loop over the pages of an
+API, without requesting more than is needed.
+
 Additionally, if an input stream is provided, the generator closure accepts two
 arguments. On each invocation an element of the input stream is provided as the
 first argument. The second argument is the `next` value from the last invocation.
@@ -74,6 +83,32 @@ In this case, generation also stops when the input stream stops."#
                 description: "Generate a continuous stream of Fibonacci numbers, using default parameters",
                 result: None,
             },
+            Example {
+                example: "generate {|i| {items: [$i, ($i * 10)], next: ($i + 1), done: ($i == 3)} } 1",
+                description: "Add several values per invocation, and stop explicitly",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_int(10),
+                    Value::test_int(2),
+                    Value::test_int(20),
+                    Value::test_int(3),
+                    Value::test_int(30),
+                ])),
+            },
+            Example {
+                example: "generate {|i| {out: $i, next: ($i + 1)} } 0 | take while {|x| $x < 3 }",
+                description: "Stop an endless generator lazily",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(0),
+                    Value::test_int(1),
+                    Value::test_int(2),
+                ])),
+            },
+            Example {
+                example: "generate {|page| let items = http get $'https://example.com/api/items?page=($page)'; {items: $items, next: ($page + 1), done: ($items | is-empty)} } 1",
+                description: "Fetch every page of a paginated API, stopping at the first empty page",
+                result: None,
+            },
             Example {
                 example: "1..5 | generate {|e, sum=0| let sum = $e + $sum; {out: $sum, next: $sum} }",
                 description: "Generate a running sum of the inputs",
@@ -115,9 +150,8 @@ In this case, generation also stops when the input stream stops."#
                         .run_with_input(PipelineData::empty());
                     let (output, next_input) = parse_closure_result(closure_result, head);
 
-                    // We use `state` to control when to stop, not `output`. By wrapping
-                    // it in a `Some`, we allow the generator to output `None` as a valid output
-                    // value.
+                    // We use `state` to control when to stop, not `output`, so that an
+                    // invocation can output no values, or `null`, without stopping.
                     state = next_input;
                     Some(output)
                 });
@@ -185,60 +219,75 @@ fn get_initial_state(
     }
 }
 
+fn invalid_block_return(msg: String, span: Span, head: Span) -> Vec<Value> {
+    let error = ShellError::GenericError {
+        error: "Invalid block return".into(),
+        msg,
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    };
+    vec![Value::error(error, head)]
+}
+
 fn parse_closure_result(
     closure_result: Result<PipelineData, ShellError>,
     head: Span,
-) -> (Option<Value>, Option<Value>) {
+) -> (Vec<Value>, Option<Value>) {
     match closure_result {
         // no data -> output nothing and stop.
-        Ok(PipelineData::Empty) => (None, None),
+        Ok(PipelineData::Empty) => (vec![], None),
 
         Ok(PipelineData::Value(value, ..)) => {
             let span = value.span();
             match value {
-                // {out: ..., next: ...} -> output and continue
+                // {out: ..., items: [...], next: ..., done: ...} -> output and maybe continue
                 Value::Record { val, .. } => {
                     let iter = val.into_owned().into_iter();
                     let mut out = None;
+                    let mut items = vec![];
                     let mut next = None;
-                    let mut err = None;
+                    let mut done = false;
 
                     for (k, v) in iter {
                         if k.eq_ignore_ascii_case("out") {
                             out = Some(v);
+                        } else if k.eq_ignore_ascii_case("items") {
+                            match v {
+                                Value::List { vals, .. } => items = vals,
+                                v => {
+                                    let msg = format!(
+                                        "Expected list for 'items', found {}",
+                                        v.get_type()
+                                    );
+                                    return (invalid_block_return(msg, v.span(), head), None);
+                                }
+                            }
                         } else if k.eq_ignore_ascii_case("next") {
                             next = Some(v);
+                        } else if k.eq_ignore_ascii_case("done") {
+                            match v {
+                                Value::Bool { val, .. } => done = val,
+                                v => {
+                                    let msg =
+                                        format!("Expected bool for 'done', found {}", v.get_type());
+                                    return (invalid_block_return(msg, v.span(), head), None);
+                                }
+                            }
                         } else {
-                            let error = ShellError::GenericError {
-                                error: "Invalid block return".into(),
-                                msg: format!("Unexpected record key '{k}'"),
-                                span: Some(span),
-                                help: None,
-                                inner: vec![],
-                            };
-                            err = Some(Value::error(error, head));
-                            break;
+                            let msg = format!("Unexpected record key '{k}'");
+                            return (invalid_block_return(msg, span, head), None);
                         }
                     }
 
-                    if err.is_some() {
-                        (err, None)
-                    } else {
-                        (out, next)
-                    }
+                    let output = out.into_iter().chain(items).collect();
+                    (output, if done { None } else { next })
                 }
 
                 // some other value -> error and stop
                 _ => {
-                    let error = ShellError::GenericError {
-                        error: "Invalid block return".into(),
-                        msg: format!("Expected record, found {}", value.get_type()),
-                        span: Some(span),
-                        help: None,
-                        inner: vec![],
-                    };
-
-                    (Some(Value::error(error, head)), None)
+                    let msg = format!("Expected record, found {}", value.get_type());
+                    (invalid_block_return(msg, span, head), None)
                 }
             }
         }
@@ -255,11 +304,11 @@ fn parse_closure_result(
                 })
                 .unwrap_or_else(|err| err);
 
-            (Some(Value::error(error, head)), None)
+            (vec![Value::error(error, head)], None)
         }
 
         // error -> error and stop
-        Err(error) => (Some(Value::error(error, head)), None),
+        Err(error) => (vec![Value::error(error, head)], None),
     }
 }
 
@@ -269,8 +318,8 @@ mod test {
 
     #[test]
     fn test_examples() {
-        use crate::test_examples;
+        use crate::{TakeWhile, test_examples_with_commands};
 
-        test_examples(Generate {})
+        test_examples_with_commands(Generate {}, &[&TakeWhile])
     }
 }
@@ -1,10 +1,13 @@
-use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, Weekday};
 use nu_engine::command_prelude::*;
 use nu_protocol::FromValue;
 
 use std::fmt::Write;
 
 const NANOSECONDS_IN_DAY: i64 = 1_000_000_000i64 * 60i64 * 60i64 * 24i64;
+// How long --periods looks for the next date to keep before giving up, so that filters which
+// never keep a date, like `--weekdays-only --every saturday`, don't run forever.
+const MAX_DAYS_BETWEEN_PERIODS: i64 = 400;
 
 #[derive(Clone)]
 pub struct SeqDate;
@@ -59,9 +62,33 @@ impl Command for SeqDate {
                 Some('p'),
             )
             .switch("reverse", "Print dates in reverse.", Some('r'))
+            .switch(
+                "weekdays-only",
+                "Leave out Saturdays and Sundays.",
+                Some('w'),
+            )
+            .named(
+                "holidays",
+                SyntaxShape::List(Box::new(SyntaxShape::Any)),
+                "Leave out these dates, given as dates or as strings in the input format.",
+                None,
+            )
+            .named(
+                "every",
+                SyntaxShape::String,
+                "Only print the days that match this recurrence, like \"tuesday\", \"2nd tuesday\", \"last friday\", \"15th day\" or \"last day\" (of each month).",
+                None,
+            )
             .category(Category::Generators)
     }
 
+    fn extra_description(&self) -> &str {
+        r#"--weekdays-only, --holidays and --every leave dates out of the sequence, and can be
+combined. With them, --periods counts the printed dates, so `--periods 10 --weekdays-only` prints
+ten business days, while --days still counts all the days of the sequence. --every goes through the
+sequence one day at a time, so it can't be used with --increment."#
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -181,6 +208,49 @@ impl Command for SeqDate {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "print the business days of two weeks in March 2024, leaving out a holiday",
+                example: "seq date --begin-date '2024-03-01' --end-date '2024-03-12' --weekdays-only --holidays ['2024-03-05']",
+                result: Some(Value::list(
+                    vec![
+                        Value::test_string("2024-03-01"),
+                        Value::test_string("2024-03-04"),
+                        Value::test_string("2024-03-06"),
+                        Value::test_string("2024-03-07"),
+                        Value::test_string("2024-03-08"),
+                        Value::test_string("2024-03-11"),
+                        Value::test_string("2024-03-12"),
+                    ],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "print the second Tuesday of each month in the first half of 2024",
+                example: "seq date --begin-date '2024-01-01' --end-date '2024-06-30' --every '2nd tuesday'",
+                result: Some(Value::list(
+                    vec![
+                        Value::test_string("2024-01-09"),
+                        Value::test_string("2024-02-13"),
+                        Value::test_string("2024-03-12"),
+                        Value::test_string("2024-04-09"),
+                        Value::test_string("2024-05-14"),
+                        Value::test_string("2024-06-11"),
+                    ],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "print the last Friday of the next three months, starting January 2024",
+                example: "seq date --begin-date '2024-01-01' --periods 3 --every 'last friday'",
+                result: Some(Value::list(
+                    vec![
+                        Value::test_string("2024-01-26"),
+                        Value::test_string("2024-02-23"),
+                        Value::test_string("2024-03-29"),
+                    ],
+                    Span::test_data(),
+                )),
+            },
         ]
     }
 
@@ -224,6 +294,30 @@ impl Command for SeqDate {
         let days: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "days")?;
         let periods: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "periods")?;
         let reverse = call.has_flag(engine_state, stack, "reverse")?;
+        let weekdays_only = call.has_flag(engine_state, stack, "weekdays-only")?;
+        let holidays: Option<Vec<Value>> = call.get_flag(engine_state, stack, "holidays")?;
+        let every: Option<Spanned<String>> = call.get_flag(engine_state, stack, "every")?;
+
+        if let (Some(every), Some(increment)) = (&every, &increment) {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "--every goes through the days one by one".into(),
+                left_span: every.span,
+                right_message: "so it can't use another increment".into(),
+                right_span: increment.span,
+            });
+        }
+        let holiday_format = input_format
+            .as_ref()
+            .map_or("%Y-%m-%d", |format| format.item.as_str());
+        let filter = DateFilter {
+            weekdays_only,
+            holidays: holidays
+                .unwrap_or_default()
+                .iter()
+                .map(|holiday| parse_holiday(holiday, holiday_format))
+                .collect::<Result<_, _>>()?,
+            every: every.as_ref().map(Recurrence::parse).transpose()?,
+        };
 
         let out_format = match output_format {
             Some(s) => Some(Value::string(s.item, s.span)),
@@ -268,12 +362,140 @@ impl Command for SeqDate {
             day_count,
             period_count,
             rev,
+            &filter,
             call.head,
         )?
         .into_pipeline_data())
     }
 }
 
+/// The dates that are left out of the sequence.
+#[derive(Default)]
+pub struct DateFilter {
+    pub weekdays_only: bool,
+    pub holidays: Vec<NaiveDate>,
+    pub every: Option<Recurrence>,
+}
+
+impl DateFilter {
+    fn is_active(&self) -> bool {
+        self.weekdays_only || !self.holidays.is_empty() || self.every.is_some()
+    }
+
+    fn keeps(&self, date: NaiveDateTime) -> bool {
+        let date = date.date();
+        !(self.weekdays_only && matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            && !self.holidays.contains(&date)
+            && self.every.is_none_or(|every| every.matches(date))
+    }
+}
+
+/// A day that comes back every week or every month.
+#[derive(Clone, Copy)]
+pub enum Recurrence {
+    /// Every week on this day, like `tuesday`
+    Weekday(Weekday),
+    /// Every month on this weekday, like `2nd tuesday` or `last friday`
+    NthWeekday(Nth, Weekday),
+    /// Every month on this day, like `15th day` or `last day`
+    NthDay(Nth),
+}
+
+#[derive(Clone, Copy)]
+pub enum Nth {
+    Number(u32),
+    Last,
+}
+
+impl Recurrence {
+    fn parse(rule: &Spanned<String>) -> Result<Self, ShellError> {
+        let invalid = || {
+            ShellError::IncorrectValue {
+            msg: "Expected a weekday like \"tuesday\", a weekday of the month like \"2nd tuesday\" or \"last friday\", or a day of the month like \"15th day\" or \"last day\"".into(),
+            val_span: rule.span,
+            call_span: rule.span,
+        }
+        };
+        let rule = rule.item.to_lowercase();
+        let words = rule
+            .split_whitespace()
+            .skip_while(|word| *word == "every")
+            .collect::<Vec<_>>();
+        match words[..] {
+            [weekday] => weekday.parse().map(Self::Weekday).map_err(|_| invalid()),
+            [nth, "day"] => match parse_nth(nth) {
+                Some(Nth::Number(n)) if n > 31 => Err(invalid()),
+                Some(nth) => Ok(Self::NthDay(nth)),
+                None => Err(invalid()),
+            },
+            [nth, weekday] => match (parse_nth(nth), weekday.parse()) {
+                (Some(Nth::Number(n)), _) if n > 5 => Err(invalid()),
+                (Some(nth), Ok(weekday)) => Ok(Self::NthWeekday(nth, weekday)),
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+
+    fn matches(self, date: NaiveDate) -> bool {
+        let is_last_of_month = |days| {
+            date.checked_add_signed(Duration::days(days))
+                .is_none_or(|later| later.month() != date.month())
+        };
+        match self {
+            Self::Weekday(weekday) => date.weekday() == weekday,
+            Self::NthWeekday(nth, weekday) => {
+                date.weekday() == weekday
+                    && match nth {
+                        Nth::Number(n) => (date.day() - 1) / 7 + 1 == n,
+                        Nth::Last => is_last_of_month(7),
+                    }
+            }
+            Self::NthDay(Nth::Number(n)) => date.day() == n,
+            Self::NthDay(Nth::Last) => is_last_of_month(1),
+        }
+    }
+}
+
+fn parse_nth(word: &str) -> Option<Nth> {
+    let n = match word {
+        "last" => return Some(Nth::Last),
+        "first" => 1,
+        "second" => 2,
+        "third" => 3,
+        "fourth" => 4,
+        "fifth" => 5,
+        _ => ["st", "nd", "rd", "th"]
+            .iter()
+            .find_map(|suffix| word.strip_suffix(suffix))?
+            .parse()
+            .ok()?,
+    };
+    (n > 0).then_some(Nth::Number(n))
+}
+
+fn parse_holiday(holiday: &Value, format: &str) -> Result<NaiveDate, ShellError> {
+    match holiday {
+        Value::Date { val, .. } => Ok(val.date_naive()),
+        Value::String { val, .. } => parse_date_string(val, format)
+            .map(|date| date.date())
+            .map_err(|_| ShellError::CantConvert {
+                to_type: "date".into(),
+                from_type: "string".into(),
+                span: holiday.span(),
+                help: Some(format!(
+                    "the holidays have to be in the input format {format}"
+                )),
+            }),
+        other => Err(ShellError::CantConvert {
+            to_type: "date".into(),
+            from_type: other.get_type().to_string(),
+            span: other.span(),
+            help: None,
+        }),
+    }
+}
+
 #[allow(clippy::unnecessary_lazy_evaluations)]
 pub fn parse_date_string(s: &str, format: &str) -> Result<NaiveDateTime, &'static str> {
     NaiveDateTime::parse_from_str(s, format).or_else(|_| {
@@ -294,6 +516,7 @@ pub fn run_seq_dates(
     day_count: Option<Value>,
     period_count: Option<Value>,
     reverse: bool,
+    filter: &DateFilter,
     call_span: Span,
 ) -> Result<Value, ShellError> {
     let today = Local::now().naive_local();
@@ -392,8 +615,18 @@ pub fn run_seq_dates(
         periods_to_output *= -1;
     }
 
+    // With a filter, --periods counts the dates that are kept, so the sequence ends when there are
+    // enough of them instead.
+    let count_kept = filter.is_active() && periods_to_output != 0;
+
     // --days is ignored when --periods is set
-    if periods_to_output != 0 {
+    if count_kept {
+        end_date = if step_size > 0 {
+            NaiveDateTime::MAX
+        } else {
+            NaiveDateTime::MIN
+        };
+    } else if periods_to_output != 0 {
         end_date = periods_to_output
             .checked_sub(1)
             .and_then(|val| val.checked_mul(step_size.abs()))
@@ -444,21 +677,42 @@ pub fn run_seq_dates(
     }
 
     let mut ret = vec![];
+    let mut last_kept = start_date;
     loop {
-        let mut date_string = String::new();
-        match write!(date_string, "{}", next.format(&out_format)) {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(ShellError::GenericError {
-                    error: "Invalid output format".into(),
-                    msg: e.to_string(),
-                    span: Some(call_span),
-                    help: None,
-                    inner: vec![],
-                });
+        if filter.keeps(next) {
+            let mut date_string = String::new();
+            match write!(date_string, "{}", next.format(&out_format)) {
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(ShellError::GenericError {
+                        error: "Invalid output format".into(),
+                        msg: e.to_string(),
+                        span: Some(call_span),
+                        help: None,
+                        inner: vec![],
+                    });
+                }
+            }
+            ret.push(Value::string(date_string, call_span));
+            last_kept = next;
+            if count_kept && ret.len() as i64 == periods_to_output.abs() {
+                break;
             }
+        } else if count_kept && (next - last_kept).num_days().abs() > MAX_DAYS_BETWEEN_PERIODS {
+            return Err(ShellError::GenericError {
+                error: "no more dates to print".into(),
+                msg: format!(
+                    "no date was kept in {MAX_DAYS_BETWEEN_PERIODS} days, after {} of the periods",
+                    ret.len()
+                ),
+                span: Some(call_span),
+                help: Some(
+                    "check that --weekdays-only, --holidays and --every can be combined".into(),
+                ),
+                inner: vec![],
+            });
         }
-        ret.push(Value::string(date_string, call_span));
+
         if let Some(n) = next.checked_add_signed(step_size) {
             next = n;
         } else {
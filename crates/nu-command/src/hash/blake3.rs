@@ -0,0 +1,75 @@
+use super::generic_digest::{GenericDigest, HashDigest, impl_digest};
+use digest::consts::U32;
+use nu_protocol::{Example, Span, Value};
+
+pub type HashBlake3 = GenericDigest<Blake3>;
+
+#[derive(Clone, Default)]
+pub struct Blake3(::blake3::Hasher);
+
+impl_digest!(Blake3, U32, |hasher| *hasher.finalize().as_bytes());
+
+impl HashDigest for Blake3 {
+    fn name() -> &'static str {
+        "blake3"
+    }
+
+    fn examples() -> Vec<Example<'static>> {
+        vec![
+            Example {
+                description: "Return the blake3 hash of a string, hex-encoded",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash blake3",
+                result: Some(Value::string(
+                    "2468eec8894acfb4e4df3a51ea916ba115d48268287754290aae8e9e6228e85f".to_owned(),
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Return the blake3 hash of a string, as binary",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash blake3 --binary",
+                result: Some(Value::binary(
+                    vec![
+                        0x24, 0x68, 0xee, 0xc8, 0x89, 0x4a, 0xcf, 0xb4, 0xe4, 0xdf, 0x3a, 0x51,
+                        0xea, 0x91, 0x6b, 0xa1, 0x15, 0xd4, 0x82, 0x68, 0x28, 0x77, 0x54, 0x29,
+                        0x0a, 0xae, 0x8e, 0x9e, 0x62, 0x28, 0xe8, 0x5f,
+                    ],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Return the blake3 hash of a file's contents",
+                example: "open ./nu_0_24_1_windows.zip | hash blake3",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_digest::{self, Arguments};
+
+    #[test]
+    fn test_examples() {
+        crate::test_examples(HashBlake3::default())
+    }
+
+    #[test]
+    fn hash_bytes() {
+        let binary = Value::binary(vec![0xC0, 0xFF, 0xEE], Span::test_data());
+        let expected = Value::string(
+            "27631689f0a30b319e816c07e0002fbd04c0ccc6a77b9b409277382c0d46ec4f".to_owned(),
+            Span::test_data(),
+        );
+        let actual = generic_digest::action::<Blake3>(
+            &binary,
+            &Arguments {
+                cell_paths: None,
+                binary: false,
+            },
+            Span::test_data(),
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,65 @@
+use super::generic_digest::{GenericDigest, HashDigest, impl_digest};
+use digest::consts::U4;
+use nu_protocol::{Example, Span, Value};
+
+pub type HashCrc32 = GenericDigest<Crc32>;
+
+#[derive(Clone, Default)]
+pub struct Crc32(crc32fast::Hasher);
+
+impl_digest!(Crc32, U4, |hasher| hasher.finalize().to_be_bytes());
+
+impl HashDigest for Crc32 {
+    fn name() -> &'static str {
+        "crc32"
+    }
+
+    fn examples() -> Vec<Example<'static>> {
+        vec![
+            Example {
+                description: "Return the crc32 checksum of a string, hex-encoded",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash crc32",
+                result: Some(Value::string("4c2750bd".to_owned(), Span::test_data())),
+            },
+            Example {
+                description: "Return the crc32 checksum of a string, as binary",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash crc32 --binary",
+                result: Some(Value::binary(
+                    vec![0x4c, 0x27, 0x50, 0xbd],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Return the crc32 checksum of a file's contents",
+                example: "open ./nu_0_24_1_windows.zip | hash crc32",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_digest::{self, Arguments};
+
+    #[test]
+    fn test_examples() {
+        crate::test_examples(HashCrc32::default())
+    }
+
+    #[test]
+    fn hash_bytes() {
+        let binary = Value::binary(vec![0xC0, 0xFF, 0xEE], Span::test_data());
+        let expected = Value::string("ba787d5f".to_owned(), Span::test_data());
+        let actual = generic_digest::action::<Crc32>(
+            &binary,
+            &Arguments {
+                cell_paths: None,
+                binary: false,
+            },
+            Span::test_data(),
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -7,6 +7,44 @@ pub trait HashDigest: digest::Digest + Clone {
     fn examples() -> Vec<Example<'static>>;
 }
 
+/// Implements [`digest::Digest`] and [`Write`] for a newtype around a hasher that doesn't come
+/// with them, given the size of its output and how to turn the finished hasher into its bytes.
+macro_rules! impl_digest {
+    ($name:ident, $size:ty, |$hasher:ident| $finalize:expr) => {
+        impl digest::HashMarker for $name {}
+
+        impl digest::OutputSizeUser for $name {
+            type OutputSize = $size;
+        }
+
+        impl digest::Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                self.0.update(data);
+            }
+        }
+
+        impl digest::FixedOutput for $name {
+            fn finalize_into(self, out: &mut digest::Output<Self>) {
+                let $hasher = self.0;
+                out.copy_from_slice(&$finalize);
+            }
+        }
+
+        impl std::io::Write for $name {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(super) use impl_digest;
+
 #[derive(Clone)]
 pub struct GenericDigest<D: HashDigest> {
     name: String,
@@ -1,8 +1,14 @@
+mod blake3;
+mod crc32;
 mod generic_digest;
 mod hash_;
 mod md5;
 mod sha256;
+mod xxhash;
 
+pub use self::blake3::HashBlake3;
+pub use self::crc32::HashCrc32;
 pub use self::hash_::Hash;
 pub use self::md5::HashMd5;
 pub use self::sha256::HashSha256;
+pub use self::xxhash::{HashXxh3, HashXxh64};
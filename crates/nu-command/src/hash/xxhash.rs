@@ -0,0 +1,102 @@
+use super::generic_digest::{GenericDigest, HashDigest, impl_digest};
+use digest::consts::U8;
+use nu_protocol::{Example, Span, Value};
+
+pub type HashXxh64 = GenericDigest<Xxh64>;
+pub type HashXxh3 = GenericDigest<Xxh3>;
+
+#[derive(Clone)]
+pub struct Xxh64(xxhash_rust::xxh64::Xxh64);
+
+impl Default for Xxh64 {
+    fn default() -> Self {
+        Self(xxhash_rust::xxh64::Xxh64::new(0))
+    }
+}
+
+impl_digest!(Xxh64, U8, |hasher| hasher.digest().to_be_bytes());
+
+impl HashDigest for Xxh64 {
+    fn name() -> &'static str {
+        "xxh64"
+    }
+
+    fn examples() -> Vec<Example<'static>> {
+        vec![
+            Example {
+                description: "Return the 64-bit xxHash of a string, hex-encoded",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash xxh64",
+                result: Some(Value::string(
+                    "cfe1f278fa89835c".to_owned(),
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Return the 64-bit xxHash of a string, as binary",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash xxh64 --binary",
+                result: Some(Value::binary(
+                    vec![0xcf, 0xe1, 0xf2, 0x78, 0xfa, 0x89, 0x83, 0x5c],
+                    Span::test_data(),
+                )),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Xxh3(xxhash_rust::xxh3::Xxh3);
+
+impl_digest!(Xxh3, U8, |hasher| hasher.digest().to_be_bytes());
+
+impl HashDigest for Xxh3 {
+    fn name() -> &'static str {
+        "xxh3"
+    }
+
+    fn examples() -> Vec<Example<'static>> {
+        vec![
+            Example {
+                description: "Return the 64-bit XXH3 hash of a string, hex-encoded",
+                example: "'abcdefghijklmnopqrstuvwxyz' | hash xxh3",
+                result: Some(Value::string(
+                    "810f9ca067fbb90c".to_owned(),
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Return the 64-bit XXH3 hash of a file's contents",
+                example: "open ./nu_0_24_1_windows.zip | hash xxh3",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_digest::{self, Arguments};
+
+    #[test]
+    fn test_examples() {
+        crate::test_examples(HashXxh64::default());
+        crate::test_examples(HashXxh3::default());
+    }
+
+    #[test]
+    fn hash_bytes() {
+        let binary = Value::binary(vec![0xC0, 0xFF, 0xEE], Span::test_data());
+        let args = Arguments {
+            cell_paths: None,
+            binary: false,
+        };
+        assert_eq!(
+            generic_digest::action::<Xxh64>(&binary, &args, Span::test_data()),
+            Value::string("1c560b7d33796b50".to_owned(), Span::test_data())
+        );
+        assert_eq!(
+            generic_digest::action::<Xxh3>(&binary, &args, Span::test_data()),
+            Value::string("83fb42f209b539ed".to_owned(), Span::test_data())
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::math::{
-    reducers::{Reduce, reducer_for},
-    utils::run_with_function,
+    reducers::Reduce,
+    utils::{Accumulator, Fold, run_with_accumulator},
 };
 use nu_engine::command_prelude::*;
 
@@ -49,7 +49,7 @@ impl Command for MathAvg {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, average)
+        run_with_accumulator(call, input, Average::default)
     }
 
     fn run_const(
@@ -58,7 +58,7 @@ impl Command for MathAvg {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, average)
+        run_with_accumulator(call, input, Average::default)
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -86,13 +86,43 @@ impl Command for MathAvg {
 }
 
 pub fn average(values: &[Value], span: Span, head: Span) -> Result<Value, ShellError> {
-    let sum = reducer_for(Reduce::Summation);
-    let total = &sum(Value::int(0, head), values.to_vec(), span, head)?;
-    let span = total.span();
-    match total {
-        Value::Filesize { val, .. } => Ok(Value::filesize(val.get() / values.len() as i64, span)),
-        Value::Duration { val, .. } => Ok(Value::duration(val / values.len() as i64, span)),
-        _ => total.div(head, &Value::int(values.len() as i64, head), head),
+    let mut average = Average::default();
+    for value in values {
+        average.push(value, head)?;
+    }
+    average.finish(span, head)
+}
+
+/// The running sum and count of the values.
+pub struct Average {
+    sum: Fold,
+    count: i64,
+}
+
+impl Default for Average {
+    fn default() -> Self {
+        Self {
+            sum: Fold::new(Reduce::Summation),
+            count: 0,
+        }
+    }
+}
+
+impl Accumulator for Average {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        self.sum.push(value, head)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self, span: Span, head: Span) -> Result<Value, ShellError> {
+        let total = &self.sum.finish(span, head)?;
+        let span = total.span();
+        match total {
+            Value::Filesize { val, .. } => Ok(Value::filesize(val.get() / self.count, span)),
+            Value::Duration { val, .. } => Ok(Value::duration(val / self.count, span)),
+            _ => total.div(head, &Value::int(self.count, head), head),
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::math::{
-    reducers::{Reduce, reducer_for},
-    utils::run_with_function,
+    reducers::Reduce,
+    utils::{Fold, run_with_accumulator},
 };
 use nu_engine::command_prelude::*;
 
@@ -46,7 +46,7 @@ impl Command for MathMax {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, maximum)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Maximum))
     }
 
     fn run_const(
@@ -55,7 +55,7 @@ impl Command for MathMax {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, maximum)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Maximum))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -84,11 +84,6 @@ impl Command for MathMax {
     }
 }
 
-pub fn maximum(values: &[Value], span: Span, head: Span) -> Result<Value, ShellError> {
-    let max_func = reducer_for(Reduce::Maximum);
-    max_func(Value::nothing(head), values.to_vec(), span, head)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
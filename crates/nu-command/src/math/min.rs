@@ -1,6 +1,6 @@
 use crate::math::{
-    reducers::{Reduce, reducer_for},
-    utils::run_with_function,
+    reducers::Reduce,
+    utils::{Fold, run_with_accumulator},
 };
 use nu_engine::command_prelude::*;
 
@@ -46,7 +46,7 @@ impl Command for MathMin {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, minimum)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Minimum))
     }
 
     fn run_const(
@@ -55,7 +55,7 @@ impl Command for MathMin {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, minimum)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Minimum))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -82,11 +82,6 @@ impl Command for MathMin {
     }
 }
 
-pub fn minimum(values: &[Value], span: Span, head: Span) -> Result<Value, ShellError> {
-    let min_func = reducer_for(Reduce::Minimum);
-    min_func(Value::nothing(head), values.to_vec(), span, head)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
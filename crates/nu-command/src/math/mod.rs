@@ -8,9 +8,11 @@ mod max;
 mod median;
 mod min;
 mod mode;
+mod percentile;
 mod product;
-mod reducers;
+pub(crate) mod reducers;
 mod round;
+mod skew;
 mod sqrt;
 mod stddev;
 mod sum;
@@ -26,8 +28,10 @@ pub use max::MathMax;
 pub use median::MathMedian;
 pub use min::MathMin;
 pub use mode::MathMode;
+pub use percentile::MathPercentile;
 pub use product::MathProduct;
 pub use round::MathRound;
+pub use skew::MathSkew;
 pub use sqrt::MathSqrt;
 pub use stddev::MathStddev;
 pub use sum::MathSum;
@@ -0,0 +1,158 @@
+use crate::math::utils::run_with_function;
+use nu_engine::command_prelude::*;
+use std::cmp::Ordering;
+
+#[derive(Clone)]
+pub struct MathPercentile;
+
+impl Command for MathPercentile {
+    fn name(&self) -> &str {
+        "math percentile"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math percentile")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Number),
+                (Type::List(Box::new(Type::Duration)), Type::Duration),
+                (Type::List(Box::new(Type::Filesize)), Type::Filesize),
+                (Type::Range, Type::Number),
+                (Type::table(), Type::record()),
+                (Type::record(), Type::record()),
+            ])
+            .required(
+                "percentile",
+                SyntaxShape::Number,
+                "The percentage of values that are below the result, from 0 to 100.",
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Math)
+    }
+
+    fn description(&self) -> &str {
+        "Computes a percentile of a list of numbers, or of each column in a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "When the percentile falls between two values, the result is interpolated linearly between \
+         them, so `math percentile 50` is the same as `math median`. Unlike the other aggregations, \
+         this needs to collect all the values."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["quantile", "p95", "p99", "statistics"]
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let percentile = percentage(call.req(engine_state, stack, 0)?)?;
+        run_with_function(call, input, move |values, span, head| {
+            compute_percentile(values, percentile, span, head)
+        })
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let percentile = percentage(call.req_const(working_set, 0)?)?;
+        run_with_function(call, input, move |values, span, head| {
+            compute_percentile(values, percentile, span, head)
+        })
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Compute the 95th percentile of a list of numbers.",
+                example: "1..100 | math percentile 95",
+                result: Some(Value::test_float(95.05)),
+            },
+            Example {
+                description: "Compute the 90th percentile of a list of durations.",
+                example: "[10ms 20ms 30ms 40ms] | math percentile 90",
+                result: Some(Value::test_duration(37_000_000)),
+            },
+            Example {
+                description: "Compute the 25th percentile of each column in a table.",
+                example: "[[a b]; [1 2] [3 4] [5 6]] | math percentile 25",
+                result: Some(Value::test_record(record! {
+                    "a" => Value::test_float(2.0),
+                    "b" => Value::test_float(3.0),
+                })),
+            },
+        ]
+    }
+}
+
+fn percentage(percentile: Spanned<f64>) -> Result<f64, ShellError> {
+    if (0.0..=100.0).contains(&percentile.item) {
+        Ok(percentile.item)
+    } else {
+        Err(ShellError::IncorrectValue {
+            msg: "The percentile has to be between 0 and 100".into(),
+            val_span: percentile.span,
+            call_span: percentile.span,
+        })
+    }
+}
+
+fn compute_percentile(
+    values: &[Value],
+    percentile: f64,
+    span: Span,
+    head: Span,
+) -> Result<Value, ShellError> {
+    if let Some(Value::Error { error, .. }) = values.iter().find(|v| v.is_error()) {
+        return Err(*error.clone());
+    }
+
+    let mut sorted = values
+        .iter()
+        .filter(|x| !x.as_float().is_ok_and(f64::is_nan))
+        .collect::<Vec<_>>();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return Err(ShellError::UnsupportedInput {
+            msg: "Empty input".to_string(),
+            input: "value originates from here".into(),
+            msg_span: head,
+            input_span: span,
+        });
+    };
+
+    let rank = percentile / 100.0 * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - rank.floor();
+    if lower == upper {
+        return Ok(sorted[lower].clone());
+    }
+    // lower + (upper - lower) * fraction
+    let difference = sorted[upper].sub(head, sorted[lower], head)?;
+    let offset = difference.mul(head, &Value::float(fraction, head), head)?;
+    sorted[lower].add(head, &offset, head)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(MathPercentile {})
+    }
+}
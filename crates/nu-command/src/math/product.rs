@@ -1,6 +1,6 @@
 use crate::math::{
-    reducers::{Reduce, reducer_for},
-    utils::run_with_function,
+    reducers::Reduce,
+    utils::{Fold, run_with_accumulator},
 };
 use nu_engine::command_prelude::*;
 
@@ -43,7 +43,7 @@ impl Command for MathProduct {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, product)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Product))
     }
 
     fn run_const(
@@ -52,7 +52,7 @@ impl Command for MathProduct {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, product)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Product))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -74,12 +74,6 @@ impl Command for MathProduct {
     }
 }
 
-/// Calculate product of given values
-pub fn product(values: &[Value], span: Span, head: Span) -> Result<Value, ShellError> {
-    let product_func = reducer_for(Reduce::Product);
-    product_func(Value::nothing(head), values.to_vec(), span, head)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
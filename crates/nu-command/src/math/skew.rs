@@ -0,0 +1,152 @@
+use super::variance::Moments;
+use crate::math::utils::{Accumulator, run_with_accumulator};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct MathSkew;
+
+impl Command for MathSkew {
+    fn name(&self) -> &str {
+        "math skew"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math skew")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Number),
+                (Type::Range, Type::Number),
+                (Type::table(), Type::record()),
+                (Type::record(), Type::record()),
+            ])
+            .switch(
+                "sample",
+                "Calculate the adjusted sample skewness, to estimate the skewness of a population from a sample of it.",
+                Some('s'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Math)
+    }
+
+    fn description(&self) -> &str {
+        "Returns the skewness of a list of numbers, or of each column in a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The skewness is positive when the numbers above the mean are further from it than the \
+         numbers below it, negative in the opposite case, and 0 for numbers that are symmetric \
+         around their mean."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["skewness", "asymmetry", "moment", "statistics"]
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let sample = call.has_flag(engine_state, stack, "sample")?;
+        run_with_accumulator(call, input, || Skewness::new(sample))
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let sample = call.has_flag_const(working_set, "sample")?;
+        run_with_accumulator(call, input, || Skewness::new(sample))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Compute the skewness of a list of numbers with a long tail to the right.",
+                example: "[1 2 3 10] | math skew",
+                result: Some(Value::test_float(1.0182337649086284)),
+            },
+            Example {
+                description: "Compute the adjusted sample skewness of a list of numbers.",
+                example: "[1 2 3 10] | math skew --sample",
+                result: Some(Value::test_float(1.763632614803888)),
+            },
+            Example {
+                description: "Compute the skewness of each column in a table.",
+                example: "[[a b]; [1 2] [2 4] [3 6]] | math skew",
+                result: Some(Value::test_record(record! {
+                    "a" => Value::test_float(0.0),
+                    "b" => Value::test_float(0.0),
+                })),
+            },
+        ]
+    }
+}
+
+pub struct Skewness {
+    sample: bool,
+    moments: Moments,
+}
+
+impl Skewness {
+    pub fn new(sample: bool) -> Self {
+        Self {
+            sample,
+            moments: Moments::default(),
+        }
+    }
+}
+
+impl Accumulator for Skewness {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        self.moments.push(value, head)
+    }
+
+    fn finish(self, span: Span, head: Span) -> Result<Value, ShellError> {
+        let n = self.moments.count() as f64;
+        if self.moments.count() == 0 {
+            return Err(ShellError::UnsupportedInput {
+                msg: "Empty input".to_string(),
+                input: "value originates from here".into(),
+                msg_span: head,
+                input_span: span,
+            });
+        }
+        if self.sample && self.moments.count() < 3 {
+            return Err(ShellError::DivisionByZero { span: head });
+        }
+
+        let m2 = self.moments.m2();
+        // numbers that are all the same aren't skewed
+        let skew = if m2 == 0.0 {
+            0.0
+        } else {
+            n.sqrt() * self.moments.m3() / m2.powf(1.5)
+        };
+        let skew = if self.sample {
+            skew * (n * (n - 1.0)).sqrt() / (n - 2.0)
+        } else {
+            skew
+        };
+        Ok(Value::float(skew, head))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(MathSkew {})
+    }
+}
@@ -1,5 +1,5 @@
-use super::variance::compute_variance as variance;
-use crate::math::utils::run_with_function;
+use super::variance::Moments;
+use crate::math::utils::{Accumulator, run_with_accumulator};
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -66,7 +66,7 @@ impl Command for MathStddev {
             }
             Ok(val) => val,
         };
-        run_with_function(call, input, compute_stddev(sample))
+        run_with_accumulator(call, input, || StandardDeviation::new(sample))
     }
 
     fn run_const(
@@ -88,7 +88,7 @@ impl Command for MathStddev {
             }
             Ok(val) => val,
         };
-        run_with_function(call, input, compute_stddev(sample))
+        run_with_accumulator(call, input, || StandardDeviation::new(sample))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -115,19 +115,31 @@ impl Command for MathStddev {
     }
 }
 
-pub fn compute_stddev(sample: bool) -> impl Fn(&[Value], Span, Span) -> Result<Value, ShellError> {
-    move |values: &[Value], span: Span, head: Span| {
-        // variance() produces its own usable error, so we can use `?` to propagated the error.
-        let variance = variance(sample)(values, span, head)?;
-        let val_span = variance.span();
-        match variance {
-            Value::Float { val, .. } => Ok(Value::float(val.sqrt(), val_span)),
-            Value::Int { val, .. } => Ok(Value::float((val as f64).sqrt(), val_span)),
-            other => Ok(other),
+pub struct StandardDeviation {
+    sample: bool,
+    moments: Moments,
+}
+
+impl StandardDeviation {
+    pub fn new(sample: bool) -> Self {
+        Self {
+            sample,
+            moments: Moments::default(),
         }
     }
 }
 
+impl Accumulator for StandardDeviation {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        self.moments.push(value, head)
+    }
+
+    fn finish(self, _span: Span, head: Span) -> Result<Value, ShellError> {
+        let variance = self.moments.variance(self.sample, head)?;
+        Ok(Value::float(variance.sqrt(), head))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,6 +1,6 @@
 use crate::math::{
-    reducers::{Reduce, reducer_for},
-    utils::run_with_function,
+    reducers::Reduce,
+    utils::{Fold, run_with_accumulator},
 };
 use nu_engine::command_prelude::*;
 
@@ -45,7 +45,7 @@ impl Command for MathSum {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, summation)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Summation))
     }
 
     fn run_const(
@@ -54,7 +54,7 @@ impl Command for MathSum {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        run_with_function(call, input, summation)
+        run_with_accumulator(call, input, || Fold::new(Reduce::Summation))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -81,11 +81,6 @@ impl Command for MathSum {
     }
 }
 
-pub fn summation(values: &[Value], span: Span, head: Span) -> Result<Value, ShellError> {
-    let sum_func = reducer_for(Reduce::Summation);
-    sum_func(Value::nothing(head), values.to_vec(), span, head)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,9 +1,169 @@
+use super::reducers::{Reduce, ReducerFunction, reducer_for};
 use core::slice;
 use indexmap::IndexMap;
 use nu_protocol::{
-    IntoPipelineData, PipelineData, Range, ShellError, Signals, Span, Value, engine::Call,
+    IntoPipelineData, PipelineData, Range, Record, ShellError, Signals, Span, Value, engine::Call,
 };
 
+/// An aggregation that looks at each value once, in a single pass, so that it can run over list
+/// streams without collecting them.
+pub trait Accumulator {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError>;
+
+    fn finish(self, span: Span, head: Span) -> Result<Value, ShellError>;
+}
+
+/// Runs one of the [`reducer_for`] functions over the values one at a time.
+pub struct Fold {
+    reduce: ReducerFunction,
+    acc: Option<Value>,
+}
+
+impl Fold {
+    pub fn new(reduce: Reduce) -> Self {
+        Self {
+            reduce: reducer_for(reduce),
+            acc: None,
+        }
+    }
+}
+
+impl Accumulator for Fold {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        let values = match self.acc.take() {
+            Some(acc) => vec![acc, value.clone()],
+            None => vec![value.clone()],
+        };
+        self.acc = Some((self.reduce)(
+            Value::nothing(head),
+            values,
+            value.span(),
+            head,
+        )?);
+        Ok(())
+    }
+
+    fn finish(self, span: Span, head: Span) -> Result<Value, ShellError> {
+        match self.acc {
+            Some(acc) => Ok(acc),
+            // let the reducer report the empty input
+            None => (self.reduce)(Value::nothing(head), vec![], span, head),
+        }
+    }
+}
+
+pub fn run_with_accumulator<A: Accumulator>(
+    call: &Call,
+    input: PipelineData,
+    new: impl Fn() -> A,
+) -> Result<PipelineData, ShellError> {
+    accumulate(input, call.head, new).map(|v| v.into_pipeline_data())
+}
+
+/// Like [`calculate`], but pushing the values into the accumulators made by `new` as they come in.
+pub fn accumulate<A: Accumulator>(
+    values: PipelineData,
+    name: Span,
+    new: impl Fn() -> A,
+) -> Result<Value, ShellError> {
+    let span = values.span().unwrap_or(name);
+    match values {
+        PipelineData::ListStream(s, ..) => accumulate_rows(s.into_iter(), span, name, new),
+        PipelineData::Value(Value::List { vals, .. }, ..) => {
+            accumulate_rows(vals.into_iter(), span, name, new)
+        }
+        PipelineData::Value(Value::Record { val, .. }, ..) => {
+            let mut record = val.into_owned();
+            record
+                .iter_mut()
+                .try_for_each(|(_, val)| -> Result<(), ShellError> {
+                    let mut acc = new();
+                    acc.push(val, name)?;
+                    *val = acc.finish(span, name)?;
+                    Ok(())
+                })?;
+            Ok(Value::record(record, span))
+        }
+        PipelineData::Value(Value::Range { val, .. }, ..) => {
+            ensure_bounded(val.as_ref(), span, name)?;
+            accumulate_rows(val.into_range_iter(span, Signals::empty()), span, name, new)
+        }
+        PipelineData::Value(val, ..) => {
+            let mut acc = new();
+            acc.push(&val, name)?;
+            acc.finish(span, name)
+        }
+        PipelineData::Empty => Err(ShellError::PipelineEmpty { dst_span: name }),
+        val => Err(ShellError::UnsupportedInput {
+            msg: "Only ints, floats, lists, records, or ranges are supported".into(),
+            input: "value originates from here".into(),
+            msg_span: name,
+            input_span: val
+                .span()
+                .expect("non-Empty non-ListStream PipelineData had no span"),
+        }),
+    }
+}
+
+fn accumulate_rows<A: Accumulator>(
+    rows: impl Iterator<Item = Value>,
+    span: Span,
+    name: Span,
+    new: impl Fn() -> A,
+) -> Result<Value, ShellError> {
+    let mut rows = rows.peekable();
+    if !matches!(rows.peek(), Some(Value::Record { .. })) {
+        let mut acc = new();
+        for value in rows {
+            acc.push(&value, name)?;
+        }
+        return acc.finish(span, name);
+    }
+
+    // The mathematical function operates over the columns of the table. The columns it can't be
+    // computed for are left out of the result.
+    let mut columns: IndexMap<String, Option<A>> = IndexMap::new();
+    for row in rows {
+        match row {
+            Value::Record { val, .. } => {
+                for (key, value) in val.into_owned() {
+                    let column = columns.entry(key).or_insert_with(|| Some(new()));
+                    if let Some(acc) = column
+                        && acc.push(&value, name).is_err()
+                    {
+                        *column = None;
+                    }
+                }
+            }
+            Value::Error { error, .. } => return Err(*error),
+            other => {
+                return Err(ShellError::UnsupportedInput {
+                    msg: "Unable to give a result with a table that has rows which are not records"
+                        .to_string(),
+                    input: "value originates from here".into(),
+                    msg_span: name,
+                    input_span: other.span(),
+                });
+            }
+        }
+    }
+
+    let column_totals = columns
+        .into_iter()
+        .filter_map(|(col_name, acc)| Some((col_name, acc?.finish(span, name).ok()?)))
+        .collect::<Record>();
+    if column_totals.is_empty() {
+        return Err(ShellError::UnsupportedInput {
+            msg: "Unable to give a result with this input".to_string(),
+            input: "value originates from here".into(),
+            msg_span: name,
+            input_span: span,
+        });
+    }
+
+    Ok(Value::record(column_totals, name))
+}
+
 pub fn run_with_function(
     call: &Call,
     input: PipelineData,
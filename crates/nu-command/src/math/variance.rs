@@ -1,4 +1,4 @@
-use crate::math::utils::run_with_function;
+use crate::math::utils::{Accumulator, run_with_accumulator};
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -58,7 +58,7 @@ impl Command for MathVariance {
             }
             Ok(val) => val,
         };
-        run_with_function(call, input, compute_variance(sample))
+        run_with_accumulator(call, input, || Variance::new(sample))
     }
 
     fn run_const(
@@ -80,7 +80,7 @@ impl Command for MathVariance {
             }
             Ok(val) => val,
         };
-        run_with_function(call, input, compute_variance(sample))
+        run_with_accumulator(call, input, || Variance::new(sample))
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -107,51 +107,93 @@ impl Command for MathVariance {
     }
 }
 
-fn sum_of_squares(values: &[Value], span: Span) -> Result<Value, ShellError> {
-    let n = Value::int(values.len() as i64, span);
-    let mut sum_x = Value::int(0, span);
-    let mut sum_x2 = Value::int(0, span);
-    for value in values {
-        let v = match &value {
-            Value::Int { .. } | Value::Float { .. } => Ok(value),
-            Value::Error { error, .. } => Err(*error.clone()),
-            other => Err(ShellError::UnsupportedInput {
-                msg: format!(
-                    "Attempted to compute the sum of squares of a non-int, non-float value '{}' with a type of `{}`.",
-                    other.coerce_string()?,
-                    other.get_type()
-                ),
-                input: "value originates from here".into(),
-                msg_span: span,
-                input_span: value.span(),
-            }),
-        }?;
-        let v_squared = &v.mul(span, v, span)?;
-        sum_x2 = sum_x2.add(span, v_squared, span)?;
-        sum_x = sum_x.add(span, v, span)?;
-    }
-
-    let sum_x_squared = sum_x.mul(span, &sum_x, span)?;
-    let sum_x_squared_div_n = sum_x_squared.div(span, &n, span)?;
-
-    let ss = sum_x2.sub(span, &sum_x_squared_div_n, span)?;
-
-    Ok(ss)
+/// The count, mean and central moments of numbers, updated one number at a time with Welford's
+/// algorithm, extended to the third moment for the skewness.
+#[derive(Default)]
+pub struct Moments {
+    count: i64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
 }
 
-pub fn compute_variance(
-    sample: bool,
-) -> impl Fn(&[Value], Span, Span) -> Result<Value, ShellError> {
-    move |values: &[Value], span: Span, head: Span| {
-        let n = if sample {
-            values.len() - 1
-        } else {
-            values.len()
+impl Moments {
+    pub fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        let x = match value {
+            Value::Int { val, .. } => *val as f64,
+            Value::Float { val, .. } => *val,
+            Value::Error { error, .. } => return Err(*error.clone()),
+            other => {
+                return Err(ShellError::UnsupportedInput {
+                    msg: format!(
+                        "Attempted to compute the sum of squares of a non-int, non-float value '{}' with a type of `{}`.",
+                        other.coerce_string()?,
+                        other.get_type()
+                    ),
+                    input: "value originates from here".into(),
+                    msg_span: head,
+                    input_span: value.span(),
+                });
+            }
         };
-        // sum_of_squares() needs the span of the original value, not the call head.
-        let ss = sum_of_squares(values, span)?;
-        let n = Value::int(n as i64, head);
-        ss.div(head, &n, head)
+
+        let n1 = self.count as f64;
+        self.count += 1;
+        let n = self.count as f64;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let term1 = delta * delta_n * n1;
+        self.mean += delta_n;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+        Ok(())
+    }
+
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+
+    /// The sum of the squared differences from the mean.
+    pub fn m2(&self) -> f64 {
+        self.m2
+    }
+
+    /// The sum of the cubed differences from the mean.
+    pub fn m3(&self) -> f64 {
+        self.m3
+    }
+
+    pub fn variance(&self, sample: bool, head: Span) -> Result<f64, ShellError> {
+        let n = if sample { self.count - 1 } else { self.count };
+        if n <= 0 {
+            return Err(ShellError::DivisionByZero { span: head });
+        }
+        Ok(self.m2 / n as f64)
+    }
+}
+
+pub struct Variance {
+    sample: bool,
+    moments: Moments,
+}
+
+impl Variance {
+    pub fn new(sample: bool) -> Self {
+        Self {
+            sample,
+            moments: Moments::default(),
+        }
+    }
+}
+
+impl Accumulator for Variance {
+    fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        self.moments.push(value, head)
+    }
+
+    fn finish(self, _span: Span, head: Span) -> Result<Value, ShellError> {
+        let variance = self.moments.variance(self.sample, head)?;
+        Ok(Value::float(variance, head))
     }
 }
 
@@ -19,6 +19,12 @@ impl Command for Source {
                 SyntaxShape::OneOf(vec![SyntaxShape::Filepath, SyntaxShape::Nothing]),
                 "The filepath to the script file to source (`null` for no-op).",
             )
+            .rest(
+                "args",
+                SyntaxShape::Any,
+                "Constant arguments for the script, which it can read from `$argv`.",
+            )
+            .allows_unknown_args()
             .category(Category::Core)
     }
 
@@ -27,7 +33,12 @@ impl Command for Source {
     }
 
     fn extra_description(&self) -> &str {
-        r#"This command is a parser keyword. For details, check:
+        r#"Arguments after the file name are passed to the script as the `$argv` constant, a list of their
+values in order. Flags are passed as strings, so `source setup.nu --profile dev` gives
+`["--profile", "dev"]`. The arguments must be constants, since the script is parsed along with the
+code that sources it.
+
+This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"#
     }
 
@@ -120,6 +131,11 @@ impl Command for Source {
                 example: r#"source ./foo.nu; say-hi"#,
                 result: None,
             },
+            Example {
+                description: "Pass arguments to a script, which reads them from `$argv`.",
+                example: r#"source setup.nu --profile dev"#,
+                result: None,
+            },
             Example {
                 description: "Sourcing `null` is a no-op.",
                 example: r#"source null"#,
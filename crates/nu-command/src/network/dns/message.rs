@@ -0,0 +1,489 @@
+//! Just enough of the DNS wire format (RFC 1035) to send a single question and read the answers.
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_TRUNCATED: u16 = 0x0200;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+const CLASS_IN: u16 = 1;
+const TYPE_OPT: u16 = 41;
+/// The UDP payload size we advertise with EDNS, the value recommended to avoid fragmentation.
+pub const UDP_PAYLOAD_SIZE: u16 = 1232;
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_PTR: u16 = 12;
+
+/// The names of the record types in [`RECORD_TYPES`], for completions.
+pub const RECORD_TYPE_NAMES: &[&str] = &[
+    "A", "NS", "CNAME", "SOA", "PTR", "MX", "TXT", "AAAA", "SRV", "CAA", "ANY",
+];
+
+/// The record types that can be queried by name.
+pub const RECORD_TYPES: &[(&str, u16)] = &[
+    ("A", 1),
+    ("NS", 2),
+    ("CNAME", 5),
+    ("SOA", 6),
+    ("PTR", 12),
+    ("MX", 15),
+    ("TXT", 16),
+    ("AAAA", 28),
+    ("SRV", 33),
+    ("CAA", 257),
+    ("ANY", 255),
+];
+
+pub fn record_type_code(name: &str) -> Option<u16> {
+    RECORD_TYPES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+        .map(|(_, code)| *code)
+}
+
+pub fn record_type_name(code: u16) -> String {
+    RECORD_TYPES
+        .iter()
+        .find(|(_, known)| *known == code)
+        .map_or_else(|| format!("TYPE{code}"), |(name, _)| name.to_string())
+}
+
+/// The name to look up for a reverse lookup of `ip`, e.g. `4.3.2.1.in-addr.arpa` for `1.2.3.4`.
+pub fn reverse_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, d] = ip.octets();
+            format!("{d}.{c}.{b}.{a}.in-addr.arpa")
+        }
+        IpAddr::V6(ip) => {
+            let mut name = String::with_capacity(72);
+            for byte in ip.octets().iter().rev() {
+                name.push_str(&format!("{:x}.{:x}.", byte & 0xf, byte >> 4));
+            }
+            name.push_str("ip6.arpa");
+            name
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCode {
+    NoError,
+    FormatError,
+    ServerFailure,
+    NameError,
+    NotImplemented,
+    Refused,
+    Other,
+}
+
+impl From<u16> for ResponseCode {
+    fn from(flags: u16) -> Self {
+        match flags & 0xf {
+            0 => ResponseCode::NoError,
+            1 => ResponseCode::FormatError,
+            2 => ResponseCode::ServerFailure,
+            3 => ResponseCode::NameError,
+            4 => ResponseCode::NotImplemented,
+            5 => ResponseCode::Refused,
+            _ => ResponseCode::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageError {
+    InvalidName(String),
+    Malformed,
+    UnexpectedResponse,
+}
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageError::InvalidName(name) => write!(f, "'{name}' is not a valid domain name"),
+            MessageError::Malformed => write!(f, "Received a malformed DNS response"),
+            MessageError::UnexpectedResponse => {
+                write!(f, "Received a response that doesn't match the query")
+            }
+        }
+    }
+}
+
+pub struct Query {
+    pub id: u16,
+    pub name: String,
+    pub record_type: u16,
+}
+
+impl Query {
+    pub fn new(name: &str, record_type: u16) -> Self {
+        let id = RandomState::new().build_hasher().finish() as u16;
+        Query {
+            id,
+            name: name.trim_end_matches('.').to_string(),
+            record_type,
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, MessageError> {
+        let mut buf = Vec::with_capacity(512);
+        buf.extend_from_slice(&self.id.to_be_bytes());
+        buf.extend_from_slice(&FLAG_RECURSION_DESIRED.to_be_bytes());
+        // one question, no answers or authorities, the EDNS record as the only additional
+        for count in [1u16, 0, 0, 1] {
+            buf.extend_from_slice(&count.to_be_bytes());
+        }
+
+        encode_name(&self.name, &mut buf)?;
+        buf.extend_from_slice(&self.record_type.to_be_bytes());
+        buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+
+        // EDNS OPT pseudo record: root name, type, payload size as class, no flags and data
+        buf.push(0);
+        buf.extend_from_slice(&TYPE_OPT.to_be_bytes());
+        buf.extend_from_slice(&UDP_PAYLOAD_SIZE.to_be_bytes());
+        buf.extend_from_slice(&[0; 6]);
+
+        Ok(buf)
+    }
+}
+
+fn encode_name(name: &str, buf: &mut Vec<u8>) -> Result<(), MessageError> {
+    let invalid = || MessageError::InvalidName(name.to_string());
+    if name.len() > 253 {
+        return Err(invalid());
+    }
+    if !name.is_empty() {
+        for label in name.split('.') {
+            if label.is_empty() || label.len() > 63 {
+                return Err(invalid());
+            }
+            buf.push(label.len() as u8);
+            buf.extend_from_slice(label.as_bytes());
+        }
+    }
+    buf.push(0);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub name: String,
+    pub record_type: u16,
+    pub ttl: u32,
+    pub data: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub code: ResponseCode,
+    pub truncated: bool,
+    pub answers: Vec<Record>,
+}
+
+impl Response {
+    pub fn decode(query: &Query, msg: &[u8]) -> Result<Self, MessageError> {
+        let mut reader = Reader { msg, pos: 0 };
+
+        let id = reader.u16()?;
+        let flags = reader.u16()?;
+        if id != query.id || flags & FLAG_RESPONSE == 0 {
+            return Err(MessageError::UnexpectedResponse);
+        }
+        let questions = reader.u16()?;
+        let answers = reader.u16()?;
+        // authorities and additionals aren't interesting
+        reader.u16()?;
+        reader.u16()?;
+
+        for _ in 0..questions {
+            reader.name()?;
+            reader.u16()?;
+            reader.u16()?;
+        }
+
+        let truncated = flags & FLAG_TRUNCATED != 0;
+        let mut records = Vec::with_capacity(answers as usize);
+        for _ in 0..answers {
+            match reader.record() {
+                Ok(record) => records.push(record),
+                // a truncated response may end in the middle of a record
+                Err(_) if truncated => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Response {
+            code: ResponseCode::from(flags),
+            truncated,
+            answers: records,
+        })
+    }
+}
+
+struct Reader<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], MessageError> {
+        let bytes = self
+            .msg
+            .get(self.pos..self.pos + len)
+            .ok_or(MessageError::Malformed)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, MessageError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, MessageError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, MessageError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a possibly compressed name, leaving the reader right after it.
+    fn name(&mut self) -> Result<String, MessageError> {
+        let mut labels = Vec::new();
+        let mut pos = self.pos;
+        let mut end = None;
+        // every pointer has to go backwards, which rules out loops
+        let mut limit = pos;
+
+        loop {
+            let len = *self.msg.get(pos).ok_or(MessageError::Malformed)? as usize;
+            match len & 0xc0 {
+                0x00 if len == 0 => {
+                    end.get_or_insert(pos + 1);
+                    break;
+                }
+                0x00 => {
+                    let label = self
+                        .msg
+                        .get(pos + 1..pos + 1 + len)
+                        .ok_or(MessageError::Malformed)?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    pos += 1 + len;
+                }
+                0xc0 => {
+                    let low = *self.msg.get(pos + 1).ok_or(MessageError::Malformed)? as usize;
+                    let target = ((len & 0x3f) << 8) | low;
+                    if target >= limit {
+                        return Err(MessageError::Malformed);
+                    }
+                    end.get_or_insert(pos + 2);
+                    limit = target;
+                    pos = target;
+                }
+                _ => return Err(MessageError::Malformed),
+            }
+        }
+
+        self.pos = end.unwrap_or(pos);
+        Ok(labels.join("."))
+    }
+
+    fn record(&mut self) -> Result<Record, MessageError> {
+        let name = self.name()?;
+        let record_type = self.u16()?;
+        let _class = self.u16()?;
+        let ttl = self.u32()?;
+        let len = self.u16()? as usize;
+        let end = self.pos + len;
+        if end > self.msg.len() {
+            return Err(MessageError::Malformed);
+        }
+
+        let data = match record_type {
+            1 if len == 4 => {
+                let bytes = self.bytes(4)?;
+                Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string()
+            }
+            28 if len == 16 => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(self.bytes(16)?);
+                Ipv6Addr::from(octets).to_string()
+            }
+            // NS, CNAME, PTR
+            2 | 5 | 12 => self.name()?,
+            // MX
+            15 => {
+                let preference = self.u16()?;
+                format!("{preference} {}", self.name()?)
+            }
+            // SOA
+            6 => {
+                let mname = self.name()?;
+                let rname = self.name()?;
+                let values = [
+                    self.u32()?,
+                    self.u32()?,
+                    self.u32()?,
+                    self.u32()?,
+                    self.u32()?,
+                ];
+                let values = values.map(|value| value.to_string()).join(" ");
+                format!("{mname} {rname} {values}")
+            }
+            // TXT, made of several strings that are meant to be joined
+            16 => {
+                let mut text = String::new();
+                while self.pos < end {
+                    let len = self.u8()? as usize;
+                    text.push_str(&String::from_utf8_lossy(self.bytes(len)?));
+                }
+                text
+            }
+            // SRV
+            33 => {
+                let priority = self.u16()?;
+                let weight = self.u16()?;
+                let port = self.u16()?;
+                format!("{priority} {weight} {port} {}", self.name()?)
+            }
+            // CAA
+            257 if len >= 2 => {
+                let flags = self.u8()?;
+                let tag_len = self.u8()? as usize;
+                let tag = String::from_utf8_lossy(self.bytes(tag_len)?).into_owned();
+                let value =
+                    String::from_utf8_lossy(self.bytes(end.saturating_sub(self.pos))?).into_owned();
+                format!("{flags} {tag} \"{value}\"")
+            }
+            // anything else in the generic format of RFC 3597
+            _ => {
+                let hex = self
+                    .bytes(len)?
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>();
+                format!("\\# {len} {hex}")
+            }
+        };
+
+        if self.pos != end {
+            return Err(MessageError::Malformed);
+        }
+
+        Ok(Record {
+            name,
+            record_type,
+            ttl,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn response(query: &Query, answers: &[u8], answer_count: u16) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&query.id.to_be_bytes());
+        msg.extend_from_slice(&0x8180u16.to_be_bytes());
+        msg.extend_from_slice(&[0, 1]);
+        msg.extend_from_slice(&answer_count.to_be_bytes());
+        msg.extend_from_slice(&[0, 0, 0, 0]);
+        encode_name(&query.name, &mut msg).expect("valid name");
+        msg.extend_from_slice(&query.record_type.to_be_bytes());
+        msg.extend_from_slice(&CLASS_IN.to_be_bytes());
+        msg.extend_from_slice(answers);
+        msg
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let query = Query {
+            id: 0x1234,
+            name: "example.com".into(),
+            record_type: 1,
+        };
+        let encoded = query.encode().expect("valid query");
+
+        assert_eq!(&encoded[..4], &[0x12, 0x34, 0x01, 0x00]);
+        assert_eq!(
+            &encoded[12..29],
+            b"\x07example\x03com\x00\x00\x01\x00\x01".as_slice()
+        );
+    }
+
+    #[test]
+    fn test_encode_invalid_name() {
+        let query = Query::new("a..b", 1);
+        assert_eq!(
+            query.encode(),
+            Err(MessageError::InvalidName("a..b".into()))
+        );
+    }
+
+    #[test]
+    fn test_decode_compressed_answers() {
+        let query = Query::new("example.com", 15);
+        // MX 10 mail.example.com, both names pointing back into the question at offset 12
+        let answer = [
+            0xc0, 12, 0, 15, 0, 1, 0, 0, 0x0e, 0x10, 0, 9, 0, 10, 4, b'm', b'a', b'i', b'l', 0xc0,
+            12,
+        ];
+        let msg = response(&query, &answer, 1);
+
+        let response = Response::decode(&query, &msg).expect("valid response");
+        assert_eq!(response.code, ResponseCode::NoError);
+        assert_eq!(
+            response.answers,
+            vec![Record {
+                name: "example.com".into(),
+                record_type: 15,
+                ttl: 3600,
+                data: "10 mail.example.com".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_pointer_loops() {
+        let query = Query::new("example.com", 1);
+        let mut msg = response(&query, &[], 1);
+        let own_offset = msg.len() as u8;
+        msg.extend_from_slice(&[0xc0, own_offset, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0]);
+
+        assert!(Response::decode(&query, &msg).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_other_ids() {
+        let query = Query::new("example.com", 1);
+        let mut msg = response(&query, &[], 0);
+        msg[0] ^= 0xff;
+
+        assert_eq!(
+            Response::decode(&query, &msg).map(|_| ()),
+            Err(MessageError::UnexpectedResponse)
+        );
+    }
+
+    #[test]
+    fn test_reverse_name() {
+        assert_eq!(
+            reverse_name("1.2.3.4".parse().expect("valid ip")),
+            "4.3.2.1.in-addr.arpa"
+        );
+        assert_eq!(
+            reverse_name("2001:db8::1".parse().expect("valid ip")),
+            "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+        );
+    }
+}
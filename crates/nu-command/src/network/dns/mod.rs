@@ -0,0 +1,6 @@
+mod dns_;
+mod message;
+mod query;
+
+pub use dns_::Dns;
+pub use query::DnsQuery;
@@ -0,0 +1,463 @@
+use super::message::{
+    MessageError, Query, RECORD_TYPE_NAMES, Response, ResponseCode, TYPE_A, TYPE_PTR,
+    UDP_PAYLOAD_SIZE, record_type_code, record_type_name, reverse_name,
+};
+use nu_engine::command_prelude::*;
+use nu_protocol::{
+    Signals,
+    shell_error::{
+        io::IoError,
+        network::{DnsError, DnsErrorKind},
+    },
+};
+use std::{
+    io::{self, Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct DnsQuery;
+
+impl Command for DnsQuery {
+    fn name(&self) -> &str {
+        "dns query"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("dns query")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("name".into(), Type::String),
+                        ("type".into(), Type::String),
+                        ("ttl".into(), Type::Duration),
+                        ("data".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The domain name to look up, or an IP address for a reverse lookup.",
+            )
+            .param(
+                Flag::new("type")
+                    .short('t')
+                    .arg(SyntaxShape::String)
+                    .desc(
+                        "The type of records to query. Default: 'A', or 'PTR' when looking up an \
+                         IP address.",
+                    )
+                    .completion(Completion::new_list(RECORD_TYPE_NAMES)),
+            )
+            .named(
+                "server",
+                SyntaxShape::String,
+                "The DNS server to ask, optionally with a port. Default: the first nameserver \
+                 in /etc/resolv.conf, or on Windows of the network interfaces.",
+                Some('s'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "How long to wait for an answer. Default: 5sec.",
+                None,
+            )
+            .switch("tcp", "Send the query over TCP instead of UDP.", None)
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Query DNS records of a domain."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Unlike the lookups done by other commands, this asks a DNS server directly, so the \
+         results aren't affected by /etc/hosts or any caching done by the operating system. \
+         Queries are sent over UDP, and retried over TCP when the answer doesn't fit."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "network", "dig", "nslookup", "host", "resolve", "mx", "txt", "reverse",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let record_type: Option<Spanned<String>> = call.get_flag(engine_state, stack, "type")?;
+        let server: Option<Spanned<String>> = call.get_flag(engine_state, stack, "server")?;
+        let timeout = match call.get_flag::<Value>(engine_state, stack, "timeout")? {
+            Some(value) => {
+                let nanos = value.as_duration()?;
+                if nanos <= 0 {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "Timeout must be larger than 0".into(),
+                        val_span: value.span(),
+                        call_span: head,
+                    });
+                }
+                Duration::from_nanos(nanos as u64)
+            }
+            None => DEFAULT_TIMEOUT,
+        };
+        let tcp = call.has_flag(engine_state, stack, "tcp")?;
+
+        let ip = name.item.parse::<IpAddr>().ok();
+        let record_type = match &record_type {
+            Some(Spanned { item, span }) => {
+                record_type_code(item).ok_or_else(|| ShellError::IncorrectValue {
+                    msg: format!(
+                        "Unknown record type, expected one of: {}",
+                        RECORD_TYPE_NAMES.join(", ")
+                    ),
+                    val_span: *span,
+                    call_span: head,
+                })?
+            }
+            None if ip.is_some() => TYPE_PTR,
+            None => TYPE_A,
+        };
+        let query_name = match ip {
+            Some(ip) if record_type == TYPE_PTR => reverse_name(ip),
+            _ => name.item.clone(),
+        };
+
+        let server = match server {
+            Some(server) => parse_server(&server)?,
+            None => system_nameserver(head)?,
+        };
+
+        let query = Query::new(&query_name, record_type);
+        let encoded = query.encode().map_err(|err| ShellError::IncorrectValue {
+            msg: err.to_string(),
+            val_span: name.span,
+            call_span: head,
+        })?;
+
+        let signals = engine_state.signals();
+        let deadline = Instant::now() + timeout;
+        let response = if tcp {
+            query_tcp(&query, &encoded, server, deadline, signals, head)?
+        } else {
+            let response = query_udp(&query, &encoded, server, deadline, signals, head)?;
+            if response.truncated {
+                query_tcp(&query, &encoded, server, deadline, signals, head)?
+            } else {
+                response
+            }
+        };
+
+        let kind = match response.code {
+            ResponseCode::NoError => None,
+            ResponseCode::NameError => Some(DnsErrorKind::NoName),
+            ResponseCode::ServerFailure => Some(DnsErrorKind::Again),
+            _ => Some(DnsErrorKind::Fail),
+        };
+        if let Some(kind) = kind {
+            return Err(DnsError {
+                kind,
+                span: head,
+                query: name,
+            }
+            .into());
+        }
+
+        let records = response
+            .answers
+            .into_iter()
+            .map(|record| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(record.name, head),
+                        "type" => Value::string(record_type_name(record.record_type), head),
+                        "ttl" => Value::duration(i64::from(record.ttl) * 1_000_000_000, head),
+                        "data" => Value::string(record.data, head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+
+        Ok(Value::list(records, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Look up the IPv4 addresses of a domain.",
+                example: "dns query example.com",
+                result: None,
+            },
+            Example {
+                description: "Ask a specific server for the mail servers of a domain.",
+                example: "dns query example.com --type MX --server 1.1.1.1",
+                result: None,
+            },
+            Example {
+                description: "Find the host name of an IP address.",
+                example: "dns query 8.8.8.8",
+                result: None,
+            },
+            Example {
+                description: "Check the SPF record of a domain.",
+                example: "dns query example.com --type TXT | where data starts-with 'v=spf1'",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn parse_server(server: &Spanned<String>) -> Result<SocketAddr, ShellError> {
+    if let Ok(addr) = server.item.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    if let Ok(ip) = server.item.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, 53));
+    }
+
+    let resolved = if server.item.contains(':') {
+        server.item.to_socket_addrs()
+    } else {
+        (server.item.as_str(), 53).to_socket_addrs()
+    };
+    resolved
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| ShellError::IncorrectValue {
+            msg: "Expected an IP address or host name of a DNS server, optionally with a port"
+                .into(),
+            val_span: server.span,
+            call_span: server.span,
+        })
+}
+
+/// The first nameserver the system is configured to use. On unix that's the first in
+/// /etc/resolv.conf, and on Windows the first configured for a network interface, set statically
+/// or by DHCP. Elsewhere a server has to be given explicitly.
+fn system_nameserver(span: Span) -> Result<SocketAddr, ShellError> {
+    let missing = || ShellError::GenericError {
+        error: "No DNS server configured".into(),
+        msg: "could not find a nameserver to ask".into(),
+        span: Some(span),
+        help: Some("Pass the DNS server to ask with --server, e.g. --server 1.1.1.1".into()),
+        inner: vec![],
+    };
+
+    #[cfg(unix)]
+    {
+        let resolv_conf = std::fs::read_to_string("/etc/resolv.conf").map_err(|_| missing())?;
+        resolv_conf
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("nameserver"))
+            .find_map(parse_nameservers)
+            .map(|ip| SocketAddr::new(ip, 53))
+            .ok_or_else(missing)
+    }
+
+    #[cfg(windows)]
+    {
+        use winreg::{RegKey, enums::HKEY_LOCAL_MACHINE};
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        for service in ["Tcpip", "Tcpip6"] {
+            let Ok(interfaces) = hklm.open_subkey(format!(
+                r"SYSTEM\CurrentControlSet\Services\{service}\Parameters\Interfaces"
+            )) else {
+                continue;
+            };
+            for name in interfaces.enum_keys().filter_map(Result::ok) {
+                let Ok(interface) = interfaces.open_subkey(&name) else {
+                    continue;
+                };
+                // a static server takes precedence over the ones from DHCP
+                for value in ["NameServer", "DhcpNameServer"] {
+                    let servers: String = interface.get_value(value).unwrap_or_default();
+                    if let Some(ip) = parse_nameservers(&servers) {
+                        return Ok(SocketAddr::new(ip, 53));
+                    }
+                }
+            }
+        }
+        Err(missing())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    Err(missing())
+}
+
+/// The first address in a list of nameservers separated by spaces or commas.
+#[cfg(any(unix, windows))]
+fn parse_nameservers(servers: &str) -> Option<IpAddr> {
+    servers
+        .split([' ', ',', '\t'])
+        // link local IPv6 servers come with a zone id we can't make use of
+        .filter_map(|server| server.split('%').next())
+        .find_map(|server| server.parse::<IpAddr>().ok())
+}
+
+fn query_udp(
+    query: &Query,
+    encoded: &[u8],
+    server: SocketAddr,
+    deadline: Instant,
+    signals: &Signals,
+    span: Span,
+) -> Result<Response, ShellError> {
+    let io_err = |err: io::Error| ShellError::from(IoError::new(err, span, None));
+
+    let local: SocketAddr = if server.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(local).map_err(io_err)?;
+    socket.connect(server).map_err(io_err)?;
+    socket.send(encoded).map_err(io_err)?;
+
+    let mut buf = vec![0; UDP_PAYLOAD_SIZE as usize];
+    loop {
+        set_step_timeout(deadline, span, signals, |timeout| {
+            socket.set_read_timeout(Some(timeout))
+        })?;
+        match socket.recv(&mut buf) {
+            Ok(len) => match Response::decode(query, &buf[..len]) {
+                Ok(response) => return Ok(response),
+                // anyone can send us packets, ignore those that aren't the answer
+                Err(MessageError::UnexpectedResponse) => continue,
+                Err(err) => return Err(malformed(err, span)),
+            },
+            Err(err) if is_timeout(&err) => continue,
+            Err(err) => return Err(io_err(err)),
+        }
+    }
+}
+
+fn query_tcp(
+    query: &Query,
+    encoded: &[u8],
+    server: SocketAddr,
+    deadline: Instant,
+    signals: &Signals,
+    span: Span,
+) -> Result<Response, ShellError> {
+    let io_err = |err: io::Error| ShellError::from(IoError::new(err, span, None));
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+        return Err(timed_out(span));
+    }
+    let mut stream = TcpStream::connect_timeout(&server, remaining).map_err(|err| {
+        if is_timeout(&err) {
+            timed_out(span)
+        } else {
+            io_err(err)
+        }
+    })?;
+
+    // over TCP, every message is prefixed with its length
+    let mut message = (encoded.len() as u16).to_be_bytes().to_vec();
+    message.extend_from_slice(encoded);
+    stream.write_all(&message).map_err(io_err)?;
+
+    let mut read_exact = |buf: &mut [u8]| -> Result<(), ShellError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            set_step_timeout(deadline, span, signals, |timeout| {
+                stream.set_read_timeout(Some(timeout))
+            })?;
+            match stream.read(&mut buf[filled..]) {
+                Ok(0) => return Err(malformed(MessageError::Malformed, span)),
+                Ok(len) => filled += len,
+                Err(err) if is_timeout(&err) => continue,
+                Err(err) => return Err(io_err(err)),
+            }
+        }
+        Ok(())
+    };
+
+    let mut len = [0; 2];
+    read_exact(&mut len)?;
+    let mut buf = vec![0; u16::from_be_bytes(len) as usize];
+    read_exact(&mut buf)?;
+
+    Response::decode(query, &buf).map_err(|err| malformed(err, span))
+}
+
+/// Waits in small steps, so that ctrl-c is noticed while waiting for an answer.
+fn set_step_timeout(
+    deadline: Instant,
+    span: Span,
+    signals: &Signals,
+    set_timeout: impl FnOnce(Duration) -> io::Result<()>,
+) -> Result<(), ShellError> {
+    signals.check(&span)?;
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+        return Err(timed_out(span));
+    }
+    set_timeout(remaining.min(Duration::from_millis(100)))
+        .map_err(|err| IoError::new(err, span, None).into())
+}
+
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+fn timed_out(span: Span) -> ShellError {
+    ShellError::NetworkFailure {
+        msg: "Timed out waiting for the DNS server to answer".into(),
+        span,
+    }
+}
+
+fn malformed(err: MessageError, span: Span) -> ShellError {
+    ShellError::NetworkFailure {
+        msg: err.to_string(),
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(DnsQuery {})
+    }
+
+    #[test]
+    fn nameserver_lists_are_parsed() {
+        assert_eq!(
+            parse_nameservers(" 192.0.2.1"),
+            Some(IpAddr::from([192, 0, 2, 1]))
+        );
+        assert_eq!(
+            parse_nameservers("fe80::1%eth0 192.0.2.1"),
+            "fe80::1".parse().ok()
+        );
+        assert_eq!(
+            parse_nameservers("nope,192.0.2.2, 192.0.2.3"),
+            Some(IpAddr::from([192, 0, 2, 2]))
+        );
+        assert_eq!(parse_nameservers(""), None);
+    }
+}
@@ -0,0 +1,697 @@
+use super::{
+    codec::Codec,
+    http2::{self, Connection, Event},
+    proto::{self, Descriptors, Method},
+};
+use crate::network::{
+    net::{connect_tcp, parse_address, resolve},
+    tls::{TlsStream, tls_stream},
+};
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::{
+    ListStream, Signals,
+    shell_error::{bridge::ShellErrorBridge, io::IoError},
+};
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often a blocked read wakes up to check for ctrl-c and the deadline.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Every message is prefixed with a compression flag and its length.
+const MESSAGE_HEADER_LEN: usize = 5;
+
+#[derive(Clone)]
+pub struct GrpcCall;
+
+impl Command for GrpcCall {
+    fn name(&self) -> &str {
+        "grpc call"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("grpc call")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::record(), Type::Any),
+                (Type::table(), Type::Any),
+            ])
+            .required(
+                "address",
+                SyntaxShape::String,
+                "The host and port of the server, e.g. example.com:443 or [::1]:50051.",
+            )
+            .required(
+                "method",
+                SyntaxShape::String,
+                "The method to call, as package.Service/Method.",
+            )
+            .required_named(
+                "proto",
+                SyntaxShape::Filepath,
+                "The .proto file that defines the service.",
+                None,
+            )
+            .named(
+                "import-path",
+                SyntaxShape::List(Box::new(SyntaxShape::Directory)),
+                "Directories to look for the files imported by the .proto file in, besides the \
+                 directory of the file itself.",
+                None,
+            )
+            .named(
+                "headers",
+                SyntaxShape::Record(vec![]),
+                "Metadata to send with the call, like an authorization token.",
+                Some('H'),
+            )
+            .switch(
+                "plaintext",
+                "Connect without TLS, as local servers often expect.",
+                None,
+            )
+            .switch(
+                "insecure",
+                "Don't verify the certificate of the server.",
+                Some('k'),
+            )
+            .named(
+                "max-time",
+                SyntaxShape::Duration,
+                "Fail if the call takes longer than this. The server is told about the deadline \
+                 as well.",
+                Some('m'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Call a method of a gRPC service."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The request is taken as a record from the pipeline and encoded using the message types \
+         from the .proto file, so no generated code is needed. Methods that stream their \
+         responses return a list stream of records, all others a single record. Methods that \
+         take a stream of requests accept a list of records. Fields missing from a response \
+         are shown with their default value, or as null if they can be told apart from it, like \
+         messages and optional fields."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "rpc", "protobuf", "grpcurl", "api"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let address: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let method_arg: Spanned<String> = call.req(engine_state, stack, 1)?;
+        let proto_arg: Spanned<String> =
+            call.get_flag(engine_state, stack, "proto")?
+                .ok_or_else(|| ShellError::MissingParameter {
+                    param_name: "proto".into(),
+                    span: head,
+                })?;
+        let import_paths: Option<Vec<String>> =
+            call.get_flag(engine_state, stack, "import-path")?;
+        let headers: Option<Record> = call.get_flag(engine_state, stack, "headers")?;
+        let plaintext = call.has_flag(engine_state, stack, "plaintext")?;
+        let insecure = call.has_flag(engine_state, stack, "insecure")?;
+        let max_time = match call.get_flag::<Value>(engine_state, stack, "max-time")? {
+            Some(value) => {
+                let nanos = value.as_duration()?;
+                if nanos <= 0 {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "Max time must be larger than 0".into(),
+                        val_span: value.span(),
+                        call_span: head,
+                    });
+                }
+                Some(Duration::from_nanos(nanos as u64))
+            }
+            None => None,
+        };
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let proto_path = expand_path_with(&proto_arg.item, &cwd, true);
+        let import_paths = import_paths
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| expand_path_with(path, &cwd, true))
+            .collect::<Vec<_>>();
+        let descriptors =
+            proto::load(&proto_path, &import_paths).map_err(|err| ShellError::GenericError {
+                error: "Invalid .proto file".into(),
+                msg: err.to_string(),
+                span: Some(proto_arg.span),
+                help: None,
+                inner: vec![],
+            })?;
+        let (path, method) = find_method(&descriptors, &method_arg)?;
+
+        // encode everything before connecting, so mistakes in the input fail fast
+        let codec = Codec::new(&descriptors, head);
+        let requests = match input.into_value(head)? {
+            Value::List { vals, .. } if method.client_streaming => vals,
+            Value::Nothing { .. } if method.client_streaming => vec![],
+            value @ (Value::Record { .. } | Value::Nothing { .. }) => vec![value],
+            value => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: match method.client_streaming {
+                        true => "record or list of records".into(),
+                        false => "record".into(),
+                    },
+                    wrong_type: value.get_type().to_string(),
+                    dst_span: head,
+                    src_span: value.span(),
+                });
+            }
+        };
+        let requests = requests
+            .iter()
+            .map(|request| {
+                let message = codec.encode(&method.input, request)?;
+                let mut framed = Vec::with_capacity(MESSAGE_HEADER_LEN + message.len());
+                framed.push(0);
+                framed.extend_from_slice(&(message.len() as u32).to_be_bytes());
+                framed.extend_from_slice(&message);
+                Ok(framed)
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        let (host, port) = parse_address(&address)?;
+        let addrs = resolve(&host, port, address.span)?;
+        let io_err = |err: io::Error| ShellError::from(IoError::new(err, head, None));
+        let stream =
+            connect_tcp(&addrs, CONNECT_TIMEOUT).map_err(|err| ShellError::NetworkFailure {
+                msg: format!("Could not connect to {}: {err}", address.item),
+                span: address.span,
+            })?;
+        let transport = if plaintext {
+            Transport::Tcp(stream.try_clone().map_err(io_err)?)
+        } else {
+            let tls = tls_stream(
+                &host,
+                stream.try_clone().map_err(io_err)?,
+                insecure,
+                &["h2"],
+                head,
+            )?;
+            Transport::Tls(Box::new(tls))
+        };
+        // the clone shares the socket, so this applies to TLS as well
+        stream
+            .set_read_timeout(Some(POLL_INTERVAL))
+            .map_err(io_err)?;
+
+        let call_err = CallError {
+            address: address.item.clone(),
+            span: head,
+        };
+        let stream = CallStream {
+            transport,
+            deadline: max_time.map(|max_time| Instant::now() + max_time),
+            signals: engine_state.signals().clone(),
+            span: head,
+        };
+        let mut connection = Connection::handshake(stream).map_err(|err| call_err.io(err))?;
+
+        let user_agent = format!("nushell/{}", env!("CARGO_PKG_VERSION"));
+        let mut request_headers = vec![
+            (":method".to_string(), "POST".to_string()),
+            (
+                ":scheme".into(),
+                if plaintext { "http" } else { "https" }.into(),
+            ),
+            (":path".into(), path),
+            (":authority".into(), address.item.clone()),
+            ("content-type".into(), "application/grpc".into()),
+            ("te".into(), "trailers".into()),
+            ("user-agent".into(), user_agent),
+        ];
+        if let Some(max_time) = max_time {
+            request_headers.push(("grpc-timeout".into(), grpc_timeout(max_time)));
+        }
+        for (name, value) in headers.unwrap_or_default() {
+            // HTTP/2 only allows lowercase header names
+            request_headers.push((name.to_lowercase(), value.coerce_into_string()?));
+        }
+        let request_headers = request_headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+
+        connection
+            .send_headers(&request_headers, false)
+            .map_err(|err| call_err.io(err))?;
+        if requests.is_empty() {
+            connection
+                .send_data(&[], true)
+                .map_err(|err| call_err.io(err))?;
+        }
+        for (idx, request) in requests.iter().enumerate() {
+            connection
+                .send_data(request, idx + 1 == requests.len())
+                .map_err(|err| call_err.io(err))?;
+        }
+
+        let mut responses = Responses::start(connection, call_err)?;
+
+        if !method.server_streaming {
+            let response = responses
+                .next_message()?
+                .ok_or_else(|| ShellError::NetworkFailure {
+                    msg: "Server ended the call without a response".into(),
+                    span: head,
+                })?;
+            // the status of the call only comes after the response
+            if responses.next_message()?.is_some() {
+                return Err(ShellError::NetworkFailure {
+                    msg: "Server sent more than one response to a unary call".into(),
+                    span: head,
+                });
+            }
+            return Ok(codec
+                .decode(&method.output, &response)?
+                .into_pipeline_data());
+        }
+
+        let mut failed = false;
+        let iter = std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let codec = Codec::new(&descriptors, head);
+            let value = match responses.next_message() {
+                Ok(Some(response)) => codec.decode(&method.output, &response),
+                Ok(None) => return None,
+                Err(err) => Err(err),
+            };
+            Some(value.unwrap_or_else(|err| {
+                failed = true;
+                Value::error(err, head)
+            }))
+        });
+
+        Ok(PipelineData::list_stream(
+            ListStream::new(iter, head, engine_state.signals().clone()),
+            None,
+        ))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Call a method of a local server that doesn't use TLS.",
+                example: "{name: 'nushell'} | grpc call localhost:50051 helloworld.Greeter/SayHello --proto helloworld.proto --plaintext",
+                result: None,
+            },
+            Example {
+                description: "Pass an authorization token along with the request.",
+                example: "{id: 42} | grpc call api.example.com:443 shop.v1.Orders/Get --proto shop.proto --headers {authorization: $'Bearer ($env.TOKEN)'}",
+                result: None,
+            },
+            Example {
+                description: "Follow the responses of a method that streams them, for at most a minute.",
+                example: "{topic: 'deploys'} | grpc call api.example.com:443 events.v1.Events/Watch --proto events.proto --max-time 1min | each {|event| print $event.summary }",
+                result: None,
+            },
+            Example {
+                description: "Send a stream of requests to a method that takes one.",
+                example: "[{value: 1} {value: 2}] | grpc call localhost:50051 stats.Stats/Sum --proto stats.proto --plaintext",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Finds a method given as `package.Service/Method`, returning the path to call it at.
+fn find_method(
+    descriptors: &Descriptors,
+    method: &Spanned<String>,
+) -> Result<(String, Method), ShellError> {
+    let not_found = |msg: String, mut names: Vec<&str>| {
+        names.sort_unstable();
+        ShellError::GenericError {
+            error: "Unknown gRPC method".into(),
+            msg,
+            span: Some(method.span),
+            help: Some(format!("Available are: {}", names.join(", "))),
+            inner: vec![],
+        }
+    };
+
+    let name = method.item.trim_start_matches('/');
+    let Some((service_name, method_name)) = name.rsplit_once('/') else {
+        return Err(ShellError::IncorrectValue {
+            msg: "Expected a method like package.Service/Method".into(),
+            val_span: method.span,
+            call_span: method.span,
+        });
+    };
+    let Some(service) = descriptors.services.get(service_name) else {
+        return Err(not_found(
+            format!("the .proto file has no service {service_name}"),
+            descriptors.services.keys().map(String::as_str).collect(),
+        ));
+    };
+    let Some(found) = service
+        .methods
+        .iter()
+        .find(|found| found.name == method_name)
+    else {
+        return Err(not_found(
+            format!("{service_name} has no method {method_name}"),
+            service
+                .methods
+                .iter()
+                .map(|method| method.name.as_str())
+                .collect(),
+        ));
+    };
+
+    Ok((format!("/{service_name}/{method_name}"), found.clone()))
+}
+
+/// Formats a deadline for the `grpc-timeout` header, which allows at most 8 digits.
+fn grpc_timeout(timeout: Duration) -> String {
+    let millis = timeout.as_millis().max(1);
+    if millis < 100_000_000 {
+        format!("{millis}m")
+    } else {
+        format!("{}S", timeout.as_secs().min(99_999_999))
+    }
+}
+
+/// The name of a gRPC status code.
+fn status_name(code: &str) -> &str {
+    match code {
+        "1" => "CANCELLED",
+        "2" => "UNKNOWN",
+        "3" => "INVALID_ARGUMENT",
+        "4" => "DEADLINE_EXCEEDED",
+        "5" => "NOT_FOUND",
+        "6" => "ALREADY_EXISTS",
+        "7" => "PERMISSION_DENIED",
+        "8" => "RESOURCE_EXHAUSTED",
+        "9" => "FAILED_PRECONDITION",
+        "10" => "ABORTED",
+        "11" => "OUT_OF_RANGE",
+        "12" => "UNIMPLEMENTED",
+        "13" => "INTERNAL",
+        "14" => "UNAVAILABLE",
+        "15" => "DATA_LOSS",
+        "16" => "UNAUTHENTICATED",
+        code => code,
+    }
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Turns the errors of a call into shell errors.
+struct CallError {
+    address: String,
+    span: Span,
+}
+
+impl CallError {
+    fn io(&self, err: io::Error) -> ShellError {
+        let err = match ShellErrorBridge::try_from(err) {
+            Ok(ShellErrorBridge(err)) => return err,
+            Err(err) => err,
+        };
+        let msg = match err.kind() {
+            io::ErrorKind::UnexpectedEof => "the server closed the connection".into(),
+            _ => err.to_string(),
+        };
+        ShellError::NetworkFailure {
+            msg: format!("gRPC call to {} failed: {msg}", self.address),
+            span: self.span,
+        }
+    }
+
+    fn failure(&self, msg: String) -> ShellError {
+        ShellError::NetworkFailure {
+            msg,
+            span: self.span,
+        }
+    }
+
+    /// Checks the `grpc-status` in the trailers, or in the headers if there was no response.
+    fn check_status(&self, headers: &[(String, String)]) -> Result<(), ShellError> {
+        match header(headers, "grpc-status") {
+            Some("0") => Ok(()),
+            Some(code) => {
+                let message = header(headers, "grpc-message")
+                    .map(|message| {
+                        percent_encoding::percent_decode_str(message).decode_utf8_lossy()
+                    })
+                    .unwrap_or_default();
+                Err(ShellError::GenericError {
+                    error: "gRPC call failed".into(),
+                    msg: match message.is_empty() {
+                        true => status_name(code).into(),
+                        false => format!("{}: {message}", status_name(code)),
+                    },
+                    span: Some(self.span),
+                    help: None,
+                    inner: vec![],
+                })
+            }
+            None => Err(self.failure("Server ended the call without a gRPC status".into())),
+        }
+    }
+}
+
+/// Reads the messages of a response, checking the status of the call once it ends.
+struct Responses {
+    connection: Connection<CallStream>,
+    buf: Vec<u8>,
+    done: bool,
+    err: CallError,
+}
+
+impl Responses {
+    /// Waits for the response headers, which tell whether the call reached a gRPC service.
+    fn start(mut connection: Connection<CallStream>, err: CallError) -> Result<Self, ShellError> {
+        let (headers, end_stream) = match connection.next_event().map_err(|e| err.io(e))? {
+            Event::Headers {
+                headers,
+                end_stream,
+            } => (headers, end_stream),
+            Event::Reset(code) => {
+                return Err(err.failure(format!(
+                    "Server cancelled the call ({})",
+                    http2::error_name(code)
+                )));
+            }
+            Event::Data { .. } => {
+                return Err(err.failure("Server sent a response without headers".into()));
+            }
+        };
+
+        match header(&headers, ":status") {
+            Some("200") => {}
+            status => {
+                return Err(err.failure(format!(
+                    "Server answered with HTTP status {}, is this a gRPC service?",
+                    status.unwrap_or("unknown")
+                )));
+            }
+        }
+        // without any response, the status is sent along with the headers
+        if end_stream {
+            err.check_status(&headers)?;
+        }
+
+        Ok(Responses {
+            connection,
+            buf: Vec::new(),
+            done: end_stream,
+            err,
+        })
+    }
+
+    /// Returns the next message, or `None` once the call ended successfully.
+    fn next_message(&mut self) -> Result<Option<Vec<u8>>, ShellError> {
+        loop {
+            if let Some(header) = self.buf.get(..MESSAGE_HEADER_LEN) {
+                let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+                if header[0] != 0 {
+                    return Err(self.err.failure(
+                        "Server sent a compressed response, which isn't supported".into(),
+                    ));
+                }
+                if self.buf.len() >= MESSAGE_HEADER_LEN + len {
+                    let message = self.buf[MESSAGE_HEADER_LEN..][..len].to_vec();
+                    self.buf.drain(..MESSAGE_HEADER_LEN + len);
+                    return Ok(Some(message));
+                }
+            }
+            if self.done {
+                return match self.buf.is_empty() {
+                    true => Ok(None),
+                    false => Err(self
+                        .err
+                        .failure("Server sent an incomplete response".into())),
+                };
+            }
+
+            match self
+                .connection
+                .next_event()
+                .map_err(|err| self.err.io(err))?
+            {
+                Event::Data { data, end_stream } => {
+                    self.buf.extend_from_slice(&data);
+                    if end_stream {
+                        return Err(self
+                            .err
+                            .failure("Server ended the call without a gRPC status".into()));
+                    }
+                }
+                Event::Headers { headers, .. } => {
+                    self.err.check_status(&headers)?;
+                    self.done = true;
+                }
+                Event::Reset(code) => {
+                    return Err(self.err.failure(format!(
+                        "Server cancelled the call ({})",
+                        http2::error_name(code)
+                    )));
+                }
+            }
+        }
+    }
+}
+
+enum Transport {
+    Tcp(TcpStream),
+    Tls(Box<TlsStream>),
+}
+
+/// The connection to the server, which wakes up regularly while waiting to check for ctrl-c and
+/// the deadline.
+struct CallStream {
+    transport: Transport,
+    deadline: Option<Instant>,
+    signals: Signals,
+    span: Span,
+}
+
+impl Read for CallStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            self.signals.check(&self.span).map_err(ShellErrorBridge)?;
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Err(ShellErrorBridge(ShellError::NetworkFailure {
+                    msg: "The call didn't finish within --max-time".into(),
+                    span: self.span,
+                })
+                .into());
+            }
+
+            let result = match &mut self.transport {
+                Transport::Tcp(stream) => stream.read(buf),
+                Transport::Tls(stream) => stream.read(buf),
+            };
+            match result {
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    continue;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Write for CallStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.transport {
+            Transport::Tcp(stream) => stream.write(buf),
+            Transport::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.transport {
+            Transport::Tcp(stream) => stream.flush(),
+            Transport::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(GrpcCall {})
+    }
+
+    #[test]
+    fn test_find_method() {
+        let descriptors = Descriptors::from_source(
+            "syntax = 'proto3'; package shop.v1;
+             message Empty {}
+             service Orders { rpc Get (Empty) returns (Empty); rpc Watch (Empty) returns (stream Empty); }",
+        )
+        .expect("valid proto");
+        let find = |method: &str| {
+            find_method(
+                &descriptors,
+                &Spanned {
+                    item: method.into(),
+                    span: Span::test_data(),
+                },
+            )
+            .map(|(path, method)| (path, method.server_streaming))
+            .ok()
+        };
+
+        assert_eq!(
+            find("shop.v1.Orders/Watch"),
+            Some(("/shop.v1.Orders/Watch".into(), true))
+        );
+        assert_eq!(
+            find("/shop.v1.Orders/Get"),
+            Some(("/shop.v1.Orders/Get".into(), false))
+        );
+        assert_eq!(find("shop.v1.Orders/Delete"), None);
+        assert_eq!(find("Orders/Get"), None);
+        assert_eq!(find("shop.v1.Orders.Get"), None);
+    }
+
+    #[test]
+    fn test_grpc_timeout() {
+        assert_eq!(grpc_timeout(Duration::from_millis(1500)), "1500m");
+        assert_eq!(grpc_timeout(Duration::from_micros(10)), "1m");
+        assert_eq!(grpc_timeout(Duration::from_secs(200_000)), "200000S");
+    }
+}
@@ -0,0 +1,707 @@
+//! Encoding records as protobuf messages and decoding messages back into records, using the
+//! descriptors from the `.proto` file instead of generated code.
+
+use super::proto::{Descriptors, Field, Kind, Label};
+use nu_protocol::{Record, ShellError, Span, Value};
+
+const VARINT: u32 = 0;
+const I64: u32 = 1;
+const LEN: u32 = 2;
+const I32: u32 = 5;
+
+/// A field as it was found on the wire, before knowing what it means.
+#[derive(Debug, Clone, Copy)]
+enum WireValue<'a> {
+    Varint(u64),
+    I64([u8; 8]),
+    Len(&'a [u8]),
+    I32([u8; 4]),
+}
+
+pub struct Codec<'a> {
+    descriptors: &'a Descriptors,
+    head: Span,
+}
+
+impl<'a> Codec<'a> {
+    pub fn new(descriptors: &'a Descriptors, head: Span) -> Self {
+        Codec { descriptors, head }
+    }
+
+    /// Encodes a record as the message type `message`. Columns that are `null` are left out.
+    pub fn encode(&self, message: &str, value: &Value) -> Result<Vec<u8>, ShellError> {
+        let mut buf = Vec::new();
+        self.encode_message(message, value, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Decodes a message of type `message` into a record with a column for every field.
+    ///
+    /// Missing fields are `null` if they can be told apart from their default value, otherwise
+    /// they are the default value, just like generated code would show them.
+    pub fn decode(&self, message: &str, bytes: &[u8]) -> Result<Value, ShellError> {
+        let fields = self.fields(message)?;
+        let wire = read_fields(bytes).ok_or_else(|| self.malformed(message))?;
+
+        let mut record = Record::new();
+        for field in fields {
+            let mut values = wire
+                .iter()
+                .filter(|(number, _)| *number == field.number)
+                .map(|(_, value)| *value)
+                .peekable();
+
+            let value = match (&field.kind, field.label) {
+                (Kind::Map(key, value), _) => {
+                    let mut map = Record::new();
+                    for entry in values {
+                        let (key, value) = self.decode_map_entry(message, key, value, entry)?;
+                        map.insert(key, value);
+                    }
+                    Value::record(map, self.head)
+                }
+                (kind, Label::Repeated) => {
+                    let mut list = Vec::new();
+                    for value in values {
+                        match (value, wire_type(kind)) {
+                            (WireValue::Len(packed), wire_type) if wire_type != LEN => {
+                                let mut reader = Reader::new(packed);
+                                while !reader.is_empty() {
+                                    let value = reader
+                                        .value(wire_type)
+                                        .ok_or_else(|| self.malformed(message))?;
+                                    list.push(self.decode_value(message, kind, value)?);
+                                }
+                            }
+                            (value, _) => list.push(self.decode_value(message, kind, value)?),
+                        }
+                    }
+                    Value::list(list, self.head)
+                }
+                // a message that is sent in parts has to be merged, which is the same as decoding
+                // all the parts at once
+                (Kind::Message(name), _) if values.peek().is_some() => {
+                    let mut bytes = Vec::new();
+                    for value in values {
+                        match value {
+                            WireValue::Len(part) => bytes.extend_from_slice(part),
+                            _ => return Err(self.malformed(message)),
+                        }
+                    }
+                    self.decode(name, &bytes)?
+                }
+                (kind, label) => match values.last() {
+                    Some(value) => self.decode_value(message, kind, value)?,
+                    None if label == Label::Implicit => self.default_value(kind),
+                    None => Value::nothing(self.head),
+                },
+            };
+            record.push(field.name.clone(), value);
+        }
+
+        Ok(Value::record(record, self.head))
+    }
+
+    fn encode_message(
+        &self,
+        message: &str,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        let fields = self.fields(message)?;
+        let record = match value {
+            Value::Record { val, .. } => val,
+            Value::Nothing { .. } => return Ok(()),
+            value => return Err(self.cant_convert(&format!("message {message}"), value)),
+        };
+
+        for (name, value) in record.iter() {
+            let field = fields
+                .iter()
+                .find(|field| field.name == *name)
+                .ok_or_else(|| ShellError::GenericError {
+                    error: "Unknown field".into(),
+                    msg: format!("{message} has no field '{name}'"),
+                    span: Some(value.span()),
+                    help: Some(format!(
+                        "The fields of {message} are: {}",
+                        fields
+                            .iter()
+                            .map(|field| field.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                    inner: vec![],
+                })?;
+            if !value.is_nothing() {
+                self.encode_field(field, value, buf)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_field(
+        &self,
+        field: &Field,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        match (&field.kind, field.label) {
+            (Kind::Map(key_kind, value_kind), _) => {
+                let span = value.span();
+                for (key, value) in value.as_record()?.iter() {
+                    let key = match key_kind.as_ref() {
+                        Kind::String => Some(Value::string(key, span)),
+                        Kind::Bool => key.parse().ok().map(|key| Value::bool(key, span)),
+                        _ => key.parse().ok().map(|key| Value::int(key, span)),
+                    }
+                    .ok_or_else(|| ShellError::IncorrectValue {
+                        msg: format!("'{key}' is not a valid key for {}", field.name),
+                        val_span: span,
+                        call_span: self.head,
+                    })?;
+
+                    let mut entry = Vec::new();
+                    self.encode_value(key_kind, 1, &key, &mut entry)?;
+                    if !value.is_nothing() {
+                        self.encode_value(value_kind, 2, value, &mut entry)?;
+                    }
+                    write_tag(buf, field.number, LEN);
+                    write_len_delimited(buf, &entry);
+                }
+            }
+            (kind, Label::Repeated) if wire_type(kind) != LEN => {
+                let mut packed = Vec::new();
+                for item in value.as_list()? {
+                    self.encode_payload(kind, item, &mut packed)?;
+                }
+                write_tag(buf, field.number, LEN);
+                write_len_delimited(buf, &packed);
+            }
+            (kind, Label::Repeated) => {
+                for item in value.as_list()? {
+                    self.encode_value(kind, field.number, item, buf)?;
+                }
+            }
+            (kind, _) => self.encode_value(kind, field.number, value, buf)?,
+        }
+        Ok(())
+    }
+
+    fn encode_value(
+        &self,
+        kind: &Kind,
+        number: u32,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        write_tag(buf, number, wire_type(kind));
+        self.encode_payload(kind, value, buf)
+    }
+
+    /// Encodes a single value without its tag.
+    fn encode_payload(
+        &self,
+        kind: &Kind,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        match kind {
+            Kind::Double => buf.extend_from_slice(&value.coerce_float()?.to_le_bytes()),
+            Kind::Float => buf.extend_from_slice(&(value.coerce_float()? as f32).to_le_bytes()),
+            // negative numbers are sign extended to 64 bits, even for int32
+            Kind::Int32 => write_varint(
+                buf,
+                self.int_in(value, "int32", i32::MIN.into(), i32::MAX.into())? as u64,
+            ),
+            Kind::Int64 => write_varint(buf, value.as_int()? as u64),
+            Kind::Uint32 => write_varint(
+                buf,
+                self.int_in(value, "uint32", 0, u32::MAX.into())? as u64,
+            ),
+            Kind::Uint64 => write_varint(buf, self.int_in(value, "uint64", 0, i64::MAX)? as u64),
+            Kind::Sint32 => write_varint(
+                buf,
+                zigzag(self.int_in(value, "sint32", i32::MIN.into(), i32::MAX.into())?),
+            ),
+            Kind::Sint64 => write_varint(buf, zigzag(value.as_int()?)),
+            Kind::Fixed32 => {
+                let int = self.int_in(value, "fixed32", 0, u32::MAX.into())? as u32;
+                buf.extend_from_slice(&int.to_le_bytes());
+            }
+            Kind::Fixed64 => {
+                let int = self.int_in(value, "fixed64", 0, i64::MAX)? as u64;
+                buf.extend_from_slice(&int.to_le_bytes());
+            }
+            Kind::Sfixed32 => {
+                let int = self.int_in(value, "sfixed32", i32::MIN.into(), i32::MAX.into())? as i32;
+                buf.extend_from_slice(&int.to_le_bytes());
+            }
+            Kind::Sfixed64 => buf.extend_from_slice(&value.as_int()?.to_le_bytes()),
+            Kind::Bool => write_varint(buf, value.as_bool()?.into()),
+            Kind::String => write_len_delimited(buf, value.as_str()?.as_bytes()),
+            Kind::Bytes => match value {
+                Value::Binary { val, .. } => write_len_delimited(buf, val),
+                Value::String { val, .. } => write_len_delimited(buf, val.as_bytes()),
+                value => return Err(self.cant_convert("bytes", value)),
+            },
+            Kind::Enum(name) => {
+                let number = match value {
+                    Value::String { val, .. } => self
+                        .enum_values(name)?
+                        .iter()
+                        .find(|(value, _)| value == val)
+                        .map(|(_, number)| *number as i64)
+                        .ok_or_else(|| ShellError::GenericError {
+                            error: "Unknown enum value".into(),
+                            msg: format!("{name} has no value '{val}'"),
+                            span: Some(value.span()),
+                            help: self.enum_values(name).ok().map(|values| {
+                                format!(
+                                    "The values of {name} are: {}",
+                                    values
+                                        .iter()
+                                        .map(|(value, _)| value.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                )
+                            }),
+                            inner: vec![],
+                        })?,
+                    value => self.int_in(value, name, i32::MIN.into(), i32::MAX.into())?,
+                };
+                write_varint(buf, number as u64);
+            }
+            Kind::Message(name) => {
+                let mut nested = Vec::new();
+                self.encode_message(name, value, &mut nested)?;
+                write_len_delimited(buf, &nested);
+            }
+            // the parser only allows maps as fields, which are handled before
+            Kind::Map(..) => {
+                return Err(ShellError::NushellFailed {
+                    msg: "map values can't be maps".into(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_value(
+        &self,
+        message: &str,
+        kind: &Kind,
+        value: WireValue,
+    ) -> Result<Value, ShellError> {
+        let head = self.head;
+        let value = match (kind, value) {
+            (Kind::Double, WireValue::I64(bytes)) => Value::float(f64::from_le_bytes(bytes), head),
+            (Kind::Float, WireValue::I32(bytes)) => {
+                Value::float(f32::from_le_bytes(bytes).into(), head)
+            }
+            // like generated code does, an int32 is cut down to its lower 32 bits
+            (Kind::Int32, WireValue::Varint(int)) => Value::int(int as i32 as i64, head),
+            (Kind::Int64, WireValue::Varint(int)) => Value::int(int as i64, head),
+            (Kind::Uint32, WireValue::Varint(int)) => Value::int(int as u32 as i64, head),
+            (Kind::Uint64, WireValue::Varint(int)) => self.uint64(int),
+            (Kind::Sint32, WireValue::Varint(int)) => Value::int(unzigzag(int) as i32 as i64, head),
+            (Kind::Sint64, WireValue::Varint(int)) => Value::int(unzigzag(int), head),
+            (Kind::Fixed32, WireValue::I32(bytes)) => {
+                Value::int(u32::from_le_bytes(bytes).into(), head)
+            }
+            (Kind::Fixed64, WireValue::I64(bytes)) => self.uint64(u64::from_le_bytes(bytes)),
+            (Kind::Sfixed32, WireValue::I32(bytes)) => {
+                Value::int(i32::from_le_bytes(bytes).into(), head)
+            }
+            (Kind::Sfixed64, WireValue::I64(bytes)) => Value::int(i64::from_le_bytes(bytes), head),
+            (Kind::Bool, WireValue::Varint(int)) => Value::bool(int != 0, head),
+            (Kind::String, WireValue::Len(bytes)) => match String::from_utf8(bytes.to_vec()) {
+                Ok(string) => Value::string(string, head),
+                Err(_) => return Err(self.malformed(message)),
+            },
+            (Kind::Bytes, WireValue::Len(bytes)) => Value::binary(bytes, head),
+            (Kind::Enum(name), WireValue::Varint(int)) => {
+                let number = int as i32;
+                // unknown values are kept as numbers, the server may know more values than we do
+                match self
+                    .enum_values(name)?
+                    .iter()
+                    .find(|(_, value)| *value == number)
+                {
+                    Some((value, _)) => Value::string(value, head),
+                    None => Value::int(number.into(), head),
+                }
+            }
+            (Kind::Message(name), WireValue::Len(bytes)) => self.decode(name, bytes)?,
+            _ => return Err(self.malformed(message)),
+        };
+        Ok(value)
+    }
+
+    fn decode_map_entry(
+        &self,
+        message: &str,
+        key_kind: &Kind,
+        value_kind: &Kind,
+        entry: WireValue,
+    ) -> Result<(String, Value), ShellError> {
+        let WireValue::Len(entry) = entry else {
+            return Err(self.malformed(message));
+        };
+        let fields = read_fields(entry).ok_or_else(|| self.malformed(message))?;
+        let field = |number| {
+            fields
+                .iter()
+                .rev()
+                .find(|(field, _)| *field == number)
+                .map(|(_, value)| *value)
+        };
+
+        let key = match field(1) {
+            Some(key) => self.decode_value(message, key_kind, key)?,
+            None => self.default_value(key_kind),
+        };
+        let value = match field(2) {
+            Some(value) => self.decode_value(message, value_kind, value)?,
+            None => self.default_value(value_kind),
+        };
+        Ok((key.coerce_into_string()?, value))
+    }
+
+    fn default_value(&self, kind: &Kind) -> Value {
+        let head = self.head;
+        match kind {
+            Kind::Double | Kind::Float => Value::float(0.0, head),
+            Kind::Bool => Value::bool(false, head),
+            Kind::String => Value::string("", head),
+            Kind::Bytes => Value::binary(vec![], head),
+            Kind::Enum(name) => match self
+                .enum_values(name)
+                .ok()
+                .and_then(|values| values.iter().find(|(_, number)| *number == 0))
+            {
+                Some((value, _)) => Value::string(value, head),
+                None => Value::int(0, head),
+            },
+            Kind::Message(_) | Kind::Map(..) => Value::nothing(head),
+            _ => Value::int(0, head),
+        }
+    }
+
+    /// Values above `i64::MAX` don't fit into an int, so they are kept exactly as strings.
+    fn uint64(&self, int: u64) -> Value {
+        match i64::try_from(int) {
+            Ok(int) => Value::int(int, self.head),
+            Err(_) => Value::string(int.to_string(), self.head),
+        }
+    }
+
+    fn int_in(
+        &self,
+        value: &Value,
+        type_name: &str,
+        min: i64,
+        max: i64,
+    ) -> Result<i64, ShellError> {
+        let int = value.as_int()?;
+        if (min..=max).contains(&int) {
+            Ok(int)
+        } else {
+            Err(ShellError::IncorrectValue {
+                msg: format!("{int} is out of range for {type_name}"),
+                val_span: value.span(),
+                call_span: self.head,
+            })
+        }
+    }
+
+    fn fields(&self, message: &str) -> Result<&'a [Field], ShellError> {
+        self.descriptors
+            .messages
+            .get(message)
+            .map(|message| message.fields.as_slice())
+            .ok_or_else(|| ShellError::NushellFailed {
+                msg: format!("message {message} was not resolved"),
+            })
+    }
+
+    fn enum_values(&self, name: &str) -> Result<&'a [(String, i32)], ShellError> {
+        self.descriptors
+            .enums
+            .get(name)
+            .map(|values| values.values.as_slice())
+            .ok_or_else(|| ShellError::NushellFailed {
+                msg: format!("enum {name} was not resolved"),
+            })
+    }
+
+    fn cant_convert(&self, to_type: &str, value: &Value) -> ShellError {
+        ShellError::CantConvert {
+            to_type: to_type.into(),
+            from_type: value.get_type().to_string(),
+            span: value.span(),
+            help: None,
+        }
+    }
+
+    fn malformed(&self, message: &str) -> ShellError {
+        ShellError::GenericError {
+            error: "Invalid protobuf message".into(),
+            msg: format!("could not decode the response as {message}"),
+            span: Some(self.head),
+            help: Some("Make sure the .proto file matches the one the server uses".into()),
+            inner: vec![],
+        }
+    }
+}
+
+fn wire_type(kind: &Kind) -> u32 {
+    match kind {
+        Kind::Double | Kind::Fixed64 | Kind::Sfixed64 => I64,
+        Kind::Float | Kind::Fixed32 | Kind::Sfixed32 => I32,
+        Kind::String | Kind::Bytes | Kind::Message(_) | Kind::Map(..) => LEN,
+        _ => VARINT,
+    }
+}
+
+fn zigzag(int: i64) -> u64 {
+    ((int << 1) ^ (int >> 63)) as u64
+}
+
+fn unzigzag(int: u64) -> i64 {
+    ((int >> 1) as i64) ^ -((int & 1) as i64)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut int: u64) {
+    while int >= 0x80 {
+        buf.push((int & 0x7f) as u8 | 0x80);
+        int >>= 7;
+    }
+    buf.push(int as u8);
+}
+
+fn write_tag(buf: &mut Vec<u8>, number: u32, wire_type: u32) {
+    write_varint(buf, ((number << 3) | wire_type).into());
+}
+
+fn write_len_delimited(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Splits a message into its fields, or returns `None` if it is malformed. Groups, which were
+/// deprecated long before proto3, are treated as malformed as well.
+fn read_fields(bytes: &[u8]) -> Option<Vec<(u32, WireValue<'_>)>> {
+    let mut reader = Reader::new(bytes);
+    let mut fields = Vec::new();
+    while !reader.is_empty() {
+        let tag = reader.varint()?;
+        let number = u32::try_from(tag >> 3).ok()?;
+        let value = reader.value((tag & 0x7) as u32)?;
+        fields.push((number, value));
+    }
+    Some(fields)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn value(&mut self, wire_type: u32) -> Option<WireValue<'a>> {
+        let value = match wire_type {
+            VARINT => WireValue::Varint(self.varint()?),
+            I64 => WireValue::I64(self.take(8)?.try_into().ok()?),
+            LEN => {
+                let len = usize::try_from(self.varint()?).ok()?;
+                WireValue::Len(self.take(len)?)
+            }
+            I32 => WireValue::I32(self.take(4)?.try_into().ok()?),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut int = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self.bytes.split_first()?;
+            self.bytes = rest;
+            int |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(int);
+            }
+        }
+        None
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.bytes.len() {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(taken)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nu_protocol::record;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package test;
+
+        message Test1 { int32 a = 1; }
+        message Test2 { string b = 2; }
+
+        message Order {
+          enum Status { PENDING = 0; SHIPPED = 1; }
+          message Item { string sku = 1; uint32 quantity = 2; }
+
+          int64 id = 1;
+          repeated Item items = 2;
+          Status status = 3;
+          map<string, int32> stock = 4;
+          optional string note = 5;
+          repeated sint32 deltas = 6;
+          double price = 7;
+          bytes signature = 8;
+          int32 offset = 9;
+          Item gift = 10;
+          uint64 big = 11;
+        }
+    "#;
+
+    fn descriptors() -> Descriptors {
+        Descriptors::from_source(PROTO).expect("valid proto")
+    }
+
+    #[test]
+    fn test_encode_known_bytes() {
+        let descriptors = descriptors();
+        let codec = Codec::new(&descriptors, Span::test_data());
+
+        // the examples from the protobuf encoding guide
+        let value = Value::test_record(record! { "a" => Value::test_int(150) });
+        let encoded = codec.encode("test.Test1", &value).expect("valid record");
+        assert_eq!(encoded, [0x08, 0x96, 0x01]);
+        assert_eq!(codec.decode("test.Test1", &encoded).ok(), Some(value));
+
+        let value = Value::test_record(record! { "b" => Value::test_string("testing") });
+        let encoded = codec.encode("test.Test2", &value).expect("valid record");
+        assert_eq!(encoded, b"\x12\x07testing");
+        assert_eq!(codec.decode("test.Test2", &encoded).ok(), Some(value));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let descriptors = descriptors();
+        let codec = Codec::new(&descriptors, Span::test_data());
+
+        let item = |sku: &str, quantity| {
+            Value::test_record(record! {
+                "sku" => Value::test_string(sku),
+                "quantity" => Value::test_int(quantity),
+            })
+        };
+        let value = Value::test_record(record! {
+            "id" => Value::test_int(-7),
+            "items" => Value::test_list(vec![item("apple", 3), item("pear", 0)]),
+            "status" => Value::test_string("SHIPPED"),
+            "stock" => Value::test_record(record! { "apple" => Value::test_int(12) }),
+            "deltas" => Value::test_list(vec![Value::test_int(-1), Value::test_int(2)]),
+            "price" => Value::test_int(3),
+            "signature" => Value::test_binary(b"\x00\xff"),
+            "offset" => Value::test_int(-2),
+            "big" => Value::test_nothing(),
+        });
+        let encoded = codec.encode("test.Order", &value).expect("valid record");
+
+        assert_eq!(
+            codec.decode("test.Order", &encoded).ok(),
+            Some(Value::test_record(record! {
+                "id" => Value::test_int(-7),
+                "items" => Value::test_list(vec![item("apple", 3), item("pear", 0)]),
+                "status" => Value::test_string("SHIPPED"),
+                "stock" => Value::test_record(record! { "apple" => Value::test_int(12) }),
+                "note" => Value::test_nothing(),
+                "deltas" => Value::test_list(vec![Value::test_int(-1), Value::test_int(2)]),
+                "price" => Value::test_float(3.0),
+                "signature" => Value::test_binary(b"\x00\xff"),
+                "offset" => Value::test_int(-2),
+                "gift" => Value::test_nothing(),
+                "big" => Value::test_int(0),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_decode_details() {
+        let descriptors = descriptors();
+        let codec = Codec::new(&descriptors, Span::test_data());
+        let decode = |bytes: &[u8], column: &str| {
+            codec
+                .decode("test.Order", bytes)
+                .ok()
+                .and_then(|record| record.get_data_by_key(column))
+        };
+
+        // unpacked repeated fields are read just as well
+        assert_eq!(
+            decode(&[0x30, 0x01, 0x30, 0x04], "deltas"),
+            Some(Value::test_list(vec![
+                Value::test_int(-1),
+                Value::test_int(2)
+            ]))
+        );
+        // unknown enum values are kept as numbers
+        assert_eq!(decode(&[0x18, 0x05], "status"), Some(Value::test_int(5)));
+        // uint64 above i64::MAX
+        let mut bytes = vec![0x58];
+        write_varint(&mut bytes, u64::MAX);
+        assert_eq!(
+            decode(&bytes, "big"),
+            Some(Value::test_string(u64::MAX.to_string()))
+        );
+        // the parts of a message sent more than once are merged
+        assert_eq!(
+            decode(b"\x52\x03\x0a\x01a\x52\x02\x10\x02", "gift"),
+            Some(Value::test_record(record! {
+                "sku" => Value::test_string("a"),
+                "quantity" => Value::test_int(2),
+            }))
+        );
+        // a length beyond the end of the message
+        assert!(codec.decode("test.Order", &[0x42, 0x05, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let descriptors = descriptors();
+        let codec = Codec::new(&descriptors, Span::test_data());
+        let encode = |column: &str, value| {
+            codec.encode(
+                "test.Order",
+                &Value::test_record(Record::from_iter([(column.to_string(), value)])),
+            )
+        };
+
+        assert!(encode("unknown", Value::test_int(1)).is_err());
+        assert!(encode("offset", Value::test_int(i64::from(i32::MAX) + 1)).is_err());
+        assert!(encode("status", Value::test_string("LOST")).is_err());
+        assert!(encode("status", Value::test_int(1)).is_ok());
+        assert!(encode("items", Value::test_int(1)).is_err());
+        assert!(encode("note", Value::test_bool(true)).is_err());
+    }
+}
@@ -0,0 +1,763 @@
+//! Header compression for HTTP/2 ([RFC 7541]), as far as the gRPC client needs it.
+//!
+//! Headers are always sent as literals without Huffman coding, which every server has to
+//! understand, so only the decoder has to deal with the dynamic table and Huffman codes.
+//!
+//! [RFC 7541]: https://www.rfc-editor.org/rfc/rfc7541
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::OnceLock,
+};
+
+/// The default size of the dynamic table, which we never change.
+const DEFAULT_TABLE_SIZE: usize = 4096;
+/// Every entry of the dynamic table counts this much on top of its name and value.
+const ENTRY_OVERHEAD: usize = 32;
+
+/// Encodes `headers` as literals that are not added to the dynamic table.
+pub fn encode(headers: &[(&str, &str)]) -> Vec<u8> {
+    let mut block = Vec::new();
+    for (name, value) in headers {
+        // literal header field without indexing, with a new name
+        block.push(0);
+        encode_string(&mut block, name);
+        encode_string(&mut block, value);
+    }
+    block
+}
+
+fn encode_string(block: &mut Vec<u8>, string: &str) {
+    encode_int(block, string.len(), 7, 0);
+    block.extend_from_slice(string.as_bytes());
+}
+
+fn encode_int(block: &mut Vec<u8>, mut value: usize, prefix_bits: u32, flags: u8) {
+    let max_prefix = (1 << prefix_bits) - 1;
+    if value < max_prefix {
+        block.push(flags | value as u8);
+        return;
+    }
+    block.push(flags | max_prefix as u8);
+    value -= max_prefix;
+    while value >= 0x80 {
+        block.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    block.push(value as u8);
+}
+
+/// Decodes header blocks, keeping the dynamic table between them.
+pub struct Decoder {
+    table: VecDeque<(String, String)>,
+    size: usize,
+    max_size: usize,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder {
+            table: VecDeque::new(),
+            size: 0,
+            max_size: DEFAULT_TABLE_SIZE,
+        }
+    }
+}
+
+impl Decoder {
+    /// Decodes a complete header block, returning `None` if it is malformed.
+    pub fn decode(&mut self, block: &[u8]) -> Option<Vec<(String, String)>> {
+        let mut headers = Vec::new();
+        let mut pos = 0;
+        while let Some(&first) = block.get(pos) {
+            if first & 0x80 != 0 {
+                // indexed header field
+                let idx = decode_int(block, &mut pos, 7)?;
+                headers.push(self.get(idx)?);
+            } else if first & 0xc0 == 0x40 {
+                // literal header field with incremental indexing
+                let header = self.decode_literal(block, &mut pos, 6)?;
+                self.insert(header.clone());
+                headers.push(header);
+            } else if first & 0xe0 == 0x20 {
+                // dynamic table size update
+                let size = decode_int(block, &mut pos, 5)?;
+                if size > DEFAULT_TABLE_SIZE {
+                    return None;
+                }
+                self.max_size = size;
+                self.evict();
+            } else {
+                // literal header field without indexing or never indexed
+                headers.push(self.decode_literal(block, &mut pos, 4)?);
+            }
+        }
+        Some(headers)
+    }
+
+    fn decode_literal(
+        &self,
+        block: &[u8],
+        pos: &mut usize,
+        prefix_bits: u32,
+    ) -> Option<(String, String)> {
+        let name = match decode_int(block, pos, prefix_bits)? {
+            0 => decode_string(block, pos)?,
+            idx => self.get(idx)?.0,
+        };
+        let value = decode_string(block, pos)?;
+        Some((name, value))
+    }
+
+    fn get(&self, idx: usize) -> Option<(String, String)> {
+        match idx {
+            0 => None,
+            idx if idx <= STATIC_TABLE.len() => {
+                let (name, value) = STATIC_TABLE[idx - 1];
+                Some((name.into(), value.into()))
+            }
+            idx => self.table.get(idx - STATIC_TABLE.len() - 1).cloned(),
+        }
+    }
+
+    fn insert(&mut self, header: (String, String)) {
+        self.size += entry_size(&header);
+        self.table.push_front(header);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.size > self.max_size {
+            match self.table.pop_back() {
+                Some(header) => self.size -= entry_size(&header),
+                None => self.size = 0,
+            }
+        }
+    }
+}
+
+fn entry_size((name, value): &(String, String)) -> usize {
+    name.len() + value.len() + ENTRY_OVERHEAD
+}
+
+fn decode_int(block: &[u8], pos: &mut usize, prefix_bits: u32) -> Option<usize> {
+    let max_prefix = (1 << prefix_bits) - 1;
+    let mut value = (*block.get(*pos)? & max_prefix as u8) as usize;
+    *pos += 1;
+    if value < max_prefix {
+        return Some(value);
+    }
+
+    let mut shift = 0;
+    loop {
+        let byte = *block.get(*pos)?;
+        *pos += 1;
+        // nothing we receive comes anywhere close to this, so it's safe to refuse
+        if shift > 28 {
+            return None;
+        }
+        value += ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+}
+
+fn decode_string(block: &[u8], pos: &mut usize) -> Option<String> {
+    let huffman = *block.get(*pos)? & 0x80 != 0;
+    let len = decode_int(block, pos, 7)?;
+    let bytes = block.get(*pos..*pos + len)?;
+    *pos += len;
+
+    let bytes = match huffman {
+        true => decode_huffman(bytes)?,
+        false => bytes.to_vec(),
+    };
+    String::from_utf8(bytes).ok()
+}
+
+fn decode_huffman(bytes: &[u8]) -> Option<Vec<u8>> {
+    static CODES: OnceLock<HashMap<(u32, u8), u16>> = OnceLock::new();
+    let codes = CODES.get_or_init(|| {
+        (0..)
+            .zip(HUFFMAN_CODES)
+            .map(|(symbol, code)| (code, symbol))
+            .collect()
+    });
+
+    let mut decoded = Vec::with_capacity(bytes.len() * 8 / 5);
+    let (mut code, mut len) = (0u32, 0u8);
+    for byte in bytes {
+        for bit in (0..8).rev() {
+            code = (code << 1) | ((byte >> bit) & 1) as u32;
+            len += 1;
+            match codes.get(&(code, len)) {
+                // the end of string symbol must not appear in the data
+                Some(256) => return None,
+                Some(&symbol) => {
+                    decoded.push(symbol as u8);
+                    (code, len) = (0, 0);
+                }
+                None if len >= 30 => return None,
+                None => {}
+            }
+        }
+    }
+
+    // the padding is the start of the end of string symbol, so at most 7 bits of ones
+    (len < 8 && code == (1 << len) - 1).then_some(decoded)
+}
+
+const STATIC_TABLE: [(&str, &str); 61] = [
+    (":authority", ""),
+    (":method", "GET"),
+    (":method", "POST"),
+    (":path", "/"),
+    (":path", "/index.html"),
+    (":scheme", "http"),
+    (":scheme", "https"),
+    (":status", "200"),
+    (":status", "204"),
+    (":status", "206"),
+    (":status", "304"),
+    (":status", "400"),
+    (":status", "404"),
+    (":status", "500"),
+    ("accept-charset", ""),
+    ("accept-encoding", "gzip, deflate"),
+    ("accept-language", ""),
+    ("accept-ranges", ""),
+    ("accept", ""),
+    ("access-control-allow-origin", ""),
+    ("age", ""),
+    ("allow", ""),
+    ("authorization", ""),
+    ("cache-control", ""),
+    ("content-disposition", ""),
+    ("content-encoding", ""),
+    ("content-language", ""),
+    ("content-length", ""),
+    ("content-location", ""),
+    ("content-range", ""),
+    ("content-type", ""),
+    ("cookie", ""),
+    ("date", ""),
+    ("etag", ""),
+    ("expect", ""),
+    ("expires", ""),
+    ("from", ""),
+    ("host", ""),
+    ("if-match", ""),
+    ("if-modified-since", ""),
+    ("if-none-match", ""),
+    ("if-range", ""),
+    ("if-unmodified-since", ""),
+    ("last-modified", ""),
+    ("link", ""),
+    ("location", ""),
+    ("max-forwards", ""),
+    ("proxy-authenticate", ""),
+    ("proxy-authorization", ""),
+    ("range", ""),
+    ("referer", ""),
+    ("refresh", ""),
+    ("retry-after", ""),
+    ("server", ""),
+    ("set-cookie", ""),
+    ("strict-transport-security", ""),
+    ("transfer-encoding", ""),
+    ("user-agent", ""),
+    ("vary", ""),
+    ("via", ""),
+    ("www-authenticate", ""),
+];
+
+/// The code and its length in bits for every byte and the end of string symbol.
+const HUFFMAN_CODES: [(u32, u8); 257] = [
+    (0x1ff8, 13),
+    (0x7fffd8, 23),
+    (0xfffffe2, 28),
+    (0xfffffe3, 28),
+    (0xfffffe4, 28),
+    (0xfffffe5, 28),
+    (0xfffffe6, 28),
+    (0xfffffe7, 28),
+    (0xfffffe8, 28),
+    (0xffffea, 24),
+    (0x3ffffffc, 30),
+    (0xfffffe9, 28),
+    (0xfffffea, 28),
+    (0x3ffffffd, 30),
+    (0xfffffeb, 28),
+    (0xfffffec, 28),
+    (0xfffffed, 28),
+    (0xfffffee, 28),
+    (0xfffffef, 28),
+    (0xffffff0, 28),
+    (0xffffff1, 28),
+    (0xffffff2, 28),
+    (0x3ffffffe, 30),
+    (0xffffff3, 28),
+    (0xffffff4, 28),
+    (0xffffff5, 28),
+    (0xffffff6, 28),
+    (0xffffff7, 28),
+    (0xffffff8, 28),
+    (0xffffff9, 28),
+    (0xffffffa, 28),
+    (0xffffffb, 28),
+    (0x14, 6),
+    (0x3f8, 10),
+    (0x3f9, 10),
+    (0xffa, 12),
+    (0x1ff9, 13),
+    (0x15, 6),
+    (0xf8, 8),
+    (0x7fa, 11),
+    (0x3fa, 10),
+    (0x3fb, 10),
+    (0xf9, 8),
+    (0x7fb, 11),
+    (0xfa, 8),
+    (0x16, 6),
+    (0x17, 6),
+    (0x18, 6),
+    (0x0, 5),
+    (0x1, 5),
+    (0x2, 5),
+    (0x19, 6),
+    (0x1a, 6),
+    (0x1b, 6),
+    (0x1c, 6),
+    (0x1d, 6),
+    (0x1e, 6),
+    (0x1f, 6),
+    (0x5c, 7),
+    (0xfb, 8),
+    (0x7ffc, 15),
+    (0x20, 6),
+    (0xffb, 12),
+    (0x3fc, 10),
+    (0x1ffa, 13),
+    (0x21, 6),
+    (0x5d, 7),
+    (0x5e, 7),
+    (0x5f, 7),
+    (0x60, 7),
+    (0x61, 7),
+    (0x62, 7),
+    (0x63, 7),
+    (0x64, 7),
+    (0x65, 7),
+    (0x66, 7),
+    (0x67, 7),
+    (0x68, 7),
+    (0x69, 7),
+    (0x6a, 7),
+    (0x6b, 7),
+    (0x6c, 7),
+    (0x6d, 7),
+    (0x6e, 7),
+    (0x6f, 7),
+    (0x70, 7),
+    (0x71, 7),
+    (0x72, 7),
+    (0xfc, 8),
+    (0x73, 7),
+    (0xfd, 8),
+    (0x1ffb, 13),
+    (0x7fff0, 19),
+    (0x1ffc, 13),
+    (0x3ffc, 14),
+    (0x22, 6),
+    (0x7ffd, 15),
+    (0x3, 5),
+    (0x23, 6),
+    (0x4, 5),
+    (0x24, 6),
+    (0x5, 5),
+    (0x25, 6),
+    (0x26, 6),
+    (0x27, 6),
+    (0x6, 5),
+    (0x74, 7),
+    (0x75, 7),
+    (0x28, 6),
+    (0x29, 6),
+    (0x2a, 6),
+    (0x7, 5),
+    (0x2b, 6),
+    (0x76, 7),
+    (0x2c, 6),
+    (0x8, 5),
+    (0x9, 5),
+    (0x2d, 6),
+    (0x77, 7),
+    (0x78, 7),
+    (0x79, 7),
+    (0x7a, 7),
+    (0x7b, 7),
+    (0x7ffe, 15),
+    (0x7fc, 11),
+    (0x3ffd, 14),
+    (0x1ffd, 13),
+    (0xffffffc, 28),
+    (0xfffe6, 20),
+    (0x3fffd2, 22),
+    (0xfffe7, 20),
+    (0xfffe8, 20),
+    (0x3fffd3, 22),
+    (0x3fffd4, 22),
+    (0x3fffd5, 22),
+    (0x7fffd9, 23),
+    (0x3fffd6, 22),
+    (0x7fffda, 23),
+    (0x7fffdb, 23),
+    (0x7fffdc, 23),
+    (0x7fffdd, 23),
+    (0x7fffde, 23),
+    (0xffffeb, 24),
+    (0x7fffdf, 23),
+    (0xffffec, 24),
+    (0xffffed, 24),
+    (0x3fffd7, 22),
+    (0x7fffe0, 23),
+    (0xffffee, 24),
+    (0x7fffe1, 23),
+    (0x7fffe2, 23),
+    (0x7fffe3, 23),
+    (0x7fffe4, 23),
+    (0x1fffdc, 21),
+    (0x3fffd8, 22),
+    (0x7fffe5, 23),
+    (0x3fffd9, 22),
+    (0x7fffe6, 23),
+    (0x7fffe7, 23),
+    (0xffffef, 24),
+    (0x3fffda, 22),
+    (0x1fffdd, 21),
+    (0xfffe9, 20),
+    (0x3fffdb, 22),
+    (0x3fffdc, 22),
+    (0x7fffe8, 23),
+    (0x7fffe9, 23),
+    (0x1fffde, 21),
+    (0x7fffea, 23),
+    (0x3fffdd, 22),
+    (0x3fffde, 22),
+    (0xfffff0, 24),
+    (0x1fffdf, 21),
+    (0x3fffdf, 22),
+    (0x7fffeb, 23),
+    (0x7fffec, 23),
+    (0x1fffe0, 21),
+    (0x1fffe1, 21),
+    (0x3fffe0, 22),
+    (0x1fffe2, 21),
+    (0x7fffed, 23),
+    (0x3fffe1, 22),
+    (0x7fffee, 23),
+    (0x7fffef, 23),
+    (0xfffea, 20),
+    (0x3fffe2, 22),
+    (0x3fffe3, 22),
+    (0x3fffe4, 22),
+    (0x7ffff0, 23),
+    (0x3fffe5, 22),
+    (0x3fffe6, 22),
+    (0x7ffff1, 23),
+    (0x3ffffe0, 26),
+    (0x3ffffe1, 26),
+    (0xfffeb, 20),
+    (0x7fff1, 19),
+    (0x3fffe7, 22),
+    (0x7ffff2, 23),
+    (0x3fffe8, 22),
+    (0x1ffffec, 25),
+    (0x3ffffe2, 26),
+    (0x3ffffe3, 26),
+    (0x3ffffe4, 26),
+    (0x7ffffde, 27),
+    (0x7ffffdf, 27),
+    (0x3ffffe5, 26),
+    (0xfffff1, 24),
+    (0x1ffffed, 25),
+    (0x7fff2, 19),
+    (0x1fffe3, 21),
+    (0x3ffffe6, 26),
+    (0x7ffffe0, 27),
+    (0x7ffffe1, 27),
+    (0x3ffffe7, 26),
+    (0x7ffffe2, 27),
+    (0xfffff2, 24),
+    (0x1fffe4, 21),
+    (0x1fffe5, 21),
+    (0x3ffffe8, 26),
+    (0x3ffffe9, 26),
+    (0xffffffd, 28),
+    (0x7ffffe3, 27),
+    (0x7ffffe4, 27),
+    (0x7ffffe5, 27),
+    (0xfffec, 20),
+    (0xfffff3, 24),
+    (0xfffed, 20),
+    (0x1fffe6, 21),
+    (0x3fffe9, 22),
+    (0x1fffe7, 21),
+    (0x1fffe8, 21),
+    (0x7ffff3, 23),
+    (0x3fffea, 22),
+    (0x3fffeb, 22),
+    (0x1ffffee, 25),
+    (0x1ffffef, 25),
+    (0xfffff4, 24),
+    (0xfffff5, 24),
+    (0x3ffffea, 26),
+    (0x7ffff4, 23),
+    (0x3ffffeb, 26),
+    (0x7ffffe6, 27),
+    (0x3ffffec, 26),
+    (0x3ffffed, 26),
+    (0x7ffffe7, 27),
+    (0x7ffffe8, 27),
+    (0x7ffffe9, 27),
+    (0x7ffffea, 27),
+    (0x7ffffeb, 27),
+    (0xffffffe, 28),
+    (0x7ffffec, 27),
+    (0x7ffffed, 27),
+    (0x7ffffee, 27),
+    (0x7ffffef, 27),
+    (0x7fffff0, 27),
+    (0x3ffffee, 26),
+    (0x3fffffff, 30),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn headers(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_rfc_examples() {
+        let mut decoder = Decoder::default();
+
+        // RFC 7541, C.3.1
+        let block = b"\x82\x86\x84\x41\x0fwww.example.com";
+        assert_eq!(
+            decoder.decode(block),
+            Some(headers(&[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+            ]))
+        );
+
+        // RFC 7541, C.4.2, which refers to the entry added by the first request
+        let block = b"\x82\x86\x84\xbe\x58\x86\xa8\xeb\x10\x64\x9c\xbf";
+        assert_eq!(
+            decoder.decode(block),
+            Some(headers(&[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+                ("cache-control", "no-cache"),
+            ]))
+        );
+        assert_eq!(decoder.table.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_huffman() {
+        // RFC 7541, C.4.1
+        let mut decoder = Decoder::default();
+        let block = b"\x82\x86\x84\x41\x8c\xf1\xe3\xc2\xe5\xf2\x3a\x6b\xa0\xab\x90\xf4\xff";
+        assert_eq!(
+            decoder.decode(block),
+            Some(headers(&[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+            ]))
+        );
+
+        // the padding has to be made of ones
+        assert_eq!(decode_huffman(&[0x07]), Some(b"0".to_vec()));
+        assert_eq!(decode_huffman(&[0x00]), None);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let long = "x".repeat(300);
+        let sent = [
+            (":path", "/helloworld.Greeter/SayHello"),
+            ("content-type", "application/grpc"),
+            ("authorization", long.as_str()),
+        ];
+        assert_eq!(
+            Decoder::default().decode(&encode(&sent)),
+            Some(headers(&sent))
+        );
+    }
+
+    #[test]
+    fn test_table_eviction() {
+        let mut decoder = Decoder::default();
+        // a table size update to 0 removes everything
+        decoder.decode(b"\x41\x0fwww.example.com");
+        assert_eq!(decoder.table.len(), 1);
+        decoder.decode(b"\x20");
+        assert_eq!(decoder.table.len(), 0);
+        assert_eq!(decoder.decode(b"\xbe"), None);
+    }
+
+    #[test]
+    fn test_int_rfc_examples() {
+        // RFC 7541, C.1.1 to C.1.3
+        for (value, prefix_bits, encoded) in [
+            (10, 5, &b"\x0a"[..]),
+            (1337, 5, &b"\x1f\x9a\x0a"[..]),
+            (42, 8, &b"\x2a"[..]),
+        ] {
+            let mut block = Vec::new();
+            encode_int(&mut block, value, prefix_bits, 0);
+            assert_eq!(block, encoded);
+
+            let mut pos = 0;
+            assert_eq!(decode_int(encoded, &mut pos, prefix_bits), Some(value));
+            assert_eq!(pos, encoded.len());
+        }
+    }
+
+    #[test]
+    fn test_decode_rfc_literals() {
+        let mut decoder = Decoder::default();
+
+        // RFC 7541, C.2.2: without indexing
+        assert_eq!(
+            decoder.decode(b"\x04\x0c/sample/path"),
+            Some(headers(&[(":path", "/sample/path")]))
+        );
+        // RFC 7541, C.2.3: never indexed
+        assert_eq!(
+            decoder.decode(b"\x10\x08password\x06secret"),
+            Some(headers(&[("password", "secret")]))
+        );
+        // RFC 7541, C.2.4: indexed
+        assert_eq!(
+            decoder.decode(b"\x82"),
+            Some(headers(&[(":method", "GET")]))
+        );
+        assert!(decoder.table.is_empty());
+    }
+
+    #[test]
+    fn test_decode_rfc_requests() {
+        let mut decoder = Decoder::default();
+
+        // RFC 7541, C.3.1 to C.3.3
+        decoder.decode(b"\x82\x86\x84\x41\x0fwww.example.com");
+        decoder.decode(b"\x82\x86\x84\xbe\x58\x08no-cache");
+        assert_eq!(
+            decoder.decode(b"\x82\x87\x85\xbf\x40\x0acustom-key\x0ccustom-value"),
+            Some(headers(&[
+                (":method", "GET"),
+                (":scheme", "https"),
+                (":path", "/index.html"),
+                (":authority", "www.example.com"),
+                ("custom-key", "custom-value"),
+            ]))
+        );
+        assert_eq!(
+            decoder.table,
+            headers(&[
+                ("custom-key", "custom-value"),
+                ("cache-control", "no-cache"),
+                (":authority", "www.example.com"),
+            ])
+        );
+        assert_eq!(decoder.size, 164);
+    }
+
+    #[test]
+    fn test_decode_rfc_responses_with_eviction() {
+        // RFC 7541, C.5.1 to C.5.3, with the table limited to 256 bytes by a size update first
+        let mut decoder = Decoder::default();
+        let date = "Mon, 21 Oct 2013 20:13:21 GMT";
+        let location = "https://www.example.com";
+
+        let block = b"\x3f\xe1\x01\x48\x03302\x58\x07private\
+            \x61\x1dMon, 21 Oct 2013 20:13:21 GMT\x6e\x17https://www.example.com";
+        assert_eq!(
+            decoder.decode(block),
+            Some(headers(&[
+                (":status", "302"),
+                ("cache-control", "private"),
+                ("date", date),
+                ("location", location),
+            ]))
+        );
+        assert_eq!(decoder.size, 222);
+
+        assert_eq!(
+            decoder.decode(b"\x48\x03307\xc1\xc0\xbf"),
+            Some(headers(&[
+                (":status", "307"),
+                ("cache-control", "private"),
+                ("date", date),
+                ("location", location),
+            ]))
+        );
+        assert_eq!(decoder.size, 222);
+
+        let block = b"\x88\xc1\x61\x1dMon, 21 Oct 2013 20:13:22 GMT\xc0\x5a\x04gzip\
+            \x77\x38foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1";
+        assert_eq!(
+            decoder.decode(block),
+            Some(headers(&[
+                (":status", "200"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+                ("location", location),
+                ("content-encoding", "gzip"),
+                (
+                    "set-cookie",
+                    "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"
+                ),
+            ]))
+        );
+        assert_eq!(
+            decoder.table,
+            headers(&[
+                (
+                    "set-cookie",
+                    "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"
+                ),
+                ("content-encoding", "gzip"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+            ])
+        );
+        assert_eq!(decoder.size, 215);
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        let mut decoder = Decoder::default();
+        // a string longer than the block
+        assert_eq!(decoder.decode(b"\x04\x0c/sample"), None);
+        // an index past the end of both tables
+        assert_eq!(decoder.decode(b"\xff\x00"), None);
+        // a table size larger than we allow
+        assert_eq!(decoder.decode(b"\x3f\xe2\x1f"), None);
+    }
+}
@@ -0,0 +1,622 @@
+//! A minimal HTTP/2 client ([RFC 9113]) that makes a single request per connection.
+//!
+//! That is all a gRPC call needs, so there is no stream multiplexing or prioritization. Received
+//! data is acknowledged right away, so the server never stalls on flow control.
+//!
+//! [RFC 9113]: https://www.rfc-editor.org/rfc/rfc9113
+
+use super::hpack;
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+};
+
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+/// The only stream we open.
+const STREAM_ID: u32 = 1;
+const DEFAULT_WINDOW_SIZE: i64 = 65535;
+/// The largest frame either side may send until told otherwise, which we never do.
+const DEFAULT_MAX_FRAME_SIZE: usize = 16384;
+/// The largest frame size a server may allow.
+const MAX_MAX_FRAME_SIZE: u32 = 16_777_215;
+/// The largest flow control window.
+const MAX_WINDOW_SIZE: u32 = 0x7fff_ffff;
+
+const DATA: u8 = 0x0;
+const HEADERS: u8 = 0x1;
+const RST_STREAM: u8 = 0x3;
+const SETTINGS: u8 = 0x4;
+const PUSH_PROMISE: u8 = 0x5;
+const PING: u8 = 0x6;
+const GOAWAY: u8 = 0x7;
+const WINDOW_UPDATE: u8 = 0x8;
+const CONTINUATION: u8 = 0x9;
+
+const END_STREAM: u8 = 0x1;
+const ACK: u8 = 0x1;
+const END_HEADERS: u8 = 0x4;
+const PADDED: u8 = 0x8;
+const PRIORITY: u8 = 0x20;
+
+const SETTINGS_ENABLE_PUSH: u16 = 0x2;
+const SETTINGS_INITIAL_WINDOW_SIZE: u16 = 0x4;
+const SETTINGS_MAX_FRAME_SIZE: u16 = 0x5;
+
+const PROTOCOL_ERROR: u32 = 0x1;
+const FLOW_CONTROL_ERROR: u32 = 0x3;
+const FRAME_SIZE_ERROR: u32 = 0x6;
+
+/// Something the server sent on our stream.
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    /// The response headers or the trailers.
+    Headers {
+        headers: Vec<(String, String)>,
+        end_stream: bool,
+    },
+    Data {
+        data: Vec<u8>,
+        end_stream: bool,
+    },
+    /// The server cancelled the stream with this error code.
+    Reset(u32),
+}
+
+pub struct Connection<S> {
+    stream: S,
+    decoder: hpack::Decoder,
+    /// Events received while waiting for the server to let us send more data.
+    events: VecDeque<Event>,
+    send_window: i64,
+    stream_send_window: i64,
+    initial_window_size: i64,
+    max_frame_size: usize,
+}
+
+impl<S: Read + Write> Connection<S> {
+    /// Sends the connection preface. The server's settings are handled whenever they arrive.
+    pub fn handshake(mut stream: S) -> io::Result<Self> {
+        let mut preface = PREFACE.to_vec();
+        // we don't want pushed responses
+        write_frame(
+            &mut preface,
+            SETTINGS,
+            0,
+            0,
+            &[&SETTINGS_ENABLE_PUSH.to_be_bytes()[..], &0u32.to_be_bytes()].concat(),
+        );
+        stream.write_all(&preface)?;
+        stream.flush()?;
+
+        Ok(Connection {
+            stream,
+            decoder: hpack::Decoder::default(),
+            events: VecDeque::new(),
+            send_window: DEFAULT_WINDOW_SIZE,
+            stream_send_window: DEFAULT_WINDOW_SIZE,
+            initial_window_size: DEFAULT_WINDOW_SIZE,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        })
+    }
+
+    /// Opens the stream by sending the request headers, pseudo headers first.
+    pub fn send_headers(&mut self, headers: &[(&str, &str)], end_stream: bool) -> io::Result<()> {
+        let block = hpack::encode(headers);
+        let mut chunks = block.chunks(self.max_frame_size).peekable();
+        let mut buf = Vec::new();
+        let mut kind = HEADERS;
+        let mut flags = if end_stream { END_STREAM } else { 0 };
+        // an empty block still needs a frame
+        if chunks.peek().is_none() {
+            write_frame(&mut buf, kind, flags | END_HEADERS, STREAM_ID, &[]);
+        }
+        while let Some(chunk) = chunks.next() {
+            if chunks.peek().is_none() {
+                flags |= END_HEADERS;
+            }
+            write_frame(&mut buf, kind, flags, STREAM_ID, chunk);
+            (kind, flags) = (CONTINUATION, 0);
+        }
+        self.stream.write_all(&buf)?;
+        self.stream.flush()
+    }
+
+    /// Sends `data` on the stream, waiting for the server to make room for it if needed.
+    pub fn send_data(&mut self, mut data: &[u8], end_stream: bool) -> io::Result<()> {
+        loop {
+            let window = self.send_window.min(self.stream_send_window).max(0) as usize;
+            if window == 0 && !data.is_empty() {
+                self.receive()?;
+                continue;
+            }
+
+            let len = data.len().min(window).min(self.max_frame_size);
+            let (chunk, rest) = data.split_at(len);
+            let last = rest.is_empty();
+            let flags = if last && end_stream { END_STREAM } else { 0 };
+            let mut buf = Vec::with_capacity(9 + len);
+            write_frame(&mut buf, DATA, flags, STREAM_ID, chunk);
+            self.stream.write_all(&buf)?;
+            self.send_window -= len as i64;
+            self.stream_send_window -= len as i64;
+            data = rest;
+
+            if last {
+                return self.stream.flush();
+            }
+        }
+    }
+
+    /// Waits for the next event on our stream.
+    pub fn next_event(&mut self) -> io::Result<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            self.receive()?;
+        }
+    }
+
+    /// Reads and handles a single frame, queueing any event on our stream.
+    fn receive(&mut self) -> io::Result<()> {
+        let (kind, flags, stream_id, payload) = self.read_frame()?;
+        match kind {
+            DATA if stream_id == STREAM_ID => {
+                // give back the whole frame, padding included, right away
+                let len = payload.len();
+                let data = strip_padding(flags, payload)?;
+                if len > 0 {
+                    let increment = (len as u32).to_be_bytes();
+                    let mut buf = Vec::new();
+                    write_frame(&mut buf, WINDOW_UPDATE, 0, 0, &increment);
+                    if flags & END_STREAM == 0 {
+                        write_frame(&mut buf, WINDOW_UPDATE, 0, STREAM_ID, &increment);
+                    }
+                    self.stream.write_all(&buf)?;
+                    self.stream.flush()?;
+                }
+                self.events.push_back(Event::Data {
+                    data,
+                    end_stream: flags & END_STREAM != 0,
+                });
+            }
+            HEADERS if stream_id == STREAM_ID => {
+                let mut block = strip_padding(flags, payload)?;
+                if flags & PRIORITY != 0 {
+                    block.drain(..5.min(block.len()));
+                }
+                let mut end_headers = flags & END_HEADERS != 0;
+                while !end_headers {
+                    let (kind, flags, stream_id, payload) = self.read_frame()?;
+                    if kind != CONTINUATION || stream_id != STREAM_ID {
+                        return self.connection_error(
+                            PROTOCOL_ERROR,
+                            "expected a CONTINUATION frame on the same stream",
+                        );
+                    }
+                    block.extend_from_slice(&payload);
+                    end_headers = flags & END_HEADERS != 0;
+                }
+                let headers = self
+                    .decoder
+                    .decode(&block)
+                    .ok_or_else(|| protocol_error("malformed header block"))?;
+                self.events.push_back(Event::Headers {
+                    headers,
+                    end_stream: flags & END_STREAM != 0,
+                });
+            }
+            RST_STREAM if stream_id == STREAM_ID => {
+                let code = read_u32(&payload).ok_or_else(|| protocol_error("bad RST_STREAM"))?;
+                self.events.push_back(Event::Reset(code));
+            }
+            SETTINGS if flags & ACK == 0 => {
+                if payload.len() % 6 != 0 {
+                    return self.connection_error(FRAME_SIZE_ERROR, "bad SETTINGS");
+                }
+                for setting in payload.chunks_exact(6) {
+                    let id = u16::from_be_bytes([setting[0], setting[1]]);
+                    let value =
+                        u32::from_be_bytes([setting[2], setting[3], setting[4], setting[5]]);
+                    match id {
+                        SETTINGS_INITIAL_WINDOW_SIZE => {
+                            if value > MAX_WINDOW_SIZE {
+                                return self.connection_error(
+                                    FLOW_CONTROL_ERROR,
+                                    "initial window size is too large",
+                                );
+                            }
+                            // this changes the window of streams that are open already
+                            self.stream_send_window += value as i64 - self.initial_window_size;
+                            self.initial_window_size = value as i64;
+                        }
+                        SETTINGS_MAX_FRAME_SIZE => {
+                            // RFC 9113, 6.5.2: anything outside of this range is an error
+                            if !(DEFAULT_MAX_FRAME_SIZE as u32..=MAX_MAX_FRAME_SIZE)
+                                .contains(&value)
+                            {
+                                return self.connection_error(
+                                    PROTOCOL_ERROR,
+                                    "max frame size is out of range",
+                                );
+                            }
+                            self.max_frame_size = value as usize;
+                        }
+                        _ => {}
+                    }
+                }
+                let mut buf = Vec::new();
+                write_frame(&mut buf, SETTINGS, ACK, 0, &[]);
+                self.stream.write_all(&buf)?;
+                self.stream.flush()?;
+            }
+            PING if flags & ACK == 0 => {
+                let mut buf = Vec::new();
+                write_frame(&mut buf, PING, ACK, 0, &payload);
+                self.stream.write_all(&buf)?;
+                self.stream.flush()?;
+            }
+            WINDOW_UPDATE => {
+                let increment = read_u32(&payload)
+                    .ok_or_else(|| protocol_error("bad WINDOW_UPDATE"))?
+                    & 0x7fff_ffff;
+                match stream_id {
+                    0 => self.send_window += increment as i64,
+                    STREAM_ID => self.stream_send_window += increment as i64,
+                    _ => {}
+                }
+            }
+            GOAWAY => {
+                let last_stream = read_u32(&payload).map(|id| id & 0x7fff_ffff);
+                let code = read_u32(payload.get(4..).unwrap_or_default()).unwrap_or_default();
+                // a graceful shutdown still lets our stream finish
+                if code != 0 || last_stream.is_none_or(|id| id < STREAM_ID) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        format!("server closed the connection ({})", error_name(code)),
+                    ));
+                }
+            }
+            PUSH_PROMISE => return Err(protocol_error("server pushed a response")),
+            // frames on other streams, acks, priorities and unknown frames don't matter to us
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Tells the server why the connection is closed with a GOAWAY frame, then fails with
+    /// `msg`. The server is going away anyway, so failing to tell it doesn't matter.
+    fn connection_error<T>(&mut self, code: u32, msg: &str) -> io::Result<T> {
+        let mut buf = Vec::new();
+        // we never processed any stream of the server's
+        write_frame(
+            &mut buf,
+            GOAWAY,
+            0,
+            0,
+            &[0u32.to_be_bytes(), code.to_be_bytes()].concat(),
+        );
+        let _ = self
+            .stream
+            .write_all(&buf)
+            .and_then(|_| self.stream.flush());
+        Err(protocol_error(msg))
+    }
+
+    fn read_frame(&mut self) -> io::Result<(u8, u8, u32, Vec<u8>)> {
+        let mut header = [0; 9];
+        self.stream.read_exact(&mut header)?;
+        let len = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+        if len > DEFAULT_MAX_FRAME_SIZE {
+            return Err(protocol_error("frame is larger than allowed"));
+        }
+        let stream_id =
+            u32::from_be_bytes([header[5], header[6], header[7], header[8]]) & 0x7fff_ffff;
+        let mut payload = vec![0; len];
+        self.stream.read_exact(&mut payload)?;
+        Ok((header[3], header[4], stream_id, payload))
+    }
+}
+
+fn write_frame(buf: &mut Vec<u8>, kind: u8, flags: u8, stream_id: u32, payload: &[u8]) {
+    buf.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    buf.push(kind);
+    buf.push(flags);
+    buf.extend_from_slice(&stream_id.to_be_bytes());
+    buf.extend_from_slice(payload);
+}
+
+fn strip_padding(flags: u8, mut payload: Vec<u8>) -> io::Result<Vec<u8>> {
+    if flags & PADDED == 0 {
+        return Ok(payload);
+    }
+    let padding = *payload
+        .first()
+        .ok_or_else(|| protocol_error("bad padding"))? as usize;
+    if padding + 1 > payload.len() {
+        return Err(protocol_error("bad padding"));
+    }
+    payload.truncate(payload.len() - padding);
+    payload.remove(0);
+    Ok(payload)
+}
+
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?))
+}
+
+fn protocol_error(msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("HTTP/2 protocol error: {msg}"),
+    )
+}
+
+/// The name of an HTTP/2 error code, as used in RST_STREAM and GOAWAY frames.
+pub fn error_name(code: u32) -> String {
+    let name = match code {
+        0x0 => "NO_ERROR",
+        0x1 => "PROTOCOL_ERROR",
+        0x2 => "INTERNAL_ERROR",
+        0x3 => "FLOW_CONTROL_ERROR",
+        0x4 => "SETTINGS_TIMEOUT",
+        0x5 => "STREAM_CLOSED",
+        0x6 => "FRAME_SIZE_ERROR",
+        0x7 => "REFUSED_STREAM",
+        0x8 => "CANCEL",
+        0x9 => "COMPRESSION_ERROR",
+        0xa => "CONNECT_ERROR",
+        0xb => "ENHANCE_YOUR_CALM",
+        0xc => "INADEQUATE_SECURITY",
+        0xd => "HTTP_1_1_REQUIRED",
+        code => return format!("error code {code:#x}"),
+    };
+    name.into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Plays back what a server sent, recording what the client sends.
+    struct FakeServer {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Read for FakeServer {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for FakeServer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn connect(frames: &[(u8, u8, u32, &[u8])]) -> Connection<FakeServer> {
+        let mut input = Vec::new();
+        for (kind, flags, stream_id, payload) in frames {
+            write_frame(&mut input, *kind, *flags, *stream_id, payload);
+        }
+        Connection::handshake(FakeServer {
+            input: Cursor::new(input),
+            output: Vec::new(),
+        })
+        .expect("handshake with a fake server can't fail")
+    }
+
+    fn frame(kind: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, kind, flags, stream_id, payload);
+        buf
+    }
+
+    fn sent(connection: &Connection<FakeServer>, frame: &[u8]) -> bool {
+        connection
+            .stream
+            .output
+            .windows(frame.len())
+            .any(|window| window == frame)
+    }
+
+    #[test]
+    fn test_request_and_response() {
+        let status = hpack::encode(&[(":status", "200")]);
+        let trailers = hpack::encode(&[("grpc-status", "0")]);
+        let mut connection = connect(&[
+            (SETTINGS, 0, 0, &[]),
+            (HEADERS, END_HEADERS, STREAM_ID, &status),
+            (PING, 0, 0, b"12345678"),
+            (DATA, PADDED, STREAM_ID, b"\x02hi\0\0"),
+            (HEADERS, END_HEADERS | END_STREAM, STREAM_ID, &trailers),
+        ]);
+
+        connection
+            .send_headers(&[(":method", "POST")], false)
+            .expect("sending to a fake server can't fail");
+        connection
+            .send_data(b"request", true)
+            .expect("sending to a fake server can't fail");
+
+        assert_eq!(
+            connection.next_event().ok(),
+            Some(Event::Headers {
+                headers: vec![(":status".into(), "200".into())],
+                end_stream: false
+            })
+        );
+        assert_eq!(
+            connection.next_event().ok(),
+            Some(Event::Data {
+                data: b"hi".to_vec(),
+                end_stream: false
+            })
+        );
+        assert_eq!(
+            connection.next_event().ok(),
+            Some(Event::Headers {
+                headers: vec![("grpc-status".into(), "0".into())],
+                end_stream: true
+            })
+        );
+
+        assert!(connection.stream.output.starts_with(PREFACE));
+        assert!(sent(
+            &connection,
+            &frame(DATA, END_STREAM, STREAM_ID, b"request")
+        ));
+        assert!(sent(&connection, &frame(SETTINGS, ACK, 0, &[])));
+        assert!(sent(&connection, &frame(PING, ACK, 0, b"12345678")));
+        // the padding counts towards flow control as well
+        assert!(sent(
+            &connection,
+            &frame(WINDOW_UPDATE, 0, STREAM_ID, &5u32.to_be_bytes())
+        ));
+    }
+
+    #[test]
+    fn test_send_waits_for_window() {
+        let increment = 10u32.to_be_bytes();
+        let mut connection = connect(&[
+            (WINDOW_UPDATE, 0, 0, &increment),
+            (WINDOW_UPDATE, 0, STREAM_ID, &increment),
+        ]);
+
+        let data = vec![1; DEFAULT_WINDOW_SIZE as usize + 10];
+        connection
+            .send_data(&data, true)
+            .expect("the fake server makes enough room");
+        assert!(sent(
+            &connection,
+            &frame(DATA, END_STREAM, STREAM_ID, &[1; 10])
+        ));
+        assert_eq!(connection.send_window, 0);
+        assert_eq!(connection.stream_send_window, 0);
+    }
+
+    #[test]
+    fn test_goaway() {
+        let graceful = [0, 0, 0, 1, 0, 0, 0, 0];
+        let refused = [0, 0, 0, 0, 0, 0, 0, 0x7];
+        let trailers = hpack::encode(&[("grpc-status", "0")]);
+
+        let mut connection = connect(&[
+            (GOAWAY, 0, 0, &graceful),
+            (HEADERS, END_HEADERS | END_STREAM, STREAM_ID, &trailers),
+        ]);
+        assert!(connection.next_event().is_ok());
+
+        let mut connection = connect(&[(GOAWAY, 0, 0, &refused)]);
+        let err = connection.next_event().expect_err("stream was refused");
+        assert!(err.to_string().contains("REFUSED_STREAM"));
+    }
+
+    #[test]
+    fn test_preface() {
+        let connection = connect(&[]);
+        // RFC 9113, 3.4 and 6.5: the preface, then SETTINGS with SETTINGS_ENABLE_PUSH = 0
+        let mut expected = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n".to_vec();
+        expected.extend_from_slice(b"\x00\x00\x06\x04\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00");
+        assert_eq!(connection.stream.output, expected);
+    }
+
+    #[test]
+    fn test_frame_layout() {
+        // RFC 9113, 4.1: a 24 bit length, the type, the flags and a 31 bit stream identifier
+        assert_eq!(
+            frame(DATA, END_STREAM, STREAM_ID, b"hi"),
+            b"\x00\x00\x02\x00\x01\x00\x00\x00\x01hi"
+        );
+        assert_eq!(
+            frame(SETTINGS, ACK, 0, &[]),
+            b"\x00\x00\x00\x04\x01\x00\x00\x00\x00"
+        );
+    }
+
+    fn max_frame_size(size: u32) -> Vec<u8> {
+        [
+            &SETTINGS_MAX_FRAME_SIZE.to_be_bytes()[..],
+            &size.to_be_bytes(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_headers_split_into_continuations() {
+        let size = DEFAULT_MAX_FRAME_SIZE as u32 + 1;
+        let mut connection = connect(&[(SETTINGS, 0, 0, &max_frame_size(size))]);
+        // the server's settings only take effect once they're read
+        connection.receive().expect("settings from a fake server");
+
+        let token = "x".repeat(2 * size as usize);
+        let headers = [("authorization", token.as_str())];
+        connection
+            .send_headers(&headers, false)
+            .expect("sending to a fake server can't fail");
+
+        let block = hpack::encode(&headers);
+        let size = size as usize;
+        let mut expected = frame(HEADERS, 0, STREAM_ID, &block[..size]);
+        expected.extend(frame(CONTINUATION, 0, STREAM_ID, &block[size..2 * size]));
+        expected.extend(frame(
+            CONTINUATION,
+            END_HEADERS,
+            STREAM_ID,
+            &block[2 * size..],
+        ));
+        assert!(connection.stream.output.ends_with(&expected));
+    }
+
+    #[test]
+    fn test_max_frame_size_out_of_range() {
+        for size in [0, DEFAULT_MAX_FRAME_SIZE as u32 - 1, MAX_MAX_FRAME_SIZE + 1] {
+            let mut connection = connect(&[(SETTINGS, 0, 0, &max_frame_size(size))]);
+            let err = connection
+                .receive()
+                .expect_err("max frame size is out of range");
+            assert!(err.to_string().contains("max frame size"));
+            let goaway = [0u32.to_be_bytes(), PROTOCOL_ERROR.to_be_bytes()].concat();
+            assert!(sent(&connection, &frame(GOAWAY, 0, 0, &goaway)));
+            assert_eq!(connection.max_frame_size, DEFAULT_MAX_FRAME_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_headers_with_continuations_received() {
+        let block = hpack::encode(&[(":status", "200"), ("grpc-status", "0")]);
+        let (first, rest) = block.split_at(4);
+        let mut connection = connect(&[
+            (HEADERS, END_STREAM, STREAM_ID, first),
+            (CONTINUATION, END_HEADERS, STREAM_ID, rest),
+        ]);
+
+        assert_eq!(
+            connection.next_event().ok(),
+            Some(Event::Headers {
+                headers: vec![
+                    (":status".into(), "200".into()),
+                    ("grpc-status".into(), "0".into())
+                ],
+                end_stream: true
+            })
+        );
+
+        let mut connection = connect(&[(HEADERS, 0, STREAM_ID, first), (DATA, 0, STREAM_ID, rest)]);
+        assert!(connection.next_event().is_err());
+
+        let mut connection = connect(&[
+            (HEADERS, 0, STREAM_ID, first),
+            (CONTINUATION, END_HEADERS, STREAM_ID + 2, rest),
+        ]);
+        assert!(connection.next_event().is_err());
+    }
+}
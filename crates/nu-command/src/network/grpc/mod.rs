@@ -0,0 +1,9 @@
+mod call;
+mod codec;
+mod grpc_;
+mod hpack;
+mod http2;
+mod proto;
+
+pub use call::GrpcCall;
+pub use grpc_::Grpc;
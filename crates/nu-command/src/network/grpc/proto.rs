@@ -0,0 +1,963 @@
+//! A parser for the parts of `.proto` files that describe messages, enums and services, which is
+//! all that's needed to encode and decode messages without generated code.
+//!
+//! Options, extensions and reserved ranges are skipped. Imports are looked up next to the file
+//! that imports them and in the import paths, except for the well-known types, which are built in.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The well-known types, so protos using them work without the protobuf sources at hand.
+const WELL_KNOWN_TYPES: &[(&str, &str)] = &[
+    (
+        "google/protobuf/any.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message Any { string type_url = 1; bytes value = 2; }",
+    ),
+    (
+        "google/protobuf/duration.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message Duration { int64 seconds = 1; int32 nanos = 2; }",
+    ),
+    (
+        "google/protobuf/empty.proto",
+        "syntax = 'proto3'; package google.protobuf; message Empty {}",
+    ),
+    (
+        "google/protobuf/field_mask.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message FieldMask { repeated string paths = 1; }",
+    ),
+    (
+        "google/protobuf/struct.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message Struct { map<string, Value> fields = 1; }
+         message Value {
+           oneof kind {
+             NullValue null_value = 1; double number_value = 2; string string_value = 3;
+             bool bool_value = 4; Struct struct_value = 5; ListValue list_value = 6;
+           }
+         }
+         enum NullValue { NULL_VALUE = 0; }
+         message ListValue { repeated Value values = 1; }",
+    ),
+    (
+        "google/protobuf/timestamp.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message Timestamp { int64 seconds = 1; int32 nanos = 2; }",
+    ),
+    (
+        "google/protobuf/wrappers.proto",
+        "syntax = 'proto3'; package google.protobuf;
+         message DoubleValue { double value = 1; } message FloatValue { float value = 1; }
+         message Int64Value { int64 value = 1; } message UInt64Value { uint64 value = 1; }
+         message Int32Value { int32 value = 1; } message UInt32Value { uint32 value = 1; }
+         message BoolValue { bool value = 1; } message StringValue { string value = 1; }
+         message BytesValue { bytes value = 1; }",
+    ),
+];
+
+/// The messages, enums and services of a `.proto` file and everything it imports, by their fully
+/// qualified names without the leading dot.
+#[derive(Debug, Default)]
+pub struct Descriptors {
+    pub messages: HashMap<String, Message>,
+    pub enums: HashMap<String, Enum>,
+    pub services: HashMap<String, Service>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Message {
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub number: u32,
+    pub label: Label,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    /// A proto3 field that can't be told apart from its default value when missing.
+    Implicit,
+    /// A field that may be missing, like proto2 fields, proto3 `optional` and `oneof` members.
+    Optional,
+    Repeated,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+    Message(String),
+    Enum(String),
+    Map(Box<Kind>, Box<Kind>),
+}
+
+impl Kind {
+    fn scalar(name: &str) -> Option<Kind> {
+        let kind = match name {
+            "double" => Kind::Double,
+            "float" => Kind::Float,
+            "int32" => Kind::Int32,
+            "int64" => Kind::Int64,
+            "uint32" => Kind::Uint32,
+            "uint64" => Kind::Uint64,
+            "sint32" => Kind::Sint32,
+            "sint64" => Kind::Sint64,
+            "fixed32" => Kind::Fixed32,
+            "fixed64" => Kind::Fixed64,
+            "sfixed32" => Kind::Sfixed32,
+            "sfixed64" => Kind::Sfixed64,
+            "bool" => Kind::Bool,
+            "string" => Kind::String,
+            "bytes" => Kind::Bytes,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Enum {
+    pub values: Vec<(String, i32)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Service {
+    pub methods: Vec<Method>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    pub name: String,
+    pub input: String,
+    pub output: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+/// Why a `.proto` file couldn't be loaded, with the file and line if there is one.
+#[derive(Debug)]
+pub struct ProtoError(String);
+
+impl fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Loads the `.proto` file at `path` and its imports, resolving all the type names in them.
+pub fn load(path: &Path, import_paths: &[PathBuf]) -> Result<Descriptors, ProtoError> {
+    let mut descriptors = Descriptors::default();
+    let source =
+        fs::read_to_string(path).map_err(|err| ProtoError(format!("{}: {err}", path.display())))?;
+
+    let mut queue = vec![(path.to_path_buf(), source)];
+    let mut seen = HashSet::new();
+    while let Some((file, source)) = queue.pop() {
+        let imports = parse(&file, &source, &mut descriptors)?;
+        for import in imports {
+            if !seen.insert(import.clone()) {
+                continue;
+            }
+            if let Some((_, source)) = WELL_KNOWN_TYPES.iter().find(|(name, _)| *name == import) {
+                queue.push((PathBuf::from(&import), source.to_string()));
+                continue;
+            }
+
+            let found = file
+                .parent()
+                .into_iter()
+                .chain(import_paths.iter().map(PathBuf::as_path))
+                .map(|dir| dir.join(&import))
+                .find(|candidate| candidate.is_file())
+                .ok_or_else(|| {
+                    ProtoError(format!("{}: import '{import}' not found", file.display()))
+                })?;
+            let source = fs::read_to_string(&found)
+                .map_err(|err| ProtoError(format!("{}: {err}", found.display())))?;
+            queue.push((found, source));
+        }
+    }
+
+    descriptors.resolve()?;
+    Ok(descriptors)
+}
+
+/// Parses a single file into `descriptors`, returning the files it imports.
+fn parse(
+    file: &Path,
+    source: &str,
+    descriptors: &mut Descriptors,
+) -> Result<Vec<String>, ProtoError> {
+    let tokens = tokenize(source).map_err(|line| {
+        ProtoError(format!(
+            "{}:{line}: unterminated string or comment",
+            file.display()
+        ))
+    })?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        file,
+        package: String::new(),
+        // files without a syntax statement are proto2
+        proto3: false,
+        imports: Vec::new(),
+        descriptors,
+    };
+    parser.parse_file()?;
+    Ok(parser.imports)
+}
+
+impl Descriptors {
+    /// Parses a single file from a string, for testing without files.
+    #[cfg(test)]
+    pub fn from_source(source: &str) -> Result<Self, ProtoError> {
+        let mut descriptors = Descriptors::default();
+        let imports = parse(Path::new("test.proto"), source, &mut descriptors)?;
+        for import in imports {
+            if let Some((_, source)) = WELL_KNOWN_TYPES.iter().find(|(name, _)| *name == import) {
+                parse(Path::new(&import), source, &mut descriptors)?;
+            }
+        }
+        descriptors.resolve()?;
+        Ok(descriptors)
+    }
+
+    /// Replaces the type names as written in the files with the fully qualified names of the
+    /// messages and enums they refer to.
+    fn resolve(&mut self) -> Result<(), ProtoError> {
+        let names = self.messages.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            let Some(message) = self.messages.get_mut(&name) else {
+                continue;
+            };
+            let mut fields = std::mem::take(&mut message.fields);
+            for field in &mut fields {
+                self.resolve_kind(&name, &mut field.kind)
+                    .map_err(|unknown| {
+                        ProtoError(format!(
+                            "unknown type '{unknown}' of field '{}' in message '{name}'",
+                            field.name
+                        ))
+                    })?;
+            }
+            if let Some(message) = self.messages.get_mut(&name) {
+                message.fields = fields;
+            }
+        }
+
+        let mut services = std::mem::take(&mut self.services);
+        for (name, service) in &mut services {
+            let package = name
+                .rsplit_once('.')
+                .map(|(package, _)| package)
+                .unwrap_or("");
+            for method in &mut service.methods {
+                for type_name in [&mut method.input, &mut method.output] {
+                    match self.lookup(package, type_name) {
+                        Some(Kind::Message(full)) => *type_name = full,
+                        _ => {
+                            return Err(ProtoError(format!(
+                                "unknown message '{type_name}' of method '{}' in service '{name}'",
+                                method.name
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        self.services = services;
+
+        Ok(())
+    }
+
+    fn resolve_kind(&self, scope: &str, kind: &mut Kind) -> Result<(), String> {
+        match kind {
+            Kind::Message(type_name) => {
+                *kind = self
+                    .lookup(scope, type_name)
+                    .ok_or_else(|| type_name.clone())?;
+            }
+            Kind::Map(_, value) => self.resolve_kind(scope, value)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Finds a type like protoc does, from the innermost scope outwards.
+    fn lookup(&self, scope: &str, type_name: &str) -> Option<Kind> {
+        let found = |full: String| {
+            if self.messages.contains_key(&full) {
+                Some(Kind::Message(full))
+            } else if self.enums.contains_key(&full) {
+                Some(Kind::Enum(full))
+            } else {
+                None
+            }
+        };
+
+        if let Some(full) = type_name.strip_prefix('.') {
+            return found(full.to_string());
+        }
+        let mut scope = scope;
+        loop {
+            if let Some(kind) = found(qualify(scope, type_name)) {
+                return Some(kind);
+            }
+            if scope.is_empty() {
+                return None;
+            }
+            scope = scope.rsplit_once('.').map(|(outer, _)| outer).unwrap_or("");
+        }
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    match scope {
+        "" => name.to_string(),
+        scope => format!("{scope}.{name}"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Symbol(char),
+}
+
+/// Splits `source` into tokens with their line numbers, or returns the line of an unterminated
+/// string or comment.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, usize> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let start = line;
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    let c = chars.next().ok_or(start)?;
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' | '\'' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next().ok_or(line)? {
+                        '\n' => return Err(line),
+                        end if end == c => break,
+                        '\\' => match chars.next().ok_or(line)? {
+                            'n' => string.push('\n'),
+                            't' => string.push('\t'),
+                            'r' => string.push('\r'),
+                            '0' => string.push('\0'),
+                            escaped => string.push(escaped),
+                        },
+                        c => string.push(c),
+                    }
+                }
+                tokens.push((Token::Str(string), line));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                let token = match c.is_ascii_digit() {
+                    true => Token::Number(word),
+                    false => Token::Ident(word),
+                };
+                tokens.push((token, line));
+            }
+            c => tokens.push((Token::Symbol(c), line)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    file: &'a Path,
+    package: String,
+    proto3: bool,
+    imports: Vec<String>,
+    descriptors: &'a mut Descriptors,
+}
+
+impl Parser<'_> {
+    fn parse_file(&mut self) -> Result<(), ProtoError> {
+        while let Some(token) = self.peek().cloned() {
+            self.pos += 1;
+            match token {
+                Token::Symbol(';') => {}
+                Token::Ident(keyword) => match keyword.as_str() {
+                    "syntax" => {
+                        self.expect('=')?;
+                        self.proto3 = self.string()? == "proto3";
+                        self.expect(';')?;
+                    }
+                    "package" => {
+                        self.package = self.ident()?;
+                        self.expect(';')?;
+                    }
+                    "import" => {
+                        if let Some(Token::Ident(modifier)) = self.peek()
+                            && (modifier == "public" || modifier == "weak")
+                        {
+                            self.pos += 1;
+                        }
+                        let import = self.string()?;
+                        self.imports.push(import);
+                        self.expect(';')?;
+                    }
+                    "message" => self.parse_message(&self.package.clone())?,
+                    "enum" => self.parse_enum(&self.package.clone())?,
+                    "service" => self.parse_service()?,
+                    "extend" => self.skip_block()?,
+                    "option" | "edition" => self.skip_statement()?,
+                    _ => return Err(self.error(format!("unexpected '{keyword}'"))),
+                },
+                token => return Err(self.error(format!("unexpected {}", describe(&token)))),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<(), ProtoError> {
+        let name = self.ident()?;
+        let name = qualify(scope, &name);
+        self.expect('{')?;
+
+        let mut fields = Vec::new();
+        loop {
+            match self.next()? {
+                Token::Symbol('}') => break,
+                Token::Symbol(';') => {}
+                Token::Ident(keyword) => match keyword.as_str() {
+                    "message" => self.parse_message(&name)?,
+                    "enum" => self.parse_enum(&name)?,
+                    "oneof" => {
+                        self.ident()?;
+                        self.expect('{')?;
+                        loop {
+                            match self.next()? {
+                                Token::Symbol('}') => break,
+                                Token::Symbol(';') => {}
+                                Token::Ident(keyword) if keyword == "option" => {
+                                    self.skip_statement()?
+                                }
+                                Token::Ident(type_name) => {
+                                    fields.push(self.parse_field(Label::Optional, type_name)?)
+                                }
+                                token => {
+                                    return Err(
+                                        self.error(format!("unexpected {}", describe(&token)))
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    "map" if self.peek() == Some(&Token::Symbol('<')) => {
+                        fields.push(self.parse_map()?)
+                    }
+                    "option" | "reserved" | "extensions" => self.skip_statement()?,
+                    "extend" => self.skip_block()?,
+                    "repeated" => {
+                        let type_name = self.ident()?;
+                        fields.push(self.parse_field(Label::Repeated, type_name)?);
+                    }
+                    "optional" | "required" => {
+                        let type_name = self.ident()?;
+                        fields.push(self.parse_field(Label::Optional, type_name)?);
+                    }
+                    _ => {
+                        let label = match self.proto3 {
+                            true => Label::Implicit,
+                            false => Label::Optional,
+                        };
+                        fields.push(self.parse_field(label, keyword)?);
+                    }
+                },
+                token => return Err(self.error(format!("unexpected {}", describe(&token)))),
+            }
+        }
+
+        self.descriptors.messages.insert(name, Message { fields });
+        Ok(())
+    }
+
+    /// Parses the rest of a field after its label and type.
+    fn parse_field(&mut self, label: Label, type_name: String) -> Result<Field, ProtoError> {
+        if type_name == "group" {
+            return Err(self.error("groups are not supported".into()));
+        }
+        let name = self.ident()?;
+        self.expect('=')?;
+        let number = self.field_number()?;
+        self.skip_options()?;
+        self.expect(';')?;
+
+        Ok(Field {
+            name,
+            number,
+            label,
+            kind: Kind::scalar(&type_name).unwrap_or(Kind::Message(type_name)),
+        })
+    }
+
+    fn parse_map(&mut self) -> Result<Field, ProtoError> {
+        self.expect('<')?;
+        let key = self.ident()?;
+        let key = match Kind::scalar(&key) {
+            Some(Kind::Double | Kind::Float | Kind::Bytes) | None => {
+                return Err(self.error(format!("'{key}' can't be the key of a map")));
+            }
+            Some(key) => key,
+        };
+        self.expect(',')?;
+        let value = self.ident()?;
+        let value = Kind::scalar(&value).unwrap_or(Kind::Message(value));
+        self.expect('>')?;
+
+        let name = self.ident()?;
+        self.expect('=')?;
+        let number = self.field_number()?;
+        self.skip_options()?;
+        self.expect(';')?;
+
+        Ok(Field {
+            name,
+            number,
+            label: Label::Repeated,
+            kind: Kind::Map(Box::new(key), Box::new(value)),
+        })
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<(), ProtoError> {
+        let name = self.ident()?;
+        let name = qualify(scope, &name);
+        self.expect('{')?;
+
+        let mut values = Vec::new();
+        loop {
+            match self.next()? {
+                Token::Symbol('}') => break,
+                Token::Symbol(';') => {}
+                Token::Ident(keyword) if keyword == "option" || keyword == "reserved" => {
+                    self.skip_statement()?
+                }
+                Token::Ident(value) => {
+                    self.expect('=')?;
+                    let number = self.number()?;
+                    let number = i32::try_from(number)
+                        .map_err(|_| self.error(format!("{number} is out of range for an enum")))?;
+                    self.skip_options()?;
+                    self.expect(';')?;
+                    values.push((value, number));
+                }
+                token => return Err(self.error(format!("unexpected {}", describe(&token)))),
+            }
+        }
+
+        self.descriptors.enums.insert(name, Enum { values });
+        Ok(())
+    }
+
+    fn parse_service(&mut self) -> Result<(), ProtoError> {
+        let name = self.ident()?;
+        let name = qualify(&self.package, &name);
+        self.expect('{')?;
+
+        let mut methods = Vec::new();
+        loop {
+            match self.next()? {
+                Token::Symbol('}') => break,
+                Token::Symbol(';') => {}
+                Token::Ident(keyword) if keyword == "option" => self.skip_statement()?,
+                Token::Ident(keyword) if keyword == "rpc" => {
+                    let name = self.ident()?;
+                    let (client_streaming, input) = self.method_type()?;
+                    match self.next()? {
+                        Token::Ident(returns) if returns == "returns" => {}
+                        _ => return Err(self.error("expected 'returns'".into())),
+                    }
+                    let (server_streaming, output) = self.method_type()?;
+                    match self.peek() {
+                        Some(Token::Symbol('{')) => self.skip_block()?,
+                        _ => self.expect(';')?,
+                    }
+                    methods.push(Method {
+                        name,
+                        input,
+                        output,
+                        client_streaming,
+                        server_streaming,
+                    });
+                }
+                token => return Err(self.error(format!("unexpected {}", describe(&token)))),
+            }
+        }
+
+        self.descriptors.services.insert(name, Service { methods });
+        Ok(())
+    }
+
+    /// Parses `(Type)` or `(stream Type)` of a method.
+    fn method_type(&mut self) -> Result<(bool, String), ProtoError> {
+        self.expect('(')?;
+        let mut type_name = self.ident()?;
+        let stream = type_name == "stream" && matches!(self.peek(), Some(Token::Ident(_)));
+        if stream {
+            type_name = self.ident()?;
+        }
+        self.expect(')')?;
+        Ok((stream, type_name))
+    }
+
+    fn field_number(&mut self) -> Result<u32, ProtoError> {
+        let number = self.number()?;
+        u32::try_from(number)
+            .ok()
+            .filter(|number| (1..1 << 29).contains(number))
+            .ok_or_else(|| self.error(format!("{number} is not a valid field number")))
+    }
+
+    fn number(&mut self) -> Result<i64, ProtoError> {
+        let negative = self.peek() == Some(&Token::Symbol('-'));
+        if negative {
+            self.pos += 1;
+        }
+        let number = match self.next()? {
+            Token::Number(number) => number,
+            token => {
+                return Err(self.error(format!("expected a number, found {}", describe(&token))));
+            }
+        };
+
+        let parsed = if let Some(hex) = number.strip_prefix("0x").or(number.strip_prefix("0X")) {
+            i64::from_str_radix(hex, 16)
+        } else if number.len() > 1
+            && let Some(octal) = number.strip_prefix('0')
+        {
+            i64::from_str_radix(octal, 8)
+        } else {
+            number.parse()
+        };
+        let number = parsed.map_err(|_| self.error(format!("'{number}' is not a valid number")))?;
+        Ok(if negative { -number } else { number })
+    }
+
+    /// Skips an option or similar statement up to its `;`, including any braced values in it.
+    fn skip_statement(&mut self) -> Result<(), ProtoError> {
+        let mut depth = 0;
+        loop {
+            match self.next()? {
+                Token::Symbol('{') => depth += 1,
+                Token::Symbol('}') => depth -= 1,
+                Token::Symbol(';') if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips everything up to and including the next braced block.
+    fn skip_block(&mut self) -> Result<(), ProtoError> {
+        let mut depth = 0;
+        loop {
+            match self.next()? {
+                Token::Symbol('{') => depth += 1,
+                Token::Symbol('}') if depth == 1 => return Ok(()),
+                Token::Symbol('}') => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips the options of a field or enum value in brackets, if there are any.
+    fn skip_options(&mut self) -> Result<(), ProtoError> {
+        if self.peek() != Some(&Token::Symbol('[')) {
+            return Ok(());
+        }
+        let mut depth = 0;
+        loop {
+            match self.next()? {
+                Token::Symbol('[') => depth += 1,
+                Token::Symbol(']') if depth == 1 => return Ok(()),
+                Token::Symbol(']') => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Result<Token, ProtoError> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| self.error("unexpected end of file".into()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ProtoError> {
+        match self.next()? {
+            Token::Symbol(c) if c == symbol => Ok(()),
+            token => Err(self.error(format!("expected '{symbol}', found {}", describe(&token)))),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ProtoError> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            token => Err(self.error(format!("expected a name, found {}", describe(&token)))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ProtoError> {
+        match self.next()? {
+            Token::Str(string) => Ok(string),
+            token => Err(self.error(format!("expected a string, found {}", describe(&token)))),
+        }
+    }
+
+    /// An error at the last token read.
+    fn error(&self, msg: String) -> ProtoError {
+        let line = self
+            .tokens
+            .get(self.pos.saturating_sub(1))
+            .or(self.tokens.last())
+            .map(|(_, line)| *line)
+            .unwrap_or(1);
+        ProtoError(format!("{}:{line}: {msg}", self.file.display()))
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Ident(ident) => format!("'{ident}'"),
+        Token::Number(number) => format!("'{number}'"),
+        Token::Str(string) => format!("\"{string}\""),
+        Token::Symbol(symbol) => format!("'{symbol}'"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package shop.v1;
+
+        import "google/protobuf/timestamp.proto";
+        option go_package = "example.com/shop";
+
+        /* An order,
+           as stored in the database. */
+        message Order {
+          message Item {
+            string sku = 1;
+            uint32 quantity = 2 [deprecated = true];
+          }
+          enum Status { option allow_alias = true; PENDING = 0; SHIPPED = 1; SENT = 1; }
+
+          int64 id = 1;
+          repeated Item items = 2;
+          Status status = 3;
+          map<string, string> labels = 4;
+          optional string note = 5;
+          google.protobuf.Timestamp created = 6;
+          oneof payment {
+            string card = 7;
+            Voucher voucher = 8;
+          }
+          reserved 9 to 11;
+        }
+
+        message Voucher { bytes code = 1; sint32 discount = 0x2; }
+
+        service Orders {
+          rpc Get (Order) returns (Order);
+          rpc Watch (Order) returns (stream Order) { option deprecated = true; }
+          rpc Import (stream Order) returns (Voucher) {}
+        }
+    "#;
+
+    fn field(name: &str, number: u32, label: Label, kind: Kind) -> Field {
+        Field {
+            name: name.into(),
+            number,
+            label,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let descriptors = Descriptors::from_source(PROTO).expect("valid proto");
+
+        let order = &descriptors.messages["shop.v1.Order"];
+        assert_eq!(
+            order.fields,
+            vec![
+                field("id", 1, Label::Implicit, Kind::Int64),
+                field(
+                    "items",
+                    2,
+                    Label::Repeated,
+                    Kind::Message("shop.v1.Order.Item".into())
+                ),
+                field(
+                    "status",
+                    3,
+                    Label::Implicit,
+                    Kind::Enum("shop.v1.Order.Status".into())
+                ),
+                field(
+                    "labels",
+                    4,
+                    Label::Repeated,
+                    Kind::Map(Box::new(Kind::String), Box::new(Kind::String))
+                ),
+                field("note", 5, Label::Optional, Kind::String),
+                field(
+                    "created",
+                    6,
+                    Label::Implicit,
+                    Kind::Message("google.protobuf.Timestamp".into())
+                ),
+                field("card", 7, Label::Optional, Kind::String),
+                field(
+                    "voucher",
+                    8,
+                    Label::Optional,
+                    Kind::Message("shop.v1.Voucher".into())
+                ),
+            ]
+        );
+        assert_eq!(
+            descriptors.messages["shop.v1.Voucher"].fields[1],
+            field("discount", 2, Label::Implicit, Kind::Sint32)
+        );
+        assert_eq!(
+            descriptors.enums["shop.v1.Order.Status"].values,
+            vec![
+                ("PENDING".into(), 0),
+                ("SHIPPED".into(), 1),
+                ("SENT".into(), 1)
+            ]
+        );
+
+        let methods = &descriptors.services["shop.v1.Orders"].methods;
+        assert_eq!(
+            methods
+                .iter()
+                .map(|method| (
+                    method.name.as_str(),
+                    method.output.as_str(),
+                    method.client_streaming,
+                    method.server_streaming
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Get", "shop.v1.Order", false, false),
+                ("Watch", "shop.v1.Order", false, true),
+                ("Import", "shop.v1.Voucher", true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proto2_fields_have_presence() {
+        let descriptors =
+            Descriptors::from_source("message M { required int32 a = 1; int32 b = 2; }")
+                .expect("valid proto");
+        assert!(
+            descriptors.messages["M"]
+                .fields
+                .iter()
+                .all(|field| field.label == Label::Optional)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |source| {
+            Descriptors::from_source(source)
+                .map(|_| ())
+                .expect_err("invalid proto")
+                .to_string()
+        };
+
+        assert_eq!(
+            err("syntax = 'proto3';\nmessage M {\n  int32 a = 1\n}"),
+            "test.proto:4: expected ';', found '}'"
+        );
+        assert_eq!(
+            err("message M { Missing a = 1; }"),
+            "unknown type 'Missing' of field 'a' in message 'M'"
+        );
+        assert_eq!(
+            err("message M { int32 a = 0; }"),
+            "test.proto:1: 0 is not a valid field number"
+        );
+        assert_eq!(
+            err("message M { map<float, int32> a = 1; }"),
+            "test.proto:1: 'float' can't be the key of a map"
+        );
+        assert_eq!(
+            err("/* comment"),
+            "test.proto:1: unterminated string or comment"
+        );
+    }
+}
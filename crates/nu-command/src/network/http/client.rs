@@ -28,7 +28,8 @@ use nu_protocol::{
 use serde_json::Value as JsonValue;
 use std::{
     collections::HashMap,
-    io::{self, Cursor},
+    fs::File,
+    io::{self, Cursor, Read},
     path::{Path, PathBuf},
     str::FromStr,
     sync::mpsc::{self, RecvTimeoutError},
@@ -80,7 +81,7 @@ impl From<Option<ContentType>> for BodyType {
     }
 }
 
-trait GetHeader {
+pub(crate) trait GetHeader {
     fn header(&self, key: &str) -> Option<&str>;
 }
 
@@ -115,6 +116,224 @@ pub fn add_unix_socket_flag(sig: Signature) -> Signature {
     )
 }
 
+/// Helper function to add the --retries, --retry-delay and --retry-on flags to command signatures.
+pub fn add_retry_flags(sig: Signature) -> Signature {
+    sig.named(
+        "retries",
+        SyntaxShape::Int,
+        "Number of times to retry the request when it fails. Default: 0.",
+        None,
+    )
+    .named(
+        "retry-delay",
+        SyntaxShape::Duration,
+        "Delay before the first retry, doubled after every attempt. Default: 1sec.",
+        None,
+    )
+    .named(
+        "retry-on",
+        SyntaxShape::OneOf(vec![
+            SyntaxShape::List(Box::new(SyntaxShape::Int)),
+            SyntaxShape::String,
+        ]),
+        "Status codes that trigger a retry, as a list or comma separated string. Default: \
+         429,502,503,504.",
+        None,
+    )
+}
+
+/// How failed requests should be retried, built from the flags added by [`add_retry_flags`].
+pub struct RetryPolicy {
+    retries: u32,
+    delay: Duration,
+    retry_on: Vec<u16>,
+}
+
+impl RetryPolicy {
+    const DEFAULT_DELAY: Duration = Duration::from_secs(1);
+    const DEFAULT_STATUS_CODES: &[u16] = &[429, 502, 503, 504];
+
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Self, ShellError> {
+        let retries = match call.get_flag::<Spanned<i64>>(engine_state, stack, "retries")? {
+            Some(Spanned { item, span }) => {
+                u32::try_from(item).map_err(|_| ShellError::IncorrectValue {
+                    msg: "Number of retries must be a non-negative integer".into(),
+                    val_span: span,
+                    call_span: call.head,
+                })?
+            }
+            None => 0,
+        };
+
+        let delay = match call.get_flag::<Value>(engine_state, stack, "retry-delay")? {
+            Some(value) => {
+                let nanos = value.as_duration()?;
+                if nanos < 0 {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "Retry delay must not be negative".into(),
+                        val_span: value.span(),
+                        call_span: call.head,
+                    });
+                }
+                Duration::from_nanos(nanos as u64)
+            }
+            None => Self::DEFAULT_DELAY,
+        };
+
+        let retry_on = match call.get_flag::<Value>(engine_state, stack, "retry-on")? {
+            Some(value) => parse_status_codes(&value)?,
+            None => Self::DEFAULT_STATUS_CODES.to_vec(),
+        };
+
+        Ok(RetryPolicy {
+            retries,
+            delay,
+            retry_on,
+        })
+    }
+
+    fn should_retry(&self, response: &Result<Response, ShellError>) -> bool {
+        match response {
+            Ok(response) => self.retry_on.contains(&response.status().as_u16()),
+            // Only failures to reach the server are worth retrying, anything else
+            // (e.g. a malformed body) would fail in exactly the same way again.
+            Err(ShellError::NetworkFailure { .. } | ShellError::Io(_)) => true,
+            Err(_) => false,
+        }
+    }
+}
+
+fn parse_status_codes(value: &Value) -> Result<Vec<u16>, ShellError> {
+    let span = value.span();
+    let invalid = || ShellError::IncorrectValue {
+        msg: "Expected HTTP status codes, e.g. [429 503] or '429,503'".into(),
+        val_span: span,
+        call_span: span,
+    };
+
+    match value {
+        Value::List { vals, .. } => vals
+            .iter()
+            .map(|val| u16::try_from(val.as_int()?).map_err(|_| invalid()))
+            .collect(),
+        Value::String { val, .. } => val
+            .split(',')
+            .map(|code| code.trim().parse::<u16>().map_err(|_| invalid()))
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Reads the `Retry-After` header, which holds either a number of seconds or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.header("retry-after")?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+/// Sleeps for `duration`, returning early with an error if the user interrupts.
+pub fn sleep_interruptible(
+    duration: Duration,
+    span: Span,
+    signals: &Signals,
+) -> Result<(), ShellError> {
+    let step = Duration::from_millis(100);
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        signals.check(&span)?;
+        let nap = remaining.min(step);
+        std::thread::sleep(nap);
+        remaining -= nap;
+    }
+    signals.check(&span)
+}
+
+/// Sends a request, retrying it as described by `policy`.
+///
+/// The request builder is consumed when a request is sent, so `send` is called once per
+/// attempt and has to build a fresh request every time. The delay between attempts grows
+/// exponentially, unless the server tells us how long to wait through `Retry-After`.
+pub fn send_with_retry(
+    policy: &RetryPolicy,
+    span: Span,
+    signals: &Signals,
+    mut send: impl FnMut() -> Result<(Result<Response, ShellError>, Headers), ShellError>,
+) -> Result<(Response, Headers), ShellError> {
+    let mut delay = policy.delay;
+    let mut attempt = 0;
+    loop {
+        let (response, headers) = send()?;
+        if attempt >= policy.retries || !policy.should_retry(&response) {
+            return response.map(|response| (response, headers));
+        }
+
+        let wait = response
+            .as_ref()
+            .ok()
+            .and_then(retry_after)
+            .unwrap_or(delay);
+        sleep_interruptible(wait, span, signals)?;
+
+        delay = delay.saturating_mul(2);
+        attempt += 1;
+    }
+}
+
+/// Like [`send_with_retry`], but for requests carrying a body.
+///
+/// A streamed body can only be read once, so it is collected up front when retries are enabled.
+#[allow(clippy::too_many_arguments)]
+pub fn send_request_with_retry(
+    engine_state: &EngineState,
+    policy: &RetryPolicy,
+    mut build_request: impl FnMut() -> Result<RequestBuilder<WithBody>, ShellError>,
+    request_span: Span,
+    body: HttpBody,
+    content_type: Option<String>,
+    span: Span,
+    signals: &Signals,
+) -> Result<(Response, Headers), ShellError> {
+    let body = match body {
+        HttpBody::ByteStream(stream) if policy.retries > 0 => HttpBody::Value(stream.into_value()?),
+        body => body,
+    };
+
+    match body {
+        HttpBody::Value(value) => send_with_retry(policy, span, signals, || {
+            Ok(send_request(
+                engine_state,
+                build_request()?,
+                request_span,
+                HttpBody::Value(value.clone()),
+                content_type.clone(),
+                span,
+                signals,
+            ))
+        }),
+        body => {
+            let (response, headers) = send_request(
+                engine_state,
+                build_request()?,
+                request_span,
+                body,
+                content_type,
+                span,
+                signals,
+            );
+            response.map(|response| (response, headers))
+        }
+    }
+}
+
 /// Expands unix socket path including tilde expansion.
 pub fn expand_unix_socket_path(
     unix_socket: Option<Spanned<String>>,
@@ -484,9 +703,14 @@ pub fn send_request(
                     serialize_types,
                 ),
                 BodyType::Form => send_form_request(request_url.as_str(), body, req, span, signals),
-                BodyType::Multipart => {
-                    send_multipart_request(request_url.as_str(), body, req, span, signals)
-                }
+                BodyType::Multipart => send_multipart_request(
+                    engine_state,
+                    request_url.as_str(),
+                    body,
+                    req,
+                    span,
+                    signals,
+                ),
                 BodyType::Unknown(_) => {
                     send_default_request(request_url.as_str(), body, req, span, signals)
                 }
@@ -598,6 +822,7 @@ fn send_form_request(
 }
 
 fn send_multipart_request(
+    engine_state: &EngineState,
     request_url: Spanned<&str>,
     body: Value,
     req: RequestBuilder<WithBody>,
@@ -607,37 +832,66 @@ fn send_multipart_request(
     let request_fn = match body {
         Value::Record { val, .. } => {
             let mut builder = MultipartWriter::new();
+            // Files are streamed from disk, so the body is made of the in-memory parts written by
+            // `builder` interleaved with the opened files.
+            let mut segments: Vec<Box<dyn Read + Send + Sync>> = vec![];
+            let mut content_length = 0;
 
             let err = |e: std::io::Error| {
                 ShellErrorOrRequestError::ShellError(IoError::new(e, span, None).into())
             };
 
             for (col, val) in val.into_owned() {
-                if let Value::Binary { val, .. } = val {
-                    let headers = [
-                        "Content-Type: application/octet-stream".to_string(),
-                        "Content-Transfer-Encoding: binary".to_string(),
-                        format!(
-                            "Content-Disposition: form-data; name=\"{col}\"; filename=\"{col}\""
-                        ),
-                        format!("Content-Length: {}", val.len()),
-                    ];
-                    builder
-                        .add(&mut Cursor::new(val), &headers.join("\r\n"))
-                        .map_err(err)?;
-                } else {
-                    let headers = format!(r#"Content-Disposition: form-data; name="{col}""#);
-                    builder
-                        .add(val.coerce_into_string()?.as_bytes(), &headers)
-                        .map_err(err)?;
+                match val {
+                    Value::Binary { val, .. } => {
+                        let headers = [
+                            "Content-Type: application/octet-stream".to_string(),
+                            "Content-Transfer-Encoding: binary".to_string(),
+                            format!(
+                                "Content-Disposition: form-data; name=\"{col}\"; filename=\"{col}\""
+                            ),
+                            format!("Content-Length: {}", val.len()),
+                        ];
+                        builder
+                            .add(&mut Cursor::new(val), &headers.join("\r\n"))
+                            .map_err(err)?;
+                    }
+                    Value::Record { val: part, .. } => {
+                        let part = MultipartPart::from_record(engine_state, &col, &part, span)?;
+                        builder
+                            .add(part.value.as_slice(), &part.headers.join("\r\n"))
+                            .map_err(err)?;
+                        if let Some((file, len)) = part.file {
+                            content_length += builder.data.len() as u64 + len;
+                            segments.push(Box::new(Cursor::new(std::mem::take(&mut builder.data))));
+                            segments.push(Box::new(file));
+                        }
+                    }
+                    val => {
+                        let headers = format!(r#"Content-Disposition: form-data; name="{col}""#);
+                        builder
+                            .add(val.coerce_into_string()?.as_bytes(), &headers)
+                            .map_err(err)?;
+                    }
                 }
             }
             builder.finish();
 
             let (boundary, data) = (builder.boundary, builder.data);
             let content_type = format!("multipart/form-data; boundary={boundary}");
-
-            move || req.header("Content-Type", &content_type).send(&data)
+            content_length += data.len() as u64;
+            segments.push(Box::new(Cursor::new(data)));
+
+            let body = segments.into_iter().fold(
+                Box::new(io::empty()) as Box<dyn Read + Send + Sync>,
+                |body, segment| Box::new(body.chain(segment)),
+            );
+
+            move || {
+                req.header("Content-Type", &content_type)
+                    .header("Content-Length", &content_length.to_string())
+                    .send(SendBody::from_owned_reader(body))
+            }
         }
         _ => {
             return Err(ShellErrorOrRequestError::ShellError(
@@ -651,6 +905,102 @@ fn send_multipart_request(
     send_cancellable_request(request_url, Box::new(request_fn), span, signals)
 }
 
+/// A multipart field given as a record, either `{file: <path>}` for a file streamed from disk or
+/// `{value: <data>}`, both optionally with `content_type` and `filename`.
+struct MultipartPart {
+    headers: Vec<String>,
+    value: Vec<u8>,
+    file: Option<(File, u64)>,
+}
+
+impl MultipartPart {
+    fn from_record(
+        engine_state: &EngineState,
+        name: &str,
+        part: &Record,
+        span: Span,
+    ) -> Result<Self, ShellError> {
+        let content_type = part
+            .get("content_type")
+            .map(|val| val.coerce_string())
+            .transpose()?;
+        let filename = part
+            .get("filename")
+            .map(|val| val.coerce_string())
+            .transpose()?;
+
+        match (part.get("file"), part.get("value")) {
+            (Some(path), None) => {
+                let path_span = path.span();
+                let path = expand_path_with(path.coerce_str()?, engine_state.cwd(None)?, true);
+                let file =
+                    File::open(&path).map_err(|e| IoError::new(e, path_span, path.clone()))?;
+                let len = file
+                    .metadata()
+                    .map_err(|e| IoError::new(e, path_span, path.clone()))?
+                    .len();
+
+                let filename = filename.unwrap_or_else(|| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| name.to_string())
+                });
+                let content_type = content_type.unwrap_or_else(|| {
+                    mime_guess::from_path(&path)
+                        .first_or_octet_stream()
+                        .to_string()
+                });
+
+                Ok(MultipartPart {
+                    headers: vec![
+                        format!("Content-Type: {content_type}"),
+                        format!(
+                            "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\""
+                        ),
+                        format!("Content-Length: {len}"),
+                    ],
+                    value: vec![],
+                    file: Some((file, len)),
+                })
+            }
+            (None, Some(value)) => {
+                let (value, binary) = match value {
+                    Value::Binary { val, .. } => (val.clone(), true),
+                    val => (val.coerce_string()?.into_bytes(), false),
+                };
+
+                let mut headers = vec![];
+                if let Some(content_type) = content_type {
+                    headers.push(format!("Content-Type: {content_type}"));
+                } else if binary {
+                    headers.push("Content-Type: application/octet-stream".to_string());
+                }
+                match filename {
+                    Some(filename) => headers.push(format!(
+                        "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\""
+                    )),
+                    None => {
+                        headers.push(format!(r#"Content-Disposition: form-data; name="{name}""#))
+                    }
+                }
+
+                Ok(MultipartPart {
+                    headers,
+                    value,
+                    file: None,
+                })
+            }
+            _ => Err(ShellError::IncorrectValue {
+                msg: format!(
+                    "Multipart field '{name}' must have either a 'file' or a 'value' column"
+                ),
+                val_span: span,
+                call_span: span,
+            }),
+        }
+    }
+}
+
 fn send_default_request(
     request_url: Spanned<&str>,
     body: Value,
@@ -797,62 +1147,68 @@ pub fn request_add_custom_headers<B>(
     mut request: RequestBuilder<B>,
 ) -> Result<RequestBuilder<B>, ShellError> {
     if let Some(headers) = headers {
-        let mut custom_headers: HashMap<String, Value> = HashMap::new();
+        for (k, v) in parse_custom_headers(&headers)? {
+            if let Ok(s) = v.coerce_into_string() {
+                request = request.header(&k, &s);
+            }
+        }
+    }
 
-        match &headers {
-            Value::Record { val, .. } => {
-                for (k, v) in &**val {
-                    custom_headers.insert(k.to_string(), v.clone());
-                }
+    Ok(request)
+}
+
+/// Parses the value given to `--headers`, either a record, a single row table or a flat list of
+/// alternating keys and values.
+pub fn parse_custom_headers(headers: &Value) -> Result<HashMap<String, Value>, ShellError> {
+    let mut custom_headers: HashMap<String, Value> = HashMap::new();
+
+    match headers {
+        Value::Record { val, .. } => {
+            for (k, v) in &**val {
+                custom_headers.insert(k.to_string(), v.clone());
             }
+        }
 
-            Value::List { vals: table, .. } => {
-                if table.len() == 1 {
-                    // single row([key1 key2]; [val1 val2])
-                    match &table[0] {
-                        Value::Record { val, .. } => {
-                            for (k, v) in &**val {
-                                custom_headers.insert(k.to_string(), v.clone());
-                            }
+        Value::List { vals: table, .. } => {
+            if table.len() == 1 {
+                // single row([key1 key2]; [val1 val2])
+                match &table[0] {
+                    Value::Record { val, .. } => {
+                        for (k, v) in &**val {
+                            custom_headers.insert(k.to_string(), v.clone());
                         }
+                    }
 
-                        x => {
-                            return Err(ShellError::CantConvert {
-                                to_type: "string list or single row".into(),
-                                from_type: x.get_type().to_string(),
-                                span: headers.span(),
-                                help: None,
-                            });
-                        }
+                    x => {
+                        return Err(ShellError::CantConvert {
+                            to_type: "string list or single row".into(),
+                            from_type: x.get_type().to_string(),
+                            span: headers.span(),
+                            help: None,
+                        });
                     }
-                } else {
-                    // primitive values ([key1 val1 key2 val2])
-                    for row in table.chunks(2) {
-                        if row.len() == 2 {
-                            custom_headers.insert(row[0].coerce_string()?, row[1].clone());
-                        }
+                }
+            } else {
+                // primitive values ([key1 val1 key2 val2])
+                for row in table.chunks(2) {
+                    if row.len() == 2 {
+                        custom_headers.insert(row[0].coerce_string()?, row[1].clone());
                     }
                 }
             }
+        }
 
-            x => {
-                return Err(ShellError::CantConvert {
-                    to_type: "string list or single row".into(),
-                    from_type: x.get_type().to_string(),
-                    span: headers.span(),
-                    help: None,
-                });
-            }
-        };
-
-        for (k, v) in custom_headers {
-            if let Ok(s) = v.coerce_into_string() {
-                request = request.header(&k, &s);
-            }
+        x => {
+            return Err(ShellError::CantConvert {
+                to_type: "string list or single row".into(),
+                from_type: x.get_type().to_string(),
+                span: headers.span(),
+                help: None,
+            });
         }
-    }
+    };
 
-    Ok(request)
+    Ok(custom_headers)
 }
 
 fn handle_status_error(span: Span, requested_url: &str, status: StatusCode) -> ShellError {
@@ -0,0 +1,107 @@
+use cookie_store::CookieStore;
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::io::IoError;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+use ureq::{Body, RequestBuilder, ResponseExt};
+use url::Url;
+
+/// Helper function to add the --cookie-jar flag to command signatures.
+pub fn add_cookie_jar_flag(sig: Signature) -> Signature {
+    sig.named(
+        "cookie-jar",
+        SyntaxShape::Filepath,
+        "Read cookies from and store cookies received into this file.",
+        None,
+    )
+}
+
+/// A cookie store persisted to a file, so that cookies survive across http commands.
+///
+/// The file is read before the request is sent and rewritten after the response arrived. Session
+/// cookies are kept as well, since a jar is typically used to carry a login over to later calls.
+pub struct CookieJar {
+    path: PathBuf,
+    store: CookieStore,
+}
+
+impl CookieJar {
+    /// Opens the jar at `path`, starting with an empty jar if the file doesn't exist yet.
+    pub fn open(
+        path: Option<Spanned<String>>,
+        cwd: impl AsRef<Path>,
+    ) -> Result<Option<Self>, ShellError> {
+        let Some(Spanned { item, span }) = path else {
+            return Ok(None);
+        };
+        let path = expand_path_with(item, cwd.as_ref(), true);
+
+        let store = match File::open(&path) {
+            Ok(file) => cookie_store::serde::json::load_all(BufReader::new(file))
+                .map_err(|err| invalid_jar(&path, err.to_string(), span))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => CookieStore::default(),
+            Err(err) => return Err(IoError::new(err, span, path).into()),
+        };
+
+        Ok(Some(CookieJar { path, store }))
+    }
+
+    /// Adds the cookies matching `url` as a `Cookie` header.
+    pub fn add_request_cookies<B>(
+        &self,
+        url: &Url,
+        request: RequestBuilder<B>,
+    ) -> RequestBuilder<B> {
+        let cookies = self
+            .store
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+
+        if cookies.is_empty() {
+            request
+        } else {
+            request.header("Cookie", &cookies.join("; "))
+        }
+    }
+
+    /// Stores the cookies set by `response` and writes the jar back to its file.
+    ///
+    /// Only the final response is inspected, cookies set while following redirects are not seen.
+    pub fn store_response_cookies(
+        &mut self,
+        response: &http::Response<Body>,
+        span: Span,
+    ) -> Result<(), ShellError> {
+        if let Ok(url) = Url::parse(&response.get_uri().to_string()) {
+            for header in response.headers().get_all("set-cookie") {
+                if let Ok(cookie) = header.to_str() {
+                    // Invalid cookies are ignored, just like a browser would.
+                    let _ = self.store.parse(cookie, &url);
+                }
+            }
+        }
+
+        let file =
+            File::create(&self.path).map_err(|err| IoError::new(err, span, self.path.clone()))?;
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(
+            &self.store,
+            &mut BufWriter::new(file),
+        )
+        .map_err(|err| invalid_jar(&self.path, err.to_string(), span))
+    }
+}
+
+fn invalid_jar(path: &Path, msg: String, span: Span) -> ShellError {
+    ShellError::GenericError {
+        error: format!("Invalid cookie jar: {}", path.display()),
+        msg,
+        span: Some(span),
+        help: Some("The cookie jar must be a file written by the --cookie-jar flag.".into()),
+        inner: vec![],
+    }
+}
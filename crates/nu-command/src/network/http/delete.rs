@@ -1,10 +1,13 @@
 use crate::network::http::client::{
-    HttpBody, RedirectMode, RequestFlags, RequestMetadata, add_unix_socket_flag,
-    check_response_redirection, expand_unix_socket_path, http_client, http_client_pool,
-    http_parse_redirect_mode, http_parse_url, request_add_authorization_header,
-    request_add_custom_headers, request_handle_response, request_set_timeout, send_request,
-    send_request_no_body,
+    HttpBody, RedirectMode, RequestFlags, RequestMetadata, RetryPolicy, add_retry_flags,
+    add_unix_socket_flag, check_response_redirection, expand_unix_socket_path, http_client,
+    http_client_pool, http_parse_redirect_mode, http_parse_url, request_add_authorization_header,
+    request_add_custom_headers, request_handle_response, request_set_timeout, send_request_no_body,
+    send_request_with_retry, send_with_retry,
 };
+use crate::network::http::cookie_jar::{CookieJar, add_cookie_jar_flag};
+use crate::network::http::proxy::{ProxySettings, add_proxy_flags};
+use crate::network::http::session::{HttpSessionValue, add_session_flag};
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -89,7 +92,9 @@ impl Command for HttpDelete {
             .filter()
             .category(Category::Network);
 
-        add_unix_socket_flag(sig)
+        add_session_flag(add_cookie_jar_flag(add_retry_flags(add_proxy_flags(
+            add_unix_socket_flag(sig),
+        ))))
     }
 
     fn description(&self) -> &str {
@@ -170,6 +175,10 @@ struct Arguments {
     redirect: Option<Spanned<String>>,
     unix_socket: Option<Spanned<String>>,
     pool: bool,
+    retry: RetryPolicy,
+    cookie_jar: Option<Spanned<String>>,
+    session: Option<HttpSessionValue>,
+    proxy: ProxySettings,
 }
 
 fn run_delete(
@@ -207,6 +216,10 @@ fn run_delete(
         redirect: call.get_flag(engine_state, stack, "redirect-mode")?,
         unix_socket: call.get_flag(engine_state, stack, "unix-socket")?,
         pool: call.has_flag(engine_state, stack, "pool")?,
+        retry: RetryPolicy::from_call(engine_state, stack, call)?,
+        cookie_jar: call.get_flag(engine_state, stack, "cookie-jar")?,
+        session: HttpSessionValue::from_call(engine_state, stack, call)?,
+        proxy: ProxySettings::from_call(engine_state, stack, call)?,
     };
 
     helper(engine_state, stack, call, args)
@@ -218,58 +231,94 @@ fn helper(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    args: Arguments,
+    mut args: Arguments,
 ) -> Result<PipelineData, ShellError> {
+    let mut session_agent = None;
+    if let Some(session) = &args.session {
+        session.apply_defaults(
+            &mut args.url,
+            &mut args.headers,
+            &mut args.user,
+            &mut args.password,
+            &mut args.timeout,
+        )?;
+        session_agent = session.connect_with(
+            &mut args.insecure,
+            &mut args.redirect,
+            &mut args.unix_socket,
+        );
+    }
+
     let span = args.url.span();
     let Spanned {
-        item: (requested_url, _),
+        item: (requested_url, url),
         span: request_span,
     } = http_parse_url(call, span, args.url)?;
     let redirect_mode = http_parse_redirect_mode(args.redirect)?;
 
     let cwd = engine_state.cwd(None)?;
     let unix_socket_path = expand_unix_socket_path(args.unix_socket, &cwd);
+    let mut cookie_jar = CookieJar::open(args.cookie_jar, &cwd)?;
 
-    let mut request = if args.pool {
-        http_client_pool(engine_state, stack)?.delete(&requested_url)
-    } else {
-        let client = http_client(
-            args.insecure,
-            redirect_mode,
-            unix_socket_path,
-            engine_state,
-            stack,
-        )?;
-        client.delete(&requested_url)
+    let mut build_request = || -> Result<_, ShellError> {
+        let mut request = if let Some(agent) = &session_agent {
+            agent.delete(&requested_url)
+        } else if args.pool {
+            http_client_pool(engine_state, stack)?.delete(&requested_url)
+        } else {
+            let client = http_client(
+                args.insecure,
+                redirect_mode,
+                unix_socket_path.clone(),
+                engine_state,
+                stack,
+            )?;
+            client.delete(&requested_url)
+        };
+        request = args.proxy.apply(&url, request);
+        request = request_set_timeout(args.timeout.clone(), request)?;
+        request =
+            request_add_authorization_header(args.user.clone(), args.password.clone(), request);
+        if let Some(jar) = &cookie_jar {
+            request = jar.add_request_cookies(&url, request);
+        }
+        request_add_custom_headers(args.headers.clone(), request)
     };
-    request = request_set_timeout(args.timeout, request)?;
-    request = request_add_authorization_header(args.user, args.password, request);
-    request = request_add_custom_headers(args.headers, request)?;
     let (response, request_headers) = match args.data {
-        None => send_request_no_body(request, request_span, call.head, engine_state.signals()),
+        None => send_with_retry(&args.retry, call.head, engine_state.signals(), || {
+            Ok(send_request_no_body(
+                build_request()?,
+                request_span,
+                call.head,
+                engine_state.signals(),
+            ))
+        })?,
 
-        Some(body) => send_request(
+        Some(body) => send_request_with_retry(
             engine_state,
+            &args.retry,
             // Nushell allows sending body via delete method, but not via get.
             // We should probably unify the behaviour here.
             //
             // Sending body with DELETE goes against the spec, but might be useful in some cases,
             // see [force_send_body] documentation.
-            request.force_send_body(),
+            || Ok(build_request()?.force_send_body()),
             request_span,
             body,
             args.content_type,
             span,
             engine_state.signals(),
-        ),
+        )?,
     };
+    if let Some(jar) = &mut cookie_jar {
+        jar.store_response_cookies(&response, span)?;
+    }
 
     let request_flags = RequestFlags {
         raw: args.raw,
         full: args.full,
         allow_errors: args.allow_errors,
     };
-    let response = response?;
 
     check_response_redirection(redirect_mode, span, &response)?;
     request_handle_response(
@@ -0,0 +1,413 @@
+use crate::network::http::{
+    client::{GetHeader, send_request_no_body},
+    timeout_extractor_reader::UreqTimeoutExtractorReader,
+};
+use http::StatusCode;
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::{Signals, byte_stream::copy_with_signals, shell_error::io::IoError};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    thread,
+};
+use ureq::{Body, RequestBuilder, typestate::WithoutBody};
+
+type Response = http::Response<Body>;
+
+/// Splitting a download further than this isn't worth the extra connections.
+const MIN_SEGMENT_SIZE: u64 = 64 * 1024;
+
+/// Helper function to add the --output, --resume, --parallel and --expect-sha256 flags to
+/// command signatures.
+pub fn add_download_flags(sig: Signature) -> Signature {
+    sig.named(
+        "output",
+        SyntaxShape::Filepath,
+        "Save the response body to this file instead of returning it.",
+        Some('o'),
+    )
+    .switch(
+        "resume",
+        "Continue a partially downloaded --output file instead of starting over.",
+        None,
+    )
+    .named(
+        "parallel",
+        SyntaxShape::Int,
+        "Download the --output file in this many segments at once, if the server supports \
+         range requests.",
+        None,
+    )
+    .named(
+        "expect-sha256",
+        SyntaxShape::String,
+        "Fail and remove the --output file if its SHA-256 checksum doesn't match this one.",
+        None,
+    )
+}
+
+/// Where and how to save a response, built from the flags added by [`add_download_flags`].
+pub struct DownloadOptions {
+    path: PathBuf,
+    span: Span,
+    /// The size of the partial file to continue, if resuming.
+    existing: u64,
+    parallel: u64,
+    expect_sha256: Option<Spanned<String>>,
+}
+
+impl DownloadOptions {
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Option<Self>, ShellError> {
+        let resume = call.has_flag(engine_state, stack, "resume")?;
+        let parallel: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "parallel")?;
+        let expect_sha256: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "expect-sha256")?;
+
+        let Some(output) = call.get_flag::<Spanned<String>>(engine_state, stack, "output")? else {
+            if resume || parallel.is_some() || expect_sha256.is_some() {
+                return Err(ShellError::MissingParameter {
+                    param_name: "output".into(),
+                    span: call.head,
+                });
+            }
+            return Ok(None);
+        };
+
+        let parallel = match parallel {
+            Some(Spanned { item, span }) => match u64::try_from(item) {
+                Ok(parallel) if parallel > 0 => parallel,
+                _ => {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "Number of parallel segments must be at least 1".into(),
+                        val_span: span,
+                        call_span: call.head,
+                    });
+                }
+            },
+            None => 1,
+        };
+
+        if let Some(expected) = &expect_sha256
+            && (expected.item.len() != 64 || !expected.item.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(ShellError::IncorrectValue {
+                msg: "Expected a SHA-256 checksum of 64 hexadecimal digits".into(),
+                val_span: expected.span,
+                call_span: call.head,
+            });
+        }
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let path = expand_path_with(output.item, cwd, true);
+        let existing = if resume {
+            match fs::metadata(&path) {
+                Ok(metadata) => metadata.len(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+                Err(err) => return Err(IoError::new(err, output.span, path).into()),
+            }
+        } else {
+            0
+        };
+
+        Ok(Some(DownloadOptions {
+            path,
+            span: output.span,
+            existing,
+            parallel,
+            expect_sha256,
+        }))
+    }
+
+    /// The `Range` header of the first request, asking for the rest of a partial file.
+    pub fn initial_range(&self) -> Option<String> {
+        (self.existing > 0).then(|| format!("bytes={}-", self.existing))
+    }
+
+    /// Whether `response` says that the partial file is complete already, in which case it
+    /// isn't an error.
+    pub fn already_complete(&self, response: &Response) -> bool {
+        self.existing > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE
+    }
+
+    /// Saves the body of `response`, the answer to a request with [`initial_range`].
+    ///
+    /// When downloading in parallel, the first segment is read from `response` and the others
+    /// are requested with `build_request`. They are saved next to the file until all of them are
+    /// complete, so an interrupted download can always be resumed from the end of the file.
+    ///
+    /// [`initial_range`]: Self::initial_range
+    pub fn download(
+        &self,
+        response: Response,
+        mut build_request: impl FnMut(&str) -> Result<RequestBuilder<WithoutBody>, ShellError>,
+        request_span: Span,
+        span: Span,
+        signals: &Signals,
+    ) -> Result<PipelineData, ShellError> {
+        let io_err =
+            |err: io::Error| ShellError::from(IoError::new(err, self.span, self.path.clone()));
+
+        if self.already_complete(&response) {
+            return self.verify(signals);
+        }
+
+        let (start, total) = match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                let (start, _, total) = response
+                    .header("content-range")
+                    .and_then(parse_content_range)
+                    .ok_or_else(|| ShellError::NetworkFailure {
+                        msg: "Server sent a partial response without a valid Content-Range".into(),
+                        span,
+                    })?;
+                if start != self.existing {
+                    return Err(ShellError::NetworkFailure {
+                        msg: format!(
+                            "Server continued the download at byte {start} instead of {}",
+                            self.existing
+                        ),
+                        span,
+                    });
+                }
+                (start, total)
+            }
+            // the server ignored the range and sends everything again
+            _ => (0, content_length(&response)),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&self.path)
+            .map_err(io_err)?;
+        file.set_len(start).map_err(io_err)?;
+        file.seek(SeekFrom::End(0)).map_err(io_err)?;
+
+        let segments = match total {
+            Some(total) if self.parallel > 1 && accepts_ranges(&response) => {
+                split_segments(start, total, self.parallel)
+            }
+            _ => vec![],
+        };
+
+        let reader = UreqTimeoutExtractorReader {
+            r: response.into_body().into_reader(),
+        };
+        let Some(((_, first_end), rest)) = segments.split_first() else {
+            copy_with_signals(reader, &mut file, span, signals)?;
+            return self.verify(signals);
+        };
+
+        // the requests are built up front, as building them may need the engine's state
+        let requests = rest
+            .iter()
+            .map(|(from, to)| Ok((*from, *to, build_request(&format!("bytes={from}-{to}"))?)))
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        let parts = thread::scope(|scope| {
+            let handles = requests
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (from, to, request))| {
+                    let part = self.part_path(idx + 1);
+                    scope.spawn(move || {
+                        let result =
+                            download_segment(request, from, to, &part, request_span, span, signals);
+                        (part, result)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let first_len = *first_end + 1 - start;
+            let first = copy_with_signals(reader.take(first_len), &mut file, span, signals)
+                .and_then(|written| check_segment_len(written, start, *first_end, span));
+
+            let parts = handles
+                .into_iter()
+                .map(|handle| match handle.join() {
+                    Ok(part) => part,
+                    Err(_) => (
+                        PathBuf::new(),
+                        Err(ShellError::NushellFailed {
+                            msg: "Download thread panicked".into(),
+                        }),
+                    ),
+                })
+                .collect::<Vec<_>>();
+            (first, parts)
+        });
+
+        let (first, parts) = parts;
+        let result = first.and_then(|_| {
+            for (part, result) in &parts {
+                result.clone()?;
+                let mut part = File::open(part).map_err(io_err)?;
+                copy_with_signals(&mut part, &mut file, span, signals)?;
+            }
+            Ok(())
+        });
+        for (part, _) in &parts {
+            let _ = fs::remove_file(part);
+        }
+        result?;
+
+        self.verify(signals)
+    }
+
+    fn part_path(&self, idx: usize) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".part{idx}"));
+        self.path.with_file_name(name)
+    }
+
+    /// Checks the checksum of the complete file, removing it if it doesn't match.
+    fn verify(&self, signals: &Signals) -> Result<PipelineData, ShellError> {
+        let Some(expected) = &self.expect_sha256 else {
+            return Ok(PipelineData::empty());
+        };
+
+        let file = File::open(&self.path)
+            .map_err(|err| IoError::new(err, self.span, self.path.clone()))?;
+        let mut hasher = Sha256::new();
+        copy_with_signals(file, &mut hasher, self.span, signals)?;
+        let actual = format!("{:x}", hasher.finalize());
+
+        if actual.eq_ignore_ascii_case(&expected.item) {
+            Ok(PipelineData::empty())
+        } else {
+            let _ = fs::remove_file(&self.path);
+            Err(ShellError::GenericError {
+                error: "Checksum mismatch".into(),
+                msg: format!("the downloaded file has the SHA-256 checksum {actual}"),
+                span: Some(expected.span),
+                help: Some(format!("{} was removed", self.path.display())),
+                inner: vec![],
+            })
+        }
+    }
+}
+
+fn download_segment(
+    request: RequestBuilder<WithoutBody>,
+    from: u64,
+    to: u64,
+    part: &Path,
+    request_span: Span,
+    span: Span,
+    signals: &Signals,
+) -> Result<(), ShellError> {
+    let (response, _) = send_request_no_body(request, request_span, span, signals);
+    let response = response?;
+
+    let range = response
+        .header("content-range")
+        .and_then(parse_content_range);
+    if response.status() != StatusCode::PARTIAL_CONTENT
+        || range.is_none_or(|(start, ..)| start != from)
+    {
+        return Err(ShellError::NetworkFailure {
+            msg: format!("Server didn't send the requested bytes {from}-{to}"),
+            span,
+        });
+    }
+
+    let file = File::create(part).map_err(|err| IoError::new(err, span, part.to_path_buf()))?;
+    let reader = UreqTimeoutExtractorReader {
+        r: response.into_body().into_reader(),
+    };
+    let written = copy_with_signals(reader.take(to + 1 - from), file, span, signals)?;
+    check_segment_len(written, from, to, span)
+}
+
+fn check_segment_len(written: u64, from: u64, to: u64, span: Span) -> Result<(), ShellError> {
+    if written == to + 1 - from {
+        Ok(())
+    } else {
+        Err(ShellError::NetworkFailure {
+            msg: format!("Connection closed before bytes {from}-{to} were received"),
+            span,
+        })
+    }
+}
+
+fn content_length(response: &Response) -> Option<u64> {
+    response
+        .header("content-length")
+        .and_then(|len| len.trim().parse().ok())
+}
+
+fn accepts_ranges(response: &Response) -> bool {
+    response.status() == StatusCode::PARTIAL_CONTENT
+        || response
+            .header("accept-ranges")
+            .is_some_and(|ranges| ranges.trim().eq_ignore_ascii_case("bytes"))
+}
+
+/// Parses a `Content-Range` header like `bytes 100-199/1000` into the first and last byte and
+/// the total size, if the server knows it.
+fn parse_content_range(value: &str) -> Option<(u64, u64, Option<u64>)> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
+    let total = match total.trim() {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    (start <= end).then_some((start, end, total))
+}
+
+/// Splits the bytes from `start` up to `total` into at most `count` inclusive ranges.
+fn split_segments(start: u64, total: u64, count: u64) -> Vec<(u64, u64)> {
+    let remaining = total.saturating_sub(start);
+    let count = count.min(remaining / MIN_SEGMENT_SIZE);
+    if count < 2 {
+        return vec![];
+    }
+
+    let size = remaining.div_ceil(count);
+    (0..count)
+        .map(|idx| start + idx * size)
+        .take_while(|from| *from < total)
+        .map(|from| (from, (from + size).min(total) - 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/1000"),
+            Some((100, 199, Some(1000)))
+        );
+        assert_eq!(parse_content_range("bytes 0-0/*"), Some((0, 0, None)));
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("bytes 10-5/1000"), None);
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn test_split_segments() {
+        let mib = 1024 * 1024;
+        assert_eq!(
+            split_segments(0, 3 * mib, 3),
+            vec![(0, mib - 1), (mib, 2 * mib - 1), (2 * mib, 3 * mib - 1)]
+        );
+        assert_eq!(
+            split_segments(100, 100 + 2 * mib + 1, 2),
+            vec![(100, 100 + mib), (101 + mib, 100 + 2 * mib)]
+        );
+        // too small to be worth splitting
+        assert_eq!(split_segments(0, MIN_SEGMENT_SIZE, 4), vec![]);
+    }
+}
@@ -0,0 +1,95 @@
+use super::module_resolver::{fetch_module, locked_modules};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct HttpFetchModule;
+
+impl Command for HttpFetchModule {
+    fn name(&self) -> &str {
+        "http fetch-module"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("http fetch-module")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .rest(
+                "urls",
+                SyntaxShape::String,
+                "The URLs of the modules to download. Without any, every module in the lockfile is downloaded.",
+            )
+            .switch(
+                "frozen",
+                "Only download modules that are in the lockfile already.",
+                None,
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Download modules so that `use` can import them from their URLs."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The modules are kept in a cache directory, and the SHA-256 checksum of each one is recorded in the `nu-modules.lock` file in the current directory. A module that's in the lockfile already has to match its checksum.
+
+`use` only imports modules from URLs that are downloaded this way, as parsing it doesn't download anything."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["use", "import", "lockfile", "remote", "download"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let urls: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let frozen = call.has_flag(engine_state, stack, "frozen")?;
+        let lock_dir = engine_state.cwd(Some(stack))?.into_std_path_buf();
+
+        let urls = if urls.is_empty() {
+            locked_modules(&lock_dir, head)?
+                .into_iter()
+                .map(|url| url.into_spanned(head))
+                .collect()
+        } else {
+            urls
+        };
+
+        let fetched = urls
+            .into_iter()
+            .map(|url| {
+                let checksum =
+                    fetch_module(engine_state, stack, &url.item, &lock_dir, frozen, url.span)?;
+                Ok(Value::record(
+                    record! {
+                        "url" => Value::string(url.item, head),
+                        "sha256" => Value::string(checksum, head),
+                    },
+                    head,
+                ))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        Ok(Value::list(fetched, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Download a module and add it to the lockfile, to import it with `use https://example.com/module.nu`.",
+                example: "http fetch-module https://example.com/module.nu",
+                result: None,
+            },
+            Example {
+                description: "Download every module in the lockfile, failing if any of them changed.",
+                example: "http fetch-module --frozen",
+                result: None,
+            },
+        ]
+    }
+}
@@ -1,10 +1,17 @@
 use crate::network::http::client::{
-    RequestFlags, RequestMetadata, add_unix_socket_flag, check_response_redirection,
-    expand_unix_socket_path, http_client, http_client_pool, http_parse_redirect_mode,
-    http_parse_url, request_add_authorization_header, request_add_custom_headers,
-    request_handle_response, request_set_timeout, send_request_no_body,
+    RequestFlags, RequestMetadata, RetryPolicy, add_retry_flags, add_unix_socket_flag,
+    check_response_redirection, expand_unix_socket_path, handle_response_status, http_client,
+    http_client_pool, http_parse_redirect_mode, http_parse_url, request_add_authorization_header,
+    request_add_custom_headers, request_handle_response, request_set_timeout, send_request_no_body,
+    send_with_retry,
 };
+use crate::network::http::cookie_jar::{CookieJar, add_cookie_jar_flag};
+use crate::network::http::download::{DownloadOptions, add_download_flags};
+use crate::network::http::proxy::{ProxySettings, add_proxy_flags};
+use crate::network::http::session::{HttpSessionValue, add_session_flag};
+use crate::network::http::sse::response_to_sse_stream;
 use nu_engine::command_prelude::*;
+use ureq::{RequestBuilder, typestate::WithoutBody};
 
 use super::client::RedirectMode;
 
@@ -70,6 +77,12 @@ impl Command for HttpGet {
                 Some('e'),
             )
             .switch("pool", "Using a global pool as a client.", None)
+            .switch(
+                "sse",
+                "Read the response as server-sent events, streaming a record with event, data \
+                 and id for every event as it arrives.",
+                None,
+            )
             .param(
                 Flag::new("redirect-mode")
                     .short('R')
@@ -83,7 +96,9 @@ impl Command for HttpGet {
             .filter()
             .category(Category::Network);
 
-        add_unix_socket_flag(sig)
+        add_download_flags(add_session_flag(add_cookie_jar_flag(add_retry_flags(
+            add_proxy_flags(add_unix_socket_flag(sig)),
+        ))))
     }
 
     fn description(&self) -> &str {
@@ -142,6 +157,31 @@ impl Command for HttpGet {
                 example: r#"http get --allow-errors https://example.com/file | metadata access {|m| if $m.http_response.status != 200 { error make {msg: "failed"} } else { } } | lines"#,
                 result: None,
             },
+            Example {
+                description: "Retry up to 3 times when the server is overloaded or rate limiting.",
+                example: "http get --retries 3 --retry-delay 500ms --retry-on [429 503] https://www.example.com",
+                result: None,
+            },
+            Example {
+                description: "Log in and reuse the cookies set by the server in a later request.",
+                example: "http post --cookie-jar cookies.json https://www.example.com/login {user: me}; http get --cookie-jar cookies.json https://www.example.com/profile",
+                result: None,
+            },
+            Example {
+                description: "Get content through an authenticated SOCKS5 proxy, except for internal hosts.",
+                example: "http get --proxy socks5://proxy.example.com:1080 --proxy-user me --proxy-password secret --no-proxy [.internal localhost] https://www.example.com",
+                result: None,
+            },
+            Example {
+                description: "Print the data of server-sent events as they arrive.",
+                example: "http get --sse https://www.example.com/events | each {|event| print $event.data }",
+                result: None,
+            },
+            Example {
+                description: "Download a large file in 4 segments at once, continuing where an earlier attempt stopped, and check its checksum.",
+                example: "http get --output ubuntu.iso --resume --parallel 4 --expect-sha256 $checksum https://www.example.com/ubuntu.iso",
+                result: None,
+            },
             Example {
                 description: "Get from Docker daemon via Unix socket.",
                 example: "http get --unix-socket /var/run/docker.sock http://localhost/containers/json",
@@ -164,6 +204,12 @@ struct Arguments {
     redirect: Option<Spanned<String>>,
     unix_socket: Option<Spanned<String>>,
     pool: bool,
+    retry: RetryPolicy,
+    cookie_jar: Option<Spanned<String>>,
+    session: Option<HttpSessionValue>,
+    proxy: ProxySettings,
+    sse: bool,
+    download: Option<DownloadOptions>,
 }
 
 pub fn run_get(
@@ -185,6 +231,12 @@ pub fn run_get(
         redirect: call.get_flag(engine_state, stack, "redirect-mode")?,
         unix_socket: call.get_flag(engine_state, stack, "unix-socket")?,
         pool: call.has_flag(engine_state, stack, "pool")?,
+        retry: RetryPolicy::from_call(engine_state, stack, call)?,
+        cookie_jar: call.get_flag(engine_state, stack, "cookie-jar")?,
+        session: HttpSessionValue::from_call(engine_state, stack, call)?,
+        proxy: ProxySettings::from_call(engine_state, stack, call)?,
+        sse: call.has_flag(engine_state, stack, "sse")?,
+        download: DownloadOptions::from_call(engine_state, stack, call)?,
     };
     helper(engine_state, stack, call, args)
 }
@@ -195,36 +247,114 @@ fn helper(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    args: Arguments,
+    mut args: Arguments,
 ) -> Result<PipelineData, ShellError> {
+    let mut session_agent = None;
+    if let Some(session) = &args.session {
+        session.apply_defaults(
+            &mut args.url,
+            &mut args.headers,
+            &mut args.user,
+            &mut args.password,
+            &mut args.timeout,
+        )?;
+        session_agent = session.connect_with(
+            &mut args.insecure,
+            &mut args.redirect,
+            &mut args.unix_socket,
+        );
+    }
+
     let span = args.url.span();
     let Spanned {
-        item: (requested_url, _),
+        item: (requested_url, url),
         span: request_span,
     } = http_parse_url(call, span, args.url)?;
     let redirect_mode = http_parse_redirect_mode(args.redirect)?;
 
     let cwd = engine_state.cwd(None)?;
     let unix_socket_path = expand_unix_socket_path(args.unix_socket, &cwd);
+    let mut cookie_jar = CookieJar::open(args.cookie_jar, &cwd)?;
 
-    let mut request = if args.pool {
-        http_client_pool(engine_state, stack)?.get(&requested_url)
-    } else {
-        let client = http_client(
-            args.insecure,
-            redirect_mode,
-            unix_socket_path,
-            engine_state,
-            stack,
-        )?;
-        client.get(&requested_url)
+    let mut build_request = |range: Option<&str>,
+                             cookie_jar: &Option<CookieJar>|
+     -> Result<RequestBuilder<WithoutBody>, ShellError> {
+        let mut request = if let Some(agent) = &session_agent {
+            agent.get(&requested_url)
+        } else if args.pool {
+            http_client_pool(engine_state, stack)?.get(&requested_url)
+        } else {
+            let client = http_client(
+                args.insecure,
+                redirect_mode,
+                unix_socket_path.clone(),
+                engine_state,
+                stack,
+            )?;
+            client.get(&requested_url)
+        };
+
+        request = args.proxy.apply(&url, request);
+        request = request_set_timeout(args.timeout.clone(), request)?;
+        request =
+            request_add_authorization_header(args.user.clone(), args.password.clone(), request);
+        if let Some(jar) = cookie_jar {
+            request = jar.add_request_cookies(&url, request);
+        }
+        if args.sse {
+            request = request.header("Accept", "text/event-stream");
+        }
+        if let Some(range) = range {
+            request = request.header("Range", range);
+        }
+        request_add_custom_headers(args.headers.clone(), request)
     };
 
-    request = request_set_timeout(args.timeout, request)?;
-    request = request_add_authorization_header(args.user, args.password, request);
-    request = request_add_custom_headers(args.headers, request)?;
+    let initial_range = args.download.as_ref().and_then(|d| d.initial_range());
     let (response, request_headers) =
-        send_request_no_body(request, request_span, call.head, engine_state.signals());
+        send_with_retry(&args.retry, call.head, engine_state.signals(), || {
+            Ok(send_request_no_body(
+                build_request(initial_range.as_deref(), &cookie_jar)?,
+                request_span,
+                call.head,
+                engine_state.signals(),
+            ))
+        })?;
+    if let Some(jar) = &mut cookie_jar {
+        jar.store_response_cookies(&response, span)?;
+    }
+
+    if let Some(download) = &args.download {
+        check_response_redirection(redirect_mode, span, &response)?;
+        if !download.already_complete(&response) {
+            handle_response_status(
+                &response,
+                redirect_mode,
+                &requested_url,
+                span,
+                args.allow_errors,
+            )?;
+        }
+        return download.download(
+            response,
+            |range| build_request(Some(range), &cookie_jar),
+            request_span,
+            call.head,
+            engine_state.signals(),
+        );
+    }
+
+    if args.sse {
+        check_response_redirection(redirect_mode, span, &response)?;
+        handle_response_status(
+            &response,
+            redirect_mode,
+            &requested_url,
+            span,
+            args.allow_errors,
+        )?;
+        return Ok(response_to_sse_stream(response, engine_state, span));
+    }
 
     let request_flags = RequestFlags {
         raw: args.raw,
@@ -232,8 +362,6 @@ fn helper(
         allow_errors: args.allow_errors,
     };
 
-    let response = response?;
-
     check_response_redirection(redirect_mode, span, &response)?;
     request_handle_response(
         engine_state,
@@ -1,9 +1,13 @@
 use crate::network::http::client::{
-    RedirectMode, add_unix_socket_flag, check_response_redirection, expand_unix_socket_path,
-    extract_response_headers, handle_response_status, headers_to_nu, http_client, http_client_pool,
-    http_parse_redirect_mode, http_parse_url, request_add_authorization_header,
-    request_add_custom_headers, request_set_timeout, send_request_no_body,
+    RedirectMode, RetryPolicy, add_retry_flags, add_unix_socket_flag, check_response_redirection,
+    expand_unix_socket_path, extract_response_headers, handle_response_status, headers_to_nu,
+    http_client, http_client_pool, http_parse_redirect_mode, http_parse_url,
+    request_add_authorization_header, request_add_custom_headers, request_set_timeout,
+    send_request_no_body, send_with_retry,
 };
+use crate::network::http::cookie_jar::{CookieJar, add_cookie_jar_flag};
+use crate::network::http::proxy::{ProxySettings, add_proxy_flags};
+use crate::network::http::session::{HttpSessionValue, add_session_flag};
 use nu_engine::command_prelude::*;
 use nu_protocol::Signals;
 
@@ -67,7 +71,9 @@ impl Command for HttpHead {
             .filter()
             .category(Category::Network);
 
-        add_unix_socket_flag(sig)
+        add_session_flag(add_cookie_jar_flag(add_retry_flags(add_proxy_flags(
+            add_unix_socket_flag(sig),
+        ))))
     }
 
     fn description(&self) -> &str {
@@ -128,6 +134,10 @@ struct Arguments {
     redirect: Option<Spanned<String>>,
     unix_socket: Option<Spanned<String>>,
     pool: bool,
+    retry: RetryPolicy,
+    cookie_jar: Option<Spanned<String>>,
+    session: Option<HttpSessionValue>,
+    proxy: ProxySettings,
 }
 
 fn run_head(
@@ -146,6 +156,10 @@ fn run_head(
         redirect: call.get_flag(engine_state, stack, "redirect-mode")?,
         unix_socket: call.get_flag(engine_state, stack, "unix-socket")?,
         pool: call.has_flag(engine_state, stack, "pool")?,
+        retry: RetryPolicy::from_call(engine_state, stack, call)?,
+        cookie_jar: call.get_flag(engine_state, stack, "cookie-jar")?,
+        session: HttpSessionValue::from_call(engine_state, stack, call)?,
+        proxy: ProxySettings::from_call(engine_state, stack, call)?,
     };
 
     helper(engine_state, stack, call, args, engine_state.signals())
@@ -156,39 +170,69 @@ fn helper(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    args: Arguments,
+    mut args: Arguments,
     signals: &Signals,
 ) -> Result<PipelineData, ShellError> {
+    let mut session_agent = None;
+    if let Some(session) = &args.session {
+        session.apply_defaults(
+            &mut args.url,
+            &mut args.headers,
+            &mut args.user,
+            &mut args.password,
+            &mut args.timeout,
+        )?;
+        session_agent = session.connect_with(
+            &mut args.insecure,
+            &mut args.redirect,
+            &mut args.unix_socket,
+        );
+    }
+
     let span = args.url.span();
     let Spanned {
-        item: (requested_url, _),
+        item: (requested_url, url),
         span: request_span,
     } = http_parse_url(call, span, args.url)?;
     let redirect_mode = http_parse_redirect_mode(args.redirect)?;
 
     let cwd = engine_state.cwd(None)?;
     let unix_socket_path = expand_unix_socket_path(args.unix_socket, &cwd);
-
-    let mut request = if args.pool {
-        http_client_pool(engine_state, stack)?.head(&requested_url)
-    } else {
-        let client = http_client(
-            args.insecure,
-            redirect_mode,
-            unix_socket_path,
-            engine_state,
-            stack,
-        )?;
-        client.head(&requested_url)
-    };
-
-    request = request_set_timeout(args.timeout, request)?;
-    request = request_add_authorization_header(args.user, args.password, request);
-    request = request_add_custom_headers(args.headers, request)?;
-
-    let (response, _request_headers) =
-        send_request_no_body(request, request_span, call.head, signals);
-    let response = response?;
+    let mut cookie_jar = CookieJar::open(args.cookie_jar, &cwd)?;
+
+    let (response, _request_headers) = send_with_retry(&args.retry, call.head, signals, || {
+        let mut request = if let Some(agent) = &session_agent {
+            agent.head(&requested_url)
+        } else if args.pool {
+            http_client_pool(engine_state, stack)?.head(&requested_url)
+        } else {
+            let client = http_client(
+                args.insecure,
+                redirect_mode,
+                unix_socket_path.clone(),
+                engine_state,
+                stack,
+            )?;
+            client.head(&requested_url)
+        };
+        request = args.proxy.apply(&url, request);
+        request = request_set_timeout(args.timeout.clone(), request)?;
+        request =
+            request_add_authorization_header(args.user.clone(), args.password.clone(), request);
+        if let Some(jar) = &cookie_jar {
+            request = jar.add_request_cookies(&url, request);
+        }
+        request = request_add_custom_headers(args.headers.clone(), request)?;
+        Ok(send_request_no_body(
+            request,
+            request_span,
+            call.head,
+            signals,
+        ))
+    })?;
+    if let Some(jar) = &mut cookie_jar {
+        jar.store_response_cookies(&response, span)?;
+    }
     check_response_redirection(redirect_mode, span, &response)?;
     handle_response_status(&response, redirect_mode, &requested_url, span, false)?;
     headers_to_nu(&extract_response_headers(&response), span)
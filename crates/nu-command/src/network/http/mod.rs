@@ -1,24 +1,42 @@
+mod auth;
 mod client;
+mod cookie_jar;
 mod delete;
+mod download;
+mod fetch_module;
 mod get;
 mod head;
 mod http_;
 mod interruptible_tcp;
 mod interruptible_unix;
+mod module_resolver;
+mod oauth2;
 mod options;
 mod patch;
 mod pool;
 mod post;
+mod proxy;
 mod put;
+mod remote;
 mod resolver;
+mod serve;
+mod session;
+mod sse;
 mod timeout_extractor_reader;
 
+pub use auth::HttpAuth;
 pub use delete::HttpDelete;
+pub use fetch_module::HttpFetchModule;
 pub use get::HttpGet;
 pub use head::HttpHead;
 pub use http_::Http;
+pub use module_resolver::HttpModuleResolver;
+pub use oauth2::HttpAuthOauth2;
 pub use options::HttpOptions;
 pub use patch::HttpPatch;
 pub use pool::HttpPool;
 pub use post::HttpPost;
 pub use put::HttpPut;
+pub(crate) use remote::fetch_remote;
+pub use serve::HttpServe;
+pub use session::HttpSession;
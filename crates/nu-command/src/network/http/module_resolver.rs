@@ -0,0 +1,198 @@
+//! Downloading the modules that `use` imports from URLs, and keeping track of them in a lockfile.
+//!
+//! Modules are only downloaded by `http fetch-module`, so that parsing `use` has no side effects.
+
+use super::fetch_remote;
+use nu_engine::command_prelude::*;
+use nu_protocol::{engine::RemoteModuleResolver, shell_error::io::IoError};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file that records the checksums of the modules imported from URLs
+const LOCKFILE_NAME: &str = "nu-modules.lock";
+
+/// Finds the local copies of modules imported from URLs in `$nu.cache-dir/modules`, where
+/// [`fetch_module`] puts them.
+///
+/// The cached files are named after the SHA-256 checksum of their contents, which is what the
+/// lockfile records for each URL, so the same contents are only stored once.
+pub struct HttpModuleResolver;
+
+impl RemoteModuleResolver for HttpModuleResolver {
+    fn locate(&self, url: &str, lock_dir: &Path, span: Span) -> Result<PathBuf, ShellError> {
+        let lock_path = lock_dir.join(LOCKFILE_NAME);
+        let lock = read_lockfile(&lock_path, span)?;
+        let Some(checksum) = lock.get(url) else {
+            return Err(ShellError::GenericError {
+                error: "Module is not in the lockfile".into(),
+                msg: format!("{url} has no checksum in {}", lock_path.display()),
+                span: Some(span),
+                help: Some(format!("run `http fetch-module {url}` to download it")),
+                inner: vec![],
+            });
+        };
+
+        let path = cache_path(checksum, span)?;
+        // A cached copy that was changed since is as good as a missing one
+        if fs::read(&path).is_ok_and(|contents| sha256(&contents) == *checksum) {
+            Ok(path)
+        } else {
+            Err(ShellError::GenericError {
+                error: "Module is not fetched".into(),
+                msg: format!("there's no copy of {url} in the cache"),
+                span: Some(span),
+                help: Some(format!(
+                    "run `http fetch-module` in {} to download the modules in its lockfile",
+                    lock_dir.display()
+                )),
+                inner: vec![],
+            })
+        }
+    }
+}
+
+/// Download the module at `url` into the cache, checking it against the lockfile in `lock_dir`,
+/// and return its checksum.
+///
+/// A module that isn't in the lockfile yet is added to it, unless `frozen` is set, in which case
+/// that's an error instead.
+pub(super) fn fetch_module(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    url: &str,
+    lock_dir: &Path,
+    frozen: bool,
+    span: Span,
+) -> Result<String, ShellError> {
+    let lock_path = lock_dir.join(LOCKFILE_NAME);
+    let mut lock = read_lockfile(&lock_path, span)?;
+    let expected = lock.get(url);
+    if expected.is_none() && frozen {
+        return Err(ShellError::GenericError {
+            error: "Module is not in the lockfile".into(),
+            msg: format!("{url} has no checksum in {}", lock_path.display()),
+            span: Some(span),
+            help: Some("fetch it once without --frozen to add it".into()),
+            inner: vec![],
+        });
+    }
+
+    let (data, _) = fetch_remote(engine_state, stack, url, span, span)?;
+    let contents = data.into_value(span)?.coerce_into_binary()?;
+    let checksum = sha256(&contents);
+    if let Some(expected) = expected
+        && *expected != checksum
+    {
+        return Err(ShellError::GenericError {
+            error: "Checksum mismatch".into(),
+            msg: format!(
+                "{url} has the SHA-256 checksum {checksum}, but {} expects {expected}",
+                lock_path.display()
+            ),
+            span: Some(span),
+            help: Some(format!(
+                "if the module was changed on purpose, remove it from {} to update it",
+                lock_path.display()
+            )),
+            inner: vec![],
+        });
+    }
+
+    let path = cache_path(&checksum, span)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| IoError::new(err, span, dir.to_path_buf()))?;
+    }
+    fs::write(&path, &contents).map_err(|err| IoError::new(err, span, path.clone()))?;
+    if expected.is_none() {
+        lock.insert(url.to_string(), checksum.clone());
+        write_lockfile(&lock_path, &lock, span)?;
+    }
+    Ok(checksum)
+}
+
+/// The URLs of the modules recorded in the lockfile in `lock_dir`.
+pub(super) fn locked_modules(lock_dir: &Path, span: Span) -> Result<Vec<String>, ShellError> {
+    let lock = read_lockfile(&lock_dir.join(LOCKFILE_NAME), span)?;
+    Ok(lock.into_keys().collect())
+}
+
+fn sha256(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn cache_path(checksum: &str, span: Span) -> Result<PathBuf, ShellError> {
+    let Some(cache_dir) = nu_path::cache_dir() else {
+        return Err(ShellError::GenericError {
+            error: "Can't find the cache directory".into(),
+            msg: "modules from URLs are kept in the cache directory".into(),
+            span: Some(span),
+            help: Some("set $env.XDG_CACHE_HOME to choose one".into()),
+            inner: vec![],
+        });
+    };
+    Ok(cache_dir
+        .join("nushell")
+        .join("modules")
+        .join(format!("{checksum}.nu"))
+        .into_std_path_buf())
+}
+
+/// Reads the checksums of the modules by their URLs, which is empty if there is no lockfile yet.
+fn read_lockfile(path: &Path, span: Span) -> Result<BTreeMap<String, String>, ShellError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(IoError::new(err, span, path.to_path_buf()).into()),
+    };
+    serde_json::from_str(&contents).map_err(|err| ShellError::GenericError {
+        error: "Invalid lockfile".into(),
+        msg: format!("{} is not a record of checksums: {err}", path.display()),
+        span: Some(span),
+        help: Some("remove the file to record the checksums again".into()),
+        inner: vec![],
+    })
+}
+
+fn write_lockfile(
+    path: &Path,
+    lock: &BTreeMap<String, String>,
+    span: Span,
+) -> Result<(), ShellError> {
+    let contents = serde_json::to_string_pretty(lock).map_err(|err| ShellError::GenericError {
+        error: "Can't write the lockfile".into(),
+        msg: err.to_string(),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    })?;
+    fs::write(path, contents + "\n")
+        .map_err(|err| IoError::new(err, span, path.to_path_buf()).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_lockfile() {
+        let dir = tempfile::tempdir().expect("temporary directory");
+        let path = dir.path().join(LOCKFILE_NAME);
+        let span = Span::test_data();
+
+        assert_eq!(read_lockfile(&path, span).ok(), Some(BTreeMap::new()));
+
+        let lock = BTreeMap::from([(
+            "https://example.com/module.nu".to_string(),
+            sha256(b"export def foo [] {}"),
+        )]);
+        assert!(write_lockfile(&path, &lock, span).is_ok());
+        assert_eq!(read_lockfile(&path, span).ok(), Some(lock));
+
+        assert!(fs::write(&path, "[1, 2, 3]").is_ok());
+        assert!(read_lockfile(&path, span).is_err());
+    }
+}
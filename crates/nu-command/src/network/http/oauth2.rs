@@ -0,0 +1,645 @@
+use crate::network::http::{
+    client::{
+        HttpBody, RedirectMode, http_client, request_add_authorization_header, send_request,
+        send_request_no_body, sleep_interruptible,
+    },
+    session::{HttpSessionValue, TYPE_NAME},
+};
+use chrono::{DateTime, Utc};
+use nu_engine::command_prelude::*;
+use nu_protocol::Signals;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use ureq::Agent;
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Seconds before it expires that a cached token is renewed, so it doesn't run out mid-request.
+const EXPIRY_MARGIN: i64 = 60;
+/// The polling interval of the device flow when the server doesn't name one, per RFC 8628.
+const DEFAULT_POLL_INTERVAL: u64 = 5;
+
+#[derive(Clone)]
+pub struct HttpAuthOauth2;
+
+impl Command for HttpAuthOauth2 {
+    fn name(&self) -> &str {
+        "http auth oauth2"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("http auth oauth2")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::custom(TYPE_NAME), Type::custom(TYPE_NAME)),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "URL",
+                SyntaxShape::String,
+                "The token endpoint of the authorization server, or its issuer URL with --discover.",
+            )
+            .required_named(
+                "client-id",
+                SyntaxShape::String,
+                "The ID of the client registered with the authorization server.",
+                Some('c'),
+            )
+            .named(
+                "client-secret",
+                SyntaxShape::String,
+                "The secret of the client, sent using HTTP basic authentication.",
+                Some('s'),
+            )
+            .named(
+                "scope",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "The scopes to request.",
+                None,
+            )
+            .named(
+                "params",
+                SyntaxShape::Record(vec![]),
+                "Extra parameters to send along, like an audience or resource.",
+                Some('P'),
+            )
+            .switch(
+                "device",
+                "Sign in a user with the device flow instead of using the client credentials.",
+                Some('d'),
+            )
+            .named(
+                "device-url",
+                SyntaxShape::String,
+                "The device authorization endpoint, if it isn't discovered. Implies --device.",
+                None,
+            )
+            .switch(
+                "discover",
+                "Look up the endpoints in the OpenID Connect configuration of the issuer.",
+                None,
+            )
+            .switch(
+                "no-cache",
+                "Always request a new token, without reading or writing the token cache.",
+                None,
+            )
+            .switch(
+                "full",
+                "Return the whole token as a record instead of only the access token.",
+                Some('f'),
+            )
+            .switch(
+                "insecure",
+                "Allow insecure server connections when using SSL.",
+                Some('k'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Get an OAuth2 access token using the client credentials or device flow."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Tokens are cached in the nushell cache directory until shortly before they expire, and \
+         renewed with their refresh token if the server handed one out, so running the command \
+         again only asks the user to sign in when it is really needed. The device flow prints a \
+         link and a code to enter there, then waits until the user signed in. Piping an `http \
+         session` into the command returns a copy of it that sends the token as a bearer token \
+         with every request."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "oidc", "openid", "token", "bearer", "login"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let url: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let client_id: String = call
+            .get_flag(engine_state, stack, "client-id")?
+            .ok_or_else(|| ShellError::MissingParameter {
+                param_name: "client-id".into(),
+                span: head,
+            })?;
+        let client_secret: Option<String> = call.get_flag(engine_state, stack, "client-secret")?;
+        let scope: Option<Vec<String>> = call.get_flag(engine_state, stack, "scope")?;
+        let params: Option<Record> = call.get_flag(engine_state, stack, "params")?;
+        let device_url: Option<String> = call.get_flag(engine_state, stack, "device-url")?;
+        let device = device_url.is_some() || call.has_flag(engine_state, stack, "device")?;
+        let discover = call.has_flag(engine_state, stack, "discover")?;
+        let no_cache = call.has_flag(engine_state, stack, "no-cache")?;
+        let full = call.has_flag(engine_state, stack, "full")?;
+        let insecure = call.has_flag(engine_state, stack, "insecure")?;
+
+        let session = match input.into_value(head)? {
+            Value::Nothing { .. } => None,
+            value => Some(HttpSessionValue::try_from_value(value)?),
+        };
+
+        // the scope and extra parameters go to whichever endpoint starts the flow
+        let mut form = vec![];
+        if let Some(scope) = scope {
+            form.push(("scope".to_string(), scope.join(" ")));
+        }
+        for (name, value) in params.unwrap_or_default() {
+            form.push((name, value.coerce_into_string()?));
+        }
+
+        let client = Client {
+            engine_state,
+            agent: http_client(insecure, RedirectMode::Follow, None, engine_state, stack)?,
+            client_id,
+            client_secret,
+            span: head,
+            signals: engine_state.signals().clone(),
+        };
+        let (token_url, device_url) = if discover {
+            let discovery = client.discover(&url.item)?;
+            (
+                discovery.token_endpoint,
+                device_url.or(discovery.device_authorization_endpoint),
+            )
+        } else {
+            (url.item, device_url)
+        };
+        if device && device_url.is_none() {
+            return Err(ShellError::GenericError {
+                error: "Unknown device authorization endpoint".into(),
+                msg: "the device flow needs the URL of the device authorization endpoint".into(),
+                span: Some(url.span),
+                help: Some(
+                    "Pass it with --device-url, or use --discover if the server supports OpenID \
+                     Connect discovery"
+                        .into(),
+                ),
+                inner: vec![],
+            });
+        }
+
+        let cache = match no_cache {
+            true => None,
+            false => TokenCache::new(&[
+                &token_url,
+                &client.client_id,
+                if device {
+                    "device"
+                } else {
+                    "client_credentials"
+                },
+                &format!("{form:?}"),
+            ]),
+        };
+        let token = match cache.as_ref().and_then(TokenCache::load) {
+            Some(token) if token.is_fresh(Utc::now().timestamp()) => token,
+            cached => {
+                let refreshed = match cached.and_then(|token| token.refresh_token) {
+                    Some(refresh_token) => client.refresh(&token_url, refresh_token)?,
+                    None => None,
+                };
+                let token = match (refreshed, device_url) {
+                    (Some(token), _) => token,
+                    (None, Some(device_url)) => {
+                        client.device_flow(&token_url, &device_url, form)?
+                    }
+                    (None, None) => client.client_credentials(&token_url, form)?,
+                };
+                if let Some(cache) = &cache {
+                    cache.save(&token);
+                }
+                token
+            }
+        };
+
+        let value = match session {
+            Some(session) => Value::custom(
+                Box::new(
+                    session.with_header("Authorization", format!("Bearer {}", token.access_token)),
+                ),
+                head,
+            ),
+            None if full => token.to_value(head),
+            None => Value::string(token.access_token, head),
+        };
+        Ok(value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get a token for a service account and use it for a request.",
+                example: "let token = http auth oauth2 https://auth.example.com/oauth/token --client-id $env.CLIENT_ID --client-secret $env.CLIENT_SECRET --scope [orders:read]; http get --headers {Authorization: $'Bearer ($token)'} https://api.example.com/orders",
+                result: None,
+            },
+            Example {
+                description: "Sign in as a user and attach the token to a session.",
+                example: "let api = http session --base-url https://api.example.com | http auth oauth2 https://login.example.com --discover --device --client-id nushell --scope [openid profile]; http get --session $api /me",
+                result: None,
+            },
+            Example {
+                description: "Show when the cached token expires.",
+                example: "http auth oauth2 https://auth.example.com/oauth/token --client-id $env.CLIENT_ID --client-secret $env.CLIENT_SECRET --full | get expires_at",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The endpoints that OpenID Connect discovery provides and that are needed here.
+#[derive(Deserialize)]
+struct Discovery {
+    token_endpoint: String,
+    device_authorization_endpoint: Option<String>,
+}
+
+/// A successful answer of the token endpoint, see RFC 6749, section 5.1.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: Option<String>,
+    // some servers send the lifetime as a string
+    expires_in: Option<serde_json::Value>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+    id_token: Option<String>,
+}
+
+/// An error answer of the token or device authorization endpoint, see RFC 6749, section 5.2.
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+impl ErrorResponse {
+    fn into_shell_error(self, span: Span) -> ShellError {
+        ShellError::GenericError {
+            error: "OAuth2 authorization failed".into(),
+            msg: match self.error_description {
+                Some(description) => format!("{}: {description}", self.error),
+                None => self.error,
+            },
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        }
+    }
+}
+
+/// The answer of the device authorization endpoint, see RFC 8628, section 3.2.
+#[derive(Deserialize)]
+struct DeviceAuthorization {
+    device_code: String,
+    user_code: String,
+    // Google calls it verification_url
+    #[serde(alias = "verification_url")]
+    verification_uri: String,
+    expires_in: u64,
+    interval: Option<u64>,
+}
+
+/// A token as it is cached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Token {
+    access_token: String,
+    token_type: Option<String>,
+    /// The unix timestamp the token expires at, if the server said so.
+    expires_at: Option<i64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+    id_token: Option<String>,
+}
+
+impl Token {
+    fn from_response(response: TokenResponse, now: i64) -> Self {
+        let expires_in = response.expires_in.and_then(|expires_in| match expires_in {
+            serde_json::Value::Number(number) => number.as_i64(),
+            serde_json::Value::String(string) => string.parse().ok(),
+            _ => None,
+        });
+        Token {
+            access_token: response.access_token,
+            token_type: response.token_type,
+            expires_at: expires_in.map(|expires_in| now + expires_in),
+            refresh_token: response.refresh_token,
+            scope: response.scope,
+            id_token: response.id_token,
+        }
+    }
+
+    fn is_fresh(&self, now: i64) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at - EXPIRY_MARGIN > now)
+    }
+
+    fn to_value(&self, span: Span) -> Value {
+        let string = |string: &Option<String>| {
+            string
+                .as_ref()
+                .map_or(Value::nothing(span), |string| Value::string(string, span))
+        };
+        let expires_at = self
+            .expires_at
+            .and_then(|expires_at| DateTime::<Utc>::from_timestamp(expires_at, 0))
+            .map_or(Value::nothing(span), |expires_at| {
+                Value::date(expires_at.fixed_offset(), span)
+            });
+
+        Value::record(
+            record! {
+                "access_token" => Value::string(&self.access_token, span),
+                "token_type" => string(&self.token_type),
+                "expires_at" => expires_at,
+                "scope" => string(&self.scope),
+                "refresh_token" => string(&self.refresh_token),
+                "id_token" => string(&self.id_token),
+            },
+            span,
+        )
+    }
+}
+
+/// Tokens cached on disk, one file per combination of server, client, flow and parameters.
+struct TokenCache {
+    path: PathBuf,
+}
+
+impl TokenCache {
+    fn new(key: &[&str]) -> Option<Self> {
+        let mut hasher = Sha256::new();
+        for part in key {
+            hasher.update(part.as_bytes());
+            hasher.update([0u8]);
+        }
+        let path = nu_path::cache_dir()?
+            .join("nushell")
+            .join("oauth2")
+            .join(format!("{:x}.json", hasher.finalize()))
+            .into_std_path_buf();
+        Some(TokenCache { path })
+    }
+
+    fn load(&self) -> Option<Token> {
+        let contents = std::fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Saves the token, readable only by the user. A token that can't be cached is simply
+    /// requested again next time, so errors are ignored.
+    fn save(&self, token: &Token) {
+        use std::io::Write;
+
+        let Some(dir) = self.path.parent() else {
+            return;
+        };
+        let Ok(contents) = serde_json::to_string(token) else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(dir);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        if let Ok(mut file) = options.open(&self.path) {
+            let _ = file.write_all(contents.as_bytes());
+        }
+    }
+}
+
+/// Talks to the authorization server on behalf of one client.
+struct Client<'a> {
+    engine_state: &'a EngineState,
+    agent: Agent,
+    client_id: String,
+    client_secret: Option<String>,
+    span: Span,
+    signals: Signals,
+}
+
+impl Client<'_> {
+    fn discover(&self, issuer: &str) -> Result<Discovery, ShellError> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        );
+        let request = self.agent.get(&url).header("Accept", "application/json");
+        let (response, _) = send_request_no_body(request, self.span, self.span, &self.signals);
+        let mut response = response?;
+        let body = read_body(&mut response, &url, self.span)?;
+        if !response.status().is_success() {
+            return Err(ShellError::NetworkFailure {
+                msg: format!(
+                    "Could not discover the endpoints of {issuer}, {url} answered with status {}",
+                    response.status()
+                ),
+                span: self.span,
+            });
+        }
+        parse(&body, &url, self.span)
+    }
+
+    fn client_credentials(
+        &self,
+        token_url: &str,
+        mut form: Vec<(String, String)>,
+    ) -> Result<Token, ShellError> {
+        form.push(("grant_type".into(), "client_credentials".into()));
+        self.token_request(token_url, form)?
+            .map_err(|err| err.into_shell_error(self.span))
+    }
+
+    /// Renews a token, returning `None` if the server doesn't accept the refresh token anymore.
+    fn refresh(&self, token_url: &str, refresh_token: String) -> Result<Option<Token>, ShellError> {
+        let form = vec![
+            ("grant_type".into(), "refresh_token".into()),
+            ("refresh_token".into(), refresh_token.clone()),
+        ];
+        Ok(self.token_request(token_url, form)?.ok().map(|mut token| {
+            // the server may keep using the same refresh token without sending it again
+            token.refresh_token.get_or_insert(refresh_token);
+            token
+        }))
+    }
+
+    fn device_flow(
+        &self,
+        token_url: &str,
+        device_url: &str,
+        form: Vec<(String, String)>,
+    ) -> Result<Token, ShellError> {
+        let (success, body) = self.post_form(device_url, form)?;
+        if !success {
+            return Err(
+                parse::<ErrorResponse>(&body, device_url, self.span)?.into_shell_error(self.span)
+            );
+        }
+        let authorization: DeviceAuthorization = parse(&body, device_url, self.span)?;
+
+        eprintln!(
+            "To sign in, open {} and enter the code {}",
+            authorization.verification_uri, authorization.user_code
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(authorization.expires_in);
+        let mut interval = authorization.interval.unwrap_or(DEFAULT_POLL_INTERVAL);
+        loop {
+            sleep_interruptible(Duration::from_secs(interval), self.span, &self.signals)?;
+            if Instant::now() >= deadline {
+                return Err(ShellError::GenericError {
+                    error: "OAuth2 authorization failed".into(),
+                    msg: "the code expired before the sign in was finished".into(),
+                    span: Some(self.span),
+                    help: Some("Run the command again to get a new code".into()),
+                    inner: vec![],
+                });
+            }
+
+            let form = vec![
+                ("grant_type".into(), DEVICE_CODE_GRANT.into()),
+                ("device_code".into(), authorization.device_code.clone()),
+            ];
+            match self.token_request(token_url, form)? {
+                Ok(token) => return Ok(token),
+                Err(err) if err.error == "authorization_pending" => {}
+                Err(err) if err.error == "slow_down" => interval += 5,
+                Err(err) => return Err(err.into_shell_error(self.span)),
+            }
+        }
+    }
+
+    /// Sends a request to the token endpoint, returning the error the server answered with
+    /// separately, so that the flows can decide what to do about it.
+    fn token_request(
+        &self,
+        token_url: &str,
+        form: Vec<(String, String)>,
+    ) -> Result<Result<Token, ErrorResponse>, ShellError> {
+        let (success, body) = self.post_form(token_url, form)?;
+        if success {
+            let response: TokenResponse = parse(&body, token_url, self.span)?;
+            Ok(Ok(Token::from_response(response, Utc::now().timestamp())))
+        } else {
+            parse(&body, token_url, self.span).map(Err)
+        }
+    }
+
+    /// Posts a form, adding the client's credentials, and returns whether it succeeded along
+    /// with the body of the response.
+    fn post_form(
+        &self,
+        url: &str,
+        mut form: Vec<(String, String)>,
+    ) -> Result<(bool, String), ShellError> {
+        let mut request = self.agent.post(url).header("Accept", "application/json");
+        match &self.client_secret {
+            Some(secret) => {
+                request = request_add_authorization_header(
+                    Some(self.client_id.clone()),
+                    Some(secret.clone()),
+                    request,
+                );
+            }
+            // public clients identify themselves in the form
+            None => form.push(("client_id".into(), self.client_id.clone())),
+        }
+        let form = form
+            .into_iter()
+            .map(|(name, value)| (name, Value::string(value, self.span)))
+            .collect();
+
+        let (response, _) = send_request(
+            self.engine_state,
+            request,
+            self.span,
+            HttpBody::Value(Value::record(form, self.span)),
+            Some("application/x-www-form-urlencoded".into()),
+            self.span,
+            &self.signals,
+        );
+        let mut response = response?;
+        let body = read_body(&mut response, url, self.span)?;
+        Ok((response.status().is_success(), body))
+    }
+}
+
+fn read_body(
+    response: &mut http::Response<ureq::Body>,
+    url: &str,
+    span: Span,
+) -> Result<String, ShellError> {
+    response
+        .body_mut()
+        .read_to_string()
+        .map_err(|err| ShellError::NetworkFailure {
+            msg: format!("Could not read the response of {url}: {err}"),
+            span,
+        })
+}
+
+fn parse<T: DeserializeOwned>(body: &str, url: &str, span: Span) -> Result<T, ShellError> {
+    serde_json::from_str(body).map_err(|err| ShellError::NetworkFailure {
+        msg: format!("Unexpected response from {url}: {err}"),
+        span,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(HttpAuthOauth2 {})
+    }
+
+    fn token(expires_at: Option<i64>) -> Token {
+        Token {
+            access_token: "abc".into(),
+            token_type: Some("Bearer".into()),
+            expires_at,
+            refresh_token: None,
+            scope: None,
+            id_token: None,
+        }
+    }
+
+    #[test]
+    fn test_token_from_response() {
+        let response: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "abc", "token_type": "Bearer", "expires_in": "3600"}"#,
+        )
+        .expect("valid response");
+        assert_eq!(Token::from_response(response, 1000), token(Some(4600)));
+
+        let response: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "abc", "token_type": "Bearer", "expires_in": 60}"#,
+        )
+        .expect("valid response");
+        assert_eq!(Token::from_response(response, 1000), token(Some(1060)));
+    }
+
+    #[test]
+    fn test_token_is_fresh() {
+        assert!(token(None).is_fresh(1000));
+        assert!(token(Some(2000)).is_fresh(1000));
+        // about to expire
+        assert!(!token(Some(1030)).is_fresh(1000));
+        assert!(!token(Some(500)).is_fresh(1000));
+    }
+}
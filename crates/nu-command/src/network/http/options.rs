@@ -1,8 +1,12 @@
 use crate::network::http::client::{
-    RedirectMode, RequestFlags, RequestMetadata, add_unix_socket_flag, expand_unix_socket_path,
-    http_client, http_client_pool, http_parse_url, request_add_authorization_header,
-    request_add_custom_headers, request_handle_response, request_set_timeout, send_request_no_body,
+    RedirectMode, RequestFlags, RequestMetadata, RetryPolicy, add_retry_flags,
+    add_unix_socket_flag, expand_unix_socket_path, http_client, http_client_pool, http_parse_url,
+    request_add_authorization_header, request_add_custom_headers, request_handle_response,
+    request_set_timeout, send_request_no_body, send_with_retry,
 };
+use crate::network::http::cookie_jar::{CookieJar, add_cookie_jar_flag};
+use crate::network::http::proxy::{ProxySettings, add_proxy_flags};
+use crate::network::http::session::{HttpSessionValue, add_session_flag};
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -60,7 +64,9 @@ impl Command for HttpOptions {
             .filter()
             .category(Category::Network);
 
-        add_unix_socket_flag(sig)
+        add_session_flag(add_cookie_jar_flag(add_retry_flags(add_proxy_flags(
+            add_unix_socket_flag(sig),
+        ))))
     }
 
     fn description(&self) -> &str {
@@ -126,6 +132,10 @@ struct Arguments {
     allow_errors: bool,
     unix_socket: Option<Spanned<String>>,
     pool: bool,
+    retry: RetryPolicy,
+    cookie_jar: Option<Spanned<String>>,
+    session: Option<HttpSessionValue>,
+    proxy: ProxySettings,
 }
 
 fn run_get(
@@ -144,6 +154,10 @@ fn run_get(
         allow_errors: call.has_flag(engine_state, stack, "allow-errors")?,
         unix_socket: call.get_flag(engine_state, stack, "unix-socket")?,
         pool: call.has_flag(engine_state, stack, "pool")?,
+        retry: RetryPolicy::from_call(engine_state, stack, call)?,
+        cookie_jar: call.get_flag(engine_state, stack, "cookie-jar")?,
+        session: HttpSessionValue::from_call(engine_state, stack, call)?,
+        proxy: ProxySettings::from_call(engine_state, stack, call)?,
     };
     helper(engine_state, stack, call, args)
 }
@@ -154,38 +168,65 @@ fn helper(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    args: Arguments,
+    mut args: Arguments,
 ) -> Result<PipelineData, ShellError> {
+    let mut session_agent = None;
+    if let Some(session) = &args.session {
+        session.apply_defaults(
+            &mut args.url,
+            &mut args.headers,
+            &mut args.user,
+            &mut args.password,
+            &mut args.timeout,
+        )?;
+        session_agent = session.connect_with(&mut args.insecure, &mut None, &mut args.unix_socket);
+    }
+
     let span = args.url.span();
     let Spanned {
-        item: (requested_url, _),
+        item: (requested_url, url),
         span: request_span,
     } = http_parse_url(call, span, args.url)?;
     let redirect_mode = RedirectMode::Follow;
 
     let cwd = engine_state.cwd(None)?;
     let unix_socket_path = expand_unix_socket_path(args.unix_socket, &cwd);
-
-    let mut request = if args.pool {
-        http_client_pool(engine_state, stack)?.options(&requested_url)
-    } else {
-        let client = http_client(
-            args.insecure,
-            redirect_mode,
-            unix_socket_path,
-            engine_state,
-            stack,
-        )?;
-        client.options(&requested_url)
-    };
-    request = request_set_timeout(args.timeout, request)?;
-    request = request_add_authorization_header(args.user, args.password, request);
-    request = request_add_custom_headers(args.headers, request)?;
+    let mut cookie_jar = CookieJar::open(args.cookie_jar, &cwd)?;
 
     let (response, request_headers) =
-        send_request_no_body(request, request_span, call.head, engine_state.signals());
-
-    let response = response?;
+        send_with_retry(&args.retry, call.head, engine_state.signals(), || {
+            let mut request = if let Some(agent) = &session_agent {
+                agent.options(&requested_url)
+            } else if args.pool {
+                http_client_pool(engine_state, stack)?.options(&requested_url)
+            } else {
+                let client = http_client(
+                    args.insecure,
+                    redirect_mode,
+                    unix_socket_path.clone(),
+                    engine_state,
+                    stack,
+                )?;
+                client.options(&requested_url)
+            };
+            request = args.proxy.apply(&url, request);
+            request = request_set_timeout(args.timeout.clone(), request)?;
+            request =
+                request_add_authorization_header(args.user.clone(), args.password.clone(), request);
+            if let Some(jar) = &cookie_jar {
+                request = jar.add_request_cookies(&url, request);
+            }
+            request = request_add_custom_headers(args.headers.clone(), request)?;
+            Ok(send_request_no_body(
+                request,
+                request_span,
+                call.head,
+                engine_state.signals(),
+            ))
+        })?;
+    if let Some(jar) = &mut cookie_jar {
+        jar.store_response_cookies(&response, span)?;
+    }
 
     // http options' response always showed in header, so we set full to true.
     // And `raw` is useless too because options method doesn't return body, here we set to true
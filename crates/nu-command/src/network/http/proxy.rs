@@ -0,0 +1,243 @@
+use nu_engine::command_prelude::*;
+use ureq::{Proxy, RequestBuilder};
+use url::Url;
+
+/// Helper function to add the --proxy, --proxy-user, --proxy-password and --no-proxy flags to
+/// command signatures.
+pub fn add_proxy_flags(sig: Signature) -> Signature {
+    sig.named(
+        "proxy",
+        SyntaxShape::String,
+        "Send the request through this proxy, e.g. http://host:8080 or socks5://host:1080. \
+         Default: taken from the http_proxy, https_proxy and all_proxy environment variables.",
+        None,
+    )
+    .named(
+        "proxy-user",
+        SyntaxShape::String,
+        "The username when authenticating with the proxy.",
+        None,
+    )
+    .named(
+        "proxy-password",
+        SyntaxShape::String,
+        "The password when authenticating with the proxy.",
+        None,
+    )
+    .named(
+        "no-proxy",
+        SyntaxShape::OneOf(vec![
+            SyntaxShape::List(Box::new(SyntaxShape::String)),
+            SyntaxShape::String,
+        ]),
+        "Hosts that are contacted directly, as a list or comma separated string. Default: taken \
+         from the no_proxy environment variable.",
+        None,
+    )
+}
+
+/// Per request proxy configuration, built from the flags added by [`add_proxy_flags`].
+///
+/// The agents created by the http commands already use the proxy from the environment, so this
+/// only overrides it when `--proxy` is given, or disables it for hosts matching `no_proxy`.
+pub struct ProxySettings {
+    proxy: Option<Proxy>,
+    no_proxy: Vec<String>,
+}
+
+impl ProxySettings {
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Self, ShellError> {
+        let user: Option<String> = call.get_flag(engine_state, stack, "proxy-user")?;
+        let password: Option<String> = call.get_flag(engine_state, stack, "proxy-password")?;
+
+        let proxy = match call.get_flag::<Spanned<String>>(engine_state, stack, "proxy")? {
+            Some(proxy) => Some(parse_proxy(proxy, user, password)?),
+            None if user.is_some() || password.is_some() => {
+                return Err(ShellError::MissingParameter {
+                    param_name: "proxy".into(),
+                    span: call.head,
+                });
+            }
+            None => None,
+        };
+
+        let no_proxy = match call.get_flag::<Value>(engine_state, stack, "no-proxy")? {
+            Some(Value::List { vals, .. }) => vals
+                .iter()
+                .map(|val| val.coerce_string())
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(value) => split_no_proxy(&value.coerce_into_string()?),
+            None => stack
+                .get_env_var(engine_state, "no_proxy")
+                .or(stack.get_env_var(engine_state, "NO_PROXY"))
+                .and_then(|value| value.coerce_str().ok())
+                .map(|value| split_no_proxy(&value))
+                .unwrap_or_default(),
+        };
+
+        Ok(ProxySettings { proxy, no_proxy })
+    }
+
+    /// Sets the proxy `request` to `url` is sent through.
+    pub fn apply<B>(&self, url: &Url, request: RequestBuilder<B>) -> RequestBuilder<B> {
+        if self.bypass(url) {
+            request.config().proxy(None).build()
+        } else if let Some(proxy) = &self.proxy {
+            request.config().proxy(Some(proxy.clone())).build()
+        } else {
+            request
+        }
+    }
+
+    fn bypass(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        // IPv6 hosts come wrapped in brackets, no_proxy entries usually don't have them
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let port = url.port_or_known_default();
+
+        self.no_proxy
+            .iter()
+            .any(|entry| no_proxy_matches(entry, host, port))
+    }
+}
+
+fn parse_proxy(
+    proxy: Spanned<String>,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<Proxy, ShellError> {
+    let invalid_proxy = |msg: String| ShellError::IncorrectValue {
+        msg,
+        val_span: proxy.span,
+        call_span: proxy.span,
+    };
+
+    let raw = if proxy.item.contains("://") {
+        proxy.item.clone()
+    } else {
+        format!("http://{}", proxy.item)
+    };
+    let mut url =
+        Url::parse(&raw).map_err(|err| invalid_proxy(format!("Invalid proxy URL: {err}")))?;
+
+    if let Some(user) = user {
+        url.set_username(&user)
+            .map_err(|_| invalid_proxy("Proxy URL can't have a username".into()))?;
+    }
+    if let Some(password) = password {
+        url.set_password(Some(&password))
+            .map_err(|_| invalid_proxy("Proxy URL can't have a password".into()))?;
+    }
+
+    Proxy::new(url.as_str()).map_err(|err| invalid_proxy(format!("Invalid proxy: {err}")))
+}
+
+fn split_no_proxy(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Checks a single no_proxy entry, following the rules curl uses: `*` matches every host, any
+/// other entry matches the host itself and all of its subdomains, optionally only on one port.
+fn no_proxy_matches(entry: &str, host: &str, port: Option<u16>) -> bool {
+    if entry == "*" {
+        return true;
+    }
+
+    let (entry_host, entry_port) = match entry.rsplit_once(':') {
+        // a bare IPv6 address contains colons as well, only treat the suffix as port if it is one
+        Some((entry_host, entry_port))
+            if !entry_host.contains(':') || entry_host.ends_with(']') =>
+        {
+            match entry_port.parse::<u16>() {
+                Ok(entry_port) => (entry_host, Some(entry_port)),
+                Err(_) => (entry, None),
+            }
+        }
+        _ => (entry, None),
+    };
+    if entry_port.is_some() && entry_port != port {
+        return false;
+    }
+
+    let entry_host = entry_host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_start_matches('.');
+    host.eq_ignore_ascii_case(entry_host)
+        || host
+            .len()
+            .checked_sub(entry_host.len() + 1)
+            .is_some_and(|idx| {
+                host.as_bytes()[idx] == b'.' && host[idx + 1..].eq_ignore_ascii_case(entry_host)
+            })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_proxy_matches() {
+        assert!(no_proxy_matches("*", "example.com", Some(80)));
+
+        assert!(no_proxy_matches("example.com", "example.com", Some(80)));
+        assert!(no_proxy_matches("example.com", "api.example.com", Some(80)));
+        assert!(no_proxy_matches(
+            ".example.com",
+            "api.example.com",
+            Some(80)
+        ));
+        assert!(no_proxy_matches("EXAMPLE.com", "example.COM", Some(80)));
+        assert!(!no_proxy_matches("example.com", "notexample.com", Some(80)));
+        assert!(!no_proxy_matches(
+            "api.example.com",
+            "example.com",
+            Some(80)
+        ));
+
+        assert!(no_proxy_matches(
+            "example.com:8080",
+            "example.com",
+            Some(8080)
+        ));
+        assert!(!no_proxy_matches(
+            "example.com:8080",
+            "example.com",
+            Some(443)
+        ));
+
+        assert!(no_proxy_matches("127.0.0.1", "127.0.0.1", Some(80)));
+        assert!(no_proxy_matches("::1", "::1", Some(80)));
+        assert!(no_proxy_matches("[::1]:8080", "::1", Some(8080)));
+        assert!(!no_proxy_matches("[::1]:8080", "::1", Some(80)));
+    }
+
+    #[test]
+    fn test_parse_proxy_with_credentials() {
+        let proxy = parse_proxy(
+            Spanned {
+                item: "socks5://proxy.example.com:1080".into(),
+                span: Span::test_data(),
+            },
+            Some("user".into()),
+            Some("pass".into()),
+        )
+        .expect("the proxy should be valid");
+
+        assert_eq!(proxy.host(), "proxy.example.com");
+        assert_eq!(proxy.port(), 1080);
+        assert_eq!(proxy.username(), Some("user"));
+        assert_eq!(proxy.password(), Some("pass"));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::network::http::client::{
-    HttpBody, RequestFlags, RequestMetadata, add_unix_socket_flag, check_response_redirection,
-    expand_unix_socket_path, http_client, http_client_pool, http_parse_redirect_mode,
-    http_parse_url, request_add_authorization_header, request_add_custom_headers,
-    request_handle_response, request_set_timeout, send_request,
+    HttpBody, RequestFlags, RequestMetadata, RetryPolicy, add_retry_flags, add_unix_socket_flag,
+    check_response_redirection, expand_unix_socket_path, http_client, http_client_pool,
+    http_parse_redirect_mode, http_parse_url, request_add_authorization_header,
+    request_add_custom_headers, request_handle_response, request_set_timeout,
+    send_request_with_retry,
 };
+use crate::network::http::cookie_jar::{CookieJar, add_cookie_jar_flag};
+use crate::network::http::proxy::{ProxySettings, add_proxy_flags};
+use crate::network::http::session::{HttpSessionValue, add_session_flag};
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -42,6 +46,13 @@ impl Command for HttpPut {
                 "The MIME type of content to post.",
                 Some('t'),
             )
+            .switch(
+                "form",
+                "Send the data as multipart/form-data. A field can be given as a record, either \
+                 {file: <path>} to stream a file from disk or {value: <data>}, each with an \
+                 optional content_type and filename.",
+                None,
+            )
             .named(
                 "max-time",
                 SyntaxShape::Duration,
@@ -90,7 +101,9 @@ impl Command for HttpPut {
             .filter()
             .category(Category::Network);
 
-        add_unix_socket_flag(sig)
+        add_session_flag(add_cookie_jar_flag(add_retry_flags(add_proxy_flags(
+            add_unix_socket_flag(sig),
+        ))))
     }
 
     fn description(&self) -> &str {
@@ -166,6 +179,10 @@ struct Arguments {
     redirect: Option<Spanned<String>>,
     unix_socket: Option<Spanned<String>>,
     pool: bool,
+    retry: RetryPolicy,
+    cookie_jar: Option<Spanned<String>>,
+    session: Option<HttpSessionValue>,
+    proxy: ProxySettings,
 }
 
 fn run_put(
@@ -195,9 +212,12 @@ fn run_put(
         });
     };
 
-    let content_type = call
-        .get_flag(engine_state, stack, "content-type")?
-        .or_else(|| maybe_metadata.and_then(|m| m.content_type));
+    let content_type = if call.has_flag(engine_state, stack, "form")? {
+        Some("multipart/form-data".to_string())
+    } else {
+        call.get_flag(engine_state, stack, "content-type")?
+            .or_else(|| maybe_metadata.and_then(|m| m.content_type))
+    };
 
     let args = Arguments {
         url: call.req(engine_state, stack, 0)?,
@@ -214,6 +234,10 @@ fn run_put(
         redirect: call.get_flag(engine_state, stack, "redirect-mode")?,
         unix_socket: call.get_flag(engine_state, stack, "unix-socket")?,
         pool: call.has_flag(engine_state, stack, "pool")?,
+        retry: RetryPolicy::from_call(engine_state, stack, call)?,
+        cookie_jar: call.get_flag(engine_state, stack, "cookie-jar")?,
+        session: HttpSessionValue::from_call(engine_state, stack, call)?,
+        proxy: ProxySettings::from_call(engine_state, stack, call)?,
     };
 
     helper(engine_state, stack, call, args)
@@ -225,51 +249,77 @@ fn helper(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-    args: Arguments,
+    mut args: Arguments,
 ) -> Result<PipelineData, ShellError> {
+    let mut session_agent = None;
+    if let Some(session) = &args.session {
+        session.apply_defaults(
+            &mut args.url,
+            &mut args.headers,
+            &mut args.user,
+            &mut args.password,
+            &mut args.timeout,
+        )?;
+        session_agent = session.connect_with(
+            &mut args.insecure,
+            &mut args.redirect,
+            &mut args.unix_socket,
+        );
+    }
+
     let span = args.url.span();
     let Spanned {
-        item: (requested_url, _),
+        item: (requested_url, url),
         span: request_span,
     } = http_parse_url(call, span, args.url)?;
     let redirect_mode = http_parse_redirect_mode(args.redirect)?;
 
     let cwd = engine_state.cwd(None)?;
     let unix_socket_path = expand_unix_socket_path(args.unix_socket, &cwd);
+    let mut cookie_jar = CookieJar::open(args.cookie_jar, &cwd)?;
 
-    let mut request = if args.pool {
-        http_client_pool(engine_state, stack)?.put(&requested_url)
-    } else {
-        let client = http_client(
-            args.insecure,
-            redirect_mode,
-            unix_socket_path,
-            engine_state,
-            stack,
-        )?;
-        client.put(&requested_url)
-    };
-
-    request = request_set_timeout(args.timeout, request)?;
-    request = request_add_authorization_header(args.user, args.password, request);
-    request = request_add_custom_headers(args.headers, request)?;
-
-    let (response, request_headers) = send_request(
+    let (response, request_headers) = send_request_with_retry(
         engine_state,
-        request,
+        &args.retry,
+        || {
+            let mut request = if let Some(agent) = &session_agent {
+                agent.put(&requested_url)
+            } else if args.pool {
+                http_client_pool(engine_state, stack)?.put(&requested_url)
+            } else {
+                let client = http_client(
+                    args.insecure,
+                    redirect_mode,
+                    unix_socket_path.clone(),
+                    engine_state,
+                    stack,
+                )?;
+                client.put(&requested_url)
+            };
+            request = args.proxy.apply(&url, request);
+            request = request_set_timeout(args.timeout.clone(), request)?;
+            request =
+                request_add_authorization_header(args.user.clone(), args.password.clone(), request);
+            if let Some(jar) = &cookie_jar {
+                request = jar.add_request_cookies(&url, request);
+            }
+            request_add_custom_headers(args.headers.clone(), request)
+        },
         request_span,
         args.data,
         args.content_type,
         call.head,
         engine_state.signals(),
-    );
+    )?;
+    if let Some(jar) = &mut cookie_jar {
+        jar.store_response_cookies(&response, span)?;
+    }
 
     let request_flags = RequestFlags {
         raw: args.raw,
         full: args.full,
         allow_errors: args.allow_errors,
     };
-    let response = response?;
 
     check_response_redirection(redirect_mode, span, &response)?;
     request_handle_response(
@@ -0,0 +1,596 @@
+use crate::formats::value_to_json_value;
+use http::StatusCode;
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::{Signals, engine::Closure, shell_error::io::IoError};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+use url::form_urlencoded;
+
+/// Requests with larger heads than this are rejected, mostly to not buffer garbage forever.
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+const MAX_HEADERS: usize = 100;
+/// Requests with larger bodies than this are rejected, as the body is read into memory whole.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Why a request couldn't be read, and the status to respond with.
+type RequestError = (StatusCode, String);
+
+#[derive(Clone)]
+pub struct HttpServe;
+
+impl Command for HttpServe {
+    fn name(&self) -> &str {
+        "http serve"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("http serve")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "handler",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Record(vec![])])),
+                "The closure to run for every request, its return value is sent as the response.",
+            )
+            .named(
+                "port",
+                SyntaxShape::Int,
+                "The port to listen on. Default: 8080.",
+                Some('p'),
+            )
+            .named(
+                "host",
+                SyntaxShape::String,
+                "The address to listen on. Default: 127.0.0.1.",
+                None,
+            )
+            .switch("once", "Stop after the first request was handled.", None)
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Run a simple HTTP server that answers requests with a closure."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure receives each request as a record with the columns method, path, query,
+headers and body, the body being binary. Requests are handled one at a time, until the
+command is interrupted with ctrl-c. Bodies of up to 16 MiB are accepted, with a Content-Length
+or in chunks.
+
+The value returned by the closure becomes the response body: strings are sent as text,
+binary as is, and any other value as JSON. To set the status or headers, return a record
+with a status column, e.g. {status: 404, headers: {x-reason: gone}, body: "not found"}.
+If the closure fails, the error is sent back with status 500."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "server", "listen", "webhook", "mock"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let port = match call.get_flag::<Spanned<i64>>(engine_state, stack, "port")? {
+            Some(Spanned { item, span }) => {
+                u16::try_from(item).map_err(|_| ShellError::IncorrectValue {
+                    msg: "Port must be between 0 and 65535".into(),
+                    val_span: span,
+                    call_span: head,
+                })?
+            }
+            None => 8080,
+        };
+        let host: String = call
+            .get_flag(engine_state, stack, "host")?
+            .unwrap_or_else(|| "127.0.0.1".into());
+        let once = call.has_flag(engine_state, stack, "once")?;
+
+        let listener = TcpListener::bind((host.as_str(), port)).map_err(|err| {
+            IoError::new_with_additional_context(
+                err,
+                head,
+                None,
+                format!("Could not listen on {host}:{port}"),
+            )
+        })?;
+        // Accepting without blocking lets us notice ctrl-c while waiting for connections.
+        listener
+            .set_nonblocking(true)
+            .map_err(|err| IoError::new(err, head, None))?;
+
+        let signals = engine_state.signals();
+        let mut handler = ClosureEval::new(engine_state, stack, closure);
+
+        loop {
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    signals.check(&head)?;
+                    std::thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+                Err(err) => return Err(IoError::new(err, head, None).into()),
+            };
+
+            // A client going away mid-request shouldn't take the whole server down.
+            let _ = handle_connection(engine_state, &mut handler, stream, signals, head);
+
+            if once {
+                return Ok(PipelineData::empty());
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Answer every request with a greeting.",
+                example: "http serve --port 8080 {|req| $'hello from ($req.path)' }",
+                result: None,
+            },
+            Example {
+                description: "Log incoming webhooks and acknowledge them.",
+                example: "http serve {|req| $req.body | decode | from json | save --append hooks.nuon; {status: 204} }",
+                result: None,
+            },
+            Example {
+                description: "Serve a mock API returning JSON, with a 404 for unknown paths.",
+                example: "http serve {|req| match $req.path { '/users' => [[name]; [alice] [bob]], _ => {status: 404, body: 'not found'} } }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn handle_connection(
+    engine_state: &EngineState,
+    handler: &mut ClosureEval,
+    stream: TcpStream,
+    signals: &Signals,
+    span: Span,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let request = match read_request(&mut reader, span) {
+        Ok(request) => request,
+        Err((status, msg)) => {
+            return write_response(
+                &mut writer,
+                status,
+                &[("Content-Type".into(), "text/plain".into())],
+                msg.as_bytes(),
+            );
+        }
+    };
+
+    let (status, headers, body) = handler
+        .run_with_value(request)
+        .and_then(|data| data.into_value(span))
+        .and_then(|value| {
+            signals.check(&span)?;
+            into_response(engine_state, value, span)
+        })
+        .unwrap_or_else(|err| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                vec![("Content-Type".into(), "text/plain".into())],
+                err.to_string().into_bytes(),
+            )
+        });
+
+    write_response(&mut writer, status, &headers, &body)
+}
+
+/// Reads the request from `reader` into the record handed to the closure.
+fn read_request(reader: &mut impl BufRead, span: Span) -> Result<Value, RequestError> {
+    let mut buf = Vec::new();
+    loop {
+        // Only what's left of the maximum is read, so that a line without an end can't grow forever
+        let remaining = (MAX_HEAD_SIZE + 1 - buf.len()) as u64;
+        let read = reader
+            .by_ref()
+            .take(remaining)
+            .read_until(b'\n', &mut buf)
+            .map_err(|err| bad_request(err.to_string()))?;
+        if buf.len() > MAX_HEAD_SIZE {
+            return Err(head_too_large());
+        }
+        if read == 0 || buf.ends_with(b"\r\n\r\n") || buf.ends_with(b"\n\n") {
+            break;
+        }
+    }
+
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut request = httparse::Request::new(&mut headers);
+    match request.parse(&buf) {
+        Ok(httparse::Status::Complete(_)) => {}
+        Ok(httparse::Status::Partial) => return Err(bad_request("Incomplete request")),
+        Err(httparse::Error::TooManyHeaders) => return Err(head_too_large()),
+        Err(err) => return Err(bad_request(format!("Invalid request: {err}"))),
+    }
+
+    let method = request.method.unwrap_or_default();
+    let target = request.path.unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = None;
+    let mut chunked = false;
+    let mut header_record = Record::new();
+    for header in request.headers.iter() {
+        let value = String::from_utf8_lossy(header.value).into_owned();
+        if header.name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| bad_request("Invalid Content-Length header"))?,
+            );
+        } else if header.name.eq_ignore_ascii_case("transfer-encoding") {
+            // Compressed bodies aren't decoded, so only plain chunks are supported
+            if !value.trim().eq_ignore_ascii_case("chunked") {
+                return Err((
+                    StatusCode::NOT_IMPLEMENTED,
+                    format!("Unsupported Transfer-Encoding: {value}"),
+                ));
+            }
+            chunked = true;
+        }
+        header_record.push(header.name.to_lowercase(), Value::string(value, span));
+    }
+
+    let body = match (chunked, content_length) {
+        // Which of the two to trust is ambiguous, and a common way to smuggle requests
+        (true, Some(_)) => {
+            return Err(bad_request(
+                "Both Content-Length and Transfer-Encoding headers given",
+            ));
+        }
+        (true, None) => read_chunked_body(reader)?,
+        (false, content_length) => read_body(reader, content_length.unwrap_or(0))?,
+    };
+
+    let query = form_urlencoded::parse(query.as_bytes())
+        .map(|(k, v)| (k.into_owned(), Value::string(v, span)))
+        .collect::<Record>();
+
+    Ok(Value::record(
+        record! {
+            "method" => Value::string(method, span),
+            "path" => Value::string(path, span),
+            "query" => Value::record(query, span),
+            "headers" => Value::record(header_record, span),
+            "body" => Value::binary(body, span),
+        },
+        span,
+    ))
+}
+
+fn bad_request(msg: impl Into<String>) -> RequestError {
+    (StatusCode::BAD_REQUEST, msg.into())
+}
+
+fn head_too_large() -> RequestError {
+    (
+        StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+        "Request head too large".into(),
+    )
+}
+
+fn body_too_large() -> RequestError {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "Request body too large".into(),
+    )
+}
+
+/// Reads a body of `len` bytes, as given by the Content-Length header.
+fn read_body(reader: &mut impl BufRead, len: usize) -> Result<Vec<u8>, RequestError> {
+    if len > MAX_BODY_SIZE {
+        return Err(body_too_large());
+    }
+    let mut body = Vec::new();
+    reader
+        .by_ref()
+        .take(len as u64)
+        .read_to_end(&mut body)
+        .map_err(|err| bad_request(err.to_string()))?;
+    if body.len() < len {
+        return Err(bad_request("Incomplete request body"));
+    }
+    Ok(body)
+}
+
+/// Reads a body sent with `Transfer-Encoding: chunked`, skipping the trailer fields after it.
+fn read_chunked_body(reader: &mut impl BufRead) -> Result<Vec<u8>, RequestError> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        // Chunk extensions after a `;` are ignored
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| bad_request(format!("Invalid chunk size: {size}")))?;
+        if size == 0 {
+            break;
+        }
+        if size > MAX_BODY_SIZE - body.len() {
+            return Err(body_too_large());
+        }
+
+        let start = body.len();
+        reader
+            .by_ref()
+            .take(size as u64)
+            .read_to_end(&mut body)
+            .map_err(|err| bad_request(err.to_string()))?;
+        if body.len() - start < size {
+            return Err(bad_request("Incomplete request body"));
+        }
+        if !read_line(reader)?.is_empty() {
+            return Err(bad_request("Chunk is longer than its size"));
+        }
+    }
+
+    let mut trailer_size = 0;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            return Ok(body);
+        }
+        trailer_size += line.len();
+        if trailer_size > MAX_HEAD_SIZE {
+            return Err(head_too_large());
+        }
+    }
+}
+
+/// Reads a line of a chunked body, without its line break.
+fn read_line(reader: &mut impl BufRead) -> Result<String, RequestError> {
+    let mut line = Vec::new();
+    reader
+        .by_ref()
+        .take(MAX_HEAD_SIZE as u64)
+        .read_until(b'\n', &mut line)
+        .map_err(|err| bad_request(err.to_string()))?;
+    if line.pop() != Some(b'\n') {
+        return Err(bad_request("Incomplete request body"));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line).map_err(|_| bad_request("Invalid chunk"))
+}
+
+type Response = (StatusCode, Vec<(String, String)>, Vec<u8>);
+
+/// Turns the value returned by the closure into the response to send.
+fn into_response(
+    engine_state: &EngineState,
+    value: Value,
+    span: Span,
+) -> Result<Response, ShellError> {
+    let val = match value {
+        Value::Record { val, .. } if val.contains("status") => val.into_owned(),
+        value => {
+            let (content_type, body) = into_response_body(engine_state, value, span)?;
+            return Ok((StatusCode::OK, content_type.into_iter().collect(), body));
+        }
+    };
+
+    let status = val.get("status").cloned().unwrap_or(Value::nothing(span));
+    let status = u16::try_from(status.as_int()?)
+        .ok()
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .ok_or_else(|| ShellError::IncorrectValue {
+            msg: "Response status must be a valid HTTP status code".into(),
+            val_span: status.span(),
+            call_span: span,
+        })?;
+
+    let mut headers = match val.get("headers") {
+        Some(record @ Value::Record { val, .. }) => val
+            .iter()
+            .map(|(k, v)| {
+                let value = v.coerce_string()?;
+                // a line break would end the header, letting the rest pass for other headers
+                for (text, text_span) in [(k, record.span()), (&value, v.span())] {
+                    if text.contains(['\r', '\n']) {
+                        return Err(ShellError::IncorrectValue {
+                            msg: format!(
+                                "Response header {k:?} must not contain line breaks in its name \
+                                 or value"
+                            ),
+                            val_span: text_span,
+                            call_span: span,
+                        });
+                    }
+                }
+                Ok((k.clone(), value))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?,
+        Some(Value::Nothing { .. }) | None => vec![],
+        Some(other) => {
+            return Err(ShellError::TypeMismatch {
+                err_message: format!(
+                    "Response headers must be a record, got {}",
+                    other.get_type()
+                ),
+                span: other.span(),
+            });
+        }
+    };
+
+    let body = val.get("body").cloned().unwrap_or(Value::nothing(span));
+    let (content_type, body) = into_response_body(engine_state, body, span)?;
+    if let Some(content_type) = content_type
+        && !headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+    {
+        headers.push(content_type);
+    }
+
+    Ok((status, headers, body))
+}
+
+fn into_response_body(
+    engine_state: &EngineState,
+    value: Value,
+    span: Span,
+) -> Result<(Option<(String, String)>, Vec<u8>), ShellError> {
+    let content_type = |mime: &str| Some(("Content-Type".to_string(), mime.to_string()));
+    Ok(match value {
+        Value::Nothing { .. } => (None, vec![]),
+        Value::String { val, .. } => (content_type("text/plain; charset=utf-8"), val.into_bytes()),
+        Value::Binary { val, .. } => (content_type("application/octet-stream"), val),
+        value => {
+            let json = value_to_json_value(engine_state, &value, span, false)?;
+            let body = serde_json::to_vec(&json).map_err(|err| ShellError::CantConvert {
+                to_type: "JSON".into(),
+                from_type: value.get_type().to_string(),
+                span: value.span(),
+                help: Some(err.to_string()),
+            })?;
+            (content_type("application/json"), body)
+        }
+    })
+}
+
+fn write_response(
+    writer: &mut impl Write,
+    status: StatusCode,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> io::Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    )?;
+    for (name, value) in headers {
+        write!(writer, "{name}: {value}\r\n")?;
+    }
+    write!(
+        writer,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    writer.write_all(body)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(HttpServe {})
+    }
+
+    fn read(request: &str) -> Result<Value, RequestError> {
+        read_request(&mut request.as_bytes(), Span::test_data())
+    }
+
+    fn body(request: &str) -> Option<Vec<u8>> {
+        let request = read(request).ok()?;
+        request.get_data_by_key("body")?.into_binary().ok()
+    }
+
+    fn status(request: &str) -> Option<StatusCode> {
+        read(request).err().map(|(status, _)| status)
+    }
+
+    #[test]
+    fn reads_body_by_content_length() {
+        let request = "POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello, world";
+        assert_eq!(body(request), Some(b"hello".to_vec()));
+
+        let request = "POST / HTTP/1.1\r\nContent-Length: 50\r\n\r\nhello";
+        assert_eq!(status(request), Some(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn reads_chunked_body() {
+        let request = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\nX-Trailer: 1\r\n\r\n";
+        assert_eq!(body(request), Some(b"hello, world".to_vec()));
+
+        let request = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello, world\r\n";
+        assert_eq!(status(request), Some(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn rejects_other_transfer_encodings() {
+        let request = "POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\n";
+        assert_eq!(status(request), Some(StatusCode::NOT_IMPLEMENTED));
+
+        let request = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 5\r\n\r\n";
+        assert_eq!(status(request), Some(StatusCode::BAD_REQUEST));
+    }
+
+    fn response_headers(headers: Record) -> Result<Vec<(String, String)>, ShellError> {
+        let span = Span::test_data();
+        let response = record! {
+            "status" => Value::int(200, span),
+            "headers" => Value::record(headers, span),
+        };
+        into_response(&EngineState::new(), Value::record(response, span), span)
+            .map(|(_, headers, _)| headers)
+    }
+
+    #[test]
+    fn rejects_line_breaks_in_response_headers() {
+        let span = Span::test_data();
+        let headers = record! { "x-ok" => Value::string("fine", span) };
+        assert!(response_headers(headers).is_ok());
+
+        let headers = record! { "x-evil" => Value::string("a\r\nSet-Cookie: b", span) };
+        assert!(response_headers(headers).is_err());
+
+        let headers = record! { "x-evil\nSet-Cookie" => Value::string("b", span) };
+        assert!(response_headers(headers).is_err());
+    }
+
+    #[test]
+    fn rejects_large_requests() {
+        let request = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert_eq!(status(&request), Some(StatusCode::PAYLOAD_TOO_LARGE));
+
+        let request = format!(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert_eq!(status(&request), Some(StatusCode::PAYLOAD_TOO_LARGE));
+
+        let request = format!("GET / HTTP/1.1\r\nX-Long: {}", "a".repeat(MAX_HEAD_SIZE));
+        assert_eq!(
+            status(&request),
+            Some(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)
+        );
+
+        let headers = "X-Header: 1\r\n".repeat(MAX_HEADERS + 1);
+        let request = format!("GET / HTTP/1.1\r\n{headers}\r\n");
+        assert_eq!(
+            status(&request),
+            Some(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)
+        );
+    }
+}
@@ -0,0 +1,458 @@
+use crate::network::http::client::{
+    RedirectMode, add_unix_socket_flag, expand_unix_socket_path, http_client,
+    http_parse_redirect_mode, parse_custom_headers,
+};
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::CustomValue;
+use serde::{Deserialize, Serialize};
+use ureq::Agent;
+
+pub const TYPE_NAME: &str = "HttpSession";
+
+#[derive(Clone)]
+pub struct HttpSession;
+
+impl Command for HttpSession {
+    fn name(&self) -> &str {
+        "http session"
+    }
+
+    fn signature(&self) -> Signature {
+        let sig = Signature::build("http session")
+            .input_output_types(vec![(Type::Nothing, Type::custom(TYPE_NAME))])
+            .allow_variants_without_examples(true)
+            .named(
+                "base-url",
+                SyntaxShape::String,
+                "URL that relative URLs given to http commands are resolved against.",
+                Some('b'),
+            )
+            .named(
+                "user",
+                SyntaxShape::Any,
+                "The username when authenticating.",
+                Some('u'),
+            )
+            .named(
+                "password",
+                SyntaxShape::Any,
+                "The password when authenticating.",
+                Some('p'),
+            )
+            .named(
+                "max-time",
+                SyntaxShape::Duration,
+                "Max duration before timeout occurs.",
+                Some('m'),
+            )
+            .named(
+                "headers",
+                SyntaxShape::Any,
+                "Headers added to every request made with the session.",
+                Some('H'),
+            )
+            .switch(
+                "insecure",
+                "Allow insecure server connections when using SSL.",
+                Some('k'),
+            )
+            .param(
+                Flag::new("redirect-mode")
+                    .short('R')
+                    .arg(SyntaxShape::String)
+                    .desc(
+                        "What to do when encountering redirects. Default: 'follow'. Valid \
+                         options: 'follow' ('f'), 'manual' ('m'), 'error' ('e').",
+                    )
+                    .completion(Completion::new_list(RedirectMode::MODES)),
+            )
+            .category(Category::Network);
+        add_unix_socket_flag(sig)
+    }
+
+    fn description(&self) -> &str {
+        "Create a reusable http client to pass to other http commands."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pass the session to the other http commands with --session. Requests made with the same \
+         session share their keep-alive connections, and fall back to the session's base URL, \
+         headers, credentials and timeout. Flags given to the http command itself take precedence."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "client", "connection", "keep-alive"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let insecure = call.has_flag(engine_state, stack, "insecure")?;
+        let redirect: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "redirect-mode")?;
+        let unix_socket = call.get_flag(engine_state, stack, "unix-socket")?;
+        let headers: Option<Value> = call.get_flag(engine_state, stack, "headers")?;
+        let timeout: Option<Value> = call.get_flag(engine_state, stack, "max-time")?;
+
+        let headers = match headers {
+            Some(headers) => parse_custom_headers(&headers)?
+                .into_iter()
+                .filter_map(|(k, v)| v.coerce_into_string().ok().map(|v| (k, v)))
+                .collect(),
+            None => vec![],
+        };
+
+        if let Some(timeout) = &timeout
+            && timeout.as_duration()? < 1
+        {
+            return Err(ShellError::TypeMismatch {
+                err_message: "Timeout value must be an int and larger than 0".to_string(),
+                span: timeout.span(),
+            });
+        }
+
+        let cwd = engine_state.cwd(None)?;
+        // expanded right away, so that the session connects to the same socket from any directory
+        let unix_socket = unix_socket.map(|socket: Spanned<String>| Spanned {
+            item: expand_path_with(socket.item, &cwd, true)
+                .to_string_lossy()
+                .into_owned(),
+            span: socket.span,
+        });
+        let agent = http_client(
+            insecure,
+            http_parse_redirect_mode(redirect.clone())?,
+            expand_unix_socket_path(unix_socket.clone(), &cwd),
+            engine_state,
+            stack,
+        )?;
+
+        let session = HttpSessionValue {
+            base_url: call.get_flag(engine_state, stack, "base-url")?,
+            headers,
+            user: call.get_flag(engine_state, stack, "user")?,
+            password: call.get_flag(engine_state, stack, "password")?,
+            timeout,
+            insecure,
+            redirect,
+            unix_socket,
+            agent,
+        };
+
+        Ok(Value::custom(Box::new(session), call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Create a session for an API and use it for several requests.",
+                example: "let api = http session --base-url https://api.example.com/v1 --headers {Authorization: $'Bearer ($env.TOKEN)'}; http get --session $api /users; http post --session $api --content-type application/json /users {name: nushell}",
+                result: None,
+            },
+            Example {
+                description: "Override a header of the session for a single request.",
+                example: "let api = http session --base-url https://api.example.com --headers {Accept: application/json}; http get --session $api --headers {Accept: text/csv} /report",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Helper function to add the --session flag to command signatures.
+pub fn add_session_flag(sig: Signature) -> Signature {
+    sig.named(
+        "session",
+        SyntaxShape::Any,
+        "Send the request with a client created by `http session`.",
+        None,
+    )
+}
+
+/// The value produced by `http session`.
+///
+/// Cloning the value shares the underlying agent, so every copy of a session reuses the same
+/// connection pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSessionValue {
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    user: Option<String>,
+    password: Option<String>,
+    timeout: Option<Value>,
+    insecure: bool,
+    redirect: Option<Spanned<String>>,
+    unix_socket: Option<Spanned<String>>,
+    // the agent holds live connections and can't be serialized, a deserialized session falls
+    // back to a default agent and loses its connection settings.
+    #[serde(skip, default = "Agent::new_with_defaults")]
+    agent: Agent,
+}
+
+impl HttpSessionValue {
+    /// Reads the session passed with `--session`, if any.
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Option<Self>, ShellError> {
+        call.get_flag::<Value>(engine_state, stack, "session")?
+            .map(Self::try_from_value)
+            .transpose()
+    }
+
+    pub fn try_from_value(value: Value) -> Result<Self, ShellError> {
+        let span = value.span();
+        match value {
+            Value::Custom { val, .. } => match val.as_any().downcast_ref::<Self>() {
+                Some(session) => Ok(session.clone()),
+                None => Err(ShellError::CantConvert {
+                    to_type: "http session".into(),
+                    from_type: val.type_name(),
+                    span,
+                    help: Some("Create a session with `http session`.".into()),
+                }),
+            },
+            x => Err(ShellError::CantConvert {
+                to_type: "http session".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: Some("Create a session with `http session`.".into()),
+            }),
+        }
+    }
+
+    /// Fills in how the session connects for everything the command wasn't given, and returns
+    /// the session's agent to send the request with.
+    ///
+    /// A command that changes how to connect, with `--insecure`, `--redirect-mode` or
+    /// `--unix-socket`, gets `None` and has to create an agent of its own, which doesn't share
+    /// the session's connections.
+    pub fn connect_with(
+        &self,
+        insecure: &mut bool,
+        redirect: &mut Option<Spanned<String>>,
+        unix_socket: &mut Option<Spanned<String>>,
+    ) -> Option<Agent> {
+        let shared = !*insecure && redirect.is_none() && unix_socket.is_none();
+        *insecure |= self.insecure;
+        if redirect.is_none() {
+            redirect.clone_from(&self.redirect);
+        }
+        if unix_socket.is_none() {
+            unix_socket.clone_from(&self.unix_socket);
+        }
+        shared.then(|| self.agent.clone())
+    }
+
+    /// Returns a copy of the session that also sends the header `name`, replacing a header of
+    /// the same name the session already had.
+    pub fn with_header(&self, name: &str, value: String) -> Self {
+        let mut session = self.clone();
+        session
+            .headers
+            .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+        session.headers.push((name.into(), value));
+        session
+    }
+
+    /// Fills in the session's defaults for everything the command wasn't given explicitly, and
+    /// resolves a relative `url` against the session's base URL. How to connect is filled in by
+    /// [`connect_with`](Self::connect_with).
+    pub fn apply_defaults(
+        &self,
+        url: &mut Value,
+        headers: &mut Option<Value>,
+        user: &mut Option<String>,
+        password: &mut Option<String>,
+        timeout: &mut Option<Value>,
+    ) -> Result<(), ShellError> {
+        if let Some(base_url) = &self.base_url {
+            let span = url.span();
+            let requested_url = url.coerce_str()?;
+            if !requested_url.contains("://") {
+                let resolved = format!(
+                    "{}/{}",
+                    base_url.trim_end_matches('/'),
+                    requested_url.trim_start_matches('/')
+                );
+                *url = Value::string(resolved, span);
+            }
+        }
+
+        if !self.headers.is_empty() {
+            let span = headers.as_ref().map_or(url.span(), Value::span);
+            let mut merged: Record = self
+                .headers
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::string(v, span)))
+                .collect();
+            if let Some(headers) = headers {
+                for (k, v) in parse_custom_headers(headers)? {
+                    // header names are case insensitive, so make sure the override replaces the
+                    // session's header instead of adding a second one
+                    merged.retain(|name, _| !name.eq_ignore_ascii_case(&k));
+                    merged.push(k, v);
+                }
+            }
+            *headers = Some(Value::record(merged, span));
+        }
+
+        if user.is_none() && password.is_none() {
+            user.clone_from(&self.user);
+            password.clone_from(&self.password);
+        }
+        if timeout.is_none() {
+            timeout.clone_from(&self.timeout);
+        }
+
+        Ok(())
+    }
+}
+
+impl CustomValue for HttpSessionValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        self.typetag_name().to_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        // the password and credentials in headers are left out on purpose, so that printing a
+        // session doesn't leak them
+        let headers = self
+            .headers
+            .iter()
+            .map(|(k, v)| match is_credential_header(k) {
+                true => (k.clone(), Value::string(REDACTED, span)),
+                false => (k.clone(), Value::string(v, span)),
+            })
+            .collect();
+
+        Ok(Value::record(
+            record! {
+                "base_url" => self.base_url.clone().map_or(Value::nothing(span), |url| Value::string(url, span)),
+                "headers" => Value::record(headers, span),
+                "user" => self.user.clone().map_or(Value::nothing(span), |user| Value::string(user, span)),
+                "timeout" => self.timeout.clone().unwrap_or(Value::nothing(span)),
+                "insecure" => Value::bool(self.insecure, span),
+                "redirect_mode" => self.redirect.as_ref().map_or(Value::nothing(span), |mode| Value::string(&mode.item, span)),
+                "unix_socket" => self.unix_socket.as_ref().map_or(Value::nothing(span), |socket| Value::string(&socket.item, span)),
+            },
+            span,
+        ))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn typetag_name(&self) -> &'static str {
+        TYPE_NAME
+    }
+
+    fn typetag_deserialize(&self) {
+        unimplemented!("typetag_deserialize")
+    }
+}
+
+/// What the value of a header with credentials is shown as.
+const REDACTED: &str = "<redacted>";
+
+fn is_credential_header(name: &str) -> bool {
+    ["authorization", "proxy-authorization", "cookie"]
+        .iter()
+        .any(|header| name.eq_ignore_ascii_case(header))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(HttpSession {})
+    }
+
+    fn session() -> HttpSessionValue {
+        HttpSessionValue {
+            base_url: None,
+            headers: vec![
+                ("Authorization".into(), "Bearer secret".into()),
+                ("Accept".into(), "application/json".into()),
+            ],
+            user: None,
+            password: None,
+            timeout: None,
+            insecure: true,
+            redirect: None,
+            unix_socket: None,
+            agent: Agent::new_with_defaults(),
+        }
+    }
+
+    #[test]
+    fn test_printing_redacts_credentials() {
+        let value = session()
+            .to_base_value(Span::test_data())
+            .expect("session converts to a record");
+        let headers = value
+            .get_data_by_key("headers")
+            .expect("session has headers");
+
+        assert_eq!(
+            headers.get_data_by_key("Authorization"),
+            Some(Value::test_string(REDACTED))
+        );
+        assert_eq!(
+            headers.get_data_by_key("Accept"),
+            Some(Value::test_string("application/json"))
+        );
+    }
+
+    #[test]
+    fn test_flags_that_change_the_connection_skip_the_session_agent() {
+        let session = session();
+
+        let (mut insecure, mut redirect, mut unix_socket) = (false, None, None);
+        assert!(
+            session
+                .connect_with(&mut insecure, &mut redirect, &mut unix_socket)
+                .is_some()
+        );
+        // the session's settings are still filled in, for anything that checks them
+        assert!(insecure);
+
+        let mut redirect = Some(Spanned {
+            item: "manual".to_string(),
+            span: Span::test_data(),
+        });
+        assert!(
+            session
+                .connect_with(&mut false, &mut redirect, &mut None)
+                .is_none()
+        );
+
+        let mut unix_socket = Some(Spanned {
+            item: "/run/docker.sock".to_string(),
+            span: Span::test_data(),
+        });
+        assert!(
+            session
+                .connect_with(&mut false, &mut None, &mut unix_socket)
+                .is_none()
+        );
+    }
+}
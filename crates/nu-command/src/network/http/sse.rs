@@ -0,0 +1,146 @@
+use crate::network::http::timeout_extractor_reader::UreqTimeoutExtractorReader;
+use nu_engine::command_prelude::*;
+use nu_protocol::{ListStream, shell_error::io::IoError};
+use std::io::{BufRead, BufReader};
+use ureq::Body;
+
+type Response = http::Response<Body>;
+
+/// Turns a `text/event-stream` response into a stream of `{event, data, id}` records, yielding
+/// every event as soon as it was received.
+pub fn response_to_sse_stream(
+    response: Response,
+    engine_state: &EngineState,
+    span: Span,
+) -> PipelineData {
+    let reader = BufReader::new(UreqTimeoutExtractorReader {
+        r: response.into_body().into_reader(),
+    });
+    let events = SseEvents::new(reader, span);
+
+    ListStream::new(events, span, engine_state.signals().clone()).into()
+}
+
+/// Parses server-sent events as described in
+/// <https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation>.
+struct SseEvents<R> {
+    reader: Option<R>,
+    last_id: Option<String>,
+    span: Span,
+}
+
+impl<R: BufRead> SseEvents<R> {
+    fn new(reader: R, span: Span) -> Self {
+        Self {
+            reader: Some(reader),
+            last_id: None,
+            span,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SseEvents<R> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        let reader = self.reader.as_mut()?;
+        let mut event = None;
+        let mut data: Option<String> = None;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                // an event that isn't terminated by a blank line is incomplete and dropped
+                Ok(0) => {
+                    self.reader = None;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    self.reader = None;
+                    return Some(Value::error(
+                        IoError::new(err, self.span, None).into(),
+                        self.span,
+                    ));
+                }
+            }
+
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                // a blank line dispatches the event, unless it didn't have any data
+                let Some(data) = data.take() else {
+                    event = None;
+                    continue;
+                };
+                return Some(Value::record(
+                    record! {
+                        "event" => Value::string(event.unwrap_or_else(|| "message".into()), self.span),
+                        "data" => Value::string(data, self.span),
+                        "id" => self.last_id.clone().map_or(Value::nothing(self.span), |id| Value::string(id, self.span)),
+                    },
+                    self.span,
+                ));
+            }
+            if line.starts_with(':') {
+                // comments are commonly used as keep-alive pings
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => event = Some(value.to_string()),
+                "data" => match &mut data {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => data = Some(value.to_string()),
+                },
+                "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+                // `retry` only matters for reconnecting, which is left to the caller
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Value> {
+        SseEvents::new(input.as_bytes(), Span::test_data()).collect()
+    }
+
+    fn event(event: &str, data: &str, id: Option<&str>) -> Value {
+        Value::test_record(record! {
+            "event" => Value::test_string(event),
+            "data" => Value::test_string(data),
+            "id" => id.map_or(Value::test_nothing(), Value::test_string),
+        })
+    }
+
+    #[test]
+    fn test_sse_events() {
+        let input = ": ping\n\ndata: first\n\nevent: update\ndata:a\ndata: b\nid: 7\n\ndata: last\r\n\r\ndata: incomplete";
+
+        assert_eq!(
+            parse(input),
+            vec![
+                event("message", "first", None),
+                event("update", "a\nb", Some("7")),
+                event("message", "last", Some("7")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sse_event_without_data_is_dropped() {
+        assert_eq!(
+            parse("event: ignored\n\ndata: kept\n\n"),
+            vec![event("message", "kept", None)]
+        );
+    }
+}
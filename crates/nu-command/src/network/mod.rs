@@ -1,15 +1,31 @@
 #[cfg(feature = "network")]
+mod dns;
+#[cfg(feature = "network")]
+mod grpc;
+#[cfg(feature = "network")]
 mod http;
 #[cfg(feature = "network")]
+mod net;
+#[cfg(feature = "network")]
 mod port;
+#[cfg(feature = "sftp")]
+mod sftp;
 #[cfg(feature = "network")]
 pub mod tls;
 mod url;
 #[cfg(feature = "network")]
 mod version_check;
 
+#[cfg(feature = "network")]
+pub use self::dns::*;
+#[cfg(feature = "network")]
+pub use self::grpc::*;
 #[cfg(feature = "network")]
 pub use self::http::*;
+#[cfg(feature = "network")]
+pub use self::net::*;
+#[cfg(feature = "sftp")]
+pub use self::sftp::*;
 pub use self::url::*;
 
 #[cfg(feature = "network")]
@@ -0,0 +1,397 @@
+use crate::network::tls::{TlsStream, tls_stream};
+use nu_engine::command_prelude::*;
+use nu_protocol::{
+    ByteStreamType, Signals,
+    shell_error::{bridge::ShellErrorBridge, io::IoError},
+};
+use std::{
+    io::{self, Read, Write},
+    net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// UDP has no way to tell that the other side is done, so we stop after this much silence.
+const DEFAULT_UDP_TIMEOUT: Duration = Duration::from_secs(1);
+/// How often a blocked read wakes up to check for ctrl-c and the idle timeout.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+#[derive(Clone)]
+pub struct NetConnect;
+
+impl Command for NetConnect {
+    fn name(&self) -> &str {
+        "net connect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("net connect")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::String, Type::Any),
+                (Type::Binary, Type::Any),
+            ])
+            .required(
+                "address",
+                SyntaxShape::String,
+                "The host and port to connect to, e.g. example.com:80 or [::1]:8080.",
+            )
+            .switch(
+                "udp",
+                "Send the input as a UDP datagram instead.",
+                Some('u'),
+            )
+            .switch("tls", "Wrap the TCP connection in TLS.", None)
+            .switch(
+                "insecure",
+                "Don't verify the certificate of the server when using TLS.",
+                Some('k'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "Stop once nothing was received for this long. Default: wait for the other side \
+                 to close the connection, or 1sec with --udp.",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Open a raw TCP or UDP connection, send the input and stream back the response."
+    }
+
+    fn extra_description(&self) -> &str {
+        "This works like netcat: the input is sent as is, and everything the other side sends \
+         back is returned as a byte stream. Over plain TCP the sending side of the connection \
+         is shut down after the input was sent, so the other side knows the request is \
+         complete."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "network", "netcat", "nc", "telnet", "socket", "tcp", "udp", "tls", "banner",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let address: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let udp = call.has_flag(engine_state, stack, "udp")?;
+        let tls = call.has_flag(engine_state, stack, "tls")?;
+        let insecure = call.has_flag(engine_state, stack, "insecure")?;
+        let timeout = match call.get_flag::<Value>(engine_state, stack, "timeout")? {
+            Some(value) => {
+                let nanos = value.as_duration()?;
+                if nanos <= 0 {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "Timeout must be larger than 0".into(),
+                        val_span: value.span(),
+                        call_span: head,
+                    });
+                }
+                Some(Duration::from_nanos(nanos as u64))
+            }
+            None => None,
+        };
+
+        if udp && tls {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "can't use TLS".into(),
+                left_span: call.get_flag_span(stack, "tls").unwrap_or(head),
+                right_message: "over UDP".into(),
+                right_span: call.get_flag_span(stack, "udp").unwrap_or(head),
+            });
+        }
+
+        let payload = match input.into_value(head)? {
+            Value::Nothing { .. } => vec![],
+            Value::String { val, .. } => val.into_bytes(),
+            Value::Binary { val, .. } => val,
+            value => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string or binary".into(),
+                    wrong_type: value.get_type().to_string(),
+                    dst_span: head,
+                    src_span: value.span(),
+                });
+            }
+        };
+
+        let (host, port) = parse_address(&address)?;
+        let addrs = resolve(&host, port, address.span)?;
+        let io_err = |err: io::Error| ShellError::from(IoError::new(err, head, None));
+
+        let connect_timeout = timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+        let mut connection = if udp {
+            let socket = connect_udp(&addrs).map_err(io_err)?;
+            socket
+                .set_read_timeout(Some(POLL_INTERVAL))
+                .map_err(io_err)?;
+            Connection::Udp(socket)
+        } else {
+            let stream =
+                connect_tcp(&addrs, connect_timeout).map_err(|err| ShellError::NetworkFailure {
+                    msg: format!("Could not connect to {}: {err}", address.item),
+                    span: address.span,
+                })?;
+            let connection = if tls {
+                let stream = stream.try_clone().map_err(io_err)?;
+                Connection::Tls(Box::new(tls_stream(&host, stream, insecure, &[], head)?))
+            } else {
+                Connection::Tcp(stream.try_clone().map_err(io_err)?)
+            };
+            // the clone shares the socket, so this applies to TLS as well
+            stream
+                .set_read_timeout(Some(POLL_INTERVAL))
+                .map_err(io_err)?;
+            connection
+        };
+        if !payload.is_empty() {
+            connection.send(&payload).map_err(io_err)?;
+        }
+
+        let reader = ConnectionReader {
+            connection,
+            idle_timeout: timeout.or(udp.then_some(DEFAULT_UDP_TIMEOUT)),
+            last_received: Instant::now(),
+            datagram: Vec::new(),
+            datagram_pos: 0,
+            signals: engine_state.signals().clone(),
+            span: head,
+        };
+
+        Ok(PipelineData::byte_stream(
+            ByteStream::read(
+                reader,
+                head,
+                engine_state.signals().clone(),
+                ByteStreamType::Unknown,
+            ),
+            None,
+        ))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Read the banner of an SSH server.",
+                example: "net connect example.com:22 --timeout 2sec",
+                result: None,
+            },
+            Example {
+                description: "Send a hand written HTTP request.",
+                example: r#""GET / HTTP/1.0\r\nHost: example.com\r\n\r\n" | net connect example.com:80"#,
+                result: None,
+            },
+            Example {
+                description: "Send a request to an HTTPS server and show the status line.",
+                example: r#""HEAD / HTTP/1.0\r\n\r\n" | net connect --tls example.com:443 | lines | first"#,
+                result: None,
+            },
+            Example {
+                description: "Send a UDP datagram to a local statsd server.",
+                example: "'deploys:1|c' | net connect --udp 127.0.0.1:8125",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Splits `host:port`, where an IPv6 host has to be wrapped in brackets.
+pub(crate) fn parse_address(address: &Spanned<String>) -> Result<(String, u16), ShellError> {
+    let invalid = || ShellError::IncorrectValue {
+        msg: "Expected an address like host:port".into(),
+        val_span: address.span,
+        call_span: address.span,
+    };
+
+    let (host, port) = address.item.rsplit_once(':').ok_or_else(invalid)?;
+    let host = match host.strip_prefix('[') {
+        Some(host) => host.strip_suffix(']').ok_or_else(invalid)?,
+        None if host.contains(':') => return Err(invalid()),
+        None => host,
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    let port = port.parse::<u16>().map_err(|_| invalid())?;
+
+    Ok((host.to_string(), port))
+}
+
+pub(crate) fn resolve(host: &str, port: u16, span: Span) -> Result<Vec<SocketAddr>, ShellError> {
+    let addrs = (host, port)
+        .to_socket_addrs()
+        .map(|addrs| addrs.collect::<Vec<_>>())
+        .unwrap_or_default();
+    if addrs.is_empty() {
+        return Err(ShellError::NetworkFailure {
+            msg: format!("Could not resolve {host}"),
+            span,
+        });
+    }
+    Ok(addrs)
+}
+
+/// Tries every address in turn, returning the error of the last one if none worked.
+pub(crate) fn connect_tcp(addrs: &[SocketAddr], timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| io::ErrorKind::AddrNotAvailable.into()))
+}
+
+fn connect_udp(addrs: &[SocketAddr]) -> io::Result<UdpSocket> {
+    let mut last_err = None;
+    for addr in addrs {
+        let local: SocketAddr = if addr.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        match UdpSocket::bind(local).and_then(|socket| socket.connect(addr).map(|_| socket)) {
+            Ok(socket) => return Ok(socket),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| io::ErrorKind::AddrNotAvailable.into()))
+}
+
+enum Connection {
+    Tcp(TcpStream),
+    Tls(Box<TlsStream>),
+    Udp(UdpSocket),
+}
+
+impl Connection {
+    fn send(&mut self, payload: &[u8]) -> io::Result<()> {
+        match self {
+            Connection::Tcp(stream) => {
+                stream.write_all(payload)?;
+                stream.shutdown(Shutdown::Write)
+            }
+            // closing TLS for writing can't be told apart from closing the whole connection,
+            // which makes a lot of servers hang up without answering
+            Connection::Tls(stream) => {
+                stream.write_all(payload)?;
+                stream.flush()
+            }
+            Connection::Udp(socket) => socket.send(payload).map(|_| ()),
+        }
+    }
+}
+
+struct ConnectionReader {
+    connection: Connection,
+    idle_timeout: Option<Duration>,
+    last_received: Instant,
+    /// The rest of the last datagram, which may not have fit into the buffer it was read into.
+    datagram: Vec<u8>,
+    datagram_pos: usize,
+    signals: Signals,
+    span: Span,
+}
+
+impl Read for ConnectionReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.datagram_pos < self.datagram.len() {
+            let len = buf.len().min(self.datagram.len() - self.datagram_pos);
+            buf[..len].copy_from_slice(&self.datagram[self.datagram_pos..][..len]);
+            self.datagram_pos += len;
+            return Ok(len);
+        }
+
+        loop {
+            self.signals.check(&self.span).map_err(ShellErrorBridge)?;
+            if let Some(timeout) = self.idle_timeout
+                && self.last_received.elapsed() >= timeout
+            {
+                return Ok(0);
+            }
+
+            let result = match &mut self.connection {
+                Connection::Tcp(stream) => stream.read(buf),
+                Connection::Tls(stream) => match stream.read(buf) {
+                    // plenty of servers just close the connection without saying goodbye in TLS
+                    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(0),
+                    result => result,
+                },
+                Connection::Udp(socket) => {
+                    self.datagram.resize(MAX_DATAGRAM_SIZE, 0);
+                    socket.recv(&mut self.datagram).map(|received| {
+                        self.datagram.truncate(received);
+                        let len = buf.len().min(received);
+                        buf[..len].copy_from_slice(&self.datagram[..len]);
+                        self.datagram_pos = len;
+                        len
+                    })
+                }
+            };
+
+            match result {
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    if let Connection::Udp(_) = self.connection {
+                        self.datagram.clear();
+                    }
+                    continue;
+                }
+                // an empty datagram isn't the end of the stream
+                Ok(0) if matches!(self.connection, Connection::Udp(_)) => continue,
+                Ok(len) => {
+                    self.last_received = Instant::now();
+                    return Ok(len);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(address: &str) -> Result<(String, u16), ShellError> {
+        parse_address(&Spanned {
+            item: address.into(),
+            span: Span::test_data(),
+        })
+    }
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            parse("example.com:80").ok(),
+            Some(("example.com".into(), 80))
+        );
+        assert_eq!(parse("[::1]:8080").ok(), Some(("::1".into(), 8080)));
+        assert!(parse("example.com").is_err());
+        assert!(parse("::1:8080").is_err());
+        assert!(parse(":80").is_err());
+        assert!(parse("example.com:http").is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetConnect {})
+    }
+}
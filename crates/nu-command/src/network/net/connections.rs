@@ -0,0 +1,292 @@
+use nu_engine::command_prelude::*;
+use std::net::SocketAddr;
+
+#[derive(Clone)]
+pub struct NetConnections;
+
+impl Command for NetConnections {
+    fn name(&self) -> &str {
+        "net connections"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("net connections")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .switch("tcp", "Only list TCP sockets.", Some('t'))
+            .switch("udp", "Only list UDP sockets.", Some('u'))
+            .switch(
+                "listening",
+                "Only list sockets waiting for connections or datagrams.",
+                Some('l'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "List the open TCP and UDP sockets, along with the processes they belong to."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Only the owners of sockets whose process can be inspected are shown, so run as root to \
+         see all of them. This command is only supported on Linux and Android for now."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "network", "socket", "netstat", "ss", "lsof", "ports", "listen",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let tcp = call.has_flag(engine_state, stack, "tcp")?;
+        let udp = call.has_flag(engine_state, stack, "udp")?;
+        let listening = call.has_flag(engine_state, stack, "listening")?;
+        // neither flag means both
+        let (tcp, udp) = (tcp || !udp, udp || !tcp);
+
+        let sockets = sockets::list(tcp, udp, head)?
+            .into_iter()
+            .filter(|socket| !listening || socket.is_listening())
+            .map(|socket| socket.into_value(head))
+            .collect();
+
+        Ok(Value::list(sockets, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Find out which process listens on port 8080.",
+                example: "net connections --listening | where local_port == 8080",
+                result: None,
+            },
+            Example {
+                description: "Count the established TCP connections per process.",
+                example: "net connections --tcp | where state == established | group-by process | transpose process connections | update connections { length }",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// An open socket, as listed by the operating system.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+struct Socket {
+    protocol: &'static str,
+    local: SocketAddr,
+    remote: SocketAddr,
+    state: &'static str,
+    owner: Option<(u32, String)>,
+}
+
+impl Socket {
+    fn is_listening(&self) -> bool {
+        // unconnected UDP sockets are shown as closed, just as `ss` does
+        match self.protocol {
+            "udp" => self.remote.port() == 0,
+            _ => self.state == "listen",
+        }
+    }
+
+    fn into_value(self, span: Span) -> Value {
+        // the remote end of a socket that isn't connected is all zeros
+        let (remote_address, remote_port) = match self.remote.port() {
+            0 if self.remote.ip().is_unspecified() => (Value::nothing(span), Value::nothing(span)),
+            port => (
+                Value::string(self.remote.ip().to_string(), span),
+                Value::int(port.into(), span),
+            ),
+        };
+        let (pid, process) = match self.owner {
+            Some((pid, name)) => (Value::int(pid.into(), span), Value::string(name, span)),
+            None => (Value::nothing(span), Value::nothing(span)),
+        };
+
+        Value::record(
+            record! {
+                "protocol" => Value::string(self.protocol, span),
+                "local_address" => Value::string(self.local.ip().to_string(), span),
+                "local_port" => Value::int(self.local.port().into(), span),
+                "remote_address" => remote_address,
+                "remote_port" => remote_port,
+                "state" => Value::string(self.state, span),
+                "pid" => pid,
+                "process" => process,
+            },
+            span,
+        )
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sockets {
+    use super::*;
+    use procfs::{
+        ProcError,
+        net::{TcpState, UdpState},
+        process::FDTarget,
+    };
+    use std::collections::HashMap;
+
+    pub fn list(tcp: bool, udp: bool, span: Span) -> Result<Vec<Socket>, ShellError> {
+        let proc_error = |err: ProcError| ShellError::GenericError {
+            error: "Could not list the open sockets".into(),
+            msg: err.to_string(),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        };
+
+        // (protocol, local, remote, state, inode)
+        let mut found = Vec::new();
+        if tcp {
+            for entry in procfs::net::tcp()
+                .map_err(proc_error)?
+                .into_iter()
+                .chain(procfs::net::tcp6().unwrap_or_default())
+            {
+                let state = tcp_state(&entry.state);
+                found.push((
+                    "tcp",
+                    entry.local_address,
+                    entry.remote_address,
+                    state,
+                    entry.inode,
+                ));
+            }
+        }
+        if udp {
+            for entry in procfs::net::udp()
+                .map_err(proc_error)?
+                .into_iter()
+                .chain(procfs::net::udp6().unwrap_or_default())
+            {
+                let state = match entry.state {
+                    UdpState::Established => "established",
+                    UdpState::Close => "close",
+                };
+                found.push((
+                    "udp",
+                    entry.local_address,
+                    entry.remote_address,
+                    state,
+                    entry.inode,
+                ));
+            }
+        }
+
+        let mut owners = socket_owners();
+        Ok(found
+            .into_iter()
+            .map(|(protocol, local, remote, state, inode)| Socket {
+                protocol,
+                local,
+                remote,
+                state,
+                owner: owners.remove(&inode),
+            })
+            .collect())
+    }
+
+    fn tcp_state(state: &TcpState) -> &'static str {
+        match state {
+            TcpState::Established => "established",
+            TcpState::SynSent => "syn_sent",
+            TcpState::SynRecv => "syn_recv",
+            TcpState::FinWait1 => "fin_wait1",
+            TcpState::FinWait2 => "fin_wait2",
+            TcpState::TimeWait => "time_wait",
+            TcpState::Close => "close",
+            TcpState::CloseWait => "close_wait",
+            TcpState::LastAck => "last_ack",
+            TcpState::Listen => "listen",
+            TcpState::Closing => "closing",
+            TcpState::NewSynRecv => "new_syn_recv",
+        }
+    }
+
+    /// Maps the inode of every socket to the process that has it open. Processes that can't be
+    /// inspected, like those of other users, are skipped.
+    fn socket_owners() -> HashMap<u64, (u32, String)> {
+        let mut owners = HashMap::new();
+        let Ok(processes) = procfs::process::all_processes() else {
+            return owners;
+        };
+        for process in processes.flatten() {
+            let Ok(fds) = process.fd() else {
+                continue;
+            };
+            let name = process.stat().map(|stat| stat.comm).unwrap_or_default();
+            for fd in fds.flatten() {
+                if let FDTarget::Socket(inode) = fd.target {
+                    // a socket shared with child processes belongs to the first one found
+                    owners
+                        .entry(inode)
+                        .or_insert_with(|| (process.pid as u32, name.clone()));
+                }
+            }
+        }
+        owners
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod sockets {
+    use super::*;
+
+    pub fn list(_tcp: bool, _udp: bool, span: Span) -> Result<Vec<Socket>, ShellError> {
+        Err(ShellError::GenericError {
+            error: "net connections not supported".into(),
+            msg: "Listing sockets is not supported on this platform".into(),
+            span: Some(span),
+            help: Some("nushell needs support for this platform to be added".into()),
+            inner: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetConnections {})
+    }
+
+    fn socket(protocol: &'static str, remote: &str, state: &'static str) -> Socket {
+        Socket {
+            protocol,
+            local: "127.0.0.1:8080".parse().expect("valid address"),
+            remote: remote.parse().expect("valid address"),
+            state,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_is_listening() {
+        assert!(socket("tcp", "0.0.0.0:0", "listen").is_listening());
+        assert!(!socket("tcp", "10.0.0.1:50000", "established").is_listening());
+        assert!(socket("udp", "0.0.0.0:0", "close").is_listening());
+        assert!(!socket("udp", "10.0.0.1:53", "established").is_listening());
+    }
+
+    #[test]
+    fn test_unconnected_remote_is_null() {
+        let value = socket("tcp", "[::]:0", "listen").into_value(Span::test_data());
+        assert_eq!(
+            value.get_data_by_key("remote_port"),
+            Some(Value::test_nothing())
+        );
+    }
+}
@@ -0,0 +1,8 @@
+mod connect;
+mod connections;
+mod net_;
+
+pub use connect::NetConnect;
+pub(crate) use connect::{connect_tcp, parse_address, resolve};
+pub use connections::NetConnections;
+pub use net_::Net;
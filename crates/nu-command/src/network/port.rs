@@ -1,7 +1,16 @@
+use chrono::Local;
 use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::io::IoError;
+use nu_protocol::{ListStream, shell_error::io::IoError};
 
-use std::{net::TcpListener, ops::RangeInclusive};
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, TcpListener},
+    ops::RangeInclusive,
+    time::Duration,
+};
+
+/// How often the listener checks for ctrl-c while waiting for connections.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone)]
 pub struct Port;
@@ -13,13 +22,27 @@ impl Command for Port {
 
     fn signature(&self) -> Signature {
         Signature::build("port")
-            .input_output_types(vec![(Type::Nothing, Type::Int)])
+            .input_output_types(vec![
+                (Type::Nothing, Type::Int),
+                (Type::Nothing, Type::list(Type::record())),
+            ])
             .optional(
                 "start",
                 SyntaxShape::Int,
                 "The start port to scan (inclusive).",
             )
             .optional("end", SyntaxShape::Int, "The end port to scan (inclusive).")
+            .switch(
+                "listen",
+                "Listen on the free port and return a stream of the connections made to it.",
+                Some('l'),
+            )
+            .named(
+                "address",
+                SyntaxShape::String,
+                "The address to listen on with --listen. Default: 127.0.0.1.",
+                Some('a'),
+            )
             .category(Category::Network)
     }
 
@@ -27,8 +50,15 @@ impl Command for Port {
         "Get a free TCP port from system."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --listen, the port is kept open and every connection made to it is reported as a \
+         record with the addresses of both ends and the time it was made. Connections are closed \
+         right after they are accepted, which is enough to check that a host or firewall lets \
+         them through."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["network", "http"]
+        vec!["network", "http", "listen", "server"]
     }
 
     fn run(
@@ -38,7 +68,14 @@ impl Command for Port {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        get_free_port(engine_state, stack, call)
+        let address: Option<Spanned<String>> = call.get_flag(engine_state, stack, "address")?;
+        let free_port = get_free_port(engine_state, stack, call)?;
+
+        if call.has_flag(engine_state, stack, "listen")? {
+            listen(engine_state, call, address, free_port)
+        } else {
+            Ok(Value::int(free_port as i64, call.head).into_pipeline_data())
+        }
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -53,6 +90,11 @@ impl Command for Port {
                 example: "port",
                 result: None,
             },
+            Example {
+                description: "report the connections made to port 8080 from other hosts",
+                example: "port --listen --address 0.0.0.0 8080 8080",
+                result: None,
+            },
         ]
     }
 }
@@ -61,7 +103,7 @@ fn get_free_port(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-) -> Result<PipelineData, ShellError> {
+) -> Result<u16, ShellError> {
     let from_io_error = IoError::factory(call.head, None);
 
     let start_port: Option<Spanned<usize>> = call.opt(engine_state, stack, 0)?;
@@ -123,7 +165,70 @@ fn get_free_port(
         search_port_in_range((start_port..=end_port).into_spanned(range_span), call.head)?
     };
 
-    Ok(Value::int(free_port as i64, call.head).into_pipeline_data())
+    Ok(free_port)
+}
+
+/// Listens on `port`, streaming a record for every connection accepted until interrupted.
+fn listen(
+    engine_state: &EngineState,
+    call: &Call,
+    address: Option<Spanned<String>>,
+    port: u16,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let ip = match address {
+        Some(address) => {
+            address
+                .item
+                .parse::<IpAddr>()
+                .map_err(|_| ShellError::IncorrectValue {
+                    msg: "Expected an IP address like 127.0.0.1 or ::".into(),
+                    val_span: address.span,
+                    call_span: head,
+                })?
+        }
+        None => Ipv4Addr::LOCALHOST.into(),
+    };
+
+    let from_io_error = IoError::factory(head, None);
+    let listener = TcpListener::bind((ip, port)).map_err(&from_io_error)?;
+    // accepting without blocking lets the stream notice ctrl-c while nobody connects
+    listener.set_nonblocking(true).map_err(&from_io_error)?;
+    let local = listener.local_addr().map_err(&from_io_error)?;
+
+    let signals = engine_state.signals().clone();
+    let iter = {
+        let signals = signals.clone();
+        std::iter::from_fn(move || {
+            loop {
+                match listener.accept() {
+                    Ok((_, remote)) => {
+                        return Some(Value::record(
+                            record! {
+                                "remote_address" => Value::string(remote.ip().to_string(), head),
+                                "remote_port" => Value::int(remote.port().into(), head),
+                                "local_address" => Value::string(local.ip().to_string(), head),
+                                "local_port" => Value::int(local.port().into(), head),
+                                "time" => Value::date(Local::now().fixed_offset(), head),
+                            },
+                            head,
+                        ));
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        if signals.interrupted() {
+                            return None;
+                        }
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                    Err(err) => {
+                        return Some(Value::error(IoError::new(err, head, None).into(), head));
+                    }
+                }
+            }
+        })
+    };
+
+    Ok(ListStream::new(iter, head, signals).into())
 }
 
 fn system_provided_port() -> Result<u16, std::io::Error> {
@@ -0,0 +1,291 @@
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use ssh2::{CheckResult, KnownHostFileKind, Session};
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    time::Duration,
+};
+
+const DEFAULT_PORT: u16 = 22;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The keys tried when neither `--identity` is given nor an agent is running, like ssh does.
+const DEFAULT_KEYS: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// Helper function to add the flags for connecting and authenticating to the sftp commands.
+pub fn add_connection_flags(sig: Signature) -> Signature {
+    sig.named(
+        "port",
+        SyntaxShape::Int,
+        "The port of the SSH server. Default: 22.",
+        Some('p'),
+    )
+    .named(
+        "identity",
+        SyntaxShape::Filepath,
+        "The private key to authenticate with. Default: the keys of a running ssh-agent, then \
+         ~/.ssh/id_ed25519, ~/.ssh/id_ecdsa and ~/.ssh/id_rsa.",
+        Some('i'),
+    )
+    .named(
+        "passphrase",
+        SyntaxShape::String,
+        "The passphrase of the private key.",
+        None,
+    )
+    .switch(
+        "insecure",
+        "Don't check the host key of the server against ~/.ssh/known_hosts.",
+        Some('k'),
+    )
+}
+
+/// A remote path in the form `[user@]host:path`, like scp takes them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Remote {
+    pub user: Option<String>,
+    pub host: String,
+    pub path: String,
+}
+
+impl Remote {
+    pub fn parse(remote: &Spanned<String>) -> Result<Self, ShellError> {
+        let invalid = || ShellError::IncorrectValue {
+            msg: "Expected a remote path like user@host:path".into(),
+            val_span: remote.span,
+            call_span: remote.span,
+        };
+
+        let (user, rest) = match remote.item.split_once('@') {
+            // an @ after the host is part of the path
+            Some((user, _)) if user.contains([':', '[']) => (None, remote.item.as_str()),
+            Some(("", _)) => return Err(invalid()),
+            Some((user, rest)) => (Some(user.to_string()), rest),
+            None => (None, remote.item.as_str()),
+        };
+        let (host, path) = match rest.strip_prefix('[') {
+            // IPv6 addresses have to be put in brackets to tell them apart from the path
+            Some(rest) => {
+                let (host, rest) = rest.split_once(']').ok_or_else(invalid)?;
+                match rest {
+                    "" => (host, ""),
+                    rest => (host, rest.strip_prefix(':').ok_or_else(invalid)?),
+                }
+            }
+            None => rest.split_once(':').unwrap_or((rest, "")),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Remote {
+            user,
+            host: host.to_string(),
+            path: path.to_string(),
+        })
+    }
+}
+
+/// Connects and authenticates to the server in `remote`, then starts an SFTP session.
+pub fn connect(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    remote: &Remote,
+    span: Span,
+) -> Result<ssh2::Sftp, ShellError> {
+    let port = match call.get_flag::<Spanned<i64>>(engine_state, stack, "port")? {
+        Some(Spanned { item, span }) => {
+            u16::try_from(item).map_err(|_| ShellError::IncorrectValue {
+                msg: "Port must be between 0 and 65535".into(),
+                val_span: span,
+                call_span: call.head,
+            })?
+        }
+        None => DEFAULT_PORT,
+    };
+    let identity: Option<Spanned<String>> = call.get_flag(engine_state, stack, "identity")?;
+    let passphrase: Option<String> = call.get_flag(engine_state, stack, "passphrase")?;
+    let insecure = call.has_flag(engine_state, stack, "insecure")?;
+
+    let user = match &remote.user {
+        Some(user) => user.clone(),
+        None => ["USER", "USERNAME"]
+            .into_iter()
+            .find_map(|name| stack.get_env_var(engine_state, name))
+            .and_then(|user| user.coerce_string().ok())
+            .ok_or_else(|| ShellError::GenericError {
+                error: "Unknown user".into(),
+                msg: "could not tell which user to log in as".into(),
+                span: Some(span),
+                help: Some("Put the user in front of the host, e.g. user@host:path".into()),
+                inner: vec![],
+            })?,
+    };
+
+    let ssh_error = |msg: &str, err: ssh2::Error| ShellError::NetworkFailure {
+        msg: format!("{msg}: {err}"),
+        span,
+    };
+
+    let addr = (remote.host.as_str(), port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| ShellError::NetworkFailure {
+            msg: format!("Could not resolve {}", remote.host),
+            span,
+        })?;
+    let tcp = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).map_err(|err| {
+        ShellError::NetworkFailure {
+            msg: format!("Could not connect to {}:{port}: {err}", remote.host),
+            span,
+        }
+    })?;
+
+    let mut session = Session::new().map_err(|err| ssh_error("Could not start SSH", err))?;
+    session.set_tcp_stream(tcp);
+    session
+        .handshake()
+        .map_err(|err| ssh_error("SSH handshake failed", err))?;
+
+    if !insecure {
+        check_host_key(&session, &remote.host, port, span)?;
+    }
+
+    let home = nu_path::home_dir().map(|home| home.join(".ssh").into_std_path_buf());
+    match identity {
+        Some(identity) => {
+            let cwd = engine_state.cwd(Some(stack))?;
+            let key = expand_path_with(identity.item, cwd, true);
+            session
+                .userauth_pubkey_file(&user, None, &key, passphrase.as_deref())
+                .map_err(|err| ssh_error("Could not authenticate with the given key", err))?;
+        }
+        None => {
+            // like ssh, try the agent first and then whatever default key exists
+            if session.userauth_agent(&user).is_err() {
+                let keys = home
+                    .iter()
+                    .flat_map(|home| DEFAULT_KEYS.iter().map(move |name| home.join(name)));
+                for key in keys.filter(|key| key.exists()) {
+                    if session
+                        .userauth_pubkey_file(&user, None, &key, passphrase.as_deref())
+                        .is_ok()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if !session.authenticated() {
+        return Err(ShellError::GenericError {
+            error: "SSH authentication failed".into(),
+            msg: format!("could not log in to {} as {user}", remote.host),
+            span: Some(span),
+            help: Some(
+                "Add your key to a running ssh-agent, or pass the key to use with --identity"
+                    .into(),
+            ),
+            inner: vec![],
+        });
+    }
+
+    session
+        .sftp()
+        .map_err(|err| ssh_error("Could not start SFTP", err))
+}
+
+fn check_host_key(session: &Session, host: &str, port: u16, span: Span) -> Result<(), ShellError> {
+    let Some((key, _)) = session.host_key() else {
+        return Err(ShellError::NetworkFailure {
+            msg: "Server didn't send a host key".into(),
+            span,
+        });
+    };
+
+    let known_hosts_file = nu_path::home_dir()
+        .map(|home| home.join(".ssh").join("known_hosts").into_std_path_buf())
+        .unwrap_or_else(PathBuf::new);
+    let result = session
+        .known_hosts()
+        .map(|mut known_hosts| {
+            // a missing file just means that no host is known yet
+            let _ = known_hosts.read_file(&known_hosts_file, KnownHostFileKind::OpenSSH);
+            known_hosts.check_port(host, port, key)
+        })
+        .unwrap_or(CheckResult::Failure);
+
+    let (msg, help) = match result {
+        CheckResult::Match => return Ok(()),
+        CheckResult::Mismatch => (
+            format!("The host key of {host} doesn't match the one in known_hosts"),
+            "Someone could be intercepting the connection. If the key was changed on purpose, \
+             remove the old one with `ssh-keygen -R`",
+        ),
+        CheckResult::NotFound => (
+            format!("{host} is not a known host"),
+            "Connect once with ssh to add it to ~/.ssh/known_hosts, or skip the check with \
+             --insecure",
+        ),
+        CheckResult::Failure => (
+            format!("Could not check the host key of {host}"),
+            "Make sure ~/.ssh/known_hosts is readable, or skip the check with --insecure",
+        ),
+    };
+    Err(ShellError::GenericError {
+        error: "Host key verification failed".into(),
+        msg,
+        span: Some(span),
+        help: Some(help.into()),
+        inner: vec![],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(remote: &str) -> Result<Remote, ShellError> {
+        Remote::parse(&Spanned {
+            item: remote.into(),
+            span: Span::test_data(),
+        })
+    }
+
+    fn remote(user: Option<&str>, host: &str, path: &str) -> Remote {
+        Remote {
+            user: user.map(String::from),
+            host: host.into(),
+            path: path.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_remote() {
+        assert_eq!(
+            parse("me@example.com:/var/log").ok(),
+            Some(remote(Some("me"), "example.com", "/var/log"))
+        );
+        assert_eq!(
+            parse("example.com:notes.txt").ok(),
+            Some(remote(None, "example.com", "notes.txt"))
+        );
+        assert_eq!(
+            parse("example.com").ok(),
+            Some(remote(None, "example.com", ""))
+        );
+        assert_eq!(
+            parse("me@[::1]:/tmp").ok(),
+            Some(remote(Some("me"), "::1", "/tmp"))
+        );
+        assert_eq!(
+            parse("example.com:/tmp/a@b").ok(),
+            Some(remote(None, "example.com", "/tmp/a@b"))
+        );
+        assert!(parse("@example.com:/tmp").is_err());
+        assert!(parse(":/tmp").is_err());
+        assert!(parse("[::1/tmp").is_err());
+    }
+}
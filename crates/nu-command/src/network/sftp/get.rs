@@ -0,0 +1,114 @@
+use super::connection::{Remote, add_connection_flags, connect};
+use nu_engine::command_prelude::*;
+use nu_protocol::{ByteStreamType, shell_error::io::IoError};
+use std::{io, path::PathBuf};
+
+#[derive(Clone)]
+pub struct SftpGet;
+
+impl Command for SftpGet {
+    fn name(&self) -> &str {
+        "sftp get"
+    }
+
+    fn signature(&self) -> Signature {
+        let sig = Signature::build("sftp get")
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .required(
+                "remote",
+                SyntaxShape::String,
+                "The file to download, as [user@]host:path.",
+            )
+            .category(Category::Network);
+
+        add_connection_flags(sig)
+    }
+
+    fn description(&self) -> &str {
+        "Stream the contents of a file on a remote server over SFTP."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The file is streamed as it is downloaded, so it can be saved with `save` or processed \
+         right away without buffering all of it."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "ssh", "scp", "remote", "download", "fetch"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let remote_arg: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let remote = Remote::parse(&remote_arg)?;
+        if remote.path.is_empty() {
+            return Err(ShellError::IncorrectValue {
+                msg: "Expected the path of the file to download, e.g. host:notes.txt".into(),
+                val_span: remote_arg.span,
+                call_span: head,
+            });
+        }
+        let sftp = connect(engine_state, stack, call, &remote, remote_arg.span)?;
+
+        let path = PathBuf::from(&remote.path);
+        let io_err =
+            |err: ssh2::Error| IoError::new(io::Error::from(err), remote_arg.span, path.clone());
+        let stat = sftp.stat(&path).map_err(io_err)?;
+        if stat.is_dir() {
+            return Err(ShellError::IncorrectValue {
+                msg: "Can't download a directory, use `sftp ls` to list its contents".into(),
+                val_span: remote_arg.span,
+                call_span: head,
+            });
+        }
+        let file = sftp.open(&path).map_err(io_err)?;
+
+        let stream = ByteStream::read(
+            file,
+            head,
+            engine_state.signals().clone(),
+            ByteStreamType::Unknown,
+        )
+        .with_known_size(stat.size);
+
+        Ok(PipelineData::byte_stream(stream, None))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Download a file from a server.",
+                example: "sftp get me@example.com:backup.tar.gz | save backup.tar.gz",
+                result: None,
+            },
+            Example {
+                description: "Read a remote JSON config without saving it first.",
+                example: "sftp get me@example.com:/etc/app/config.json | from json",
+                result: None,
+            },
+            Example {
+                description: "Follow the last lines of a remote log through a server on another port.",
+                example: "sftp get --port 2222 me@example.com:/var/log/app.log | lines | last 20",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SftpGet {})
+    }
+}
@@ -0,0 +1,166 @@
+use super::connection::{Remote, add_connection_flags, connect};
+use chrono::{DateTime, Local};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use ssh2::FileStat;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone)]
+pub struct SftpLs;
+
+impl Command for SftpLs {
+    fn name(&self) -> &str {
+        "sftp ls"
+    }
+
+    fn signature(&self) -> Signature {
+        let sig = Signature::build("sftp ls")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("name".into(), Type::String),
+                        ("type".into(), Type::String),
+                        ("size".into(), Type::Filesize),
+                        ("mode".into(), Type::String),
+                        ("modified".into(), Type::Date),
+                    ]
+                    .into(),
+                ),
+            )])
+            .required(
+                "remote",
+                SyntaxShape::String,
+                "The directory to list, as [user@]host:path. Default path: the home directory.",
+            )
+            .category(Category::Network);
+
+        add_connection_flags(sig)
+    }
+
+    fn description(&self) -> &str {
+        "List the contents of a directory on a remote server over SFTP."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "ssh", "scp", "remote", "dir"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let remote_arg: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let remote = Remote::parse(&remote_arg)?;
+        let sftp = connect(engine_state, stack, call, &remote, remote_arg.span)?;
+
+        let dir = match remote.path.as_str() {
+            "" => ".",
+            path => path,
+        };
+        let mut entries = sftp.readdir(dir).map_err(|err| {
+            IoError::new(io::Error::from(err), remote_arg.span, PathBuf::from(dir))
+        })?;
+
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let entries = entries
+            .iter()
+            .map(|(path, stat)| entry_to_value(path, stat, head))
+            .collect();
+
+        Ok(Value::list(entries, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "List your home directory on a server.",
+                example: "sftp ls me@example.com",
+                result: None,
+            },
+            Example {
+                description: "Find the largest logs on a server.",
+                example: "sftp ls me@example.com:/var/log | where type == file | sort-by size --reverse | first 5",
+                result: None,
+            },
+            Example {
+                description: "List a directory authenticating with a specific key.",
+                example: "sftp ls --identity ~/.ssh/deploy_key deploy@example.com:/srv/app",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn entry_to_value(path: &Path, stat: &FileStat, span: Span) -> Value {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned());
+    let file_type = stat.file_type();
+    let kind = if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "dir"
+    } else if file_type.is_file() {
+        "file"
+    } else {
+        "unknown"
+    };
+    let modified = stat
+        .mtime
+        .and_then(|mtime| DateTime::from_timestamp(i64::try_from(mtime).ok()?, 0))
+        .map_or(Value::nothing(span), |mtime| {
+            Value::date(mtime.with_timezone(&Local).fixed_offset(), span)
+        });
+
+    Value::record(
+        record! {
+            "name" => Value::string(name, span),
+            "type" => Value::string(kind, span),
+            "size" => stat.size.map_or(Value::nothing(span), |size| Value::filesize(size as i64, span)),
+            "mode" => stat.perm.map_or(Value::nothing(span), |perm| Value::string(mode_string(perm), span)),
+            "modified" => modified,
+        },
+        span,
+    )
+}
+
+/// Formats the permission bits of `perm` like `ls -l` does, e.g. `rwxr-xr-x`.
+fn mode_string(perm: u32) -> String {
+    (0..9)
+        .map(|bit| {
+            if perm & (0o400 >> bit) == 0 {
+                '-'
+            } else {
+                ['r', 'w', 'x'][bit % 3]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mode_string() {
+        assert_eq!(mode_string(0o100755), "rwxr-xr-x");
+        assert_eq!(mode_string(0o640), "rw-r-----");
+        assert_eq!(mode_string(0), "---------");
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SftpLs {})
+    }
+}
@@ -0,0 +1,10 @@
+mod connection;
+mod get;
+mod ls;
+mod put;
+mod sftp_;
+
+pub use get::SftpGet;
+pub use ls::SftpLs;
+pub use put::SftpPut;
+pub use sftp_::Sftp;
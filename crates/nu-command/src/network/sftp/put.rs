@@ -0,0 +1,144 @@
+use super::connection::{Remote, add_connection_flags, connect};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use ssh2::OpenFlags;
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+#[derive(Clone)]
+pub struct SftpPut;
+
+impl Command for SftpPut {
+    fn name(&self) -> &str {
+        "sftp put"
+    }
+
+    fn signature(&self) -> Signature {
+        let sig = Signature::build("sftp put")
+            .input_output_types(vec![
+                (Type::String, Type::Nothing),
+                (Type::Binary, Type::Nothing),
+            ])
+            .required(
+                "remote",
+                SyntaxShape::String,
+                "The file to write, as [user@]host:path.",
+            )
+            .switch(
+                "append",
+                "Append the input to the end of the file instead of replacing it.",
+                Some('a'),
+            )
+            .category(Category::Network);
+
+        add_connection_flags(sig)
+    }
+
+    fn description(&self) -> &str {
+        "Write the input to a file on a remote server over SFTP."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Streams are uploaded as they are read, so large files aren't buffered in memory."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "ssh", "scp", "remote", "upload", "save"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let remote_arg: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let append = call.has_flag(engine_state, stack, "append")?;
+        let remote = Remote::parse(&remote_arg)?;
+        if remote.path.is_empty() {
+            return Err(ShellError::IncorrectValue {
+                msg: "Expected the path of the file to write, e.g. host:notes.txt".into(),
+                val_span: remote_arg.span,
+                call_span: head,
+            });
+        }
+
+        // check the input before connecting, so wrong input fails fast
+        let input = match input {
+            PipelineData::ByteStream(..) => input,
+            PipelineData::Value(Value::String { .. } | Value::Binary { .. }, ..) => input,
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string or binary".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: other.span().unwrap_or(head),
+                });
+            }
+        };
+
+        let sftp = connect(engine_state, stack, call, &remote, remote_arg.span)?;
+
+        let path = PathBuf::from(&remote.path);
+        let io_err =
+            |err: io::Error| ShellError::from(IoError::new(err, remote_arg.span, path.clone()));
+        let flags = if append {
+            OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::APPEND
+        } else {
+            OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE
+        };
+        let mut file = sftp
+            .open_mode(&path, flags, 0o644, ssh2::OpenType::File)
+            .map_err(|err| io_err(err.into()))?;
+
+        match input {
+            PipelineData::ByteStream(stream, ..) => stream.write_to(&mut file)?,
+            PipelineData::Value(Value::String { val, .. }, ..) => {
+                file.write_all(val.as_bytes()).map_err(io_err)?
+            }
+            PipelineData::Value(Value::Binary { val, .. }, ..) => {
+                file.write_all(&val).map_err(io_err)?
+            }
+            _ => {}
+        }
+        file.flush().map_err(io_err)?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Upload a local file to a server.",
+                example: "open --raw report.pdf | sftp put me@example.com:reports/report.pdf",
+                result: None,
+            },
+            Example {
+                description: "Write a generated config to a server.",
+                example: "{port: 8080} | to json | sftp put me@example.com:/etc/app/config.json",
+                result: None,
+            },
+            Example {
+                description: "Append a line to a remote log.",
+                example: "$'deployed (date now)(char nl)' | sftp put --append me@example.com:deploys.log",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SftpPut {})
+    }
+}
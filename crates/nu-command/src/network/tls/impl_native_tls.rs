@@ -1,4 +1,6 @@
-use nu_protocol::ShellError;
+use std::net::TcpStream;
+
+use nu_protocol::{ShellError, Span};
 use ureq::tls::{TlsConfig, TlsProvider};
 
 #[doc = include_str!("./tls_config.rustdoc.md")]
@@ -10,3 +12,34 @@ pub fn tls_config(allow_insecure: bool) -> Result<TlsConfig, ShellError> {
         .disable_verification(allow_insecure)
         .build())
 }
+
+/// A TLS connection over a plain TCP stream, as created by [`tls_stream`].
+pub type TlsStream = native_tls::TlsStream<TcpStream>;
+
+/// Wraps an already connected `stream` in TLS for talking to `host`, for commands that need a
+/// raw connection instead of going through [`ureq`].
+///
+/// The handshake is done right away, so certificate errors show up here and not on the first
+/// read. If `allow_insecure` is set to `true`, the certificate isn't verified. The protocols in
+/// `alpn`, like `h2`, are offered to the server during the handshake.
+pub fn tls_stream(
+    host: &str,
+    stream: TcpStream,
+    allow_insecure: bool,
+    alpn: &[&str],
+    span: Span,
+) -> Result<TlsStream, ShellError> {
+    let handshake_failed = |msg: String| ShellError::NetworkFailure {
+        msg: format!("TLS handshake with {host} failed: {msg}"),
+        span,
+    };
+
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(allow_insecure)
+        .request_alpns(alpn)
+        .build()
+        .map_err(|err| handshake_failed(err.to_string()))?;
+    connector
+        .connect(host, stream)
+        .map_err(|err| handshake_failed(err.to_string()))
+}
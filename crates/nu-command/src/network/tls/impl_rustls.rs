@@ -1,7 +1,16 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    net::TcpStream,
+    sync::{Arc, OnceLock},
+};
 
-use nu_protocol::ShellError;
-use rustls::crypto::CryptoProvider;
+use nu_protocol::{ShellError, Span};
+use rustls::{
+    ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, SignatureScheme,
+    StreamOwned,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+};
 use ureq::tls::{RootCerts, TlsConfig};
 
 // TODO: replace all these generic errors with proper errors
@@ -156,3 +165,121 @@ pub fn native_certs() -> RootCerts {
 
     RootCerts::Specific(certs)
 }
+
+/// A TLS connection over a plain TCP stream, as created by [`tls_stream`].
+pub type TlsStream = StreamOwned<ClientConnection, TcpStream>;
+
+/// Wraps an already connected `stream` in TLS for talking to `host`, for commands that need a
+/// raw connection instead of going through [`ureq`].
+///
+/// The handshake is done right away, so certificate errors show up here and not on the first
+/// read. If `allow_insecure` is set to `true`, the certificate isn't verified. The protocols in
+/// `alpn`, like `h2`, are offered to the server during the handshake.
+pub fn tls_stream(
+    host: &str,
+    mut stream: TcpStream,
+    allow_insecure: bool,
+    alpn: &[&str],
+    span: Span,
+) -> Result<TlsStream, ShellError> {
+    let handshake_failed = |msg: String| ShellError::NetworkFailure {
+        msg: format!("TLS handshake with {host} failed: {msg}"),
+        span,
+    };
+
+    let crypto_provider = CRYPTO_PROVIDER.get()?;
+    let builder = ClientConfig::builder_with_provider(crypto_provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| handshake_failed(err.to_string()))?;
+    let mut config = match allow_insecure {
+        false => builder.with_root_certificates(root_cert_store()),
+        true => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(crypto_provider))),
+    }
+    .with_no_client_auth();
+    config.alpn_protocols = alpn
+        .iter()
+        .map(|protocol| protocol.as_bytes().to_vec())
+        .collect();
+
+    let server_name =
+        ServerName::try_from(host.to_string()).map_err(|_| ShellError::IncorrectValue {
+            msg: format!("'{host}' is not a valid TLS server name"),
+            val_span: span,
+            call_span: span,
+        })?;
+    let mut connection = ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|err| handshake_failed(err.to_string()))?;
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut stream)
+            .map_err(|err| handshake_failed(err.to_string()))?;
+    }
+
+    Ok(StreamOwned::new(connection, stream))
+}
+
+fn root_cert_store() -> RootCertStore {
+    #[cfg(feature = "os")]
+    {
+        let mut store = RootCertStore::empty();
+        store.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        if !store.is_empty() {
+            return store;
+        }
+    }
+
+    RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    }
+}
+
+/// Accepts any certificate, while still checking that the handshake is signed by it.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
@@ -1,6 +1,7 @@
 use filesize::file_real_size_fast;
 use nu_glob::Pattern;
 use nu_protocol::{ShellError, Signals, Span, Value, record, shell_error::io::IoError};
+use rayon::prelude::*;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -10,6 +11,10 @@ pub struct DirBuilder {
     pub deref: bool,
     pub exclude: Option<Pattern>,
     pub long: bool,
+    /// Only list entries of at least this size, or at most this size if it's negative.
+    pub threshold: Option<i64>,
+    /// Compare the apparent size instead of the allocated size with the threshold.
+    pub apparent_size: bool,
 }
 
 impl DirBuilder {
@@ -19,6 +24,8 @@ impl DirBuilder {
         deref: bool,
         exclude: Option<Pattern>,
         long: bool,
+        threshold: Option<i64>,
+        apparent_size: bool,
     ) -> DirBuilder {
         DirBuilder {
             tag,
@@ -26,6 +33,22 @@ impl DirBuilder {
             deref,
             exclude,
             long,
+            threshold,
+            apparent_size,
+        }
+    }
+
+    /// Whether an entry of these sizes should be listed, according to `--threshold`.
+    pub fn within_threshold(&self, apparent: u64, physical: u64) -> bool {
+        let size = if self.apparent_size {
+            apparent
+        } else {
+            physical
+        };
+        match self.threshold {
+            Some(threshold) if threshold < 0 => size <= threshold.unsigned_abs(),
+            Some(threshold) => size >= threshold as u64,
+            None => true,
         }
     }
 }
@@ -80,6 +103,15 @@ impl FileInfo {
             Err(e) => Err(IoError::new(e, tag, path).into()),
         }
     }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    /// The space allocated on disk for the file, or its size if that isn't known.
+    pub fn get_blocks(&self) -> u64 {
+        self.blocks.unwrap_or(self.size)
+    }
 }
 
 impl DirInfo {
@@ -112,68 +144,66 @@ impl DirInfo {
             Err(e) => s = s.add_error(from_io_error(e).into()),
         };
 
-        match std::fs::read_dir(&s.path) {
-            Ok(d) => {
-                for f in d {
-                    signals.check(&span)?;
+        let entries = match std::fs::read_dir(&s.path) {
+            Ok(d) => d.collect::<Vec<_>>(),
+            Err(e) => return Ok(s.add_error(from_io_error(e).into())),
+        };
 
-                    match f {
-                        Ok(i) => match i.file_type() {
-                            Ok(t) if t.is_dir() => {
-                                s = s.add_dir(i.path(), depth, params, span, signals)?
-                            }
-                            Ok(_t) => s = s.add_file(i.path(), params),
-                            Err(e) => s = s.add_error(from_io_error(e).into()),
+        // the entries of a directory are measured in parallel, and then added up in their
+        // original order so that the output doesn't depend on which thread finishes first
+        let children = entries
+            .into_par_iter()
+            .map(|entry| {
+                signals.check(&span)?;
+
+                let child = match entry {
+                    Ok(i) => match i.file_type() {
+                        Ok(t) if t.is_dir() => match depth.map(|depth| depth.checked_sub(1)) {
+                            // the recursion limit was reached
+                            Some(None) => Child::Skipped,
+                            depth => Child::Dir(DirInfo::new(
+                                i.path(),
+                                params,
+                                depth.flatten(),
+                                span,
+                                signals,
+                            )?),
                         },
-                        Err(e) => s = s.add_error(from_io_error(e).into()),
-                    }
-                }
-            }
-            Err(e) => s = s.add_error(from_io_error(e).into()),
+                        Ok(_t) => Child::file(i.path(), params),
+                        Err(e) => Child::Error(from_io_error(e).into()),
+                    },
+                    Err(e) => Child::Error(from_io_error(e).into()),
+                };
+                Ok(child)
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        for child in children {
+            s = match child {
+                Child::Dir(d) => s.add_dir(d, params),
+                Child::File(f) => s.add_file(f, params),
+                Child::Error(e) => s.add_error(e),
+                Child::Skipped => s,
+            };
         }
         Ok(s)
     }
 
-    fn add_dir(
-        mut self,
-        path: impl Into<PathBuf>,
-        mut depth: Option<u64>,
-        params: &DirBuilder,
-        span: Span,
-        signals: &Signals,
-    ) -> Result<Self, ShellError> {
-        if let Some(current) = depth {
-            if let Some(new) = current.checked_sub(1) {
-                depth = Some(new);
-            } else {
-                return Ok(self);
-            }
-        }
-
-        let d = DirInfo::new(path, params, depth, span, signals)?;
+    fn add_dir(mut self, d: DirInfo, params: &DirBuilder) -> Self {
         self.size += d.size;
         self.blocks += d.blocks;
-        self.dirs.push(d);
-        Ok(self)
+        // the contents are only listed with `--long`, so don't keep them around otherwise
+        if params.long && params.within_threshold(d.size, d.blocks) {
+            self.dirs.push(d);
+        }
+        self
     }
 
-    fn add_file(mut self, f: impl Into<PathBuf>, params: &DirBuilder) -> Self {
-        let f = f.into();
-        let include = params.exclude.as_ref().is_none_or(|x| !x.matches_path(&f));
-        if include {
-            match FileInfo::new(f, params.deref, self.tag, self.long) {
-                Ok(file) => {
-                    let inc = params.min.is_none_or(|s| file.size >= s);
-                    if inc {
-                        self.size += file.size;
-                        self.blocks += file.blocks.unwrap_or(0);
-                        if params.long {
-                            self.files.push(file);
-                        }
-                    }
-                }
-                Err(e) => self = self.add_error(e),
-            }
+    fn add_file(mut self, file: FileInfo, params: &DirBuilder) -> Self {
+        self.size += file.size;
+        self.blocks += file.blocks.unwrap_or(0);
+        if params.long && params.within_threshold(file.size, file.get_blocks()) {
+            self.files.push(file);
         }
         self
     }
@@ -186,6 +216,33 @@ impl DirInfo {
     pub fn get_size(&self) -> u64 {
         self.size
     }
+
+    /// The space allocated on disk for the directory and everything in it.
+    pub fn get_blocks(&self) -> u64 {
+        self.blocks
+    }
+}
+
+/// What a single entry of a directory adds to it.
+enum Child {
+    Dir(DirInfo),
+    File(FileInfo),
+    Error(ShellError),
+    Skipped,
+}
+
+impl Child {
+    fn file(f: PathBuf, params: &DirBuilder) -> Self {
+        let include = params.exclude.as_ref().is_none_or(|x| !x.matches_path(&f));
+        if !include {
+            return Child::Skipped;
+        }
+        match FileInfo::new(f, params.deref, params.tag, params.long) {
+            Ok(file) if params.min.is_none_or(|s| file.size >= s) => Child::File(file),
+            Ok(_) => Child::Skipped,
+            Err(e) => Child::Error(e),
+        }
+    }
 }
 
 impl From<DirInfo> for Value {
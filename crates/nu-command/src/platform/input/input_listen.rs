@@ -70,14 +70,32 @@ There are 4 `key_type` variants:
     f - f1, f2, f3 ... keys
     char - alphanumeric and special symbols (a, A, 1, $ ...)
     media - dedicated media keys (play, pause, tracknext ...)
-    other - keys not falling under previous categories (up, down, backspace, enter ...)"#
+    other - keys not falling under previous categories (up, down, backspace, enter ...)
+The mouse `kind` is one of <button>_down, <button>_up, <button>_drag (with a Left, Right or
+Middle button), moved, scroll_down, scroll_up, scroll_left or scroll_right.
+
+Only the requested event types are captured, and the terminal is restored before returning,
+also when timing out. To write a simple TUI, call it in a loop and redraw after each event,
+using --timeout to also redraw when nothing happens (the timeout is an error that `try` can catch)."#
     }
     fn examples(&self) -> Vec<Example<'_>> {
-        vec![Example {
-            description: "Listen for a keyboard shortcut and find out how nu receives it.",
-            example: "input listen --types [key]",
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Listen for a keyboard shortcut and find out how nu receives it.",
+                example: "input listen --types [key]",
+                result: None,
+            },
+            Example {
+                description: "Wait for a mouse click or a paste, and ignore mouse moves.",
+                example: "loop { let event = input listen --types [mouse paste]; if $event.type == paste or ($event.kind | str ends-with _down) { print $event; break } }",
+                result: None,
+            },
+            Example {
+                description: "Print the terminal size on each resize until a key is pressed, and give up after a minute.",
+                example: "loop { let event = input listen --types [key resize] --timeout 1min; if $event.type == key { break }; print $'($event.col)x($event.row)' }",
+                result: None,
+            },
+        ]
     }
     fn run(
         &self,
@@ -93,6 +111,11 @@ There are 4 `key_type` variants:
         let config = stack.get_config(engine_state);
 
         terminal::enable_raw_mode().map_err(|err| IoError::new(err, head, None))?;
+        // Restores the terminal on every return from here on, including errors and timeouts
+        let mut console_state = DeferredConsoleRestore {
+            setup_event_types: EventTypeFilter::none(),
+            kitty_protocol: false,
+        };
 
         if config.use_kitty_protocol {
             if let Ok(false) = crossterm::terminal::supports_keyboard_enhancement() {
@@ -118,13 +141,15 @@ There are 4 `key_type` variants:
                     crossterm::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
                 )
             );
+            console_state.kitty_protocol = true;
         }
 
-        let console_state = event_type_filter.enable_events(head)?;
-        let start = Instant::now();
-        let mut remaining_time = timeout;
+        event_type_filter.enable_events(&mut console_state, head)?;
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
 
         loop {
+            let remaining_time =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
             if let Some(t) = remaining_time
                 && !crossterm::event::poll(t).map_err(|_| ShellError::GenericError {
                     error: "Error with user input".into(),
@@ -134,7 +159,6 @@ There are 4 `key_type` variants:
                     inner: vec![],
                 })?
             {
-                terminal::disable_raw_mode().map_err(|err| IoError::new(err, head, None))?;
                 return Err(ShellError::GenericError {
                     error: "Timed out while waiting for user input".into(),
                     msg: "no input was received within the timeout duration".into(),
@@ -152,22 +176,8 @@ There are 4 `key_type` variants:
             })?;
             let event = parse_event(head, &event, &event_type_filter, add_raw);
             if let Some(event) = event {
-                terminal::disable_raw_mode().map_err(|err| IoError::new(err, head, None))?;
-                if config.use_kitty_protocol {
-                    let _ = execute!(
-                        std::io::stdout(),
-                        crossterm::event::PopKeyboardEnhancementFlags
-                    );
-                }
-
-                console_state.restore();
                 return Ok(event.into_pipeline_data());
             }
-
-            if let Some(t) = remaining_time.as_mut() {
-                let now = Instant::now();
-                *t -= now.duration_since(start);
-            }
         }
     }
 }
@@ -259,38 +269,45 @@ impl EventTypeFilter {
     }
 
     /// Enable capturing of all events allowed by this filter.
-    /// Call [`DeferredConsoleRestore::restore`] when done capturing events to restore
-    /// console state
-    fn enable_events(&self, span: Span) -> Result<DeferredConsoleRestore, ShellError> {
+    /// Each enabled event type is recorded in `console_state`, which disables it again when
+    /// dropped
+    fn enable_events(
+        &self,
+        console_state: &mut DeferredConsoleRestore,
+        span: Span,
+    ) -> Result<(), ShellError> {
         if self.listen_mouse {
             crossterm::execute!(stdout(), EnableMouseCapture)
                 .map_err(|err| IoError::new(err, span, None))?;
+            console_state.setup_event_types.listen_mouse = true;
         }
 
         if self.listen_paste {
             crossterm::execute!(stdout(), EnableBracketedPaste)
                 .map_err(|err| IoError::new(err, span, None))?;
+            console_state.setup_event_types.listen_paste = true;
         }
 
         if self.listen_focus {
             crossterm::execute!(stdout(), crossterm::event::EnableFocusChange)
                 .map_err(|err| IoError::new(err, span, None))?;
+            console_state.setup_event_types.listen_focus = true;
         }
 
-        Ok(DeferredConsoleRestore {
-            setup_event_types: self.clone(),
-        })
+        Ok(())
     }
 }
 
-/// Promise to disable all event capturing previously enabled by [`EventTypeFilter::enable_events`]
+/// Promise to restore the console state set up for listening, kept until it's dropped
 struct DeferredConsoleRestore {
     setup_event_types: EventTypeFilter,
+    kitty_protocol: bool,
 }
 
-impl DeferredConsoleRestore {
-    /// Disable all event capturing flags set up by [`EventTypeFilter::enable_events`]
-    fn restore(self) {
+impl Drop for DeferredConsoleRestore {
+    /// Disable all event capturing flags set up by [`EventTypeFilter::enable_events`], the
+    /// kitty keyboard protocol and raw mode
+    fn drop(&mut self) {
         if self.setup_event_types.listen_mouse {
             let _ = crossterm::execute!(stdout(), DisableMouseCapture);
         }
@@ -302,6 +319,12 @@ impl DeferredConsoleRestore {
         if self.setup_event_types.listen_focus {
             let _ = crossterm::execute!(stdout(), DisableFocusChange);
         }
+
+        if self.kitty_protocol {
+            let _ = execute!(stdout(), crossterm::event::PopKeyboardEnhancementFlags);
+        }
+
+        let _ = terminal::disable_raw_mode();
     }
 }
 
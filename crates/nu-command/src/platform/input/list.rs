@@ -16,8 +16,9 @@ use nu_protocol::engine::Closure;
 use nu_protocol::{TableMode, shell_error::io::IoError};
 use nu_table::common::nu_value_to_string;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{self, Stderr, Write},
+    sync::Arc,
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
@@ -72,6 +73,8 @@ fn table_mode_to_separator(mode: TableMode) -> char {
         TableMode::Dots => ':',
         // Minimal/no borders
         TableMode::Restructured | TableMode::None => ' ',
+        // Resolved to its base mode by the caller
+        TableMode::Custom => DEFAULT_TABLE_COLUMN_SEPARATOR,
     }
 }
 
@@ -95,6 +98,8 @@ fn table_mode_to_header_separator(mode: TableMode) -> (char, char) {
         TableMode::Dots => ('.', ':'),
         // Minimal/no borders - use simple dashes
         TableMode::Restructured | TableMode::None => (' ', ' '),
+        // Resolved to its base mode by the caller
+        TableMode::Custom => ('─', '┼'),
     }
 }
 
@@ -145,10 +150,29 @@ impl InputListConfig {
         ret.selected_marker = color_config_row_index;
 
         // Derive table separators from user's table mode
-        ret.table_column_separator = table_mode_to_separator(config.table.mode);
-        let (header_sep, header_int) = table_mode_to_header_separator(config.table.mode);
+        let custom = &config.table.custom_theme;
+        let mode = match config.table.mode {
+            TableMode::Custom => custom.base,
+            mode => mode,
+        };
+        ret.table_column_separator = table_mode_to_separator(mode);
+        let (header_sep, header_int) = table_mode_to_header_separator(mode);
         ret.table_header_separator = header_sep;
         ret.table_header_intersection = header_int;
+        if config.table.mode == TableMode::Custom {
+            let chars = &custom.chars;
+            if custom.outer_border_only {
+                ret.table_column_separator = ' ';
+            } else if let Some(vertical) = chars.vertical {
+                ret.table_column_separator = vertical;
+            }
+            if let Some(horizontal) = chars.horizontal {
+                ret.table_header_separator = horizontal;
+            }
+            if let Some(intersection) = chars.intersection {
+                ret.table_header_intersection = intersection;
+            }
+        }
 
         ret
     }
@@ -172,6 +196,162 @@ struct TableLayout {
     truncated_cols: usize, // Number of columns that fit in terminal starting from horizontal_offset
 }
 
+/// The output of the `--preview` closure, shown below the list for the highlighted item
+struct Preview {
+    closure: ClosureEval,
+    config: Arc<nu_protocol::Config>,
+    /// Number of lines the preview takes, not counting the separator line
+    height: u16,
+    /// Preview lines by item index, so the closure runs at most once per item
+    cache: HashMap<usize, Vec<String>>,
+}
+
+impl Preview {
+    fn lines(&mut self, index: usize, value: &Value) -> &[String] {
+        let Self {
+            closure,
+            config,
+            height,
+            cache,
+        } = self;
+        cache.entry(index).or_insert_with(|| {
+            let text = match closure
+                .run_with_value(value.clone())
+                .and_then(|data| data.into_value(value.span()))
+            {
+                Ok(Value::String { val, .. }) => val,
+                Ok(val) => val.to_expanded_string(", ", config),
+                Err(err) => format!("Error: {err}"),
+            };
+            text.lines()
+                .take(*height as usize)
+                .map(|line| line.replace('\t', "    "))
+                .collect()
+        })
+    }
+}
+
+/// A key that confirms the selection when passed to `--expect`
+struct ExpectKey {
+    name: String,
+    key: KeyEvent,
+}
+
+impl ExpectKey {
+    /// Parse key names like `ctrl-o`, `alt-enter` or `f2`
+    fn parse(name: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = name;
+        loop {
+            if let Some(key) = rest.strip_prefix("ctrl-").filter(|key| !key.is_empty()) {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = key;
+            } else if let Some(key) = rest.strip_prefix("alt-").filter(|key| !key.is_empty()) {
+                modifiers |= KeyModifiers::ALT;
+                rest = key;
+            } else if let Some(key) = rest.strip_prefix("shift-").filter(|key| !key.is_empty()) {
+                modifiers |= KeyModifiers::SHIFT;
+                rest = key;
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest {
+                "enter" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                _ => {
+                    let n = rest.strip_prefix('f')?.parse::<u8>().ok()?;
+                    if !(1..=12).contains(&n) {
+                        return None;
+                    }
+                    KeyCode::F(n)
+                }
+            },
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            key: KeyEvent::new(code, modifiers),
+        })
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        match (self.key.code, key.code) {
+            (KeyCode::Char(expected), KeyCode::Char(pressed)) => {
+                // Terminals differ in whether shifted characters come with SHIFT, and in the case
+                // they report for characters typed with Ctrl or Alt
+                let ignore = KeyModifiers::SHIFT;
+                let modifiers_match =
+                    self.key.modifiers.difference(ignore) == key.modifiers.difference(ignore);
+                let chars_match = if key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                {
+                    expected.eq_ignore_ascii_case(&pressed)
+                } else {
+                    expected == pressed
+                };
+                modifiers_match && chars_match
+            }
+            (expected, pressed) => expected == pressed && self.key.modifiers == key.modifiers,
+        }
+    }
+}
+
+/// Truncate a line to `width` columns, keeping its ANSI escape sequences
+fn truncate_ansi(line: &str, width: usize) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut used = 0;
+    let mut chars = line.chars().peekable();
+    let mut has_escapes = false;
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Copy the escape sequence without counting its width
+            has_escapes = true;
+            out.push(c);
+            if chars.next_if_eq(&'[').is_some() {
+                out.push('[');
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if c.is_control() {
+            continue;
+        }
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width {
+            break;
+        }
+        used += char_width;
+        out.push(c);
+    }
+    if has_escapes {
+        out.push_str(RESET);
+    }
+    out
+}
+
 #[derive(Clone)]
 pub struct InputList;
 
@@ -231,6 +411,24 @@ impl Command for InputList {
                 "Match filter text against each column independently (table mode only).",
                 Some('c'),
             )
+            .named(
+                "preview",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "Closure whose output is shown below the list as a preview of the highlighted item.",
+                Some('p'),
+            )
+            .named(
+                "preview-height",
+                SyntaxShape::Int,
+                "Number of lines of the preview (default: 10).",
+                None,
+            )
+            .named(
+                "expect",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Keys that also confirm the selection, like 'ctrl-o' or 'alt-d'. The output becomes a record with the pressed key and the selection.",
+                Some('e'),
+            )
             .allow_variants_without_examples(true)
             .category(Category::Platform)
     }
@@ -301,7 +499,20 @@ Styling (inherited from $env.config.color_config):
 - header: Table column headers
 - Table column characters inherit from $env.config.table.mode
 
-Use --no-footer and --no-separator to hide the footer and separator line."#
+Use --no-footer and --no-separator to hide the footer and separator line.
+
+Preview:
+Use --preview with a closure to show its output below the list for the highlighted item.
+The closure receives the original item and runs once per item. Its output is cut to
+--preview-height lines (10 by default) and to the terminal width, keeping ANSI colors.
+
+Custom keys:
+Use --expect with a list of key names to confirm the selection with other keys than Enter,
+so a picker can offer several actions. The output is then a record with the name of the pressed
+key ("enter" for Enter) in `key` and the selection in `selection`.
+Key names are a character or one of enter, tab, space, backspace, delete, insert, home, end,
+pageup, pagedown, up, down, left, right and f1 to f12, prefixed with any of ctrl-, alt- and shift-.
+Expected keys take precedence over the keys above."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -328,6 +539,10 @@ Use --no-footer and --no-separator to hide the footer and separator line."#
         let case_sensitive: Option<Value> = call.get_flag(engine_state, stack, "case-sensitive")?;
         let no_table = call.has_flag(engine_state, stack, "no-table")?;
         let per_column = call.has_flag(engine_state, stack, "per-column")?;
+        let preview: Option<Closure> = call.get_flag(engine_state, stack, "preview")?;
+        let preview_height: Option<Spanned<i64>> =
+            call.get_flag(engine_state, stack, "preview-height")?;
+        let expect: Option<Vec<Spanned<String>>> = call.get_flag(engine_state, stack, "expect")?;
         let config = stack.get_config(engine_state);
         let style_computer = StyleComputer::from_config(engine_state, stack);
         let mut input_list_config = InputListConfig::from_nu_config(&config, &style_computer);
@@ -354,6 +569,30 @@ Use --no-footer and --no-separator to hide the footer and separator line."#
             };
         }
 
+        let preview_height = match preview_height {
+            Some(Spanned { item, span }) => u16::try_from(item)
+                .ok()
+                .filter(|height| *height > 0)
+                .ok_or_else(|| ShellError::IncorrectValue {
+                msg: "the preview height must be a positive number of lines".into(),
+                val_span: span,
+                call_span: head,
+            })?,
+            None => 10,
+        };
+        let expect = expect
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| {
+                ExpectKey::parse(&name.item).ok_or_else(|| ShellError::IncorrectValue {
+                    msg: format!("unknown key '{}'", name.item),
+                    val_span: name.span,
+                    call_span: head,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let output_key = !expect.is_empty();
+
         // Collect all values first for table detection
         let values: Vec<Value> = match input {
             PipelineData::Value(Value::Range { .. }, ..)
@@ -494,11 +733,19 @@ Use --no-footer and --no-separator to hide the footer and separator line."#
             table_layout,
             per_column,
         );
+        widget.preview = preview.map(|closure| Preview {
+            closure: ClosureEval::new(engine_state, stack, closure),
+            config: config.clone(),
+            height: preview_height,
+            cache: HashMap::new(),
+        });
+        widget.expect = expect;
         let answer = widget.run().map_err(|err| {
             IoError::new_with_additional_context(err, call.head, None, INTERACT_ERROR)
         })?;
+        let pressed_key = widget.pressed_key.take();
 
-        Ok(match answer {
+        let selection = match answer {
             InteractMode::Multi(res) => {
                 if index {
                     match res {
@@ -533,6 +780,17 @@ Use --no-footer and --no-separator to hide the footer and separator line."#
                     }
                 }
             }
+        };
+
+        Ok(match pressed_key {
+            Some(key) if output_key && !selection.is_nothing() => Value::record(
+                record! {
+                    "key" => Value::string(key, head),
+                    "selection" => selection,
+                },
+                head,
+            ),
+            _ => selection,
         }
         .into_pipeline_data())
     }
@@ -609,6 +867,16 @@ Use --no-footer and --no-separator to hide the footer and separator line."#
                 example: r#"ls | input list --fuzzy --multi"#,
                 result: None,
             },
+            Example {
+                description: "Pick a file while previewing its first lines",
+                example: r#"ls | where type == file | input list --fuzzy --preview {|file| open --raw $file.name | lines | first 20 | str join "\n"}"#,
+                result: None,
+            },
+            Example {
+                description: "Open the picked file, or delete it when picked with Ctrl+D",
+                example: r#"let pick = ls | input list --fuzzy --expect [ctrl-d]; if $pick.key == ctrl-d { rm $pick.selection.name } else { open $pick.selection.name }"#,
+                result: None,
+            },
         ]
     }
 }
@@ -702,6 +970,12 @@ struct SelectWidget<'a> {
     /// Cached visible columns calculation (cols_visible, has_more_right)
     /// Invalidated when horizontal_offset, term_width, or table_layout changes
     visible_columns_cache: Option<(usize, bool)>,
+    /// Preview of the highlighted item shown below the list (--preview)
+    preview: Option<Preview>,
+    /// Keys that confirm the selection besides Enter (--expect)
+    expect: Vec<ExpectKey>,
+    /// Name of the key that confirmed the selection, set when `expect` is not empty
+    pressed_key: Option<String>,
 }
 
 impl<'a> SelectWidget<'a> {
@@ -760,6 +1034,9 @@ impl<'a> SelectWidget<'a> {
             settings_changed: false,
             selected_marker_cached,
             visible_columns_cache: None,
+            preview: None,
+            expect: Vec::new(),
+            pressed_key: None,
         }
     }
 
@@ -1090,6 +1367,23 @@ impl<'a> SelectWidget<'a> {
         }
     }
 
+    /// Lines taken by the preview, including its separator line
+    fn preview_lines(&self) -> usize {
+        match &self.preview {
+            Some(preview) => preview.height as usize + usize::from(self.config.show_separator),
+            None => 0,
+        }
+    }
+
+    /// Index in `items` of the highlighted item, if any
+    fn current_item_index(&self) -> Option<usize> {
+        match self.mode {
+            SelectMode::Single => Some(self.cursor),
+            SelectMode::Multi if !self.refined => Some(self.cursor),
+            _ => self.filtered_indices.get(self.cursor).copied(),
+        }
+    }
+
     /// Header lines for fuzzy modes (prompt + filter + separator + table header)
     fn fuzzy_header_lines(&self) -> u16 {
         let mut header_lines: u16 = if self.prompt.is_some() { 2 } else { 1 };
@@ -1143,6 +1437,7 @@ impl<'a> SelectWidget<'a> {
         if self.config.show_footer {
             reserved += 1; // footer
         }
+        reserved += self.preview_lines() as u16;
         self.visible_height = height.saturating_sub(reserved).max(1);
     }
 
@@ -1215,6 +1510,14 @@ impl<'a> SelectWidget<'a> {
             return KeyAction::Cancel;
         }
 
+        if let Some(expected) = self.expect.iter().find(|expected| expected.matches(&key)) {
+            self.pressed_key = Some(expected.name.clone());
+            return KeyAction::Confirm;
+        }
+        if !self.expect.is_empty() && key.code == KeyCode::Enter {
+            self.pressed_key = Some("enter".into());
+        }
+
         match self.mode {
             SelectMode::Single => self.handle_single_key(key),
             SelectMode::Multi => self.handle_multi_key(key),
@@ -2613,23 +2916,27 @@ impl<'a> SelectWidget<'a> {
 
     #[allow(clippy::collapsible_if)]
     fn render(&mut self, stderr: &mut Stderr) -> io::Result<()> {
+        // The partial updates below expect the list to end the rendered lines, which isn't the
+        // case with a preview
+        let has_preview = self.preview.is_some();
+
         // Check for fuzzy multi mode toggle-all optimization
-        if self.can_do_fuzzy_multi_toggle_all_update() {
+        if !has_preview && self.can_do_fuzzy_multi_toggle_all_update() {
             return self.render_fuzzy_multi_toggle_all_update(stderr);
         }
 
         // Check for multi mode toggle-all optimization
-        if self.can_do_multi_toggle_all_update() {
+        if !has_preview && self.can_do_multi_toggle_all_update() {
             return self.render_multi_toggle_all(stderr);
         }
 
         // Check for multi mode toggle-only optimization
-        if self.can_do_multi_toggle_only_update() {
+        if !has_preview && self.can_do_multi_toggle_only_update() {
             return self.render_multi_toggle_only(stderr);
         }
 
         // Check for fuzzy multi mode toggle+move optimization
-        if self.can_do_fuzzy_multi_toggle_update() {
+        if !has_preview && self.can_do_fuzzy_multi_toggle_update() {
             return self.render_fuzzy_multi_toggle_update(stderr);
         }
 
@@ -2648,6 +2955,7 @@ impl<'a> SelectWidget<'a> {
             && !self.horizontal_scroll_changed
             && !self.settings_changed
             && !self.toggled_all
+            && self.toggled_item.is_none()
         {
             return Ok(());
         }
@@ -2679,6 +2987,7 @@ impl<'a> SelectWidget<'a> {
         if has_scroll_indicator {
             lines_needed += 1;
         }
+        lines_needed += self.preview_lines();
 
         // On first render, claim vertical space by printing newlines (causes scroll if needed)
         if self.first_render && lines_needed > 1 {
@@ -2844,6 +3153,41 @@ impl<'a> SelectWidget<'a> {
                 Clear(ClearType::UntilNewLine),
             )?;
             lines_rendered += 1;
+            if lines_rendered < lines_needed {
+                execute!(stderr, MoveDown(1), MoveToColumn(0))?;
+            }
+        }
+
+        // Render the preview of the highlighted item, padded to its full height
+        if has_preview {
+            let mut lines = Vec::with_capacity(self.preview_lines());
+            if self.config.show_separator {
+                lines.push(
+                    self.config
+                        .separator
+                        .paint(&self.separator_line)
+                        .to_string(),
+                );
+            }
+            let width = self.term_width as usize;
+            let current = self.current_item_index();
+            if let (Some(preview), Some(index)) = (&mut self.preview, current) {
+                lines.extend(
+                    preview
+                        .lines(index, &self.items[index].value)
+                        .iter()
+                        .map(|line| truncate_ansi(line, width)),
+                );
+            }
+            lines.resize(self.preview_lines(), String::new());
+
+            for line in lines {
+                execute!(stderr, Print(line), Clear(ClearType::UntilNewLine))?;
+                lines_rendered += 1;
+                if lines_rendered < lines_needed {
+                    execute!(stderr, MoveDown(1), MoveToColumn(0))?;
+                }
+            }
         }
 
         // Clear any extra lines from previous render
@@ -3693,6 +4037,58 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn expect_keys_are_parsed_and_matched() {
+        let ctrl_o = ExpectKey::parse("ctrl-o").expect("valid key");
+        assert!(ctrl_o.matches(&KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        assert!(ctrl_o.matches(&KeyEvent::new(KeyCode::Char('O'), KeyModifiers::CONTROL)));
+        assert!(!ctrl_o.matches(&KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE)));
+
+        let alt_enter = ExpectKey::parse("alt-enter").expect("valid key");
+        assert!(alt_enter.matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)));
+        assert!(!alt_enter.matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+
+        let upper = ExpectKey::parse("D").expect("valid key");
+        assert!(upper.matches(&KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT)));
+        assert!(!upper.matches(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE)));
+
+        let minus = ExpectKey::parse("ctrl--").expect("valid key");
+        assert_eq!(minus.key.code, KeyCode::Char('-'));
+
+        assert_eq!(
+            ExpectKey::parse("f12").map(|key| key.key.code),
+            Some(KeyCode::F(12))
+        );
+        assert!(ExpectKey::parse("f13").is_none());
+        assert!(ExpectKey::parse("ctrl-").is_none());
+        assert!(ExpectKey::parse("hyper-x").is_none());
+    }
+
+    #[test]
+    fn expected_key_confirms_selection() {
+        let mut w = make_widget(&["A", "B"]);
+        w.expect = vec![ExpectKey::parse("ctrl-o").expect("valid key")];
+
+        let action = w.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        assert!(matches!(action, KeyAction::Confirm));
+        assert_eq!(w.pressed_key.as_deref(), Some("ctrl-o"));
+
+        let action = w.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(action, KeyAction::Confirm));
+        assert_eq!(w.pressed_key.as_deref(), Some("enter"));
+    }
+
+    #[test]
+    fn preview_lines_are_truncated_keeping_colors() {
+        assert_eq!(truncate_ansi("hello world", 5), "hello");
+        assert_eq!(truncate_ansi("日本語", 5), "日本");
+        assert_eq!(
+            truncate_ansi("\x1b[31mred text\x1b[0m", 3),
+            format!("\x1b[31mred{RESET}")
+        );
+        assert_eq!(truncate_ansi("a\rb", 5), "ab");
+    }
+
     #[test]
     fn test_examples() {
         use crate::test_examples;
@@ -5,6 +5,7 @@ mod chars;
 mod float;
 mod int;
 mod random_;
+mod table;
 mod uuid;
 
 pub use self::binary::RandomBinary;
@@ -12,5 +13,6 @@ pub use self::bool::RandomBool;
 pub use self::chars::RandomChars;
 pub use self::float::RandomFloat;
 pub use self::int::RandomInt;
+pub use self::table::RandomTable;
 pub use self::uuid::RandomUuid;
 pub use random_::Random;
@@ -0,0 +1,412 @@
+use chrono::{Duration, NaiveDate, NaiveTime};
+use nu_engine::command_prelude::*;
+use nu_protocol::Range;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+use std::ops::Bound;
+
+const FIRST_NAMES: [&str; 32] = [
+    "Ada", "Alan", "Amara", "Bea", "Carlos", "Chen", "Dana", "Diego", "Elena", "Farah", "Grace",
+    "Hiro", "Ines", "Ivan", "Jamal", "Julia", "Kenji", "Lena", "Liam", "Maya", "Mateo", "Nadia",
+    "Noah", "Olga", "Omar", "Priya", "Rosa", "Sam", "Sofia", "Tariq", "Yuki", "Zoe",
+];
+
+const LAST_NAMES: [&str; 32] = [
+    "Adams", "Ahmed", "Baker", "Becker", "Costa", "Dubois", "Evans", "Fischer", "Garcia", "Haddad",
+    "Ivanova", "Jensen", "Kim", "Kowalski", "Lopez", "Martin", "Meyer", "Nakamura", "Novak",
+    "Okafor", "Patel", "Quinn", "Rossi", "Santos", "Schmidt", "Silva", "Tanaka", "Nguyen",
+    "Walker", "Wang", "Yilmaz", "Zhang",
+];
+
+// Reserved for documentation, so that the fake addresses can't belong to anyone.
+const EMAIL_DOMAINS: [&str; 3] = ["example.com", "example.org", "example.net"];
+
+const CITIES: [&str; 20] = [
+    "Amsterdam",
+    "Auckland",
+    "Berlin",
+    "Bogotá",
+    "Cairo",
+    "Chicago",
+    "Lagos",
+    "Lima",
+    "Lisbon",
+    "London",
+    "Madrid",
+    "Montreal",
+    "Mumbai",
+    "Nairobi",
+    "Osaka",
+    "Paris",
+    "Seoul",
+    "Sydney",
+    "Toronto",
+    "Warsaw",
+];
+
+const COUNTRIES: [&str; 20] = [
+    "Argentina",
+    "Australia",
+    "Brazil",
+    "Canada",
+    "Chile",
+    "Egypt",
+    "France",
+    "Germany",
+    "India",
+    "Italy",
+    "Japan",
+    "Kenya",
+    "Mexico",
+    "Netherlands",
+    "Nigeria",
+    "Norway",
+    "Poland",
+    "South Korea",
+    "Spain",
+    "Vietnam",
+];
+
+const WORDS: [&str; 24] = [
+    "amber", "anchor", "breeze", "canyon", "cedar", "comet", "delta", "ember", "falcon", "glacier",
+    "harbor", "island", "juniper", "lagoon", "meadow", "nebula", "orbit", "pebble", "quartz",
+    "river", "summit", "thunder", "velvet", "willow",
+];
+
+const GENERATOR_NAMES: &str = "name, first_name, last_name, email, username, uuid, int, float, bool, date, city, country, phone or word";
+
+#[derive(Clone)]
+pub struct RandomTable;
+
+impl Command for RandomTable {
+    fn name(&self) -> &str {
+        "random table"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random table")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .required_named(
+                "schema",
+                SyntaxShape::Record(vec![]),
+                "The columns of the table, with the kind of data to fill each one with.",
+                Some('s'),
+            )
+            .named(
+                "rows",
+                SyntaxShape::Int,
+                "The number of rows to generate (default: 10).",
+                Some('r'),
+            )
+            .named(
+                "seed",
+                SyntaxShape::Int,
+                "Generate the same table every time for the same seed.",
+                None,
+            )
+            .category(Category::Random)
+    }
+
+    fn description(&self) -> &str {
+        "Generate a table of fake data, to test pipelines and databases with."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each column of the schema is one of name, first_name, last_name, email, username, uuid,
+int, float, bool, date, city, country, phone or word. int, float and date take an optional
+range, like "int(18..80)", "float(0..1)" or "date(2020-01-01..2024-12-31)", and default to 0 to
+100, 0 to 1, and the years 2000 to 2025. A range value is the same as an int or float with that
+range, and a list picks one of its items at random.
+
+The emails use the reserved example.com, example.org and example.net domains, and the phone
+numbers the 555-0100 to 555-0199 numbers reserved for fiction, so that they can't reach anyone."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "generate",
+            "fake",
+            "mock",
+            "synthetic",
+            "test data",
+            "faker",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let schema: Record = call
+            .get_flag(engine_state, stack, "schema")?
+            .ok_or_else(|| ShellError::MissingParameter {
+                param_name: "schema".into(),
+                span: head,
+            })?;
+        let rows: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "rows")?;
+        let seed: Option<i64> = call.get_flag(engine_state, stack, "seed")?;
+
+        let rows = match rows {
+            Some(rows) => usize::try_from(rows.item)
+                .map_err(|_| ShellError::NeedsPositiveValue { span: rows.span })?,
+            None => 10,
+        };
+        let columns = schema
+            .into_iter()
+            .map(|(column, kind)| Ok((column, Generator::from_value(kind)?)))
+            .collect::<Result<Vec<_>, ShellError>>()?;
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed as u64),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+
+        let table = (0..rows).map(move |_| {
+            let record = columns
+                .iter()
+                .map(|(column, generator)| (column.clone(), generator.generate(&mut rng, head)))
+                .collect();
+            Value::record(record, head)
+        });
+
+        Ok(PipelineData::list_stream(
+            ListStream::new(table, head, engine_state.signals().clone()),
+            None,
+        ))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Generate 1000 people.",
+                example: "random table --rows 1000 --schema {name: name, email: email, age: 'int(18..80)'}",
+                result: None,
+            },
+            Example {
+                description: "Generate orders with a range and a list of choices.",
+                example: "random table --schema {id: uuid, total: 1.0..500.0, status: [pending paid shipped]}",
+                result: None,
+            },
+            Example {
+                description: "Generate the same table every time.",
+                example: "random table --rows 3 --seed 42 --schema {city: city, joined: 'date(2024-01-01..2024-12-31)'}",
+                result: None,
+            },
+        ]
+    }
+}
+
+enum Generator {
+    Name,
+    FirstName,
+    LastName,
+    Email,
+    Username,
+    Uuid,
+    Int(i64, i64),
+    Float(f64, f64),
+    Bool,
+    Date(NaiveDate, i64),
+    City,
+    Country,
+    Phone,
+    Word,
+    OneOf(Vec<Value>),
+}
+
+impl Generator {
+    fn from_value(kind: Value) -> Result<Self, ShellError> {
+        let span = kind.span();
+        match kind {
+            Value::String { val, .. } => Self::from_name(&val, span),
+            Value::Range { val, .. } => match *val {
+                Range::IntRange(range) => {
+                    let end = match range.end() {
+                        Bound::Included(end) => end,
+                        Bound::Excluded(end) => end.saturating_sub(1),
+                        Bound::Unbounded => i64::MAX,
+                    };
+                    int_range(range.start(), end, span)
+                }
+                Range::FloatRange(range) => match range.end() {
+                    Bound::Included(end) | Bound::Excluded(end) => {
+                        float_range(range.start(), end, span)
+                    }
+                    Bound::Unbounded => Err(ShellError::IncorrectValue {
+                        msg: "A float range needs an end".into(),
+                        val_span: span,
+                        call_span: span,
+                    }),
+                },
+            },
+            Value::List { vals, .. } if !vals.is_empty() => Ok(Self::OneOf(vals)),
+            other => Err(ShellError::IncorrectValue {
+                msg: format!(
+                    "Expected {GENERATOR_NAMES}, a range or a non-empty list, found {}",
+                    other.get_type()
+                ),
+                val_span: span,
+                call_span: span,
+            }),
+        }
+    }
+
+    fn from_name(name: &str, span: Span) -> Result<Self, ShellError> {
+        let invalid = |msg: String| ShellError::IncorrectValue {
+            msg,
+            val_span: span,
+            call_span: span,
+        };
+        let (name, range) = match name.trim().split_once('(') {
+            Some((name, range)) => {
+                let range = range
+                    .strip_suffix(')')
+                    .and_then(|range| range.split_once(".."))
+                    .ok_or_else(|| invalid(format!("Expected a range like {name}(1..10)")))?;
+                (name, Some(range))
+            }
+            None => (name.trim(), None),
+        };
+
+        let generator = match (name, range) {
+            ("name", None) => Self::Name,
+            ("first_name", None) => Self::FirstName,
+            ("last_name", None) => Self::LastName,
+            ("email", None) => Self::Email,
+            ("username", None) => Self::Username,
+            ("uuid", None) => Self::Uuid,
+            ("int", None) => Self::Int(0, 100),
+            ("int", Some((start, end))) => match (start.trim().parse(), end.trim().parse()) {
+                (Ok(start), Ok(end)) => int_range(start, end, span)?,
+                _ => return Err(invalid("Expected integers in the range of int".into())),
+            },
+            ("float", None) => Self::Float(0.0, 1.0),
+            ("float", Some((start, end))) => match (start.trim().parse(), end.trim().parse()) {
+                (Ok(start), Ok(end)) => float_range(start, end, span)?,
+                _ => return Err(invalid("Expected numbers in the range of float".into())),
+            },
+            ("bool", None) => Self::Bool,
+            ("date", None) => date_range("2000-01-01", "2025-12-31", span)?,
+            ("date", Some((start, end))) => date_range(start.trim(), end.trim(), span)?,
+            ("city", None) => Self::City,
+            ("country", None) => Self::Country,
+            ("phone", None) => Self::Phone,
+            ("word", None) => Self::Word,
+            (_, Some(_)) => return Err(invalid(format!("`{name}` doesn't take a range"))),
+            _ => {
+                return Err(invalid(format!(
+                    "Expected {GENERATOR_NAMES}, found `{name}`"
+                )));
+            }
+        };
+        Ok(generator)
+    }
+
+    fn generate(&self, rng: &mut StdRng, head: Span) -> Value {
+        match self {
+            Self::Name => Value::string(
+                format!("{} {}", pick(rng, &FIRST_NAMES), pick(rng, &LAST_NAMES)),
+                head,
+            ),
+            Self::FirstName => Value::string(pick(rng, &FIRST_NAMES), head),
+            Self::LastName => Value::string(pick(rng, &LAST_NAMES), head),
+            Self::Email => {
+                let first = pick(rng, &FIRST_NAMES).to_lowercase();
+                let last = pick(rng, &LAST_NAMES).to_lowercase();
+                let number: u8 = rng.random_range(1..=99);
+                let domain = pick(rng, &EMAIL_DOMAINS);
+                Value::string(format!("{first}.{last}{number}@{domain}"), head)
+            }
+            Self::Username => {
+                let first = pick(rng, &FIRST_NAMES).to_lowercase();
+                let word = pick(rng, &WORDS);
+                let number: u16 = rng.random_range(1..=999);
+                Value::string(format!("{first}_{word}{number}"), head)
+            }
+            Self::Uuid => {
+                let uuid = uuid::Builder::from_random_bytes(rng.random()).into_uuid();
+                Value::string(uuid.hyphenated().to_string(), head)
+            }
+            Self::Int(start, end) => Value::int(rng.random_range(*start..=*end), head),
+            Self::Float(start, end) => Value::float(rng.random_range(*start..=*end), head),
+            Self::Bool => Value::bool(rng.random_bool(0.5), head),
+            Self::Date(start, days) => {
+                let date = *start + Duration::days(rng.random_range(0..=*days));
+                Value::date(date.and_time(NaiveTime::MIN).and_utc().fixed_offset(), head)
+            }
+            Self::City => Value::string(pick(rng, &CITIES), head),
+            Self::Country => Value::string(pick(rng, &COUNTRIES), head),
+            Self::Phone => {
+                let area: u16 = rng.random_range(201..=989);
+                let line: u8 = rng.random_range(0..=99);
+                Value::string(format!("({area}) 555-01{line:02}"), head)
+            }
+            Self::Word => Value::string(pick(rng, &WORDS), head),
+            Self::OneOf(values) => values
+                .choose(rng)
+                .cloned()
+                .unwrap_or_else(|| Value::nothing(head)),
+        }
+    }
+}
+
+fn pick(rng: &mut StdRng, words: &[&str]) -> String {
+    words.choose(rng).copied().unwrap_or_default().to_string()
+}
+
+fn int_range(start: i64, end: i64, span: Span) -> Result<Generator, ShellError> {
+    if start > end {
+        return Err(ShellError::InvalidRange {
+            left_flank: start.to_string(),
+            right_flank: end.to_string(),
+            span,
+        });
+    }
+    Ok(Generator::Int(start, end))
+}
+
+fn float_range(start: f64, end: f64, span: Span) -> Result<Generator, ShellError> {
+    if start.is_nan() || end.is_nan() || start > end {
+        return Err(ShellError::InvalidRange {
+            left_flank: start.to_string(),
+            right_flank: end.to_string(),
+            span,
+        });
+    }
+    Ok(Generator::Float(start, end))
+}
+
+fn date_range(start: &str, end: &str, span: Span) -> Result<Generator, ShellError> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| ShellError::CantConvert {
+            to_type: "date".into(),
+            from_type: "string".into(),
+            span,
+            help: Some(format!("`{date}` isn't a date like 2024-12-31")),
+        })
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    let days = (end - start).num_days();
+    if days < 0 {
+        return Err(ShellError::InvalidRange {
+            left_flank: start.to_string(),
+            right_flank: end.to_string(),
+            span,
+        });
+    }
+    Ok(Generator::Date(start, days))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(RandomTable {})
+    }
+}
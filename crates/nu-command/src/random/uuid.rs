@@ -94,7 +94,7 @@ impl Command for RandomUuid {
                 result: None,
             },
             Example {
-                description: "Generate a uuid v7 string (timestamp + random).",
+                description: "Generate a uuid v7 string (timestamp + random), which sorts by creation time.",
                 example: "random uuid -v 7",
                 result: None,
             },
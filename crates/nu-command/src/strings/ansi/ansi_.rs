@@ -525,6 +525,10 @@ static CODE_LIST: LazyLock<Vec<AnsiCode>> = LazyLock::new(|| { vec![
 static CODE_MAP: LazyLock<HashMap<&'static str, &'static str>> =
     LazyLock::new(|| build_ansi_hashmap(&CODE_LIST));
 
+/// The long names of the codes, to complete the code argument with.
+static CODE_NAMES: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| CODE_LIST.iter().map(|code| code.long_name).collect());
+
 impl Command for Ansi {
     fn name(&self) -> &str {
         "ansi"
@@ -533,10 +537,11 @@ impl Command for Ansi {
     fn signature(&self) -> Signature {
         Signature::build("ansi")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
-            .optional(
-                "code",
-                SyntaxShape::Any,
-                "The name of the code to use (from `ansi -l`).",
+            .param(
+                PositionalArg::new("code", SyntaxShape::Any)
+                    .desc("The name of the code to use (from `ansi -l`).")
+                    .completion(Completion::new_list(CODE_NAMES.as_slice()))
+                    .optional(),
             )
             .switch(
                 "escape", // \x1b[
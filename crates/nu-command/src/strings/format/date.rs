@@ -1,5 +1,8 @@
-use crate::{generate_strftime_list, parse_date_from_string};
-use chrono::{DateTime, Datelike, Locale, TimeZone};
+use crate::{
+    COMMON_DATE_FORMATS, TIMEZONES, TimeZoneArg, generate_strftime_list, locale_from_name,
+    parse_date_from_string,
+};
+use chrono::{DateTime, Datelike, FixedOffset, Local, Locale, TimeZone};
 use nu_engine::command_prelude::*;
 
 use nu_utils::locale::{LOCALE_OVERRIDE_ENV_VAR, get_system_locale_string};
@@ -26,10 +29,24 @@ impl Command for FormatDate {
             ])
             .allow_variants_without_examples(true) // https://github.com/nushell/nushell/issues/7032
             .switch("list", "Lists strftime cheatsheet.", Some('l'))
-            .optional(
-                "format string",
+            .named(
+                "locale",
                 SyntaxShape::String,
-                "The desired format date.",
+                "The locale of the month and weekday names and of formats like %x, like de-DE, instead of the one from the environment.",
+                None,
+            )
+            .param(
+                Flag::new("timezone")
+                    .short('z')
+                    .arg(SyntaxShape::String)
+                    .desc("Show the date in this time zone, like Europe/Berlin, +0500 or local, instead of its own offset.")
+                    .completion(Completion::new_list(TIMEZONES.as_slice())),
+            )
+            .param(
+                PositionalArg::new("format string", SyntaxShape::String)
+                    .desc("The desired format date.")
+                    .completion(Completion::new_list(COMMON_DATE_FORMATS))
+                    .optional(),
             )
             .category(Category::Strings)
     }
@@ -38,8 +55,13 @@ impl Command for FormatDate {
         "Format a given date using a format string."
     }
 
+    fn extra_description(&self) -> &str {
+        "The locale is taken from the LC_ALL, LC_TIME or LANG environment variables, in that order, \
+         unless it is given with --locale."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["fmt", "strftime"]
+        vec!["fmt", "strftime", "locale"]
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -83,6 +105,16 @@ impl Command for FormatDate {
                 example: r#""2021-10-22 20:00:12 +01:00" | format date "%Y-%m-%d""#,
                 result: Some(Value::test_string("2021-10-22")),
             },
+            Example {
+                description: "Format a given date with German month and weekday names.",
+                example: r#""2021-10-22 20:00:12 +01:00" | format date --locale de-DE "%A, %-d. %B %Y""#,
+                result: Some(Value::test_string("Freitag, 22. Oktober 2021")),
+            },
+            Example {
+                description: "Format a given date in another time zone, with the name of its offset at that date.",
+                example: r#""2021-10-22 20:00:12 +01:00" | format date --timezone America/New_York "%Y-%m-%d %H:%M %Z""#,
+                result: Some(Value::test_string("2021-10-22 15:00 EDT")),
+            },
         ]
     }
 
@@ -99,13 +131,16 @@ impl Command for FormatDate {
     ) -> Result<PipelineData, ShellError> {
         let list = call.has_flag(engine_state, stack, "list")?;
         let format = call.opt::<Spanned<String>>(engine_state, stack, 0)?;
+        let timezone = call.get_flag(engine_state, stack, "timezone")?;
 
         // env var preference is documented at https://www.gnu.org/software/gettext/manual/html_node/Locale-Environment-Variables.html
         // LC_ALL overrides LC_TIME, LC_TIME overrides LANG
 
         // get the locale first so we can use the proper get_env_var functions since this is a const command
         // we can override the locale by setting $env.NU_TEST_LOCALE_OVERRIDE or $env.LC_TIME
-        let locale = if let Some(loc) = stack
+        let locale = if let Some(name) = call.get_flag(engine_state, stack, "locale")? {
+            locale_from_name(&name)?
+        } else if let Some(loc) = stack
             .get_env_var(engine_state, LOCALE_OVERRIDE_ENV_VAR)
             .or_else(|| stack.get_env_var(engine_state, "LC_ALL"))
             .or_else(|| stack.get_env_var(engine_state, "LC_TIME"))
@@ -122,7 +157,7 @@ impl Command for FormatDate {
                 .unwrap_or(Locale::en_US)
         };
 
-        run(engine_state, call, input, list, format, locale)
+        run(engine_state, call, input, list, format, timezone, locale)
     }
 
     fn run_const(
@@ -133,13 +168,16 @@ impl Command for FormatDate {
     ) -> Result<PipelineData, ShellError> {
         let list = call.has_flag_const(working_set, "list")?;
         let format = call.opt_const::<Spanned<String>>(working_set, 0)?;
+        let timezone = call.get_flag_const(working_set, "timezone")?;
 
         // env var preference is documented at https://www.gnu.org/software/gettext/manual/html_node/Locale-Environment-Variables.html
         // LC_ALL overrides LC_TIME, LC_TIME overrides LANG
 
         // get the locale first so we can use the proper get_env_var functions since this is a const command
         // we can override the locale by setting $env.NU_TEST_LOCALE_OVERRIDE or $env.LC_TIME
-        let locale = if let Some(loc) = working_set
+        let locale = if let Some(name) = call.get_flag_const(working_set, "locale")? {
+            locale_from_name(&name)?
+        } else if let Some(loc) = working_set
             .get_env_var(LOCALE_OVERRIDE_ENV_VAR)
             .or_else(|| working_set.get_env_var("LC_ALL"))
             .or_else(|| working_set.get_env_var("LC_TIME"))
@@ -156,7 +194,15 @@ impl Command for FormatDate {
                 .unwrap_or(Locale::en_US)
         };
 
-        run(working_set.permanent(), call, input, list, format, locale)
+        run(
+            working_set.permanent(),
+            call,
+            input,
+            list,
+            format,
+            timezone,
+            locale,
+        )
     }
 }
 
@@ -166,6 +212,7 @@ fn run(
     input: PipelineData,
     list: bool,
     format: Option<Spanned<String>>,
+    timezone: Option<Spanned<String>>,
     locale: Locale,
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
@@ -176,14 +223,30 @@ fn run(
         ));
     }
 
+    let timezone = timezone
+        .map(|timezone| {
+            TimeZoneArg::parse(&timezone.item).map_err(|_| ShellError::TypeMismatch {
+                err_message: String::from("invalid time zone"),
+                span: timezone.span,
+            })
+        })
+        .transpose()?;
+
     // This doesn't match explicit nulls
     if let PipelineData::Empty = input {
         return Err(ShellError::PipelineEmpty { dst_span: head });
     }
     input.map(
         move |value| match &format {
-            Some(format) => format_helper(value, format.item.as_str(), format.span, head, locale),
-            None => format_helper_rfc2822(value, head),
+            Some(format) => format_helper(
+                value,
+                format.item.as_str(),
+                format.span,
+                head,
+                timezone,
+                locale,
+            ),
+            None => format_helper_rfc2822(value, head, timezone),
         },
         engine_state.signals(),
     )
@@ -217,20 +280,45 @@ where
     }
 }
 
+/// Formats the date in the time zone, so that `%Z` shows the name of a named time zone's offset.
+fn format_in_timezone(
+    date_time: DateTime<FixedOffset>,
+    timezone: Option<TimeZoneArg>,
+    formatter: &str,
+    span: Span,
+    locale: Locale,
+) -> Value {
+    match timezone {
+        None => format_from(date_time, formatter, span, locale),
+        Some(TimeZoneArg::Fixed(offset)) => {
+            format_from(date_time.with_timezone(&offset), formatter, span, locale)
+        }
+        Some(TimeZoneArg::Local) => {
+            format_from(date_time.with_timezone(&Local), formatter, span, locale)
+        }
+        Some(TimeZoneArg::Named(tz)) => {
+            format_from(date_time.with_timezone(&tz), formatter, span, locale)
+        }
+    }
+}
+
 fn format_helper(
     value: Value,
     formatter: &str,
     formatter_span: Span,
     head_span: Span,
+    timezone: Option<TimeZoneArg>,
     locale: Locale,
 ) -> Value {
     match value {
-        Value::Date { val, .. } => format_from(val, formatter, formatter_span, locale),
+        Value::Date { val, .. } => {
+            format_in_timezone(val, timezone, formatter, formatter_span, locale)
+        }
         Value::String { val, .. } => {
             let dt = parse_date_from_string(&val, formatter_span);
 
             match dt {
-                Ok(x) => format_from(x, formatter, formatter_span, locale),
+                Ok(x) => format_in_timezone(x, timezone, formatter, formatter_span, locale),
                 Err(e) => e,
             }
         }
@@ -246,11 +334,16 @@ fn format_helper(
     }
 }
 
-fn format_helper_rfc2822(value: Value, span: Span) -> Value {
+fn format_helper_rfc2822(value: Value, span: Span, timezone: Option<TimeZoneArg>) -> Value {
     let val_span = value.span();
+    let in_timezone = |dt: DateTime<FixedOffset>| match timezone {
+        Some(timezone) => timezone.convert(&dt),
+        None => dt,
+    };
     match value {
         Value::Date { val, .. } => Value::string(
             {
+                let val = in_timezone(val);
                 if val.year() >= 0 && val.year() <= 9999 {
                     val.to_rfc2822()
                 } else {
@@ -269,7 +362,7 @@ fn format_helper_rfc2822(value: Value, span: Span) -> Value {
             span,
         ),
         Value::String { val, .. } => {
-            let dt = parse_date_from_string(&val, val_span);
+            let dt = parse_date_from_string(&val, val_span).map(in_timezone);
             match dt {
                 Ok(x) => Value::string(
                     {
@@ -1,13 +1,43 @@
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
-use nu_protocol::SUPPORTED_DURATION_UNITS;
+use nu_protocol::{SUPPORTED_DURATION_UNITS, ast::PathMember, casing::Casing};
+
+const NS_PER_US: u64 = 1_000;
+const NS_PER_MS: u64 = 1_000_000;
+const NS_PER_SEC: u64 = 1_000_000_000;
+const NS_PER_MINUTE: u64 = 60 * NS_PER_SEC;
+const NS_PER_HOUR: u64 = 60 * NS_PER_MINUTE;
+const NS_PER_DAY: u64 = 24 * NS_PER_HOUR;
+
+// The units of the human and compact styles, with their singular name and abbreviation.
+const STYLE_UNITS: [(u64, &str, &str); 7] = [
+    (NS_PER_DAY, "day", "d"),
+    (NS_PER_HOUR, "hour", "h"),
+    (NS_PER_MINUTE, "minute", "m"),
+    (NS_PER_SEC, "second", "s"),
+    (NS_PER_MS, "millisecond", "ms"),
+    (NS_PER_US, "microsecond", "us"),
+    (1, "nanosecond", "ns"),
+];
 
 struct Arguments {
-    format_value: Spanned<String>,
+    format: Format,
     float_precision: usize,
     cell_paths: Option<Vec<CellPath>>,
 }
 
+enum Format {
+    Unit(Spanned<String>),
+    Style(Style),
+}
+
+#[derive(Clone, Copy)]
+enum Style {
+    Human,
+    Compact,
+    Clock,
+}
+
 impl CmdArgument for Arguments {
     fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
         self.cell_paths.take()
@@ -33,11 +63,18 @@ impl Command for FormatDuration {
                 (Type::table(), Type::table()),
             ])
             .allow_variants_without_examples(true)
-            .required(
+            .optional(
                 "format value",
                 SyntaxShape::String,
                 "The unit in which to display the duration.",
             )
+            .param(
+                Flag::new("style")
+                    .short('s')
+                    .arg(SyntaxShape::String)
+                    .desc("Display the duration split into several units instead: human, compact or clock.")
+                    .completion(Completion::new_list(&["human", "compact", "clock"])),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -50,6 +87,14 @@ impl Command for FormatDuration {
         "Outputs duration with a specified unit of time."
     }
 
+    fn extra_description(&self) -> &str {
+        r#"Instead of a unit, --style can split the duration into days, hours, minutes, seconds and
+fractions of a second: `human` writes out their names, like "1 hour, 30 minutes", `compact` uses
+their abbreviations, like "1h30m", and `clock` shows hours, minutes and seconds, like "01:30:00".
+The human and compact styles can be read back with `into duration`. With --style, all the
+arguments are cell paths."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["convert", "display", "pattern", "human readable"]
     }
@@ -65,20 +110,11 @@ impl Command for FormatDuration {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let format_value = call.req::<Value>(engine_state, stack, 0)?;
-        let format_value_span = format_value.span();
-        let format_value = Spanned {
-            item: format_value.coerce_into_string()?.to_ascii_lowercase(),
-            span: format_value_span,
-        };
+        let format_value = call.opt::<Value>(engine_state, stack, 0)?;
+        let style = call.get_flag(engine_state, stack, "style")?;
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
-        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
         let float_precision = engine_state.config.float_precision as usize;
-        let arg = Arguments {
-            format_value,
-            float_precision,
-            cell_paths,
-        };
+        let arg = arguments(format_value, style, cell_paths, float_precision, call.head)?;
         operate(
             format_value_impl,
             arg,
@@ -94,20 +130,11 @@ impl Command for FormatDuration {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let format_value = call.req_const::<Value>(working_set, 0)?;
-        let format_value_span = format_value.span();
-        let format_value = Spanned {
-            item: format_value.coerce_into_string()?.to_ascii_lowercase(),
-            span: format_value_span,
-        };
+        let format_value = call.opt_const::<Value>(working_set, 0)?;
+        let style = call.get_flag_const(working_set, "style")?;
         let cell_paths: Vec<CellPath> = call.rest_const(working_set, 1)?;
-        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
         let float_precision = working_set.permanent().config.float_precision as usize;
-        let arg = Arguments {
-            format_value,
-            float_precision,
-            cell_paths,
-        };
+        let arg = arguments(format_value, style, cell_paths, float_precision, call.head)?;
         operate(
             format_value_impl,
             arg,
@@ -137,22 +164,153 @@ impl Command for FormatDuration {
                 example: "1sec | format duration us",
                 result: Some(Value::test_string("1000000 µs")),
             },
+            Example {
+                description: "Write out a duration with the names of its units.",
+                example: "5400sec | format duration --style human",
+                result: Some(Value::test_string("1 hour, 30 minutes")),
+            },
+            Example {
+                description: "Write a duration with the abbreviations of its units.",
+                example: "93784500ms | format duration --style compact",
+                result: Some(Value::test_string("1d2h3m4s500ms")),
+            },
+            Example {
+                description: "Show the durations of a table column like a clock.",
+                example: "[[task time]; [build 90min] [test 26hr]] | format duration --style clock time",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "task" => Value::test_string("build"),
+                        "time" => Value::test_string("01:30:00"),
+                    }),
+                    Value::test_record(record! {
+                        "task" => Value::test_string("test"),
+                        "time" => Value::test_string("26:00:00"),
+                    }),
+                ])),
+            },
         ]
     }
 }
 
+fn arguments(
+    format_value: Option<Value>,
+    style: Option<Spanned<String>>,
+    mut cell_paths: Vec<CellPath>,
+    float_precision: usize,
+    head: Span,
+) -> Result<Arguments, ShellError> {
+    let format = match (format_value, style) {
+        (Some(format_value), None) => {
+            let format_value_span = format_value.span();
+            Format::Unit(Spanned {
+                item: format_value.coerce_into_string()?.to_ascii_lowercase(),
+                span: format_value_span,
+            })
+        }
+        (format_value, Some(style)) => {
+            // without a unit, the first argument is a cell path too
+            if let Some(column) = format_value {
+                let span = column.span();
+                let member = PathMember::string(
+                    column.coerce_into_string()?,
+                    false,
+                    Casing::Sensitive,
+                    span,
+                );
+                cell_paths.insert(
+                    0,
+                    CellPath {
+                        members: vec![member],
+                    },
+                );
+            }
+            Format::Style(Style::from_name(&style)?)
+        }
+        (None, None) => {
+            return Err(ShellError::MissingParameter {
+                param_name: "format value or --style".into(),
+                span: head,
+            });
+        }
+    };
+    Ok(Arguments {
+        format,
+        float_precision,
+        cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+    })
+}
+
+impl Style {
+    fn from_name(name: &Spanned<String>) -> Result<Self, ShellError> {
+        match name.item.as_str() {
+            "human" => Ok(Self::Human),
+            "compact" => Ok(Self::Compact),
+            "clock" => Ok(Self::Clock),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "Expected one of human, compact or clock".into(),
+                val_span: name.span,
+                call_span: name.span,
+            }),
+        }
+    }
+
+    fn format(self, duration: i64) -> String {
+        let sign = if duration < 0 { "-" } else { "" };
+        let mut rest = duration.unsigned_abs();
+        match self {
+            Self::Human | Self::Compact => {
+                let mut parts = Vec::new();
+                for (factor, name, abbreviation) in STYLE_UNITS {
+                    let count = rest / factor;
+                    rest %= factor;
+                    if count == 0 {
+                        continue;
+                    }
+                    parts.push(match (self, count) {
+                        (Self::Compact, _) => format!("{count}{abbreviation}"),
+                        (_, 1) => format!("{count} {name}"),
+                        _ => format!("{count} {name}s"),
+                    });
+                }
+                match (self, parts.is_empty()) {
+                    (Self::Compact, true) => "0s".into(),
+                    (Self::Compact, false) => format!("{sign}{}", parts.concat()),
+                    (_, true) => "0 seconds".into(),
+                    (_, false) => format!("{sign}{}", parts.join(", ")),
+                }
+            }
+            Self::Clock => {
+                let hours = rest / NS_PER_HOUR;
+                let minutes = rest % NS_PER_HOUR / NS_PER_MINUTE;
+                let seconds = rest % NS_PER_MINUTE / NS_PER_SEC;
+                let fraction = rest % NS_PER_SEC;
+                let clock = format!("{sign}{hours:02}:{minutes:02}:{seconds:02}");
+                if fraction == 0 {
+                    clock
+                } else {
+                    let fraction = format!("{fraction:09}");
+                    format!("{clock}.{}", fraction.trim_end_matches('0'))
+                }
+            }
+        }
+    }
+}
+
 fn format_value_impl(val: &Value, arg: &Arguments, span: Span) -> Value {
     let inner_span = val.span();
-    match val {
-        Value::Duration { val: inner, .. } => {
+    match (val, &arg.format) {
+        (Value::Duration { val: inner, .. }, Format::Style(style)) => {
+            Value::string(style.format(*inner), inner_span)
+        }
+        (Value::Duration { val: inner, .. }, Format::Unit(format_value)) => {
             let duration = *inner;
             let float_precision = arg.float_precision;
-            match convert_inner_to_unit(duration, &arg.format_value.item, arg.format_value.span) {
+            match convert_inner_to_unit(duration, &format_value.item, format_value.span) {
                 Ok(d) => {
-                    let unit = if &arg.format_value.item == "us" {
+                    let unit = if &format_value.item == "us" {
                         "µs"
                     } else {
-                        &arg.format_value.item
+                        &format_value.item
                     };
                     if d.fract() == 0.0 {
                         Value::string(format!("{d} {unit}"), inner_span)
@@ -163,7 +321,7 @@ fn format_value_impl(val: &Value, arg: &Arguments, span: Span) -> Value {
                 Err(e) => Value::error(e, inner_span),
             }
         }
-        Value::Error { .. } => val.clone(),
+        (Value::Error { .. }, _) => val.clone(),
         _ => Value::error(
             ShellError::OnlySupportsThisInputType {
                 exp_input_type: "filesize".into(),
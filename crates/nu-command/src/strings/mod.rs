@@ -10,7 +10,11 @@ mod guess_width;
 mod parse;
 mod split;
 mod str_;
+#[cfg(feature = "uuid")]
+mod uuid;
 
+#[cfg(feature = "uuid")]
+pub use self::uuid::*;
 pub use ansi::{Ansi, AnsiLink, AnsiStrip};
 pub use base::{
     DecodeBase32, DecodeBase32Hex, DecodeBase64, DecodeHex, EncodeBase32, EncodeBase32Hex,
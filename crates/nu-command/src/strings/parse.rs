@@ -1,7 +1,8 @@
+use crate::{compound_to_duration, filesize_from_string, int_from_string, parse_date_from_string};
 use fancy_regex::{Captures, Regex, RegexBuilder};
 use nu_engine::command_prelude::*;
 use nu_protocol::{ListStream, Signals, engine::StateWorkingSet};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 #[derive(Clone)]
 pub struct Parse;
@@ -20,7 +21,11 @@ impl Command for Parse {
     }
 
     fn extra_description(&self) -> &str {
-        "The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression."
+        r#"The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression.
+
+A byte stream, like the output of an external command, is parsed line by line, so the rows are produced while the stream is still being read.
+
+The columns of a simple pattern can be annotated with a type, like `{count:int}`, to convert what they capture to that type. The supported types are string, path, int, float, bool, datetime, duration and filesize."#
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -102,6 +107,15 @@ impl Command for Parse {
                     "capture0" => Value::test_string("b"),
                 })])),
             },
+            Example {
+                description: "Parse a string into columns of the annotated types.",
+                example: "\"3 errors in main.rs after 1.5 seconds\" | parse \"{count:int} errors in {file:path} after {time:float} seconds\"",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "count" => Value::test_int(3),
+                    "file" => Value::test_string("main.rs"),
+                    "time" => Value::test_float(1.5),
+                })])),
+            },
             Example {
                 description: "Parse a string with a manually set fancy-regex backtrack limit.",
                 example: "\"hi there\" | parse --backtrack 1500000 \"{foo} {bar}\"",
@@ -167,8 +181,8 @@ fn operate(
     let pattern_item = pattern.item;
     let pattern_span = pattern.span;
 
-    let item_to_parse = if regex {
-        pattern_item
+    let (item_to_parse, types) = if regex {
+        (pattern_item, HashMap::new())
     } else {
         build_regex(&pattern_item, pattern_span)?
     };
@@ -189,8 +203,11 @@ fn operate(
         .skip(1)
         .enumerate()
         .map(|(i, name)| {
-            name.map(String::from)
-                .unwrap_or_else(|| format!("capture{i}"))
+            let column = name
+                .map(String::from)
+                .unwrap_or_else(|| format!("capture{i}"));
+            let type_ = types.get(&column).copied().unwrap_or(CaptureType::String);
+            (column, type_)
         })
         .collect::<Vec<_>>();
 
@@ -276,8 +293,72 @@ fn operate(
     }
 }
 
-fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
+/// The type that the text captured by a column of a simple pattern is converted to.
+#[derive(Clone, Copy)]
+enum CaptureType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Datetime,
+    Duration,
+    Filesize,
+}
+
+impl CaptureType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "string" | "path" => Some(Self::String),
+            "int" => Some(Self::Int),
+            "float" => Some(Self::Float),
+            "bool" => Some(Self::Bool),
+            "datetime" => Some(Self::Datetime),
+            "duration" => Some(Self::Duration),
+            "filesize" => Some(Self::Filesize),
+            _ => None,
+        }
+    }
+
+    fn convert(self, text: &str, span: Span) -> Result<Value, ShellError> {
+        let cant_convert = |to_type: &str| ShellError::CantConvert {
+            to_type: to_type.into(),
+            from_type: "string".into(),
+            span,
+            help: Some(format!("the captured text was {text:?}")),
+        };
+        match self {
+            Self::String => Ok(Value::string(text, span)),
+            Self::Int => int_from_string(text, span).map(|val| Value::int(val, span)),
+            Self::Float => text
+                .trim()
+                .parse()
+                .map(|val| Value::float(val, span))
+                .map_err(|_| cant_convert("float")),
+            Self::Bool => match text.trim().to_ascii_lowercase().as_str() {
+                "true" => Ok(Value::bool(true, span)),
+                "false" => Ok(Value::bool(false, span)),
+                _ => Err(cant_convert("bool")),
+            },
+            Self::Datetime => parse_date_from_string(text, span)
+                .map(|val| Value::date(val, span))
+                .map_err(|_| cant_convert("datetime")),
+            Self::Duration => {
+                compound_to_duration(text, span).map(|val| Value::duration(val, span))
+            }
+            Self::Filesize => {
+                filesize_from_string(text, span).map(|val| Value::filesize(val, span))
+            }
+        }
+    }
+}
+
+/// Turns a simple pattern into a regular expression, and returns the types its columns are annotated with.
+fn build_regex(
+    input: &str,
+    span: Span,
+) -> Result<(String, HashMap<String, CaptureType>), ShellError> {
     let mut output = "(?s)\\A".to_string();
+    let mut types = HashMap::new();
 
     let mut loop_input = input.chars().peekable();
     loop {
@@ -314,6 +395,23 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
             }
         }
 
+        let column = match column.split_once(':') {
+            Some((name, type_name)) => {
+                let type_ = CaptureType::from_name(type_name.trim()).ok_or_else(|| {
+                    ShellError::IncorrectValue {
+                        msg: format!(
+                            "Unknown type `{type_name}` for the `{name}` column. Expected one of string, path, int, float, bool, datetime, duration or filesize."
+                        ),
+                        val_span: span,
+                        call_span: span,
+                    }
+                })?;
+                types.insert(name.to_string(), type_);
+                name.to_string()
+            }
+            None => column,
+        };
+
         if !column.is_empty() {
             output.push_str("(?");
             if column == "_" {
@@ -334,13 +432,13 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
     }
 
     output.push_str("\\z");
-    Ok(output)
+    Ok((output, types))
 }
 
 struct ParseIter<I: Iterator<Item = Result<String, ShellError>>> {
     captures: VecDeque<Value>,
     regex: Regex,
-    columns: Vec<String>,
+    columns: Vec<(String, CaptureType)>,
     iter: I,
     span: Span,
     signals: Signals,
@@ -383,7 +481,7 @@ impl<I: Iterator<Item = Result<String, ShellError>>> Iterator for ParseIter<I> {
 
 fn captures_to_value(
     captures: Result<Captures, fancy_regex::Error>,
-    columns: &[String],
+    columns: &[(String, CaptureType)],
     span: Span,
 ) -> Result<Value, ShellError> {
     let captures = captures.map_err(|err| ShellError::GenericError {
@@ -397,13 +495,14 @@ fn captures_to_value(
     let record = columns
         .iter()
         .zip(captures.iter().skip(1))
-        .map(|(column, match_)| {
-            let match_value = match_
-                .map(|m| Value::string(m.as_str(), span))
-                .unwrap_or(Value::nothing(span));
-            (column.clone(), match_value)
+        .map(|((column, type_), match_)| -> Result<_, ShellError> {
+            let match_value = match match_ {
+                Some(m) => type_.convert(m.as_str(), span)?,
+                None => Value::nothing(span),
+            };
+            Ok((column.clone(), match_value))
         })
-        .collect();
+        .collect::<Result<Record, _>>()?;
 
     Ok(Value::record(record, span))
 }
@@ -1,5 +1,7 @@
 use fancy_regex::{Regex, escape};
 use nu_engine::command_prelude::*;
+use nu_protocol::{Chunks, ListStream};
+use std::collections::VecDeque;
 
 #[derive(Clone)]
 pub struct SplitRow;
@@ -38,6 +40,12 @@ impl Command for SplitRow {
         "Split a string into multiple rows using a separator."
     }
 
+    fn extra_description(&self) -> &str {
+        "When the input is a byte stream, like the output of an external command, the rows are \
+         produced as soon as the separator that ends them has been read, instead of after the whole \
+         stream has been collected."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["separate", "divide", "regex"]
     }
@@ -167,10 +175,154 @@ fn split_row(
         help: None,
         inner: vec![],
     })?;
-    input.flat_map(
-        move |x| split_row_helper(&x, &regex, args.max_split, name_span),
-        engine_state.signals(),
-    )
+    match input {
+        PipelineData::ByteStream(stream, metadata) => {
+            let span = stream.span();
+            let Some(chunks) = stream.chunks() else {
+                return Ok(PipelineData::empty());
+            };
+            let iter = StreamSplitter {
+                chunks,
+                regex,
+                max_split: args.max_split,
+                buffer: String::new(),
+                continuation: false,
+                emitted: 0,
+                rows: VecDeque::new(),
+                done: false,
+                span,
+                head: name_span,
+            };
+            Ok(PipelineData::list_stream(
+                ListStream::new(iter, name_span, engine_state.signals().clone()),
+                metadata,
+            ))
+        }
+        input => input.flat_map(
+            move |x| split_row_helper(&x, &regex, args.max_split, name_span),
+            engine_state.signals(),
+        ),
+    }
+}
+
+/// Splits a byte stream into rows while it is being read.
+///
+/// A separator is only used once there is more text after it, as the rest of it could still be
+/// in the next chunk, like more spaces for a `\s+` separator.
+struct StreamSplitter {
+    chunks: Chunks,
+    regex: Regex,
+    max_split: Option<usize>,
+    /// The text after the last separator that has been used.
+    buffer: String,
+    /// Whether the buffer starts right after a separator, rather than at the start of the stream.
+    continuation: bool,
+    /// How many rows have been split off so far.
+    emitted: usize,
+    rows: VecDeque<Value>,
+    done: bool,
+    span: Span,
+    head: Span,
+}
+
+impl StreamSplitter {
+    fn split_buffer(&mut self, finished: bool) {
+        let mut last = 0;
+        for found in self.regex.find_iter(&self.buffer) {
+            let found = match found {
+                Ok(found) => found,
+                Err(err) => {
+                    self.rows.push_back(Value::error(
+                        ShellError::GenericError {
+                            error: "Error with regular expression".into(),
+                            msg: err.to_string(),
+                            span: Some(self.span),
+                            help: None,
+                            inner: vec![],
+                        },
+                        self.span,
+                    ));
+                    self.done = true;
+                    return;
+                }
+            };
+            if !finished && found.end() >= self.buffer.len() {
+                break;
+            }
+            // an empty separator right at the start is the one that ended the previous row
+            if self.continuation && found.start() == 0 && found.end() == 0 {
+                continue;
+            }
+            if self.max_split.is_some_and(|max| self.emitted + 1 >= max) {
+                break;
+            }
+            self.rows
+                .push_back(Value::string(&self.buffer[last..found.start()], self.span));
+            self.emitted += 1;
+            last = found.end();
+        }
+
+        if finished {
+            if self.max_split != Some(0) {
+                self.rows
+                    .push_back(Value::string(&self.buffer[last..], self.span));
+            }
+            self.buffer.clear();
+        } else if last > 0 {
+            self.buffer.drain(..last);
+            self.continuation = true;
+        }
+    }
+}
+
+impl Iterator for StreamSplitter {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(row);
+            }
+            if self.done {
+                return None;
+            }
+            match self.chunks.next() {
+                Some(Ok(Value::String { val, .. })) => {
+                    self.buffer.push_str(&val);
+                    if self.max_split.is_none_or(|max| self.emitted + 1 < max) {
+                        self.split_buffer(false);
+                    }
+                }
+                Some(Ok(value)) => {
+                    self.done = true;
+                    return Some(Value::error(
+                        ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "string".into(),
+                            wrong_type: value.get_type().to_string(),
+                            dst_span: self.head,
+                            src_span: self.span,
+                        },
+                        self.head,
+                    ));
+                }
+                Some(Err(err)) => {
+                    self.done = true;
+                    return Some(Value::error(err, self.span));
+                }
+                None => {
+                    // like collecting the stream into a string, the final line ending is dropped
+                    if self.buffer.ends_with('\n') {
+                        self.buffer.pop();
+                        if self.buffer.ends_with('\r') {
+                            self.buffer.pop();
+                        }
+                    }
+                    self.split_buffer(true);
+                    self.done = true;
+                }
+            }
+        }
+    }
 }
 
 fn split_row_helper(v: &Value, regex: &Regex, max_split: Option<usize>, name: Span) -> Vec<Value> {
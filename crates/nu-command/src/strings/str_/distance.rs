@@ -7,6 +7,7 @@ pub struct StrDistance;
 
 struct Arguments {
     compare_string: String,
+    algorithm: Algorithm,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -25,6 +26,7 @@ impl Command for StrDistance {
         Signature::build("str distance")
             .input_output_types(vec![
                 (Type::String, Type::Int),
+                (Type::String, Type::Float),
                 (Type::table(), Type::table()),
                 (Type::record(), Type::record()),
             ])
@@ -38,6 +40,12 @@ impl Command for StrDistance {
                 SyntaxShape::CellPath,
                 "For a data structure input, check strings at the given cell paths, and replace with result.",
             )
+            .named(
+                "algorithm",
+                SyntaxShape::String,
+                "The algorithm to use: levenshtein (the default), jaro or jaro-winkler.",
+                Some('a'),
+            )
             .category(Category::Strings)
     }
 
@@ -45,8 +53,16 @@ impl Command for StrDistance {
         "Compare two strings and return the edit distance/Levenshtein distance."
     }
 
+    fn extra_description(&self) -> &str {
+        "The Levenshtein distance is the number of characters that have to be inserted, deleted or \
+         substituted to turn one string into the other. The Jaro and Jaro-Winkler distances are \
+         floats between 0 for identical strings and 1 for strings that have nothing in common. \
+         Jaro-Winkler favors strings that start the same way, which makes it well suited for short \
+         strings like names."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["edit", "levenshtein"]
+        vec!["edit", "levenshtein", "jaro", "winkler", "similarity"]
     }
 
     fn is_const(&self) -> bool {
@@ -63,8 +79,13 @@ impl Command for StrDistance {
         let compare_string: String = call.req(engine_state, stack, 0)?;
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let algorithm = match call.get_flag(engine_state, stack, "algorithm")? {
+            Some(name) => Algorithm::from_name(&name)?,
+            None => Algorithm::Levenshtein,
+        };
         let args = Arguments {
             compare_string,
+            algorithm,
             cell_paths,
         };
         operate(action, args, input, call.head, engine_state.signals())
@@ -79,8 +100,13 @@ impl Command for StrDistance {
         let compare_string: String = call.req_const(working_set, 0)?;
         let cell_paths: Vec<CellPath> = call.rest_const(working_set, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let algorithm = match call.get_flag_const(working_set, "algorithm")? {
+            Some(name) => Algorithm::from_name(&name)?,
+            None => Algorithm::Levenshtein,
+        };
         let args = Arguments {
             compare_string,
+            algorithm,
             cell_paths,
         };
         operate(
@@ -99,6 +125,11 @@ impl Command for StrDistance {
                 example: "'nushell' | str distance 'nutshell'",
                 result: Some(Value::test_int(1)),
             },
+            Example {
+                description: "Get the Jaro-Winkler distance between two strings.",
+                example: "'martha' | str distance --algorithm jaro-winkler 'marhta'",
+                result: Some(Value::test_float(0.03888888888888886)),
+            },
             Example {
                 description: "Compute edit distance between strings in table and another string, using cell paths.",
                 example: "[{a: 'nutshell' b: 'numetal'}] | str distance 'nushell' 'a' 'b'",
@@ -122,10 +153,13 @@ impl Command for StrDistance {
 fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     let compare_string = &args.compare_string;
     match input {
-        Value::String { val, .. } => {
-            let distance = levenshtein_distance(val, compare_string);
-            Value::int(distance as i64, head)
-        }
+        Value::String { val, .. } => match args.algorithm {
+            Algorithm::Levenshtein => {
+                let distance = levenshtein_distance(val, compare_string);
+                Value::int(distance as i64, head)
+            }
+            algorithm => Value::float(1.0 - algorithm.similarity(val, compare_string), head),
+        },
         Value::Error { .. } => input.clone(),
         _ => Value::error(
             ShellError::OnlySupportsThisInputType {
@@ -139,6 +173,98 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     }
 }
 
+/// The ways of comparing strings that `str distance` and `str fuzzy-match` support.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Algorithm {
+    Levenshtein,
+    Jaro,
+    JaroWinkler,
+}
+
+impl Algorithm {
+    pub(super) fn from_name(name: &Spanned<String>) -> Result<Self, ShellError> {
+        match name.item.as_str() {
+            "levenshtein" => Ok(Self::Levenshtein),
+            "jaro" => Ok(Self::Jaro),
+            "jaro-winkler" => Ok(Self::JaroWinkler),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "Expected one of levenshtein, jaro or jaro-winkler".into(),
+                val_span: name.span,
+                call_span: name.span,
+            }),
+        }
+    }
+
+    /// How similar two strings are, from 0 for nothing in common to 1 for identical strings.
+    pub(super) fn similarity(self, a: &str, b: &str) -> f64 {
+        match self {
+            Self::Levenshtein => {
+                let longest = a.chars().count().max(b.chars().count());
+                if longest == 0 {
+                    1.0
+                } else {
+                    1.0 - levenshtein_distance(a, b) as f64 / longest as f64
+                }
+            }
+            Self::Jaro => jaro_similarity(a, b),
+            Self::JaroWinkler => jaro_winkler_similarity(a, b),
+        }
+    }
+}
+
+fn jaro_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // characters only match if they aren't further apart than this
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == *c) {
+            a_matched[i] = true;
+            b_matched[j] = true;
+            matches += 1;
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_order = a.iter().zip(&a_matched).filter(|(_, m)| **m);
+    let b_order = b.iter().zip(&b_matched).filter(|(_, m)| **m);
+    let transpositions = a_order
+        .zip(b_order)
+        .filter(|((a, _), (b, _))| a != b)
+        .count() as f64
+        / 2.0;
+
+    let matches = matches as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions) / matches)
+        / 3.0
+}
+
+fn jaro_winkler_similarity(a: &str, b: &str) -> f64 {
+    let jaro = jaro_similarity(a, b);
+    // a common prefix of up to 4 characters makes strings more similar
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +275,33 @@ mod tests {
 
         test_examples(StrDistance {})
     }
+
+    #[test]
+    fn test_jaro_winkler_similarity() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        assert!(close(
+            jaro_similarity("martha", "marhta"),
+            0.9444444444444445
+        ));
+        assert!(close(
+            jaro_winkler_similarity("martha", "marhta"),
+            0.9611111111111111
+        ));
+        assert!(close(
+            jaro_winkler_similarity("dixon", "dicksonx"),
+            0.8133333333333332
+        ));
+        assert!(close(jaro_similarity("abc", "xyz"), 0.0));
+        assert!(close(jaro_similarity("", ""), 1.0));
+    }
+
+    #[test]
+    fn test_levenshtein_similarity() {
+        assert_eq!(
+            Algorithm::Levenshtein.similarity("kitten", "sitten"),
+            1.0 - 1.0 / 6.0
+        );
+        assert_eq!(Algorithm::Levenshtein.similarity("", ""), 1.0);
+    }
 }
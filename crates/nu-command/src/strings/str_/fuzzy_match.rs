@@ -0,0 +1,168 @@
+use super::distance::Algorithm;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct StrFuzzyMatch;
+
+impl Command for StrFuzzyMatch {
+    fn name(&self) -> &str {
+        "str fuzzy-match"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str fuzzy-match")
+            .input_output_types(vec![(Type::List(Box::new(Type::String)), Type::table())])
+            .required("query", SyntaxShape::String, "The string to look for.")
+            .named(
+                "threshold",
+                SyntaxShape::Number,
+                "Only return the strings with at least this score, between 0 and 1.",
+                Some('t'),
+            )
+            .named(
+                "algorithm",
+                SyntaxShape::String,
+                "The algorithm to score the strings with: jaro-winkler (the default), jaro or levenshtein.",
+                Some('a'),
+            )
+            .switch(
+                "ignore-case",
+                "Compare the strings case-insensitively.",
+                Some('i'),
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Score how similar a list of strings is to a query, best matches first."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The scores are floats between 0 for strings that have nothing in common with the query and \
+         1 for strings that are identical to it. With the levenshtein algorithm, the score is the \
+         edit distance relative to the length of the longer string."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "fuzzy",
+            "similar",
+            "did you mean",
+            "jaro",
+            "winkler",
+            "levenshtein",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let query: String = call.req(engine_state, stack, 0)?;
+        let threshold = match call.get_flag::<Spanned<f64>>(engine_state, stack, "threshold")? {
+            Some(threshold) if !(0.0..=1.0).contains(&threshold.item) => {
+                return Err(ShellError::IncorrectValue {
+                    msg: "The threshold has to be between 0 and 1".into(),
+                    val_span: threshold.span,
+                    call_span: head,
+                });
+            }
+            Some(threshold) => threshold.item,
+            None => 0.0,
+        };
+        let algorithm = match call.get_flag(engine_state, stack, "algorithm")? {
+            Some(name) => Algorithm::from_name(&name)?,
+            None => Algorithm::JaroWinkler,
+        };
+        let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
+        let query = if ignore_case {
+            query.to_lowercase()
+        } else {
+            query
+        };
+
+        let mut matches = vec![];
+        for value in input.into_iter() {
+            let span = value.span();
+            let string = match value {
+                Value::String { val, .. } => val,
+                Value::Error { error, .. } => return Err(*error),
+                value => {
+                    return Err(ShellError::OnlySupportsThisInputType {
+                        exp_input_type: "string".into(),
+                        wrong_type: value.get_type().to_string(),
+                        dst_span: head,
+                        src_span: span,
+                    });
+                }
+            };
+            let score = if ignore_case {
+                algorithm.similarity(&string.to_lowercase(), &query)
+            } else {
+                algorithm.similarity(&string, &query)
+            };
+            if score >= threshold {
+                matches.push((string, score));
+            }
+        }
+        // the sort is stable, so equally good matches keep their order
+        matches.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let rows = matches
+            .into_iter()
+            .map(|(string, score)| {
+                Value::record(
+                    record! {
+                        "value" => Value::string(string, head),
+                        "score" => Value::float(score, head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Suggest what a misspelled subcommand could have meant.",
+                example: "[commit checkout clone config] | str fuzzy-match comit --threshold 0.7",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "value" => Value::test_string("commit"),
+                        "score" => Value::test_float(0.9611111111111111),
+                    }),
+                    Value::test_record(record! {
+                        "value" => Value::test_string("config"),
+                        "score" => Value::test_float(0.76),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Find the closest name regardless of case, using the Levenshtein distance.",
+                example: "[Alice Bob Carol] | str fuzzy-match --ignore-case --algorithm levenshtein alise | first",
+                result: Some(Value::test_record(record! {
+                    "value" => Value::test_string("Alice"),
+                    "score" => Value::test_float(0.8),
+                })),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(StrFuzzyMatch {})
+    }
+}
@@ -4,6 +4,7 @@ mod distance;
 mod ends_with;
 mod escape_regex;
 mod expand;
+mod fuzzy_match;
 mod index_of;
 mod join;
 mod length;
@@ -20,6 +21,7 @@ pub use distance::StrDistance;
 pub use ends_with::StrEndswith;
 pub use escape_regex::StrEscapeRegex;
 pub use expand::StrExpand;
+pub use fuzzy_match::StrFuzzyMatch;
 pub use index_of::StrIndexOf;
 pub use join::*;
 pub use length::StrLength;
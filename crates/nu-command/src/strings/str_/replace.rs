@@ -84,7 +84,9 @@ impl Command for StrReplace {
 The replacement can be a a string, possibly containing references to numbered (`$1` etc) or
 named capture groups (`$name`), or it can be closure that is invoked for each match.
 In the latter case, the closure is invoked with the entire match as its input and any capture
-groups as its argument. It must return a string that will be used as a replacement for the match.
+groups as its arguments, or the entire match as its argument if the pattern has no capture groups.
+It must return a string, or a number or boolean that is converted to one, which will be used as a
+replacement for the match.
 "#
     }
 
@@ -251,6 +253,11 @@ groups as its argument. It must return a string that will be used as a replaceme
                     "string: \"abc\" backslash: \\ newline:\nend",
                 )),
             },
+            Example {
+                description: "Replace every word with its length, using a closure that gets the match as its argument.",
+                example: r#"'one three five' | str replace --all --regex '\w+' {|word| $word | str length }"#,
+                result: Some(Value::test_string("3 5 4")),
+            },
         ]
     }
 }
@@ -279,19 +286,10 @@ fn action(
                         // find_str is fixed, so we need to run the closure only once
                         let mut closure_eval = closure.item.clone();
                         let span = closure.span;
-                        let result: Result<Value, ShellError> = closure_eval
-                            .run_with_value(Value::string(find.item.clone(), find.span))
-                            .and_then(|result| result.into_value(span));
-                        match result {
-                            Ok(Value::String { val, .. }) => Ok(Arc::new(val.into_spanned(span))),
-                            Ok(res) => Err((
-                                ShellError::RuntimeTypeMismatch {
-                                    expected: Type::String,
-                                    actual: res.get_type(),
-                                    span: res.span(),
-                                },
-                                span,
-                            )),
+                        let result = closure_eval
+                            .run_with_value(Value::string(find.item.clone(), find.span));
+                        match replacement_string(result, span) {
+                            Ok(val) => Ok(Arc::new(val.into_spanned(span))),
                             Err(error) => Err((error, span)),
                         }
                     }
@@ -350,6 +348,15 @@ fn action(
                         let mut closure_eval = closure.item.clone();
                         let mut first_error: Option<ShellError> = None;
                         let replacer = |caps: &Captures| {
+                            let value = match caps.get(0) {
+                                Some(m) => Value::string(m.as_str().to_string(), head),
+                                None => Value::nothing(head),
+                            };
+                            // without capture groups, the match itself is the argument, just like
+                            // it is when replacing a substring
+                            if caps.len() == 1 {
+                                closure_eval.add_arg(value.clone());
+                            }
                             for capture in caps.iter().skip(1) {
                                 let arg = match capture {
                                     Some(m) => Value::string(m.as_str().to_string(), head),
@@ -357,23 +364,10 @@ fn action(
                                 };
                                 closure_eval.add_arg(arg);
                             }
-                            let value = match caps.get(0) {
-                                Some(m) => Value::string(m.as_str().to_string(), head),
-                                None => Value::nothing(head),
-                            };
-                            let result: Result<Value, ShellError> = closure_eval
-                                .run_with_input(PipelineData::value(value, None))
-                                .and_then(|result| result.into_value(span));
-                            match result {
-                                Ok(Value::String { val, .. }) => val.to_string(),
-                                Ok(res) => {
-                                    first_error = Some(ShellError::RuntimeTypeMismatch {
-                                        expected: Type::String,
-                                        actual: res.get_type(),
-                                        span: res.span(),
-                                    });
-                                    "".to_string()
-                                }
+                            let result =
+                                closure_eval.run_with_input(PipelineData::value(value, None));
+                            match replacement_string(result, span) {
+                                Ok(val) => val,
                                 Err(e) => {
                                     first_error = Some(e);
                                     "".to_string()
@@ -414,6 +408,24 @@ fn action(
     }
 }
 
+/// Turns the output of a replacement closure into the string that replaces the match.
+fn replacement_string(
+    result: Result<PipelineData, ShellError>,
+    span: Span,
+) -> Result<String, ShellError> {
+    match result?.into_value(span)? {
+        Value::String { val, .. } => Ok(val),
+        val @ (Value::Int { .. } | Value::Float { .. } | Value::Bool { .. }) => {
+            val.coerce_into_string()
+        }
+        val => Err(ShellError::RuntimeTypeMismatch {
+            expected: Type::String,
+            actual: val.get_type(),
+            span: val.span(),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
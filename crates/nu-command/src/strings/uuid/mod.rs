@@ -0,0 +1,5 @@
+mod parse;
+mod uuid_;
+
+pub use parse::UuidParse;
+pub use uuid_::Uuid;
@@ -0,0 +1,182 @@
+use chrono::{DateTime, TimeZone, Utc};
+use nu_cmd_base::input_handler::{CellPathOnlyArgs, operate};
+use nu_engine::command_prelude::*;
+use uuid::{Uuid, Variant};
+
+#[derive(Clone)]
+pub struct UuidParse;
+
+impl Command for UuidParse {
+    fn name(&self) -> &str {
+        "uuid parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uuid parse")
+            .input_output_types(vec![
+                (Type::String, Type::record()),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, parse the UUIDs at the given cell paths.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Validate a UUID and extract its version, variant, timestamp and node id."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The UUID can be hyphenated, simple (without hyphens), braced or a URN, and is returned in
+its lowercase hyphenated form. The timestamp is only there for the time based versions 1, 6 and 7,
+and the node id for versions 1 and 6. Strings that aren't UUIDs are errors, so `uuid parse` can
+also be used to validate them."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["validate", "guid", "version", "timestamp", "into uuid"]
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let args = CellPathOnlyArgs::from(cell_paths);
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths: Vec<CellPath> = call.rest_const(working_set, 0)?;
+        let args = CellPathOnlyArgs::from(cell_paths);
+        operate(
+            action,
+            args,
+            input,
+            call.head,
+            working_set.permanent().signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get the time a version 7 UUID was made at.",
+                example: "'01890a5d-ac96-774b-bcce-b302099a8057' | uuid parse",
+                result: Some(Value::test_record(record! {
+                    "uuid" => Value::test_string("01890a5d-ac96-774b-bcce-b302099a8057"),
+                    "version" => Value::test_int(7),
+                    "variant" => Value::test_string("RFC4122"),
+                    "timestamp" => Value::test_date(
+                        Utc.timestamp_millis_opt(1_688_096_058_518).unwrap().into(),
+                    ),
+                    "node_id" => Value::test_nothing(),
+                })),
+            },
+            Example {
+                description: "Normalize a braced UUID in uppercase.",
+                example: "'{67E55044-10B1-426F-9247-BB680E5FE0C8}' | uuid parse | get uuid",
+                result: Some(Value::test_string("67e55044-10b1-426f-9247-bb680e5fe0c8")),
+            },
+            Example {
+                description: "Check that a string is a UUID.",
+                example: "'not-a-uuid' | try { uuid parse; true } catch { false }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &CellPathOnlyArgs, head: Span) -> Value {
+    let span = input.span();
+    match input {
+        Value::String { val, .. } => match Uuid::parse_str(val) {
+            Ok(uuid) => parse(uuid, head),
+            Err(error) => Value::error(
+                ShellError::CantConvert {
+                    to_type: "UUID".into(),
+                    from_type: "string".into(),
+                    span,
+                    help: Some(error.to_string()),
+                },
+                head,
+            ),
+        },
+        Value::Error { .. } => input.clone(),
+        other => Value::error(
+            ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: span,
+            },
+            head,
+        ),
+    }
+}
+
+fn parse(uuid: Uuid, head: Span) -> Value {
+    let variant = match uuid.get_variant() {
+        Variant::NCS => "NCS",
+        Variant::RFC4122 => "RFC4122",
+        Variant::Microsoft => "Microsoft",
+        _ => "Future",
+    };
+    let timestamp = uuid
+        .get_timestamp()
+        .and_then(|timestamp| {
+            let (seconds, nanoseconds) = timestamp.to_unix();
+            DateTime::from_timestamp(seconds as i64, nanoseconds)
+        })
+        .map_or_else(
+            || Value::nothing(head),
+            |timestamp| Value::date(timestamp.fixed_offset(), head),
+        );
+    let node_id = uuid.get_node_id().map_or_else(
+        || Value::nothing(head),
+        |node_id| {
+            let node_id = node_id.map(|byte| format!("{byte:02x}")).join(":");
+            Value::string(node_id, head)
+        },
+    );
+
+    Value::record(
+        record! {
+            "uuid" => Value::string(uuid.hyphenated().to_string(), head),
+            "version" => Value::int(uuid.get_version_num() as i64, head),
+            "variant" => Value::string(variant, head),
+            "timestamp" => timestamp,
+            "node_id" => node_id,
+        },
+        head,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(UuidParse {})
+    }
+}
@@ -0,0 +1,38 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Uuid;
+
+impl Command for Uuid {
+    fn name(&self) -> &str {
+        "uuid"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uuid")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with UUIDs."
+    }
+
+    fn extra_description(&self) -> &str {
+        "You must use one of the following subcommands. Using this command as-is will only produce this help message."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["guid", "identifier", "parse"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
@@ -566,6 +566,21 @@ pub fn command_not_found(
                     span,
                 };
             }
+            // A list of suggestions, like packages providing the command, is shown line by line
+            Ok(PipelineData::Value(Value::List { vals, .. }, ..))
+                if !vals.is_empty() && vals.iter().all(|val| val.as_str().is_ok()) =>
+            {
+                let help = vals
+                    .iter()
+                    .filter_map(|val| val.as_str().ok())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return ShellError::ExternalCommand {
+                    label: format!("Command `{name}` not found"),
+                    help,
+                    span,
+                };
+            }
             Err(err) => {
                 return err;
             }
@@ -1,5 +1,7 @@
 mod griddle;
 mod table;
+#[cfg(feature = "os")]
+mod table_pager;
 
 pub use griddle::Griddle;
 pub use table::Table;
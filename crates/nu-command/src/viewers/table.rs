@@ -2,7 +2,13 @@
 //        overall reduce the redundant calls to StyleComputer etc.
 //        the goal is to configure it once...
 
-use std::{collections::VecDeque, io::Read, path::PathBuf, str::FromStr, time::Duration};
+use std::{
+    collections::VecDeque,
+    io::{IsTerminal, Read},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 
 use devicons::icon_for_file;
 use lscolors::{LsColors, Style};
@@ -30,6 +36,8 @@ type NuPathBuf = nu_path::PathBuf<Absolute>;
 type NuPath = nu_path::Path<Absolute>;
 
 const DEFAULT_TABLE_WIDTH: usize = 80;
+/// The width tables are rendered at for `--page`, unless `--width` is given.
+const PAGED_TABLE_WIDTH: usize = 10_000;
 
 #[derive(Clone)]
 pub struct Table;
@@ -45,7 +53,11 @@ impl Command for Table {
     }
 
     fn extra_description(&self) -> &str {
-        "If the table contains a column called 'index', this column is used as the table index instead of the usual continuous index."
+        "If the table contains a column called 'index', this column is used as the table index instead of the usual continuous index.
+
+Columns can be given a `max_width`, an `alignment`, a `trim` strategy for cells wider than `max_width`, and be `hidden` by name in $env.config.table.columns, or with --column-config for a single run.
+
+With --page, the whole input is rendered as one table, without wrapping it to the terminal width unless --width is given, and shown in a pager. Scroll with the arrow keys or j/k/h/l, page with space/b, jump with g/G (top/bottom) and 0/$ (line start/end), and quit with q. When the output is not a terminal, --page is ignored."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -107,6 +119,22 @@ impl Command for Table {
             .switch("list", "List available table modes/themes.", Some('l'))
             .switch("icons", "Add icons to file paths in tables.", Some('o'),
             )
+            .named(
+                "column-config",
+                SyntaxShape::Record(vec![]),
+                "Per-column display settings, merged into $env.config.table.columns for this run.",
+                None,
+            )
+            .switch(
+                "all",
+                "Show the columns hidden by $env.config.table.columns.",
+                None,
+            )
+            .switch(
+                "page",
+                "Show the table in a pager that keeps the header on screen.",
+                Some('p'),
+            )
             .category(Category::Viewers)
     }
 
@@ -132,6 +160,15 @@ impl Command for Table {
             let _ = nu_utils::enable_vt_processing();
         }
 
+        #[cfg(feature = "os")]
+        if input.cfg.page {
+            let head = call.head;
+            let config = input.get_config();
+            let text = handle_table_command(input)?.collect_string("", &config)?;
+            super::table_pager::page(&text).map_err(|err| IoError::new(err, head, None))?;
+            return Ok(PipelineData::empty());
+        }
+
         handle_table_command(input)
     }
 
@@ -210,6 +247,16 @@ impl Command for Table {
                 example: r#"[[a b]; [1 2] [3 [4 4]]] | table -i false"#,
                 result: None,
             },
+            Example {
+                description: "Browse a long listing, with the header kept at the top",
+                example: r#"ls **/* | table --page"#,
+                result: None,
+            },
+            Example {
+                description: "Truncate long messages, right-align the level and hide the host column of a log",
+                example: r#"[[level message host]; [warn "disk almost full on /var" web-1]] | table --column-config {message: {max_width: 12, trim: {methodology: truncating, truncating_suffix: "…"}}, level: {alignment: right}, host: {hidden: true}}"#,
+                result: None,
+            },
         ]
     }
 }
@@ -244,9 +291,11 @@ struct TableConfig {
     index: Option<usize>,
     use_ansi_coloring: bool,
     icons: bool,
+    page: bool,
 }
 
 impl TableConfig {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         view: TableView,
         width: usize,
@@ -255,6 +304,7 @@ impl TableConfig {
         index: Option<usize>,
         use_ansi_coloring: bool,
         icons: bool,
+        page: bool,
     ) -> Self {
         Self {
             view,
@@ -264,6 +314,7 @@ impl TableConfig {
             index,
             use_ansi_coloring,
             icons,
+            page,
         }
     }
 }
@@ -291,6 +342,7 @@ struct CLIArgs {
     index: Option<usize>,
     use_ansi_coloring: bool,
     icons: bool,
+    page: bool,
 }
 
 fn parse_table_config(
@@ -300,7 +352,11 @@ fn parse_table_config(
 ) -> ShellResult<TableConfig> {
     let args = get_cli_args(call, state, stack)?;
     let table_view = get_table_view(&args);
-    let term_width = get_table_width(args.width);
+    let term_width = match args.width {
+        // The pager scrolls sideways, so there's no need to squeeze the table
+        None if args.page => PAGED_TABLE_WIDTH,
+        width => get_table_width(width),
+    };
 
     let cfg = TableConfig::new(
         table_view,
@@ -310,6 +366,7 @@ fn parse_table_config(
         args.index,
         args.use_ansi_coloring,
         args.icons,
+        args.page,
     );
 
     Ok(cfg)
@@ -342,6 +399,10 @@ fn get_cli_args(call: &Call<'_>, state: &EngineState, stack: &mut Stack) -> Shel
         get_theme_flag(call, state, stack)?.unwrap_or_else(|| stack.get_config(state).table.mode);
     let index = get_index_flag(call, state, stack)?;
     let icons = call.has_flag(state, stack, "icons")?;
+    // Paging needs a terminal, and crossterm to drive it
+    let page = cfg!(feature = "os")
+        && call.has_flag(state, stack, "page")?
+        && std::io::stdout().is_terminal();
 
     let use_ansi_coloring = stack.get_config(state).use_ansi_coloring.get(state);
 
@@ -357,6 +418,7 @@ fn get_cli_args(call: &Call<'_>, state: &EngineState, stack: &mut Stack) -> Shel
         index,
         use_ansi_coloring,
         icons,
+        page,
     })
 }
 
@@ -419,6 +481,51 @@ fn get_theme_flag(
         .transpose()
 }
 
+/// The config, with `--column-config` merged into `table.columns` and `--all` showing hidden
+/// columns.
+fn get_config_with_column_flags(
+    call: &Call,
+    state: &EngineState,
+    stack: &mut Stack,
+) -> ShellResult<std::sync::Arc<Config>> {
+    let config = stack.get_config(state);
+    let column_config: Option<Value> = call.get_flag(state, stack, "column-config")?;
+    let all: bool = call.has_flag(state, stack, "all")?;
+    if column_config.is_none() && !all {
+        return Ok(config);
+    }
+
+    let mut new_config = Config::clone(&config);
+    if let Some(column_config) = column_config {
+        let span = column_config.span();
+        let mut columns = config
+            .table
+            .columns
+            .clone()
+            .into_value(span)
+            .into_record()?;
+        for (name, column) in column_config.into_record()? {
+            columns.insert(name, column);
+        }
+
+        let value = Value::record(
+            record! {
+                "table" => Value::record(record! { "columns" => Value::record(columns, span) }, span),
+            },
+            span,
+        );
+        new_config.update_from_value(&config, &value)?;
+    }
+
+    if all {
+        for column in new_config.table.columns.values_mut() {
+            column.hidden = false;
+        }
+    }
+
+    Ok(std::sync::Arc::new(new_config))
+}
+
 struct CmdInput<'a> {
     engine_state: &'a EngineState,
     stack: &'a mut Stack,
@@ -426,6 +533,7 @@ struct CmdInput<'a> {
     data: PipelineData,
     cfg: TableConfig,
     cwd: Option<NuPathBuf>,
+    config: std::sync::Arc<Config>,
 }
 
 impl<'a> CmdInput<'a> {
@@ -437,6 +545,7 @@ impl<'a> CmdInput<'a> {
     ) -> ShellResult<Self> {
         let cfg = parse_table_config(call, engine_state, stack)?;
         let cwd = get_cwd(engine_state, stack)?;
+        let config = get_config_with_column_flags(call, engine_state, stack)?;
 
         Ok(Self {
             engine_state,
@@ -445,11 +554,12 @@ impl<'a> CmdInput<'a> {
             data,
             cfg,
             cwd,
+            config,
         })
     }
 
     fn get_config(&self) -> std::sync::Arc<Config> {
-        self.stack.get_config(self.engine_state)
+        self.config.clone()
     }
 }
 
@@ -978,6 +1088,16 @@ impl Iterator for PagingTableCreator {
                 (batch, _, end) =
                     stream_collect_abbreviated(&mut self.stream, abbr, self.engine_state.signals());
             }
+            // The pager shows a single table, so the whole stream goes into one batch
+            None if self.table_config.page => {
+                let signals = self.engine_state.signals();
+                batch = self
+                    .stream
+                    .by_ref()
+                    .take_while(|_| !signals.interrupted())
+                    .collect();
+                end = true;
+            }
             None => {
                 // Pull from stream until time runs out or we have enough items
                 (batch, end) = stream_collect(
@@ -1304,6 +1424,7 @@ const SUPPORTED_TABLE_MODES: &[&str] = &[
     "basic_compact",
     "single",
     "double",
+    "custom",
 ];
 
 fn supported_table_modes() -> Vec<Value> {
@@ -0,0 +1,244 @@
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{
+        self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use nu_ansi_term::{Style, ansi::RESET};
+use nu_utils::strip_ansi_string_likely;
+use std::io::{self, Stdout, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// How many columns a horizontal scroll moves by.
+const HORIZONTAL_STEP: usize = 8;
+/// How far down the header separator of a table is looked for.
+const MAX_HEADER_LINES: usize = 10;
+
+/// Leaves the alternate screen and raw mode again, however the pager exits.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Show a rendered table in the alternate screen, keeping its header at the top while the rows
+/// are scrolled.
+pub(crate) fn page(text: &str) -> io::Result<()> {
+    let lines: Vec<&str> = text.lines().collect();
+    let header_len = header_len(&lines);
+    let (header, body) = lines.split_at(header_len);
+    let text_width = lines
+        .iter()
+        .map(|line| strip_ansi_string_likely(line.to_string()).width())
+        .max()
+        .unwrap_or(0);
+
+    enable_raw_mode()?;
+    let _guard = TerminalGuard;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+
+    let mut top = 0;
+    let mut left = 0;
+    loop {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        // One line is kept for the status line
+        let body_height = height.saturating_sub(header.len() + 1).max(1);
+        let max_top = body.len().saturating_sub(body_height);
+        let max_left = text_width.saturating_sub(width);
+        top = top.min(max_top);
+        left = left.min(max_left);
+
+        draw(&mut stdout, header, body, top, left, width, body_height)?;
+
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            // Redraw on resizes, and ignore everything else
+            continue;
+        };
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Enter => top += 1,
+            KeyCode::Up | KeyCode::Char('k') => top = top.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') | KeyCode::Char('f') => top += body_height,
+            KeyCode::PageUp | KeyCode::Char('b') => top = top.saturating_sub(body_height),
+            KeyCode::Home | KeyCode::Char('g') => top = 0,
+            KeyCode::End | KeyCode::Char('G') => top = max_top,
+            KeyCode::Right | KeyCode::Char('l') => left += HORIZONTAL_STEP,
+            KeyCode::Left | KeyCode::Char('h') => left = left.saturating_sub(HORIZONTAL_STEP),
+            KeyCode::Char('0') => left = 0,
+            KeyCode::Char('$') => left = max_left,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(
+    stdout: &mut Stdout,
+    header: &[&str],
+    body: &[&str],
+    top: usize,
+    left: usize,
+    width: usize,
+    body_height: usize,
+) -> io::Result<()> {
+    let visible = body.iter().skip(top).take(body_height);
+    for (row, line) in header.iter().chain(visible).enumerate() {
+        queue!(
+            stdout,
+            MoveTo(0, row as u16),
+            Clear(ClearType::CurrentLine),
+            Print(slice_ansi(line, left, width))
+        )?;
+    }
+
+    let shown = header.len() + body.len().saturating_sub(top).min(body_height);
+    for row in shown..header.len() + body_height {
+        queue!(stdout, MoveTo(0, row as u16), Clear(ClearType::CurrentLine))?;
+    }
+
+    let last = (top + body_height).min(body.len());
+    let status = format!(
+        " lines {}-{last} of {}, column {} | q quit, j/k h/l scroll, space/b page, g/G top/bottom",
+        (top + 1).min(last),
+        body.len(),
+        left + 1,
+    );
+    queue!(
+        stdout,
+        MoveTo(0, (header.len() + body_height) as u16),
+        Clear(ClearType::CurrentLine),
+        Print(Style::new().reverse().paint(slice_ansi(&status, 0, width)))
+    )?;
+
+    stdout.flush()
+}
+
+fn is_separator(line: &str) -> bool {
+    let line = strip_ansi_string_likely(line.to_string());
+    !line.chars().any(char::is_alphanumeric)
+        && line
+            .chars()
+            .any(|c| matches!(c, '-' | '=' | '─' | '━' | '═' | '┄' | '┈'))
+}
+
+/// The number of lines at the start of a table, up to and including the line under its header.
+///
+/// Tables without a header (or themes without separators) have none.
+fn header_len(lines: &[&str]) -> usize {
+    // The last line is never the end of a header, as a bottom border looks the same
+    let candidates = lines.len().saturating_sub(1).min(MAX_HEADER_LINES);
+    (1..candidates)
+        .find(|&i| is_separator(lines[i]) && !is_separator(lines[i - 1]))
+        .map_or(0, |i| i + 1)
+}
+
+/// The part of `line` that is `width` columns wide and starts `offset` columns in, keeping its
+/// colors.
+fn slice_ansi(line: &str, offset: usize, width: usize) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut column = 0;
+    let mut chars = line.chars().peekable();
+    let mut has_escapes = false;
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Escape sequences are copied even when scrolled past, so the colors carry over
+            has_escapes = true;
+            out.push(c);
+            if chars.next_if_eq(&'[').is_some() {
+                out.push('[');
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if c.is_control() {
+            continue;
+        }
+        let char_width = c.width().unwrap_or(0);
+        let start = column;
+        column += char_width;
+        if column <= offset {
+            continue;
+        }
+        if column > offset + width {
+            break;
+        }
+        if start < offset {
+            // A wide character cut in half by the left edge
+            out.extend(std::iter::repeat_n(' ', column - offset));
+        } else {
+            out.push(c);
+        }
+    }
+    if has_escapes {
+        out.push_str(RESET);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_header_of_rounded_table() {
+        let lines = [
+            "╭───┬──────╮",
+            "│ # │ name │",
+            "├───┼──────┤",
+            "│ 0 │ foo  │",
+            "╰───┴──────╯",
+        ];
+        assert_eq!(header_len(&lines), 3);
+    }
+
+    #[test]
+    fn finds_header_of_markdown_table() {
+        let lines = ["| a | b |", "| --- | --- |", "| 1 | 2 |"];
+        assert_eq!(header_len(&lines), 2);
+    }
+
+    #[test]
+    fn table_without_header_has_none() {
+        let lines = ["╭───┬─────╮", "│ 0 │ foo │", "│ 1 │ bar │", "╰───┴─────╯"];
+        assert_eq!(header_len(&lines), 0);
+    }
+
+    #[test]
+    fn slices_plain_lines() {
+        assert_eq!(slice_ansi("abcdefgh", 2, 3), "cde");
+        assert_eq!(slice_ansi("abc", 5, 3), "");
+        assert_eq!(slice_ansi("日本語", 1, 4), " 本");
+    }
+
+    #[test]
+    fn slicing_keeps_colors() {
+        let line = "\x1b[31mred\x1b[0m plain";
+        assert_eq!(
+            slice_ansi(line, 1, 4),
+            format!("\x1b[31med\x1b[0m p{RESET}")
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use nu_test_support::nu;
+
+const CITIES: &str = r#"[
+    [region country city population];
+    [eu de berlin 3] [eu fr paris 2] [eu de munich 1] [na us nyc 8]
+]"#;
+
+#[test]
+fn aggregates_every_level_of_a_tree() {
+    let actual = nu!(format!(
+        r#"
+            {CITIES}
+            | group-by region country --into-tree
+            | aggregate {{population: {{ get population | math sum }}}}
+            | each {{ [$in.population ($in.groups | get population)] }}
+            | to nuon --raw
+        "#
+    ));
+    assert_eq!(actual.out, "[[6, [4, 2]], [8, [8]]]");
+}
+
+#[test]
+fn aggregates_a_flat_table() {
+    let actual = nu!(format!(
+        r#"
+            {CITIES}
+            | group-by region --to-table
+            | aggregate {{cities: {{|items| $items | length }}}}
+            | select region cities
+            | to nuon --raw
+        "#
+    ));
+    assert_eq!(actual.out, "[[region, cities]; [eu, 3], [na, 1]]");
+}
+
+#[test]
+fn errors_without_items_or_groups() {
+    let actual = nu!("[[a]; [1]] | aggregate {count: { length }}");
+    assert!(actual.err.contains("cannot find column 'items'"));
+}
@@ -0,0 +1,116 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn chmod_octal() {
+    Playground::setup("chmod_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("script.sh")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod 750 script.sh | ignore; ls -l script.sh | get mode.0"
+        );
+
+        assert_eq!(actual.out, "rwxr-x---");
+    })
+}
+
+#[test]
+fn chmod_symbolic() {
+    Playground::setup("chmod_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("script.sh")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod 644 script.sh | ignore; chmod u+x,go-r script.sh | get mode.0"
+        );
+
+        assert_eq!(actual.out, "rwx------");
+    })
+}
+
+#[test]
+fn chmod_from_ls_record() {
+    Playground::setup("chmod_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("template.sh"), EmptyFile("new.sh")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod rwxr-xr-- template.sh | ignore; chmod (ls -l template.sh | first) new.sh | ignore; ls -l new.sh | get mode.0"
+        );
+
+        assert_eq!(actual.out, "rwxr-xr--");
+    })
+}
+
+#[test]
+fn chmod_piped_paths() {
+    Playground::setup("chmod_test_4", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.sh"), EmptyFile("b.sh"), EmptyFile("c.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod 600 * | ignore; ls *.sh | chmod u+x | ignore; ls -l | sort-by name | get mode | to nuon"
+        );
+
+        assert_eq!(actual.out, "[rwx------, rwx------, rw-------]");
+    })
+}
+
+#[test]
+fn chmod_recursive() {
+    Playground::setup("chmod_test_5", |dirs, sandbox| {
+        sandbox
+            .mkdir("dir")
+            .with_files(&[EmptyFile("dir/a.txt"), EmptyFile("dir/b.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod --recursive u=rwX,go= dir | length"
+        );
+        assert_eq!(actual.out, "3");
+
+        let actual = nu!(cwd: dirs.test(), "ls -l dir | get mode | uniq | to nuon");
+        assert_eq!(actual.out, "[rw-------]");
+    })
+}
+
+#[test]
+fn chmod_mode_that_looks_like_a_flag() {
+    Playground::setup("chmod_test_7", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("script.sh")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod 755 script.sh | ignore; chmod -x script.sh | get mode.0"
+        );
+
+        assert_eq!(actual.out, "rw-r--r--");
+    })
+}
+
+#[test]
+fn chmod_recursive_short_flag() {
+    Playground::setup("chmod_test_8", |dirs, sandbox| {
+        sandbox.mkdir("dir").with_files(&[EmptyFile("dir/a.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "chmod -R 777 dir | ignore; chmod -R go-w dir | get mode | to nuon"
+        );
+
+        assert_eq!(actual.out, "[rwxr-xr-x, rwxr-xr-x]");
+    })
+}
+
+#[test]
+fn chmod_invalid_mode() {
+    Playground::setup("chmod_test_6", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "chmod u*x a.txt");
+
+        assert!(actual.err.contains("Invalid mode"));
+    })
+}
@@ -115,3 +115,19 @@ fn locale_with_different_format_specifiers() {
         "#);
     assert!(actual.out.contains("26-10-23 22:52:14"));
 }
+
+#[test]
+fn format_with_locale_flag_overrides_environment() {
+    let actual = nu!(locale: "en_US", r#"
+        "2021-10-22 20:00:12 +01:00" | format date --locale de-DE "%A %x"
+        "#);
+    assert_eq!(actual.out, "Freitag 22.10.2021");
+}
+
+#[test]
+fn format_with_unknown_locale() {
+    let actual = nu!(r#"
+        "2021-10-22 20:00:12 +01:00" | format date --locale xx-YY "%B"
+        "#);
+    assert!(actual.err.contains("Unknown locale `xx-YY`"));
+}
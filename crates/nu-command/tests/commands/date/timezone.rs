@@ -0,0 +1,66 @@
+use nu_test_support::nu;
+
+#[test]
+fn to_timezone_uses_offset_at_that_date() {
+    let actual = nu!(r#"
+        "2024-01-15 12:00:00 +00:00" | date to-timezone Europe/Berlin | format date '%H:%M %z'
+        "#);
+
+    assert_eq!(actual.out, "13:00 +0100");
+}
+
+#[test]
+fn with_timezone_keeps_wall_clock_time() {
+    let actual = nu!(r#"
+        "2024-07-01 12:00:00 +00:00" | date with-timezone America/New_York | format date '%H:%M %z'
+        "#);
+
+    assert_eq!(actual.out, "12:00 -0400");
+}
+
+#[test]
+fn with_timezone_rejects_skipped_time() {
+    let actual = nu!(r#"
+        "2024-03-31 02:30:00 +00:00" | date with-timezone Europe/Berlin
+        "#);
+
+    assert!(actual.err.contains("doesn't exist"));
+}
+
+#[test]
+fn add_days_across_dst_change_keeps_time_of_day() {
+    let actual = nu!(r#"
+        "2024-10-26 09:00:00 +02:00" | date add 1day -z Europe/Berlin | format date '%F %H:%M %z'
+        "#);
+
+    assert_eq!(actual.out, "2024-10-27 09:00 +0100");
+}
+
+#[test]
+fn add_hours_across_dst_change_is_elapsed_time() {
+    let actual = nu!(r#"
+        "2024-10-27 01:30:00 +02:00" | date add 2hr -z Europe/Berlin | format date '%H:%M %z'
+        "#);
+
+    assert_eq!(actual.out, "02:30 +0100");
+}
+
+#[test]
+fn dates_in_different_timezones_compare_by_instant() {
+    let actual = nu!(r#"
+        let a = "2024-07-01 12:00:00 +00:00" | into datetime
+        let b = $a | date to-timezone Asia/Tokyo
+        [($a == $b) ($a < ($b | date add 1min -z Asia/Tokyo))] | to nuon
+        "#);
+
+    assert_eq!(actual.out, "[true, true]");
+}
+
+#[test]
+fn format_date_in_named_timezone() {
+    let actual = nu!(r#"
+        "2024-01-15 12:00:00 +00:00" | format date -z America/New_York '%H:%M %Z'
+        "#);
+
+    assert_eq!(actual.out, "07:00 EST");
+}
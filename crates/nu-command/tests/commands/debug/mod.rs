@@ -1,3 +1,4 @@
 mod metadata_set;
+mod profile;
 mod timeit;
 mod view_source;
@@ -0,0 +1,37 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn profile_tree_merges_instructions_by_span() -> Result {
+    let code = "
+        debug profile --tree { do { 'spam' } }
+        | get 0.children
+        | where source == do
+        | get 0.children.0.source
+    ";
+    test().run(code).expect_value_eq("'spam'")
+}
+
+#[test]
+fn profile_tree_has_cpu_time() -> Result {
+    let code = "debug profile --tree --duration-values { 1 + 1 } | get 0 | columns | 'cpu' in $in";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn profile_folded_lines_end_in_self_time() -> Result {
+    let code = r#"
+        debug profile --folded { do { 'spam' } }
+        | lines
+        | all { $in =~ '^debug profile.* \d+$' }
+    "#;
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn profile_folded_and_tree_are_incompatible() -> Result {
+    let err = test()
+        .run("debug profile --folded --tree { 1 }")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncompatibleParameters { .. }));
+    Ok(())
+}
@@ -72,3 +72,35 @@ fn timeit_show_output() -> Result {
     let code = "timeit --output { 'this is a test' } | get output";
     test().run(code).expect_value_eq("this is a test")
 }
+
+#[test]
+fn timeit_runs_reports_stats() -> Result {
+    let code = "timeit --runs 5 --warmup 2 { 'x' } | columns";
+    test()
+        .run(code)
+        .expect_value_eq(["runs", "min", "median", "mean", "max", "stddev"])
+}
+
+#[test]
+fn timeit_runs_closure_for_warmup_and_runs() -> Result {
+    Playground::setup("timeit_runs_count", |dirs, _| {
+        let code = "
+            timeit --runs 3 --warmup 2 { 'x' | save --append count.txt }
+            open count.txt | str length
+        ";
+        test().cwd(dirs.test()).run(code).expect_value_eq(5)
+    })
+}
+
+#[test]
+fn timeit_runs_passes_input_to_every_run() -> Result {
+    let code = "[1 2 3] | timeit --runs 3 --output { math sum } | get output";
+    test().run(code).expect_value_eq(6)
+}
+
+#[test]
+fn timeit_runs_must_be_positive() -> Result {
+    let err = test().run("timeit --runs 0 { 'x' }").expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
@@ -199,6 +199,35 @@ fn def_default_value_should_restrict_implicit_type() {
     assert!(actual2.err.contains("expected int"));
 }
 
+#[test]
+fn def_default_value_from_env() {
+    let actual = nu!(r#"
+        def deploy [target: string = $env.DEPLOY_ENV] { $target }
+        $env.DEPLOY_ENV = staging
+        [(deploy) (deploy prod)] | to nuon
+    "#);
+    assert_eq!(actual.out, "[staging, prod]");
+}
+
+#[test]
+fn def_default_value_evaluated_on_each_call() {
+    let actual = nu!(r#"
+        def default-region [] { $env.REGION }
+        def deploy [--region: string = (default-region)] { $region }
+        $env.REGION = eu
+        let first = deploy
+        $env.REGION = us
+        [$first (deploy) (deploy --region ap)] | to nuon
+    "#);
+    assert_eq!(actual.out, "[eu, us, ap]");
+}
+
+#[test]
+fn def_default_value_is_type_checked_on_call() {
+    let actual = nu!("def foo [x: int = $env.FOO] { $x }; $env.FOO = bar; foo");
+    assert!(actual.err.contains("Can't convert to int"));
+}
+
 #[test]
 fn def_wrapped_with_block() {
     let actual = nu!(
@@ -355,3 +384,62 @@ export def recursive [c: int] {
 }"#);
     assert!(actual.err.is_empty());
 }
+
+#[test]
+fn def_memo_reuses_result_for_same_arguments() {
+    let actual = nu!(r#"
+        @memo
+        def lookup [key: string] { random uuid }
+        [((lookup a) == (lookup a)) ((lookup a) == (lookup b))] | to nuon
+    "#);
+    assert_eq!(actual.out, "[true, false]");
+}
+
+#[test]
+fn def_memo_keeps_argument_types_apart() {
+    let actual = nu!(r#"
+        @memo
+        def describe-arg [x: any] { $x | describe }
+        [(describe-arg 1) (describe-arg 1.0)] | to nuon
+    "#);
+    assert_eq!(actual.out, "[int, float]");
+}
+
+#[test]
+fn def_memo_expires_after_ttl() {
+    let actual = nu!(r#"
+        @memo --ttl 0sec
+        def lookup [] { random uuid }
+        (lookup) == (lookup)
+    "#);
+    assert_eq!(actual.out, "false");
+}
+
+#[test]
+fn def_memo_by_input() {
+    let actual = nu!(r#"
+        @memo --input
+        def by-input [] { random uuid }
+        @memo
+        def by-args [] { random uuid }
+        [
+            (('a' | by-input) == ('a' | by-input))
+            (('a' | by-input) == ('b' | by-input))
+            (('a' | by-args) == ('b' | by-args))
+        ] | to nuon
+    "#);
+    assert_eq!(actual.out, "[true, false, true]");
+}
+
+#[test]
+fn def_memo_does_not_cache_errors() {
+    let actual = nu!(r#"
+        @memo
+        def flaky [] { if ($env.FAIL? | default false) { error make { msg: 'failed' } } else { 'ok' } }
+        $env.FAIL = true
+        try { flaky }
+        $env.FAIL = false
+        flaky
+    "#);
+    assert_eq!(actual.out, "ok");
+}
@@ -0,0 +1,93 @@
+use nu_test_support::nu;
+
+#[test]
+fn defer_runs_after_the_rest_of_the_command() {
+    let actual = nu!("def f [] { defer { print 'second' }; print 'first' }; f");
+
+    assert_eq!(actual.out, "firstsecond");
+}
+
+#[test]
+fn defer_runs_in_reverse_order() {
+    let actual = nu!("do { defer { print 1 }; defer { print 2 }; defer { print 3 } }");
+
+    assert_eq!(actual.out, "321");
+}
+
+#[test]
+fn defer_runs_on_error() {
+    let actual = nu!("def f [] { defer { print 'cleanup' }; error make {msg: boom} }; f");
+
+    assert_eq!(actual.out, "cleanup");
+    assert!(actual.err.contains("boom"));
+}
+
+#[test]
+fn defer_runs_on_return() {
+    let actual = nu!("def f [] { defer { print 'cleanup' }; return 3; 4 }; let x = f; $x");
+
+    assert_eq!(actual.out, "cleanup3");
+}
+
+#[test]
+fn defer_runs_on_exit() {
+    let actual = nu!("def f [] { defer { print 'cleanup' }; exit 3 }; f");
+
+    assert_eq!(actual.out, "cleanup");
+    assert_eq!(actual.status.code(), Some(3));
+}
+
+#[test]
+fn defer_waits_for_the_surrounding_command() {
+    let actual = nu!("def f [] { if true { defer { print 'cleanup' } }; print 'after if' }; f");
+
+    assert_eq!(actual.out, "after ifcleanup");
+}
+
+#[test]
+fn defer_collects_the_output_first() {
+    let actual =
+        nu!("def f [] { defer { print 'cleanup' }; 1..3 | each { print $in; $in } }; f | length");
+
+    assert_eq!(actual.out, "123cleanup3");
+}
+
+#[test]
+fn defer_can_restore_the_environment() {
+    let actual = nu!(
+        "def --env f [] { let old = $env.FOO; defer { $env.FOO = $old }; $env.FOO = 'changed' }; $env.FOO = 'original'; f; $env.FOO"
+    );
+
+    assert_eq!(actual.out, "original");
+}
+
+#[test]
+fn defer_error_is_reported() {
+    let actual = nu!("def f [] { defer { error make {msg: 'cleanup failed'} }; 3 }; f");
+
+    assert!(actual.err.contains("cleanup failed"));
+}
+
+#[test]
+fn defer_error_does_not_hide_the_original_error() {
+    let actual =
+        nu!("def f [] { defer { error make {msg: 'cleanup failed'} }; error make {msg: boom} }; f");
+
+    assert!(actual.err.contains("boom"));
+    assert!(!actual.err.contains("cleanup failed"));
+}
+
+#[test]
+fn defer_sees_variables_from_when_it_was_registered() {
+    let actual = nu!("do { mut x = 1; defer { print $x }; $x = 2 }");
+
+    assert_eq!(actual.out, "1");
+}
+
+#[test]
+fn defer_in_recursive_command() {
+    let actual =
+        nu!("def f [n: int] { if $n == 0 { return }; defer { print $n }; f ($n - 1) }; f 3");
+
+    assert_eq!(actual.out, "123");
+}
@@ -1,4 +1,4 @@
-use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
 use nu_test_support::{nu, playground::Playground};
 use rstest::rstest;
 
@@ -130,3 +130,40 @@ fn du_wildcards() {
         assert_eq!(actual.out, "1");
     });
 }
+
+#[test]
+fn du_threshold() {
+    Playground::setup("du_threshold", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("small.txt", "a"),
+            FileWithContent("large.txt", &"a".repeat(4096)),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "du --apparent-size --threshold 1kb | get path | path basename | to nuon",
+        );
+        assert_eq!(actual.out, r#"["large.txt"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "du --apparent-size --threshold -1kb | get path | path basename | to nuon",
+        );
+        assert_eq!(actual.out, r#"["small.txt"]"#);
+    });
+}
+
+#[test]
+fn du_sums_nested_directories() {
+    Playground::setup("du_nested", |dirs, sandbox| {
+        sandbox
+            .mkdir("a/b/c")
+            .with_files(&[FileWithContent("a/b/c/data.txt", &"a".repeat(1000))]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "(du a | get apparent.0) >= 1000b",
+        );
+        assert_eq!(actual.out, "true");
+    });
+}
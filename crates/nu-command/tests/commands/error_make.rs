@@ -141,3 +141,46 @@ fn error_source() {
         }");
     assert!(actual.err.contains("snippet line 1: foo bar"))
 }
+
+#[test]
+fn error_make_data_is_available_in_catch() {
+    let actual = nu!(r#"
+        try {
+            error make {msg: "user not found" code: "users::not_found" data: {id: 42}}
+        } catch {|err| $"($err.code) ($err.data.id)" }
+    "#);
+
+    assert_eq!(actual.out, "users::not_found 42");
+}
+
+#[test]
+fn error_make_inner_flag_is_the_cause() {
+    let actual = nu!(r#"
+        try {
+            try { 1 / 0 } catch {|err| error make --inner $err {msg: "calculation failed"} }
+        } catch {|err| $err.raw }
+    "#);
+
+    assert!(actual.err.contains("calculation failed"));
+    assert!(actual.err.contains("Division by zero"));
+}
+
+#[test]
+fn error_make_inner_flag_keeps_the_cause() {
+    let actual = nu!(r#"
+        try {
+            try {
+                error make {msg: inner code: "lib::inner" data: 1}
+            } catch {|err| error make --inner $err "outer" }
+        } catch {|err| $err.json | from json | get inner.0.code }
+    "#);
+
+    assert_eq!(actual.out, "lib::inner");
+}
+
+#[test]
+fn error_make_inner_flag_needs_an_error() {
+    let actual = nu!("error make --inner 5 'outer'");
+
+    assert!(actual.err.contains("nu::shell::cant_convert"));
+}
@@ -0,0 +1,51 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn runs_closure_with_input() {
+    Playground::setup("flock_test_1", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "[1 2 3] | flock state.lock { math sum }"
+        );
+
+        assert_eq!(actual.out, "6");
+        assert!(dirs.test().join("state.lock").exists());
+    })
+}
+
+#[test]
+fn times_out_while_locked() {
+    Playground::setup("flock_test_2", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "flock state.lock { flock --timeout 100ms state.lock { 'inner' } }"
+        );
+
+        assert!(actual.err.contains("Timed out waiting for the lock"));
+    })
+}
+
+#[test]
+fn shared_locks_can_be_held_together() {
+    Playground::setup("flock_test_3", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "flock --shared state.lock { flock --shared --timeout 100ms state.lock { 'inner' } }"
+        );
+
+        assert_eq!(actual.out, "inner");
+    })
+}
+
+#[test]
+fn releases_lock_after_error() {
+    Playground::setup("flock_test_4", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "try { flock state.lock { error make { msg: 'failed' } } }; flock --timeout 100ms state.lock { 'again' }"
+        );
+
+        assert_eq!(actual.out, "again");
+    })
+}
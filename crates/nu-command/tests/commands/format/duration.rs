@@ -13,3 +13,40 @@ fn format_duration_with_invalid_unit() -> Result {
     assert!(matches!(err, ShellError::InvalidUnit { .. }));
     Ok(())
 }
+
+#[test]
+fn format_duration_human_style() -> Result {
+    let code = "1day + 1sec + 2ms | format duration --style human";
+    test()
+        .run(code)
+        .expect_value_eq("1 day, 1 second, 2 milliseconds")
+}
+
+#[test]
+fn format_duration_compact_style_of_negative_duration() -> Result {
+    let code = "-90sec | format duration --style compact";
+    test().run(code).expect_value_eq("-1m30s")
+}
+
+#[test]
+fn format_duration_clock_style_with_fraction() -> Result {
+    let code = "3723sec + 250ms | format duration --style clock";
+    test().run(code).expect_value_eq("01:02:03.25")
+}
+
+#[test]
+fn format_duration_styles_round_trip() -> Result {
+    let code = "
+        let d = 2day + 3hr + 4min + 5sec + 6ms + 7us + 8ns
+        [human compact] | all {|style| ($d | format duration --style $style | into duration) == $d }
+    ";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn format_duration_with_invalid_style() -> Result {
+    let code = "1hr | format duration --style long";
+    let err = test().run(code).expect_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
@@ -154,3 +154,50 @@ fn generate_with_input_is_streaming() {
     assert_eq!(actual.out, "[1, 3, 6, 10, 15]");
     assert_eq!(actual.err, "12345");
 }
+
+#[test]
+fn generate_items_outputs_several_values() {
+    let actual = nu!(r#"
+    generate {|x|
+      if $x < 3 {
+        {out: $x, items: [($x * 10), ($x * 100)], next: ($x + 1)}
+      }
+    } 1 | to nuon
+          "#);
+
+    assert_eq!(actual.out, "[1, 10, 100, 2, 20, 200]");
+}
+
+#[test]
+fn generate_done_stops_generation() {
+    let actual = nu!("generate {|x| {out: $x, next: ($x + 1), done: ($x == 2)}} 0 | to nuon");
+
+    assert_eq!(actual.out, "[0, 1, 2]");
+}
+
+#[test]
+fn generate_disallows_non_list_items() {
+    let actual = nu!("generate {|x| {items: $x}} 0");
+    assert!(actual.err.contains("Expected list for 'items'"));
+}
+
+#[test]
+fn generate_disallows_non_bool_done() {
+    let actual = nu!("generate {|x| {out: $x, done: yes}} 0");
+    assert!(actual.err.contains("Expected bool for 'done'"));
+}
+
+#[test]
+fn generate_is_lazy_with_take_while() {
+    let actual = nu!(r#"
+    generate {|page|
+      print -en $page
+      {items: [($page * 2), ($page * 2 + 1)], next: ($page + 1)}
+    } 0
+    | take while {|x| $x < 3}
+    | to nuon
+    "#);
+
+    assert_eq!(actual.out, "[0, 1, 2]");
+    assert_eq!(actual.err, "01");
+}
@@ -1,4 +1,4 @@
-use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
 use nu_test_support::nu;
 use nu_test_support::playground::Playground;
 use rstest::rstest;
@@ -213,3 +213,50 @@ fn glob_follow_symlinks() {
         );
     })
 }
+
+#[test]
+fn glob_respects_gitignore() {
+    Playground::setup("glob_gitignore", |dirs, sandbox| {
+        sandbox
+            .with_files(&[
+                FileWithContent(".gitignore", "target/\n*.log\n!keep.log\n"),
+                EmptyFile("notes.txt"),
+                EmptyFile("debug.log"),
+                EmptyFile("keep.log"),
+            ])
+            .mkdir("src")
+            .with_files(&[EmptyFile("src/main.rs")])
+            .mkdir("target")
+            .with_files(&[EmptyFile("target/build.rs")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "glob **/*.{rs,log,txt} --gitignore | path basename | sort | to nuon"
+        );
+        assert_eq!(actual.out, r#"[keep.log, main.rs, notes.txt]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "glob **/*.{rs,log,txt} | length"
+        );
+        assert_eq!(actual.out, "5");
+    })
+}
+
+#[test]
+fn glob_gitignore_in_subdirectory() {
+    Playground::setup("glob_gitignore_nested", |dirs, sandbox| {
+        sandbox.mkdir("src").with_files(&[
+            FileWithContent("src/.ignore", "generated.rs\n"),
+            EmptyFile("src/main.rs"),
+            EmptyFile("src/generated.rs"),
+            EmptyFile("generated.rs"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "glob **/*.rs --gitignore | path relative-to $env.PWD | sort | str replace --all '\\' '/' | to nuon"
+        );
+        assert_eq!(actual.out, r#"[generated.rs, src/main.rs]"#);
+    })
+}
@@ -90,3 +90,35 @@ fn optional_cell_path_works() {
     let expected = r#"{"123": [[foo]; [123]], "234": [[foo]; [234]]}"#;
     assert_eq!(actual.out, expected)
 }
+
+#[test]
+fn group_by_into_tree_nests_the_groups() {
+    let actual = nu!(r#"
+        [[region country city]; [eu de berlin] [eu fr paris] [eu de munich] [na us nyc]]
+        | group-by region country --into-tree
+        | update groups { update items { get city } }
+        | to nuon --raw
+    "#);
+    let expected = "[{region: eu, groups: [{country: de, items: [berlin, munich]}, {country: fr, items: [paris]}]}, {region: na, groups: [{country: us, items: [nyc]}]}]";
+    assert_eq!(actual.out, expected);
+}
+
+#[test]
+fn group_by_into_tree_with_one_grouper_is_a_table() {
+    let actual = nu!("[1 2 3] | group-by { $in mod 2 } --into-tree | to nuon --raw");
+    let expected = r#"[[closure_0, items]; ["1", [1, 3]], ["0", [2]]]"#;
+    assert_eq!(actual.out, expected);
+}
+
+#[test]
+fn group_by_into_tree_on_empty_list_returns_empty_list() {
+    let actual = nu!("[[a b]; [1 2]] | where false | group-by --into-tree a | to nuon --raw");
+    assert!(actual.err.is_empty());
+    assert_eq!(actual.out, "[]");
+}
+
+#[test]
+fn group_by_into_tree_and_to_table_are_incompatible() {
+    let actual = nu!("[[a b]; [1 2]] | group-by --into-tree --to-table a");
+    assert!(actual.err.contains("can't be used with --to-table"));
+}
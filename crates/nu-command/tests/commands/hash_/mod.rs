@@ -74,3 +74,51 @@ fn sha256_works_with_file() {
         "2f5050e7eea415c1f3d80b5d93355efd15043ec9157a2bb167a9e73f2ae651f2"
     );
 }
+
+#[test]
+fn blake3_works_with_file() {
+    let actual = nu!(cwd: "tests/fixtures/formats", r#"
+    open sample.db --raw | hash blake3
+    "#);
+
+    assert_eq!(
+        actual.out,
+        "d3abe921025fc42be9fca145087de0f26cc77ebe19ef920ae1299d733097902b"
+    );
+}
+
+#[test]
+fn crc32_works_with_file() {
+    let actual = nu!(cwd: "tests/fixtures/formats", r#"
+    open sample.db --raw | hash crc32
+    "#);
+
+    assert_eq!(actual.out, "c39e4895");
+}
+
+#[test]
+fn xxh64_works_with_file() {
+    let actual = nu!(cwd: "tests/fixtures/formats", r#"
+    open sample.db --raw | hash xxh64
+    "#);
+
+    assert_eq!(actual.out, "02e84d01eb71a509");
+}
+
+#[test]
+fn xxh3_works_with_file() {
+    let actual = nu!(cwd: "tests/fixtures/formats", r#"
+    open sample.db --raw | hash xxh3
+    "#);
+
+    assert_eq!(actual.out, "5693ea27a8d971ba");
+}
+
+#[test]
+fn crc32_of_stream_matches_collected_input() {
+    let actual = nu!(cwd: "tests/fixtures/formats", r#"
+    (open sample.db --raw | hash crc32 --binary) == (open sample.db --raw | collect | hash crc32 --binary)
+    "#);
+
+    assert_eq!(actual.out, "true");
+}
@@ -98,3 +98,68 @@ fn count_with_normalize_percentage() {
 
     assert_eq!(actual.out, bit_json);
 }
+
+#[test]
+fn bins_of_the_same_width() {
+    let actual = nu!("
+        [0 1 2 5 9 10 11 -1]
+        | histogram --bins 2 --range 0..10
+        | select start end count
+        | to nuon
+    ");
+
+    assert_eq!(
+        actual.out,
+        "[[start, end, count]; [0.0, 5.0, 3], [5.0, 10.0, 3]]"
+    );
+}
+
+#[test]
+fn bins_default_to_the_range_of_the_numbers() {
+    let actual = nu!("[1 2 3 4 5] | histogram --bins 4 | get count | to nuon");
+
+    assert_eq!(actual.out, "[1, 1, 1, 2]");
+}
+
+#[test]
+fn bins_skip_numbers_that_are_not_finite() {
+    let actual = nu!("[1 NaN 2 inf 3 -inf] | histogram --bins 2 | get count | to nuon");
+
+    assert_eq!(actual.out, "[1, 2]");
+}
+
+#[test]
+fn bins_with_explicit_edges() {
+    let actual = nu!("
+        [[size]; [5] [50] [500] [5000]]
+        | histogram size --bins [0 10 100 1000]
+        | select start end count quantile
+        | to nuon
+    ");
+
+    assert_eq!(
+        actual.out,
+        "[[start, end, count, quantile]; [0, 10, 1, 0.3333333333333333], [10, 100, 1, 0.3333333333333333], [100, 1000, 1, 0.3333333333333333]]"
+    );
+}
+
+#[test]
+fn range_alone_makes_ten_bins() {
+    let actual = nu!("[1 2 3] | histogram --range 0..100 | length");
+
+    assert_eq!(actual.out, "10");
+}
+
+#[test]
+fn bins_need_numbers() {
+    let actual = nu!("[a b] | histogram --bins 2");
+
+    assert!(actual.err.contains("Only numbers can be counted in bins"));
+}
+
+#[test]
+fn bin_edges_must_increase() {
+    let actual = nu!("[1 2] | histogram --bins [0 10 5]");
+
+    assert!(actual.err.contains("at least 2 increasing numbers"));
+}
@@ -160,3 +160,30 @@ fn test_q_format_specifier_time_only() {
     // Check for the time components - should parse as time with default date
     assert!(actual.out.contains("20:00:12"));
 }
+
+#[test]
+fn into_datetime_with_locale_month_names() {
+    let actual = nu!(r#"
+        "22. Oktober 2021 20:00 +0100" | into datetime --locale de_DE | format date "%F %R"
+        "#);
+
+    assert_eq!(actual.out, "2021-10-22 20:00");
+}
+
+#[test]
+fn into_datetime_with_locale_day_first() {
+    let actual = nu!(r#"
+        "03/02/2021 +0000" | into datetime --locale fr-FR | format date "%F"
+        "#);
+
+    assert_eq!(actual.out, "2021-02-03");
+}
+
+#[test]
+fn into_datetime_with_locale_and_format() {
+    let actual = nu!(r#"
+        "mercredi 3 févr. 2021" | into datetime --locale fr_FR --format "%A %d %b %Y" | format date "%F"
+        "#);
+
+    assert_eq!(actual.out, "2021-02-03");
+}
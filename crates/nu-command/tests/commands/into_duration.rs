@@ -124,3 +124,31 @@ fn into_duration_from_record_incompatible_with_unit_flag() {
 
     assert!(actual.err.contains("nu::shell::incompatible_parameters"));
 }
+
+#[test]
+fn into_duration_from_compact_string() {
+    let actual = nu!(r#"'1h30m' | into duration"#);
+
+    assert_eq!("1hr 30min", actual.out);
+}
+
+#[test]
+fn into_duration_from_spelled_out_string() {
+    let actual = nu!(r#"'2 days, 4 hours' | into duration"#);
+
+    assert_eq!("2day 4hr", actual.out);
+}
+
+#[test]
+fn into_duration_from_iso8601_string() {
+    let actual = nu!(r#"'P1DT2H30M0.5S' | into duration"#);
+
+    assert_eq!("1day 2hr 30min 500ms", actual.out);
+}
+
+#[test]
+fn into_duration_from_iso8601_string_with_months() {
+    let actual = nu!(r#"'P1M' | into duration"#);
+
+    assert!(actual.err.contains("nu::shell::invalid_unit"));
+}
@@ -410,3 +410,70 @@ fn test_alternative_table_syntax() {
         assert_eq!(actual, expected, "Expression was {}", &expr);
     }
 }
+
+#[test]
+fn semi_and_anti_joins() {
+    let left = "[{a: 1 b: 1} {a: 2 b: 2} {a: 1 b: 3} {b: 4}]";
+    let right = "[{a: 1 c: 1} {a: 1 c: 2} {a: 3 c: 3}]";
+    for (join_type, expected) in [
+        ("--semi", "[[a, b]; [1, 1], [1, 3]]"),
+        ("--anti", "[{a: 2, b: 2}, {b: 4}]"),
+    ] {
+        let actual = nu!(format!("{left} | join {right} {join_type} a | to nuon")).out;
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn joins_on_several_columns() {
+    let left = "[{x: 1 y: 1 a: 1} {x: 1 y: 2 a: 2} {x: 2 y: 1 a: 3}]";
+    let right = "[{u: 1 y: 2 b: 4} {u: 2 y: 1 b: 5} {u: 2 y: 2 b: 6}]";
+    for (join_type, expected) in [
+        (
+            "--inner",
+            "[[x, y, a, u, b]; [1, 2, 2, 1, 4], [2, 1, 3, 2, 5]]",
+        ),
+        (
+            "--outer",
+            "[[x, y, a, u, b]; [1, 1, 1, null, null], [1, 2, 2, 1, 4], [2, 1, 3, 2, 5], [null, 2, null, 2, 6]]",
+        ),
+    ] {
+        let actual = nu!(format!(
+            "{left} | join {right} {join_type} [x y] [u y] | to nuon"
+        ))
+        .out;
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn errors_on_different_numbers_of_join_columns() {
+    let actual = nu!("[{a: 1 b: 1}] | join [{a: 1 b: 1}] [a b] [a]");
+    assert!(actual.err.contains("on 1 columns"));
+}
+
+#[test]
+fn errors_on_several_join_types() {
+    let actual = nu!("[{a: 1}] | join [{a: 1}] --semi --anti a");
+    assert!(actual.err.contains("Choose one of"));
+}
+
+#[test]
+fn streams_the_input_table() {
+    let actual = nu!(r#"
+        1.. | each { {id: ($in mod 3) n: $in} }
+        | join [{id: 0 name: zero} {id: 2 name: two}] id
+        | first 4
+        | get n
+        | to nuon
+    "#);
+    assert_eq!(actual.out, "[2, 3, 5, 6]");
+}
+
+#[test]
+fn joins_list_values_and_streams_alike() {
+    let value = nu!("[{a: 1 b: 2}] | join [{a: 1 c: 3}] a | to nuon");
+    let stream = nu!("[{a: 1 b: 2}] | each { $in } | join [{a: 1 c: 3}] a | to nuon");
+    assert_eq!(value.out, "[[a, b, c]; [1, 2, 3]]");
+    assert_eq!(stream.out, value.out);
+}
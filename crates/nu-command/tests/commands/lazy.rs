@@ -0,0 +1,36 @@
+use nu_test_support::nu;
+
+#[test]
+fn lazy_does_not_run_the_closure_until_read() {
+    let actual = nu!("lazy { error make {msg: boom} } | describe --no-collect");
+
+    assert_eq!(actual.out, "stream");
+}
+
+#[test]
+fn lazy_runs_the_closure_once_read() {
+    let actual = nu!("lazy { error make {msg: boom} } | collect");
+
+    assert!(actual.err.contains("boom"));
+}
+
+#[test]
+fn lazy_streams_the_closure_output() {
+    let actual = nu!("lazy { 1..5 | each { $in * 2 } } | first 2 | to json --raw");
+
+    assert_eq!(actual.out, "[2,4]");
+}
+
+#[test]
+fn lazy_passes_the_input_to_the_closure() {
+    let actual = nu!("[3 1 2] | lazy { sort } | to json --raw");
+
+    assert_eq!(actual.out, "[1,2,3]");
+}
+
+#[test]
+fn lazy_in_custom_command() {
+    let actual = nu!("def evens [] { lazy { 1..10 | where $it mod 2 == 0 } }; evens | math sum");
+
+    assert_eq!(actual.out, "30");
+}
@@ -255,3 +255,31 @@ fn let_var_at_beginning_error() {
         actual.err.contains("doesn't support") || actual.err.contains("invalid `let` keyword call")
     );
 }
+
+#[test]
+fn let_destructures_record() {
+    let actual = nu!("let {name, version} = {name: nu, version: 1}; $'($name) ($version)'");
+    assert_eq!(actual.out, "nu 1");
+}
+
+#[test]
+fn let_destructures_nested_record_and_list() {
+    let actual = nu!(
+        "let {package: {name: $pkg}, authors: [first, second]} = {package: {name: nu}, authors: [a b]}; $'($pkg) ($first) ($second)'"
+    );
+    assert_eq!(actual.out, "nu a b");
+}
+
+#[test]
+fn let_destructure_missing_field_errors() {
+    let actual = nu!("let {name, version} = {name: nu}");
+    assert!(actual.err.contains("version"));
+}
+
+#[test]
+fn closure_parameter_destructures_record() {
+    let actual = nu!(
+        "[{name: a, size: 1} {name: b, size: 2}] | each {|{name, size}| $'($name)($size)' } | str join ' '"
+    );
+    assert_eq!(actual.out, "a1 b2");
+}
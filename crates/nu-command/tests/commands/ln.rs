@@ -0,0 +1,118 @@
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn creates_hard_link() {
+    Playground::setup("ln_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("notes.txt", "hello")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ln notes.txt backup.txt; open backup.txt"
+        );
+
+        assert_eq!(actual.out, "hello");
+        assert!(!dirs.test().join("backup.txt").is_symlink());
+    })
+}
+
+#[test]
+fn creates_relative_symlink() {
+    Playground::setup("ln_test_2", |dirs, sandbox| {
+        sandbox
+            .mkdir("dotfiles")
+            .mkdir("config")
+            .with_files(&[EmptyFile("dotfiles/config.nu")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ln --symbolic --relative dotfiles/config.nu config/config.nu; ls -l config | get target.0"
+        );
+
+        assert_eq!(actual.out, "../dotfiles/config.nu");
+    })
+}
+
+#[test]
+fn relative_requires_symbolic() {
+    Playground::setup("ln_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "ln --relative notes.txt backup.txt");
+
+        assert!(actual.err.contains("Only symbolic links can be relative"));
+    })
+}
+
+#[test]
+fn links_into_existing_directory() {
+    Playground::setup("ln_test_4", |dirs, sandbox| {
+        sandbox.mkdir("bin").with_files(&[EmptyFile("app")]);
+
+        nu!(cwd: dirs.test(), "ln --symbolic app bin");
+
+        assert!(dirs.test().join("bin").join("app").is_symlink());
+    })
+}
+
+#[test]
+fn force_replaces_existing_link() {
+    Playground::setup("ln_test_5", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("old.txt", "old"),
+            FileWithContent("new.txt", "new"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ln --symbolic old.txt current.txt; ln --symbolic --force new.txt current.txt; open current.txt"
+        );
+
+        assert_eq!(actual.out, "new");
+    })
+}
+
+#[test]
+fn fails_without_force_if_link_exists() {
+    Playground::setup("ln_test_6", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("old.txt"), EmptyFile("current.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "ln --symbolic old.txt current.txt");
+
+        assert!(!actual.err.is_empty());
+        assert!(!dirs.test().join("current.txt").is_symlink());
+    })
+}
+
+#[test]
+fn creates_links_from_table() {
+    Playground::setup("ln_test_7", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt"), EmptyFile("b.txt")]);
+
+        nu!(
+            cwd: dirs.test(),
+            "[[target link]; [a.txt link-a.txt] [b.txt link-b.txt]] | ln --symbolic"
+        );
+
+        assert!(dirs.test().join("link-a.txt").is_symlink());
+        assert!(dirs.test().join("link-b.txt").is_symlink());
+    })
+}
+
+#[test]
+fn force_does_not_link_file_to_itself() {
+    Playground::setup("ln_test_8", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("notes.txt", "hello")]);
+
+        let actual = nu!(cwd: dirs.test(), "ln -f notes.txt notes.txt");
+        assert!(actual.err.contains("Can't link a file to itself"));
+
+        let actual = nu!(cwd: dirs.test(), "ln -f notes.txt .");
+        assert!(actual.err.contains("Can't link a file to itself"));
+
+        let contents =
+            std::fs::read_to_string(dirs.test().join("notes.txt")).expect("notes.txt is kept");
+        assert_eq!(contents, "hello");
+    })
+}
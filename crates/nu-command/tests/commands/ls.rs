@@ -1,4 +1,4 @@
-use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
 use nu_test_support::nu;
 use nu_test_support::playground::Playground;
 
@@ -450,6 +450,8 @@ fn list_all_columns() {
                     "user",
                     "group",
                     "size",
+                    "allocated",
+                    "sparse",
                     "created",
                     "accessed",
                     "modified",
@@ -460,7 +462,16 @@ fn list_all_columns() {
             #[cfg(windows)]
             {
                 [
-                    "name", "type", "target", "readonly", "size", "created", "accessed", "modified",
+                    "name",
+                    "type",
+                    "target",
+                    "readonly",
+                    "size",
+                    "allocated",
+                    "sparse",
+                    "created",
+                    "accessed",
+                    "modified",
                 ]
                 .join("")
             }
@@ -802,3 +813,78 @@ fn consistent_list_order() {
         assert_eq!(no_arg.out, with_arg.out);
     })
 }
+
+#[cfg(unix)]
+#[test]
+fn list_extended_columns() {
+    Playground::setup("ls_test_extended", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("los.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --extended | columns | where $it in [xattrs acl immutable append_only] | length"
+        );
+
+        assert_eq!(actual.out, "4");
+    })
+}
+
+#[test]
+fn list_streaming() {
+    Playground::setup("ls_test_stream", |dirs, sandbox| {
+        sandbox.mkdir("sub").with_files(&[
+            EmptyFile("a.txt"),
+            EmptyFile("b.txt"),
+            EmptyFile(".hidden"),
+            EmptyFile("sub/c.txt"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --stream | get name | sort | to nuon"
+        );
+        assert_eq!(actual.out, r#"["a.txt", "b.txt", sub]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --stream --all | length"
+        );
+        assert_eq!(actual.out, "4");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --stream sub | get name | path split | get 0 | to nuon"
+        );
+        assert_eq!(actual.out, r#"[sub, "c.txt"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --stream | first | columns | first"
+        );
+        assert_eq!(actual.out, "name");
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn list_sparse_files() {
+    Playground::setup("ls_test_sparse", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("dense.txt", &"a".repeat(64 * 1024))]);
+        // a file with only a hole, that has no space allocated for its contents
+        std::fs::File::create(dirs.test().join("sparse.img"))
+            .and_then(|file| file.set_len(64 * 1024 * 1024))
+            .expect("can create sparse file");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls -l sparse.img | get 0 | [$in.sparse ($in.allocated < $in.size)] | to nuon"
+        );
+        assert_eq!(actual.out, "[true, true]");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls -l dense.txt | get sparse.0"
+        );
+        assert_eq!(actual.out, "false");
+    })
+}
@@ -7,8 +7,10 @@ mod max;
 mod median;
 mod min;
 mod mode;
+mod percentile;
 mod product;
 mod round;
+mod skew;
 mod sqrt;
 mod stddev;
 mod sum;
@@ -370,6 +372,72 @@ fn duration_math_with_negative() -> Result {
         .expect_value_eq(Value::test_duration(-518_400_000_000_000))
 }
 
+#[test]
+fn bit_filesize() -> Result {
+    test()
+        .run("100Mbit")
+        .expect_value_eq(Value::test_filesize(12_500_000))
+}
+
+#[test]
+fn bit_filesize_has_to_be_whole_bytes() -> Result {
+    let err = test().run("7bit").expect_parse_error()?;
+    assert!(matches!(err, ParseError::LabeledErrorWithHelp { .. }));
+    Ok(())
+}
+
+#[test]
+fn rate_literal_that_rounds_to_zero_is_an_error() -> Result {
+    let err = test().run("1B/wk").expect_parse_error()?;
+    assert!(matches!(err, ParseError::LabeledErrorWithHelp { .. }));
+    Ok(())
+}
+
+#[test]
+fn zero_rate_literal() -> Result {
+    test().run("0bit/sec").expect_value_eq(Value::test_rate(0))
+}
+
+#[test]
+fn rate_literal() -> Result {
+    test()
+        .run("12.5MB/sec == 100Mbit/sec")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn filesize_divided_by_duration_is_rate() -> Result {
+    test()
+        .run("10MB / 2sec")
+        .expect_value_eq(Value::test_rate(40_000_000))
+}
+
+#[test]
+fn rate_times_duration_is_filesize() -> Result {
+    test()
+        .run("100Mbit/sec * 1min")
+        .expect_value_eq(Value::test_filesize(750_000_000))
+}
+
+#[test]
+fn filesize_divided_by_rate_is_duration() -> Result {
+    test()
+        .run("1GB / 100Mbit/sec")
+        .expect_value_eq(Value::test_duration(80_000_000_000))
+}
+
+#[test]
+fn rate_to_string_uses_the_literal_unit() -> Result {
+    test()
+        .run("100Mbit/sec | into string")
+        .expect_value_eq("12.5 MB/sec")
+}
+
+#[test]
+fn rate_describe() -> Result {
+    test().run("1kbit/ms | describe").expect_value_eq("rate")
+}
+
 #[test]
 fn compound_comparison() -> Result {
     let code = r#"
@@ -0,0 +1,38 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn percentile_interpolates_between_values() -> Result {
+    test()
+        .run("[1 2 3 4] | math percentile 50")
+        .expect_value_eq(2.5)
+}
+
+#[test]
+fn percentile_of_unsorted_values() -> Result {
+    test()
+        .run("[5 1 4 2 3] | math percentile 100")
+        .expect_value_eq(5)
+}
+
+#[test]
+fn percentile_zero_is_the_minimum() -> Result {
+    test()
+        .run("[5 1 4 2 3] | math percentile 0")
+        .expect_value_eq(1)
+}
+
+#[test]
+fn percentile_of_a_stream() -> Result {
+    test()
+        .run("1..100 | each { $in } | math percentile 95")
+        .expect_value_eq(95.05)
+}
+
+#[test]
+fn percentile_out_of_range() -> Result {
+    let err = test()
+        .run("[1 2 3] | math percentile 101")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn skew_of_symmetric_values_is_zero() -> Result {
+    test().run("[1 2 3 4 5] | math skew").expect_value_eq(0.0)
+}
+
+#[test]
+fn skew_to_the_left_is_negative() -> Result {
+    test()
+        .run("[-10 1 2 3] | math skew | $in < 0")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn skew_of_a_stream() -> Result {
+    test()
+        .run("[1 2 3 10] | each { $in } | math skew")
+        .expect_value_eq(1.0182337649086284)
+}
+
+#[test]
+fn sample_skew_needs_three_values() -> Result {
+    let err = test()
+        .run("[1 2] | math skew --sample")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::DivisionByZero { .. }));
+    Ok(())
+}
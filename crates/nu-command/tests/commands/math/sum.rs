@@ -89,3 +89,17 @@ fn cannot_sum_infinite_range() -> Result {
     assert!(matches!(outcome, ShellError::IncorrectValue { .. }));
     Ok(())
 }
+
+#[test]
+fn sum_of_a_stream() -> Result {
+    test()
+        .run("1..1000 | each { $in } | math sum")
+        .expect_value_eq(500500)
+}
+
+#[test]
+fn sum_of_a_table_stream() -> Result {
+    test()
+        .run("[[a b]; [1 2] [3 4]] | each { $in } | math sum | $in.a + $in.b")
+        .expect_value_eq(10)
+}
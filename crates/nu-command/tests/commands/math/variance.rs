@@ -20,3 +20,11 @@ fn cannot_variance_infinite_range() -> Result {
     assert!(matches!(outcome, ShellError::IncorrectValue { .. }));
     Ok(())
 }
+
+#[test]
+fn variance_of_a_stream() -> Result {
+    let expected: Value = test().run("[1 2 3 4 5 6] | math variance")?;
+    test()
+        .run("1..6 | each { $in } | math variance")
+        .expect_value_eq(expected)
+}
@@ -55,3 +55,36 @@ fn doesnt_create_temp_file() {
         assert!(!loc.exists());
     })
 }
+
+#[test]
+fn scoped_removes_directory() {
+    Playground::setup("mktemp_test_scoped_1", |dirs, _| {
+        let output = nu!(
+            cwd: dirs.test(),
+            "let out = mktemp --directory --scoped {|dir| 'data' | save ($dir | path join file.txt); $dir }; [$out ($out | path exists)] | to nuon"
+        );
+        assert!(output.out.ends_with(",false]"), "{}", output.out);
+    })
+}
+
+#[test]
+fn scoped_returns_closure_output() {
+    Playground::setup("mktemp_test_scoped_2", |dirs, _| {
+        let output = nu!(
+            cwd: dirs.test(),
+            "mktemp --scoped {|file| 'hello' | save --force $file; open $file }"
+        );
+        assert_eq!(output.out, "hello");
+    })
+}
+
+#[test]
+fn scoped_removes_file_on_error() {
+    Playground::setup("mktemp_test_scoped_3", |dirs, _| {
+        let output = nu!(
+            cwd: dirs.test(),
+            "try { mktemp --scoped {|file| $file | save path.txt; error make { msg: 'failed' } } }; open path.txt | path exists"
+        );
+        assert_eq!(output.out, "false");
+    })
+}
@@ -1,3 +1,4 @@
+mod aggregate;
 mod alias;
 mod all;
 mod any;
@@ -8,6 +9,8 @@ mod break_;
 mod bytes;
 mod cal;
 mod cd;
+#[cfg(unix)]
+mod chmod;
 mod chunk_by;
 mod chunks;
 mod compact;
@@ -22,6 +25,7 @@ mod date;
 mod debug_info;
 mod def;
 mod default;
+mod defer;
 mod detect_columns;
 mod do_;
 mod drop;
@@ -39,6 +43,7 @@ mod filter;
 mod find;
 mod first;
 mod flatten;
+mod flock;
 mod for_;
 mod format;
 mod generate;
@@ -60,9 +65,12 @@ mod into_filesize;
 mod into_int;
 mod join;
 mod last;
+mod lazy;
 mod length;
 mod let_;
 mod lines;
+#[cfg(unix)]
+mod ln;
 mod loop_;
 mod ls;
 mod match_;
@@ -78,6 +86,7 @@ mod open;
 mod par_each;
 mod parse;
 mod path;
+mod pivot;
 mod platform;
 mod prepend;
 mod print;
@@ -92,7 +101,9 @@ mod return_;
 mod reverse;
 mod rm;
 mod roll;
+mod rolling;
 mod rotate;
+mod run_command;
 mod run_external;
 mod save;
 mod select;
@@ -130,14 +141,18 @@ mod umkdir;
 mod uname;
 mod uniq;
 mod uniq_by;
+mod unpivot;
 mod update;
 mod upsert;
 mod url;
 mod use_;
 mod utouch;
+mod uuid;
 mod where_;
 mod which;
 mod while_;
 mod with_env;
 mod wrap;
+#[cfg(target_os = "linux")]
+mod xattr;
 mod zip;
@@ -718,3 +718,38 @@ fn mv_with_tilde() {
         assert!(files_exist_at(&["f1.txt"], dirs.test()));
     })
 }
+
+#[test]
+fn keeps_numbered_backups() {
+    Playground::setup("umv_test_backup", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("config.toml", "old"),
+            FileWithContent("new.toml", "new"),
+            FileWithContent("newer.toml", "newer"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "mv --backup new.toml config.toml; mv --backup newer.toml config.toml"
+        );
+        assert!(actual.err.is_empty());
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "[config.toml config.toml.~1~ config.toml.~2~] | each { open --raw } | to nuon"
+        );
+        assert_eq!(actual.out, "[newer, old, new]");
+    })
+}
+
+#[test]
+fn several_sources_need_a_directory_target() {
+    Playground::setup("umv_test_several_sources", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("a.txt", "a"), FileWithContent("b.txt", "b")]);
+
+        let actual = nu!(cwd: dirs.test(), "mv a.txt b.txt newname");
+        assert!(actual.err.contains("Target is not a directory"));
+        assert!(files_exist_at(&["a.txt", "b.txt"], dirs.test()));
+        assert!(!dirs.test().join("newname").exists());
+    })
+}
@@ -0,0 +1,102 @@
+use nu_test_support::nu;
+use std::{net::UdpSocket, thread};
+
+/// Answers a single query with the given answer records, whose names point back at the question.
+fn fake_dns_server(rcode: u8, answers: Vec<Vec<u8>>) -> (u16, thread::JoinHandle<Vec<u8>>) {
+    let socket = UdpSocket::bind("127.0.0.1:0").expect("should bind");
+    let port = socket.local_addr().expect("should have address").port();
+
+    let handle = thread::spawn(move || {
+        let mut buf = [0; 512];
+        let (len, peer) = socket.recv_from(&mut buf).expect("should receive query");
+        let query = buf[..len].to_vec();
+
+        // the question ends 4 bytes after the terminating zero of its name
+        let question_end = 12 + query[12..].iter().position(|b| *b == 0).expect("name") + 5;
+        let mut response = query[..2].to_vec();
+        response.extend_from_slice(&[0x81, 0x80 | rcode, 0, 1, 0, answers.len() as u8, 0, 0, 0, 0]);
+        response.extend_from_slice(&query[12..question_end]);
+        for answer in answers {
+            response.extend_from_slice(&[0xc0, 12]);
+            response.extend_from_slice(&answer);
+        }
+        socket
+            .send_to(&response, peer)
+            .expect("should send response");
+        query
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn dns_query_returns_records() {
+    let (port, server) = fake_dns_server(
+        0,
+        vec![
+            vec![0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 215, 14],
+            vec![0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 215, 15],
+        ],
+    );
+
+    let actual = nu!(format!(
+        "dns query example.com --server 127.0.0.1:{port} | each {{|r| $'($r.name) ($r.type) ($r.ttl) ($r.data)' }} | str join ';'"
+    ));
+
+    assert_eq!(
+        actual.out,
+        "example.com A 1min 93.184.215.14;example.com A 1min 93.184.215.15"
+    );
+    let query = server.join().expect("server should not panic");
+    assert_eq!(&query[12..29], b"\x07example\x03com\x00\x00\x01\x00\x01");
+}
+
+#[test]
+fn dns_query_sends_requested_type() {
+    let (port, server) = fake_dns_server(
+        0,
+        vec![vec![
+            0, 15, 0, 1, 0, 0, 0, 60, 0, 7, 0, 10, 2, b'm', b'x', 0xc0, 12,
+        ]],
+    );
+
+    let actual = nu!(format!(
+        "dns query example.com --type mx --server 127.0.0.1:{port} | get 0.data"
+    ));
+
+    assert_eq!(actual.out, "10 mx.example.com");
+    let query = server.join().expect("server should not panic");
+    assert_eq!(&query[25..27], &[0, 15]);
+}
+
+#[test]
+fn dns_query_reverse_lookup_for_ip() {
+    let (port, server) = fake_dns_server(0, vec![]);
+
+    let actual = nu!(format!(
+        "dns query 1.2.3.4 --server 127.0.0.1:{port} | length"
+    ));
+
+    assert_eq!(actual.out, "0");
+    let query = server.join().expect("server should not panic");
+    let name = b"\x014\x013\x012\x011\x07in-addr\x04arpa\x00\x00\x0c";
+    assert_eq!(&query[12..12 + name.len()], name.as_slice());
+}
+
+#[test]
+fn dns_query_unknown_domain_fails() {
+    let (port, _server) = fake_dns_server(3, vec![]);
+
+    let actual = nu!(format!(
+        "dns query does-not-exist.example --server 127.0.0.1:{port}"
+    ));
+
+    assert!(actual.err.contains("nu::shell::network::dns::no_name"));
+}
+
+#[test]
+fn dns_query_rejects_unknown_type() {
+    let actual = nu!("dns query example.com --type BOGUS --server 127.0.0.1");
+
+    assert!(actual.err.contains("Unknown record type"));
+}
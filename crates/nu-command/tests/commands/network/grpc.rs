@@ -0,0 +1,151 @@
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Request, Response};
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+use std::thread::JoinHandle;
+
+const GREETER_PROTO: &str = r#"
+    syntax = "proto3";
+    package helloworld;
+
+    service Greeter {
+      rpc SayHello (HelloRequest) returns (HelloReply);
+    }
+
+    message HelloRequest { string name = 1; }
+    message HelloReply { string message = 1; }
+"#;
+
+#[test]
+fn grpc_call_unknown_method() {
+    Playground::setup("grpc_call_unknown_method", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("helloworld.proto", GREETER_PROTO)]);
+
+        let actual = nu!(cwd: dirs.test(), "
+            grpc call 127.0.0.1:1 helloworld.Greeter/SayGoodbye --proto helloworld.proto --plaintext
+        ");
+
+        assert!(actual.err.contains("Unknown gRPC method"));
+        assert!(actual.err.contains("SayHello"));
+    })
+}
+
+#[test]
+fn grpc_call_invalid_proto() {
+    Playground::setup("grpc_call_invalid_proto", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "broken.proto",
+            "syntax = \"proto3\"; message Broken { string name = }",
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), "
+            grpc call 127.0.0.1:1 Broken/Call --proto broken.proto --plaintext
+        ");
+
+        assert!(actual.err.contains("Invalid .proto file"));
+    })
+}
+
+#[test]
+fn grpc_call_unknown_field_fails_before_connecting() {
+    Playground::setup("grpc_call_unknown_field", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("helloworld.proto", GREETER_PROTO)]);
+
+        let actual = nu!(cwd: dirs.test(), "
+            {nickname: 'nu'}
+            | grpc call 127.0.0.1:1 helloworld.Greeter/SayHello --proto helloworld.proto --plaintext
+        ");
+
+        assert!(actual.err.contains("Unknown field"));
+        assert!(actual.err.contains("name"));
+    })
+}
+
+/// Starts a Greeter server on a free local port, built on the `h2` crate, that answers a single
+/// call to SayHello with "Hello <name>".
+fn greeter_server() -> (u16, JoinHandle<()>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .expect("failed to start the runtime");
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .expect("failed to listen on a local port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let server = std::thread::spawn(move || {
+        runtime.block_on(async move {
+            let (socket, _) = listener.accept().await.expect("client connects");
+            let mut connection = h2::server::handshake(socket)
+                .await
+                .expect("HTTP/2 handshake");
+            // the connection has to be driven while the call is answered
+            while let Some(Ok((request, respond))) = connection.accept().await {
+                tokio::spawn(say_hello(request, respond));
+            }
+        })
+    });
+    (port, server)
+}
+
+async fn say_hello(request: Request<h2::RecvStream>, mut respond: h2::server::SendResponse<Bytes>) {
+    assert_eq!(request.uri().path(), "/helloworld.Greeter/SayHello");
+    assert_eq!(request.headers()["content-type"], "application/grpc");
+
+    let mut body = request.into_body();
+    let mut message = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.expect("request data");
+        let _ = body.flow_control().release_capacity(chunk.len());
+        message.extend_from_slice(&chunk);
+    }
+    // an uncompressed message with field 1 of HelloRequest, the name
+    let [0, _, _, _, _, 0x0a, len, name @ ..] = message.as_slice() else {
+        panic!("unexpected request: {message:?}");
+    };
+    assert_eq!(name.len(), *len as usize);
+    let reply = format!("Hello {}", String::from_utf8_lossy(name));
+
+    let mut response = vec![0];
+    response.extend_from_slice(&(reply.len() as u32 + 2).to_be_bytes());
+    response.extend_from_slice(&[0x0a, reply.len() as u8]);
+    response.extend_from_slice(reply.as_bytes());
+
+    let head = Response::builder()
+        .status(200)
+        .header("content-type", "application/grpc")
+        .body(())
+        .expect("valid response");
+    let mut stream = respond
+        .send_response(head, false)
+        .expect("sending the response headers");
+    stream
+        .send_data(Bytes::from(response), false)
+        .expect("sending the response");
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    stream
+        .send_trailers(trailers)
+        .expect("sending the trailers");
+}
+
+#[test]
+fn grpc_call_local_server() {
+    Playground::setup("grpc_call_local_server", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("helloworld.proto", GREETER_PROTO)]);
+        let (port, server) = greeter_server();
+
+        let actual = nu!(cwd: dirs.test(), format!("
+            {{name: 'nu'}}
+            | grpc call 127.0.0.1:{port} helloworld.Greeter/SayHello --proto helloworld.proto --plaintext
+            | get message
+        "));
+
+        assert_eq!(actual.out, "Hello nu");
+        server.join().expect("server ran into a problem");
+    })
+}
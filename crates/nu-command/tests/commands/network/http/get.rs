@@ -1,7 +1,7 @@
 use std::{thread, time::Duration};
 
-use mockito::Server;
-use nu_test_support::nu;
+use mockito::{Matcher, Server};
+use nu_test_support::{fs::Stub::FileWithContent, nu, playground::Playground};
 
 #[test]
 fn http_get_is_success() {
@@ -327,3 +327,300 @@ fn http_get_response_metadata() {
 
     assert_eq!(actual.out, "200");
 }
+
+#[test]
+fn http_get_retries_on_retryable_status() {
+    let mut server = Server::new();
+
+    let failure = server.mock("GET", "/").with_status(503).expect(2).create();
+    let _success = server.mock("GET", "/").with_body("foo").create();
+
+    let actual = nu!(format!(
+        "http get --retries 2 --retry-delay 10ms {url}",
+        url = server.url()
+    ));
+
+    failure.assert();
+    assert_eq!(actual.out, "foo");
+}
+
+#[test]
+fn http_get_gives_up_after_retries() {
+    let mut server = Server::new();
+
+    let failure = server.mock("GET", "/").with_status(503).expect(2).create();
+
+    let actual = nu!(format!(
+        "http get --retries 1 --retry-delay 10ms {url}",
+        url = server.url()
+    ));
+
+    failure.assert();
+    assert!(
+        actual.err.contains("Cannot make request"),
+        "unexpected error: {:?}",
+        actual.err
+    );
+}
+
+#[test]
+fn http_get_does_not_retry_unlisted_status() {
+    let mut server = Server::new();
+
+    let failure = server.mock("GET", "/").with_status(503).expect(1).create();
+
+    let actual = nu!(format!(
+        "http get --retries 3 --retry-delay 10ms --retry-on 429,502 {url}",
+        url = server.url()
+    ));
+
+    failure.assert();
+    assert!(!actual.err.is_empty());
+}
+
+#[test]
+fn http_get_honors_retry_after() {
+    let mut server = Server::new();
+
+    let _failure = server
+        .mock("GET", "/")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .expect(1)
+        .create();
+    let _success = server.mock("GET", "/").with_body("foo").create();
+
+    // A huge delay would make this test hang if Retry-After was ignored.
+    let actual = nu!(format!(
+        "http get --retries 1 --retry-delay 1hr {url}",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "foo");
+}
+
+#[test]
+fn http_get_cookie_jar_round_trip() {
+    Playground::setup("http_get_cookie_jar_round_trip", |dirs, _| {
+        let mut server = Server::new();
+
+        let _login = server
+            .mock("GET", "/login")
+            .with_header("set-cookie", "session=abc123; Path=/")
+            .create();
+        let _me = server
+            .mock("GET", "/me")
+            .match_header("cookie", "session=abc123")
+            .with_body("logged in")
+            .create();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!(
+                "http get --cookie-jar jar.json {url}/login; http get --cookie-jar jar.json {url}/me",
+                url = server.url()
+            )
+        );
+
+        assert_eq!(actual.out, "logged in");
+        assert!(dirs.test().join("jar.json").exists());
+    })
+}
+
+#[test]
+fn http_get_with_unreachable_proxy_fails() {
+    let mut server = Server::new();
+
+    let _mock = server.mock("GET", "/").with_body("direct").create();
+
+    let actual = nu!(format!(
+        "http get --proxy http://127.0.0.1:1 {url}",
+        url = server.url()
+    ));
+
+    assert!(!actual.status.success());
+}
+
+#[test]
+fn http_get_no_proxy_bypasses_proxy() {
+    let mut server = Server::new();
+
+    let _mock = server.mock("GET", "/").with_body("direct").create();
+
+    let actual = nu!(format!(
+        "http get --proxy http://127.0.0.1:1 --no-proxy [localhost 127.0.0.1] {url}",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "direct");
+}
+
+#[test]
+fn http_get_proxy_credentials_require_proxy() {
+    let actual = nu!("http get --proxy-user user https://example.com");
+
+    assert!(actual.err.contains("missing parameter: proxy"));
+}
+
+#[test]
+fn http_get_sse_streams_events() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("GET", "/events")
+        .match_header("accept", "text/event-stream")
+        .with_header("content-type", "text/event-stream")
+        .with_body(": ping\n\nevent: greeting\ndata: hello\nid: 1\n\ndata: {\"n\": 2}\n\n")
+        .create();
+
+    let actual = nu!(format!(
+        "http get --sse {url}/events | each {{|e| $'($e.event)=($e.data)@($e.id)' }} | str join ';'",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, r#"greeting=hello@1;message={"n": 2}@1"#);
+}
+
+#[test]
+fn http_get_output_saves_body() {
+    Playground::setup("http_get_output_saves_body", |dirs, _| {
+        let mut server = Server::new();
+
+        let _mock = server.mock("GET", "/").with_body("hello world").create();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("http get --output out.txt {url}", url = server.url())
+        );
+
+        assert!(actual.err.is_empty(), "{}", actual.err);
+        let saved = std::fs::read_to_string(dirs.test().join("out.txt")).expect("file exists");
+        assert_eq!(saved, "hello world");
+    })
+}
+
+#[test]
+fn http_get_resume_continues_partial_file() {
+    Playground::setup("http_get_resume_continues_partial_file", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("out.txt", "hello ")]);
+        let mut server = Server::new();
+
+        let _mock = server
+            .mock("GET", "/")
+            .match_header("range", "bytes=6-")
+            .with_status(206)
+            .with_header("content-range", "bytes 6-10/11")
+            .with_body("world")
+            .create();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("http get --output out.txt --resume {url}", url = server.url())
+        );
+
+        assert!(actual.err.is_empty(), "{}", actual.err);
+        let saved = std::fs::read_to_string(dirs.test().join("out.txt")).expect("file exists");
+        assert_eq!(saved, "hello world");
+    })
+}
+
+#[test]
+fn http_get_resume_starts_over_when_range_is_ignored() {
+    Playground::setup("http_get_resume_starts_over", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("out.txt", "stale data")]);
+        let mut server = Server::new();
+
+        let _mock = server.mock("GET", "/").with_body("hello world").create();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("http get --output out.txt --resume {url}", url = server.url())
+        );
+
+        assert!(actual.err.is_empty(), "{}", actual.err);
+        let saved = std::fs::read_to_string(dirs.test().join("out.txt")).expect("file exists");
+        assert_eq!(saved, "hello world");
+    })
+}
+
+#[test]
+fn http_get_parallel_download() {
+    Playground::setup("http_get_parallel_download", |dirs, _| {
+        let segment = 64 * 1024;
+        let body: Vec<u8> = (0..4 * segment).map(|i| (i % 251) as u8).collect();
+        let mut server = Server::new();
+
+        let _full = server
+            .mock("GET", "/big")
+            .match_header("range", Matcher::Missing)
+            .with_header("accept-ranges", "bytes")
+            .with_body(&body)
+            .create();
+        let segments = (1..4)
+            .map(|i| {
+                let (from, to) = (i * segment, (i + 1) * segment - 1);
+                server
+                    .mock("GET", "/big")
+                    .match_header("range", format!("bytes={from}-{to}").as_str())
+                    .with_status(206)
+                    .with_header(
+                        "content-range",
+                        &format!("bytes {from}-{to}/{}", body.len()),
+                    )
+                    .with_body(&body[from..=to])
+                    .expect(1)
+                    .create()
+            })
+            .collect::<Vec<_>>();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("http get --output big.bin --parallel 4 {url}/big", url = server.url())
+        );
+
+        assert!(actual.err.is_empty(), "{}", actual.err);
+        let saved = std::fs::read(dirs.test().join("big.bin")).expect("file exists");
+        assert!(saved == body, "downloaded file differs from the served one");
+        for mock in &segments {
+            mock.assert();
+        }
+        assert!(!dirs.test().join("big.bin.part1").exists());
+    })
+}
+
+#[test]
+fn http_get_checksum_mismatch_removes_file() {
+    Playground::setup("http_get_checksum_mismatch_removes_file", |dirs, _| {
+        let mut server = Server::new();
+
+        let _mock = server.mock("GET", "/").with_body("hello world").create();
+
+        let ok = nu!(
+            cwd: dirs.test(),
+            format!(
+                "http get --output ok.txt --expect-sha256 B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9 {url}",
+                url = server.url()
+            )
+        );
+        let bad = nu!(
+            cwd: dirs.test(),
+            format!(
+                "http get --output bad.txt --expect-sha256 {zeros} {url}",
+                zeros = "0".repeat(64),
+                url = server.url()
+            )
+        );
+
+        assert!(ok.err.is_empty(), "{}", ok.err);
+        assert!(dirs.test().join("ok.txt").exists());
+        assert!(bad.err.contains("Checksum mismatch"));
+        assert!(!dirs.test().join("bad.txt").exists());
+    })
+}
+
+#[test]
+fn http_get_resume_requires_output() {
+    let actual = nu!("http get --resume https://example.com");
+
+    assert!(actual.err.contains("missing parameter: output"));
+}
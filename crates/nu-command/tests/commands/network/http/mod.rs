@@ -4,10 +4,13 @@ use rstest::*;
 mod delete;
 mod get;
 mod head;
+mod oauth2;
 mod options;
 mod patch;
 mod post;
 mod put;
+mod serve;
+mod session;
 
 #[rstest]
 #[case::delete("delete")]
@@ -0,0 +1,132 @@
+use mockito::{Matcher, Server};
+use nu_test_support::nu;
+
+const TOKEN: &str = r#"{"access_token": "abc", "token_type": "Bearer", "expires_in": 3600}"#;
+
+#[test]
+fn http_auth_oauth2_client_credentials() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("POST", "/token")
+        .match_header("authorization", "Basic aWQ6c2VjcmV0")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()),
+            Matcher::UrlEncoded("scope".into(), "read write".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(TOKEN)
+        .create();
+
+    let actual = nu!(format!(
+        "http auth oauth2 {url}/token --client-id id --client-secret secret --scope [read write] --no-cache",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "abc");
+}
+
+#[test]
+fn http_auth_oauth2_attaches_token_to_session() {
+    let mut server = Server::new();
+
+    let _token = server.mock("POST", "/token").with_body(TOKEN).create();
+    let _mock = server
+        .mock("GET", "/me")
+        .match_header("authorization", "Bearer abc")
+        .with_body("me")
+        .create();
+
+    let actual = nu!(format!(
+        "let s = http session | http auth oauth2 {url}/token --client-id id --client-secret secret --no-cache; http get --session $s {url}/me",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "me");
+}
+
+#[test]
+fn http_auth_oauth2_device_flow_waits_for_sign_in() {
+    let mut server = Server::new();
+
+    let _device = server
+        .mock("POST", "/device")
+        .match_body(Matcher::UrlEncoded("client_id".into(), "cli".into()))
+        .with_body(
+            r#"{"device_code": "dev", "user_code": "ABCD-EFGH", "verification_uri": "https://example.com/device", "expires_in": 60, "interval": 0}"#,
+        )
+        .create();
+    let pending = server
+        .mock("POST", "/token")
+        .with_status(400)
+        .with_body(r#"{"error": "authorization_pending"}"#)
+        .expect(1)
+        .create();
+    let success = server
+        .mock("POST", "/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("device_code".into(), "dev".into()),
+            Matcher::UrlEncoded("client_id".into(), "cli".into()),
+        ]))
+        .with_body(TOKEN)
+        .create();
+
+    let actual = nu!(format!(
+        "http auth oauth2 {url}/token --device-url {url}/device --client-id cli --no-cache",
+        url = server.url()
+    ));
+
+    pending.assert();
+    success.assert();
+    assert_eq!(actual.out, "abc");
+    assert!(actual.err.contains("ABCD-EFGH"));
+}
+
+#[test]
+fn http_auth_oauth2_discovers_endpoints() {
+    let mut server = Server::new();
+
+    let _discovery = server
+        .mock("GET", "/.well-known/openid-configuration")
+        .with_body(format!(
+            r#"{{"issuer": "{url}", "token_endpoint": "{url}/oauth/token"}}"#,
+            url = server.url()
+        ))
+        .create();
+    let _token = server
+        .mock("POST", "/oauth/token")
+        .with_body(TOKEN)
+        .create();
+
+    let actual = nu!(format!(
+        "http auth oauth2 {url} --discover --client-id id --client-secret secret --no-cache --full | get token_type",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "Bearer");
+}
+
+#[test]
+fn http_auth_oauth2_reports_server_error() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("POST", "/token")
+        .with_status(401)
+        .with_body(r#"{"error": "invalid_client", "error_description": "Unknown client"}"#)
+        .create();
+
+    let actual = nu!(format!(
+        "http auth oauth2 {url}/token --client-id id --client-secret wrong --no-cache",
+        url = server.url()
+    ));
+
+    assert!(actual.err.contains("invalid_client: Unknown client"));
+}
+
+#[test]
+fn http_auth_oauth2_device_flow_needs_endpoint() {
+    let actual = nu!("http auth oauth2 https://example.com/token --device --client-id cli");
+
+    assert!(actual.err.contains("Unknown device authorization endpoint"));
+}
@@ -1,7 +1,8 @@
 use std::{thread, time::Duration};
 
 use mockito::{Matcher, Server, ServerOpts};
-use nu_test_support::nu;
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::{nu, playground::Playground};
 
 #[test]
 fn http_post_is_success() {
@@ -24,6 +25,32 @@ fn http_post_is_success_pipeline() {
     assert!(actual.out.is_empty())
 }
 
+#[test]
+fn http_post_retries_with_same_body() {
+    let mut server = Server::new();
+
+    let failure = server
+        .mock("POST", "/")
+        .match_body("foo")
+        .with_status(502)
+        .expect(1)
+        .create();
+    let success = server
+        .mock("POST", "/")
+        .match_body("foo")
+        .with_body("bar")
+        .create();
+
+    let actual = nu!(format!(
+        r#""foo" | http post --retries 1 --retry-delay 10ms {url}"#,
+        url = server.url()
+    ));
+
+    failure.assert();
+    success.assert();
+    assert_eq!(actual.out, "bar")
+}
+
 #[test]
 fn http_post_failed_due_to_server_error() {
     let mut server = Server::new();
@@ -231,6 +258,55 @@ fn http_post_multipart_is_success() {
     assert!(actual.out.is_empty())
 }
 
+#[test]
+fn http_post_form_streams_file_parts() {
+    Playground::setup("http_post_form_streams_file_parts", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("report.csv", "a,b\n1,2\n")]);
+
+        let mut server = Server::new_with_opts(ServerOpts {
+            assert_on_drop: true,
+            ..Default::default()
+        });
+        let _mock = server
+            .mock("POST", "/")
+            .match_header(
+                "content-type",
+                Matcher::Regex("multipart/form-data; boundary=.*".to_string()),
+            )
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(
+                    r#"(?m)^Content-Disposition: form-data; name="upload"; filename="report.csv""#
+                        .to_string(),
+                ),
+                Matcher::Regex(r#"(?m)^Content-Type: text/csv"#.to_string()),
+                Matcher::Regex(r#"(?m)^a,b\r?\n1,2"#.to_string()),
+                Matcher::Regex(r#"(?m)^Content-Disposition: form-data; name="meta""#.to_string()),
+                Matcher::Regex(r#"(?m)^Content-Type: application/json"#.to_string()),
+                Matcher::Regex(r#"(?m)^\{"kind":"csv"\}"#.to_string()),
+                Matcher::Regex(r#"(?m)^Content-Disposition: form-data; name="note""#.to_string()),
+            ]))
+            .with_status(200)
+            .create();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!(
+                r#"http post --form {url} {{upload: {{file: report.csv}}, meta: {{value: '{{"kind":"csv"}}', content_type: application/json}}, note: hello}}"#,
+                url = server.url()
+            )
+        );
+
+        assert!(actual.out.is_empty(), "{}", actual.err)
+    })
+}
+
+#[test]
+fn http_post_form_missing_file_fails() {
+    let actual = nu!("http post --form https://example.com {upload: {file: does-not-exist.bin}}");
+
+    assert!(actual.err.contains("does-not-exist.bin"));
+}
+
 #[test]
 fn http_post_timeout() {
     let mut server = Server::new();
@@ -0,0 +1,99 @@
+use nu_test_support::nu;
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("should get a free port")
+}
+
+/// Runs `http serve --once` with `handler` and sends it `request`, returning the raw response.
+fn serve_once(handler: &str, request: &str) -> String {
+    let port = free_port();
+    let script = format!("http serve --once --port {port} {handler}");
+    let server = thread::spawn(move || nu!(script));
+
+    let mut stream = (0..50)
+        .find_map(|_| {
+            TcpStream::connect(("127.0.0.1", port))
+                .inspect_err(|_| thread::sleep(Duration::from_millis(100)))
+                .ok()
+        })
+        .expect("server should start listening");
+    stream
+        .write_all(request.as_bytes())
+        .expect("should send request");
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("should read response");
+
+    let outcome = server.join().expect("server thread should not panic");
+    assert!(outcome.err.is_empty(), "{}", outcome.err);
+    response
+}
+
+#[test]
+fn http_serve_responds_with_closure_result() {
+    let response = serve_once(
+        "{|req| $'($req.method) ($req.path) ($req.query.name)' }",
+        "GET /hello?name=nu HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: text/plain"));
+    assert!(response.ends_with("\r\n\r\nGET /hello nu"));
+}
+
+#[test]
+fn http_serve_passes_request_body_and_headers() {
+    let response = serve_once(
+        "{|req| {token: $req.headers.x-token, data: ($req.body | decode | from json)} }",
+        "POST / HTTP/1.1\r\nHost: localhost\r\nX-Token: abc\r\nContent-Length: 8\r\n\r\n{\"a\": 1}",
+    );
+
+    assert!(response.contains("Content-Type: application/json"));
+    assert!(response.contains(r#""token":"abc""#));
+    assert!(response.contains(r#""data":{"a":1}"#));
+}
+
+#[test]
+fn http_serve_uses_status_and_headers_from_record() {
+    let response = serve_once(
+        "{|req| {status: 404, headers: {x-reason: gone}, body: 'not found'} }",
+        "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    assert!(response.contains("x-reason: gone\r\n"));
+    assert!(response.ends_with("not found"));
+}
+
+#[test]
+fn http_serve_reports_closure_errors() {
+    let response = serve_once(
+        "{|req| error make {msg: boom} }",
+        "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 500 Internal Server Error\r\n"));
+    assert!(response.contains("boom"));
+}
+
+#[test]
+fn http_serve_rejects_line_breaks_in_headers() {
+    let response = serve_once(
+        "{|req| {status: 200, headers: {x-reason: \"gone\\r\\nSet-Cookie: a=b\"}} }",
+        "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 500 Internal Server Error\r\n"));
+    assert!(!response.contains("Set-Cookie: a=b\r\n"));
+}
@@ -0,0 +1,74 @@
+use mockito::Server;
+use nu_test_support::nu;
+
+#[test]
+fn http_session_resolves_relative_urls_and_adds_headers() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("GET", "/api/items")
+        .match_header("x-token", "abc")
+        .with_body("items")
+        .create();
+
+    let actual = nu!(format!(
+        "let s = http session --base-url {url}/api/ --headers {{x-token: abc}}; http get --session $s /items",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "items");
+}
+
+#[test]
+fn http_session_headers_can_be_overridden() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("POST", "/")
+        .match_header("x-token", "override")
+        .match_body("data")
+        .with_body("posted")
+        .create();
+
+    let actual = nu!(format!(
+        "let s = http session --headers {{X-Token: abc}}; http post --session $s --headers {{x-token: override}} {url} data",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "posted");
+}
+
+#[test]
+fn http_session_uses_session_credentials() {
+    let mut server = Server::new();
+
+    let _mock = server
+        .mock("GET", "/")
+        .match_header("authorization", "Basic dXNlcjpwYXNz")
+        .with_body("authorized")
+        .create();
+
+    let actual = nu!(format!(
+        "let s = http session --user user --password pass; http get --session $s {url}",
+        url = server.url()
+    ));
+
+    assert_eq!(actual.out, "authorized");
+}
+
+#[test]
+fn http_session_does_not_show_password() {
+    let actual = nu!(
+        "http session --base-url https://example.com --user user --password secret | to json --raw"
+    );
+
+    assert!(actual.out.contains("https://example.com"));
+    assert!(!actual.out.contains("secret"));
+}
+
+#[test]
+fn http_session_flag_rejects_other_values() {
+    let actual = nu!("http get --session {a: 1} https://example.com");
+
+    assert!(actual.err.contains("http session"));
+}
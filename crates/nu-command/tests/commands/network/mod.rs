@@ -1,2 +1,7 @@
+mod dns;
+mod grpc;
 mod http;
+mod net;
 mod port;
+#[cfg(all(unix, feature = "sftp"))]
+mod sftp;
@@ -0,0 +1,114 @@
+use nu_test_support::nu;
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, UdpSocket},
+    thread,
+};
+
+#[test]
+fn net_connect_sends_input_and_returns_response() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+    let port = listener.local_addr().expect("should have address").port();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("should accept");
+        // the client shuts down its sending side, so this ends after the input
+        let mut request = String::new();
+        stream
+            .read_to_string(&mut request)
+            .expect("should read request");
+        stream
+            .write_all(format!("you said: {request}").as_bytes())
+            .expect("should answer");
+    });
+
+    let actual = nu!(format!("'hello' | net connect 127.0.0.1:{port}"));
+
+    server.join().expect("server should not panic");
+    assert_eq!(actual.out, "you said: hello");
+}
+
+#[test]
+fn net_connect_reads_banner_without_input() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+    let port = listener.local_addr().expect("should have address").port();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("should accept");
+        stream
+            .write_all(b"SSH-2.0-OpenSSH_9.6\r\n")
+            .expect("should send banner");
+    });
+
+    let actual = nu!(format!("net connect 127.0.0.1:{port} | str trim"));
+
+    server.join().expect("server should not panic");
+    assert_eq!(actual.out, "SSH-2.0-OpenSSH_9.6");
+}
+
+#[test]
+fn net_connect_stops_after_idle_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+    let port = listener.local_addr().expect("should have address").port();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("should accept");
+        stream.write_all(b"partial").expect("should send");
+        // keep the connection open until the client went away
+        let _ = stream.read(&mut [0; 1]);
+    });
+
+    let actual = nu!(format!(
+        "net connect 127.0.0.1:{port} --timeout 200ms | decode"
+    ));
+
+    server.join().expect("server should not panic");
+    assert_eq!(actual.out, "partial");
+}
+
+#[test]
+fn net_connect_udp_round_trip() {
+    let socket = UdpSocket::bind("127.0.0.1:0").expect("should bind");
+    let port = socket.local_addr().expect("should have address").port();
+
+    let server = thread::spawn(move || {
+        let mut buf = [0; 64];
+        let (len, peer) = socket.recv_from(&mut buf).expect("should receive");
+        let mut answer = b"pong: ".to_vec();
+        answer.extend_from_slice(&buf[..len]);
+        socket.send_to(&answer, peer).expect("should answer");
+    });
+
+    let actual = nu!(format!(
+        "'ping' | net connect --udp 127.0.0.1:{port} --timeout 500ms | decode"
+    ));
+
+    server.join().expect("server should not panic");
+    assert_eq!(actual.out, "pong: ping");
+}
+
+#[test]
+fn net_connect_refused() {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("should get a free port");
+
+    let actual = nu!(format!("net connect 127.0.0.1:{port}"));
+
+    assert!(actual.err.contains("Could not connect"));
+}
+
+#[test]
+fn net_connect_rejects_tls_over_udp() {
+    let actual = nu!("net connect --udp --tls 127.0.0.1:443");
+
+    assert!(actual.err.contains("over UDP"));
+}
+
+#[test]
+fn net_connect_requires_port() {
+    let actual = nu!("net connect example.com");
+
+    assert!(actual.err.contains("Expected an address like host:port"));
+}
@@ -0,0 +1,26 @@
+use nu_test_support::nu;
+use std::net::{TcpListener, UdpSocket};
+
+#[test]
+fn net_connections_shows_listening_socket_and_owner() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+    let port = listener.local_addr().expect("should have address").port();
+
+    let actual = nu!(format!(
+        "net connections --listening --tcp | where local_port == {port} | first | [$in.state $in.pid] | str join ' '"
+    ));
+
+    assert_eq!(actual.out, format!("listen {}", std::process::id()));
+}
+
+#[test]
+fn net_connections_filters_protocol() {
+    let socket = UdpSocket::bind("127.0.0.1:0").expect("should bind");
+    let port = socket.local_addr().expect("should have address").port();
+
+    let actual = nu!(format!(
+        "[(net connections --udp | where local_port == {port} | length) (net connections --tcp | where local_port == {port} | length)] | str join ' '"
+    ));
+
+    assert_eq!(actual.out, "1 0");
+}
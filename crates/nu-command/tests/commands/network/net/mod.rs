@@ -0,0 +1,3 @@
+mod connect;
+#[cfg(target_os = "linux")]
+mod connections;
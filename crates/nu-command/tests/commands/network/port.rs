@@ -1,6 +1,7 @@
 use nu_test_support::nu;
-use std::net::TcpListener;
+use std::net::{TcpListener, TcpStream};
 use std::sync::mpsc;
+use std::time::Duration;
 
 #[test]
 fn port_with_invalid_range() {
@@ -51,3 +52,36 @@ fn port_out_of_range() {
 
     assert!(actual.err.contains("can't convert usize to u16"));
 }
+
+#[test]
+fn port_listen_reports_connections() {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("should get a free port");
+
+    let client = std::thread::spawn(move || {
+        // keep trying until nushell is listening
+        for _ in 0..100 {
+            if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        panic!("could not connect to the listener");
+    });
+
+    let actual = nu!(format!(
+        "port --listen {port} {port} | first | [$in.remote_address $in.local_port] | str join ' '"
+    ));
+
+    client.join().expect("client should not panic");
+    assert_eq!(actual.out, format!("127.0.0.1 {port}"));
+}
+
+#[test]
+fn port_listen_rejects_invalid_address() {
+    let actual = nu!("port --listen --address localhost");
+
+    assert!(actual.err.contains("Expected an IP address"));
+}
@@ -0,0 +1,214 @@
+use nu_test_support::{fs::Stub::FileWithContent, nu, playground::Playground};
+use std::{
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// An OpenSSH server for the current user on a free local port, accepting only the key at
+/// `key`. It's stopped when dropped.
+struct SshServer {
+    child: Child,
+    port: u16,
+    user: String,
+    key: PathBuf,
+}
+
+impl SshServer {
+    /// Starts the server with its keys and config in `dir`, or returns `None` if OpenSSH isn't
+    /// installed or the server doesn't come up.
+    fn start(dir: &Path) -> Option<Self> {
+        let sshd = find_program("sshd")?;
+        let ssh_keygen = find_program("ssh-keygen")?;
+        let user = std::env::var("USER").ok()?;
+
+        let key = dir.join("user_key");
+        let host_key = dir.join("host_key");
+        for path in [&key, &host_key] {
+            let generated = Command::new(&ssh_keygen)
+                .args(["-q", "-t", "ed25519", "-N", ""])
+                .arg("-f")
+                .arg(path)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .ok()?;
+            if !generated.success() {
+                return None;
+            }
+        }
+        let authorized_keys = dir.join("authorized_keys");
+        std::fs::copy(key.with_extension("pub"), &authorized_keys).ok()?;
+
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .ok()?
+            .port();
+        let config = dir.join("sshd_config");
+        std::fs::write(
+            &config,
+            format!(
+                "Port {port}\n\
+                 ListenAddress 127.0.0.1\n\
+                 HostKey {}\n\
+                 AuthorizedKeysFile {}\n\
+                 PidFile {}\n\
+                 StrictModes no\n\
+                 UsePAM no\n\
+                 PasswordAuthentication no\n\
+                 KbdInteractiveAuthentication no\n\
+                 Subsystem sftp internal-sftp\n",
+                host_key.display(),
+                authorized_keys.display(),
+                dir.join("sshd.pid").display(),
+            ),
+        )
+        .ok()?;
+
+        let child = Command::new(sshd)
+            .args(["-D", "-e", "-f"])
+            .arg(&config)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut server = SshServer {
+            child,
+            port,
+            user,
+            key,
+        };
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            if Instant::now() > deadline || !matches!(server.child.try_wait(), Ok(None)) {
+                return None;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        Some(server)
+    }
+
+    /// The remote path of `path` on this server.
+    fn remote(&self, path: &Path) -> String {
+        format!("{}@127.0.0.1:{}", self.user, path.display())
+    }
+
+    /// The flags to connect to this server, without checking its host key.
+    fn flags(&self) -> String {
+        format!(
+            "--port {} --identity '{}' --insecure",
+            self.port,
+            self.key.display()
+        )
+    }
+}
+
+impl Drop for SshServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn find_program(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    // sshd usually isn't in the PATH of regular users
+    std::env::split_paths(&path)
+        .chain(["/usr/sbin", "/usr/local/sbin"].map(PathBuf::from))
+        .map(|dir| dir.join(name))
+        .find(|program| program.is_file())
+}
+
+/// Runs `test` with a server, or skips it if there's no SSH server to run.
+fn with_server(topic: &str, test: impl FnOnce(&Path, &SshServer)) {
+    Playground::setup(topic, |dirs, playground| {
+        playground
+            .within("files")
+            .with_files(&[FileWithContent("notes.txt", "hello sftp")])
+            .mkdir("docs");
+
+        let Some(server) = SshServer::start(dirs.test().as_std_path()) else {
+            eprintln!("skipping {topic}: no SSH server available");
+            return;
+        };
+        test(dirs.test().join("files").as_std_path(), &server);
+    })
+}
+
+#[test]
+fn sftp_ls_lists_a_directory() {
+    with_server("sftp ls lists a directory", |files, server| {
+        let actual = nu!(format!(
+            "sftp ls '{}' {} | each {{|e| $'($e.name) ($e.type)' }} | str join ';'",
+            server.remote(files),
+            server.flags()
+        ));
+
+        assert_eq!(actual.out, "docs dir;notes.txt file");
+    })
+}
+
+#[test]
+fn sftp_get_streams_a_file() {
+    with_server("sftp get streams a file", |files, server| {
+        let actual = nu!(format!(
+            "sftp get '{}' {} | decode utf-8",
+            server.remote(&files.join("notes.txt")),
+            server.flags()
+        ));
+
+        assert_eq!(actual.out, "hello sftp");
+    })
+}
+
+#[test]
+fn sftp_put_writes_and_appends() {
+    with_server("sftp put writes and appends", |files, server| {
+        let remote = server.remote(&files.join("docs").join("new.txt"));
+        let actual = nu!(format!(
+            "'first' | sftp put '{remote}' {flags}
+             ' second' | sftp put --append '{remote}' {flags}",
+            flags = server.flags()
+        ));
+
+        assert!(actual.err.is_empty(), "{}", actual.err);
+        let written = std::fs::read_to_string(files.join("docs").join("new.txt"));
+        assert_eq!(written.ok().as_deref(), Some("first second"));
+    })
+}
+
+#[test]
+fn sftp_rejects_an_unauthorized_key() {
+    with_server("sftp rejects an unauthorized key", |files, server| {
+        // the host key is a valid key, but not one the server accepts for the user
+        let actual = nu!(format!(
+            "sftp ls '{}' --port {} --identity '{}' --insecure",
+            server.remote(files),
+            server.port,
+            server.key.with_file_name("host_key").display()
+        ));
+
+        assert!(
+            actual
+                .err
+                .contains("Could not authenticate with the given key")
+        );
+    })
+}
+
+#[test]
+fn sftp_checks_the_host_key() {
+    with_server("sftp checks the host key", |files, server| {
+        let actual = nu!(format!(
+            "sftp ls '{}' --port {} --identity '{}'",
+            server.remote(files),
+            server.port,
+            server.key.display()
+        ));
+
+        assert!(actual.err.contains("Host key verification failed"));
+    })
+}
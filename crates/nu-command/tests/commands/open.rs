@@ -1372,3 +1372,90 @@ fn test_metadata_without_raw() {
         assert!(result.out.contains("appveyor.yml"));
     })
 }
+
+#[cfg(feature = "network")]
+mod remote {
+    use mockito::{Matcher, Server};
+    use nu_test_support::nu;
+
+    #[test]
+    fn open_url_parses_by_content_type() {
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/users")
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"name": "alice"}]"#)
+            .create();
+
+        let actual = nu!(format!("open {}/users | get name.0", server.url()));
+
+        assert_eq!(actual.out, "alice");
+    }
+
+    #[test]
+    fn open_url_parses_by_extension() {
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/data.csv")
+            .with_header("content-type", "application/octet-stream")
+            .with_body("a,b\n1,2\n")
+            .create();
+
+        let actual = nu!(format!("open {}/data.csv | get b.0", server.url()));
+
+        assert_eq!(actual.out, "2");
+    }
+
+    #[test]
+    fn open_url_raw() {
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/data.json")
+            .with_header("content-type", "application/json")
+            .with_body("[1, 2]")
+            .create();
+
+        let actual = nu!(format!(
+            "open --raw {}/data.json | decode utf-8",
+            server.url()
+        ));
+
+        assert_eq!(actual.out, "[1, 2]");
+    }
+
+    #[test]
+    fn open_url_fails_on_error_status() {
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/missing.json")
+            .with_status(404)
+            .create();
+
+        let actual = nu!(format!("open {}/missing.json", server.url()));
+
+        assert!(actual.err.contains("404"));
+    }
+
+    #[test]
+    fn open_s3_object_signs_request() {
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/bucket/dir/data.csv")
+            .match_header(
+                "authorization",
+                Matcher::Regex(
+                    r"^AWS4-HMAC-SHA256 Credential=AKID/\d{8}/eu-west-1/s3/aws4_request, ".into(),
+                ),
+            )
+            .match_header("x-amz-date", Matcher::Any)
+            .with_body("a,b\n1,2\n")
+            .create();
+
+        let actual = nu!(format!(
+            "with-env {{AWS_ENDPOINT_URL: '{}', AWS_REGION: eu-west-1, AWS_ACCESS_KEY_ID: AKID, AWS_SECRET_ACCESS_KEY: secret}} {{ open s3://bucket/dir/data.csv | get a.0 }}",
+            server.url()
+        ));
+
+        assert_eq!(actual.out, "1");
+    }
+}
@@ -7,3 +7,51 @@ fn par_each_does_not_flatten_nested_structures() {
 
     assert_eq!(actual.out, "[[1,1],[2,2],[3,3]]");
 }
+
+#[test]
+fn par_each_ordered_keeps_input_order() {
+    let actual = nu!(
+        "1..200 | par-each --ordered --max-threads 4 {|it| sleep (($it mod 3) * 1ms); $it } | to json --raw"
+    );
+
+    let expected = format!(
+        "[{}]",
+        (1..=200)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    assert_eq!(actual.out, expected);
+}
+
+#[test]
+fn par_each_ordered_streams_the_output() {
+    // Only the rows in the buffer ahead of the first ten are processed, so this doesn't take long.
+    let actual = nu!(
+        "1..1000000 | each { $in } | par-each --ordered --buffer 8 {|it| $it * 2 } | first 10 | math sum"
+    );
+
+    assert_eq!(actual.out, "110");
+}
+
+#[test]
+fn par_each_buffer_without_ordered() {
+    let actual = nu!("[1 2 3 4 5] | par-each --buffer 2 {|it| $it * 2 } | sort | to json --raw");
+
+    assert_eq!(actual.out, "[2,4,6,8,10]");
+}
+
+#[test]
+fn par_each_ordered_skips_nothing() {
+    let actual =
+        nu!("[1 2 3 4] | par-each --ordered {|it| if $it mod 2 == 0 { $it } } | to json --raw");
+
+    assert_eq!(actual.out, "[2,4]");
+}
+
+#[test]
+fn par_each_buffer_needs_positive_value() {
+    let actual = nu!("[1 2 3] | par-each --buffer 0 {|it| $it }");
+
+    assert!(actual.err.contains("nu::shell::needs_positive_value"));
+}
@@ -91,6 +91,53 @@ mod simple {
             r#"[{"level":"INFO","entry":"all is well"},{"level":"ERROR","entry":"something bad happened"}]"#
         );
     }
+
+    #[test]
+    fn converts_typed_columns() {
+        let actual = nu!(r#"
+            "12 errors in src/main.rs, 1.5MB in 2min 30sec"
+            | parse "{count:int} errors in {file:path}, {size:filesize} in {time:duration}"
+            | first
+            | describe
+        "#);
+
+        assert_eq!(
+            actual.out,
+            "record<count: int, file: string, size: filesize, time: duration>"
+        );
+    }
+
+    #[test]
+    fn sums_typed_columns() {
+        let actual = nu!(r#"
+            ["3 errors" "4 errors"]
+            | parse "{count:int} errors"
+            | get count
+            | math sum
+        "#);
+
+        assert_eq!(actual.out, "7");
+    }
+
+    #[test]
+    fn errors_when_typed_column_does_not_convert() {
+        let actual = nu!(r#"
+            "many errors"
+            | parse "{count:int} errors"
+        "#);
+
+        assert!(actual.err.contains("Can't convert to int"));
+    }
+
+    #[test]
+    fn errors_with_unknown_column_type() {
+        let actual = nu!(r#"
+            "3 errors"
+            | parse "{count:number} errors"
+        "#);
+
+        assert!(actual.err.contains("Unknown type `number`"));
+    }
 }
 
 mod regex {
@@ -206,4 +253,17 @@ mod regex {
             assert_eq!(actual.out, "1000");
         })
     }
+
+    #[test]
+    fn parse_streams_byte_stream_input() {
+        let actual = nu!(r#"
+            1.. | each { $"item ($in)" } | to text
+            | parse --regex 'item (?<n>\d+)'
+            | first 3
+            | get n
+            | str join ','
+        "#);
+
+        assert_eq!(actual.out, "1,2,3");
+    }
 }
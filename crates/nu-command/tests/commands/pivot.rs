@@ -0,0 +1,65 @@
+use nu_test_support::nu;
+
+const SALES: &str = "[[region month sales]; [eu jan 10] [eu feb 20] [na jan 5] [eu jan 7]]";
+
+#[test]
+fn pivot_aggregations() {
+    for (agg, expected) in [
+        ("first", "[[region, jan, feb]; [eu, 10, 20], [na, 5, null]]"),
+        ("last", "[[region, jan, feb]; [eu, 7, 20], [na, 5, null]]"),
+        ("sum", "[[region, jan, feb]; [eu, 17, 20], [na, 5, null]]"),
+        ("min", "[[region, jan, feb]; [eu, 7, 20], [na, 5, null]]"),
+        (
+            "avg",
+            "[[region, jan, feb]; [eu, 8.5, 20.0], [na, 5.0, null]]",
+        ),
+        ("count", "[[region, jan, feb]; [eu, 2, 1], [na, 1, 0]]"),
+        (
+            "list",
+            "[[region, jan, feb]; [eu, [10, 7], [20]], [na, [5], []]]",
+        ),
+    ] {
+        let actual = nu!(format!(
+            "{SALES} | pivot --index region --columns month --values sales --agg {agg} | to nuon"
+        ));
+        assert_eq!(actual.out, expected, "--agg {agg}");
+    }
+}
+
+#[test]
+fn pivot_defaults_to_first() {
+    let actual = nu!(format!(
+        "{SALES} | pivot -i region -c month -v sales | get 0.jan"
+    ));
+    assert_eq!(actual.out, "10");
+}
+
+#[test]
+fn pivot_errors_on_missing_column() {
+    let actual = nu!(format!(
+        "{SALES} | pivot --index region --columns day --values sales"
+    ));
+    assert!(actual.err.contains("cannot find column 'day'"));
+}
+
+#[test]
+fn pivot_errors_on_unknown_aggregation() {
+    let actual = nu!(format!(
+        "{SALES} | pivot --index region --columns month --values sales --agg total"
+    ));
+    assert!(actual.err.contains("Expected one of"));
+}
+
+#[test]
+fn pivot_then_unpivot_round_trips() {
+    let actual = nu!(format!(
+        "{SALES} | pivot --index region --columns month --values sales --agg sum
+        | unpivot --index [region] --variable-name month --value-name sales
+        | compact sales
+        | to nuon"
+    ));
+    assert_eq!(
+        actual.out,
+        "[[region, month, sales]; [eu, jan, 17], [eu, feb, 20], [na, jan, 5]]"
+    );
+}
@@ -3,4 +3,5 @@ mod bool;
 mod chars;
 mod float;
 mod int;
+mod table;
 mod uuid;
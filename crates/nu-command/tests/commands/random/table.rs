@@ -0,0 +1,52 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn generates_the_number_of_rows() -> Result {
+    test()
+        .run("random table --rows 25 --schema {name: name} | length")
+        .expect_value_eq(25)
+}
+
+#[test]
+fn generates_the_columns_of_the_schema() -> Result {
+    test()
+        .run("random table --rows 1 --schema {id: uuid, name: name, email: email} | columns")
+        .expect_value_eq(["id", "name", "email"])
+}
+
+#[test]
+fn generates_ints_in_range() -> Result {
+    let code = "random table --rows 200 --schema {age: 'int(18..20)'} | get age | all { $in >= 18 and $in <= 20 }";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn generates_values_of_a_list() -> Result {
+    let code =
+        "random table --rows 50 --schema {status: [on off]} | get status | all { $in in [on off] }";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn generates_dates_in_range() -> Result {
+    let code = "random table --rows 50 --schema {day: 'date(2024-02-28..2024-03-01)'} | get day | format date '%Y-%m-%d' | all { $in in ['2024-02-28' '2024-02-29' '2024-03-01'] }";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn generates_the_same_table_for_a_seed() -> Result {
+    let code = "
+        let schema = {name: name, id: uuid, score: float}
+        (random table --seed 7 --schema $schema) == (random table --seed 7 --schema $schema)
+    ";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn fails_on_unknown_kind() -> Result {
+    let err = test()
+        .run("random table --schema {a: color}")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
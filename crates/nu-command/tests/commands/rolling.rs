@@ -0,0 +1,76 @@
+use nu_test_support::nu;
+
+#[test]
+fn moving_aggregates() {
+    for (args, expected) in [
+        ("sum --size 2", "[null, 4, 8, 12]"),
+        ("sum --size 2 --partial", "[1, 4, 8, 12]"),
+        ("min --size 3", "[null, null, 1, 3]"),
+        ("max --size 3 --partial", "[1, 3, 5, 7]"),
+        ("avg --size 3", "[null, null, 3.0, 5.0]"),
+    ] {
+        let actual = nu!(format!("[1 3 5 7] | rolling {args} | to nuon"));
+        assert_eq!(actual.out, expected, "rolling {args}");
+    }
+}
+
+#[test]
+fn cumulative_aggregates() {
+    for (args, expected) in [
+        ("sum", "[3, 4, 9, 11]"),
+        ("min", "[3, 1, 1, 1]"),
+        ("max", "[3, 3, 5, 5]"),
+        ("avg", "[3.0, 2.0, 3.0, 2.75]"),
+    ] {
+        let actual = nu!(format!("[3 1 5 2] | rolling {args} | to nuon"));
+        assert_eq!(actual.out, expected, "rolling {args}");
+    }
+}
+
+#[test]
+fn lag_and_lead() {
+    for (args, expected) in [
+        ("lag", "[null, 1, 2, 3]"),
+        ("lag --offset 2", "[null, null, 1, 2]"),
+        ("lead", "[2, 3, 4, null]"),
+        ("lead -n 3", "[4, null, null, null]"),
+    ] {
+        let actual = nu!(format!("[1 2 3 4] | rolling {args} | to nuon"));
+        assert_eq!(actual.out, expected, "rolling {args}");
+    }
+}
+
+#[test]
+fn adds_a_column_to_tables() {
+    let actual = nu!("[[t x]; [1 2] [2 4] [3 6]] | rolling avg x --size 2 | to nuon");
+    assert_eq!(
+        actual.out,
+        "[[t, x, x_avg]; [1, 2, null], [2, 4, 3.0], [3, 6, 5.0]]"
+    );
+}
+
+#[test]
+fn ranks_a_column() {
+    let actual = nu!(
+        "[[n score]; [a 5] [b 9] [c 5]] | rolling rank score --into place | get place | to nuon"
+    );
+    assert_eq!(actual.out, "[1, 3, 1]");
+}
+
+#[test]
+fn moving_aggregates_stream() {
+    let actual = nu!("1.. | rolling sum --size 3 | first 4 | to nuon");
+    assert_eq!(actual.out, "[null, null, 6, 9]");
+}
+
+#[test]
+fn errors_on_unknown_operation() {
+    let actual = nu!("[1 2] | rolling median");
+    assert!(actual.err.contains("Expected one of"));
+}
+
+#[test]
+fn errors_on_zero_size() {
+    let actual = nu!("[1 2] | rolling sum --size 0");
+    assert!(actual.err.contains("Expected a positive number"));
+}
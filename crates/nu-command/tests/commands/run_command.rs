@@ -0,0 +1,98 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn run_command_dispatches_by_name() -> Result {
+    let code = r#"
+        def "op add" [a: int, b: int] { $a + $b }
+        def "op mul" [a: int, b: int] { $a * $b }
+        ['add' 'mul'] | each {|op| run-command $"op ($op)" 3 4 }
+    "#;
+    test().run(code).expect_value_eq([7, 12])
+}
+
+#[test]
+fn run_command_passes_flags_and_input() -> Result {
+    let code = r#"
+        def greet [name: string, --loud, --greeting: string = 'hello'] {
+            let text = $"($greeting) ($name)($in)"
+            if $loud { $text | str upcase } else { $text }
+        }
+        '!' | run-command greet --loud --greeting=hi bob
+    "#;
+    test().run(code).expect_value_eq("HI BOB!")
+}
+
+#[test]
+fn run_command_runs_builtins() -> Result {
+    let code = "'hello world' | run-command 'str replace' --all o '0'";
+    test().run(code).expect_value_eq("hell0 w0rld")
+}
+
+#[test]
+fn run_command_checks_argument_types() -> Result {
+    let err = test()
+        .run("def double [x: int] { $x * 2 }; run-command double 'two'")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::CantConvert { .. }));
+    Ok(())
+}
+
+#[test]
+fn run_command_checks_missing_arguments() -> Result {
+    let err = test()
+        .run("def double [x: int] { $x * 2 }; run-command double")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::MissingParameter { .. }));
+    Ok(())
+}
+
+#[test]
+fn run_command_rejects_unknown_flags() -> Result {
+    let err = test()
+        .run("def double [x: int] { $x * 2 }; run-command double --triple 2")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
+
+#[test]
+fn run_command_unknown_command() -> Result {
+    let err = test()
+        .run("run-command no-such-command")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::CommandNotFound { .. }));
+    Ok(())
+}
+
+#[test]
+fn run_command_keeps_caller_arguments() -> Result {
+    let code = "def one [] { 1 }; [(run-command one) (run-command one)] | math sum";
+    test().run(code).expect_value_eq(2)
+}
+
+#[test]
+fn run_command_parses_inline_flag_values_with_their_shape() -> Result {
+    let code = "def twice [--count: int] { $count * 2 }; run-command twice --count=3";
+    test().run(code).expect_value_eq(6)
+}
+
+#[test]
+fn run_command_checks_inline_flag_values() -> Result {
+    let err = test()
+        .run("def twice [--count: int] { $count * 2 }; run-command twice --count=three")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::CantConvert { .. }));
+    Ok(())
+}
+
+#[test]
+fn run_command_skips_hidden_overlays() -> Result {
+    let code = "
+        module spam { export def foo [] { 'foo' } }
+        overlay use spam
+        if true { overlay hide spam; run-command foo }
+    ";
+    let err = test().run(code).expect_shell_error()?;
+    assert!(matches!(err, ShellError::CommandNotFound { .. }));
+    Ok(())
+}
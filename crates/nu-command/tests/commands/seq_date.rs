@@ -17,3 +17,59 @@ fn fails_when_increment_not_integer_or_duration() {
             .contains("expected one of a list of accepted shapes: [Duration, Int]")
     )
 }
+
+#[test]
+fn weekdays_only_counts_periods_of_business_days() {
+    let actual = nu!(
+        "seq date --begin-date 2024-03-08 --periods 3 --weekdays-only --holidays ['2024-03-11'] | str join ','"
+    );
+
+    assert_eq!(actual.out, "2024-03-08,2024-03-12,2024-03-13");
+}
+
+#[test]
+fn holidays_accept_dates() {
+    let actual = nu!(
+        "seq date --begin-date 2024-12-24 --end-date 2024-12-27 --holidays [2024-12-25 2024-12-26] | str join ','"
+    );
+
+    assert_eq!(actual.out, "2024-12-24,2024-12-27");
+}
+
+#[test]
+fn every_last_day_of_month() {
+    let actual = nu!(
+        "seq date --begin-date 2024-01-01 --end-date 2024-04-30 --every 'last day' | str join ','"
+    );
+
+    assert_eq!(actual.out, "2024-01-31,2024-02-29,2024-03-31,2024-04-30");
+}
+
+#[test]
+fn every_weekday_in_reverse() {
+    let actual =
+        nu!("seq date --begin-date 2024-01-31 --periods 2 --every monday --reverse | str join ','");
+
+    assert_eq!(actual.out, "2024-01-29,2024-01-22");
+}
+
+#[test]
+fn fails_on_invalid_recurrence() {
+    let actual = nu!("seq date --begin-date 2024-01-01 --every '6th monday'");
+
+    assert!(actual.err.contains("nu::shell::incorrect_value"));
+}
+
+#[test]
+fn fails_when_every_is_used_with_increment() {
+    let actual = nu!("seq date --begin-date 2024-01-01 --every monday --increment 2day");
+
+    assert!(actual.err.contains("nu::shell::incompatible_parameters"));
+}
+
+#[test]
+fn fails_when_no_date_can_be_kept() {
+    let actual = nu!("seq date --begin-date 2024-01-01 --periods 1 --weekdays-only --every sunday");
+
+    assert!(actual.err.contains("no more dates to print"));
+}
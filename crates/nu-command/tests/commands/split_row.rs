@@ -1,4 +1,4 @@
-use nu_test_support::fs::Stub::FileWithContentToBeTrimmed;
+use nu_test_support::fs::Stub::{FileWithContent, FileWithContentToBeTrimmed};
 use nu_test_support::nu;
 use nu_test_support::playground::Playground;
 
@@ -50,3 +50,40 @@ fn to_row() {
         assert!(actual.out.contains("list<string>"));
     })
 }
+
+#[test]
+fn split_row_streams_byte_stream_input() {
+    let actual = nu!(r"
+        1.. | each { $in } | to text | split row -r '\n+' | first 3 | str join ','
+    ");
+
+    assert_eq!(actual.out, "1,2,3");
+}
+
+#[test]
+fn split_row_byte_stream_matches_string_input() {
+    Playground::setup("split_row_test_streaming", |dirs, sandbox| {
+        let data = "a  b c\n\nd   e\n".repeat(2000);
+        sandbox.with_files(&[FileWithContent("data.txt", &data)]);
+
+        let actual = nu!(cwd: dirs.test(), r"
+            (open data.txt | split row -r '\s+') == (open data.txt | collect | split row -r '\s+')
+        ");
+        assert_eq!(actual.out, "true");
+
+        let actual = nu!(cwd: dirs.test(), r"
+            open data.txt | split row -n 3 ' ' | last | str length
+        ");
+        assert_eq!(actual.out, (data.trim_end().len() - 3).to_string());
+    })
+}
+
+#[test]
+fn split_row_byte_stream_drops_only_the_final_line_ending() {
+    Playground::setup("split_row_test_line_ending", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("data.txt", "a,b,\r\n\r\n")]);
+
+        let actual = nu!(cwd: dirs.test(), "open data.txt | split row ',' | to nuon");
+        assert_eq!(actual.out, r#"[a, b, "\r\n"]"#);
+    })
+}
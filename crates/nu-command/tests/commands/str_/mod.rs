@@ -267,11 +267,31 @@ fn find_and_replaces_regex_closure_error() {
     );
 }
 
+#[test]
+fn find_and_replaces_regex_with_closure_without_capture_groups() {
+    let actual = nu!(r#"
+         '3 apples and 12 pears'
+         | str replace --all -r '\d+' {|n| ($n | into int) * 2 }
+     "#);
+
+    assert_eq!(actual.out, "6 apples and 24 pears");
+}
+
+#[test]
+fn find_and_replaces_with_closure_returning_number() {
+    let actual = nu!(r#"
+         'version 7'
+         | str replace '7' { into int | $in + 1 }
+     "#);
+
+    assert_eq!(actual.out, "version 8");
+}
+
 #[test]
 fn find_and_replaces_closure_type_mismatch() {
     let actual = nu!(r#"
          'source string'
-         | str replace 'str' { 42 }
+         | str replace 'str' { {a: 42} }
      "#);
 
     let err = actual.err;
@@ -286,7 +306,7 @@ fn find_and_replaces_closure_type_mismatch() {
 fn find_and_replaces_regex_closure_type_mismatch() {
     let actual = nu!(r#"
          'source string'
-         | str replace -r 'str' { 42 }
+         | str replace -r 'str' { {a: 42} }
      "#);
 
     let err = actual.err;
@@ -470,3 +490,43 @@ fn test_redirection_trim() {
 
     assert_eq!(actual.out, "7");
 }
+
+#[test]
+fn distance_with_unknown_algorithm() {
+    let actual = nu!("'nushell' | str distance --algorithm hamming 'nutshell'");
+
+    assert!(
+        actual
+            .err
+            .contains("Expected one of levenshtein, jaro or jaro-winkler")
+    );
+}
+
+#[test]
+fn fuzzy_match_ranks_streamed_strings() {
+    let actual = nu!(r#"
+        [checkout clone commit] | each { $in } | str fuzzy-match comit | get value | str join ','
+    "#);
+
+    assert_eq!(actual.out, "commit,clone,checkout");
+}
+
+#[test]
+fn fuzzy_match_ignores_case() {
+    let actual = nu!(r#"
+        [COMMIT] | str fuzzy-match commit --ignore-case | get 0.score | $in == 1.0
+    "#);
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn fuzzy_match_threshold_out_of_range() {
+    let actual = nu!("[commit] | str fuzzy-match comit --threshold 80");
+
+    assert!(
+        actual
+            .err
+            .contains("The threshold has to be between 0 and 1")
+    );
+}
@@ -3616,19 +3616,19 @@ fn table_list() {
     let actual = nu!("table --list");
     assert_eq!(
         actual.out,
-        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         │╰────┴────────────────╯"
+        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         ││ 19 │ custom         │╰────┴────────────────╯"
     );
 
     let actual = nu!("ls | table --list");
     assert_eq!(
         actual.out,
-        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         │╰────┴────────────────╯"
+        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         ││ 19 │ custom         │╰────┴────────────────╯"
     );
 
     let actual = nu!("table --list --theme basic");
     assert_eq!(
         actual.out,
-        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         │╰────┴────────────────╯"
+        "╭────┬────────────────╮│  0 │ basic          ││  1 │ compact        ││  2 │ compact_double ││  3 │ default        ││  4 │ heavy          ││  5 │ light          ││  6 │ none           ││  7 │ reinforced     ││  8 │ rounded        ││  9 │ thin           ││ 10 │ with_love      ││ 11 │ psql           ││ 12 │ markdown       ││ 13 │ dots           ││ 14 │ restructured   ││ 15 │ ascii_rounded  ││ 16 │ basic_compact  ││ 17 │ single         ││ 18 │ double         ││ 19 │ custom         │╰────┴────────────────╯"
     );
 }
 
@@ -4069,3 +4069,102 @@ fn metadata_path_columns_multiple_with_icons() {
     let expected = "\u{1b}[39m╭───┬────────┬────────────╮\u{1b}[0m\u{1b}[39m│\u{1b}[0m \u{1b}[1;32m#\u{1b}[0m \u{1b}[39m│\u{1b}[0m  \u{1b}[1;32mdir\u{1b}[0m   \u{1b}[39m│\u{1b}[0m    \u{1b}[1;32mfile\u{1b}[0m    \u{1b}[39m│\u{1b}[0m\u{1b}[39m├───┼────────┼────────────┤\u{1b}[0m\u{1b}[39m│\u{1b}[0m \u{1b}[1;32m0\u{1b}[0m \u{1b}[39m│\u{1b}[0m \u{1b}[39m\u{1b}[38;2;126;142;168m\u{f115}\u{1b}[0m  \u{1b}[38;5;81msrc\u{1b}[0m\u{1b}[0m \u{1b}[39m│\u{1b}[0m \u{1b}[39m\u{1b}[38;2;222;165;132m\u{e68b}\u{1b}[0m  \u{1b}[38;5;48mmain.rs\u{1b}[0m\u{1b}[0m \u{1b}[39m│\u{1b}[0m\u{1b}[39m╰───┴────────┴────────────╯\u{1b}[0m";
     assert_eq!(actual.out, expected);
 }
+
+#[test]
+fn table_column_config() {
+    let actual = nu!(concat!(
+        "$env.config.table.columns = {",
+        "host: {hidden: true},",
+        r#"message: {max_width: 7, trim: {methodology: truncating, truncating_suffix: "…"}},"#,
+        "level: {alignment: right}",
+        "};",
+        r#"[[level message host]; [warn "disk almost full" web1]] | table --width=80"#
+    ));
+    assert_eq!(
+        actual.out,
+        "╭───┬───────┬─────────╮│ # │ level │ message │├───┼───────┼─────────┤│ 0 │  warn │ disk a… │╰───┴───────┴─────────╯"
+    );
+}
+
+#[test]
+fn table_column_config_show_hidden() {
+    let actual = nu!(concat!(
+        "$env.config.table.columns = {host: {hidden: true}, level: {alignment: right}};",
+        r#"[[level message host]; [warn "disk a…" web1]] | table --width=80 --all"#
+    ));
+    assert_eq!(
+        actual.out,
+        "╭───┬───────┬─────────┬──────╮│ # │ level │ message │ host │├───┼───────┼─────────┼──────┤│ 0 │  warn │ disk a… │ web1 │╰───┴───────┴─────────┴──────╯"
+    );
+}
+
+#[test]
+fn table_column_config_flag() {
+    let actual = nu!(r#"[[a]; [abcdef]] | table --width=80 --column-config {a: {max_width: 3}}"#);
+    assert_eq!(
+        actual.out,
+        "╭───┬─────╮│ # │  a  │├───┼─────┤│ 0 │ abc ││   │ def │╰───┴─────╯"
+    );
+
+    let actual = nu!(r#"[[a]; [abcdef]] | table --column-config {a: {max_width: -1}}"#);
+    assert!(actual.err.contains("positive integer"));
+}
+
+#[test]
+fn table_page_without_terminal() {
+    let actual = nu!("[[a b]; [1 2]] | table --width=80 --page");
+    assert_eq!(
+        actual.out,
+        "╭───┬───┬───╮│ # │ a │ b │├───┼───┼───┤│ 0 │ 1 │ 2 │╰───┴───┴───╯"
+    );
+}
+
+#[test]
+fn table_custom_theme() {
+    let actual = nu!(concat!(
+        "$env.config.table.mode = 'custom';",
+        "$env.config.table.custom_theme = {base: basic, vertical: '!', row_separator_every: 1};",
+        "[[a]; [1] [2]] | table --width=80"
+    ));
+    assert_eq!(
+        actual.out,
+        "+---+---+| # ! a |+---+---+| 0 ! 1 |+---+---+| 1 ! 2 |+---+---+"
+    );
+}
+
+#[test]
+fn table_custom_theme_outer_border_only() {
+    let actual = nu!(concat!(
+        "$env.config.table.custom_theme = {outer_border_only: true};",
+        "[[a]; [1] [2]] | table --width=80 --theme custom"
+    ));
+    assert_eq!(
+        actual.out,
+        "╭──────╮│ #  a │├──────┤│ 0  1 ││ 1  2 │╰──────╯"
+    );
+}
+
+#[test]
+fn table_custom_theme_rejects_custom_base() {
+    let actual = nu!("$env.config.table.custom_theme = {base: custom}");
+    assert!(actual.err.contains("a built-in table mode"));
+}
+
+#[test]
+fn table_number_format() {
+    let actual = nu!(concat!(
+        "$env.config.table.numbers = {group_digits: true};",
+        "$env.config.table.columns = {price: {currency: '$'}};",
+        "[[count price]; [1234567 1999]] | table --width=80"
+    ));
+    assert_eq!(
+        actual.out,
+        "╭───┬───────────┬───────────╮│ # │   count   │   price   │├───┼───────────┼───────────┤│ 0 │ 1,234,567 │ $1,999.00 │╰───┴───────────┴───────────╯"
+    );
+
+    let actual = nu!(concat!(
+        "$env.config.table.numbers = {group_digits: true};",
+        "[[count]; [1234567]] | get 0.count | $in + 1"
+    ));
+    assert_eq!(actual.out, "1234568");
+}
@@ -211,7 +211,7 @@ fn get_error_columns() {
     let actual = nu!(" try { non_existent_command } catch {|err| $err} | columns | to json -r");
     assert_eq!(
         actual.out,
-        "[\"msg\",\"debug\",\"raw\",\"rendered\",\"json\"]"
+        "[\"msg\",\"code\",\"debug\",\"raw\",\"rendered\",\"json\"]"
     );
 }
 
@@ -342,3 +342,43 @@ fn catch_finally_with_variable() {
     );
     assert_eq!(actual.out, "true");
 }
+
+#[test]
+fn catch_can_match_on_error_code() {
+    let output = nu!(r#"
+        try { 1 / 0 } catch { |err|
+            match $err.code {
+                "nu::shell::division_by_zero" => "division",
+                _ => "other",
+            }
+        }
+    "#);
+
+    assert_eq!(output.out, "division");
+}
+
+#[test]
+fn catch_error_code_of_external_command() {
+    let output = nu!("try { nu --testbin fail } catch { |err| $err.code }");
+
+    assert_eq!(output.out, "nu::shell::non_zero_exit_code");
+}
+
+#[test]
+fn catch_error_code_of_error_make() {
+    let output = nu!(r#"
+        [
+            (try { error make {msg: oops} } catch { |err| $err.code })
+            (try { error make {msg: oops, code: my::oops} } catch { |err| $err.code })
+        ] | str join ' '
+    "#);
+
+    assert_eq!(output.out, "nu::shell::error my::oops");
+}
+
+#[test]
+fn catch_error_code_inside_closure() {
+    let output = nu!("try { [0] | each { 1 / $in } } catch { |err| $err.code }");
+
+    assert_eq!(output.out, "nu::shell::division_by_zero");
+}
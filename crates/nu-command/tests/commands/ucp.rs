@@ -1335,3 +1335,75 @@ fn test_cp_wildcards() {
         assert!(files_exist_at(&[".a"], dirs.test()));
     });
 }
+
+#[test]
+fn copy_dir_with_verify() {
+    Playground::setup("ucp_test_verify", |dirs, sandbox| {
+        sandbox.mkdir("dir_a").with_files(&[
+            FileWithContent("dir_a/first.txt", "first"),
+            FileWithContent("dir_a/second.txt", "second"),
+        ]);
+        sandbox
+            .mkdir("dir_a/nested")
+            .with_files(&[FileWithContent("dir_a/nested/third.txt", "third")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "cp -r --verify sha256 dir_a dir_b"
+        );
+
+        assert!(actual.err.is_empty());
+        assert_eq!(
+            file_contents(dirs.test().join("dir_b/nested/third.txt")),
+            "third"
+        );
+    });
+}
+
+#[test]
+fn copy_into_dir_with_verify() {
+    Playground::setup("ucp_test_verify_into_dir", |dirs, sandbox| {
+        sandbox
+            .mkdir("dir_b")
+            .with_files(&[FileWithContent("first.txt", "first")]);
+
+        let actual = nu!(cwd: dirs.test(), "cp --verify sha256 first.txt dir_b");
+
+        assert!(actual.err.is_empty());
+        assert!(dirs.test().join("dir_b/first.txt").exists());
+    });
+}
+
+#[test]
+fn verify_with_unknown_algorithm_fails() {
+    Playground::setup("ucp_test_verify_algorithm", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("first.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "cp --verify md5 first.txt second.txt");
+
+        assert!(actual.err.contains("expected one of sha256"));
+        assert!(!dirs.test().join("second.txt").exists());
+    });
+}
+
+#[test]
+fn verify_conflicts_with_update() {
+    Playground::setup("ucp_test_verify_update", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("first.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "cp --update --verify sha256 first.txt second.txt");
+
+        assert!(actual.err.contains("can't verify the copies"));
+    });
+}
+
+#[test]
+fn reflink_with_unknown_mode_fails() {
+    Playground::setup("ucp_test_reflink_mode", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("first.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "cp --reflink sometimes first.txt second.txt");
+
+        assert!(actual.err.contains("expected one of auto, always, never"));
+    });
+}
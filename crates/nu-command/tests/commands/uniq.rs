@@ -192,3 +192,24 @@ fn table_with_ignore_case() {
 
     assert_eq!(actual.out, expected.out);
 }
+
+#[test]
+fn uniq_streams_infinite_input() {
+    let actual = nu!("1.. | each { $in mod 3 } | uniq | first 3 | to nuon");
+
+    assert_eq!(actual.out, "[1, 2, 0]");
+}
+
+#[test]
+fn uniq_approximate() {
+    let actual = nu!("1..10000 | each { $in mod 1000 } | uniq --approximate | length");
+
+    assert_eq!(actual.out, "1000");
+}
+
+#[test]
+fn uniq_approximate_with_count() {
+    let actual = nu!("[1 2 2] | uniq --approximate --count");
+
+    assert!(actual.err.contains("--approximate can't be used with"));
+}
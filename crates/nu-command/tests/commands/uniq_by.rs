@@ -148,3 +148,12 @@ fn wrong_column() {
 
     assert!(actual.err.contains("cannot find column 'column1'"));
 }
+
+#[test]
+fn uniq_by_streams_infinite_input() {
+    let actual = nu!(
+        "1.. | each { {n: $in, parity: ($in mod 2)} } | uniq-by parity | first 2 | get n | to nuon"
+    );
+
+    assert_eq!(actual.out, "[1, 2]");
+}
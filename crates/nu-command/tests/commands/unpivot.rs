@@ -0,0 +1,36 @@
+use nu_test_support::nu;
+
+#[test]
+fn unpivots_all_columns_by_default() {
+    let actual = nu!("[[a b]; [1 2]] | unpivot | to nuon");
+    assert_eq!(actual.out, "[[variable, value]; [a, 1], [b, 2]]");
+}
+
+#[test]
+fn unpivots_with_index_and_on() {
+    let actual = nu!("[[id a b c]; [1 2 3 4]] | unpivot --index [id] --on [c a] | to nuon");
+    assert_eq!(actual.out, "[[id, variable, value]; [1, c, 4], [1, a, 2]]");
+}
+
+#[test]
+fn missing_columns_are_null() {
+    let actual = nu!("[{id: 1 a: 2} {a: 3}] | unpivot --index [id] --on [a b] | to nuon");
+    assert_eq!(
+        actual.out,
+        "[[id, variable, value]; [1, a, 2], [1, b, null], [null, a, 3], [null, b, null]]"
+    );
+}
+
+#[test]
+fn streams_the_input() {
+    let actual = nu!(
+        "1.. | each { {id: $in x: ($in * 2)} } | unpivot --index [id] | first 2 | get value | to nuon"
+    );
+    assert_eq!(actual.out, "[2, 4]");
+}
+
+#[test]
+fn errors_on_clashing_names() {
+    let actual = nu!("[[a b]; [1 2]] | unpivot --variable-name x --value-name x");
+    assert!(actual.err.contains("need different names"));
+}
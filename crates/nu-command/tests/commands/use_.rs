@@ -357,3 +357,159 @@ fn report_errors_in_export_env() {
 
     assert!(actual.err.contains("reported"));
 }
+
+#[cfg(feature = "network")]
+mod remote {
+    use mockito::Server;
+    use nu_test_support::fs::Stub::FileWithContent;
+    use nu_test_support::nu;
+    use nu_test_support::playground::Playground;
+
+    const MODULE: &str = "export def greet [] { 'hello from the web' }";
+
+    fn cache_env(dirs: &nu_test_support::playground::Dirs) -> Vec<(String, String)> {
+        vec![(
+            "XDG_CACHE_HOME".to_string(),
+            dirs.test().join("cache").to_string_lossy().into_owned(),
+        )]
+    }
+
+    #[test]
+    fn use_module_from_url() {
+        Playground::setup("use_module_from_url", |dirs, _| {
+            let mut server = Server::new();
+            let _mock = server.mock("GET", "/greet.nu").with_body(MODULE).create();
+            let url = format!("{}/greet.nu", server.url());
+
+            nu!(cwd: dirs.test(), envs: cache_env(&dirs), format!("http fetch-module {url}"));
+            let actual = nu!(
+                cwd: dirs.test(),
+                envs: cache_env(&dirs),
+                format!("use {url}; greet greet")
+            );
+
+            assert_eq!(actual.out, "hello from the web");
+            assert!(dirs.test().join("nu-modules.lock").exists());
+        })
+    }
+
+    #[test]
+    fn use_module_from_url_does_not_download_it() {
+        Playground::setup("use_module_from_url_does_not_download_it", |dirs, _| {
+            let mut server = Server::new();
+            let mock = server
+                .mock("GET", "/greet.nu")
+                .with_body(MODULE)
+                .expect(0)
+                .create();
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                envs: cache_env(&dirs),
+                format!("use {}/greet.nu *; greet", server.url())
+            );
+
+            assert!(actual.err.contains("Module is not in the lockfile"));
+            assert!(actual.err.contains("http fetch-module"));
+            assert!(!dirs.test().join("nu-modules.lock").exists());
+            mock.assert();
+        })
+    }
+
+    #[test]
+    fn use_module_from_url_uses_the_cache() {
+        Playground::setup("use_module_from_url_uses_the_cache", |dirs, _| {
+            let mut server = Server::new();
+            let mock = server
+                .mock("GET", "/greet.nu")
+                .with_body(MODULE)
+                .expect(1)
+                .create();
+            let url = format!("{}/greet.nu", server.url());
+            let command = format!("use {url} *; greet");
+
+            nu!(cwd: dirs.test(), envs: cache_env(&dirs), format!("http fetch-module {url}"));
+            nu!(cwd: dirs.test(), envs: cache_env(&dirs), command.clone());
+            let actual = nu!(cwd: dirs.test(), envs: cache_env(&dirs), command);
+
+            assert_eq!(actual.out, "hello from the web");
+            mock.assert();
+        })
+    }
+
+    #[test]
+    fn fetch_module_checks_the_lockfile() {
+        Playground::setup("fetch_module_checks_the_lockfile", |dirs, sandbox| {
+            let mut server = Server::new();
+            let _mock = server.mock("GET", "/greet.nu").with_body(MODULE).create();
+            let url = format!("{}/greet.nu", server.url());
+            let lockfile = format!(
+                r#"{{"{url}": "0000000000000000000000000000000000000000000000000000000000000000"}}"#
+            );
+            sandbox.with_files(&[FileWithContent("nu-modules.lock", &lockfile)]);
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                envs: cache_env(&dirs),
+                format!("http fetch-module {url}")
+            );
+
+            assert!(actual.err.contains("Checksum mismatch"));
+        })
+    }
+
+    #[test]
+    fn fetch_module_frozen_needs_the_module_in_the_lockfile() {
+        Playground::setup(
+            "fetch_module_frozen_needs_the_module_in_the_lockfile",
+            |dirs, _| {
+                let mut server = Server::new();
+                let _mock = server.mock("GET", "/greet.nu").with_body(MODULE).create();
+
+                let actual = nu!(
+                    cwd: dirs.test(),
+                    envs: cache_env(&dirs),
+                    format!("http fetch-module --frozen {}/greet.nu", server.url())
+                );
+
+                assert!(actual.err.contains("Module is not in the lockfile"));
+                assert!(!dirs.test().join("nu-modules.lock").exists());
+            },
+        )
+    }
+
+    #[test]
+    fn fetch_module_without_urls_fetches_the_lockfile() {
+        Playground::setup(
+            "fetch_module_without_urls_fetches_the_lockfile",
+            |dirs, _| {
+                let mut server = Server::new();
+                let mock = server
+                    .mock("GET", "/greet.nu")
+                    .with_body(MODULE)
+                    .expect(2)
+                    .create();
+                let url = format!("{}/greet.nu", server.url());
+
+                nu!(cwd: dirs.test(), envs: cache_env(&dirs), format!("http fetch-module {url}"));
+                // A fresh cache, as on another machine with the same lockfile
+                let fresh_cache = vec![(
+                    "XDG_CACHE_HOME".to_string(),
+                    dirs.test()
+                        .join("other-cache")
+                        .to_string_lossy()
+                        .into_owned(),
+                )];
+                nu!(cwd: dirs.test(), envs: fresh_cache.clone(), "http fetch-module --frozen");
+                let actual = nu!(
+                    cwd: dirs.test(),
+                    envs: fresh_cache,
+                    format!("use {url} *; greet")
+                );
+
+                assert_eq!(actual.out, "hello from the web");
+                mock.assert();
+            },
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn parses_version_1_fields() -> Result {
+    let code = "'6ba7b810-9dad-11d1-80b4-00c04fd430c8' | uuid parse | [$in.version $in.node_id ($in.timestamp | format date '%Y-%m-%d')]";
+    test()
+        .run(code)
+        .expect_value_eq((1, "00:c0:4f:d4:30:c8", "1998-02-04"))
+}
+
+#[test]
+fn parses_version_4_without_timestamp() -> Result {
+    let code = "random uuid -v 4 | uuid parse | [$in.version $in.timestamp]";
+    test().run(code).expect_value_eq((4, ()))
+}
+
+#[test]
+fn random_version_7_round_trips() -> Result {
+    let code = "let id = random uuid -v 7; ($id | uuid parse).uuid == $id";
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn parses_uuids_in_a_column() -> Result {
+    let code =
+        "[[id]; ['urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8']] | uuid parse id | get 0.id.uuid";
+    test()
+        .run(code)
+        .expect_value_eq("67e55044-10b1-426f-9247-bb680e5fe0c8")
+}
+
+#[test]
+fn fails_on_invalid_uuid() -> Result {
+    let err = test()
+        .run("'67e55044-10b1' | uuid parse")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::CantConvert { .. }));
+    Ok(())
+}
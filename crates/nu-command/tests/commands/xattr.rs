@@ -0,0 +1,81 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn set_and_get_attribute() {
+    Playground::setup("xattr_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "xattr set notes.txt user.reviewed yes; xattr get notes.txt user.reviewed"
+        );
+
+        assert_eq!(actual.out, "yes");
+    })
+}
+
+#[test]
+fn get_all_attributes_as_record() {
+    Playground::setup("xattr_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "xattr set notes.txt user.a one; xattr set notes.txt user.b 0x[ff]; xattr get notes.txt | columns | sort | to nuon"
+        );
+
+        assert_eq!(actual.out, "[user.a, user.b]");
+    })
+}
+
+#[test]
+fn invalid_utf8_is_binary() {
+    Playground::setup("xattr_test_binary", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "xattr set notes.txt user.b 0x[ff]; xattr get notes.txt user.b | to nuon"
+        );
+
+        assert_eq!(actual.out, "0x[FF]");
+    })
+}
+
+#[test]
+fn remove_attribute() {
+    Playground::setup("xattr_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "xattr set notes.txt user.a 1; xattr set --remove notes.txt user.a; ls --extended | get xattrs.0 | length"
+        );
+
+        assert_eq!(actual.out, "0");
+    })
+}
+
+#[test]
+fn missing_attribute_is_an_error() {
+    Playground::setup("xattr_test_4", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "xattr get notes.txt user.missing");
+
+        assert!(actual.err.contains("Extended attribute not found"));
+    })
+}
+
+#[test]
+fn set_requires_a_value() {
+    Playground::setup("xattr_test_5", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("notes.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "xattr set notes.txt user.a");
+
+        assert!(actual.err.contains("value"));
+    })
+}
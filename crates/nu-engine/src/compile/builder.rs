@@ -173,6 +173,7 @@ impl BlockBuilder {
                         | Literal::Float(_)
                         | Literal::Filesize(_)
                         | Literal::Duration(_)
+                        | Literal::Rate(_)
                         | Literal::Binary(_)
                         | Literal::Block(_)
                         | Literal::Closure(_)
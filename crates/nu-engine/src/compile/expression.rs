@@ -600,6 +600,7 @@ fn literal_from_value_with_unit(value_with_unit: &ValueWithUnit) -> Result<Liter
         })? {
         Value::Filesize { val, .. } => Ok(Literal::Filesize(val)),
         Value::Duration { val, .. } => Ok(Literal::Duration(val)),
+        Value::Rate { val, .. } => Ok(Literal::Rate(val)),
         other => Err(CompileError::InvalidLiteral {
             msg: format!("bad value returned by Unit::build_value(): {other:?}"),
             span: value_with_unit.unit.span,
@@ -1,8 +1,9 @@
 use nu_protocol::{
     IntoSpanned, RegId, Span, Type, VarId,
-    ast::{Block, Call, Expr, Expression},
+    ast::{Block, Call, CellPath, Expr, Expression, ListItem, PathMember, RecordItem},
+    casing::Casing,
     engine::StateWorkingSet,
-    ir::Instruction,
+    ir::{Instruction, Literal},
 };
 
 use super::{BlockBuilder, CompileError, RedirectModes, compile_block, compile_expression};
@@ -332,7 +333,6 @@ pub(crate) fn compile_let(
     };
 
     let var_decl_arg = call.positional_nth(0).ok_or_else(invalid)?;
-    let var_id = var_decl_arg.as_var().ok_or_else(invalid)?;
 
     // Handle the two syntax forms:
     // 1. `let var = expr`: compile expr and store result
@@ -340,20 +340,32 @@ pub(crate) fn compile_let(
     let has_initial_value = call.positional_nth(1).is_some();
     if has_initial_value {
         // Safe to use expect here since we just checked is_some()
-        let block_arg = call.positional_nth(1).expect("checked above");
-        let block_id = block_arg.as_block().ok_or_else(invalid)?;
-        let block = working_set.get_block(block_id);
+        let value_arg = call.positional_nth(1).expect("checked above");
 
-        // Pass the input_reg to the block so expressions like `let x = (str length)`
-        // can access the pipeline input from the enclosing context
-        compile_block(
-            working_set,
-            builder,
-            block,
-            RedirectModes::value(call.head),
-            input_reg,
-            io_reg,
-        )?;
+        if let Some(block_id) = value_arg.as_block() {
+            let block = working_set.get_block(block_id);
+
+            // Pass the input_reg to the block so expressions like `let x = (str length)`
+            // can access the pipeline input from the enclosing context
+            compile_block(
+                working_set,
+                builder,
+                block,
+                RedirectModes::value(call.head),
+                input_reg,
+                io_reg,
+            )?;
+        } else {
+            // Destructured closure parameters are assigned from the parameter's variable
+            compile_expression(
+                working_set,
+                builder,
+                value_arg,
+                RedirectModes::value(call.head),
+                None,
+                io_reg,
+            )?;
+        }
     } else if let Some(input_reg) = input_reg {
         // For `let var` without =, assign the input value
         builder.push(Instruction::Collect { src_dst: input_reg }.into_spanned(call.head))?;
@@ -369,6 +381,19 @@ pub(crate) fn compile_let(
     }
     // If no initial_value and no input_reg, io_reg should already be empty (this shouldn't normally occur)
 
+    // `let {name, version} = expr`: assign each variable its part of the value
+    if matches!(var_decl_arg.expr, Expr::Record(_) | Expr::List(_)) {
+        if !has_initial_value {
+            return Err(invalid());
+        }
+        builder.push(Instruction::Collect { src_dst: io_reg }.into_spanned(call.head))?;
+        compile_destructure(builder, var_decl_arg, io_reg)?;
+        builder.add_comment("let");
+        builder.load_empty(io_reg)?;
+        return Ok(());
+    }
+
+    let var_id = var_decl_arg.as_var().ok_or_else(invalid)?;
     let variable = working_set.get_variable(var_id);
 
     // If the variable is annotated with type `glob`, convert the value to
@@ -413,6 +438,93 @@ pub(crate) fn compile_let(
     Ok(())
 }
 
+/// Store the value in `src` into the variables of a destructuring target, like `{name, version}`.
+///
+/// Each record field or list item is followed into the value in turn, so a missing one is an
+/// error just like with a cell path. `src` is consumed.
+fn compile_destructure(
+    builder: &mut BlockBuilder,
+    target: &Expression,
+    src: RegId,
+) -> Result<(), CompileError> {
+    // Pseudocode, for each member of the target:
+    //
+    //   clone %item, %src
+    //   %path <- <member>
+    //   follow-cell-path %item, %path
+    //   ...<store %item into the member's target>...
+    //
+    // and then:
+    //
+    //   drop %src
+    //
+    // where storing into a variable is:
+    //
+    //   store-variable $var, %src
+    //   drop %src
+    let invalid = |span| CompileError::InvalidKeywordCall {
+        keyword: "let".into(),
+        span,
+    };
+
+    let members: Vec<(PathMember, &Expression)> = match &target.expr {
+        Expr::VarDecl(var_id) => {
+            builder.push(
+                Instruction::StoreVariable {
+                    var_id: *var_id,
+                    src,
+                }
+                .into_spanned(target.span),
+            )?;
+            return builder.drop_reg(src);
+        }
+        Expr::Record(items) => items
+            .iter()
+            .map(|item| match item {
+                RecordItem::Pair(key, value) => key
+                    .as_string()
+                    .map(|key| {
+                        (
+                            PathMember::string(key, false, Casing::Sensitive, value.span),
+                            value,
+                        )
+                    })
+                    .ok_or_else(|| invalid(key.span)),
+                RecordItem::Spread(span, _) => Err(invalid(*span)),
+            })
+            .collect::<Result<_, _>>()?,
+        Expr::List(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| match item {
+                ListItem::Item(value) => Ok((PathMember::int(index, false, value.span), value)),
+                ListItem::Spread(span, _) => Err(invalid(*span)),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err(invalid(target.span)),
+    };
+
+    for (member, value) in members {
+        let item_reg = builder.clone_reg(src, value.span)?;
+        let path_reg = builder.literal(
+            Literal::CellPath(Box::new(CellPath {
+                members: vec![member],
+            }))
+            .into_spanned(value.span),
+        )?;
+        builder.push(
+            Instruction::FollowCellPath {
+                src_dst: item_reg,
+                path: path_reg,
+            }
+            .into_spanned(value.span),
+        )?;
+        compile_destructure(builder, value, item_reg)?;
+    }
+
+    builder.drop_reg(src)
+}
+
 /// Compile a call to `try`, setting an error handler over the evaluated block
 pub(crate) fn compile_try(
     working_set: &StateWorkingSet,
@@ -1,4 +1,4 @@
-use crate::eval_ir::eval_ir_block;
+use crate::eval_ir::{MemoLookup, eval_ir_block, memo_lookup, memoize_output};
 #[allow(deprecated)]
 use crate::get_full_help;
 use nu_protocol::{
@@ -12,6 +12,26 @@ use nu_protocol::{
 use nu_utils::IgnoreCaseExt;
 use std::sync::Arc;
 
+/// The default value of a parameter, computing it on the callee's stack if it isn't a constant.
+fn default_value<D: DebugContext>(
+    engine_state: &EngineState,
+    callee_stack: &mut Stack,
+    value: &Option<Value>,
+    block_id: Option<BlockId>,
+    span: Span,
+) -> Result<Option<Value>, ShellError> {
+    match block_id {
+        Some(block_id) => {
+            let block = engine_state.get_block(block_id);
+            eval_block::<D>(engine_state, callee_stack, block, PipelineData::empty())?
+                .body
+                .into_value(span)
+                .map(Some)
+        }
+        None => Ok(value.clone()),
+    }
+}
+
 pub fn eval_call<D: DebugContext>(
     engine_state: &EngineState,
     caller_stack: &mut Stack,
@@ -73,8 +93,14 @@ pub fn eval_call<D: DebugContext>(
                     });
                 }
                 callee_stack.add_var(var_id, result);
-            } else if let Some(value) = &param.default_value {
-                callee_stack.add_var(var_id, value.to_owned());
+            } else if let Some(value) = default_value::<D>(
+                engine_state,
+                &mut callee_stack,
+                &param.default_value,
+                param.default_block,
+                call.head,
+            )? {
+                callee_stack.add_var(var_id, value);
             } else {
                 callee_stack.add_var(var_id, Value::nothing(call.head));
             }
@@ -115,8 +141,14 @@ pub fn eval_call<D: DebugContext>(
                                 let result = eval_expression::<D>(engine_state, caller_stack, arg)?;
 
                                 callee_stack.add_var(var_id, result);
-                            } else if let Some(value) = &named.default_value {
-                                callee_stack.add_var(var_id, value.to_owned());
+                            } else if let Some(value) = default_value::<D>(
+                                engine_state,
+                                &mut callee_stack,
+                                &named.default_value,
+                                named.default_block,
+                                call.head,
+                            )? {
+                                callee_stack.add_var(var_id, value);
                             } else {
                                 callee_stack.add_var(var_id, Value::bool(true, call.head))
                             }
@@ -127,8 +159,14 @@ pub fn eval_call<D: DebugContext>(
                             let result = eval_expression::<D>(engine_state, caller_stack, arg)?;
 
                             callee_stack.add_var(var_id, result);
-                        } else if let Some(value) = &named.default_value {
-                            callee_stack.add_var(var_id, value.to_owned());
+                        } else if let Some(value) = default_value::<D>(
+                            engine_state,
+                            &mut callee_stack,
+                            &named.default_value,
+                            named.default_block,
+                            call.head,
+                        )? {
+                            callee_stack.add_var(var_id, value);
                         } else {
                             callee_stack.add_var(var_id, Value::bool(true, call.head))
                         }
@@ -139,7 +177,13 @@ pub fn eval_call<D: DebugContext>(
                 if !found {
                     if named.arg.is_none() {
                         callee_stack.add_var(var_id, Value::bool(false, call.head))
-                    } else if let Some(value) = named.default_value {
+                    } else if let Some(value) = default_value::<D>(
+                        engine_state,
+                        &mut callee_stack,
+                        &named.default_value,
+                        named.default_block,
+                        call.head,
+                    )? {
                         callee_stack.add_var(var_id, value);
                     } else {
                         callee_stack.add_var(var_id, Value::nothing(call.head))
@@ -148,10 +192,36 @@ pub fn eval_call<D: DebugContext>(
             }
         }
 
+        // Commands marked with `@memo` return their cached result for the same arguments
+        let memo = decl.memo_options();
+        let (input, memo_key) = match &memo {
+            Some(memo) => {
+                match memo_lookup(
+                    engine_state,
+                    call.decl_id,
+                    memo,
+                    &callee_stack,
+                    input,
+                    call.head,
+                )? {
+                    MemoLookup::Cached(output) => return Ok(output),
+                    MemoLookup::Miss { input, key } => (input, Some(key)),
+                }
+            }
+            None => (input, None),
+        };
+
         let result =
             eval_block_with_early_return::<D>(engine_state, &mut callee_stack, block, input)
                 .map(|p| p.body);
 
+        let result = match (memo, memo_key) {
+            (Some(memo), Some(key)) => result.and_then(|output| {
+                memoize_output(engine_state, call.decl_id, &memo, key, output, call.head)
+            }),
+            _ => result,
+        };
+
         if block.redirect_env {
             redirect_env(engine_state, caller_stack, &callee_stack);
         }
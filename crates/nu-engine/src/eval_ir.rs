@@ -1,26 +1,32 @@
-use std::{borrow::Cow, fs::File, sync::Arc};
+use std::{
+    borrow::Cow,
+    fs::File,
+    sync::{Arc, PoisonError},
+};
 
 use nu_path::{expand_path, expand_path_with};
 #[cfg(feature = "os")]
 use nu_protocol::process::check_exit_status_future;
 use nu_protocol::{
-    DeclId, ENV_VARIABLE_ID, Flag, IntoPipelineData, IntoSpanned, ListStream, OutDest,
-    PipelineData, PipelineExecutionData, PositionalArg, Range, Record, RegId, ShellError, Signals,
-    Signature, Span, Spanned, Type, Value, VarId,
+    BlockId, ByteStreamType, DeclId, ENV_VARIABLE_ID, Flag, IntoPipelineData, IntoSpanned,
+    ListStream, OutDest, PipelineData, PipelineExecutionData, PositionalArg, Range, Record, RegId,
+    ShellError, Signals, Signature, Span, Spanned, Type, Value, VarId,
     ast::{Bits, Block, Boolean, CellPath, Comparison, Math, Operator},
     combined_type_string,
     debugger::DebugContext,
     engine::{
-        Argument, Closure, EngineState, EnvName, ErrorHandler, Matcher, Redirection, Stack,
-        StateWorkingSet,
+        Argument, Closure, EngineState, EnvName, ErrorHandler, Matcher, MemoKey, MemoOptions,
+        Redirection, Stack, StateWorkingSet,
     },
     ir::{Call, DataSlice, Instruction, IrAstRef, IrBlock, Literal, RedirectMode},
+    record,
     shell_error::io::IoError,
 };
 use nu_utils::IgnoreCaseExt;
 
 use crate::{
     ENV_CONVERSIONS, convert_env_vars, eval::is_automatic_env_var, eval_block_with_early_return,
+    get_eval_block, redirect_env,
 };
 
 pub fn eval_ir_block<D: DebugContext>(
@@ -41,12 +47,70 @@ pub fn eval_ir_block<D: DebugContext>(
         });
     }
 
+    let mut tail_call = match eval_ir_block_frame::<D>(engine_state, stack, block, input)? {
+        BlockExit::Return(data) => return Ok(data),
+        BlockExit::TailCall(tail_call) => tail_call,
+    };
+
+    // The block ended with a call to a custom command, which we run here in its place rather than
+    // nested inside of it, so that recursion in tail position doesn't grow the Rust stack.
+    loop {
+        let block = engine_state.get_block(tail_call.block_id);
+        // Without growing the stack, a tail call that never ends would otherwise run forever
+        engine_state.signals().check(&block.span)?;
+        if tail_call.stack.recursion_count > maximum_call_stack_depth {
+            return Err(ShellError::RecursionLimitReached {
+                recursion_limit: maximum_call_stack_depth,
+                span: block.span,
+            });
+        }
+
+        let output = match eval_ir_block_frame::<D>(
+            engine_state,
+            &mut tail_call.stack,
+            block,
+            tail_call.input,
+        ) {
+            Ok(BlockExit::Return(data)) => data.body,
+            Ok(BlockExit::TailCall(mut next)) => {
+                // Recursion adds the same check each time, which only has to be done once
+                for check in tail_call.output_checks.into_iter().rev() {
+                    if !next.output_checks.contains(&check) {
+                        next.output_checks.insert(0, check);
+                    }
+                }
+                tail_call = next;
+                continue;
+            }
+            // The called command handles its own `return`, the same as in `eval_call`
+            Err(ShellError::Return { value, .. }) => PipelineData::value(*value, None),
+            Err(err) => return Err(err),
+        };
+
+        let output = tail_call
+            .output_checks
+            .iter()
+            .try_fold(output, |output, (types, head)| {
+                check_output_types(output, types, *head)
+            })?;
+        return Ok(PipelineExecutionData::from(output));
+    }
+}
+
+/// Evaluate a single IR block, up to its return or tail call.
+fn eval_ir_block_frame<D: DebugContext>(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block: &Block,
+    input: PipelineData,
+) -> Result<BlockExit, ShellError> {
     if let Some(ir_block) = &block.ir_block {
         D::enter_block(engine_state, block);
 
         let args_base = stack.arguments.get_base();
         let error_handler_base = stack.error_handlers.get_base();
         let finally_handler_base = stack.finally_run_handlers.get_base();
+        let deferred_base = stack.deferred.len();
 
         // Allocate and initialize registers. I've found that it's not really worth trying to avoid
         // the heap allocation here by reusing buffers - our allocator is fast enough
@@ -67,6 +131,9 @@ pub fn eval_ir_block<D: DebugContext>(
                 args_base,
                 error_handler_base,
                 finally_handler_base,
+                deferred_base,
+                // The environment of a `def --env` command has to stay in its own frame
+                tail_calls: !block.redirect_env,
                 redirect_out: None,
                 redirect_err: None,
                 matches: vec![],
@@ -81,6 +148,8 @@ pub fn eval_ir_block<D: DebugContext>(
         stack.finally_run_handlers.leave_frame(finally_handler_base);
         stack.arguments.leave_frame(args_base);
 
+        let result = run_deferred::<D>(engine_state, stack, deferred_base, result);
+
         D::leave_block(engine_state, block);
 
         result
@@ -96,6 +165,93 @@ pub fn eval_ir_block<D: DebugContext>(
     }
 }
 
+/// Run the blocks registered with `defer` while the current block was evaluated, most recent
+/// first, once it has finished with `result`.
+fn run_deferred<D: DebugContext>(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    deferred_base: usize,
+    mut result: Result<BlockExit, ShellError>,
+) -> Result<BlockExit, ShellError> {
+    if stack.deferred.len() <= deferred_base {
+        return result;
+    }
+
+    // Anything the block streams has to be read before it gets cleaned up after
+    if let Ok(BlockExit::Return(data)) = &mut result
+        && matches!(
+            data.body,
+            PipelineData::ListStream(..) | PipelineData::ByteStream(..)
+        )
+    {
+        let body = std::mem::replace(&mut data.body, PipelineData::empty());
+        let span = body.span().unwrap_or(Span::unknown());
+        match body.into_value(span) {
+            Ok(value) => data.body = value.into_pipeline_data(),
+            Err(err) => result = Err(err),
+        }
+    }
+
+    // Cleanup also has to happen after Ctrl-C, so it can't be interrupted by the same signal
+    let engine_state = if engine_state.signals().interrupted() {
+        let mut engine_state = engine_state.clone();
+        engine_state.set_signals(Signals::empty());
+        Cow::Owned(engine_state)
+    } else {
+        Cow::Borrowed(engine_state)
+    };
+
+    while stack.deferred.len() > deferred_base {
+        let Some(closure) = stack.deferred.pop() else {
+            break;
+        };
+        let block = engine_state.get_block(closure.block_id);
+        let mut callee_stack = stack
+            .captures_to_stack_preserve_out_dest(closure.captures)
+            .reset_out_dest();
+        let output = eval_block_with_early_return::<D>(
+            &engine_state,
+            &mut callee_stack,
+            block,
+            PipelineData::empty(),
+        )
+        .and_then(|data| data.body.drain());
+
+        // Deferred blocks can restore the environment of the block they were registered in
+        redirect_env(&engine_state, stack, &callee_stack);
+
+        // An error from cleanup is only reported if the block didn't already fail on its own
+        if let Err(err) = output
+            && matches!(
+                result,
+                Ok(_) | Err(ShellError::Return { .. } | ShellError::Exit { .. })
+            )
+        {
+            result = Err(err);
+        }
+    }
+
+    result
+}
+
+/// How the evaluation of an IR block ended
+enum BlockExit {
+    Return(PipelineExecutionData),
+    TailCall(TailCall),
+}
+
+/// A call to a custom command that is the last thing a block does, ready to be run in place of
+/// that block
+#[derive(Debug)]
+struct TailCall {
+    block_id: BlockId,
+    stack: Stack,
+    input: PipelineData,
+    /// The declared output types of the commands called in tail position so far, which their
+    /// common output still has to be checked against
+    output_checks: Vec<(Vec<Type>, Span)>,
+}
+
 /// All of the pointers necessary for evaluation
 struct EvalContext<'a> {
     engine_state: &'a EngineState,
@@ -109,6 +265,10 @@ struct EvalContext<'a> {
     error_handler_base: usize,
     /// Base index on the finally handler stack to reset to after a call
     finally_handler_base: usize,
+    /// Number of deferred blocks registered before this block started
+    deferred_base: usize,
+    /// Whether calls in tail position can be run in place of this block
+    tail_calls: bool,
     /// State set by redirect-out
     redirect_out: Option<Redirection>,
     /// State set by redirect-err
@@ -198,7 +358,7 @@ fn eval_ir_block_impl<D: DebugContext>(
     ctx: &mut EvalContext<'_>,
     ir_block: &IrBlock,
     input: PipelineData,
-) -> Result<PipelineExecutionData, ShellError> {
+) -> Result<BlockExit, ShellError> {
     if !ctx.registers.is_empty() {
         ctx.registers[0] = PipelineExecutionData::from(input);
     }
@@ -215,7 +375,15 @@ fn eval_ir_block_impl<D: DebugContext>(
 
         D::enter_instruction(ctx.engine_state, ir_block, pc, ctx.registers);
 
-        let result = eval_instruction::<D>(ctx, instruction, span, ast, need_backtrace);
+        let result = match instruction {
+            Instruction::Call { decl_id, src_dst } if ret_val.is_none() => {
+                match tail_call_block(ctx, ir_block, pc, *decl_id, *src_dst) {
+                    Some(block_id) => eval_tail_call(ctx, block_id, *span, *src_dst),
+                    None => eval_instruction::<D>(ctx, instruction, span, ast, need_backtrace),
+                }
+            }
+            _ => eval_instruction::<D>(ctx, instruction, span, ast, need_backtrace),
+        };
 
         D::leave_instruction(
             ctx.engine_state,
@@ -237,9 +405,12 @@ fn eval_ir_block_impl<D: DebugContext>(
                 // `Shell::Return` first. If so, we need to respect that value.
                 match ret_val {
                     Some(err) => return Err(err),
-                    None => return Ok(ctx.take_reg(reg_id)),
+                    None => return Ok(BlockExit::Return(ctx.take_reg(reg_id))),
                 }
             }
+            Ok(InstructionResult::TailCall(tail_call)) => {
+                return Ok(BlockExit::TailCall(tail_call));
+            }
             Err(err @ (ShellError::Continue { .. } | ShellError::Break { .. })) => {
                 return Err(err);
             }
@@ -324,6 +495,7 @@ enum InstructionResult {
     Continue,
     Branch(usize),
     Return(RegId),
+    TailCall(TailCall),
 }
 
 /// Perform an instruction
@@ -993,6 +1165,7 @@ fn literal_value(
         Literal::Float(f) => Value::float(*f, span),
         Literal::Filesize(q) => Value::filesize(*q, span),
         Literal::Duration(q) => Value::duration(*q, span),
+        Literal::Rate(q) => Value::rate(*q, span),
         Literal::Binary(bin) => Value::binary(&ctx.data[*bin], span),
         Literal::Block(block_id) | Literal::RowCondition(block_id) | Literal::Closure(block_id) => {
             let block = ctx.engine_state.get_block(*block_id);
@@ -1086,48 +1259,48 @@ fn binary_op(
 
     let result = match op {
         Operator::Comparison(cmp) => match cmp {
-            Comparison::Equal => lhs_val.eq(op_span, &rhs_val, span)?,
-            Comparison::NotEqual => lhs_val.ne(op_span, &rhs_val, span)?,
-            Comparison::LessThan => lhs_val.lt(op_span, &rhs_val, span)?,
-            Comparison::GreaterThan => lhs_val.gt(op_span, &rhs_val, span)?,
-            Comparison::LessThanOrEqual => lhs_val.lte(op_span, &rhs_val, span)?,
-            Comparison::GreaterThanOrEqual => lhs_val.gte(op_span, &rhs_val, span)?,
+            Comparison::Equal => lhs_val.eq(op_span, &rhs_val, span),
+            Comparison::NotEqual => lhs_val.ne(op_span, &rhs_val, span),
+            Comparison::LessThan => lhs_val.lt(op_span, &rhs_val, span),
+            Comparison::GreaterThan => lhs_val.gt(op_span, &rhs_val, span),
+            Comparison::LessThanOrEqual => lhs_val.lte(op_span, &rhs_val, span),
+            Comparison::GreaterThanOrEqual => lhs_val.gte(op_span, &rhs_val, span),
             Comparison::RegexMatch => {
-                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, false, span)?
+                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, false, span)
             }
             Comparison::NotRegexMatch => {
-                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, true, span)?
+                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, true, span)
             }
-            Comparison::In => lhs_val.r#in(op_span, &rhs_val, span)?,
-            Comparison::NotIn => lhs_val.not_in(op_span, &rhs_val, span)?,
-            Comparison::Has => lhs_val.has(op_span, &rhs_val, span)?,
-            Comparison::NotHas => lhs_val.not_has(op_span, &rhs_val, span)?,
-            Comparison::StartsWith => lhs_val.starts_with(op_span, &rhs_val, span)?,
-            Comparison::NotStartsWith => lhs_val.not_starts_with(op_span, &rhs_val, span)?,
-            Comparison::EndsWith => lhs_val.ends_with(op_span, &rhs_val, span)?,
-            Comparison::NotEndsWith => lhs_val.not_ends_with(op_span, &rhs_val, span)?,
+            Comparison::In => lhs_val.r#in(op_span, &rhs_val, span),
+            Comparison::NotIn => lhs_val.not_in(op_span, &rhs_val, span),
+            Comparison::Has => lhs_val.has(op_span, &rhs_val, span),
+            Comparison::NotHas => lhs_val.not_has(op_span, &rhs_val, span),
+            Comparison::StartsWith => lhs_val.starts_with(op_span, &rhs_val, span),
+            Comparison::NotStartsWith => lhs_val.not_starts_with(op_span, &rhs_val, span),
+            Comparison::EndsWith => lhs_val.ends_with(op_span, &rhs_val, span),
+            Comparison::NotEndsWith => lhs_val.not_ends_with(op_span, &rhs_val, span),
         },
         Operator::Math(mat) => match mat {
-            Math::Add => lhs_val.add(op_span, &rhs_val, span)?,
-            Math::Subtract => lhs_val.sub(op_span, &rhs_val, span)?,
-            Math::Multiply => lhs_val.mul(op_span, &rhs_val, span)?,
-            Math::Divide => lhs_val.div(op_span, &rhs_val, span)?,
-            Math::FloorDivide => lhs_val.floor_div(op_span, &rhs_val, span)?,
-            Math::Modulo => lhs_val.modulo(op_span, &rhs_val, span)?,
-            Math::Pow => lhs_val.pow(op_span, &rhs_val, span)?,
-            Math::Concatenate => lhs_val.concat(op_span, &rhs_val, span)?,
+            Math::Add => lhs_val.add(op_span, &rhs_val, span),
+            Math::Subtract => lhs_val.sub(op_span, &rhs_val, span),
+            Math::Multiply => lhs_val.mul(op_span, &rhs_val, span),
+            Math::Divide => lhs_val.div(op_span, &rhs_val, span),
+            Math::FloorDivide => lhs_val.floor_div(op_span, &rhs_val, span),
+            Math::Modulo => lhs_val.modulo(op_span, &rhs_val, span),
+            Math::Pow => lhs_val.pow(op_span, &rhs_val, span),
+            Math::Concatenate => lhs_val.concat(op_span, &rhs_val, span),
         },
         Operator::Boolean(bl) => match bl {
-            Boolean::Or => lhs_val.or(op_span, &rhs_val, span)?,
-            Boolean::Xor => lhs_val.xor(op_span, &rhs_val, span)?,
-            Boolean::And => lhs_val.and(op_span, &rhs_val, span)?,
+            Boolean::Or => lhs_val.or(op_span, &rhs_val, span),
+            Boolean::Xor => lhs_val.xor(op_span, &rhs_val, span),
+            Boolean::And => lhs_val.and(op_span, &rhs_val, span),
         },
         Operator::Bits(bit) => match bit {
-            Bits::BitOr => lhs_val.bit_or(op_span, &rhs_val, span)?,
-            Bits::BitXor => lhs_val.bit_xor(op_span, &rhs_val, span)?,
-            Bits::BitAnd => lhs_val.bit_and(op_span, &rhs_val, span)?,
-            Bits::ShiftLeft => lhs_val.bit_shl(op_span, &rhs_val, span)?,
-            Bits::ShiftRight => lhs_val.bit_shr(op_span, &rhs_val, span)?,
+            Bits::BitOr => lhs_val.bit_or(op_span, &rhs_val, span),
+            Bits::BitXor => lhs_val.bit_xor(op_span, &rhs_val, span),
+            Bits::BitAnd => lhs_val.bit_and(op_span, &rhs_val, span),
+            Bits::ShiftLeft => lhs_val.bit_shl(op_span, &rhs_val, span),
+            Bits::ShiftRight => lhs_val.bit_shr(op_span, &rhs_val, span),
         },
         Operator::Assignment(_asg) => {
             return Err(ShellError::IrEvalError {
@@ -1135,7 +1308,9 @@ fn binary_op(
                 span: Some(span),
             });
         }
-    };
+    }
+    // A custom value on the right-hand side may define the operation if the left-hand side doesn't
+    .or_else(|err| rhs_val.reverse_custom_operation(&lhs_val, *op, op_span, err))?;
 
     ctx.put_reg(
         lhs_dst,
@@ -1170,38 +1345,16 @@ fn eval_call<D: DebugContext>(
     let result = (|| {
         if let Some(block_id) = decl.block_id() {
             // If the decl is a custom command
-            let block = engine_state.get_block(block_id);
-
-            // check types after acquiring block to avoid unnecessarily cloning Signature
-            check_input_types(&input, &block.signature, head)?;
-
-            // Set up a callee stack with the captures and move arguments from the stack into variables
-            let mut callee_stack = caller_stack.gather_captures(engine_state, &block.captures);
-
-            gather_arguments(
+            eval_custom_call::<D>(
                 engine_state,
-                block,
                 &mut caller_stack,
-                &mut callee_stack,
+                decl_id,
+                block_id,
+                head,
                 *args_base,
                 args_len,
-                head,
-            )?;
-
-            // Add one to the recursion count, so we don't recurse too deep. Stack overflows are not
-            // recoverable in Rust.
-            callee_stack.recursion_count += 1;
-
-            let result =
-                eval_block_with_early_return::<D>(engine_state, &mut callee_stack, block, input)
-                    .map(|p| p.body);
-
-            // Move environment variables back into the caller stack scope if requested to do so
-            if block.redirect_env {
-                redirect_env(engine_state, &mut caller_stack, &callee_stack);
-            }
-
-            result
+                input,
+            )
         } else {
             check_input_types(&input, &decl.signature(), head)?;
             // FIXME: precalculate this and save it somewhere
@@ -1243,6 +1396,223 @@ fn eval_call<D: DebugContext>(
     result
 }
 
+/// Run a call to a custom command, with its arguments on the caller's argument stack.
+#[allow(clippy::too_many_arguments)]
+fn eval_custom_call<D: DebugContext>(
+    engine_state: &EngineState,
+    caller_stack: &mut Stack,
+    decl_id: DeclId,
+    block_id: BlockId,
+    head: Span,
+    args_base: usize,
+    args_len: usize,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let decl = engine_state.get_decl(decl_id);
+    let block = engine_state.get_block(block_id);
+
+    // check types after acquiring block to avoid unnecessarily cloning Signature
+    check_input_types(&input, &block.signature, head)?;
+    let output_types = declared_output_types(&input, &block.signature);
+
+    // Set up a callee stack with the captures and move arguments from the stack into variables
+    let mut callee_stack = caller_stack.gather_captures(engine_state, &block.captures);
+
+    gather_arguments(
+        engine_state,
+        block,
+        caller_stack,
+        &mut callee_stack,
+        args_base,
+        args_len,
+        head,
+    )?;
+
+    // Commands marked with `@memo` return their cached result for the same arguments
+    let memo = decl.memo_options();
+    let (input, memo_key) = match &memo {
+        Some(memo) => match memo_lookup(engine_state, decl_id, memo, &callee_stack, input, head)? {
+            MemoLookup::Cached(output) => return Ok(output),
+            MemoLookup::Miss { input, key } => (input, Some(key)),
+        },
+        None => (input, None),
+    };
+
+    // Add one to the recursion count, so we don't recurse too deep. Stack overflows are not
+    // recoverable in Rust.
+    callee_stack.recursion_count += 1;
+
+    let result = eval_block_with_early_return::<D>(engine_state, &mut callee_stack, block, input)
+        .and_then(|p| match &output_types {
+            Some(types) => check_output_types(p.body, types, head),
+            None => Ok(p.body),
+        });
+
+    let result = match (memo, memo_key) {
+        (Some(memo), Some(key)) => result
+            .and_then(|output| memoize_output(engine_state, decl_id, &memo, key, output, head)),
+        _ => result,
+    };
+
+    // Move environment variables back into the caller stack scope if requested to do so
+    if block.redirect_env {
+        redirect_env(engine_state, caller_stack, &callee_stack);
+    }
+
+    result
+}
+
+/// Run a call to any command, with its arguments already pushed onto the stack, like the ones
+/// built with [`ir::Call::build`](nu_protocol::ir::Call::build).
+///
+/// Unlike [`Command::run`](nu_protocol::engine::Command::run), this also runs custom commands.
+/// The caller is still responsible for removing the call's arguments from the stack afterwards.
+pub fn eval_decl_call<D: DebugContext>(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let decl = engine_state.get_decl(call.decl_id);
+    if let Some(block_id) = decl.block_id() {
+        eval_custom_call::<D>(
+            engine_state,
+            stack,
+            call.decl_id,
+            block_id,
+            call.head,
+            call.args_base,
+            call.args_len,
+            input,
+        )
+    } else {
+        check_input_types(&input, &decl.signature(), call.head)?;
+        decl.run(engine_state, stack, &call.into(), input)
+    }
+}
+
+/// Find the block of a call to a custom command that can be run in place of the current block,
+/// because the call's output is returned right away, like `f` in `if $x { f } else { 1 }`.
+fn tail_call_block(
+    ctx: &EvalContext<'_>,
+    ir_block: &IrBlock,
+    pc: usize,
+    decl_id: DeclId,
+    src_dst: RegId,
+) -> Option<BlockId> {
+    // `try` and `finally` handlers, and deferred blocks, still need the current block after the call
+    if !ctx.tail_calls
+        || ctx.stack.error_handlers.get_base() != ctx.error_handler_base
+        || ctx.stack.finally_run_handlers.get_base() != ctx.finally_handler_base
+        || ctx.stack.deferred.len() != ctx.deferred_base
+        || has_exit_status(&ctx.registers[src_dst.get() as usize])
+    {
+        return None;
+    }
+
+    let decl = ctx.engine_state.get_decl(decl_id);
+    let block_id = decl.block_id()?;
+    // Memoized results are cached by the caller after the call
+    if ctx.engine_state.get_block(block_id).redirect_env || decl.memo_options().is_some() {
+        return None;
+    }
+
+    // Follow the jumps after the call, which is in tail position if they lead to its output being
+    // returned. Bounded by the number of instructions, so a loop of jumps can't hang here.
+    let mut next = pc + 1;
+    for _ in 0..ir_block.instructions.len() {
+        match ir_block.instructions.get(next)? {
+            Instruction::Jump { index } => next = *index,
+            Instruction::Return { src } => return (*src == src_dst).then_some(block_id),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Exit statuses of external commands earlier in the pipeline are checked by the caller after the
+/// call, so the caller can't be replaced.
+#[cfg(feature = "os")]
+fn has_exit_status(data: &PipelineExecutionData) -> bool {
+    data.exit.iter().any(Option::is_some)
+}
+
+#[cfg(not(feature = "os"))]
+fn has_exit_status(_data: &PipelineExecutionData) -> bool {
+    false
+}
+
+/// Set up a call to a custom command in tail position, the same way as [`eval_call`] does, but
+/// without running it.
+fn eval_tail_call(
+    ctx: &mut EvalContext<'_>,
+    block_id: BlockId,
+    head: Span,
+    src_dst: RegId,
+) -> Result<InstructionResult, ShellError> {
+    let input = ctx.take_reg(src_dst).body;
+    let EvalContext {
+        engine_state,
+        stack: caller_stack,
+        args_base,
+        redirect_out,
+        redirect_err,
+        ..
+    } = ctx;
+
+    let args_len = caller_stack.arguments.get_len(*args_base);
+    let mut caller_stack = caller_stack.push_redirection(redirect_out.take(), redirect_err.take());
+
+    let result = (|| {
+        let block = engine_state.get_block(block_id);
+        check_input_types(&input, &block.signature, head)?;
+        let output_checks = declared_output_types(&input, &block.signature)
+            .map(|types| vec![(types, head)])
+            .unwrap_or_default();
+
+        let mut callee_stack = caller_stack.gather_captures(engine_state, &block.captures);
+        gather_arguments(
+            engine_state,
+            block,
+            &mut caller_stack,
+            &mut callee_stack,
+            *args_base,
+            args_len,
+            head,
+        )?;
+
+        // The caller's scope ends here, so the callee can keep using it instead of adding its
+        // own, which would keep growing with each tail call. The recursion count stays the same
+        // for the same reason.
+        callee_stack.env_vars.pop();
+
+        // Except for a call without arguments or input, which repeats the same work each time,
+        // so it's still counted towards the limit to catch one that never ends
+        let no_input = matches!(
+            input,
+            PipelineData::Empty | PipelineData::Value(Value::Nothing { .. }, _)
+        );
+        if args_len == 0 && no_input {
+            callee_stack.recursion_count += 1;
+        }
+
+        Ok(InstructionResult::TailCall(TailCall {
+            block_id,
+            stack: callee_stack,
+            input,
+            output_checks,
+        }))
+    })();
+
+    drop(caller_stack);
+
+    ctx.stack.arguments.leave_frame(ctx.args_base);
+    ctx.redirect_out = None;
+    ctx.redirect_err = None;
+
+    result
+}
+
 fn find_named_var_id(
     sig: &Signature,
     name: &[u8],
@@ -1390,13 +1760,16 @@ fn gather_arguments(
     // Check for arguments that haven't yet been set and set them to their defaults
     for (positional_arg, _) in positional_iter {
         let var_id = expect_positional_var_id(positional_arg, call_head)?;
-        callee_stack.add_var(
-            var_id,
+        let val = if let Some(block_id) = positional_arg.default_block {
+            let ty = positional_arg.shape.to_type();
+            eval_default_block(engine_state, callee_stack, block_id, &ty, call_head)?
+        } else {
             positional_arg
                 .default_value
                 .clone()
-                .unwrap_or(Value::nothing(call_head)),
-        );
+                .unwrap_or(Value::nothing(call_head))
+        };
+        callee_stack.add_var(var_id, val);
     }
 
     for named_arg in &block.signature.named {
@@ -1405,12 +1778,19 @@ fn gather_arguments(
             // the stack. This assumes that the stack's variables was previously empty, but that's a
             // fair assumption for a brand new callee stack.
             if !callee_stack.vars.iter().any(|(id, _)| *id == var_id) {
-                let val = if named_arg.arg.is_none() {
-                    Value::bool(false, call_head)
-                } else if let Some(value) = &named_arg.default_value {
-                    value.clone()
-                } else {
-                    Value::nothing(call_head)
+                let val = match (&named_arg.arg, named_arg.default_block) {
+                    (None, _) => Value::bool(false, call_head),
+                    (Some(shape), Some(block_id)) => eval_default_block(
+                        engine_state,
+                        callee_stack,
+                        block_id,
+                        &shape.to_type(),
+                        call_head,
+                    )?,
+                    (Some(_), None) => named_arg
+                        .default_value
+                        .clone()
+                        .unwrap_or(Value::nothing(call_head)),
                 };
                 callee_stack.add_var(var_id, val);
             }
@@ -1420,6 +1800,23 @@ fn gather_arguments(
     Ok(())
 }
 
+/// Compute a default value that isn't a constant, using the arguments already on the callee's stack.
+fn eval_default_block(
+    engine_state: &EngineState,
+    callee_stack: &mut Stack,
+    block_id: BlockId,
+    ty: &Type,
+    call_head: Span,
+) -> Result<Value, ShellError> {
+    let block = engine_state.get_block(block_id);
+    let eval_block = get_eval_block(engine_state);
+    let val = eval_block(engine_state, callee_stack, block, PipelineData::empty())?
+        .body
+        .into_value(call_head)?;
+    check_type(&val, ty)?;
+    Ok(val)
+}
+
 /// Type check helper. Produces `CantConvert` error if `val` is not compatible with `ty`.
 fn check_type(val: &Value, ty: &Type) -> Result<(), ShellError> {
     match val {
@@ -1500,6 +1897,129 @@ fn check_input_types(
     }
 }
 
+/// The output types a custom command declares for its input, or `None` if any output is allowed
+fn declared_output_types(input: &PipelineData, signature: &Signature) -> Option<Vec<Type>> {
+    let io_types = &signature.input_output_types;
+    // Commands that only declare a nothing input type accept any input, see `check_input_types`
+    let any_input = io_types.iter().all(|(intype, _)| intype == &Type::Nothing);
+
+    let types: Vec<Type> = io_types
+        .iter()
+        .filter(|(intype, _)| any_input || input.is_subtype_of(intype))
+        .map(|(_, outtype)| outtype.clone())
+        .collect();
+
+    if types.is_empty() || types.contains(&Type::Any) {
+        None
+    } else {
+        Some(types)
+    }
+}
+
+/// Type check the output of a custom command against the output types it declares.
+///
+/// Streams are only checked by their kind, so that they stay lazy. No output at all is accepted
+/// for any type, like a command that returns early with nothing to return, but a command that
+/// declares a `nothing` output has to return nothing.
+fn check_output_types(
+    output: PipelineData,
+    types: &[Type],
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    match &output {
+        // errors are reported as they are, rather than as the wrong type
+        PipelineData::Value(Value::Error { .. }, ..) => Ok(output),
+        // bypass run-time typechecking for custom types
+        PipelineData::Value(Value::Custom { .. }, ..) => Ok(output),
+        // the output of externals has no known type until it's read
+        PipelineData::ByteStream(stream, ..) if stream.type_() == ByteStreamType::Unknown => {
+            Ok(output)
+        }
+        _ if output.is_nothing() => Ok(output),
+        _ if types.iter().any(|ty| output.is_subtype_of(ty)) => Ok(output),
+        _ => Err(ShellError::OnlySupportsThisOutputType {
+            exp_output_type: combined_type_string(types, "or").unwrap_or_default(),
+            wrong_type: output.get_type().to_string(),
+            call_span: head,
+        }),
+    }
+}
+
+/// The result of looking up a call to a command marked with `@memo` in the cache
+pub(crate) enum MemoLookup {
+    Cached(PipelineData),
+    /// The command has to run, with this input, and its result is cached under this key
+    Miss {
+        input: PipelineData,
+        key: MemoKey,
+    },
+}
+
+/// Look up a call to a command marked with `@memo`, whose arguments are in `callee_stack`.
+pub(crate) fn memo_lookup(
+    engine_state: &EngineState,
+    decl_id: DeclId,
+    memo: &MemoOptions,
+    callee_stack: &Stack,
+    input: PipelineData,
+    head: Span,
+) -> Result<MemoLookup, ShellError> {
+    let (input, input_key) = if memo.input {
+        let metadata = input.metadata();
+        let value = input.into_value(head)?;
+        (PipelineData::value(value.clone(), metadata), Some(value))
+    } else {
+        (input, None)
+    };
+    let key = memo_key(callee_stack, input_key, head)?;
+    let cached = engine_state
+        .memo_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(decl_id, &key);
+    Ok(match cached {
+        Some(value) => MemoLookup::Cached(value.into_pipeline_data()),
+        None => MemoLookup::Miss { input, key },
+    })
+}
+
+/// The key a call to a command marked with `@memo` is cached under: the values of its parameters,
+/// after defaults are filled in, and its input if the command is memoized by input too.
+fn memo_key(callee_stack: &Stack, input: Option<Value>, head: Span) -> Result<MemoKey, ShellError> {
+    let mut vars = callee_stack.vars.iter().collect::<Vec<_>>();
+    vars.sort_by_key(|(var_id, _)| *var_id);
+    let args = vars.into_iter().map(|(_, val)| val.clone()).collect();
+
+    MemoKey::new(&Value::record(
+        record! {
+            "args" => Value::list(args, head),
+            "input" => input.unwrap_or(Value::nothing(head)),
+        },
+        head,
+    ))
+}
+
+/// Collect the output of a command marked with `@memo` and cache it, unless it's an error.
+pub(crate) fn memoize_output(
+    engine_state: &EngineState,
+    decl_id: DeclId,
+    memo: &MemoOptions,
+    key: MemoKey,
+    output: PipelineData,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let metadata = output.metadata();
+    let value = output.into_value(head)?;
+    if !matches!(value, Value::Error { .. }) {
+        engine_state
+            .memo_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(decl_id, key, value.clone(), memo.ttl);
+    }
+    Ok(PipelineData::value(value, metadata))
+}
+
 /// Get variable from [`Stack`] or [`EngineState`]
 fn get_var(ctx: &EvalContext<'_>, var_id: VarId, span: Span) -> Result<Value, ShellError> {
     match var_id {
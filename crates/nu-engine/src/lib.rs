@@ -24,5 +24,5 @@ pub use eval::{
     eval_expression_with_input, eval_subexpression, eval_variable, redirect_env,
 };
 pub use eval_helpers::*;
-pub use eval_ir::eval_ir_block;
+pub use eval_ir::{eval_decl_call, eval_ir_block};
 pub use glob_from::glob_from;
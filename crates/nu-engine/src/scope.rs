@@ -1,5 +1,5 @@
 use nu_protocol::{
-    CommandWideCompleter, DeclId, ModuleId, Signature, Span, Type, Value, VarId,
+    CommandWideCompleter, DeclId, ModuleId, Record, Signature, Span, Type, Value, VarId,
     ast::Expr,
     engine::{Command, EngineState, Stack, Visibility},
     record,
@@ -73,17 +73,24 @@ impl<'e, 's> ScopeData<'e, 's> {
             let var_id_val = Value::int(var_id.get() as i64, span);
             let memory_size = Value::int(var_value.memory_size() as i64, span);
 
-            vars.push(Value::record(
-                record! {
-                    "name" => var_name,
-                    "type" => var_type,
-                    "value" => var_value,
-                    "is_const" => is_const,
-                    "var_id" => var_id_val,
-                    "mem_size" => memory_size,
-                },
-                span,
-            ));
+            let (description, extra_description) = self
+                .engine_state
+                .build_var_desc(**var_id)
+                .unwrap_or_default();
+
+            let mut record = record! {
+                "name" => var_name,
+                "type" => var_type,
+                "value" => var_value,
+                "is_const" => is_const,
+                "description" => Value::string(description, span),
+                "extra_description" => Value::string(extra_description, span),
+                "var_id" => var_id_val,
+                "mem_size" => memory_size,
+            };
+            self.push_location(&mut record, Some(var.declaration_span), span);
+
+            vars.push(Value::record(record, span));
         }
 
         sort_rows(&mut vars);
@@ -92,6 +99,7 @@ impl<'e, 's> ScopeData<'e, 's> {
 
     pub fn collect_commands(&self, span: Span) -> Vec<Value> {
         let mut commands = vec![];
+        let decl_modules = self.decl_modules();
 
         for (command_name, decl_id) in &self.decls_map {
             if self.visibility.is_decl_id_visible(decl_id)
@@ -129,7 +137,15 @@ impl<'e, 's> ScopeData<'e, 's> {
                     })
                     .collect();
 
-                let record = record! {
+                let module = decl_modules
+                    .get(*decl_id)
+                    .map(|module_id| {
+                        let module = self.engine_state.get_module(*module_id);
+                        Value::string(String::from_utf8_lossy(&module.name), span)
+                    })
+                    .unwrap_or(Value::nothing(span));
+
+                let mut record = record! {
                     "name" => Value::string(String::from_utf8_lossy(command_name), span),
                     "category" => Value::string(signature.category.to_string(), span),
                     "signatures" => self.collect_signatures(&signature, span),
@@ -148,7 +164,13 @@ impl<'e, 's> ScopeData<'e, 's> {
                         None => Value::nothing(span),
                     },
                     "decl_id" => Value::int(decl_id.get() as i64, span),
+                    "module" => module,
                 };
+                let definition = decl.decl_span().or_else(|| {
+                    decl.block_id()
+                        .and_then(|block_id| self.engine_state.get_block(block_id).span)
+                });
+                self.push_location(&mut record, definition, span);
 
                 commands.push(Value::record(record, span))
             }
@@ -159,6 +181,65 @@ impl<'e, 's> ScopeData<'e, 's> {
         commands
     }
 
+    /// Map each command defined in a module to that module.
+    ///
+    /// Modules that re-export a command are created after the module defining it, so the
+    /// module with the lowest id is the one it comes from.
+    fn decl_modules(&self) -> HashMap<DeclId, ModuleId> {
+        let mut decl_modules = HashMap::new();
+
+        for module_id in (0..self.engine_state.num_modules()).map(ModuleId::new) {
+            for decl_id in self.engine_state.get_module(module_id).decls.values() {
+                decl_modules.entry(*decl_id).or_insert(module_id);
+            }
+        }
+
+        decl_modules
+    }
+
+    /// Add the file, line (indexed from 1) and span where something was defined to its record.
+    ///
+    /// These are `nothing` for things without a definition in source code, like built-in commands.
+    fn push_location(&self, record: &mut Record, definition: Option<Span>, span: Span) {
+        let location = definition
+            .filter(|definition| *definition != Span::unknown())
+            .and_then(|definition| {
+                self.engine_state
+                    .files()
+                    .find(|file| file.covered_span.contains_span(definition))
+                    .map(|file| (file, definition))
+            });
+
+        match location {
+            Some((file, definition)) => {
+                let offset = definition.start - file.covered_span.start;
+                let line = file.content[..offset]
+                    .iter()
+                    .filter(|byte| **byte == b'\n')
+                    .count()
+                    + 1;
+
+                record.push("file", Value::string(file.name.as_ref(), span));
+                record.push("line", Value::int(line as i64, span));
+                record.push(
+                    "span",
+                    Value::record(
+                        record! {
+                            "start" => Value::int(definition.start as i64, span),
+                            "end" => Value::int(definition.end as i64, span),
+                        },
+                        span,
+                    ),
+                );
+            }
+            None => {
+                record.push("file", Value::nothing(span));
+                record.push("line", Value::nothing(span));
+                record.push("span", Value::nothing(span));
+            }
+        }
+    }
+
     fn collect_signatures(&self, signature: &Signature, span: Span) -> Value {
         let mut sigs = signature
             .input_output_types
@@ -53,6 +53,7 @@ pub fn nu_value_to_json(
         }
         nu_protocol::Value::Filesize { val, .. } => Value::Number(val.get().into()),
         nu_protocol::Value::Duration { val, .. } => Value::Number((*val).into()),
+        nu_protocol::Value::Rate { val, .. } => Value::Number(val.get().into()),
         nu_protocol::Value::Date { val, .. } => Value::String(val.to_string()),
         nu_protocol::Value::Glob { val, .. } => Value::String(val.to_string()),
         nu_protocol::Value::CellPath { val, .. } => {
@@ -277,6 +278,11 @@ pub fn json_to_nu_value_with_types(
                             return Ok(nu_protocol::Value::filesize(i, span));
                         }
                     }
+                    NuValueType::Rate => {
+                        if let Some(i) = n.as_i64() {
+                            return Ok(nu_protocol::Value::rate(i, span));
+                        }
+                    }
                     _ => {}
                 }
             }
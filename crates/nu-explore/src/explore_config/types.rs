@@ -48,6 +48,7 @@ pub enum NuValueType {
     Closure,
     Filesize,
     Duration,
+    Rate,
     Date,
     Glob,
     CellPath,
@@ -70,6 +71,7 @@ impl NuValueType {
             nu_protocol::Value::Closure { .. } => NuValueType::Closure,
             nu_protocol::Value::Filesize { .. } => NuValueType::Filesize,
             nu_protocol::Value::Duration { .. } => NuValueType::Duration,
+            nu_protocol::Value::Rate { .. } => NuValueType::Rate,
             nu_protocol::Value::Date { .. } => NuValueType::Date,
             nu_protocol::Value::Glob { .. } => NuValueType::Glob,
             nu_protocol::Value::CellPath { .. } => NuValueType::CellPath,
@@ -92,6 +94,7 @@ impl NuValueType {
             NuValueType::Closure => "closure",
             NuValueType::Filesize => "filesize",
             NuValueType::Duration => "duration",
+            NuValueType::Rate => "rate",
             NuValueType::Date => "date",
             NuValueType::Glob => "glob",
             NuValueType::CellPath => "cell-path",
@@ -114,6 +117,7 @@ impl NuValueType {
             NuValueType::Closure => Color::Cyan,
             NuValueType::Filesize => Color::LightMagenta,
             NuValueType::Duration => Color::LightMagenta,
+            NuValueType::Rate => Color::LightMagenta,
             NuValueType::Date => Color::LightYellow,
             NuValueType::Glob => Color::LightGreen,
             NuValueType::CellPath => Color::LightBlue,
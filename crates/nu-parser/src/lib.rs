@@ -23,5 +23,6 @@ pub use parse_keywords::*;
 
 pub use parser::{
     DURATION_UNIT_GROUPS, is_math_expression_like, parse, parse_block, parse_expression,
-    parse_external_call, parse_unit_value, trim_quotes, trim_quotes_str, unescape_unquote_string,
+    parse_external_call, parse_unit_value, parse_value, trim_quotes, trim_quotes_str,
+    unescape_unquote_string,
 };
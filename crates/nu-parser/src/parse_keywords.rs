@@ -39,6 +39,7 @@ use crate::{
     known_external::KnownExternal,
     lex,
     lite_parser::{LiteCommand, lite_parse},
+    parse_patterns::parse_destructure_target,
     parser::{
         ParsedInternalCall, garbage, garbage_pipeline, parse, parse_call, parse_expression,
         parse_full_signature, parse_import_pattern, parse_internal_call, parse_string,
@@ -367,6 +368,7 @@ pub fn parse_for(working_set: &mut StateWorkingSet, lite_command: &LiteCommand)
                 shape: var_type.to_shape(),
                 var_id: Some(*var_id),
                 default_value: None,
+                default_block: None,
                 completion: None,
             },
         );
@@ -1435,7 +1437,11 @@ pub fn parse_export_in_block(
         // Other definitions can't have attributes, so we handle attributes here with parse_attribute_block
         _ if lite_command.has_attributes() => parse_attribute_block(working_set, lite_command),
         "export alias" => parse_alias(working_set, lite_command, None),
-        "export const" => parse_const(working_set, &lite_command.parts[1..]).0,
+        "export const" => {
+            let pipeline = parse_const(working_set, &lite_command.parts[1..]).0;
+            add_variable_comments(working_set, &pipeline, &lite_command.comments);
+            pipeline
+        }
         "export use" => parse_use(working_set, lite_command, None).0,
         "export module" => parse_module(working_set, lite_command, None).0,
         _ => {
@@ -1657,6 +1663,7 @@ pub fn parse_export_in_module(
             }
             b"const" => {
                 let (mut pipeline, var_name_span) = parse_const(working_set, &spans[1..]);
+                add_variable_comments(working_set, &pipeline, &lite_command.comments);
 
                 // Trying to warp the 'const' call into the 'export const' in a very clumsy way
                 if !warp_export_call(working_set, &mut pipeline, "export const", spans) {
@@ -1976,9 +1983,11 @@ pub fn parse_module_block(
                 _ if command.has_attributes() => block
                     .pipelines
                     .push(parse_attribute_block(working_set, command)),
-                b"const" => block
-                    .pipelines
-                    .push(parse_const(working_set, &command.parts).0),
+                b"const" => {
+                    let pipeline = parse_const(working_set, &command.parts).0;
+                    add_variable_comments(working_set, &pipeline, &command.comments);
+                    block.pipelines.push(pipeline)
+                }
                 b"alias" => {
                     block.pipelines.push(parse_alias(
                         working_set,
@@ -2147,6 +2156,10 @@ pub fn parse_module_file_or_dir(
         return None;
     }
 
+    if is_module_url(&module_path_str) {
+        return parse_remote_module(working_set, &module_path_str, path_span, name_override);
+    }
+
     #[allow(deprecated)]
     let cwd = working_set.get_cwd();
 
@@ -2224,6 +2237,144 @@ pub fn parse_module_file_or_dir(
     }
 }
 
+fn is_module_url(path: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        path.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// Parse a module imported from a URL, from the local copy that the engine's
+/// [`RemoteModuleResolver`](nu_protocol::engine::RemoteModuleResolver) finds. Nothing is
+/// downloaded here, that's done beforehand by `http fetch-module`.
+///
+/// The module name is inferred from the last segment of the URL, unless specified in
+/// `name_override`.
+fn parse_remote_module(
+    working_set: &mut StateWorkingSet,
+    url: &str,
+    url_span: Span,
+    name_override: Option<String>,
+) -> Option<ModuleId> {
+    let Some(resolver) = working_set.permanent_state.remote_modules.clone() else {
+        working_set.error(ParseError::LabeledErrorWithHelp {
+            error: "Can't import modules from URLs".into(),
+            label: "importing from a URL is not supported here".into(),
+            help: "Nushell may have been built without networking support".into(),
+            span: url_span,
+        });
+        return None;
+    };
+
+    let module_name = name_override.or_else(|| {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let (_, file_name) = path.split_once("://")?.1.split_once('/')?;
+        let file_name = file_name.rsplit('/').next().unwrap_or_default();
+        let stem = file_name.strip_suffix(".nu").unwrap_or(file_name);
+        (!stem.is_empty()).then(|| stem.to_string())
+    });
+    let Some(module_name) = module_name else {
+        working_set.error(ParseError::LabeledErrorWithHelp {
+            error: "Can't infer the name of the module".into(),
+            label: "this URL has no file name".into(),
+            help: "Point the URL to a module file, like https://example.com/module.nu".into(),
+            span: url_span,
+        });
+        return None;
+    };
+
+    // The lockfile lives next to the file being parsed, or in the current directory
+    #[allow(deprecated)]
+    let lock_dir = match working_set.files.current_working_directory() {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from(working_set.get_cwd()),
+    };
+
+    let path = match resolver.locate(url, &lock_dir, url_span) {
+        Ok(path) => path,
+        Err(ShellError::GenericError {
+            error,
+            msg,
+            help: Some(help),
+            ..
+        }) => {
+            working_set.error(ParseError::LabeledErrorWithHelp {
+                error,
+                label: msg,
+                help,
+                span: url_span,
+            });
+            return None;
+        }
+        Err(err) => {
+            working_set.error(ParseError::LabeledError(
+                format!("Can't import module from {url}"),
+                err.to_string(),
+                url_span,
+            ));
+            return None;
+        }
+    };
+
+    parse_module_file(
+        working_set,
+        ParserPath::RealPath(path),
+        url_span,
+        Some(module_name),
+    )
+}
+
+/// Import a module from the autoload module directories, if `name_span` refers to one.
+///
+/// This is how modules in the autoload module directories are loaded lazily: the parser calls it
+/// when it can't find a command, and the module is then imported as if with `use <module>`. Since
+/// this happens during parsing, the module's `export-env` block is not evaluated.
+///
+/// Returns whether a module was imported.
+pub fn parse_autoload_module(working_set: &mut StateWorkingSet, name_span: Span) -> bool {
+    let name = working_set.get_span_contents(name_span);
+
+    // A module that's already in scope wouldn't make the command any easier to find
+    if working_set.find_module(name).is_some() {
+        return false;
+    }
+
+    let Some(path) = std::str::from_utf8(name)
+        .ok()
+        .and_then(|name| working_set.permanent_state.get_autoload_module(name))
+        .map(Path::to_path_buf)
+    else {
+        return false;
+    };
+
+    trace!("autoloading module from {}", path.display());
+
+    let Some(module_id) =
+        parse_module_file(working_set, ParserPath::RealPath(path), name_span, None)
+    else {
+        return false;
+    };
+
+    let module = working_set.get_module(module_id).clone();
+    let (definitions, errors) =
+        module.resolve_import_pattern(working_set, module_id, &[], None, name_span, &mut vec![]);
+    working_set.parse_errors.extend(errors);
+
+    let mut constants = definitions.constants;
+    for (name, const_val) in definitions.constant_values {
+        let const_var_id =
+            working_set.add_variable(name.clone(), name_span, const_val.get_type(), false);
+        working_set.set_variable_const_val(const_var_id, const_val);
+        constants.push((name, const_var_id));
+    }
+
+    working_set.use_decls(definitions.decls);
+    working_set.use_modules(definitions.modules);
+    working_set.use_variables(constants);
+
+    true
+}
+
 pub fn parse_module(
     working_set: &mut StateWorkingSet,
     lite_command: &LiteCommand,
@@ -3197,6 +3348,26 @@ pub fn parse_overlay_hide(working_set: &mut StateWorkingSet, call: Box<Call>) ->
     pipeline
 }
 
+/// Attach the doc comments above a `let`, `mut` or `const` definition to the variable it declares.
+pub fn add_variable_comments(
+    working_set: &mut StateWorkingSet,
+    pipeline: &Pipeline,
+    comments: &[Span],
+) {
+    let var_id = pipeline
+        .elements
+        .first()
+        .and_then(|element| match &element.expr.expr {
+            Expr::Call(call) => call.positional_nth(0),
+            _ => None,
+        })
+        .and_then(Expression::as_var);
+
+    if let Some(var_id) = var_id {
+        working_set.add_var_comments(var_id, comments.to_vec());
+    }
+}
+
 pub fn parse_let(working_set: &mut StateWorkingSet, spans: &[Span]) -> Pipeline {
     trace!("parsing: let");
 
@@ -3241,8 +3412,15 @@ pub fn parse_let(working_set: &mut StateWorkingSet, spans: &[Span]) -> Pipeline
                     );
 
                     let mut idx = 0;
-                    let (lvalue, explicit_type) =
-                        parse_var_with_opt_type(working_set, &spans[1..(span.0)], &mut idx, false);
+                    let lvalue_bytes = working_set.get_span_contents(spans[1]);
+                    let (lvalue, explicit_type) = if lvalue_bytes.starts_with(b"{")
+                        || lvalue_bytes.starts_with(b"[")
+                    {
+                        // Destructuring, e.g. `let {name, version} = ...`
+                        (parse_destructure_target(working_set, spans[1]), None)
+                    } else {
+                        parse_var_with_opt_type(working_set, &spans[1..(span.0)], &mut idx, false)
+                    };
                     // check for extra tokens after the identifier
                     if idx + 1 < span.0 - 1 {
                         working_set.error(ParseError::ExtraTokens(spans[idx + 2]));
@@ -3674,6 +3852,24 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                     }
                 };
 
+                // The rest of the arguments are passed to the script as the `$argv` constant
+                let argv = if scoped {
+                    None
+                } else {
+                    match source_arguments(working_set, &call) {
+                        Ok(argv) => Some(argv),
+                        Err(err) => {
+                            working_set.error(err.wrap(working_set, Span::concat(&spans[2..])));
+                            return Pipeline::from_vec(vec![Expression::new(
+                                working_set,
+                                Expr::Call(call),
+                                Span::concat(spans),
+                                Type::Any,
+                            )]);
+                        }
+                    }
+                };
+
                 if let Some(path) = find_in_dirs(&filename, working_set, &cwd, Some(LIB_DIRS_VAR)) {
                     if let Some(contents) = path.read(working_set) {
                         // Add the file to the stack of files being processed.
@@ -3682,6 +3878,24 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                             return garbage_pipeline(working_set, spans);
                         }
 
+                        // `$argv` is only visible in the sourced file, so save the caller's to
+                        // restore it afterwards
+                        let caller_argv = argv.map(|argv| {
+                            let caller_argv = working_set
+                                .last_overlay_mut()
+                                .vars
+                                .get(b"$argv".as_slice())
+                                .copied();
+                            let var_id = working_set.add_variable(
+                                b"$argv".to_vec(),
+                                argv.span(),
+                                argv.get_type(),
+                                false,
+                            );
+                            working_set.set_variable_const_val(var_id, argv);
+                            caller_argv
+                        });
+
                         // This will load the defs from the file into the
                         // working set, if it was a successful parse.
                         let mut block = parse(
@@ -3690,6 +3904,14 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                             &contents,
                             scoped,
                         );
+
+                        if let Some(caller_argv) = caller_argv {
+                            let overlay = working_set.last_overlay_mut();
+                            overlay.vars.remove(b"$argv".as_slice());
+                            if let Some(var_id) = caller_argv {
+                                overlay.vars.insert(b"$argv".to_vec(), var_id);
+                            }
+                        }
                         if block.ir_block.is_none() {
                             let block_mut = Arc::make_mut(&mut block);
                             compile_block(working_set, block_mut);
@@ -3752,6 +3974,36 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
     garbage_pipeline(working_set, spans)
 }
 
+/// Evaluate the arguments after the file name of a `source` call, which must be constants.
+///
+/// Unknown flags are passed as strings, so `source setup.nu --profile dev` gives
+/// `["--profile", "dev"]`.
+fn source_arguments(working_set: &StateWorkingSet, call: &Call) -> Result<Value, ShellError> {
+    let mut argv = vec![];
+    let mut span: Option<Span> = None;
+    let mut filename_seen = false;
+
+    for arg in &call.arguments {
+        match arg {
+            Argument::Positional(_) if !filename_seen => {
+                filename_seen = true;
+                continue;
+            }
+            Argument::Positional(expr) | Argument::Unknown(expr) => {
+                argv.push(eval_constant(working_set, expr)?);
+            }
+            Argument::Spread(expr) => match eval_constant(working_set, expr)? {
+                Value::List { vals, .. } => argv.extend(vals),
+                val => return Err(ShellError::CannotSpreadAsList { span: val.span() }),
+            },
+            Argument::Named(_) => continue,
+        }
+        span = Some(span.map_or(arg.span(), |s| s.append(arg.span())));
+    }
+
+    Ok(Value::list(argv, span.unwrap_or(call.head)))
+}
+
 pub fn parse_where_expr(working_set: &mut StateWorkingSet, spans: &[Span]) -> Expression {
     trace!("parsing: where");
 
@@ -6,7 +6,7 @@ use crate::{
 };
 use nu_protocol::{
     ParseError, Span, SyntaxShape, Type, VarId,
-    ast::{MatchPattern, Pattern},
+    ast::{Expr, Expression, ListItem, MatchPattern, Pattern, RecordItem},
     engine::StateWorkingSet,
 };
 pub fn garbage(span: Span) -> MatchPattern {
@@ -229,3 +229,101 @@ pub fn parse_record_pattern(working_set: &mut StateWorkingSet, span: Span) -> Ma
         span,
     }
 }
+
+/// Parse the target of a destructuring `let` or closure parameter, like `{name, version}` in
+/// `let {name, version} = (open package.json)`.
+///
+/// A record binds each field to the variable of the same name, or to another target with
+/// `field: target`. A list binds its items by position. The result is a record or list expression
+/// with an [`Expr::VarDecl`] for every variable it binds.
+pub fn parse_destructure_target(working_set: &mut StateWorkingSet, span: Span) -> Expression {
+    let bytes = working_set.get_span_contents(span);
+
+    let (is_record, close) = match bytes.first() {
+        Some(b'{') => (true, b'}'),
+        Some(b'[') => (false, b']'),
+        _ => return parse_destructure_variable(working_set, span),
+    };
+
+    let end = if bytes.len() > 1 && bytes.ends_with(&[close]) {
+        span.end - 1
+    } else {
+        working_set.error(ParseError::Unclosed(
+            char::from(close).into(),
+            Span::new(span.end, span.end),
+        ));
+        span.end
+    };
+
+    let inner_span = Span::new(span.start + 1, end);
+    let source = working_set.get_span_contents(inner_span);
+
+    let (tokens, err) = lex(
+        source,
+        inner_span.start,
+        &[b'\n', b'\r', b','],
+        &[b':'],
+        true,
+    );
+    if let Some(err) = err {
+        working_set.error(err);
+    }
+
+    if !is_record {
+        let items = tokens
+            .iter()
+            .map(|token| ListItem::Item(parse_destructure_target(working_set, token.span)))
+            .collect();
+        return Expression::new(working_set, Expr::List(items), span, Type::Any);
+    }
+
+    let mut items = vec![];
+    let mut tokens = tokens.iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let field = working_set.get_span_contents(token.span);
+        let field = String::from_utf8_lossy(field.strip_prefix(b"$").unwrap_or(field)).to_string();
+
+        let (key_span, target) = if tokens
+            .next_if(|colon| working_set.get_span_contents(colon.span) == b":")
+            .is_some()
+        {
+            let Some(target) = tokens.next() else {
+                working_set.error(ParseError::Expected(
+                    "destructuring target",
+                    Span::new(end, end),
+                ));
+                return Expression::garbage(working_set, span);
+            };
+            (
+                token.span,
+                parse_destructure_target(working_set, target.span),
+            )
+        } else {
+            // The field is also the name of the variable, so the key doesn't get a span of its own
+            (
+                Span::new(token.span.start, token.span.start),
+                parse_destructure_variable(working_set, token.span),
+            )
+        };
+
+        let key = Expression::new(working_set, Expr::String(field), key_span, Type::String);
+        items.push(RecordItem::Pair(key, target));
+    }
+
+    Expression::new(working_set, Expr::Record(items), span, Type::Any)
+}
+
+fn parse_destructure_variable(working_set: &mut StateWorkingSet, span: Span) -> Expression {
+    let name = working_set.get_span_contents(span).to_vec();
+
+    if !is_variable(&name) {
+        working_set.error(ParseError::Expected("valid variable name", span));
+        return Expression::garbage(working_set, span);
+    }
+
+    ensure_not_reserved_variable_name(working_set, &name, span);
+
+    let var_id = working_set.add_variable(name, span, Type::Any, false);
+    Expression::new(working_set, Expr::VarDecl(var_id), span, Type::Any)
+}
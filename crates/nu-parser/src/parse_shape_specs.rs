@@ -49,6 +49,7 @@ pub fn parse_shape_name(
         b"number" => SyntaxShape::Number,
         b"path" => SyntaxShape::Filepath,
         b"range" => SyntaxShape::Range,
+        b"rate" => SyntaxShape::Rate,
         b"string" => SyntaxShape::String,
         _ if bytes.starts_with(b"oneof")
             || bytes.starts_with(b"list")
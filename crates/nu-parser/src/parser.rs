@@ -5,7 +5,7 @@ use crate::{
     lex::{LexState, is_assignment_operator, lex, lex_n_tokens, lex_signature},
     lite_parser::{LiteCommand, LitePipeline, LiteRedirection, LiteRedirectionTarget, lite_parse},
     parse_keywords::*,
-    parse_patterns::parse_pattern,
+    parse_patterns::{parse_destructure_target, parse_pattern},
     parse_shape_specs::{parse_completer, parse_shape_name, parse_type},
     type_check::{self, check_range_types, math_result_type, type_compatible},
 };
@@ -1140,6 +1140,7 @@ pub fn parse_internal_call(
                     desc: "".to_string(),
                     var_id: None,
                     default_value: None,
+                    default_block: None,
                     completion: None,
                 })
             }
@@ -1410,7 +1411,12 @@ pub fn parse_call(working_set: &mut StateWorkingSet, spans: &[Span], head: Span)
         return garbage(working_set, head);
     }
 
-    let (cmd_start, pos, _name, maybe_decl_id) = find_longest_decl(working_set, spans);
+    let (mut cmd_start, mut pos, _name, mut maybe_decl_id) = find_longest_decl(working_set, spans);
+
+    // The command may come from a module in the autoload module directories
+    if maybe_decl_id.is_none() && parse_autoload_module(working_set, spans[0]) {
+        (cmd_start, pos, _, maybe_decl_id) = find_longest_decl(working_set, spans);
+    }
 
     if let Some(decl_id) = maybe_decl_id {
         // Before the internal parsing we check if there is no let or alias declarations
@@ -2950,13 +2956,153 @@ pub fn parse_filesize(working_set: &mut StateWorkingSet, span: Span) -> Expressi
             working_set.error(mk_err_for("filesize"));
             garbage(working_set, span)
         }
+        None => match parse_bit_filesize(bytes, span) {
+            Some(Ok(expr)) => {
+                let span_id = working_set.add_span(span);
+                expr.with_span_id(span_id)
+            }
+            Some(Err(err)) => {
+                working_set.error(err);
+                garbage(working_set, span)
+            }
+            None => {
+                working_set.error(ParseError::Expected("filesize with valid units", span));
+                garbage(working_set, span)
+            }
+        },
+    }
+}
+
+/// Parse a filesize given in bits, eg '100Mbit', which has to be a whole number of bytes
+fn parse_bit_filesize(bytes: &[u8], span: Span) -> Option<Result<Expression, ParseError>> {
+    let value = str::from_utf8(bytes).ok()?;
+    let (number, unit) = split_unit_suffix(value)?;
+    let bits = number * nu_protocol::bit_unit_as_bits(unit)? as f64;
+    if bits % 8.0 != 0.0 {
+        return Some(Err(ParseError::LabeledErrorWithHelp {
+            error: "Filesize is not a whole number of bytes".into(),
+            label: format!("{bits} bits don't make whole bytes"),
+            help: "use a number of bits that is a multiple of 8".into(),
+            span,
+        }));
+    }
+    let num_bytes = bits / 8.0;
+    if !(i64::MIN as f64 <= num_bytes && num_bytes <= i64::MAX as f64) {
+        return None;
+    }
+
+    let lhs_len = value.len() - unit.len();
+    trace!("-- found {num_bytes} bytes");
+    Some(Ok(value_with_unit_expression(
+        num_bytes as i64,
+        Unit::Filesize(FilesizeUnit::B),
+        lhs_len,
+        span,
+        Type::Filesize,
+    )))
+}
+
+/// Parse a data rate, eg '100Mbit/sec' or '12.5MB/sec'
+pub fn parse_rate(working_set: &mut StateWorkingSet, span: Span) -> Expression {
+    trace!("parsing: rate");
+
+    let bytes = working_set.get_span_contents(span);
+
+    match rate_in_bits_per_second(bytes) {
+        // Rates are whole bits per second, so a rate like `1B/wk` would silently become zero
+        Some((0, _)) if !is_zero_rate(bytes) => {
+            working_set.error(ParseError::LabeledErrorWithHelp {
+                error: "Rate is too small".into(),
+                label: "this is less than 1bit/sec".into(),
+                help: "rates are stored as whole bits per second".into(),
+                span,
+            });
+            garbage(working_set, span)
+        }
+        Some((bits, lhs_len)) => {
+            trace!("-- found {bits} bit/sec");
+            let expr =
+                value_with_unit_expression(bits, Unit::BitPerSecond, lhs_len, span, Type::Rate);
+            let span_id = working_set.add_span(span);
+            expr.with_span_id(span_id)
+        }
         None => {
-            working_set.error(ParseError::Expected("filesize with valid units", span));
+            working_set.error(ParseError::Expected("rate with valid units", span));
             garbage(working_set, span)
         }
     }
 }
 
+/// Returns the number of bits per second in a rate literal, and the length of its number part.
+///
+/// The amount can be given in bits or as a filesize, and it's followed by `/` and a duration unit.
+fn rate_in_bits_per_second(bytes: &[u8]) -> Option<(i64, usize)> {
+    let value = str::from_utf8(bytes).ok()?;
+    let (amount, per) = value.rsplit_once('/')?;
+    let (number, unit) = split_unit_suffix(amount)?;
+
+    let bits = match nu_protocol::bit_unit_as_bits(unit) {
+        Some(bits) => bits as f64,
+        None => {
+            let unit = unit.to_ascii_uppercase();
+            let (unit, ..) = FILESIZE_UNIT_GROUPS.iter().find(|x| x.1 == unit)?;
+            unit_to_byte_factor(unit)? * 8.0
+        }
+    };
+    let (per, ..) = DURATION_UNIT_GROUPS.iter().find(|x| x.1 == per)?;
+    let nanos = unit_to_ns_factor(per)?;
+
+    let rate = (number * bits * 1_000_000_000.0 / nanos).round();
+    if i64::MIN as f64 <= rate && rate <= i64::MAX as f64 {
+        Some((rate as i64, amount.len() - unit.len()))
+    } else {
+        None
+    }
+}
+
+/// Whether the amount of a rate literal is zero, like in `0bit/sec`.
+fn is_zero_rate(bytes: &[u8]) -> bool {
+    str::from_utf8(bytes)
+        .ok()
+        .and_then(|value| value.rsplit_once('/'))
+        .and_then(|(amount, _)| split_unit_suffix(amount))
+        .is_some_and(|(number, _)| number == 0.0)
+}
+
+/// Split a number from the unit that follows it, eg '12.5MB' into `12.5` and 'MB'
+fn split_unit_suffix(value: &str) -> Option<(f64, &str)> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 2
+        || !(bytes[0].is_ascii_digit() || (bytes[0] == b'-' && bytes[1].is_ascii_digit()))
+    {
+        return None;
+    }
+
+    let unit_start = value.rfind(|c: char| !c.is_ascii_alphabetic())? + 1;
+    let (number, unit) = value.split_at(unit_start);
+    let number = strip_underscores(number.as_bytes()).parse::<f64>().ok()?;
+    Some((number, unit))
+}
+
+fn value_with_unit_expression(
+    num: i64,
+    unit: Unit,
+    lhs_len: usize,
+    span: Span,
+    ty: Type,
+) -> Expression {
+    let lhs_span = Span::new(span.start, span.start + lhs_len);
+    let unit_span = Span::new(span.start + lhs_len, span.end);
+    let value = ValueWithUnit {
+        expr: Expression::new_unknown(Expr::Int(num), lhs_span, Type::Number),
+        unit: Spanned {
+            item: unit,
+            span: unit_span,
+        },
+    };
+    Expression::new_unknown(Expr::ValueWithUnit(Box::new(value)), span, ty)
+}
+
 type ParseUnitResult<'res> = Result<Expression, Box<dyn Fn(&'res str) -> ParseError>>;
 type UnitGroup<'unit> = (Unit, &'unit str, Option<(Unit, i64)>);
 
@@ -4042,6 +4188,7 @@ pub fn parse_row_condition(working_set: &mut StateWorkingSet, spans: &[Span]) ->
                 shape: SyntaxShape::Any,
                 var_id: Some(var_id),
                 default_value: None,
+                default_block: None,
                 completion: None,
             });
 
@@ -4054,6 +4201,32 @@ pub fn parse_row_condition(working_set: &mut StateWorkingSet, spans: &[Span]) ->
     Expression::new(working_set, Expr::RowCondition(block_id), span, Type::Bool)
 }
 
+/// Evaluate the default value of a parameter if it's a constant, otherwise turn it into a block
+/// that computes the default value on each call, eg `$env.DEPLOY_ENV` or `(default-region)`.
+fn parse_default_value(
+    working_set: &mut StateWorkingSet,
+    expression: Expression,
+) -> (Option<Value>, Option<BlockId>) {
+    if let Ok(constant) = eval_constant(working_set, &expression) {
+        return (Some(constant), None);
+    }
+
+    let span = expression.span;
+    let mut block = Block::new();
+    let mut pipeline = Pipeline::new();
+    pipeline.elements.push(PipelineElement {
+        pipe: None,
+        expr: expression,
+        redirection: None,
+    });
+    block.pipelines.push(pipeline);
+    block.span = Some(span);
+
+    compile_block(working_set, &mut block);
+
+    (None, Some(working_set.add_block(Arc::new(block))))
+}
+
 pub fn parse_signature(working_set: &mut StateWorkingSet, span: Span) -> Expression {
     let bytes = working_set.get_span_contents(span);
 
@@ -4084,6 +4257,27 @@ pub fn parse_signature(working_set: &mut StateWorkingSet, span: Span) -> Express
 }
 
 pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) -> Box<Signature> {
+    parse_signature_inner(working_set, span, None)
+}
+
+/// Parse the parameters of a closure, which unlike those of a command can be destructured, like
+/// `{name, size}` in `{|{name, size}| ...}`.
+///
+/// Also returns the variable and the span of the target of each destructured parameter.
+fn parse_closure_signature(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Box<Signature>, Vec<(VarId, Span)>) {
+    let mut destructured = vec![];
+    let signature = parse_signature_inner(working_set, span, Some(&mut destructured));
+    (signature, destructured)
+}
+
+fn parse_signature_inner(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+    mut destructured: Option<&mut Vec<(VarId, Span)>>,
+) -> Box<Signature> {
     enum ParseMode {
         Arg,
         AfterCommaArg,
@@ -4242,6 +4436,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                             required: false,
                                             var_id: Some(var_id),
                                             default_value: None,
+                                            default_block: None,
                                             completion: None,
                                         },
                                         type_annotated: false,
@@ -4282,6 +4477,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                                 required: false,
                                                 var_id: Some(var_id),
                                                 default_value: None,
+                                                default_block: None,
                                                 completion: None,
                                             },
                                             type_annotated: false,
@@ -4332,6 +4528,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                         required: false,
                                         var_id: Some(var_id),
                                         default_value: None,
+                                        default_block: None,
                                         completion: None,
                                     },
                                     type_annotated: false,
@@ -4405,6 +4602,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                         shape: SyntaxShape::Any,
                                         var_id: Some(var_id),
                                         default_value: None,
+                                        default_block: None,
                                         completion: None,
                                     },
                                     required: false,
@@ -4436,10 +4634,35 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                     shape: SyntaxShape::Any,
                                     var_id: Some(var_id),
                                     default_value: None,
+                                    default_block: None,
                                     completion: None,
                                 }));
                                 parse_mode = ParseMode::Arg;
                             }
+                            // Destructured param, e.g. {name, size}. The variables it binds
+                            // are added by the caller, which has to bind them too
+                            else if (contents.starts_with(b"{") || contents.starts_with(b"["))
+                                && let Some(destructured) = destructured.as_mut()
+                            {
+                                let var_id =
+                                    working_set.add_variable_without_scope(span, Type::Any, false);
+                                destructured.push((var_id, span));
+
+                                args.push(Arg::Positional {
+                                    arg: PositionalArg {
+                                        desc: String::new(),
+                                        name: String::from_utf8_lossy(&contents).to_string(),
+                                        shape: SyntaxShape::Any,
+                                        var_id: Some(var_id),
+                                        default_value: None,
+                                        default_block: None,
+                                        completion: None,
+                                    },
+                                    required: true,
+                                    type_annotated: false,
+                                });
+                                parse_mode = ParseMode::Arg;
+                            }
                             // Normal param
                             else {
                                 let name = String::from_utf8_lossy(&contents).to_string();
@@ -4466,6 +4689,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                         shape: SyntaxShape::Any,
                                         var_id: Some(var_id),
                                         default_value: None,
+                                        default_block: None,
                                         completion: None,
                                     },
                                     required: true,
@@ -4574,6 +4798,7 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                                 shape,
                                                 var_id,
                                                 default_value,
+                                                default_block,
                                                 ..
                                             },
                                         required,
@@ -4605,20 +4830,12 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                             }
                                         }
 
-                                        *default_value = if let Ok(constant) =
-                                            eval_constant(working_set, &expression)
-                                        {
-                                            Some(constant)
-                                        } else {
-                                            working_set.error(ParseError::NonConstantDefaultValue(
-                                                expression.span,
-                                            ));
-                                            None
-                                        };
-
                                         if !*type_annotated {
                                             *shape = expression.ty.to_shape();
                                         }
+
+                                        (*default_value, *default_block) =
+                                            parse_default_value(working_set, expression);
                                         *required = false;
                                     }
                                     Arg::RestPositional(..) => {
@@ -4634,26 +4851,19 @@ pub fn parse_signature_helper(working_set: &mut StateWorkingSet, span: Span) ->
                                                 arg,
                                                 var_id,
                                                 default_value,
+                                                default_block,
                                                 ..
                                             },
                                         type_annotated,
                                     } => {
                                         let expression_span = expression.span;
+                                        let expression_ty = expression.ty.clone();
 
-                                        *default_value = if let Ok(value) =
-                                            eval_constant(working_set, &expression)
-                                        {
-                                            Some(value)
-                                        } else {
-                                            working_set.error(ParseError::NonConstantDefaultValue(
-                                                expression_span,
-                                            ));
-                                            None
-                                        };
+                                        (*default_value, *default_block) =
+                                            parse_default_value(working_set, expression);
 
                                         let var_id = var_id.expect("internal error: all custom parameters must have var_ids");
                                         let var_type = &working_set.get_variable(var_id).ty;
-                                        let expression_ty = expression.ty.clone();
 
                                         // Flags without type annotations are present/not-present
                                         // switches *except* when they have a default value
@@ -5342,6 +5552,8 @@ pub fn parse_closure_expression(
 
     working_set.enter_scope();
 
+    let mut destructured = vec![];
+
     // Check to see if we have parameters
     let (signature, amt_to_skip): (Option<(Box<Signature>, Span)>, usize) = match output.first() {
         Some(Token {
@@ -5374,7 +5586,8 @@ pub fn parse_closure_expression(
             };
 
             let signature_span = Span::new(start_point, end_point);
-            let signature = parse_signature_helper(working_set, signature_span);
+            let (signature, params) = parse_closure_signature(working_set, signature_span);
+            destructured = params;
 
             (Some((signature, signature_span)), amt_to_skip)
         }
@@ -5417,8 +5630,45 @@ pub fn parse_closure_expression(
         }
     }
 
+    // The variables of destructured parameters are bound before the body is parsed, so it can
+    // use them, and assigned with a `let` at the start of the body
+    let destructured: Vec<_> = destructured
+        .into_iter()
+        .map(|(var_id, span)| (var_id, parse_destructure_target(working_set, span)))
+        .collect();
+
     let mut output = parse_block(working_set, &output[amt_to_skip..], span, false, false);
 
+    if !destructured.is_empty()
+        && let Some(let_decl_id) = working_set.find_decl(b"let")
+    {
+        let lets: Vec<_> = destructured
+            .into_iter()
+            .map(|(var_id, target)| {
+                let span = target.span;
+                let value = Expression::new(
+                    working_set,
+                    Expr::Var(var_id),
+                    Span::new(span.end, span.end),
+                    Type::Any,
+                );
+                let call = Box::new(Call {
+                    decl_id: let_decl_id,
+                    head: Span::unknown(),
+                    arguments: vec![Argument::Positional(target), Argument::Positional(value)],
+                    parser_info: HashMap::new(),
+                });
+                Pipeline::from_vec(vec![Expression::new(
+                    working_set,
+                    Expr::Call(call),
+                    span,
+                    Type::Any,
+                )])
+            })
+            .collect();
+        output.pipelines.splice(0..0, lets);
+    }
+
     // NOTE: closures need to be compiled eagerly due to these reasons:
     //  - their `Block`s (which contains their `IrBlock`) are stored in the working_set
     //  - Ir compiler does not have mutable access to the working_set and can't attach `IrBlock`s
@@ -5500,6 +5750,7 @@ pub fn parse_value(
         SyntaxShape::Duration => parse_duration(working_set, span),
         SyntaxShape::DateTime => parse_datetime(working_set, span),
         SyntaxShape::Filesize => parse_filesize(working_set, span),
+        SyntaxShape::Rate => parse_rate(working_set, span),
         SyntaxShape::Range => {
             parse_range(working_set, span).unwrap_or_else(|| garbage(working_set, span))
         }
@@ -5562,6 +5813,7 @@ pub fn parse_value(
                 let shapes = [
                     SyntaxShape::Binary,
                     SyntaxShape::Range,
+                    SyntaxShape::Rate,
                     SyntaxShape::Filesize,
                     SyntaxShape::Duration,
                     SyntaxShape::DateTime,
@@ -6320,19 +6572,18 @@ pub fn parse_builtin_commands(
         b"export-env" => parse_export_env(working_set, &lite_command.parts).0,
         // Other definitions can't have attributes, so we handle attributes here with parse_attribute_block
         _ if lite_command.has_attributes() => parse_attribute_block(working_set, lite_command),
-        b"let" => parse_let(
-            working_set,
-            &lite_command
-                .parts_including_redirection()
-                .collect::<Vec<Span>>(),
-        ),
-        b"const" => parse_const(working_set, &lite_command.parts).0,
-        b"mut" => parse_mut(
-            working_set,
-            &lite_command
+        b"let" | b"const" | b"mut" => {
+            let parts = lite_command
                 .parts_including_redirection()
-                .collect::<Vec<Span>>(),
-        ),
+                .collect::<Vec<Span>>();
+            let pipeline = match name {
+                b"let" => parse_let(working_set, &parts),
+                b"const" => parse_const(working_set, &lite_command.parts).0,
+                _ => parse_mut(working_set, &parts),
+            };
+            add_variable_comments(working_set, &pipeline, &lite_command.comments);
+            pipeline
+        }
         b"for" => {
             let expr = parse_for(working_set, lite_command);
             Pipeline::from_vec(vec![expr])
@@ -6907,6 +7158,25 @@ pub fn discover_captures_in_closure(
         seen.push(var_id);
     }
 
+    // Default values computed on each call can use variables from outside the block too
+    let default_blocks = block
+        .signature
+        .optional_positional
+        .iter()
+        .filter_map(|positional| positional.default_block)
+        .chain(
+            block
+                .signature
+                .named
+                .iter()
+                .filter_map(|flag| flag.default_block),
+        );
+    for block_id in default_blocks {
+        for pipeline in &working_set.get_block(block_id).pipelines {
+            discover_captures_in_pipeline(working_set, pipeline, seen, seen_blocks, output)?;
+        }
+    }
+
     for pipeline in &block.pipelines {
         discover_captures_in_pipeline(working_set, pipeline, seen, seen_blocks, output)?;
     }
@@ -137,8 +137,10 @@ pub fn math_result_type(
             (Type::Duration, Type::Date) => (Type::Date, None),
             (Type::Duration, Type::Duration) => (Type::Duration, None),
             (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+            (Type::Rate, Type::Rate) => (Type::Rate, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -152,7 +154,8 @@ pub fn math_result_type(
                             | Type::String
                             | Type::Date
                             | Type::Duration
-                            | Type::Filesize,
+                            | Type::Filesize
+                            | Type::Rate,
                     )
                 })
             }
@@ -171,8 +174,10 @@ pub fn math_result_type(
             (Type::Date, Type::Duration) => (Type::Date, None),
             (Type::Duration, Type::Duration) => (Type::Duration, None),
             (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+            (Type::Rate, Type::Rate) => (Type::Rate, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -186,6 +191,7 @@ pub fn math_result_type(
                             | Type::Date
                             | Type::Duration
                             | Type::Filesize
+                            | Type::Rate
                     )
                 })
             }
@@ -208,8 +214,15 @@ pub fn math_result_type(
             (Type::Int, Type::Duration) => (Type::Duration, None),
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Float, Type::Duration) => (Type::Duration, None),
+            (Type::Rate, Type::Int) => (Type::Rate, None),
+            (Type::Int, Type::Rate) => (Type::Rate, None),
+            (Type::Rate, Type::Float) => (Type::Rate, None),
+            (Type::Float, Type::Rate) => (Type::Rate, None),
+            (Type::Rate, Type::Duration) => (Type::Filesize, None),
+            (Type::Duration, Type::Rate) => (Type::Filesize, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -217,7 +230,12 @@ pub fn math_result_type(
                 type_error(operator, op.span, lhs, rhs, |ty| {
                     matches!(
                         ty,
-                        Type::Int | Type::Float | Type::Number | Type::Duration | Type::Filesize,
+                        Type::Int
+                            | Type::Float
+                            | Type::Number
+                            | Type::Duration
+                            | Type::Filesize
+                            | Type::Rate,
                     )
                 })
             }
@@ -238,8 +256,14 @@ pub fn math_result_type(
             (Type::Duration, Type::Duration) => (Type::Float, None),
             (Type::Duration, Type::Int) => (Type::Duration, None),
             (Type::Duration, Type::Float) => (Type::Duration, None),
+            (Type::Filesize, Type::Duration) => (Type::Rate, None),
+            (Type::Filesize, Type::Rate) => (Type::Duration, None),
+            (Type::Rate, Type::Rate) => (Type::Float, None),
+            (Type::Rate, Type::Int) => (Type::Rate, None),
+            (Type::Rate, Type::Float) => (Type::Rate, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -247,7 +271,12 @@ pub fn math_result_type(
                 type_error(operator, op.span, lhs, rhs, |ty| {
                     matches!(
                         ty,
-                        Type::Int | Type::Float | Type::Number | Type::Filesize | Type::Duration
+                        Type::Int
+                            | Type::Float
+                            | Type::Number
+                            | Type::Filesize
+                            | Type::Duration
+                            | Type::Rate
                     )
                 })
             }
@@ -270,6 +299,7 @@ pub fn math_result_type(
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -300,6 +330,7 @@ pub fn math_result_type(
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -324,6 +355,7 @@ pub fn math_result_type(
             (Type::Float, Type::Number) => (Type::Number, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -361,6 +393,7 @@ pub fn math_result_type(
             (Type::Binary, Type::Binary) => (Type::Binary, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) | (_, Type::Any) => (Type::Any, None),
             _ => {
                 *op = Expression::garbage(working_set, op.span);
@@ -416,6 +449,7 @@ pub fn math_result_type(
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -437,9 +471,11 @@ pub fn math_result_type(
             (Type::Duration, Type::Duration) => (Type::Bool, None),
             (Type::Date, Type::Date) => (Type::Bool, None),
             (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+            (Type::Rate, Type::Rate) => (Type::Bool, None),
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -462,6 +498,7 @@ pub fn math_result_type(
                             | Type::String
                             | Type::Filesize
                             | Type::Duration
+                            | Type::Rate
                             | Type::Date
                             | Type::Bool
                             | Type::Nothing
@@ -483,9 +520,11 @@ pub fn math_result_type(
             (Type::Duration, Type::Duration) => (Type::Bool, None),
             (Type::Date, Type::Date) => (Type::Bool, None),
             (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+            (Type::Rate, Type::Rate) => (Type::Bool, None),
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -508,6 +547,7 @@ pub fn math_result_type(
                             | Type::String
                             | Type::Filesize
                             | Type::Duration
+                            | Type::Rate
                             | Type::Date
                             | Type::Bool
                             | Type::Nothing
@@ -529,9 +569,11 @@ pub fn math_result_type(
             (Type::Duration, Type::Duration) => (Type::Bool, None),
             (Type::Date, Type::Date) => (Type::Bool, None),
             (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+            (Type::Rate, Type::Rate) => (Type::Bool, None),
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -554,6 +596,7 @@ pub fn math_result_type(
                             | Type::String
                             | Type::Filesize
                             | Type::Duration
+                            | Type::Rate
                             | Type::Date
                             | Type::Bool
                             | Type::Nothing
@@ -575,9 +618,11 @@ pub fn math_result_type(
             (Type::Duration, Type::Duration) => (Type::Bool, None),
             (Type::Date, Type::Date) => (Type::Bool, None),
             (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+            (Type::Rate, Type::Rate) => (Type::Bool, None),
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -600,6 +645,7 @@ pub fn math_result_type(
                             | Type::String
                             | Type::Filesize
                             | Type::Duration
+                            | Type::Rate
                             | Type::Date
                             | Type::Bool
                             | Type::Nothing
@@ -611,6 +657,7 @@ pub fn math_result_type(
             match (&lhs.ty, &rhs.ty) {
                 (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
                 (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+                (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
                 _ => (Type::Bool, None),
             }
         }
@@ -620,6 +667,7 @@ pub fn math_result_type(
                 // TODO: should this include glob?
                 (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
                 (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+                (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
                 _ => {
                     *op = Expression::garbage(working_set, op.span);
                     type_error(operator, op.span, lhs, rhs, |ty| matches!(ty, Type::String))
@@ -637,6 +685,7 @@ pub fn math_result_type(
                 // TODO: should this include glob?
                 (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
                 (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+                (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
                 _ => {
                     *op = Expression::garbage(working_set, op.span);
                     type_error(operator, op.span, lhs, rhs, |ty| matches!(ty, Type::String))
@@ -650,6 +699,7 @@ pub fn math_result_type(
             (Type::String, Type::Record(_)) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Bool, None),
             (_, Type::Any) => (Type::Bool, None),
             _ => {
@@ -691,6 +741,7 @@ pub fn math_result_type(
             (Type::Record(_), Type::String) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Bool, None),
             (_, Type::Any) => (Type::Bool, None),
             _ => {
@@ -827,9 +878,21 @@ pub fn check_pipeline_type(
                 continue;
             };
 
+            let input_types: Vec<Type> = decl
+                .signature()
+                .input_output_types
+                .into_iter()
+                .map(|(in_type, _)| in_type)
+                .collect();
+            let supported_string = combined_type_string(&input_types, "or").unwrap_or_default();
+
             output_errors
                 .get_or_insert_default()
-                .push(ParseError::InputMismatch(types_string, call.head));
+                .push(ParseError::InputMismatch(
+                    types_string,
+                    supported_string,
+                    call.head,
+                ));
         } else {
             new_types = vec![elem.expr.ty.clone()];
         }
@@ -8,6 +8,9 @@ use nu_protocol::shell_error::io::IoError;
 
 #[cfg(feature = "local-socket")]
 mod local_socket;
+mod remote;
+
+pub use remote::{PLUGIN_SECRET_ENV, RemoteAddress, RemoteConnection};
 
 #[cfg(feature = "local-socket")]
 use local_socket::*;
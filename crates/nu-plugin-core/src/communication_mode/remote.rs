@@ -0,0 +1,380 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+
+use nu_protocol::ShellError;
+
+/// The environment variable with the secret that engines have to present to a remote plugin
+/// before anything else. It's set for both the plugin and nushell.
+pub const PLUGIN_SECRET_ENV: &str = "NU_PLUGIN_SECRET";
+
+/// Longer secrets are refused, so that a connection can't make the plugin read forever.
+const MAX_SECRET_LEN: usize = 4096;
+/// How long a connection has to present its secret, as the plugin serves one at a time.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// The byte the plugin answers the secret with when it's accepted. Otherwise the connection is
+/// just closed.
+const SECRET_ACCEPTED: u8 = 1;
+
+/// The address of a plugin that runs somewhere else, like on another host or in a container, and
+/// listens for connections from the engine instead of being spawned by it.
+///
+/// Plugins serve connections on an address with `--listen <address>`. The address is written as
+/// `tcp://<host>:<port>`, or `unix://<path>` for a unix socket.
+///
+/// Before any protocol messages, the engine presents a shared secret, from [`PLUGIN_SECRET_ENV`],
+/// as its length in four big-endian bytes followed by the secret itself. The plugin answers with a
+/// single `1` byte if it matches, or closes the connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteAddress {
+    /// A TCP address, like `localhost:4000`.
+    Tcp(String),
+    /// The path to a unix socket.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// The streams of a connection to a remote plugin.
+pub type RemoteConnection = (Box<dyn Read + Send>, Box<dyn Write + Send>);
+
+impl RemoteAddress {
+    /// Parse an address, like `tcp://localhost:4000` or `unix:///run/nu_plugin_gpu.sock`.
+    pub fn parse(address: &str) -> Result<RemoteAddress, ShellError> {
+        if let Some(tcp) = address.strip_prefix("tcp://") {
+            return Ok(RemoteAddress::Tcp(tcp.into()));
+        }
+        #[cfg(unix)]
+        if let Some(path) = address.strip_prefix("unix://") {
+            return Ok(RemoteAddress::Unix(path.into()));
+        }
+        Err(ShellError::GenericError {
+            error: format!("Invalid plugin address: {address}"),
+            msg: "not a supported address".into(),
+            span: None,
+            help: Some(if cfg!(unix) {
+                "addresses look like `tcp://<host>:<port>` or `unix://<path>`".into()
+            } else {
+                "addresses look like `tcp://<host>:<port>`".into()
+            }),
+            inner: vec![],
+        })
+    }
+
+    /// Connect to a plugin listening on this address, presenting `secret` to it.
+    pub fn connect(&self, secret: &str) -> io::Result<RemoteConnection> {
+        match self {
+            RemoteAddress::Tcp(address) => {
+                let mut stream = TcpStream::connect(address)?;
+                stream.set_nodelay(true)?;
+                present_secret(&mut stream, secret)?;
+                Ok((Box::new(stream.try_clone()?), Box::new(stream)))
+            }
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => {
+                let mut stream = UnixStream::connect(path)?;
+                present_secret(&mut stream, secret)?;
+                Ok((Box::new(stream.try_clone()?), Box::new(stream)))
+            }
+        }
+    }
+
+    /// Listen on this address, and call `serve` for each connection from an engine that presents
+    /// `secret`, one at a time.
+    ///
+    /// TCP connections always need a secret, and are only accepted on loopback addresses unless
+    /// `allow_remote` is set. A unix socket is protected by its file permissions, so a secret is
+    /// optional there.
+    ///
+    /// This only returns if listening fails.
+    pub fn listen(
+        &self,
+        secret: Option<&str>,
+        allow_remote: bool,
+        mut serve: impl FnMut(RemoteConnection),
+    ) -> io::Result<()> {
+        let secret = secret.filter(|secret| !secret.is_empty());
+        match self {
+            RemoteAddress::Tcp(address) => {
+                if secret.is_none() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("${PLUGIN_SECRET_ENV} has to be set to listen on TCP"),
+                    ));
+                }
+                if !allow_remote
+                    && !address
+                        .to_socket_addrs()?
+                        .all(|addr| addr.ip().is_loopback())
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        format!(
+                            "{address} is not a loopback address, pass --allow-remote to listen on it"
+                        ),
+                    ));
+                }
+                for stream in TcpListener::bind(address)?.incoming() {
+                    let Some(mut stream) = accepted(stream) else {
+                        continue;
+                    };
+                    let handshake = stream
+                        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+                        .map(|()| accept_secret(&mut stream, secret))
+                        .and_then(|accepted| {
+                            stream.set_read_timeout(None)?;
+                            stream.set_nodelay(true)?;
+                            Ok(accepted.then_some(stream.try_clone()?))
+                        });
+                    if let Some(read) = handshake_finished(handshake) {
+                        serve((Box::new(read), Box::new(stream)));
+                    }
+                }
+            }
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => {
+                use std::os::unix::fs::FileTypeExt;
+
+                // Remove a socket left over from a previous run, but nothing else that might
+                // be at the path by mistake
+                match std::fs::symlink_metadata(path) {
+                    Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+                    Ok(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::AlreadyExists,
+                            format!("{} already exists and is not a socket", path.display()),
+                        ));
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+                    Err(err) => return Err(err),
+                }
+                for stream in UnixListener::bind(path)?.incoming() {
+                    let Some(mut stream) = accepted(stream) else {
+                        continue;
+                    };
+                    let handshake = stream
+                        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+                        .map(|()| accept_secret(&mut stream, secret))
+                        .and_then(|accepted| {
+                            stream.set_read_timeout(None)?;
+                            Ok(accepted.then_some(stream.try_clone()?))
+                        });
+                    if let Some(read) = handshake_finished(handshake) {
+                        serve((Box::new(read), Box::new(stream)));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A connection that failed to be accepted only affects that connection, so the listener logs it
+/// and keeps going.
+fn accepted<S>(stream: io::Result<S>) -> Option<S> {
+    stream
+        .inspect_err(|err| log::warn!("failed to accept a remote plugin connection: {err}"))
+        .ok()
+}
+
+/// The reading half of a connection that presented the right secret, if it did.
+fn handshake_finished<S>(handshake: io::Result<Option<S>>) -> Option<S> {
+    handshake
+        .inspect_err(|err| log::warn!("failed to set up a remote plugin connection: {err}"))
+        .ok()
+        .flatten()
+}
+
+/// The engine's side of the handshake: send the secret and wait for the plugin to accept it.
+fn present_secret(stream: &mut (impl Read + Write), secret: &str) -> io::Result<()> {
+    let len = u32::try_from(secret.len())
+        .ok()
+        .filter(|&len| len as usize <= MAX_SECRET_LEN)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the secret is too long"))?;
+    stream.write_all(&len.to_be_bytes())?;
+    stream.write_all(secret.as_bytes())?;
+    stream.flush()?;
+
+    let mut answer = [0];
+    match stream.read_exact(&mut answer) {
+        Ok(()) if answer[0] == SECRET_ACCEPTED => Ok(()),
+        Ok(()) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected answer to the secret, is this a nushell plugin?",
+        )),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("the plugin refused the connection, check ${PLUGIN_SECRET_ENV}"),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// The plugin's side of the handshake: read the secret and answer if it matches `secret`.
+/// Without a secret, any is accepted.
+fn accept_secret(stream: &mut (impl Read + Write), secret: Option<&str>) -> bool {
+    let mut read_secret = || -> io::Result<Vec<u8>> {
+        let mut len = [0; 4];
+        stream.read_exact(&mut len)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_SECRET_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "secret too long",
+            ));
+        }
+        let mut presented = vec![0; len];
+        stream.read_exact(&mut presented)?;
+        Ok(presented)
+    };
+
+    let accepted = match (read_secret(), secret) {
+        (Ok(presented), Some(secret)) => constant_time_eq(&presented, secret.as_bytes()),
+        (Ok(_), None) => true,
+        (Err(err), _) => {
+            log::warn!("remote plugin connection failed before presenting a secret: {err}");
+            return false;
+        }
+    };
+    if !accepted {
+        log::warn!("remote plugin connection presented the wrong secret");
+        return false;
+    }
+    stream
+        .write_all(&[SECRET_ACCEPTED])
+        .and_then(|()| stream.flush())
+        .is_ok()
+}
+
+/// Compare without returning early, so that how long it takes doesn't reveal the secret.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl fmt::Display for RemoteAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteAddress::Tcp(address) => write!(f, "tcp://{address}"),
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tcp() -> Result<(), ShellError> {
+        let address = RemoteAddress::parse("tcp://localhost:4000")?;
+        assert_eq!(RemoteAddress::Tcp("localhost:4000".into()), address);
+        assert_eq!("tcp://localhost:4000", address.to_string());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_unix() -> Result<(), ShellError> {
+        let address = RemoteAddress::parse("unix:///run/nu_plugin_gpu.sock")?;
+        assert_eq!(
+            RemoteAddress::Unix("/run/nu_plugin_gpu.sock".into()),
+            address
+        );
+        assert_eq!("unix:///run/nu_plugin_gpu.sock", address.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(RemoteAddress::parse("localhost:4000").is_err());
+    }
+
+    /// Listen on a free loopback port in the background, and return its address.
+    fn listen_in_background(secret: &'static str) -> io::Result<RemoteAddress> {
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        let address = RemoteAddress::Tcp(format!("127.0.0.1:{port}"));
+        let listening = address.clone();
+        std::thread::spawn(move || {
+            listening.listen(Some(secret), false, |(mut read, mut write)| {
+                let mut buf = [0; 2];
+                if read.read_exact(&mut buf).is_ok() {
+                    let _ = write.write_all(&buf);
+                }
+            })
+        });
+        // Wait for the listener to be bound
+        for _ in 0..100 {
+            if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        Ok(address)
+    }
+
+    #[test]
+    fn connect_with_secret() -> io::Result<()> {
+        let address = listen_in_background("hunter2")?;
+        let (mut read, mut write) = address.connect("hunter2")?;
+        write.write_all(b"hi")?;
+
+        let mut buf = [0; 2];
+        read.read_exact(&mut buf)?;
+        assert_eq!(b"hi", &buf);
+        Ok(())
+    }
+
+    #[test]
+    fn connect_with_wrong_secret() -> io::Result<()> {
+        let address = listen_in_background("hunter2")?;
+        let err = address.connect("hunter3").err().map(|err| err.kind());
+        assert_eq!(Some(io::ErrorKind::PermissionDenied), err);
+        Ok(())
+    }
+
+    #[test]
+    fn listen_on_tcp_needs_secret() {
+        let address = RemoteAddress::Tcp("127.0.0.1:0".into());
+        let result = address.listen(None, false, |_| {});
+        assert_eq!(
+            Some(io::ErrorKind::InvalidInput),
+            result.err().map(|err| err.kind())
+        );
+    }
+
+    #[test]
+    fn listen_on_tcp_only_on_loopback_by_default() {
+        let address = RemoteAddress::Tcp("0.0.0.0:0".into());
+        let result = address.listen(Some("hunter2"), false, |_| {});
+        assert_eq!(
+            Some(io::ErrorKind::PermissionDenied),
+            result.err().map(|err| err.kind())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn listen_on_unix_keeps_other_files() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("nu-plugin-remote-{}", std::process::id()));
+        std::fs::write(&path, "not a socket")?;
+        let result = RemoteAddress::Unix(path.clone()).listen(None, false, |_| {});
+        let contents = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(
+            Some(io::ErrorKind::AlreadyExists),
+            result.err().map(|err| err.kind())
+        );
+        assert_eq!("not a socket", contents?);
+        Ok(())
+    }
+}
@@ -10,7 +10,8 @@ mod interface;
 mod serializers;
 
 pub use communication_mode::{
-    ClientCommunicationIo, CommunicationMode, PreparedServerCommunication, ServerCommunicationIo,
+    ClientCommunicationIo, CommunicationMode, PLUGIN_SECRET_ENV, PreparedServerCommunication,
+    RemoteAddress, RemoteConnection, ServerCommunicationIo,
 };
 pub use interface::{
     Interface, InterfaceManager, PipelineDataWriter, PluginRead, PluginWrite,
@@ -37,14 +37,15 @@ pub trait PluginExecutionContext: Send + Sync {
     fn get_help(&self) -> Result<Spanned<String>, ShellError>;
     /// Get the contents of a [`Span`]
     fn get_span_contents(&self, span: Span) -> Result<Spanned<Vec<u8>>, ShellError>;
-    /// Evaluate a closure passed to the plugin
+    /// Evaluate a closure passed to the plugin, optionally keeping its changes to the environment
     fn eval_closure(
-        &self,
+        &mut self,
         closure: Spanned<Closure>,
         positional: Vec<Value>,
         input: PipelineData,
         redirect_stdout: bool,
         redirect_stderr: bool,
+        redirect_env: bool,
     ) -> Result<PipelineData, ShellError>;
     /// Find a declaration by name
     fn find_decl(&self, name: &str) -> Result<Option<DeclId>, ShellError>;
@@ -165,12 +166,13 @@ impl PluginExecutionContext for PluginExecutionCommandContext<'_> {
     }
 
     fn eval_closure(
-        &self,
+        &mut self,
         closure: Spanned<Closure>,
         positional: Vec<Value>,
         input: PipelineData,
         redirect_stdout: bool,
         redirect_stderr: bool,
+        redirect_env: bool,
     ) -> Result<PipelineData, ShellError> {
         let block = self
             .engine_state
@@ -186,12 +188,12 @@ impl PluginExecutionContext for PluginExecutionCommandContext<'_> {
                 inner: vec![],
             })?;
 
-        let mut stack = self
+        let mut callee_stack = self
             .stack
             .captures_to_stack(closure.item.captures)
             .reset_pipes();
 
-        let stack = &mut stack.push_redirection(
+        let stack = &mut callee_stack.push_redirection(
             redirect_stdout.then_some(Redirection::Pipe(OutDest::PipeSeparate)),
             redirect_stderr.then_some(Redirection::Pipe(OutDest::PipeSeparate)),
         );
@@ -213,7 +215,14 @@ impl PluginExecutionContext for PluginExecutionCommandContext<'_> {
 
         let eval_block_with_early_return = get_eval_block_with_early_return(&self.engine_state);
 
-        eval_block_with_early_return(&self.engine_state, stack, block, input).map(|p| p.body)
+        let result =
+            eval_block_with_early_return(&self.engine_state, stack, block, input).map(|p| p.body);
+
+        if redirect_env {
+            nu_engine::redirect_env(&self.engine_state, &mut self.stack, stack);
+        }
+
+        result
     }
 
     fn find_decl(&self, name: &str) -> Result<Option<DeclId>, ShellError> {
@@ -366,12 +375,13 @@ impl PluginExecutionContext for PluginExecutionBogusContext {
     }
 
     fn eval_closure(
-        &self,
+        &mut self,
         _closure: Spanned<Closure>,
         _positional: Vec<Value>,
         _input: PipelineData,
         _redirect_stdout: bool,
         _redirect_stderr: bool,
+        _redirect_env: bool,
     ) -> Result<PipelineData, ShellError> {
         Err(ShellError::NushellFailed {
             msg: "eval_closure not implemented on bogus".into(),
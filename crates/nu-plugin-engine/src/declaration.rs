@@ -168,6 +168,7 @@ impl Command for PluginDeclaration {
                 call: call.call.clone(),
                 strip: call.strip,
                 pos: call.pos,
+                prefix: call.prefix.to_owned(),
             },
         })
     }
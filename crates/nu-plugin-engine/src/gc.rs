@@ -1,16 +1,22 @@
 use crate::PersistentPlugin;
 use nu_protocol::{PluginGcConfig, RegisteredPlugin};
 use std::{
-    sync::{Arc, Weak, mpsc},
+    sync::{
+        Arc, Weak,
+        mpsc::{self, RecvTimeoutError},
+    },
     thread,
     time::{Duration, Instant},
 };
 
+/// How often to check how much memory an inactive plugin uses, if it has a `max_idle_memory`
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Plugin garbage collector
 ///
 /// Many users don't want all of their plugins to stay running indefinitely after using them, so
 /// this runs a thread that monitors the plugin's usage and stops it automatically if it meets
-/// certain conditions of inactivity.
+/// certain conditions of inactivity, or uses too much memory while inactive.
 #[derive(Debug, Clone)]
 pub struct PluginGc {
     sender: mpsc::Sender<PluginGcMsg>,
@@ -108,6 +114,27 @@ struct PluginGcState {
 }
 
 impl PluginGcState {
+    /// True if the plugin isn't in use, and was used at some point.
+    fn is_idle(&self) -> bool {
+        self.locks <= 0 && !self.disabled && self.last_update.is_some()
+    }
+
+    /// How long to wait before checking the plugin's memory, if it should be checked at all.
+    fn next_memory_check(&self) -> Option<Duration> {
+        (self.config.enabled && self.config.max_idle_memory.is_some() && self.is_idle())
+            .then_some(MEMORY_CHECK_INTERVAL)
+    }
+
+    /// True if the plugin should be stopped for using `memory` bytes.
+    fn exceeds_max_idle_memory(&self, memory: u64) -> bool {
+        self.config.enabled
+            && self.is_idle()
+            && self
+                .config
+                .max_idle_memory
+                .is_some_and(|max| memory > max.get().max(0) as u64)
+    }
+
     fn next_timeout(&self, now: Instant) -> Option<Duration> {
         if self.locks <= 0 && !self.disabled {
             self.last_update
@@ -168,12 +195,35 @@ impl PluginGcState {
         let mut always_stop = false;
 
         loop {
-            let Some(msg) = (match self.next_timeout(Instant::now()) {
-                Some(duration) => receiver.recv_timeout(duration).ok(),
-                None => receiver.recv().ok(),
-            }) else {
-                // If the timeout was reached, or the channel is disconnected, break the loop
-                break;
+            let timeout = self.next_timeout(Instant::now());
+            let memory_check = self.next_memory_check();
+
+            let msg = match timeout.into_iter().chain(memory_check).min() {
+                Some(duration) => match receiver.recv_timeout(duration) {
+                    Ok(msg) => msg,
+                    // Stop waiting if the plugin should be stopped for inactivity
+                    Err(RecvTimeoutError::Timeout) if timeout.is_some_and(|t| t <= duration) => {
+                        break;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        let memory = self.plugin.upgrade().and_then(|plugin| plugin.memory());
+                        if memory.is_some_and(|memory| self.exceeds_max_idle_memory(memory)) {
+                            log::debug!(
+                                "Plugin GC ({name}) stopping plugin for using too much memory",
+                                name = self.name
+                            );
+                            always_stop = true;
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                },
+                None => match receiver.recv() {
+                    Ok(msg) => msg,
+                    // If the channel is disconnected, break the loop
+                    Err(_) => break,
+                },
             };
 
             log::trace!("Plugin GC ({name}) message: {msg:?}", name = self.name);
@@ -215,6 +265,7 @@ impl PluginGcState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nu_protocol::Filesize;
 
     fn test_state() -> PluginGcState {
         PluginGcState {
@@ -292,6 +343,46 @@ mod tests {
         assert_eq!(None, state.next_timeout(now));
     }
 
+    #[test]
+    fn memory_checked_if_idle_with_max_idle_memory() {
+        let mut state = test_state();
+        state.config.max_idle_memory = Some(Filesize::new(1000));
+        state.last_update = Some(Instant::now());
+
+        assert_eq!(Some(MEMORY_CHECK_INTERVAL), state.next_memory_check());
+    }
+
+    #[test]
+    fn memory_not_checked_without_max_idle_memory() {
+        let mut state = test_state();
+        state.last_update = Some(Instant::now());
+
+        assert_eq!(None, state.next_memory_check());
+    }
+
+    #[test]
+    fn memory_not_checked_if_locks_count_over_zero() {
+        let mut state = test_state();
+        state.config.max_idle_memory = Some(Filesize::new(1000));
+        state.locks = 1;
+        state.last_update = Some(Instant::now());
+
+        assert_eq!(None, state.next_memory_check());
+    }
+
+    #[test]
+    fn exceeds_max_idle_memory() {
+        let mut state = test_state();
+        state.config.max_idle_memory = Some(Filesize::new(1000));
+        state.last_update = Some(Instant::now());
+
+        assert!(state.exceeds_max_idle_memory(1001));
+        assert!(!state.exceeds_max_idle_memory(1000));
+
+        state.config.enabled = false;
+        assert!(!state.exceeds_max_idle_memory(1001));
+    }
+
     #[test]
     fn adding_locks_changes_last_update() {
         let mut state = test_state();
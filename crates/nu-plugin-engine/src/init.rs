@@ -1,5 +1,6 @@
 use std::{
-    io::{BufReader, BufWriter},
+    fs::File,
+    io::{BufReader, BufWriter, Read},
     path::Path,
     process::Child,
     sync::{Arc, Mutex},
@@ -15,13 +16,16 @@ use nu_plugin_core::{
     ServerCommunicationIo,
 };
 use nu_protocol::{
-    PluginIdentity, PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData,
-    RegisteredPlugin, ShellError, Span, engine::StateWorkingSet, report_shell_error,
+    PluginIdentity, PluginMetadata, PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData,
+    PluginStartMode, RegisteredPlugin, ShellError, Span,
+    engine::{EngineState, Stack, StateWorkingSet},
+    report_shell_error,
 };
 
 use crate::{
-    PersistentPlugin, PluginDeclaration, PluginGc, PluginInterface, PluginInterfaceManager,
-    PluginSource,
+    GetPlugin, PersistentPlugin, PluginDeclaration, PluginGc, PluginInterface,
+    PluginInterfaceManager, PluginMetrics, PluginSource,
+    metrics::{CountingReader, CountingWriter},
 };
 
 /// This should be larger than the largest commonly sent message to avoid excessive fragmentation.
@@ -29,12 +33,34 @@ use crate::{
 /// The buffers coming from byte streams are typically each 8192 bytes, so double that.
 pub(crate) const OUTPUT_BUFFER_SIZE: usize = 16384;
 
+/// The environment variable with the command line of the WebAssembly runtime that runs `.wasm`
+/// plugins. The plugin's file and arguments are added after it.
+pub const WASM_RUNTIME_ENV: &str = "NU_WASM_RUNTIME";
+
+/// The default WebAssembly runtime. The plugin side of the protocol needs threads, which are
+/// opt-in for WASI.
+const DEFAULT_WASM_RUNTIME: &str = "wasmtime run -W threads=y -S threads=y";
+
+/// Whether the file at `path` starts with the magic number of a WebAssembly module. A file that
+/// can't be opened is left for the runtime to report.
+pub(crate) fn is_wasm_module(path: &Path) -> bool {
+    let mut magic = [0; 4];
+    match File::open(path) {
+        Ok(mut file) => file.read_exact(&mut magic).is_ok() && magic == *b"\0asm",
+        Err(_) => true,
+    }
+}
+
 /// Spawn the command for a plugin, in the given `mode`. After spawning, it can be passed to
 /// [`make_plugin_interface()`] to get a [`PluginInterface`].
-pub fn create_command(
+///
+/// `.wasm` plugins are run with `wasm_runtime`, the value of [`WASM_RUNTIME_ENV`], or with
+/// `wasmtime` if it isn't set.
+pub fn create_command<'a>(
     path: &Path,
-    mut shell: Option<&Path>,
+    mut shell: Option<&'a Path>,
     mode: &CommunicationMode,
+    wasm_runtime: Option<&'a str>,
 ) -> std::process::Command {
     log::trace!("Starting plugin: {path:?}, shell = {shell:?}, mode = {mode:?}");
 
@@ -65,6 +91,16 @@ pub fn create_command(
                 shell_args.push("-jar");
                 Some(Path::new("java"))
             }
+            // WASI plugins run sandboxed in a WebAssembly runtime
+            Some("wasm") => {
+                let mut runtime = wasm_runtime
+                    .filter(|runtime| !runtime.trim().is_empty())
+                    .unwrap_or(DEFAULT_WASM_RUNTIME)
+                    .split_whitespace();
+                let program = runtime.next().map(Path::new);
+                shell_args.extend(runtime);
+                program
+            }
             _ => None,
         };
     }
@@ -109,6 +145,7 @@ pub fn make_plugin_interface(
     source: Arc<PluginSource>,
     pid: Option<u32>,
     gc: Option<PluginGc>,
+    metrics: Option<Arc<PluginMetrics>>,
 ) -> Result<PluginInterface, ShellError> {
     match comm.connect(&mut child)? {
         ServerCommunicationIo::Stdio(stdin, stdout) => make_plugin_interface_with_streams(
@@ -120,6 +157,7 @@ pub fn make_plugin_interface(
             source,
             pid,
             gc,
+            metrics,
         ),
         #[cfg(feature = "local-socket")]
         ServerCommunicationIo::LocalSocket { read_out, write_in } => {
@@ -132,6 +170,7 @@ pub fn make_plugin_interface(
                 source,
                 pid,
                 gc,
+                metrics,
             )
         }
     }
@@ -143,6 +182,7 @@ pub fn make_plugin_interface(
 /// - `source` is required so that custom values produced by the plugin can spawn it.
 /// - `pid` may be provided for process management (e.g. `EnterForeground`).
 /// - `gc` may be provided for communication with the plugin's GC (e.g. `SetGcDisabled`).
+/// - `metrics` may be provided to count calls and bytes transferred.
 pub fn make_plugin_interface_with_streams(
     mut reader: impl std::io::Read + Send + 'static,
     writer: impl std::io::Write + Send + 'static,
@@ -150,15 +190,25 @@ pub fn make_plugin_interface_with_streams(
     source: Arc<PluginSource>,
     pid: Option<u32>,
     gc: Option<PluginGc>,
+    metrics: Option<Arc<PluginMetrics>>,
 ) -> Result<PluginInterface, ShellError> {
     let encoder = get_plugin_encoding(&mut reader)?;
 
+    let reader = CountingReader {
+        inner: reader,
+        metrics: metrics.clone(),
+    };
+    let writer = CountingWriter {
+        inner: writer,
+        metrics: metrics.clone(),
+    };
     let reader = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, reader);
     let writer = BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, writer);
 
     let mut manager =
         PluginInterfaceManager::new(source.clone(), pid, (Mutex::new(writer), encoder));
     manager.set_garbage_collector(gc);
+    manager.set_metrics(metrics);
 
     let interface = manager.get_interface();
     interface.hello()?;
@@ -241,23 +291,24 @@ pub fn load_plugin_registry_item(
     plugin: &PluginRegistryItem,
     span: Option<Span>,
 ) -> Result<Arc<PersistentPlugin>, ShellError> {
-    let identity =
-        PluginIdentity::new(plugin.filename.clone(), plugin.shell.clone()).map_err(|_| {
-            ShellError::GenericError {
-                error: "Invalid plugin filename in plugin registry file".into(),
-                msg: "loaded from here".into(),
-                span,
-                help: Some(format!(
-                    "the filename for `{}` is not a valid nushell plugin: {}",
-                    plugin.name,
-                    plugin.filename.display()
-                )),
-                inner: vec![],
-            }
-        })?;
+    let identity = PluginIdentity::new(plugin.filename.clone(), plugin.shell.clone())
+        .map_err(|_| ShellError::GenericError {
+            error: "Invalid plugin filename in plugin registry file".into(),
+            msg: "loaded from here".into(),
+            span,
+            help: Some(format!(
+                "the filename for `{}` is not a valid nushell plugin: {}",
+                plugin.name,
+                plugin.filename.display()
+            )),
+            inner: vec![],
+        })?
+        .with_address(plugin.address.clone());
 
     match &plugin.data {
         PluginRegistryItemData::Valid { metadata, commands } => {
+            check_plugin_compatibility(&identity, metadata, span)?;
+
             let plugin = add_plugin_to_working_set(working_set, &identity)?;
 
             // Ensure that the plugin is reset. We're going to load new signatures, so we want to
@@ -283,6 +334,54 @@ pub fn load_plugin_registry_item(
     }
 }
 
+/// Check that this version of Nushell is at least the minimum version that the plugin requires in
+/// its `metadata`, if it has one.
+pub fn check_plugin_compatibility(
+    identity: &PluginIdentity,
+    metadata: &PluginMetadata,
+    span: Option<Span>,
+) -> Result<(), ShellError> {
+    let Some(min_nu_version) = &metadata.min_nu_version else {
+        return Ok(());
+    };
+
+    let required =
+        semver::Version::parse(min_nu_version).map_err(|err| ShellError::GenericError {
+            error: "Invalid minimum Nushell version for plugin".into(),
+            msg: format!("`{}` requires an invalid version: {err}", identity.name()),
+            span,
+            help: Some(format!(
+                "the plugin's minimum version is `{min_nu_version}`"
+            )),
+            inner: vec![],
+        })?;
+    let mut current = semver::Version::parse(env!("CARGO_PKG_VERSION")).map_err(|err| {
+        ShellError::NushellFailed {
+            msg: format!("Failed to parse the Nushell version: {err}"),
+        }
+    })?;
+    // A nightly version (1.2.3-nightly.1) counts as the version it leads up to
+    current.pre = semver::Prerelease::EMPTY;
+
+    if current >= required {
+        Ok(())
+    } else {
+        Err(ShellError::GenericError {
+            error: "Plugin requires a newer version of Nushell".into(),
+            msg: format!(
+                "`{}` requires Nushell {min_nu_version} or newer",
+                identity.name()
+            ),
+            span,
+            help: Some(format!(
+                "this is Nushell {}. Upgrade Nushell, or use an older version of the plugin",
+                env!("CARGO_PKG_VERSION")
+            )),
+            inner: vec![],
+        })
+    }
+}
+
 /// Find [`PersistentPlugin`] with the given `identity` in the `working_set`, or construct it
 /// if it doesn't exist.
 ///
@@ -315,3 +414,146 @@ pub fn add_plugin_to_working_set(
             msg: "encountered unexpected RegisteredPlugin type".into(),
         })
 }
+
+/// Start the plugins configured with `start: eager` in `$env.config.plugin_gc`, so that they're
+/// already running when their commands are first used.
+///
+/// Errors are reported, and don't keep other plugins from being started.
+pub fn start_eager_plugins(engine_state: &EngineState, stack: &mut Stack) {
+    let config = stack.get_config(engine_state);
+    for plugin in engine_state.plugins() {
+        if config.plugin_gc.get(plugin.identity().name()).start != PluginStartMode::Eager {
+            continue;
+        }
+        let Ok(plugin) = plugin.clone().as_any().downcast::<PersistentPlugin>() else {
+            continue;
+        };
+        if let Err(err) = plugin.get_plugin(Some((engine_state, stack))) {
+            report_shell_error(Some(&*stack), engine_state, &err);
+        }
+    }
+}
+
+/// Reload the plugins whose executables changed since they were loaded, so that rebuilding a plugin
+/// doesn't require `plugin add` and `plugin use` again.
+///
+/// Running instances of those plugins are stopped, their commands are replaced with the signatures
+/// of the new executable, and the plugin registry file is updated. Errors are reported, and don't
+/// keep other plugins from being reloaded.
+pub fn reload_changed_plugins(engine_state: &mut EngineState, stack: &mut Stack) {
+    let changed: Vec<Arc<PersistentPlugin>> = engine_state
+        .plugins()
+        .iter()
+        .filter_map(|plugin| plugin.clone().as_any().downcast::<PersistentPlugin>().ok())
+        .filter(|plugin| plugin.executable_changed())
+        .collect();
+
+    if changed.is_empty() {
+        return;
+    }
+
+    let mut working_set = StateWorkingSet::new(engine_state);
+    for plugin in changed {
+        if let Err(err) = reload_plugin(&mut working_set, stack, plugin) {
+            report_shell_error(Some(&*stack), working_set.permanent_state, &err);
+        }
+    }
+
+    let delta = working_set.render();
+    if let Err(err) = engine_state.merge_delta(delta) {
+        report_shell_error(Some(&*stack), engine_state, &err);
+    }
+}
+
+fn reload_plugin(
+    working_set: &mut StateWorkingSet,
+    stack: &mut Stack,
+    plugin: Arc<PersistentPlugin>,
+) -> Result<(), ShellError> {
+    // Stop the instance running the old executable, so that the new one gets started
+    plugin.reset()?;
+
+    let interface = plugin
+        .clone()
+        .get_plugin(Some((working_set.permanent_state, stack)))?;
+    let metadata = interface.get_metadata()?;
+    check_plugin_compatibility(plugin.identity(), &metadata, None)?;
+    let commands = interface.get_signature()?;
+
+    plugin.set_metadata(Some(metadata.clone()));
+    for signature in &commands {
+        let decl = PluginDeclaration::new(plugin.clone(), signature.clone());
+        working_set.add_decl(Box::new(decl));
+    }
+
+    working_set.update_plugin_registry(PluginRegistryItem::new(
+        plugin.identity(),
+        metadata,
+        commands,
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn command_line(command: &std::process::Command) -> Vec<&OsStr> {
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .collect()
+    }
+
+    #[test]
+    fn wasm_module_is_recognized() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/fixtures/plugins/nu_plugin_minimal.wasm");
+        assert!(is_wasm_module(&fixture));
+        assert!(!is_wasm_module(Path::new(file!())));
+    }
+
+    #[test]
+    fn wasm_plugins_run_with_wasmtime_by_default() {
+        let command = create_command(
+            Path::new("nu_plugin_example.wasm"),
+            None,
+            &CommunicationMode::Stdio,
+            None,
+        );
+        assert_eq!(
+            command_line(&command),
+            [
+                "wasmtime",
+                "run",
+                "-W",
+                "threads=y",
+                "-S",
+                "threads=y",
+                "nu_plugin_example.wasm",
+                "--stdio"
+            ]
+        );
+    }
+
+    #[test]
+    fn wasm_plugins_run_with_the_configured_runtime() {
+        let command = create_command(
+            Path::new("nu_plugin_example.wasm"),
+            None,
+            &CommunicationMode::Stdio,
+            Some("wasmer run --enable-threads"),
+        );
+        assert_eq!(
+            command_line(&command),
+            [
+                "wasmer",
+                "run",
+                "--enable-threads",
+                "nu_plugin_example.wasm",
+                "--stdio"
+            ]
+        );
+    }
+}
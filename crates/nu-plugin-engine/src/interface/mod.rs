@@ -20,10 +20,11 @@ use std::{
     collections::{BTreeMap, btree_map},
     path::Path,
     sync::{Arc, OnceLock, mpsc},
+    time::Instant,
 };
 
 use crate::{
-    PluginCustomValueWithSource, PluginExecutionContext, PluginGc, PluginSource,
+    PluginCustomValueWithSource, PluginExecutionContext, PluginGc, PluginMetrics, PluginSource,
     process::PluginProcess,
 };
 
@@ -153,6 +154,8 @@ pub struct PluginInterfaceManager {
     plugin_call_input_streams: BTreeMap<StreamId, PluginCallId>,
     /// Garbage collector handle, to notify about the state of the plugin
     gc: Option<PluginGc>,
+    /// Metrics to record plugin calls in
+    metrics: Option<Arc<PluginMetrics>>,
 }
 
 impl PluginInterfaceManager {
@@ -181,6 +184,7 @@ impl PluginInterfaceManager {
             plugin_call_subscription_receiver: subscription_rx,
             plugin_call_input_streams: BTreeMap::new(),
             gc: None,
+            metrics: None,
         }
     }
 
@@ -191,6 +195,12 @@ impl PluginInterfaceManager {
         self.gc = gc;
     }
 
+    /// Add metrics to this plugin. Interfaces created by the manager will record the plugin calls
+    /// they make in them.
+    pub fn set_metrics(&mut self, metrics: Option<Arc<PluginMetrics>>) {
+        self.metrics = metrics;
+    }
+
     /// Consume pending messages in the `plugin_call_subscription_receiver`
     fn receive_plugin_call_subscriptions(&mut self) {
         while let Ok((id, state)) = self.plugin_call_subscription_receiver.try_recv() {
@@ -465,6 +475,7 @@ impl InterfaceManager for PluginInterfaceManager {
             state: self.state.clone(),
             stream_manager_handle: self.stream_manager.get_handle(),
             gc: self.gc.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 
@@ -635,6 +646,8 @@ pub struct PluginInterface {
     stream_manager_handle: StreamManagerHandle,
     /// Handle to plugin garbage collector
     gc: Option<PluginGc>,
+    /// Metrics to record plugin calls in
+    metrics: Option<Arc<PluginMetrics>>,
 }
 
 impl PluginInterface {
@@ -924,12 +937,17 @@ impl PluginInterface {
             });
         }
 
+        let start = Instant::now();
         let result = self.write_plugin_call(call, context.as_deref())?;
 
         // Finish writing stream in the background
         result.writer.write_background()?;
 
-        self.receive_plugin_call_response(result.receiver, context, result.state)
+        let response = self.receive_plugin_call_response(result.receiver, context, result.state);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_call(start.elapsed());
+        }
+        response
     }
 
     /// Get the metadata from the plugin.
@@ -1078,6 +1096,16 @@ impl PluginInterface {
         self.custom_value_op_expecting_value(left, CustomValueOp::Operation(operator, right))
     }
 
+    /// Invoke functionality for an operator with a custom value on the right-hand side.
+    pub fn custom_value_reverse_operation(
+        &self,
+        left: Value,
+        operator: Spanned<Operator>,
+        right: Spanned<PluginCustomValueWithSource>,
+    ) -> Result<Value, ShellError> {
+        self.custom_value_op_expecting_value(right, CustomValueOp::ReverseOperation(operator, left))
+    }
+
     /// Invoke saving operation on a custom value.
     pub fn custom_value_save(
         &self,
@@ -1299,6 +1327,7 @@ impl CurrentCallState {
                     CustomValueOp::FollowPathString { .. } => Ok(()),
                     CustomValueOp::PartialCmp(value) => self.prepare_value(value, source),
                     CustomValueOp::Operation(_, value) => self.prepare_value(value, source),
+                    CustomValueOp::ReverseOperation(_, value) => self.prepare_value(value, source),
                     CustomValueOp::Save { .. } => Ok(()),
                     CustomValueOp::Dropped => Ok(()),
                 }
@@ -1380,8 +1409,16 @@ pub(crate) fn handle_engine_call(
             input,
             redirect_stdout,
             redirect_stderr,
+            redirect_env,
         } => context
-            .eval_closure(closure, positional, input, redirect_stdout, redirect_stderr)
+            .eval_closure(
+                closure,
+                positional,
+                input,
+                redirect_stdout,
+                redirect_stderr,
+                redirect_env,
+            )
             .map(EngineCallResponse::PipelineData),
         EngineCall::FindDecl(name) => context.find_decl(&name).map(|decl_id| {
             if let Some(decl_id) = decl_id {
@@ -445,6 +445,7 @@ fn manager_consume_engine_call_forwards_to_subscriber_with_pipeline_data() -> Re
             input: PipelineDataHeader::list_stream(ListStreamInfo::new(2, Span::test_data())),
             redirect_stdout: false,
             redirect_stderr: false,
+            redirect_env: false,
         },
     })?;
 
@@ -1147,6 +1148,7 @@ fn interface_get_dynamic_completion() -> Result<(), ShellError> {
             },
             pos: 0,
             strip: true,
+            prefix: String::new(),
         },
     })?;
 
@@ -1169,6 +1171,7 @@ fn interface_get_dynamic_completion() -> Result<(), ShellError> {
             },
             pos: 0,
             strip: true,
+            prefix: String::new(),
         },
     })?;
 
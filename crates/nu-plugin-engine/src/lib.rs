@@ -5,6 +5,7 @@ mod declaration;
 mod gc;
 mod init;
 mod interface;
+mod metrics;
 mod persistent;
 mod plugin_custom_value_with_source;
 mod process;
@@ -19,6 +20,7 @@ pub use declaration::PluginDeclaration;
 pub use gc::PluginGc;
 pub use init::*;
 pub use interface::{PluginInterface, PluginInterfaceManager};
+pub use metrics::PluginMetrics;
 pub use persistent::{GetPlugin, PersistentPlugin};
 pub use plugin_custom_value_with_source::{PluginCustomValueWithSource, WithSource};
 pub use source::PluginSource;
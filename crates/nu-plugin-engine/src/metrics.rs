@@ -0,0 +1,125 @@
+use std::{
+    io::{self, Read, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+/// Counters for the communication with a plugin, kept across restarts of the plugin.
+///
+/// These are shown by `plugin list --metrics`, to help find plugins that are slow or leak memory.
+#[derive(Debug, Default)]
+pub struct PluginMetrics {
+    calls: AtomicU64,
+    total_latency_nanos: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+impl PluginMetrics {
+    /// Record a plugin call that took `latency` until the plugin responded.
+    pub fn record_call(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// The number of plugin calls made.
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// The average time until the plugin responded to a call, if any calls were made.
+    pub fn average_latency(&self) -> Option<Duration> {
+        let calls = self.calls();
+        (calls > 0)
+            .then(|| Duration::from_nanos(self.total_latency_nanos.load(Ordering::Relaxed) / calls))
+    }
+
+    /// The number of bytes written to the plugin.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes read from the plugin.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received.load(Ordering::Relaxed)
+    }
+}
+
+/// Counts the bytes read from a plugin in [`PluginMetrics`].
+pub(crate) struct CountingReader<R> {
+    pub inner: R,
+    pub metrics: Option<Arc<PluginMetrics>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .bytes_received
+                .fetch_add(len as u64, Ordering::Relaxed);
+        }
+        Ok(len)
+    }
+}
+
+/// Counts the bytes written to a plugin in [`PluginMetrics`].
+pub(crate) struct CountingWriter<W> {
+    pub inner: W,
+    pub metrics: Option<Arc<PluginMetrics>>,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.bytes_sent.fetch_add(len as u64, Ordering::Relaxed);
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_latency() {
+        let metrics = PluginMetrics::default();
+        assert_eq!(None, metrics.average_latency());
+
+        metrics.record_call(Duration::from_millis(10));
+        metrics.record_call(Duration::from_millis(30));
+        assert_eq!(2, metrics.calls());
+        assert_eq!(Some(Duration::from_millis(20)), metrics.average_latency());
+    }
+
+    #[test]
+    fn counts_bytes() -> io::Result<()> {
+        let metrics = Arc::new(PluginMetrics::default());
+
+        let mut reader = CountingReader {
+            inner: &b"hello"[..],
+            metrics: Some(metrics.clone()),
+        };
+        reader.read_to_end(&mut vec![])?;
+
+        let mut writer = CountingWriter {
+            inner: vec![],
+            metrics: Some(metrics.clone()),
+        };
+        writer.write_all(b"hi")?;
+
+        assert_eq!(5, metrics.bytes_received());
+        assert_eq!(2, metrics.bytes_sent());
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
-    PluginGc,
-    init::{create_command, make_plugin_interface},
+    PluginGc, PluginMetrics,
+    init::{
+        WASM_RUNTIME_ENV, create_command, is_wasm_module, make_plugin_interface,
+        make_plugin_interface_with_streams,
+    },
 };
 
 use super::{PluginInterface, PluginSource};
-use nu_plugin_core::CommunicationMode;
+use nu_plugin_core::{CommunicationMode, PLUGIN_SECRET_ENV, RemoteAddress};
 use nu_protocol::{
     HandlerGuard, Handlers, PluginGcConfig, PluginIdentity, PluginMetadata, RegisteredPlugin,
     ShellError,
@@ -14,7 +17,9 @@ use nu_protocol::{
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
 };
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 
 /// A box that can keep a plugin that was spawned persistent for further uses. The plugin may or
 /// may not be currently running. [`.get()`] gets the currently running plugin, or spawns it if it's
@@ -25,6 +30,8 @@ pub struct PersistentPlugin {
     identity: PluginIdentity,
     /// Mutable state
     mutable: Mutex<MutableState>,
+    /// Counters for calls and bytes transferred, kept across restarts
+    metrics: Arc<PluginMetrics>,
 }
 
 /// The mutable state for the persistent plugin. This should all be behind one lock to prevent lock
@@ -41,6 +48,8 @@ struct MutableState {
     gc_config: PluginGcConfig,
     /// RAII guard for this plugin's signal handler
     signal_guard: Option<HandlerGuard>,
+    /// When the plugin's executable was last modified, as of the last time it was checked
+    executable_modified: Option<SystemTime>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -56,23 +65,73 @@ struct RunningPlugin {
     interface: PluginInterface,
     /// Garbage collector for the plugin
     gc: PluginGc,
+    /// When the plugin was started
+    started: Instant,
 }
 
 impl PersistentPlugin {
     /// Create a new persistent plugin. The plugin will not be spawned immediately.
     pub fn new(identity: PluginIdentity, gc_config: PluginGcConfig) -> PersistentPlugin {
         PersistentPlugin {
-            identity,
             mutable: Mutex::new(MutableState {
                 running: None,
                 metadata: None,
                 preferred_mode: None,
                 gc_config,
                 signal_guard: None,
+                executable_modified: executable_modified(&identity),
             }),
+            metrics: Arc::default(),
+            identity,
+        }
+    }
+
+    /// Check whether the plugin's executable was modified since the last check, or since the
+    /// plugin was created.
+    ///
+    /// An executable that can't be found, for example because it's being rebuilt, doesn't count
+    /// as changed until it's back.
+    pub fn executable_changed(&self) -> bool {
+        let modified = executable_modified(&self.identity);
+        let Ok(mut mutable) = self.mutable.lock() else {
+            return false;
+        };
+
+        if modified.is_some() && modified != mutable.executable_modified {
+            mutable.executable_modified = modified;
+            true
+        } else {
+            false
         }
     }
 
+    /// Get the amount of memory used by the plugin's process, in bytes, if it's running.
+    pub fn memory(&self) -> Option<u64> {
+        let pid = Pid::from_u32(self.pid()?);
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        system.process(pid).map(|process| process.memory())
+    }
+
+    /// Get the counters for calls to the plugin and bytes transferred.
+    pub fn metrics(&self) -> &PluginMetrics {
+        &self.metrics
+    }
+
+    /// Get how long the plugin's process has been running, if it's running.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.mutable
+            .lock()
+            .ok()?
+            .running
+            .as_ref()
+            .map(|running| running.started.elapsed())
+    }
+
     /// Get the plugin interface of the running plugin, or spawn it if it's not currently running.
     ///
     /// Will call `envs` to get environment variables to spawn the plugin if the plugin needs to be
@@ -144,6 +203,11 @@ impl PersistentPlugin {
             running.gc.stop_tracking();
         }
 
+        // Remote plugins are already running somewhere else, so connect instead of spawning
+        if let Some(address) = self.identity.address() {
+            return self.connect(address, envs, mutable);
+        }
+
         let source_file = self.identity.filename();
 
         // Determine the mode to use based on the preferred mode
@@ -157,7 +221,23 @@ impl PersistentPlugin {
             }
         };
 
-        let mut plugin_cmd = create_command(source_file, self.identity.shell(), &mode);
+        // Check the file before it gets to the runtime, which would fail with its own message
+        if self.identity.shell().is_none()
+            && source_file.extension().is_some_and(|ext| ext == "wasm")
+            && !is_wasm_module(source_file)
+        {
+            return Err(ShellError::PluginFailedToLoad {
+                msg: format!(
+                    "{} isn't a WebAssembly module. Plugins with a .wasm extension must be \
+                        compiled for the wasm32-wasip1-threads target.",
+                    source_file.display()
+                ),
+            });
+        }
+
+        let wasm_runtime = envs.get(WASM_RUNTIME_ENV).map(String::as_str);
+        let mut plugin_cmd =
+            create_command(source_file, self.identity.shell(), &mode, wasm_runtime);
 
         // We need the current environment variables for `python` based plugins
         // Or we'll likely have a problem when a plugin is implemented in a virtual Python environment.
@@ -172,6 +252,16 @@ impl PersistentPlugin {
         let child = plugin_cmd.spawn().map_err(|err| {
             let error_msg = match err.kind() {
                 std::io::ErrorKind::NotFound => match program_name {
+                    Ok(prog_name)
+                        if self.identity.shell().is_none()
+                            && source_file.extension().is_some_and(|ext| ext == "wasm") =>
+                    {
+                        format!(
+                            "Can't find the WebAssembly runtime {prog_name} to run this plugin. \
+                                Install wasmtime, or set $env.{WASM_RUNTIME_ENV} to the command line \
+                                of another runtime."
+                        )
+                    }
                     Ok(prog_name) => {
                         format!(
                             "Can't find {prog_name}, please make sure that {prog_name} is in PATH."
@@ -199,6 +289,7 @@ impl PersistentPlugin {
             Arc::new(PluginSource::new(self.clone())),
             Some(pid),
             Some(gc.clone()),
+            Some(self.metrics.clone()),
         )?;
 
         // If our current preferred mode is None, check to see if the plugin might support another
@@ -221,7 +312,55 @@ impl PersistentPlugin {
             return self.spawn(envs, mutable);
         }
 
-        mutable.running = Some(RunningPlugin { interface, gc });
+        mutable.running = Some(RunningPlugin {
+            interface,
+            gc,
+            started: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// Connect to a plugin listening on `address`, instead of running its executable.
+    ///
+    /// The secret the plugin expects is taken from `$env.NU_PLUGIN_SECRET`.
+    fn connect(
+        self: Arc<Self>,
+        address: &str,
+        envs: &HashMap<String, String>,
+        mutable: &mut MutableState,
+    ) -> Result<(), ShellError> {
+        let secret = envs.get(PLUGIN_SECRET_ENV).map_or("", String::as_str);
+        let address = RemoteAddress::parse(address)?;
+        let (reader, writer) =
+            address
+                .connect(secret)
+                .map_err(|err| ShellError::PluginFailedToLoad {
+                    msg: format!(
+                        "Failed to connect to the `{}` plugin at {address}: {err}",
+                        self.identity.name()
+                    ),
+                })?;
+
+        // Start the plugin garbage collector. Stopping a remote plugin only closes the connection,
+        // and the plugin keeps listening for the next one.
+        let gc = PluginGc::new(mutable.gc_config.clone(), &self)
+            .map_err(|err| IoError::new_internal(err, "Could not start plugin gc"))?;
+
+        let interface = make_plugin_interface_with_streams(
+            reader,
+            writer,
+            || (),
+            Arc::new(PluginSource::new(self.clone())),
+            None,
+            Some(gc.clone()),
+            Some(self.metrics.clone()),
+        )?;
+
+        mutable.running = Some(RunningPlugin {
+            interface,
+            gc,
+            started: Instant::now(),
+        });
         Ok(())
     }
 
@@ -335,6 +474,12 @@ impl RegisteredPlugin for PersistentPlugin {
     }
 }
 
+fn executable_modified(identity: &PluginIdentity) -> Option<SystemTime> {
+    std::fs::metadata(identity.filename())
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
 /// Anything that can produce a plugin interface.
 pub trait GetPlugin: RegisteredPlugin {
     /// Retrieve or spawn a [`PluginInterface`]. The `context` may be used for determining
@@ -367,3 +512,30 @@ impl GetPlugin for PersistentPlugin {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, time::Duration};
+
+    #[test]
+    fn executable_changed_after_modification() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "nu_plugin_executable_changed_{}",
+            std::process::id()
+        ));
+        let file = File::create(&path)?;
+        let identity = PluginIdentity::new(&path, None).expect("plugin filename is invalid");
+        let plugin = PersistentPlugin::new(identity, PluginGcConfig::default());
+
+        assert!(!plugin.executable_changed());
+
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))?;
+        assert!(plugin.executable_changed());
+        // The change is only reported once
+        assert!(!plugin.executable_changed());
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -235,6 +235,21 @@ impl CustomValue for PluginCustomValueWithSource {
             )
     }
 
+    fn reverse_operation(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        self.get_plugin(Some(rhs_span), "invoke operator")?
+            .custom_value_reverse_operation(
+                left.clone(),
+                operator.into_spanned(op),
+                self.clone().into_spanned(rhs_span),
+            )
+    }
+
     fn save(
         &self,
         path: Spanned<&Path>,
@@ -88,6 +88,12 @@ pub struct DynamicCompletionCall {
     pub strip: bool,
     /// The position in input buffer, which is useful to find placeholder from arguments.
     pub pos: usize,
+    /// The text of the argument being completed, up to the cursor.
+    ///
+    /// Suggestions are filtered by this afterwards, but it can be used to avoid fetching every
+    /// candidate, e.g. from a remote service.
+    #[serde(default)]
+    pub prefix: String,
 }
 
 /// Information about `get_dynamic_completion` of a plugin call invocation.
@@ -259,6 +265,8 @@ pub enum CustomValueOp {
     PartialCmp(Value),
     /// [`operation()`](nu_protocol::CustomValue::operation)
     Operation(Spanned<Operator>, Value),
+    /// [`reverse_operation()`](nu_protocol::CustomValue::reverse_operation)
+    ReverseOperation(Spanned<Operator>, Value),
     /// [`save()`](nu_protocol::CustomValue::save)
     Save {
         path: Spanned<PathBuf>,
@@ -278,6 +286,7 @@ impl CustomValueOp {
             CustomValueOp::FollowPathString { .. } => "follow_path_string",
             CustomValueOp::PartialCmp(_) => "partial_cmp",
             CustomValueOp::Operation(_, _) => "operation",
+            CustomValueOp::ReverseOperation(_, _) => "reverse_operation",
             CustomValueOp::Save { .. } => "save",
             CustomValueOp::Dropped => "dropped",
         }
@@ -599,6 +608,10 @@ pub enum EngineCall<D> {
         redirect_stdout: bool,
         /// Whether to redirect stderr from external commands
         redirect_stderr: bool,
+        /// Whether to keep changes the closure makes to the environment in the caller's
+        /// environment, like `do --env`
+        #[serde(default)]
+        redirect_env: bool,
     },
     /// Find a declaration by name
     FindDecl(String),
@@ -663,12 +676,14 @@ impl<D> EngineCall<D> {
                 input,
                 redirect_stdout,
                 redirect_stderr,
+                redirect_env,
             } => EngineCall::EvalClosure {
                 closure,
                 positional,
                 input: f(input)?,
                 redirect_stdout,
                 redirect_stderr,
+                redirect_env,
             },
             EngineCall::FindDecl(name) => EngineCall::FindDecl(name),
             EngineCall::GetBlockIR(block_id) => EngineCall::GetBlockIR(block_id),
@@ -93,6 +93,16 @@ impl CustomValue for PluginCustomValue {
         panic!("operation() not available on plugin custom value without source");
     }
 
+    fn reverse_operation(
+        &self,
+        _left: &Value,
+        _operator: Operator,
+        _op: Span,
+        _rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        panic!("reverse_operation() not available on plugin custom value without source");
+    }
+
     fn save(
         &self,
         _path: Spanned<&Path>,
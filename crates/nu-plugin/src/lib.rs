@@ -64,6 +64,20 @@
 //! }
 //! ```
 //!
+//! Plugins can also be compiled to WebAssembly for the `wasm32-wasip1-threads` target, with the
+//! default features of this crate disabled. Nushell runs plugins with a `.wasm` extension with
+//! [wasmtime](https://wasmtime.dev), which has to be in `PATH`, or with the runtime whose command
+//! line is in `$env.NU_WASM_RUNTIME`. Such plugins only have the access to the system that WASI
+//! gives them, and the same file works on every platform. Nushell doesn't embed a WebAssembly
+//! runtime, so the plugin still runs as a separate process of that runtime, talking to the engine
+//! over stdio like any other plugin.
+//!
+//! ```sh
+//! cargo build --release --target wasm32-wasip1-threads
+//! cp target/wasm32-wasip1-threads/release/nu_plugin_my.wasm .
+//! nu -c 'plugin add nu_plugin_my.wasm'
+//! ```
+//!
 //! Nushell's source tree contains a
 //! [Plugin Example](https://github.com/nushell/nushell/tree/main/crates/nu_plugin_example)
 //! that demonstrates the full range of plugin capabilities.
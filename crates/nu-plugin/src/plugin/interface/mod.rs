@@ -844,12 +844,80 @@ impl EngineInterface {
     /// Closure says: Hello, 4
     /// ```
     pub fn eval_closure_with_stream(
+        &self,
+        closure: &Spanned<Closure>,
+        positional: Vec<Value>,
+        input: PipelineData,
+        redirect_stdout: bool,
+        redirect_stderr: bool,
+    ) -> Result<PipelineData, ShellError> {
+        self.eval_closure_inner(
+            closure,
+            positional,
+            input,
+            redirect_stdout,
+            redirect_stderr,
+            false,
+        )
+    }
+
+    /// Ask the engine to evaluate a closure like
+    /// [`eval_closure_with_stream()`](Self::eval_closure_with_stream), but keep any changes it
+    /// makes to the environment in the environment of the caller of the plugin command, like
+    /// `do --env`.
+    ///
+    /// The closure always sees the caller's environment and overlays. This should only be used if
+    /// the command is documented to change the environment, as the user otherwise wouldn't expect
+    /// a closure to have effects outside of it. Changes are only kept if the closure is evaluated
+    /// before the command returns its response, so evaluating it lazily in an output stream won't
+    /// work.
+    ///
+    /// # Example
+    ///
+    /// Invoked as:
+    ///
+    /// ```nushell
+    /// my_command { $env.FOO = 'bar' }
+    /// $env.FOO # => bar
+    /// ```
+    ///
+    /// ```rust,no_run
+    /// # use nu_protocol::{ShellError, PipelineData};
+    /// # use nu_plugin::{EngineInterface, EvaluatedCall};
+    /// # fn example(engine: &EngineInterface, call: &EvaluatedCall) -> Result<(), ShellError> {
+    /// let closure = call.req(0)?;
+    /// engine
+    ///     .eval_closure_with_env(&closure, vec![], PipelineData::empty(), true, false)?
+    ///     .drain()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eval_closure_with_env(
+        &self,
+        closure: &Spanned<Closure>,
+        positional: Vec<Value>,
+        input: PipelineData,
+        redirect_stdout: bool,
+        redirect_stderr: bool,
+    ) -> Result<PipelineData, ShellError> {
+        self.eval_closure_inner(
+            closure,
+            positional,
+            input,
+            redirect_stdout,
+            redirect_stderr,
+            true,
+        )
+    }
+
+    fn eval_closure_inner(
         &self,
         closure: &Spanned<Closure>,
         mut positional: Vec<Value>,
         input: PipelineData,
         redirect_stdout: bool,
         redirect_stderr: bool,
+        redirect_env: bool,
     ) -> Result<PipelineData, ShellError> {
         // Ensure closure args have custom values serialized
         positional
@@ -862,6 +930,7 @@ impl EngineInterface {
             input,
             redirect_stdout,
             redirect_stderr,
+            redirect_env,
         };
 
         match self.engine_call(call)? {
@@ -1068,6 +1068,7 @@ fn interface_eval_closure_with_stream() -> Result<(), ShellError> {
                 input,
                 redirect_stdout,
                 redirect_stderr,
+                redirect_env,
             } => {
                 assert_eq!(
                     BlockId::new(42),
@@ -1086,6 +1087,7 @@ fn interface_eval_closure_with_stream() -> Result<(), ShellError> {
                 assert!(matches!(input, PipelineDataHeader::Empty));
                 assert!(redirect_stdout);
                 assert!(!redirect_stderr);
+                assert!(!redirect_env);
             }
             _ => panic!("wrong engine call: {call:?}"),
         },
@@ -6,14 +6,17 @@ use std::{
     ops::Deref,
     panic::AssertUnwindSafe,
     path::Path,
-    sync::mpsc::{self, TrySendError},
+    sync::{
+        Condvar, Mutex, PoisonError,
+        mpsc::{self, TrySendError},
+    },
     thread,
 };
 
 use nu_engine::documentation::{FormatterValue, HelpStyle, get_flags_section};
 use nu_plugin_core::{
-    ClientCommunicationIo, CommunicationMode, InterfaceManager, PluginEncoder, PluginRead,
-    PluginWrite,
+    ClientCommunicationIo, CommunicationMode, InterfaceManager, PLUGIN_SECRET_ENV, PluginEncoder,
+    PluginRead, PluginWrite, RemoteAddress,
 };
 use nu_plugin_protocol::{
     CallInfo, CustomValueOp, GetCompletionInfo, PluginCustomValue, PluginInput, PluginOutput,
@@ -114,6 +117,17 @@ pub trait Plugin: Sync {
     /// ```
     fn version(&self) -> String;
 
+    /// The oldest version of Nushell that the plugin works with.
+    ///
+    /// Compatibility of the plugin protocol is already checked when the plugin starts, so this is
+    /// only needed if the plugin relies on newer behavior of Nushell itself, e.g. of a command it
+    /// calls. Nushell refuses to add or load the plugin if it's older than this version.
+    ///
+    /// The default implementation returns `None`, which places no requirement.
+    fn min_nu_version(&self) -> Option<String> {
+        None
+    }
+
     /// The commands supported by the plugin
     ///
     /// Each [`PluginCommand`] contains both the signature of the command and the functionality it
@@ -217,6 +231,25 @@ pub trait Plugin: Sync {
             .map_err(LabeledError::from)
     }
 
+    /// Implement functionality for an operator with a custom value on the right-hand side, when
+    /// the value on the left-hand side doesn't support it.
+    ///
+    /// The default implementation of this method just calls [`CustomValue::reverse_operation`],
+    /// but the method can be implemented differently if accessing plugin state is desirable.
+    fn custom_value_reverse_operation(
+        &self,
+        engine: &EngineInterface,
+        left: Value,
+        operator: Spanned<Operator>,
+        right: Spanned<Box<dyn CustomValue>>,
+    ) -> Result<Value, LabeledError> {
+        let _ = engine;
+        right
+            .item
+            .reverse_operation(&left, operator.item, operator.span, right.span)
+            .map_err(LabeledError::from)
+    }
+
     /// Implement saving logic for a custom value.
     ///
     /// The default implementation of this method just calls [`CustomValue::save`], but
@@ -275,6 +308,12 @@ pub trait Plugin: Sync {
 ///    serve_plugin(&MyPlugin::new(), MsgPackSerializer)
 /// }
 /// ```
+///
+/// Besides being run by the engine, the plugin can be started on its own with
+/// `--listen tcp://<host>:<port>` (or `unix://<path>`), to serve engines that connect to it with
+/// `plugin add --address`. Those engines have to present the secret set in `NU_PLUGIN_SECRET`,
+/// which is required for TCP, and only loopback addresses are listened on unless `--allow-remote`
+/// is given too.
 pub fn serve_plugin(plugin: &impl Plugin, encoder: impl PluginEncoder + 'static) {
     let args: Vec<OsString> = env::args_os().skip(1).collect();
 
@@ -297,6 +336,19 @@ pub fn serve_plugin(plugin: &impl Plugin, encoder: impl PluginEncoder + 'static)
         std::process::exit(0)
     }
 
+    // Serve engines connecting over the network instead, until killed
+    if args[0] == "--listen" && (args.len() == 2 || args.len() == 3 && args[2] == "--allow-remote")
+    {
+        let allow_remote = args.len() == 3;
+        listen(
+            plugin,
+            &plugin_name,
+            &args[1].to_string_lossy(),
+            allow_remote,
+            encoder,
+        );
+    }
+
     // Implement different communication modes:
     let mode = if args[0] == "--stdio" && args.len() == 1 {
         // --stdio always supported.
@@ -375,6 +427,61 @@ pub fn serve_plugin(plugin: &impl Plugin, encoder: impl PluginEncoder + 'static)
     }
 }
 
+/// Serve connections from engines on `address`, one at a time, for `plugin add --address`.
+///
+/// Engines have to present the secret in `NU_PLUGIN_SECRET`, and TCP addresses that aren't
+/// loopback are only listened on with `allow_remote`.
+fn listen(
+    plugin: &impl Plugin,
+    plugin_name: &str,
+    address: &str,
+    allow_remote: bool,
+    encoder: impl PluginEncoder + 'static,
+) -> ! {
+    use std::io::{BufReader, BufWriter};
+    use std::sync::Mutex;
+
+    let address = match RemoteAddress::parse(address) {
+        Ok(address) => address,
+        Err(err) => {
+            eprintln!("{plugin_name}: {err}");
+            std::process::exit(1);
+        }
+    };
+
+    let secret = env::var(PLUGIN_SECRET_ENV).ok();
+    eprintln!("{plugin_name}: listening on {address}");
+    let result = address.listen(
+        secret.as_deref(),
+        allow_remote,
+        |(read_in, mut write_out)| {
+            if let Err(err) = tell_nushell_encoding(&mut write_out, &encoder) {
+                eprintln!("{plugin_name}: failed to tell nushell encoding: {err}");
+                return;
+            }
+
+            let read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, read_in);
+            let write = Mutex::new(BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, write_out));
+            let encoder_clone = encoder.clone();
+            let encoder = encoder.clone();
+            // A failed connection shouldn't stop the plugin from serving the next one
+            if let Err(err) = serve_plugin_io(
+                plugin,
+                plugin_name,
+                move || (read, encoder_clone),
+                move || (write, encoder),
+            ) {
+                eprintln!("Plugin `{plugin_name}` error: {err}");
+            }
+        },
+    );
+
+    if let Err(err) = result {
+        eprintln!("{plugin_name}: failed to listen on {address}: {err}");
+    }
+    std::process::exit(1)
+}
+
 fn tell_nushell_encoding(
     writer: &mut impl std::io::Write,
     encoder: &impl PluginEncoder,
@@ -507,7 +614,10 @@ where
             .map_err(ServePluginError::ThreadSpawnError)?;
     }
 
-    // Handle each Run plugin call on a thread
+    let pending_ops = PendingCustomValueOps::default();
+    let pending_ops = &pending_ops;
+
+    // Handle each plugin call on a thread
     thread::scope(|scope| {
         let run = |engine, call_info| {
             // SAFETY: It should be okay to use `AssertUnwindSafe` here, because we don't use any
@@ -572,17 +682,64 @@ where
             }
         };
 
-        // As an optimization: create one thread that can be reused for Run calls in sequence
-        let (run_tx, run_rx) = mpsc::sync_channel(0);
+        let run_custom_value_op = |engine: EngineInterface, custom_value, op| {
+            // SAFETY: It should be okay to use `AssertUnwindSafe` here, because we don't use any
+            // of the references after we catch the unwind, and immediately exit.
+            let unwind_result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                let write_result =
+                    custom_value_op(plugin, &engine, custom_value, op).try_to_report(&engine);
+                if let Err(err) = write_result {
+                    let _ = error_tx.send(err);
+                }
+            }));
+            if unwind_result.is_err() {
+                // Exit after unwind if a panic occurred
+                std::process::exit(1);
+            }
+        };
+
+        let handle = move |call: RunnerCall| match call {
+            RunnerCall::Run { engine, call } => run(engine, call),
+            RunnerCall::CustomValueOp {
+                engine,
+                custom_value,
+                op,
+            } => {
+                let value = custom_value.item.clone();
+                run_custom_value_op(engine, custom_value, op);
+                pending_ops.finish(&value);
+            }
+            RunnerCall::GetCompletion { engine, info } => get_dynamic_completion(engine, info),
+        };
+
+        // As an optimization: create one thread that can be reused for calls in sequence
+        let (runner_tx, runner_rx) = mpsc::sync_channel(0);
         thread::Builder::new()
             .name("plugin runner (primary)".into())
             .spawn_scoped(scope, move || {
-                for (engine, call) in run_rx {
-                    run(engine, call);
+                for call in runner_rx {
+                    handle(call);
                 }
             })
             .map_err(ServePluginError::ThreadSpawnError)?;
 
+        // Calls are handled on a runner thread, so that a slow call doesn't hold up other calls
+        // that the engine makes at the same time, e.g. from `par-each`
+        let dispatch = |call: RunnerCall| {
+            // Try to run it on the primary thread
+            match runner_tx.try_send(call) {
+                Ok(()) => Ok(()),
+                // If the primary thread isn't ready, spawn a secondary thread to do it
+                Err(TrySendError::Full(call)) | Err(TrySendError::Disconnected(call)) => {
+                    thread::Builder::new()
+                        .name("plugin runner (secondary)".into())
+                        .spawn_scoped(scope, move || handle(call))
+                        .map(|_| ())
+                        .map_err(ServePluginError::ThreadSpawnError)
+                }
+            }
+        };
+
         for plugin_call in call_receiver {
             // Check for pending errors
             if let Ok(error) = error_rx.try_recv() {
@@ -592,9 +749,11 @@ where
             match plugin_call {
                 // Send metadata back to nushell so it can be stored with the plugin signatures
                 ReceivedPluginCall::Metadata { engine } => {
-                    engine
-                        .write_metadata(PluginMetadata::new().with_version(plugin.version()))
-                        .try_to_report(&engine)?;
+                    let mut metadata = PluginMetadata::new().with_version(plugin.version());
+                    if let Some(min_nu_version) = plugin.min_nu_version() {
+                        metadata = metadata.with_min_nu_version(min_nu_version);
+                    }
+                    engine.write_metadata(metadata).try_to_report(&engine)?;
                 }
                 // Sending the signature back to nushell to create the declaration definition
                 ReceivedPluginCall::Signature { engine } => {
@@ -611,18 +770,17 @@ where
                 }
                 // Run the plugin on a background thread, handling any input or output streams
                 ReceivedPluginCall::Run { engine, call } => {
-                    // Try to run it on the primary thread
-                    match run_tx.try_send((engine, call)) {
-                        Ok(()) => (),
-                        // If the primary thread isn't ready, spawn a secondary thread to do it
-                        Err(TrySendError::Full((engine, call)))
-                        | Err(TrySendError::Disconnected((engine, call))) => {
-                            thread::Builder::new()
-                                .name("plugin runner (secondary)".into())
-                                .spawn_scoped(scope, move || run(engine, call))
-                                .map_err(ServePluginError::ThreadSpawnError)?;
-                        }
-                    }
+                    dispatch(RunnerCall::Run { engine, call })?;
+                }
+                // Drop a custom value in order, once the operations on it that were received
+                // before are done
+                ReceivedPluginCall::CustomValueOp {
+                    engine,
+                    custom_value,
+                    op: CustomValueOp::Dropped,
+                } => {
+                    pending_ops.wait(&custom_value.item);
+                    run_custom_value_op(engine, custom_value, CustomValueOp::Dropped);
                 }
                 // Do an operation on a custom value
                 ReceivedPluginCall::CustomValueOp {
@@ -630,10 +788,15 @@ where
                     custom_value,
                     op,
                 } => {
-                    custom_value_op(plugin, &engine, custom_value, op).try_to_report(&engine)?;
+                    pending_ops.start(&custom_value.item);
+                    dispatch(RunnerCall::CustomValueOp {
+                        engine,
+                        custom_value,
+                        op,
+                    })?;
                 }
                 ReceivedPluginCall::GetCompletion { engine, info } => {
-                    get_dynamic_completion(engine, info)
+                    dispatch(RunnerCall::GetCompletion { engine, info })?;
                 }
             }
         }
@@ -652,6 +815,64 @@ where
     }
 }
 
+/// A plugin call handled on one of the runner threads.
+enum RunnerCall {
+    Run {
+        engine: EngineInterface,
+        call: CallInfo<PipelineData>,
+    },
+    CustomValueOp {
+        engine: EngineInterface,
+        custom_value: Spanned<PluginCustomValue>,
+        op: CustomValueOp,
+    },
+    GetCompletion {
+        engine: EngineInterface,
+        info: GetCompletionInfo,
+    },
+}
+
+/// Counts the operations on each custom value that are running on the runner threads, so that a
+/// value isn't dropped while it's still in use.
+#[derive(Default)]
+struct PendingCustomValueOps {
+    counts: Mutex<HashMap<(String, Vec<u8>), usize>>,
+    done: Condvar,
+}
+
+impl PendingCustomValueOps {
+    fn key(value: &PluginCustomValue) -> (String, Vec<u8>) {
+        (value.name().to_owned(), value.data().to_vec())
+    }
+
+    fn start(&self, value: &PluginCustomValue) {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        *counts.entry(Self::key(value)).or_default() += 1;
+    }
+
+    fn finish(&self, value: &PluginCustomValue) {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let key = Self::key(value);
+        if let Some(count) = counts.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&key);
+                self.done.notify_all();
+            }
+        }
+    }
+
+    /// Wait until there are no operations running on the value
+    fn wait(&self, value: &PluginCustomValue) {
+        let key = Self::key(value);
+        let counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let _counts = self
+            .done
+            .wait_while(counts, |counts| counts.contains_key(&key))
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+}
+
 fn custom_value_op(
     plugin: &impl Plugin,
     engine: &EngineInterface,
@@ -709,6 +930,15 @@ fn custom_value_op(
                 .write_response(result)
                 .and_then(|writer| writer.write())
         }
+        CustomValueOp::ReverseOperation(operator, mut left) => {
+            PluginCustomValue::deserialize_custom_values_in(&mut left)?;
+            let result = plugin
+                .custom_value_reverse_operation(engine, left, operator, local_value)
+                .map(|value| PipelineData::value(value, None));
+            engine
+                .write_response(result)
+                .and_then(|writer| writer.write())
+        }
         CustomValueOp::Save {
             path,
             save_call_span,
@@ -745,6 +975,10 @@ fn print_help(plugin: &impl Plugin, encoder: impl PluginEncoder) {
         .map(|stem| stem.to_string_lossy().into_owned())
         .unwrap_or_else(|| "(unknown)".into());
     println!("Plugin file path: {plugin_name}");
+    println!(
+        "Run with `--listen tcp://<host>:<port>` to serve engines connecting with `plugin add --address`,\n\
+        with the secret they present in $NU_PLUGIN_SECRET, and `--allow-remote` for other hosts than this one"
+    );
 
     let mut help = String::new();
     let help_style = HelpStyle::default();
@@ -816,3 +1050,28 @@ fn print_help(plugin: &impl Plugin, encoder: impl PluginEncoder) {
 
     println!("{help}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn pending_custom_value_ops_wait_for_the_same_value() {
+        let pending = PendingCustomValueOps::default();
+        let value = PluginCustomValue::new("test".into(), vec![1], true);
+        let other = PluginCustomValue::new("test".into(), vec![2], true);
+
+        pending.start(&value);
+        // Doesn't wait for operations on other values
+        pending.wait(&other);
+
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| pending.wait(&value));
+            thread::sleep(Duration::from_millis(50));
+            assert!(!waiter.is_finished());
+            pending.finish(&value);
+            waiter.join().expect("waiter panicked");
+        });
+    }
+}
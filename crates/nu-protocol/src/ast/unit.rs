@@ -16,7 +16,7 @@ pub struct ParseUnitError(());
 
 impl fmt::Display for ParseUnitError {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(fmt, "invalid file size or duration unit")
+        write!(fmt, "invalid file size, duration or rate unit")
     }
 }
 
@@ -33,6 +33,9 @@ pub enum Unit {
     Hour,
     Day,
     Week,
+
+    // Data rate unit
+    BitPerSecond,
 }
 
 // TODO: something like `Filesize::from_unit` in the future?
@@ -73,6 +76,7 @@ impl Unit {
             Unit::Hour => duration_mul_and_check(size, 1000 * 1000 * 1000 * 60 * 60, span),
             Unit::Day => duration_mul_and_check(size, 1000 * 1000 * 1000 * 60 * 60 * 24, span),
             Unit::Week => duration_mul_and_check(size, 1000 * 1000 * 1000 * 60 * 60 * 24 * 7, span),
+            Unit::BitPerSecond => Ok(Value::rate(size, span)),
         }
     }
 
@@ -100,6 +104,7 @@ impl Unit {
             Unit::Hour => "hr",
             Unit::Day => "day",
             Unit::Week => "wk",
+            Unit::BitPerSecond => "bit/sec",
         }
     }
 }
@@ -121,6 +126,7 @@ impl FromStr for Unit {
             "hr" => Ok(Unit::Hour),
             "day" => Ok(Unit::Day),
             "wk" => Ok(Unit::Week),
+            "bit/sec" => Ok(Unit::BitPerSecond),
             _ => Err(ParseUnitError(())),
         }
     }
@@ -70,4 +70,6 @@ pub struct DynamicCompletionCallRef<'a> {
     pub strip: bool,
     /// The position in input buffer, which is useful to find placeholder from arguments.
     pub pos: usize,
+    /// The text of the argument being completed, up to the cursor.
+    pub prefix: &'a str,
 }
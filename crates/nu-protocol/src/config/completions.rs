@@ -1,6 +1,7 @@
 use super::{config_update_string_enum, prelude::*};
 use crate as nu_protocol;
 use crate::engine::Closure;
+use std::{collections::HashMap, time::Duration};
 
 #[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CompletionAlgorithm {
@@ -59,6 +60,12 @@ pub struct ExternalCompleterConfig {
     pub enable: bool,
     pub max_results: i64,
     pub completer: Option<Closure>,
+    /// Completers for specific commands, by the words the command line starts with
+    pub completers: HashMap<String, Closure>,
+    /// How long the results of a completer are reused for the same command line and directory
+    pub cache_duration: Duration,
+    /// How long to wait for a completer before falling back to file completions
+    pub timeout: Option<Duration>,
 }
 
 impl Default for ExternalCompleterConfig {
@@ -67,10 +74,50 @@ impl Default for ExternalCompleterConfig {
             enable: true,
             max_results: 100,
             completer: None,
+            completers: HashMap::new(),
+            cache_duration: Duration::ZERO,
+            timeout: None,
         }
     }
 }
 
+impl ExternalCompleterConfig {
+    /// The completer to use for a command line, given as its words.
+    ///
+    /// This is the completer in `completers` with the longest key matching the start of the
+    /// command line, so `git commit` takes precedence over `git`, or else `completer`.
+    pub fn completer_for(&self, words: &[impl AsRef<str>]) -> Option<&Closure> {
+        self.completers
+            .iter()
+            .filter_map(|(key, closure)| {
+                let key_words = key.split_whitespace().collect::<Vec<_>>();
+                let matches = !key_words.is_empty()
+                    && key_words.len() < words.len()
+                    && key_words
+                        .iter()
+                        .zip(words)
+                        .all(|(key_word, word)| *key_word == word.as_ref());
+                matches.then_some((key_words.len(), closure))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, closure)| closure)
+            .or(self.completer.as_ref())
+    }
+}
+
+fn update_duration(
+    duration: &mut Duration,
+    value: &Value,
+    path: &ConfigPath,
+    errors: &mut ConfigErrors,
+) {
+    match value.as_duration() {
+        Ok(nanos) if nanos >= 0 => *duration = Duration::from_nanos(nanos as u64),
+        Ok(_) => errors.invalid_value(path, "a non-negative duration", value),
+        Err(_) => errors.type_mismatch(path, Type::Duration, value),
+    }
+}
+
 impl UpdateFromValue for ExternalCompleterConfig {
     fn update<'a>(
         &mut self,
@@ -91,6 +138,34 @@ impl UpdateFromValue for ExternalCompleterConfig {
                     Value::Closure { val, .. } => self.completer = Some(val.as_ref().clone()),
                     _ => errors.type_mismatch(path, Type::custom("closure or nothing"), val),
                 },
+                "completers" => match val {
+                    Value::Record { val: record, .. } => {
+                        let mut completers = HashMap::new();
+                        for (key, completer) in record.iter() {
+                            match completer {
+                                Value::Closure { val, .. } => {
+                                    completers.insert(key.clone(), val.as_ref().clone());
+                                }
+                                _ => errors.type_mismatch(
+                                    &mut path.push(key),
+                                    Type::Closure,
+                                    completer,
+                                ),
+                            }
+                        }
+                        self.completers = completers;
+                    }
+                    _ => errors.type_mismatch(path, Type::record(), val),
+                },
+                "cache_duration" => update_duration(&mut self.cache_duration, val, path, errors),
+                "timeout" => match val {
+                    Value::Nothing { .. } => self.timeout = None,
+                    _ => {
+                        let mut timeout = Duration::ZERO;
+                        update_duration(&mut timeout, val, path, errors);
+                        self.timeout = Some(timeout);
+                    }
+                },
                 "max_results" => self.max_results.update(val, path, errors),
                 "enable" => self.enable.update(val, path, errors),
                 _ => errors.unknown_option(path, val),
@@ -151,3 +226,56 @@ impl UpdateFromValue for CompletionConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockId;
+
+    fn closure(block_id: usize) -> Closure {
+        Closure {
+            block_id: BlockId::new(block_id),
+            captures: vec![],
+        }
+    }
+
+    fn completer_for(config: &ExternalCompleterConfig, line: &str) -> Option<BlockId> {
+        let words = line.split(' ').collect::<Vec<_>>();
+        config.completer_for(&words).map(|closure| closure.block_id)
+    }
+
+    #[test]
+    fn test_completer_for_longest_prefix() {
+        let config = ExternalCompleterConfig {
+            completer: Some(closure(0)),
+            completers: HashMap::from([
+                ("git".into(), closure(1)),
+                ("git commit".into(), closure(2)),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            completer_for(&config, "git commit -"),
+            Some(BlockId::new(2))
+        );
+        assert_eq!(completer_for(&config, "git push "), Some(BlockId::new(1)));
+        assert_eq!(
+            completer_for(&config, "git committed "),
+            Some(BlockId::new(1))
+        );
+        // The word being completed isn't matched, so the command itself can be completed
+        assert_eq!(completer_for(&config, "git"), Some(BlockId::new(0)));
+        assert_eq!(completer_for(&config, "ls "), Some(BlockId::new(0)));
+    }
+
+    #[test]
+    fn test_completer_for_without_fallback() {
+        let config = ExternalCompleterConfig {
+            completers: HashMap::from([("git".into(), closure(1))]),
+            ..Default::default()
+        };
+
+        assert_eq!(completer_for(&config, "ls "), None);
+    }
+}
@@ -64,6 +64,7 @@ pub struct HistoryConfig {
     pub sync_on_enter: bool,
     pub file_format: HistoryFileFormat,
     pub isolation: bool,
+    pub ignore_duplicates: bool,
     pub path: HistoryPath,
 }
 
@@ -85,6 +86,7 @@ impl IntoValue for HistoryConfig {
                 "sync_on_enter" => self.sync_on_enter.into_value(span),
                 "file_format" => self.file_format.into_value(span),
                 "isolation" => self.isolation.into_value(span),
+                "ignore_duplicates" => self.ignore_duplicates.into_value(span),
                 "path" => self.path.into_value(span),
             },
             span,
@@ -118,6 +120,7 @@ impl Default for HistoryConfig {
             sync_on_enter: true,
             file_format: HistoryFileFormat::Plaintext,
             isolation: false,
+            ignore_duplicates: false,
             path: HistoryPath::Default,
         }
     }
@@ -138,6 +141,7 @@ impl UpdateFromValue for HistoryConfig {
         // might not be correct if file format was changed away from sqlite rather than isolation,
         // but this is an edge case and the span of the relevant value here should be close enough
         let mut isolation_span = value.span();
+        let mut ignore_duplicates_span = value.span();
 
         for (col, val) in record.iter() {
             let path = &mut path.push(col);
@@ -146,6 +150,10 @@ impl UpdateFromValue for HistoryConfig {
                     isolation_span = val.span();
                     self.isolation.update(val, path, errors)
                 }
+                "ignore_duplicates" => {
+                    ignore_duplicates_span = val.span();
+                    self.ignore_duplicates.update(val, path, errors)
+                }
                 "sync_on_enter" => self.sync_on_enter.update(val, path, errors),
                 "max_size" => self.max_size.update(val, path, errors),
                 "file_format" => self.file_format.update(val, path, errors),
@@ -181,5 +189,17 @@ impl UpdateFromValue for HistoryConfig {
             (true, HistoryFileFormat::Sqlite) => (),
             (false, _) => (),
         }
+
+        match (self.ignore_duplicates, self.file_format) {
+            (true, HistoryFileFormat::Plaintext) => {
+                errors.warn(ConfigWarning::IncompatibleOptions {
+                    label: "ignoring duplicate history entries only compatible with SQLite format",
+                    span: ignore_duplicates_span,
+                    help: r#"disable ignore_duplicates, or set $env.config.history.file_format = "sqlite""#,
+                });
+            }
+            (true, HistoryFileFormat::Sqlite) => (),
+            (false, _) => (),
+        }
     }
 }
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 pub struct Hooks {
     pub pre_prompt: Vec<Value>,
     pub pre_execution: Vec<Value>,
+    pub command_duration: Vec<Value>,
     pub env_change: HashMap<String, Vec<Value>>,
     pub display_output: Option<Value>,
     pub command_not_found: Option<Value>,
@@ -17,6 +18,7 @@ impl Hooks {
         Self {
             pre_prompt: Vec::new(),
             pre_execution: Vec::new(),
+            command_duration: Vec::new(),
             env_change: HashMap::new(),
             display_output: Some(Value::string(
                 "if (term size).columns >= 100 { table -e } else { table }",
@@ -62,6 +64,13 @@ impl UpdateFromValue for Hooks {
                         errors.type_mismatch(path, Type::list(Type::Any), val);
                     }
                 }
+                "command_duration" => {
+                    if let Ok(hooks) = val.as_list() {
+                        self.command_duration = hooks.into()
+                    } else {
+                        errors.type_mismatch(path, Type::list(Type::Any), val);
+                    }
+                }
                 "env_change" => {
                     if let Ok(record) = val.as_record() {
                         self.env_change = record
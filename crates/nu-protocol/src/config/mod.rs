@@ -18,12 +18,19 @@ pub use helper::extract_value;
 pub use history::{HistoryConfig, HistoryFileFormat, HistoryPath};
 pub use hooks::Hooks;
 pub use ls::LsConfig;
+pub use notify::{NotifyConfig, NotifyMethod};
 pub use output::{BannerKind, ErrorStyle};
-pub use plugin_gc::{PluginGcConfig, PluginGcConfigs};
-pub use reedline::{CursorShapeConfig, EditBindings, NuCursorShape, ParsedKeybinding, ParsedMenu};
+pub use plugin_gc::{PluginGcConfig, PluginGcConfigs, PluginStartMode};
+pub use reedline::{
+    CursorShapeConfig, EditBindings, HintRanking, NuCursorShape, ParsedKeybinding, ParsedMenu,
+};
 pub use rm::RmConfig;
 pub use shell_integration::ShellIntegrationConfig;
-pub use table::{FooterMode, TableConfig, TableIndent, TableIndexMode, TableMode, TrimStrategy};
+pub use table::{
+    FooterMode, TableBorderChars, TableColumnAlignment, TableColumnConfig, TableConfig,
+    TableCustomTheme, TableIndent, TableIndexMode, TableMode, TableNumberFormat, TrimStrategy,
+};
+pub use terminal_title::TerminalTitleConfig;
 
 mod ansi_coloring;
 mod clip;
@@ -36,6 +43,7 @@ mod helper;
 mod history;
 mod hooks;
 mod ls;
+mod notify;
 mod output;
 mod plugin_gc;
 mod prelude;
@@ -43,6 +51,7 @@ mod reedline;
 mod rm;
 mod shell_integration;
 mod table;
+mod terminal_title;
 
 #[derive(Clone, Debug, IntoValue, Serialize, Deserialize)]
 pub struct Config {
@@ -58,12 +67,19 @@ pub struct Config {
     pub completions: CompletionConfig,
     pub edit_mode: EditBindings,
     pub show_hints: bool,
+    pub hint_ranking: HintRanking,
     pub history: HistoryConfig,
     pub keybindings: Vec<ParsedKeybinding>,
+    /// Abbreviations expanded in the line editor when space is pressed after them.
+    pub abbreviations: HashMap<String, String>,
     pub menus: Vec<ParsedMenu>,
     pub hooks: Hooks,
+    /// Load the `.nu-env` file of the current directory, once it's trusted.
+    pub directory_env: bool,
+    pub notify: NotifyConfig,
     pub rm: RmConfig,
     pub shell_integration: ShellIntegrationConfig,
+    pub terminal_title: TerminalTitleConfig,
     pub buffer_editor: Value,
     pub show_banner: BannerKind,
     pub bracketed_paste: bool,
@@ -84,6 +100,8 @@ pub struct Config {
     pub plugins: HashMap<String, Value>,
     /// Configuration for plugin garbage collection.
     pub plugin_gc: PluginGcConfigs,
+    /// Reload plugins in the REPL when their executable changes.
+    pub plugin_hot_reload: bool,
 }
 
 impl Default for Config {
@@ -119,17 +137,25 @@ impl Default for Config {
             bracketed_paste: true,
             edit_mode: EditBindings::default(),
             show_hints: true,
+            hint_ranking: HintRanking::default(),
 
             shell_integration: ShellIntegrationConfig::default(),
+            terminal_title: TerminalTitleConfig::default(),
 
             render_right_prompt_on_last_line: false,
 
             hooks: Hooks::new(),
 
+            directory_env: false,
+
+            notify: NotifyConfig::default(),
+
             menus: Vec::new(),
 
             keybindings: Vec::new(),
 
+            abbreviations: HashMap::new(),
+
             error_style: ErrorStyle::default(),
             error_lines: 1,
             display_errors: DisplayErrors::default(),
@@ -139,6 +165,7 @@ impl Default for Config {
 
             plugins: HashMap::new(),
             plugin_gc: PluginGcConfigs::default(),
+            plugin_hot_reload: false,
         }
     }
 }
@@ -173,7 +200,9 @@ impl UpdateFromValue for Config {
                 "use_ansi_coloring" => self.use_ansi_coloring.update(val, path, errors),
                 "edit_mode" => self.edit_mode.update(val, path, errors),
                 "show_hints" => self.show_hints.update(val, path, errors),
+                "hint_ranking" => self.hint_ranking.update(val, path, errors),
                 "shell_integration" => self.shell_integration.update(val, path, errors),
+                "terminal_title" => self.terminal_title.update(val, path, errors),
                 "buffer_editor" => match val {
                     Value::Nothing { .. } | Value::String { .. } => {
                         self.buffer_editor = val.clone();
@@ -201,6 +230,7 @@ impl UpdateFromValue for Config {
                 }
                 "plugins" => self.plugins.update(val, path, errors),
                 "plugin_gc" => self.plugin_gc.update(val, path, errors),
+                "plugin_hot_reload" => self.plugin_hot_reload.update(val, path, errors),
                 "menus" => match Vec::from_value(val.clone()) {
                     Ok(menus) => self.menus = menus,
                     Err(err) => errors.error(err.into()),
@@ -209,7 +239,10 @@ impl UpdateFromValue for Config {
                     Ok(keybindings) => self.keybindings = keybindings,
                     Err(err) => errors.error(err.into()),
                 },
+                "abbreviations" => self.abbreviations.update(val, path, errors),
                 "hooks" => self.hooks.update(val, path, errors),
+                "directory_env" => self.directory_env.update(val, path, errors),
+                "notify" => self.notify.update(val, path, errors),
                 "datetime_format" => self.datetime_format.update(val, path, errors),
                 "error_style" => self.error_style.update(val, path, errors),
                 "error_lines" => {
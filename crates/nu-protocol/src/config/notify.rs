@@ -0,0 +1,89 @@
+use super::{config_update_string_enum, prelude::*};
+use crate as nu_protocol;
+
+/// Configures the notification sent when a command takes a long time to finish
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// Notify when a command takes at least this long (in nanoseconds), or never if `None`
+    pub threshold: Option<i64>,
+    /// How to send the notification
+    pub method: NotifyMethod,
+}
+
+impl IntoValue for NotifyConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "threshold" => self
+                .threshold
+                .map_or_else(|| Value::nothing(span), |threshold| Value::duration(threshold, span)),
+            "method" => self.method.into_value(span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for NotifyConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "threshold" => match val {
+                    Value::Nothing { .. } => self.threshold = None,
+                    Value::Duration { val: duration, .. } if *duration >= 0 => {
+                        self.threshold = Some(*duration)
+                    }
+                    Value::Duration { .. } => {
+                        errors.invalid_value(path, "a non-negative duration", val)
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("duration or nothing"), val),
+                },
+                "method" => self.method.update(val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
+
+/// How to notify that a long command finished
+#[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotifyMethod {
+    /// A desktop notification through the terminal, with OSC 777
+    #[default]
+    Osc777,
+    /// A desktop notification through the terminal, with OSC 9
+    Osc9,
+    /// The terminal bell
+    Bell,
+    /// A desktop notification with the `notify-send` command
+    NotifySend,
+}
+
+impl FromStr for NotifyMethod {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "osc777" => Ok(Self::Osc777),
+            "osc9" => Ok(Self::Osc9),
+            "bell" => Ok(Self::Bell),
+            "notify_send" => Ok(Self::NotifySend),
+            _ => Err("'osc777', 'osc9', 'bell', or 'notify_send'"),
+        }
+    }
+}
+
+impl UpdateFromValue for NotifyMethod {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
@@ -1,5 +1,5 @@
-use super::prelude::*;
-use crate as nu_protocol;
+use super::{config_update_string_enum, prelude::*};
+use crate::{self as nu_protocol, Filesize};
 use std::collections::HashMap;
 
 /// Configures when plugins should be stopped if inactive
@@ -42,13 +42,17 @@ impl UpdateFromValue for PluginGcConfigs {
     }
 }
 
-/// Configures when a plugin should be stopped if inactive
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Configures when a plugin should be started, and when it should be stopped if inactive
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PluginGcConfig {
     /// True if the plugin should be stopped automatically
     pub enabled: bool,
     /// When to stop the plugin if not in use for this long (in nanoseconds)
     pub stop_after: i64,
+    /// Stop the plugin as soon as it's not in use if it's using more memory than this
+    pub max_idle_memory: Option<Filesize>,
+    /// When to start the plugin
+    pub start: PluginStartMode,
 }
 
 impl Default for PluginGcConfig {
@@ -56,6 +60,8 @@ impl Default for PluginGcConfig {
         PluginGcConfig {
             enabled: true,
             stop_after: 10_000_000_000, // 10sec
+            max_idle_memory: None,
+            start: PluginStartMode::Lazy,
         }
     }
 }
@@ -65,6 +71,8 @@ impl IntoValue for PluginGcConfig {
         record! {
             "enabled" => self.enabled.into_value(span),
             "stop_after" => Value::duration(self.stop_after, span),
+            "max_idle_memory" => self.max_idle_memory.into_value(span),
+            "start" => self.start.into_value(span),
         }
         .into_value(span)
     }
@@ -97,12 +105,53 @@ impl UpdateFromValue for PluginGcConfig {
                         errors.type_mismatch(path, Type::Duration, val);
                     }
                 }
+                "max_idle_memory" => match val {
+                    Value::Nothing { .. } => self.max_idle_memory = None,
+                    Value::Filesize { val: size, .. } if size.get() >= 0 => {
+                        self.max_idle_memory = Some(*size)
+                    }
+                    Value::Filesize { .. } => {
+                        errors.invalid_value(path, "a non-negative filesize", val)
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("filesize or nothing"), val),
+                },
+                "start" => self.start.update(val, path, errors),
                 _ => errors.unknown_option(path, val),
             }
         }
     }
 }
 
+/// When a plugin should be started
+#[derive(
+    Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum PluginStartMode {
+    /// Start the plugin the first time one of its commands is used
+    #[default]
+    Lazy,
+    /// Start the plugin when the shell starts
+    Eager,
+}
+
+impl FromStr for PluginStartMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lazy" => Ok(Self::Lazy),
+            "eager" => Ok(Self::Eager),
+            _ => Err("'lazy' or 'eager'"),
+        }
+    }
+}
+
+impl UpdateFromValue for PluginStartMode {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,12 +163,16 @@ mod tests {
                 default: PluginGcConfig {
                     enabled: true,
                     stop_after: 30_000_000_000,
+                    max_idle_memory: None,
+                    start: PluginStartMode::Lazy,
                 },
                 plugins: [(
                     "my_plugin".to_owned(),
                     PluginGcConfig {
                         enabled: false,
                         stop_after: 0,
+                        max_idle_memory: Some(Filesize::new(100_000_000)),
+                        start: PluginStartMode::Eager,
                     },
                 )]
                 .into_iter()
@@ -129,11 +182,15 @@ mod tests {
                 "default" => Value::test_record(record! {
                     "enabled" => Value::test_bool(true),
                     "stop_after" => Value::test_duration(30_000_000_000),
+                    "max_idle_memory" => Value::test_nothing(),
+                    "start" => Value::test_string("lazy"),
                 }),
                 "plugins" => Value::test_record(record! {
                     "my_plugin" => Value::test_record(record! {
                         "enabled" => Value::test_bool(false),
                         "stop_after" => Value::test_duration(0),
+                        "max_idle_memory" => Value::test_filesize(100_000_000),
+                        "start" => Value::test_string("eager"),
                     }),
                 }),
             }),
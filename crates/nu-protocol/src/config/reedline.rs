@@ -116,3 +116,32 @@ impl UpdateFromValue for EditBindings {
         config_update_string_enum(self, value, path, errors)
     }
 }
+
+/// How the history entry shown as an inline hint is chosen.
+#[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintRanking {
+    /// The most recent entry starting with the current line.
+    #[default]
+    Recent,
+    /// The entry ranked highest by how recently and often it was run, whether it was run in the
+    /// current directory, and how often it succeeded.
+    Ranked,
+}
+
+impl FromStr for HintRanking {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "recent" => Ok(Self::Recent),
+            "ranked" => Ok(Self::Ranked),
+            _ => Err("'recent' or 'ranked'"),
+        }
+    }
+}
+
+impl UpdateFromValue for HintRanking {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
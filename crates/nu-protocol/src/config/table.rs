@@ -1,4 +1,4 @@
-use std::{num::NonZeroU16, time::Duration};
+use std::{collections::HashMap, num::NonZeroU16, time::Duration};
 
 use super::{config_update_string_enum, prelude::*};
 use crate::{self as nu_protocol, ConfigError, FromValue};
@@ -24,6 +24,8 @@ pub enum TableMode {
     BasicCompact,
     Single,
     Double,
+    /// The theme of `table.custom_theme`.
+    Custom,
 }
 
 impl FromStr for TableMode {
@@ -50,8 +52,9 @@ impl FromStr for TableMode {
             "basic_compact" => Ok(Self::BasicCompact),
             "single" => Ok(Self::Single),
             "double" => Ok(Self::Double),
+            "custom" => Ok(Self::Custom),
             _ => Err(
-                "'basic', 'thin', 'light', 'compact', 'with_love', 'compact_double', 'rounded', 'reinforced', 'heavy', 'none', 'psql', 'markdown', 'dots', 'restructured', 'ascii_rounded', 'basic_compact', 'single', or 'double'",
+                "'basic', 'thin', 'light', 'compact', 'with_love', 'compact_double', 'rounded', 'reinforced', 'heavy', 'none', 'psql', 'markdown', 'dots', 'restructured', 'ascii_rounded', 'basic_compact', 'single', 'double', or 'custom'",
             ),
         }
     }
@@ -338,7 +341,379 @@ impl UpdateFromValue for TableIndent {
     }
 }
 
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableColumnAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl FromStr for TableColumnAlignment {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "left" => Ok(Self::Left),
+            "center" => Ok(Self::Center),
+            "right" => Ok(Self::Right),
+            _ => Err("'left', 'center' or 'right'"),
+        }
+    }
+}
+
+impl UpdateFromValue for TableColumnAlignment {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
+
+/// How the cells of a column with a given name are displayed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableColumnConfig {
+    /// The maximum width of the cells, not counting padding.
+    pub max_width: Option<usize>,
+    /// The alignment of the cells, instead of the one of their type.
+    pub alignment: Option<TableColumnAlignment>,
+    /// How cells wider than `max_width` are shortened, instead of `table.trim`.
+    pub trim: Option<TrimStrategy>,
+    /// Whether the column is left out, unless `table --all` is used.
+    pub hidden: bool,
+    /// The number of decimal places numbers are shown with.
+    pub decimals: Option<usize>,
+    /// A currency symbol numbers are prefixed with.
+    pub currency: Option<String>,
+}
+
+impl IntoValue for TableColumnConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "max_width" => self.max_width.map(|width| width as i64).into_value(span),
+            "alignment" => self.alignment.into_value(span),
+            "trim" => self.trim.into_value(span),
+            "hidden" => self.hidden.into_value(span),
+            "decimals" => self.decimals.map(|decimals| decimals as i64).into_value(span),
+            "currency" => self.currency.into_value(span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for TableColumnConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "max_width" => match val {
+                    Value::Nothing { .. } => self.max_width = None,
+                    &Value::Int { val: width, .. } => match usize::try_from(width) {
+                        Ok(width) if width > 0 => self.max_width = Some(width),
+                        _ => errors.invalid_value(path, "a positive integer", val),
+                    },
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                "alignment" => match val {
+                    Value::Nothing { .. } => self.alignment = None,
+                    _ => {
+                        let mut alignment = self.alignment.unwrap_or(TableColumnAlignment::Left);
+                        alignment.update(val, path, errors);
+                        self.alignment = Some(alignment);
+                    }
+                },
+                "trim" => match val {
+                    Value::Nothing { .. } => self.trim = None,
+                    _ => {
+                        let mut trim = self.trim.take().unwrap_or_default();
+                        trim.update(val, path, errors);
+                        self.trim = Some(trim);
+                    }
+                },
+                "hidden" => self.hidden.update(val, path, errors),
+                "decimals" => match val {
+                    Value::Nothing { .. } => self.decimals = None,
+                    &Value::Int { val: decimals, .. } => match usize::try_from(decimals) {
+                        Ok(decimals) => self.decimals = Some(decimals),
+                        Err(_) => errors.invalid_value(path, "a non-negative integer", val),
+                    },
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                "currency" => match val {
+                    Value::Nothing { .. } => self.currency = None,
+                    Value::String { val: symbol, .. } => self.currency = Some(symbol.clone()),
+                    _ => errors.type_mismatch(path, Type::custom("string or nothing"), val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
+
+/// The characters of a custom table theme. Those left out are taken from its base theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableBorderChars {
+    pub top: Option<char>,
+    pub bottom: Option<char>,
+    pub left: Option<char>,
+    pub right: Option<char>,
+    pub horizontal: Option<char>,
+    pub vertical: Option<char>,
+    pub corner_top_left: Option<char>,
+    pub corner_top_right: Option<char>,
+    pub corner_bottom_left: Option<char>,
+    pub corner_bottom_right: Option<char>,
+    pub intersection_top: Option<char>,
+    pub intersection_bottom: Option<char>,
+    pub intersection_left: Option<char>,
+    pub intersection_right: Option<char>,
+    pub intersection: Option<char>,
+}
+
+impl TableBorderChars {
+    fn iter(&self) -> impl Iterator<Item = (&'static str, Option<char>)> {
+        [
+            ("top", self.top),
+            ("bottom", self.bottom),
+            ("left", self.left),
+            ("right", self.right),
+            ("horizontal", self.horizontal),
+            ("vertical", self.vertical),
+            ("corner_top_left", self.corner_top_left),
+            ("corner_top_right", self.corner_top_right),
+            ("corner_bottom_left", self.corner_bottom_left),
+            ("corner_bottom_right", self.corner_bottom_right),
+            ("intersection_top", self.intersection_top),
+            ("intersection_bottom", self.intersection_bottom),
+            ("intersection_left", self.intersection_left),
+            ("intersection_right", self.intersection_right),
+            ("intersection", self.intersection),
+        ]
+        .into_iter()
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut Option<char>> {
+        let border = match name {
+            "top" => &mut self.top,
+            "bottom" => &mut self.bottom,
+            "left" => &mut self.left,
+            "right" => &mut self.right,
+            "horizontal" => &mut self.horizontal,
+            "vertical" => &mut self.vertical,
+            "corner_top_left" => &mut self.corner_top_left,
+            "corner_top_right" => &mut self.corner_top_right,
+            "corner_bottom_left" => &mut self.corner_bottom_left,
+            "corner_bottom_right" => &mut self.corner_bottom_right,
+            "intersection_top" => &mut self.intersection_top,
+            "intersection_bottom" => &mut self.intersection_bottom,
+            "intersection_left" => &mut self.intersection_left,
+            "intersection_right" => &mut self.intersection_right,
+            "intersection" => &mut self.intersection,
+            _ => return None,
+        };
+        Some(border)
+    }
+}
+
+/// A table theme made of user-given characters, used with `table.mode = "custom"`.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCustomTheme {
+    /// The built-in theme the characters that aren't given are taken from.
+    pub base: TableMode,
+    pub chars: TableBorderChars,
+    /// Whether a line is drawn under the header.
+    pub header_separator: bool,
+    /// Draw a line between the rows after every this many rows.
+    pub row_separator_every: Option<usize>,
+    /// Whether only the border around the table is drawn, without lines between the columns.
+    pub outer_border_only: bool,
+}
+
+impl Default for TableCustomTheme {
+    fn default() -> Self {
+        Self {
+            base: TableMode::Rounded,
+            chars: TableBorderChars::default(),
+            header_separator: true,
+            row_separator_every: None,
+            outer_border_only: false,
+        }
+    }
+}
+
+impl IntoValue for TableCustomTheme {
+    fn into_value(self, span: Span) -> Value {
+        let mut record = record! {
+            "base" => self.base.into_value(span),
+            "header_separator" => self.header_separator.into_value(span),
+            "row_separator_every" => self.row_separator_every.map(|every| every as i64).into_value(span),
+            "outer_border_only" => self.outer_border_only.into_value(span),
+        };
+        for (name, c) in self.chars.iter() {
+            record.push(name, c.map(String::from).into_value(span));
+        }
+        record.into_value(span)
+    }
+}
+
+impl UpdateFromValue for TableCustomTheme {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "base" => {
+                    let mut base = self.base;
+                    base.update(val, path, errors);
+                    if base == TableMode::Custom {
+                        errors.invalid_value(path, "a built-in table mode", val);
+                    } else {
+                        self.base = base;
+                    }
+                }
+                "header_separator" => self.header_separator.update(val, path, errors),
+                "row_separator_every" => match val {
+                    Value::Nothing { .. } => self.row_separator_every = None,
+                    &Value::Int { val: every, .. } => match usize::try_from(every) {
+                        Ok(every) if every > 0 => self.row_separator_every = Some(every),
+                        _ => errors.invalid_value(path, "a positive integer", val),
+                    },
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                "outer_border_only" => self.outer_border_only.update(val, path, errors),
+                name => match self.chars.get_mut(name) {
+                    Some(border) => match val {
+                        Value::Nothing { .. } => *border = None,
+                        Value::String { val: s, .. } => {
+                            let mut chars = s.chars();
+                            match (chars.next(), chars.next()) {
+                                (Some(c), None) => *border = Some(c),
+                                _ => errors.invalid_value(path, "a single character", val),
+                            }
+                        }
+                        _ => errors.type_mismatch(path, Type::custom("string or nothing"), val),
+                    },
+                    None => errors.unknown_option(path, val),
+                },
+            }
+        }
+    }
+}
+
+/// How numbers are displayed in tables. The values themselves are left as they are.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableNumberFormat {
+    /// Whether the digits of the integer part are grouped by thousands.
+    pub group_digits: bool,
+    /// Whether the separators are the ones of the system locale, rather than `,` and `.`.
+    pub locale: bool,
+    /// The separator between groups of digits, instead of the default one.
+    pub thousands_separator: Option<String>,
+    /// The separator before the decimal places, instead of the default one.
+    pub decimal_separator: Option<String>,
+    /// Numbers at least this large (ignoring their sign) are shown in scientific notation.
+    pub scientific_above: Option<f64>,
+    /// Numbers smaller than this (ignoring their sign), but not 0, are shown in scientific
+    /// notation.
+    pub scientific_below: Option<f64>,
+}
+
+impl IntoValue for TableNumberFormat {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "group_digits" => self.group_digits.into_value(span),
+            "locale" => self.locale.into_value(span),
+            "thousands_separator" => self.thousands_separator.into_value(span),
+            "decimal_separator" => self.decimal_separator.into_value(span),
+            "scientific_above" => self.scientific_above.into_value(span),
+            "scientific_below" => self.scientific_below.into_value(span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for TableNumberFormat {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "group_digits" => self.group_digits.update(val, path, errors),
+                "locale" => self.locale.update(val, path, errors),
+                "thousands_separator" => {
+                    update_separator(&mut self.thousands_separator, val, path, errors)
+                }
+                "decimal_separator" => {
+                    update_separator(&mut self.decimal_separator, val, path, errors)
+                }
+                "scientific_above" => {
+                    update_threshold(&mut self.scientific_above, val, path, errors)
+                }
+                "scientific_below" => {
+                    update_threshold(&mut self.scientific_below, val, path, errors)
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
+
+fn update_separator(
+    separator: &mut Option<String>,
+    value: &Value,
+    path: &mut ConfigPath,
+    errors: &mut ConfigErrors,
+) {
+    match value {
+        Value::Nothing { .. } => *separator = None,
+        Value::String { val, .. } => *separator = Some(val.clone()),
+        _ => errors.type_mismatch(path, Type::custom("string or nothing"), value),
+    }
+}
+
+fn update_threshold(
+    threshold: &mut Option<f64>,
+    value: &Value,
+    path: &mut ConfigPath,
+    errors: &mut ConfigErrors,
+) {
+    match *value {
+        Value::Nothing { .. } => *threshold = None,
+        Value::Int { val, .. } if val > 0 => *threshold = Some(val as f64),
+        Value::Float { val, .. } if val > 0.0 => *threshold = Some(val),
+        Value::Int { .. } | Value::Float { .. } => {
+            errors.invalid_value(path, "a positive number", value)
+        }
+        _ => errors.type_mismatch(path, Type::custom("number or nothing"), value),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TableConfig {
     pub mode: TableMode,
     pub index_mode: TableIndexMode,
@@ -351,6 +726,12 @@ pub struct TableConfig {
     pub missing_value_symbol: String,
     pub batch_duration: Duration,
     pub stream_page_size: NonZeroU16,
+    /// Display settings for the columns with the given names.
+    pub columns: HashMap<String, TableColumnConfig>,
+    /// The theme used when `mode` is `custom`.
+    pub custom_theme: TableCustomTheme,
+    /// How numbers are displayed.
+    pub numbers: TableNumberFormat,
 }
 
 impl IntoValue for TableConfig {
@@ -372,6 +753,9 @@ impl IntoValue for TableConfig {
             "missing_value_symbol" => self.missing_value_symbol.into_value(span),
             "batch_duration" => self.batch_duration.into_value(span),
             "stream_page_size" => self.stream_page_size.get().into_value(span),
+            "columns" => self.columns.into_value(span),
+            "custom_theme" => self.custom_theme.into_value(span),
+            "numbers" => self.numbers.into_value(span),
         }
         .into_value(span)
     }
@@ -391,6 +775,9 @@ impl Default for TableConfig {
             missing_value_symbol: "❎".into(),
             batch_duration: Duration::from_secs(1),
             stream_page_size: const { NonZeroU16::new(1000).expect("Non zero integer") },
+            columns: HashMap::new(),
+            custom_theme: TableCustomTheme::default(),
+            numbers: TableNumberFormat::default(),
         }
     }
 }
@@ -449,6 +836,9 @@ impl UpdateFromValue for TableConfig {
                     };
                     self.stream_page_size = n;
                 }
+                "columns" => self.columns.update(val, path, errors),
+                "custom_theme" => self.custom_theme.update(val, path, errors),
+                "numbers" => self.numbers.update(val, path, errors),
                 _ => errors.unknown_option(path, val),
             }
         }
@@ -0,0 +1,45 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Templates for the terminal title, set with OSC 2 when `shell_integration.osc2` is enabled
+///
+/// The templates can contain the placeholders `{cwd}`, `{dir}`, `{command}` and `{commandline}`.
+#[derive(Clone, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalTitleConfig {
+    /// The title while the prompt is shown
+    pub prompt: String,
+    /// The title while a command is running
+    pub command: String,
+}
+
+impl Default for TerminalTitleConfig {
+    fn default() -> Self {
+        Self {
+            prompt: "{cwd}".into(),
+            command: "{cwd}> {command}".into(),
+        }
+    }
+}
+
+impl UpdateFromValue for TerminalTitleConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "prompt" => self.prompt.update(val, path, errors),
+                "command" => self.command.update(val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
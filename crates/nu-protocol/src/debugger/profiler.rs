@@ -4,13 +4,14 @@
 //! profiling Nushell code.
 
 use crate::{
-    PipelineData, PipelineExecutionData, ShellError, Span, Value,
+    PipelineData, PipelineExecutionData, Record, ShellError, Span, Value,
     ast::{Block, Expr, PipelineElement},
     debugger::Debugger,
     engine::EngineState,
     ir::IrBlock,
     record,
 };
+use indexmap::IndexMap;
 use std::{borrow::Borrow, io::BufRead};
 use web_time::Instant;
 
@@ -27,11 +28,13 @@ struct ElementInfo {
     element_output: Option<Value>,
     expr: Option<String>,
     instruction: Option<(usize, String)>,
+    start_cpu_ns: Option<i64>,
+    cpu_ns: Option<i64>,
     children: Vec<ElementId>,
 }
 
 impl ElementInfo {
-    pub fn new(depth: i64, element_span: Span) -> Self {
+    pub fn new(depth: i64, element_span: Span, collect_cpu_time: bool) -> Self {
         ElementInfo {
             start: Instant::now(),
             duration_ns: 0,
@@ -40,9 +43,34 @@ impl ElementInfo {
             element_output: None,
             expr: None,
             instruction: None,
+            start_cpu_ns: collect_cpu_time.then(thread_cpu_time_ns).flatten(),
+            cpu_ns: None,
             children: vec![],
         }
     }
+
+    fn stop(&mut self) {
+        self.duration_ns = self.start.elapsed().as_nanos() as i64;
+        self.cpu_ns = self
+            .start_cpu_ns
+            .zip(thread_cpu_time_ns())
+            .map(|(start, end)| end - start);
+    }
+}
+
+/// CPU time consumed by the current thread, if the platform can measure it
+fn thread_cpu_time_ns() -> Option<i64> {
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+    {
+        use nix::time::{ClockId, clock_gettime};
+        clock_gettime(ClockId::CLOCK_THREAD_CPUTIME_ID)
+            .ok()
+            .map(|time| std::time::Duration::from(time).as_nanos() as i64)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+    {
+        None
+    }
 }
 
 /// Whether [`Profiler`] should report duration as [`Value::Duration`]
@@ -52,6 +80,17 @@ pub enum DurationMode {
     Value,
 }
 
+/// How [`Profiler`] should lay out its report
+#[derive(Debug, Clone, Copy)]
+pub enum ReportMode {
+    /// One row per profiled element, in evaluation order
+    Table,
+    /// Nested records, with sibling elements sharing a span merged into one node
+    Tree,
+    /// Folded stacks (`a;b;c <self time in ns>`), as consumed by flamegraph tools
+    Folded,
+}
+
 /// Options for [`Profiler`]
 #[derive(Debug, Clone)]
 pub struct ProfilerOptions {
@@ -63,7 +102,9 @@ pub struct ProfilerOptions {
     pub collect_exprs: bool,
     pub collect_instructions: bool,
     pub collect_lines: bool,
+    pub collect_cpu_time: bool,
     pub duration_mode: DurationMode,
+    pub report_mode: ReportMode,
 }
 
 /// Basic profiler, used in `debug profile`
@@ -88,6 +129,8 @@ impl Profiler {
             instruction: opts
                 .collect_instructions
                 .then(|| (0, "<start>".to_string())),
+            start_cpu_ns: None,
+            cpu_ns: None,
             children: vec![],
         };
 
@@ -111,12 +154,14 @@ impl Profiler {
 
 impl Debugger for Profiler {
     fn activate(&mut self) {
+        let collect_cpu_time = self.opts.collect_cpu_time;
         let Some(root_element) = self.last_element_mut() else {
             eprintln!("Profiler Error: Missing root element.");
             return;
         };
 
         root_element.start = Instant::now();
+        root_element.start_cpu_ns = collect_cpu_time.then(thread_cpu_time_ns).flatten();
     }
 
     fn deactivate(&mut self) {
@@ -125,7 +170,7 @@ impl Debugger for Profiler {
             return;
         };
 
-        root_element.duration_ns = root_element.start.elapsed().as_nanos() as i64;
+        root_element.stop();
     }
 
     fn enter_block(&mut self, _engine_state: &EngineState, _block: &Block) {
@@ -153,7 +198,8 @@ impl Debugger for Profiler {
 
         let new_id = ElementId(self.elements.len());
 
-        let mut new_element = ElementInfo::new(self.depth, element.expr.span);
+        let mut new_element =
+            ElementInfo::new(self.depth, element.expr.span, self.opts.collect_cpu_time);
         new_element.expr = expr_opt;
 
         self.elements.push(new_element);
@@ -194,7 +240,7 @@ impl Debugger for Profiler {
             return;
         };
 
-        last_element.duration_ns = last_element.start.elapsed().as_nanos() as i64;
+        last_element.stop();
         last_element.element_output = out_opt;
 
         self.element_stack.pop();
@@ -230,7 +276,7 @@ impl Debugger for Profiler {
 
         let new_id = ElementId(self.elements.len());
 
-        let mut new_element = ElementInfo::new(self.depth, span);
+        let mut new_element = ElementInfo::new(self.depth, span, self.opts.collect_cpu_time);
         new_element.instruction = instruction_opt;
 
         self.elements.push(new_element);
@@ -279,23 +325,38 @@ impl Debugger for Profiler {
             return;
         };
 
-        last_element.duration_ns = last_element.start.elapsed().as_nanos() as i64;
+        last_element.stop();
         last_element.element_output = out_opt;
 
         self.element_stack.pop();
     }
 
     fn report(&self, engine_state: &EngineState, profiler_span: Span) -> Result<Value, ShellError> {
-        Ok(Value::list(
-            collect_data(
-                engine_state,
-                self,
-                ElementId(0),
-                ElementId(0),
+        match self.opts.report_mode {
+            ReportMode::Table => Ok(Value::list(
+                collect_data(
+                    engine_state,
+                    self,
+                    ElementId(0),
+                    ElementId(0),
+                    profiler_span,
+                )?,
                 profiler_span,
-            )?,
-            profiler_span,
-        ))
+            )),
+            ReportMode::Tree => Ok(Value::list(
+                collect_tree(engine_state, self, &[ElementId(0)], profiler_span)?,
+                profiler_span,
+            )),
+            ReportMode::Folded => {
+                let mut stacks = IndexMap::new();
+                collect_folded(engine_state, self, ElementId(0), "", &mut stacks);
+                let folded = stacks
+                    .into_iter()
+                    .map(|(stack, self_ns)| format!("{stack} {self_ns}\n"))
+                    .collect::<String>();
+                Ok(Value::string(folded, profiler_span))
+            }
+        }
     }
 }
 
@@ -403,6 +464,63 @@ fn find_file_of_span(engine_state: &EngineState, span: Span) -> Option<(&str, us
     None
 }
 
+fn source_fragment(engine_state: &EngineState, profiler: &Profiler, span: Span) -> String {
+    let val = String::from_utf8_lossy(engine_state.get_span_contents(span));
+    let val = val.trim();
+    let nlines = val.lines().count();
+
+    if profiler.opts.collect_expanded_source {
+        val.to_string()
+    } else {
+        let mut first_line = val.lines().next().unwrap_or("").to_string();
+
+        if nlines > 1 {
+            first_line.push_str(" ...");
+        }
+
+        first_line
+    }
+}
+
+// Pushes `{name}_ms` as milliseconds, or `{name}` as a duration value, depending on the options
+fn push_duration(
+    row: &mut Record,
+    profiler: &Profiler,
+    name: &str,
+    duration_ns: i64,
+    profiler_span: Span,
+) {
+    match profiler.opts.duration_mode {
+        DurationMode::Milliseconds => {
+            let val = Value::float(duration_ns as f64 / 1000.0 / 1000.0, profiler_span);
+            row.push(format!("{name}_ms"), val);
+        }
+        DurationMode::Value => {
+            let val = Value::duration(duration_ns, profiler_span);
+            row.push(name, val);
+        }
+    };
+}
+
+fn push_optional_duration(
+    row: &mut Record,
+    profiler: &Profiler,
+    name: &str,
+    duration_ns: Option<i64>,
+    profiler_span: Span,
+) {
+    match duration_ns {
+        Some(duration_ns) => push_duration(row, profiler, name, duration_ns, profiler_span),
+        None => {
+            let name = match profiler.opts.duration_mode {
+                DurationMode::Milliseconds => format!("{name}_ms"),
+                DurationMode::Value => name.to_string(),
+            };
+            row.push(name, Value::nothing(profiler_span));
+        }
+    }
+}
+
 fn collect_data(
     engine_state: &EngineState,
     profiler: &Profiler,
@@ -447,22 +565,7 @@ fn collect_data(
     }
 
     if profiler.opts.collect_source {
-        let val = String::from_utf8_lossy(engine_state.get_span_contents(element.element_span));
-        let val = val.trim();
-        let nlines = val.lines().count();
-
-        let fragment = if profiler.opts.collect_expanded_source {
-            val.to_string()
-        } else {
-            let mut first_line = val.lines().next().unwrap_or("").to_string();
-
-            if nlines > 1 {
-                first_line.push_str(" ...");
-            }
-
-            first_line
-        };
-
+        let fragment = source_fragment(engine_state, profiler, element.element_span);
         row.push("source", Value::string(fragment, profiler_span));
     }
 
@@ -485,16 +588,17 @@ fn collect_data(
         row.push("output", val.clone());
     }
 
-    match profiler.opts.duration_mode {
-        DurationMode::Milliseconds => {
-            let val = Value::float(element.duration_ns as f64 / 1000.0 / 1000.0, profiler_span);
-            row.push("duration_ms", val);
-        }
-        DurationMode::Value => {
-            let val = Value::duration(element.duration_ns, profiler_span);
-            row.push("duration", val);
-        }
-    };
+    push_duration(
+        &mut row,
+        profiler,
+        "duration",
+        element.duration_ns,
+        profiler_span,
+    );
+
+    if profiler.opts.collect_cpu_time {
+        push_optional_duration(&mut row, profiler, "cpu", element.cpu_ns, profiler_span);
+    }
 
     let mut rows = vec![Value::record(row, profiler_span)];
 
@@ -505,3 +609,145 @@ fn collect_data(
 
     Ok(rows)
 }
+
+// Builds one tree node per distinct span among `element_ids`, merging the time and children of
+// elements sharing a span. Instructions compiled from the same source thus show up as one node.
+fn collect_tree(
+    engine_state: &EngineState,
+    profiler: &Profiler,
+    element_ids: &[ElementId],
+    profiler_span: Span,
+) -> Result<Vec<Value>, ShellError> {
+    let mut groups: IndexMap<(usize, usize), Vec<&ElementInfo>> = IndexMap::new();
+    for id in element_ids {
+        let element = &profiler.elements[id.0];
+        groups
+            .entry((element.element_span.start, element.element_span.end))
+            .or_default()
+            .push(element);
+    }
+
+    let mut nodes = vec![];
+
+    for elements in groups.into_values() {
+        let Some(first) = elements.first() else {
+            continue;
+        };
+        let span = first.element_span;
+
+        let mut row = record! {
+            "depth" => Value::int(first.depth, profiler_span),
+            "source" => Value::string(source_fragment(engine_state, profiler, span), profiler_span),
+        };
+
+        if profiler.opts.collect_lines {
+            if let Some((fname, line_num)) = find_file_of_span(engine_state, span) {
+                row.push("file", Value::string(fname, profiler_span));
+                row.push("line", Value::int(line_num as i64, profiler_span));
+            } else {
+                row.push("file", Value::nothing(profiler_span));
+                row.push("line", Value::nothing(profiler_span));
+            }
+        }
+
+        if profiler.opts.collect_spans {
+            let span_start = i64::try_from(span.start)
+                .map_err(|_| profiler_error("error converting span start to i64", profiler_span))?;
+            let span_end = i64::try_from(span.end)
+                .map_err(|_| profiler_error("error converting span end to i64", profiler_span))?;
+
+            row.push(
+                "span",
+                Value::record(
+                    record! {
+                        "start" => Value::int(span_start, profiler_span),
+                        "end" => Value::int(span_end, profiler_span),
+                    },
+                    profiler_span,
+                ),
+            );
+        }
+
+        row.push("count", Value::int(elements.len() as i64, profiler_span));
+
+        let duration_ns = elements.iter().map(|element| element.duration_ns).sum();
+        push_duration(&mut row, profiler, "duration", duration_ns, profiler_span);
+
+        if profiler.opts.collect_cpu_time {
+            let cpu_ns = elements
+                .iter()
+                .map(|element| element.cpu_ns)
+                .sum::<Option<i64>>();
+            push_optional_duration(&mut row, profiler, "cpu", cpu_ns, profiler_span);
+        }
+
+        let children = elements
+            .iter()
+            .flat_map(|element| element.children.iter().copied())
+            .collect::<Vec<_>>();
+        row.push(
+            "children",
+            Value::list(
+                collect_tree(engine_state, profiler, &children, profiler_span)?,
+                profiler_span,
+            ),
+        );
+
+        nodes.push(Value::record(row, profiler_span));
+    }
+
+    Ok(nodes)
+}
+
+// Name of an element's stack frame in folded output
+fn frame_name(engine_state: &EngineState, profiler: &Profiler, element: &ElementInfo) -> String {
+    let source = String::from_utf8_lossy(engine_state.get_span_contents(element.element_span));
+    let mut name = match source.trim().lines().next() {
+        Some(line) if !line.is_empty() => line.to_string(),
+        _ => match &element.instruction {
+            Some((_, instruction)) => instruction.clone(),
+            None => "<unknown>".to_string(),
+        },
+    };
+
+    if profiler.opts.collect_lines {
+        if let Some((fname, line_num)) = find_file_of_span(engine_state, element.element_span) {
+            name.push_str(&format!(" ({fname}:{line_num})"));
+        }
+    }
+
+    // `;` separates frames in folded stacks
+    name.replace(';', ",")
+}
+
+// Adds the self time of an element and its children to `stacks`, keyed by their folded stack
+fn collect_folded(
+    engine_state: &EngineState,
+    profiler: &Profiler,
+    element_id: ElementId,
+    parent_stack: &str,
+    stacks: &mut IndexMap<String, i64>,
+) {
+    let element = &profiler.elements[element_id.0];
+    let frame = frame_name(engine_state, profiler, element);
+    let stack = if parent_stack.is_empty() {
+        frame
+    } else {
+        format!("{parent_stack};{frame}")
+    };
+
+    let children_ns: i64 = element
+        .children
+        .iter()
+        .map(|child| profiler.elements[child.0].duration_ns)
+        .sum();
+    let self_ns = (element.duration_ns - children_ns).max(0);
+
+    if self_ns > 0 {
+        *stacks.entry(stack.clone()).or_default() += self_ns;
+    }
+
+    for child in &element.children {
+        collect_folded(engine_state, profiler, *child, &stack, stacks);
+    }
+}
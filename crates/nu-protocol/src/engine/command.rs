@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{EngineState, Stack, StateWorkingSet};
+use super::{EngineState, MemoOptions, Stack, StateWorkingSet};
 use crate::{
     Alias, BlockId, DeprecationEntry, DynamicCompletionCallRef, DynamicSuggestion, Example,
     OutDest, PipelineData, ShellError, Signature, Span, Value, engine::Call,
@@ -165,6 +165,11 @@ pub trait Command: Send + Sync + CommandClone {
         vec![]
     }
 
+    /// How the results of this command are cached, for custom commands marked with `@memo`
+    fn memo_options(&self) -> Option<MemoOptions> {
+        None
+    }
+
     fn pipe_redirection(&self) -> (Option<OutDest>, Option<OutDest>) {
         (None, None)
     }
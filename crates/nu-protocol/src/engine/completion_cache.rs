@@ -0,0 +1,31 @@
+use crate::{BlockId, Value};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+use web_time::Instant;
+
+/// The command line and directory an external completer was run for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompletionCacheKey {
+    pub block_id: BlockId,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+}
+
+/// Results of external completers, kept for `$env.config.completions.external.cache_duration`
+#[derive(Default)]
+pub struct CompletionCache {
+    entries: HashMap<CompletionCacheKey, (Instant, Value)>,
+}
+
+impl CompletionCache {
+    /// Get the cached result for a command line, if it's more recent than `max_age`
+    pub fn get(&self, key: &CompletionCacheKey, max_age: Duration) -> Option<Value> {
+        let (time, value) = self.entries.get(key)?;
+        (time.elapsed() < max_age).then(|| value.clone())
+    }
+
+    /// Cache the result for a command line, forgetting the results older than `max_age`
+    pub fn insert(&mut self, key: CompletionCacheKey, value: Value, max_age: Duration) {
+        self.entries.retain(|_, (time, _)| time.elapsed() < max_age);
+        self.entries.insert(key, (Instant::now(), value));
+    }
+}
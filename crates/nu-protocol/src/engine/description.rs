@@ -1,4 +1,4 @@
-use crate::{ModuleId, Span};
+use crate::{ModuleId, Span, VarId};
 use std::collections::HashMap;
 
 /// Organizes documentation comments for various primitives
@@ -6,12 +6,14 @@ use std::collections::HashMap;
 pub(super) struct Doccomments {
     // TODO: Move decl doccomments here
     module_comments: HashMap<ModuleId, Vec<Span>>,
+    var_comments: HashMap<VarId, Vec<Span>>,
 }
 
 impl Doccomments {
     pub fn new() -> Self {
         Doccomments {
             module_comments: HashMap::new(),
+            var_comments: HashMap::new(),
         }
     }
 
@@ -23,9 +25,18 @@ impl Doccomments {
         self.module_comments.get(&module_id).map(|v| v.as_ref())
     }
 
+    pub fn add_var_comments(&mut self, var_id: VarId, comments: Vec<Span>) {
+        self.var_comments.insert(var_id, comments);
+    }
+
+    pub fn get_var_comments(&self, var_id: VarId) -> Option<&[Span]> {
+        self.var_comments.get(&var_id).map(|v| v.as_ref())
+    }
+
     /// Overwrite own values with the other
     pub fn merge_with(&mut self, other: Doccomments) {
         self.module_comments.extend(other.module_comments);
+        self.var_comments.extend(other.var_comments);
     }
 }
 
@@ -5,8 +5,8 @@ use crate::{
     ast::Block,
     debugger::{Debugger, NoopDebugger},
     engine::{
-        CachedFile, Command, DEFAULT_OVERLAY_NAME, EnvName, EnvVars, OverlayFrame, ScopeFrame,
-        Stack, StateDelta, Variable, Visibility,
+        CachedFile, Command, CompletionCache, DEFAULT_OVERLAY_NAME, EnvName, EnvVars, MemoCache,
+        OverlayFrame, RemoteModuleResolver, ScopeFrame, Stack, StateDelta, Variable, Visibility,
         description::{Doccomments, build_desc},
     },
     eval_const::create_nu_constant,
@@ -17,9 +17,9 @@ use fancy_regex::Regex;
 use lru::LruCache;
 use nu_path::AbsolutePathBuf;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     num::NonZeroUsize,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Arc, Mutex, MutexGuard, PoisonError,
         atomic::{AtomicBool, AtomicU32, Ordering},
@@ -41,12 +41,31 @@ pub enum VirtualPath {
     Dir(Vec<VirtualPathId>),
 }
 
+#[derive(Default)]
 pub struct ReplState {
     pub buffer: String,
     // A byte position, as `EditCommand::MoveToPosition` is also a byte position
     pub cursor_pos: usize,
     /// Immediately accept the buffer on the next loop.
     pub accept: bool,
+    /// The buffers discarded with Ctrl+C this session, from the oldest.
+    pub discarded: VecDeque<String>,
+}
+
+impl ReplState {
+    /// How many discarded buffers are kept.
+    pub const MAX_DISCARDED: usize = 50;
+
+    /// Keep a discarded buffer, so it can be restored later.
+    pub fn discard(&mut self, buffer: String) {
+        if buffer.trim().is_empty() || self.discarded.back() == Some(&buffer) {
+            return;
+        }
+        if self.discarded.len() >= Self::MAX_DISCARDED {
+            self.discarded.pop_front();
+        }
+        self.discarded.push_back(buffer);
+    }
 }
 
 pub struct IsDebugging(AtomicBool);
@@ -112,6 +131,10 @@ pub struct EngineState {
     // Path to the file Nushell is currently evaluating, or None if we're in an interactive session.
     pub file: Option<PathBuf>,
     pub regex_cache: Arc<Mutex<LruCache<String, Regex>>>,
+    /// Results of custom commands marked with `@memo`
+    pub memo_cache: Arc<Mutex<MemoCache>>,
+    /// Results of external completers
+    pub completion_cache: Arc<Mutex<CompletionCache>>,
     pub is_interactive: bool,
     pub is_login: bool,
     pub is_lsp: bool,
@@ -119,6 +142,10 @@ pub struct EngineState {
     startup_time: i64,
     is_debugging: IsDebugging,
     pub debugger: Arc<Mutex<Box<dyn Debugger>>>,
+    /// Used by the parser to import modules from URLs
+    pub remote_modules: Option<Arc<dyn RemoteModuleResolver>>,
+    /// Modules in the autoload module directories, imported by the parser on first reference
+    autoload_modules: Arc<HashMap<String, PathBuf>>,
     pub report_log: Arc<Mutex<ReportLog>>,
 
     pub jobs: Arc<Mutex<Jobs>>,
@@ -185,11 +212,7 @@ impl EngineState {
             previous_env_vars: Arc::new(HashMap::new()),
             config: Arc::new(Config::default()),
             pipeline_externals_state: Arc::new((AtomicU32::new(0), AtomicU32::new(0))),
-            repl_state: Arc::new(Mutex::new(ReplState {
-                buffer: "".to_string(),
-                cursor_pos: 0,
-                accept: false,
-            })),
+            repl_state: Arc::new(Mutex::new(ReplState::default())),
             table_decl_id: None,
             #[cfg(feature = "plugin")]
             plugin_path: None,
@@ -202,6 +225,8 @@ impl EngineState {
             regex_cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             ))),
+            memo_cache: Arc::default(),
+            completion_cache: Arc::default(),
             is_interactive: false,
             is_login: false,
             is_lsp: false,
@@ -209,6 +234,8 @@ impl EngineState {
             startup_time: -1,
             is_debugging: IsDebugging::new(false),
             debugger: Arc::new(Mutex::new(Box::new(NoopDebugger))),
+            remote_modules: None,
+            autoload_modules: Arc::default(),
             report_log: Arc::default(),
             jobs: Arc::new(Mutex::new(Jobs::default())),
             current_job: CurrentJob {
@@ -702,6 +729,10 @@ impl EngineState {
         self.doccomments.get_module_comments(module_id)
     }
 
+    pub fn get_var_comments(&self, var_id: VarId) -> Option<&[Span]> {
+        self.doccomments.get_var_comments(var_id)
+    }
+
     #[cfg(feature = "plugin")]
     pub fn plugin_decls(&self) -> impl Iterator<Item = &Box<dyn Command + 'static>> {
         let mut unique_plugin_decls = HashMap::new();
@@ -951,6 +982,16 @@ impl EngineState {
         self.config_path.get(key)
     }
 
+    /// Set the modules that can be imported on first reference, by module name.
+    pub fn set_autoload_modules(&mut self, modules: HashMap<String, PathBuf>) {
+        self.autoload_modules = Arc::new(modules);
+    }
+
+    /// Get the path of the autoload module called `name`, if there is one.
+    pub fn get_autoload_module(&self, name: &str) -> Option<&Path> {
+        self.autoload_modules.get(name).map(PathBuf::as_path)
+    }
+
     pub fn build_desc(&self, spans: &[Span]) -> (String, String) {
         let comment_lines: Vec<&[u8]> = spans
             .iter()
@@ -964,6 +1005,11 @@ impl EngineState {
             .map(|comment_spans| self.build_desc(comment_spans))
     }
 
+    pub fn build_var_desc(&self, var_id: VarId) -> Option<(String, String)> {
+        self.get_var_comments(var_id)
+            .map(|comment_spans| self.build_desc(comment_spans))
+    }
+
     /// Returns the current working directory, which is guaranteed to be canonicalized.
     ///
     /// Returns an empty String if $env.PWD doesn't exist.
@@ -1070,11 +1116,7 @@ impl EngineState {
 
     pub fn recover_from_panic(&mut self) {
         if Mutex::is_poisoned(&self.repl_state) {
-            self.repl_state = Arc::new(Mutex::new(ReplState {
-                buffer: "".to_string(),
-                cursor_pos: 0,
-                accept: false,
-            }));
+            self.repl_state = Arc::new(Mutex::new(ReplState::default()));
         }
         if Mutex::is_poisoned(&self.jobs) {
             self.jobs = Arc::new(Mutex::new(Jobs::default()));
@@ -1084,6 +1126,9 @@ impl EngineState {
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             )));
         }
+        if Mutex::is_poisoned(&self.memo_cache) {
+            self.memo_cache = Arc::default();
+        }
     }
 
     /// Add new span and return its ID
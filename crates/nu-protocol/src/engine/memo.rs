@@ -0,0 +1,171 @@
+use crate::{DeclId, ShellError, Span, Value};
+use indexmap::IndexMap;
+use std::{collections::HashMap, fmt::Write, time::Duration};
+use web_time::Instant;
+
+// Make nu_protocol available in this namespace, consumers of this crate will
+// have this without such an export.
+// The `FromValue` derive macro fully qualifies paths to "nu_protocol".
+use crate::{self as nu_protocol, FromValue};
+
+/// The max number of results to keep per command, arbitrarily chosen
+const MEMO_CACHE_SIZE: usize = 1000;
+
+/// How the results of a custom command are cached, set with the `@memo` attribute
+///
+/// Custom commands return these from [`Command::memo_options`](crate::engine::Command::memo_options).
+#[derive(Debug, Clone, FromValue)]
+pub struct MemoOptions {
+    /// How long a result is reused for, or for the rest of the session if `None`
+    pub ttl: Option<Duration>,
+    /// Whether the pipeline input is part of the cache key, besides the arguments
+    #[nu_value(default)]
+    pub input: bool,
+}
+
+/// The key a call is cached under, built from the values of its arguments
+///
+/// Unlike [`Value`] equality, the key keeps the type of every value, so that a call with `1`
+/// doesn't get the result of a call with `1.0`. Spans don't matter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoKey(String);
+
+impl MemoKey {
+    pub fn new(value: &Value) -> Result<Self, ShellError> {
+        let mut key = String::new();
+        write_key(&mut key, value)?;
+        Ok(Self(key))
+    }
+}
+
+fn write_key(key: &mut String, value: &Value) -> Result<(), ShellError> {
+    // writing to a `String` can't fail, so the results of `write!` are ignored
+    match value {
+        Value::Bool { val, .. } => _ = write!(key, "bool:{val}"),
+        Value::Int { val, .. } => _ = write!(key, "int:{val}"),
+        Value::Float { val, .. } => _ = write!(key, "float:{val:?}"),
+        Value::String { val, .. } => _ = write!(key, "string:{val:?}"),
+        Value::Glob { val, no_expand, .. } => _ = write!(key, "glob:{no_expand}:{val:?}"),
+        Value::Filesize { val, .. } => _ = write!(key, "filesize:{val:?}"),
+        Value::Duration { val, .. } => _ = write!(key, "duration:{val}"),
+        Value::Rate { val, .. } => _ = write!(key, "rate:{val:?}"),
+        Value::Date { val, .. } => _ = write!(key, "date:{val:?}"),
+        Value::Range { val, .. } => _ = write!(key, "range:{val:?}"),
+        Value::Record { val, .. } => {
+            key.push_str("record{");
+            for (col, val) in val.iter() {
+                _ = write!(key, "{col:?}:");
+                write_key(key, val)?;
+                key.push(',');
+            }
+            key.push('}');
+        }
+        Value::List { vals, .. } => {
+            key.push_str("list[");
+            for val in vals {
+                write_key(key, val)?;
+                key.push(',');
+            }
+            key.push(']');
+        }
+        Value::Closure { val, .. } => {
+            _ = write!(key, "closure:{}[", val.block_id.get());
+            for (var_id, val) in &val.captures {
+                _ = write!(key, "{}:", var_id.get());
+                write_key(key, val)?;
+                key.push(',');
+            }
+            key.push(']');
+        }
+        Value::Error { error, .. } => _ = write!(key, "error:{error:?}"),
+        Value::Binary { val, .. } => _ = write!(key, "binary:{val:?}"),
+        Value::CellPath { val, .. } => _ = write!(key, "cell-path:{val}"),
+        Value::Custom { val, .. } => {
+            _ = write!(key, "custom:{:?}:", val.type_name());
+            write_key(key, &val.to_base_value(Span::unknown())?)?;
+        }
+        Value::Nothing { .. } => key.push_str("nothing"),
+    }
+    Ok(())
+}
+
+struct MemoEntry {
+    value: Value,
+    expires: Option<Instant>,
+}
+
+/// Cached results of calls to custom commands marked with `@memo`
+///
+/// Results are keyed by the command and the [`MemoKey`] of the call's arguments.
+#[derive(Default)]
+pub struct MemoCache {
+    // kept in insertion order, so that the oldest result is the first one
+    entries: HashMap<DeclId, IndexMap<MemoKey, MemoEntry>>,
+}
+
+impl MemoCache {
+    /// Get the cached result of a call, if there is one that hasn't expired yet
+    pub fn get(&mut self, decl_id: DeclId, key: &MemoKey) -> Option<Value> {
+        let entries = self.entries.get_mut(&decl_id)?;
+        let now = Instant::now();
+        entries.retain(|_, entry| entry.expires.is_none_or(|expires| expires > now));
+        entries.get(key).map(|entry| entry.value.clone())
+    }
+
+    /// Cache the result of a call, replacing the oldest result once the command has too many
+    pub fn insert(&mut self, decl_id: DeclId, key: MemoKey, value: Value, ttl: Option<Duration>) {
+        let entries = self.entries.entry(decl_id).or_default();
+        entries.shift_remove(&key);
+        if entries.len() >= MEMO_CACHE_SIZE {
+            entries.shift_remove_index(0);
+        }
+        entries.insert(
+            key,
+            MemoEntry {
+                value,
+                expires: ttl.and_then(|ttl| Instant::now().checked_add(ttl)),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(value: Value) -> MemoKey {
+        MemoKey::new(&value).expect("key can be built")
+    }
+
+    #[test]
+    fn key_keeps_the_type() {
+        assert_ne!(key(Value::test_int(1)), key(Value::test_float(1.0)));
+        assert_ne!(
+            key(Value::test_list(vec![Value::test_int(1)])),
+            key(Value::test_list(vec![Value::test_float(1.0)]))
+        );
+        assert_ne!(key(Value::test_string("1")), key(Value::test_int(1)));
+    }
+
+    #[test]
+    fn key_ignores_spans() {
+        assert_eq!(
+            key(Value::int(1, Span::new(0, 1))),
+            key(Value::int(1, Span::new(5, 6)))
+        );
+    }
+
+    #[test]
+    fn oldest_result_is_replaced() {
+        let mut cache = MemoCache::default();
+        let decl_id = DeclId::new(0);
+        for i in 0..=MEMO_CACHE_SIZE as i64 {
+            cache.insert(decl_id, key(Value::test_int(i)), Value::test_int(i), None);
+        }
+        assert_eq!(cache.get(decl_id, &key(Value::test_int(0))), None);
+        assert_eq!(
+            cache.get(decl_id, &key(Value::test_int(1))),
+            Some(Value::test_int(1))
+        );
+    }
+}
@@ -5,13 +5,16 @@ mod call;
 mod call_info;
 mod closure;
 mod command;
+mod completion_cache;
 mod description;
 mod engine_state;
 mod env_name;
 mod error_handler;
 mod jobs;
+mod memo;
 mod overlay;
 mod pattern_match;
+mod remote_module;
 mod sequence;
 mod stack;
 mod stack_out_dest;
@@ -26,12 +29,15 @@ pub use call::*;
 pub use call_info::*;
 pub use closure::*;
 pub use command::*;
+pub use completion_cache::*;
 pub use engine_state::*;
 pub use env_name::*;
 pub use error_handler::*;
 pub use jobs::*;
+pub use memo::*;
 pub use overlay::*;
 pub use pattern_match::*;
+pub use remote_module::*;
 pub use sequence::*;
 pub use stack::*;
 pub use stack_out_dest::*;
@@ -0,0 +1,17 @@
+use crate::{ShellError, Span};
+use std::path::{Path, PathBuf};
+
+/// Finds the local copies of the modules that are imported from a URL, like with
+/// `use https://example.com/module.nu`.
+///
+/// The parser can't make requests itself, so a resolver is set on the
+/// [`EngineState`](crate::engine::EngineState) by whatever provides networking. Without one,
+/// importing from a URL is a parse error.
+pub trait RemoteModuleResolver: Send + Sync {
+    /// Get the path of the local copy of the module at `url`.
+    ///
+    /// The copy has to match the checksum recorded for `url` in the lockfile in `lock_dir`. As
+    /// this is used while parsing, it must not download or write anything: a module that's
+    /// missing from the lockfile or the cache is an error, which names how to fetch it.
+    fn locate(&self, url: &str, lock_dir: &Path, span: Span) -> Result<PathBuf, ShellError>;
+}
@@ -1,8 +1,8 @@
 use crate::{
     Config, ENV_VARIABLE_ID, IntoValue, NU_VARIABLE_ID, OutDest, ShellError, Span, Value, VarId,
     engine::{
-        ArgumentStack, DEFAULT_OVERLAY_NAME, EngineState, EnvName, ErrorHandlerStack, Redirection,
-        StackCallArgGuard, StackCollectValueGuard, StackIoGuard, StackOutDest,
+        ArgumentStack, Closure, DEFAULT_OVERLAY_NAME, EngineState, EnvName, ErrorHandlerStack,
+        Redirection, StackCallArgGuard, StackCollectValueGuard, StackIoGuard, StackOutDest,
     },
     report_shell_warning,
 };
@@ -49,6 +49,8 @@ pub struct Stack {
     pub error_handlers: ErrorHandlerStack,
     /// Finally handler stack for IR evaluation
     pub finally_run_handlers: ErrorHandlerStack,
+    /// Blocks registered with `defer`, run when the IR block that registered them finishes
+    pub deferred: Vec<Closure>,
     pub recursion_count: u64,
     pub parent_stack: Option<Arc<Stack>>,
     /// Variables that have been deleted (this is used to hide values from parent stack lookups)
@@ -83,6 +85,7 @@ impl Stack {
             arguments: ArgumentStack::new(),
             error_handlers: ErrorHandlerStack::new(),
             finally_run_handlers: ErrorHandlerStack::new(),
+            deferred: vec![],
             recursion_count: 0,
             parent_stack: None,
             parent_deletions: vec![],
@@ -105,6 +108,7 @@ impl Stack {
             arguments: ArgumentStack::new(),
             error_handlers: ErrorHandlerStack::new(),
             finally_run_handlers: ErrorHandlerStack::new(),
+            deferred: vec![],
             recursion_count: parent.recursion_count,
             vars: vec![],
             parent_deletions: vec![],
@@ -331,6 +335,7 @@ impl Stack {
             arguments: ArgumentStack::new(),
             error_handlers: ErrorHandlerStack::new(),
             finally_run_handlers: ErrorHandlerStack::new(),
+            deferred: vec![],
             recursion_count: self.recursion_count,
             parent_stack: None,
             parent_deletions: vec![],
@@ -366,6 +371,7 @@ impl Stack {
             arguments: ArgumentStack::new(),
             error_handlers: ErrorHandlerStack::new(),
             finally_run_handlers: ErrorHandlerStack::new(),
+            deferred: vec![],
             recursion_count: self.recursion_count,
             parent_stack: None,
             parent_deletions: vec![],
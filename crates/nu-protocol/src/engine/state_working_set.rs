@@ -304,6 +304,13 @@ impl<'a> StateWorkingSet<'a> {
             .or_else(|| self.permanent_state.get_module_comments(module_id))
     }
 
+    /// Attach doc comments to a variable, like the ones above a `const` definition.
+    pub fn add_var_comments(&mut self, var_id: VarId, comments: Vec<Span>) {
+        if !comments.is_empty() {
+            self.delta.doccomments.add_var_comments(var_id, comments);
+        }
+    }
+
     pub fn next_span_start(&self) -> usize {
         let permanent_span_start = self.permanent_state.next_span_start();
 
@@ -31,6 +31,9 @@ pub struct LabeledError {
     /// Errors that are related to or caused this error
     #[serde(default)]
     pub inner: Box<Vec<ShellError>>,
+    /// Structured data about the error, for code that handles it to act on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
 }
 
 impl LabeledError {
@@ -136,6 +139,21 @@ impl LabeledError {
         self
     }
 
+    /// Attach structured data about the error, for code that handles it to act on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use nu_protocol::{LabeledError, Value};
+    /// let error = LabeledError::new("Reactor overheating")
+    ///     .with_data(Value::test_int(1200));
+    /// assert_eq!(Some(Value::test_int(1200)), error.data);
+    /// ```
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
     /// Create a [`LabeledError`] from a type that implements [`miette::Diagnostic`].
     ///
     /// # Example
@@ -178,6 +196,7 @@ impl LabeledError {
                 .map(|i| Self::from_diagnostic(i).into())
                 .collect::<Vec<_>>()
                 .into(),
+            data: None,
         }
     }
 }
@@ -417,7 +436,10 @@ impl Diagnostic for LabeledError {
 
 impl From<ShellError> for LabeledError {
     fn from(err: ShellError) -> Self {
-        Self::from_diagnostic(&err)
+        match err {
+            ShellError::LabeledError(labeled) => *labeled,
+            err => Self::from_diagnostic(&err),
+        }
     }
 }
 
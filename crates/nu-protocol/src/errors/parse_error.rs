@@ -60,8 +60,15 @@ pub enum ParseError {
     ExpectedWithDidYouMean(&'static str, DidYouMean, #[label("expected {0}. {1}")] Span),
 
     #[error("Command does not support {0} input.")]
-    #[diagnostic(code(nu::parser::input_type_mismatch))]
-    InputMismatch(String, #[label("command doesn't support {0} input")] Span),
+    #[diagnostic(
+        code(nu::parser::input_type_mismatch),
+        help("The command only supports {1} input.")
+    )]
+    InputMismatch(
+        String,
+        String,
+        #[label("command doesn't support {0} input")] Span,
+    ),
 
     #[error("Command output doesn't match {0}.")]
     #[diagnostic(code(nu::parser::output_type_mismatch))]
@@ -440,10 +447,6 @@ pub enum ParseError {
         #[label = "parameter {0} needs to be '{1}' instead of '{2}'"] Span,
     ),
 
-    #[error("Default values should be constant expressions.")]
-    #[diagnostic(code(nu::parser::non_constant_default_value))]
-    NonConstantDefaultValue(#[label = "expected a constant value"] Span),
-
     #[error("Extra columns.")]
     #[diagnostic(code(nu::parser::extra_columns))]
     ExtraColumns(
@@ -625,7 +628,7 @@ impl ParseError {
             ParseError::MissingType(s) => *s,
             ParseError::TypeMismatch(_, _, s) => *s,
             ParseError::TypeMismatchHelp(_, _, s, _) => *s,
-            ParseError::InputMismatch(_, s) => *s,
+            ParseError::InputMismatch(_, _, s) => *s,
             ParseError::OutputMismatch(_, _, s) => *s,
             ParseError::MissingRequiredFlag(_, s) => *s,
             ParseError::IncompleteMathExpression(s) => *s,
@@ -634,7 +637,6 @@ impl ParseError {
             ParseError::IncompleteParser(s) => *s,
             ParseError::RestNeedsName(s) => *s,
             ParseError::ParameterMismatchType(_, _, _, s) => *s,
-            ParseError::NonConstantDefaultValue(s) => *s,
             ParseError::ExtraColumns(_, s) => *s,
             ParseError::MissingColumns(_, s) => *s,
             ParseError::AssignmentMismatch(_, _, s) => *s,
@@ -20,6 +20,10 @@ pub mod network;
 /// The fundamental error type for the evaluation engine. These cases represent different kinds of errors
 /// the evaluator might face, along with helpful spans to label. An error renderer will take this error value
 /// and pass it into an error viewer to display to the user.
+///
+/// Every case has a diagnostic code, like `nu::shell::division_by_zero`, which scripts can match on
+/// to handle specific errors in `catch` (see [`ShellError::error_code`]). The codes are part of the
+/// public interface, so they should be kept stable when cases are changed or renamed.
 #[derive(Debug, Clone, Error, Diagnostic, PartialEq)]
 pub enum ShellError {
     /// One or more of the values have types not supported by the operator.
@@ -105,6 +109,20 @@ pub enum ShellError {
         src_span: Span,
     },
 
+    /// A custom command produced output that doesn't match the output types it declares.
+    ///
+    /// ## Resolution
+    ///
+    /// Check the output of the command's body, or change its declared output types.
+    #[error("Output type doesn't match the command's signature.")]
+    #[diagnostic(code(nu::shell::only_supports_this_output_type))]
+    OnlySupportsThisOutputType {
+        exp_output_type: String,
+        wrong_type: String,
+        #[label("command declares {exp_output_type} output, but produced {wrong_type}")]
+        call_span: Span,
+    },
+
     /// No input value was piped into the command.
     ///
     /// ## Resolution
@@ -1450,6 +1468,41 @@ On Windows, this would be %USERPROFILE%\AppData\Roaming"#
 }
 
 impl ShellError {
+    /// The code identifying the kind of this error, like `nu::shell::non_zero_exit_code`.
+    ///
+    /// Errors wrapping another one to point out where it happened, like the ones raised by closures
+    /// that were given an input or the ones chained for a backtrace, have the code of the wrapped
+    /// error, so the code doesn't depend on where the error was raised.
+    pub fn error_code(&self) -> String {
+        self.wrapped_error()
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "nu::shell::error".into())
+    }
+
+    /// The structured data attached to this error by `error make`, if any.
+    ///
+    /// Like the [code](Self::error_code), it's taken from the wrapped error.
+    pub fn error_data(&self) -> Option<&Value> {
+        match self.wrapped_error() {
+            Self::LabeledError(labeled) => labeled.data.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The error that this one wraps to point out where it happened, or this error itself.
+    fn wrapped_error(&self) -> &Self {
+        match self {
+            Self::EvalBlockWithInput { sources, .. } if !sources.is_empty() => {
+                sources[0].wrapped_error()
+            }
+            Self::ChainedError(chained) if !chained.sources.is_empty() => {
+                chained.sources[0].wrapped_error()
+            }
+            _ => self,
+        }
+    }
+
     pub fn external_exit_code(&self) -> Option<Spanned<i32>> {
         let (item, span) = match *self {
             Self::NonZeroExitCode { exit_code, span } => (exit_code.into(), span),
@@ -1478,6 +1531,7 @@ impl ShellError {
 
         let mut record = record! {
             "msg" => Value::string(self.to_string(), span),
+            "code" => Value::string(self.error_code(), span),
             "debug" => Value::string(format!("{self:?}"), span),
             "raw" => Value::error(self.clone(), span),
             "rendered" => Value::string(format_cli_error(Some(stack), working_set, &self, Some("nu::shell::error")), span),
@@ -1488,6 +1542,10 @@ impl ShellError {
             record.push("exit_code", Value::int(code.item.into(), code.span));
         }
 
+        if let Some(data) = self.error_data() {
+            record.push("data", data.clone());
+        }
+
         Value::record(record, span)
     }
 
@@ -1603,7 +1661,11 @@ impl Serialize for ShellError {
     where
         S: serde::Serializer,
     {
-        LabeledError::from_diagnostic(self).serialize(serializer)
+        match self {
+            // Serialized directly to keep the data, which a diagnostic doesn't have
+            Self::LabeledError(labeled) => labeled.serialize(serializer),
+            _ => LabeledError::from_diagnostic(self).serialize(serializer),
+        }
     }
 }
 
@@ -266,6 +266,7 @@ pub trait Eval {
                             Math::Pow => lhs.pow(op_span, &rhs, expr_span),
                             Math::Concatenate => lhs.concat(op_span, &rhs, expr_span),
                         }
+                        .or_else(|err| rhs.reverse_custom_operation(&lhs, op, op_span, err))
                     }
                     Operator::Comparison(comparison) => {
                         let lhs = Self::eval::<D>(state, mut_state, lhs)?;
@@ -292,6 +293,7 @@ pub trait Eval {
                                 Self::regex_match(state, op_span, &lhs, &rhs, true, expr_span)
                             }
                         }
+                        .or_else(|err| rhs.reverse_custom_operation(&lhs, op, op_span, err))
                     }
                     Operator::Bits(bits) => {
                         let lhs = Self::eval::<D>(state, mut_state, lhs)?;
@@ -303,6 +305,7 @@ pub trait Eval {
                             Bits::ShiftLeft => lhs.bit_shl(op_span, &rhs, expr_span),
                             Bits::ShiftRight => lhs.bit_shr(op_span, &rhs, expr_span),
                         }
+                        .or_else(|err| rhs.reverse_custom_operation(&lhs, op, op_span, err))
                     }
                     Operator::Assignment(assignment) => Self::eval_assignment::<D>(
                         state, mut_state, lhs, rhs, assignment, op_span, expr_span
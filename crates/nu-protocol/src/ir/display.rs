@@ -355,6 +355,7 @@ impl fmt::Display for FmtLiteral<'_> {
             Literal::Float(fl) => write!(f, "float({fl:?})"),
             Literal::Filesize(q) => write!(f, "filesize({q}b)"),
             Literal::Duration(q) => write!(f, "duration({q}ns)"),
+            Literal::Rate(q) => write!(f, "rate({}bit/sec)", q.get()),
             Literal::Binary(b) => write!(f, "binary({})", FmtData(self.data, *b)),
             Literal::Block(id) => write!(f, "block({})", id.get()),
             Literal::Closure(id) => write!(f, "closure({})", id.get()),
@@ -1,5 +1,5 @@
 use crate::{
-    BlockId, DeclId, Filesize, RegId, ShellError, Span, Value, VarId,
+    BlockId, DeclId, Filesize, Rate, RegId, ShellError, Span, Value, VarId,
     ast::{CellPath, Expression, Operator, Pattern, RangeInclusion},
     engine::EngineState,
 };
@@ -425,6 +425,7 @@ pub enum Literal {
     Float(f64),
     Filesize(Filesize),
     Duration(i64),
+    Rate(Rate),
     Binary(DataSlice),
     Block(BlockId),
     Closure(BlockId),
@@ -40,6 +40,8 @@ pub struct PluginIdentity {
     filename: PathBuf,
     /// The shell used to start the plugin, if required
     shell: Option<PathBuf>,
+    /// The address to connect to, if the plugin runs elsewhere instead of being started
+    address: Option<String>,
     /// The friendly name of the plugin (e.g. `inc` for `C:\nu_plugin_inc.exe`)
     name: String,
 }
@@ -69,10 +71,18 @@ impl PluginIdentity {
         Ok(PluginIdentity {
             filename,
             shell,
+            address: None,
             name,
         })
     }
 
+    /// Set the address of a plugin that runs elsewhere, like `tcp://gpu-box:4000`. The engine
+    /// connects to the plugin at this address instead of running the plugin's executable, and the
+    /// filename is only used for the plugin's name.
+    pub fn with_address(self, address: Option<String>) -> PluginIdentity {
+        PluginIdentity { address, ..self }
+    }
+
     /// The filename of the plugin executable.
     pub fn filename(&self) -> &Path {
         &self.filename
@@ -83,6 +93,11 @@ impl PluginIdentity {
         self.shell.as_deref()
     }
 
+    /// The address to connect to the plugin at, if it runs elsewhere.
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
     /// The name of the plugin, determined by the part of the filename after `nu_plugin_` excluding
     /// the extension.
     ///
@@ -110,7 +125,12 @@ impl PluginIdentity {
 
     /// A command that could be used to add the plugin, for suggesting in errors.
     pub fn add_command(&self) -> String {
-        if let Some(shell) = self.shell() {
+        if let Some(address) = self.address() {
+            format!(
+                "plugin add --address '{address}' '{}'",
+                self.filename().display(),
+            )
+        } else if let Some(shell) = self.shell() {
             format!(
                 "plugin add --shell '{}' '{}'",
                 shell.display(),
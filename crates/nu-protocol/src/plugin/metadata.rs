@@ -7,12 +7,19 @@ use serde::{Deserialize, Serialize};
 pub struct PluginMetadata {
     /// The version of the plugin itself, as self-reported.
     pub version: Option<String>,
+    /// The oldest version of Nushell that the plugin works with, as self-reported. The plugin
+    /// isn't loaded by older versions.
+    #[serde(default)]
+    pub min_nu_version: Option<String>,
 }
 
 impl PluginMetadata {
     /// Create empty metadata.
     pub const fn new() -> PluginMetadata {
-        PluginMetadata { version: None }
+        PluginMetadata {
+            version: None,
+            min_nu_version: None,
+        }
     }
 
     /// Set the version of the plugin on the metadata. A suggested way to construct this is:
@@ -29,6 +36,15 @@ impl PluginMetadata {
         self.version = Some(version.into());
         self
     }
+
+    /// Set the oldest version of Nushell that the plugin works with on the metadata.
+    ///
+    /// This is only needed if the plugin depends on behavior of Nushell that is newer than the
+    /// plugin protocol it was built with, which is already checked when the plugin starts.
+    pub fn with_min_nu_version(mut self, min_nu_version: impl Into<String>) -> Self {
+        self.min_nu_version = Some(min_nu_version.into());
+        self
+    }
 }
 
 impl Default for PluginMetadata {
@@ -114,6 +114,11 @@ pub struct PluginRegistryItem {
     /// The shell program used to run the plugin, if applicable.
     pub shell: Option<PathBuf>,
 
+    /// The address of the plugin, if it runs elsewhere and listens for connections instead of
+    /// being run by the engine, like `tcp://gpu-box:4000`.
+    #[serde(default)]
+    pub address: Option<String>,
+
     /// Additional data that might be invalid so that we don't fail to load the whole plugin file
     /// if there's a deserialization error.
     #[serde(flatten)]
@@ -134,6 +139,7 @@ impl PluginRegistryItem {
             name: identity.name().to_owned(),
             filename: identity.filename().to_owned(),
             shell: identity.shell().map(|p| p.to_owned()),
+            address: identity.address().map(|a| a.to_owned()),
             data: PluginRegistryItemData::Valid { metadata, commands },
         }
     }
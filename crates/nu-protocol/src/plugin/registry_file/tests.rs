@@ -11,9 +11,11 @@ fn foo_plugin() -> PluginRegistryItem {
         name: "foo".into(),
         filename: "/path/to/nu_plugin_foo".into(),
         shell: None,
+        address: None,
         data: PluginRegistryItemData::Valid {
             metadata: PluginMetadata {
                 version: Some("0.1.0".into()),
+                min_nu_version: None,
             },
             commands: vec![PluginSignature {
                 sig: Signature::new("foo")
@@ -39,9 +41,11 @@ fn bar_plugin() -> PluginRegistryItem {
         name: "bar".into(),
         filename: "/path/to/nu_plugin_bar".into(),
         shell: None,
+        address: None,
         data: PluginRegistryItemData::Valid {
             metadata: PluginMetadata {
                 version: Some("0.2.0".into()),
+                min_nu_version: Some("0.100.0".into()),
             },
             commands: vec![PluginSignature {
                 sig: Signature::new("bar")
@@ -85,6 +89,7 @@ fn roundtrip_invalid() -> Result<(), ShellError> {
             name: "invalid".into(),
             filename: "/path/to/nu_plugin_invalid".into(),
             shell: None,
+            address: None,
             data: PluginRegistryItemData::Invalid,
         }],
     };
@@ -1,7 +1,7 @@
 use crate::{
     BlockId, DeclId, DeprecationEntry, Example, FromValue, IntoValue, PipelineData, ShellError,
     Span, SyntaxShape, Type, Value, VarId,
-    engine::{Call, Command, CommandType, EngineState, Stack},
+    engine::{Call, Command, CommandType, EngineState, MemoOptions, Stack},
 };
 use nu_derive_value::FromValue as DeriveFromValue;
 use nu_utils::NuCow;
@@ -39,6 +39,8 @@ pub struct Flag {
     // For custom commands
     pub var_id: Option<VarId>,
     pub default_value: Option<Value>,
+    /// Computes the default value on each call, when it isn't a constant
+    pub default_block: Option<BlockId>,
 }
 
 impl Flag {
@@ -53,6 +55,7 @@ impl Flag {
             completion: None,
             var_id: None,
             default_value: None,
+            default_block: None,
         }
     }
 
@@ -108,6 +111,8 @@ pub struct PositionalArg {
     // For custom commands
     pub var_id: Option<VarId>,
     pub default_value: Option<Value>,
+    /// Computes the default value on each call, when it isn't a constant
+    pub default_block: Option<BlockId>,
 }
 
 impl PositionalArg {
@@ -120,6 +125,7 @@ impl PositionalArg {
             completion: None,
             var_id: None,
             default_value: None,
+            default_block: None,
         }
     }
 
@@ -426,6 +432,7 @@ impl Signature {
             required: false,
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         };
         self.named.push(flag);
@@ -529,6 +536,7 @@ impl Signature {
             shape: shape.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -548,6 +556,7 @@ impl Signature {
             shape: shape.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -573,6 +582,7 @@ impl Signature {
             shape: shape.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -613,6 +623,7 @@ impl Signature {
             desc: desc.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -637,6 +648,7 @@ impl Signature {
             desc: desc.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -660,6 +672,7 @@ impl Signature {
             desc: desc.into(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         });
 
@@ -1006,4 +1019,11 @@ impl Command for BlockCommand {
             })
             .collect()
     }
+
+    fn memo_options(&self) -> Option<MemoOptions> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == "memo")
+            .and_then(|(_, value)| MemoOptions::from_value(value.clone()).ok())
+    }
 }
@@ -100,6 +100,9 @@ pub enum SyntaxShape {
     /// A range is allowed (eg, `1..3`)
     Range,
 
+    /// A data rate value is allowed, eg `100Mbit/sec`
+    Rate,
+
     /// A record value, eg `{x: 1, y: 2}`
     Record(Vec<(String, SyntaxShape)>),
 
@@ -169,6 +172,7 @@ impl SyntaxShape {
             SyntaxShape::OneOf(types) => Type::one_of(types.iter().map(SyntaxShape::to_type)),
             SyntaxShape::Operator => Type::Any,
             SyntaxShape::Range => Type::Range,
+            SyntaxShape::Rate => Type::Rate,
             SyntaxShape::Record(entries) => Type::Record(mk_ty(entries)),
             SyntaxShape::RowCondition => Type::Bool,
             SyntaxShape::Boolean => Type::Bool,
@@ -233,6 +237,7 @@ impl Display for SyntaxShape {
             }
             SyntaxShape::Filesize => write!(f, "filesize"),
             SyntaxShape::Duration => write!(f, "duration"),
+            SyntaxShape::Rate => write!(f, "rate"),
             SyntaxShape::DateTime => write!(f, "datetime"),
             SyntaxShape::Operator => write!(f, "operator"),
             SyntaxShape::RowCondition => write!(
@@ -29,6 +29,7 @@ pub enum Type {
     /// Supertype of all types it contains.
     OneOf(Box<[Type]>),
     Range,
+    Rate,
     Record(Box<[(String, Type)]>),
     String,
     Glob,
@@ -322,6 +323,7 @@ impl Type {
             Type::Duration => SyntaxShape::Duration,
             Type::Date => SyntaxShape::DateTime,
             Type::Filesize => SyntaxShape::Filesize,
+            Type::Rate => SyntaxShape::Rate,
             Type::List(x) => SyntaxShape::List(Box::new(x.to_shape())),
             Type::Number => SyntaxShape::Number,
             Type::OneOf(types) => SyntaxShape::OneOf(types.iter().map(Type::to_shape).collect()),
@@ -350,6 +352,7 @@ impl Type {
             Type::Float => String::from("float"),
             Type::Int => String::from("int"),
             Type::Range => String::from("range"),
+            Type::Rate => String::from("rate"),
             Type::Record(_) => String::from("record"),
             Type::Table(_) => String::from("table"),
             Type::List(_) => String::from("list"),
@@ -383,6 +386,7 @@ impl Display for Type {
             Type::Float => write!(f, "float"),
             Type::Int => write!(f, "int"),
             Type::Range => write!(f, "range"),
+            Type::Rate => write!(f, "rate"),
             Type::Record(fields) => {
                 if fields.is_empty() {
                     write!(f, "record")
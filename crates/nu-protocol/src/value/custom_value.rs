@@ -110,6 +110,29 @@ pub trait CustomValue: fmt::Debug + Send + Sync {
         })
     }
 
+    /// Definition of an operation between another Value and the object that implements the trait,
+    /// when the object is on the right-hand side, e.g. `2 * $custom`.
+    ///
+    /// This is only used when the other value doesn't support the operation itself.
+    ///
+    /// Default impl raises [`ShellError::OperatorUnsupportedType`].
+    fn reverse_operation(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        let _ = left;
+        Err(ShellError::OperatorUnsupportedType {
+            op: operator,
+            unsupported: Type::Custom(self.type_name().into()),
+            op_span: op,
+            unsupported_span: rhs_span,
+            help: None,
+        })
+    }
+
     /// Save custom value to disk.
     ///
     /// This method is used in `save` to save a custom value to disk.
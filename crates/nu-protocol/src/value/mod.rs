@@ -5,6 +5,7 @@ mod from_value;
 mod glob;
 mod into_value;
 mod range;
+mod rate;
 #[cfg(test)]
 mod test_derive;
 
@@ -17,6 +18,7 @@ pub use glob::*;
 pub use into_value::{IntoValue, TryIntoValue};
 pub use nu_utils::MultiLife;
 pub use range::{FloatRange, IntRange, Range};
+pub use rate::*;
 pub use record::Record;
 
 use crate::{
@@ -108,6 +110,20 @@ pub enum Value {
         #[serde(rename = "span")]
         internal_span: Span,
     },
+    /// A data rate in bits per second, like `100Mbit/sec`.
+    ///
+    /// This variant is new to the plugin protocol, where it's sent as
+    /// `{"Rate": {"val": <bits per second>, "span": ...}}`. Plugins built against an older
+    /// `nu-plugin-protocol` can't deserialize it, which is fine as long as the protocol version
+    /// is bumped with it: older plugins are then refused as incompatible when they're loaded.
+    #[non_exhaustive]
+    Rate {
+        val: Rate,
+        /// note: spans are being refactored out of Value
+        /// please use .span() instead of matching this span value
+        #[serde(rename = "span")]
+        internal_span: Span,
+    },
     #[non_exhaustive]
     Date {
         val: DateTime<FixedOffset>,
@@ -211,6 +227,10 @@ impl Clone for Value {
                 val: *val,
                 internal_span: *internal_span,
             },
+            Value::Rate { val, internal_span } => Value::Rate {
+                val: *val,
+                internal_span: *internal_span,
+            },
             Value::Date { val, internal_span } => Value::Date {
                 val: *val,
                 internal_span: *internal_span,
@@ -356,6 +376,15 @@ impl Value {
         }
     }
 
+    /// Returns the inner [`Rate`] value or an error if this `Value` is not a rate
+    pub fn as_rate(&self) -> Result<Rate, ShellError> {
+        if let Value::Rate { val, .. } = self {
+            Ok(*val)
+        } else {
+            self.cant_convert_to("rate")
+        }
+    }
+
     /// Returns the inner [`DateTime`] value or an error if this `Value` is not a date
     pub fn as_date(&self) -> Result<DateTime<FixedOffset>, ShellError> {
         if let Value::Date { val, .. } = self {
@@ -757,6 +786,7 @@ impl Value {
             | Value::Float { internal_span, .. }
             | Value::Filesize { internal_span, .. }
             | Value::Duration { internal_span, .. }
+            | Value::Rate { internal_span, .. }
             | Value::Date { internal_span, .. }
             | Value::Range { internal_span, .. }
             | Value::String { internal_span, .. }
@@ -780,6 +810,7 @@ impl Value {
             | Value::Float { internal_span, .. }
             | Value::Filesize { internal_span, .. }
             | Value::Duration { internal_span, .. }
+            | Value::Rate { internal_span, .. }
             | Value::Date { internal_span, .. }
             | Value::Range { internal_span, .. }
             | Value::String { internal_span, .. }
@@ -809,6 +840,7 @@ impl Value {
             Value::Float { .. } => Type::Float,
             Value::Filesize { .. } => Type::Filesize,
             Value::Duration { .. } => Type::Duration,
+            Value::Rate { .. } => Type::Rate,
             Value::Date { .. } => Type::Date,
             Value::Range { .. } => Type::Range,
             Value::String { .. } => Type::String,
@@ -869,6 +901,7 @@ impl Value {
                 | Value::Glob { .. }
                 | Value::Filesize { .. }
                 | Value::Duration { .. }
+                | Value::Rate { .. }
                 | Value::Date { .. }
                 | Value::Range { .. }
                 | Value::Closure { .. }
@@ -959,6 +992,7 @@ impl Value {
             Value::Float { val, .. } => ObviousFloat(*val).to_string(),
             Value::Filesize { val, .. } => config.filesize.format(*val).to_string(),
             Value::Duration { val, .. } => format_duration(*val),
+            Value::Rate { val, .. } => val.format(config.filesize.formatter().show_unit(true)),
             Value::Date { val, .. } => match &config.datetime_format.normal {
                 Some(format) => self.format_datetime(val, format),
                 None => {
@@ -1801,6 +1835,7 @@ impl Value {
             | Value::Float { .. }
             | Value::Filesize { .. }
             | Value::Duration { .. }
+            | Value::Rate { .. }
             | Value::Date { .. }
             | Value::Range { .. }
             | Value::String { .. }
@@ -1867,6 +1902,7 @@ impl Value {
             Value::Float { .. } => std::mem::size_of::<Self>(),
             Value::Filesize { .. } => std::mem::size_of::<Self>(),
             Value::Duration { .. } => std::mem::size_of::<Self>(),
+            Value::Rate { .. } => std::mem::size_of::<Self>(),
             Value::Date { .. } => std::mem::size_of::<Self>(),
             Value::Range { val, .. } => std::mem::size_of::<Self>() + val.memory_size(),
             Value::String { val, .. } => std::mem::size_of::<Self>() + val.capacity(),
@@ -1921,6 +1957,13 @@ impl Value {
         }
     }
 
+    pub fn rate(val: impl Into<Rate>, span: Span) -> Value {
+        Value::Rate {
+            val: val.into(),
+            internal_span: span,
+        }
+    }
+
     pub fn date(val: DateTime<FixedOffset>, span: Span) -> Value {
         Value::Date {
             val,
@@ -2038,6 +2081,12 @@ impl Value {
         Value::duration(val, Span::test_data())
     }
 
+    /// Note: Only use this for test data, *not* live data, as it will point into unknown source
+    /// when used in errors.
+    pub fn test_rate(val: impl Into<Rate>) -> Value {
+        Value::rate(val, Span::test_data())
+    }
+
     /// Note: Only use this for test data, *not* live data, as it will point into unknown source
     /// when used in errors.
     pub fn test_date(val: DateTime<FixedOffset>) -> Value {
@@ -2115,6 +2164,7 @@ impl Value {
             Value::test_int(0),
             Value::test_filesize(0),
             Value::test_duration(0),
+            Value::test_rate(0),
             Value::test_date(DateTime::UNIX_EPOCH.into()),
             Value::test_range(Range::IntRange(IntRange {
                 start: 0,
@@ -2375,6 +2425,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Less),
                 Value::Filesize { .. } => Some(Ordering::Less),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2394,6 +2445,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Less),
                 Value::Filesize { .. } => Some(Ordering::Less),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2413,6 +2465,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Less),
                 Value::Filesize { .. } => Some(Ordering::Less),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2432,6 +2485,7 @@ impl PartialOrd for Value {
                 Value::Glob { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Filesize { .. } => Some(Ordering::Less),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2451,6 +2505,7 @@ impl PartialOrd for Value {
                 Value::Glob { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Filesize { .. } => Some(Ordering::Less),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2470,6 +2525,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Duration { .. } => Some(Ordering::Less),
+                Value::Rate { .. } => Some(Ordering::Less),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2489,6 +2545,27 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { val: rhs, .. } => lhs.partial_cmp(rhs),
+                Value::Rate { .. } => Some(Ordering::Less),
+                Value::Date { .. } => Some(Ordering::Less),
+                Value::Range { .. } => Some(Ordering::Less),
+                Value::Record { .. } => Some(Ordering::Less),
+                Value::List { .. } => Some(Ordering::Less),
+                Value::Closure { .. } => Some(Ordering::Less),
+                Value::Error { .. } => Some(Ordering::Less),
+                Value::Binary { .. } => Some(Ordering::Less),
+                Value::CellPath { .. } => Some(Ordering::Less),
+                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Nothing { .. } => Some(Ordering::Less),
+            },
+            (Value::Rate { val: lhs, .. }, rhs) => match rhs {
+                Value::Bool { .. } => Some(Ordering::Greater),
+                Value::Int { .. } => Some(Ordering::Greater),
+                Value::Float { .. } => Some(Ordering::Greater),
+                Value::String { .. } => Some(Ordering::Greater),
+                Value::Glob { .. } => Some(Ordering::Greater),
+                Value::Filesize { .. } => Some(Ordering::Greater),
+                Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Date { .. } => Some(Ordering::Less),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2508,6 +2585,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Range { .. } => Some(Ordering::Less),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2527,6 +2605,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::Record { .. } => Some(Ordering::Less),
@@ -2546,6 +2625,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { val: rhs, .. } => {
@@ -2591,6 +2671,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2610,6 +2691,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2629,6 +2711,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2648,6 +2731,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2667,6 +2751,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2687,6 +2772,7 @@ impl PartialOrd for Value {
                 Value::Glob { .. } => Some(Ordering::Greater),
                 Value::Filesize { .. } => Some(Ordering::Greater),
                 Value::Duration { .. } => Some(Ordering::Greater),
+                Value::Rate { .. } => Some(Ordering::Greater),
                 Value::Date { .. } => Some(Ordering::Greater),
                 Value::Range { .. } => Some(Ordering::Greater),
                 Value::Record { .. } => Some(Ordering::Greater),
@@ -2723,7 +2809,50 @@ where
     }
 }
 
+fn checked_rate_operation(val: Option<Rate>, span: Span) -> Result<Value, ShellError> {
+    if let Some(val) = val {
+        Ok(Value::rate(val, span))
+    } else {
+        Err(ShellError::OperatorOverflow {
+            msg: "operation overflowed".to_owned(),
+            span,
+            help: None,
+        })
+    }
+}
+
 impl Value {
+    /// Handle an operation between `lhs` and `self` that failed with `error`, if `self` is a custom
+    /// value that defines it with [`CustomValue::reverse_operation`].
+    ///
+    /// If `lhs` is a custom value, or `self` doesn't support the operation either, `error` is
+    /// returned as it is.
+    pub fn reverse_custom_operation(
+        &self,
+        lhs: &Value,
+        operator: Operator,
+        op: Span,
+        error: ShellError,
+    ) -> Result<Value, ShellError> {
+        match (lhs, self) {
+            (Value::Custom { .. }, _) => Err(error),
+            (_, Value::Custom { val, .. }) => {
+                match val.reverse_operation(lhs, operator, op, self.span()) {
+                    Err(ShellError::OperatorUnsupportedType { .. }) => Err(error),
+                    // Errors from plugins arrive as labeled errors, but keep their code
+                    Err(ShellError::LabeledError(labeled))
+                        if labeled.code.as_deref()
+                            == Some("nu::shell::operator_unsupported_type") =>
+                    {
+                        Err(error)
+                    }
+                    result => result,
+                }
+            }
+            _ => Err(error),
+        }
+    }
+
     pub fn add(&self, op: Span, rhs: &Value, span: Span) -> Result<Value, ShellError> {
         match (self, rhs) {
             (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
@@ -2785,6 +2914,9 @@ impl Value {
                     })
                 }
             }
+            (Value::Rate { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                checked_rate_operation(*lhs + *rhs, span)
+            }
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Add), op, rhs)
             }
@@ -2801,7 +2933,8 @@ impl Value {
                             | Value::String { .. }
                             | Value::Date { .. }
                             | Value::Duration { .. }
-                            | Value::Filesize { .. },
+                            | Value::Filesize { .. }
+                            | Value::Rate { .. },
                     )
                 },
             )),
@@ -2866,6 +2999,9 @@ impl Value {
                     })
                 }
             }
+            (Value::Rate { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                checked_rate_operation(*lhs - *rhs, span)
+            }
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Subtract), op, rhs)
             }
@@ -2881,7 +3017,8 @@ impl Value {
                             | Value::Float { .. }
                             | Value::Date { .. }
                             | Value::Duration { .. }
-                            | Value::Filesize { .. },
+                            | Value::Filesize { .. }
+                            | Value::Rate { .. },
                     )
                 },
             )),
@@ -2966,6 +3103,30 @@ impl Value {
             (Value::Float { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
                 Ok(Value::duration((*lhs * *rhs as f64) as i64, span))
             }
+            (Value::Int { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                checked_rate_operation(*rhs * *lhs, span)
+            }
+            (Value::Rate { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                checked_rate_operation(*lhs * *rhs, span)
+            }
+            (Value::Float { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                checked_rate_operation(*rhs * *lhs, span)
+            }
+            (Value::Rate { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                checked_rate_operation(*lhs * *rhs, span)
+            }
+            (Value::Rate { val: rate, .. }, Value::Duration { val: nanos, .. })
+            | (Value::Duration { val: nanos, .. }, Value::Rate { val: rate, .. }) => {
+                if let Some(val) = rate.filesize_in_nanos(*nanos) {
+                    Ok(Value::filesize(val, span))
+                } else {
+                    Err(ShellError::OperatorOverflow {
+                        msg: "multiply operation overflowed".into(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Multiply), op, rhs)
             }
@@ -2980,7 +3141,8 @@ impl Value {
                         Value::Int { .. }
                             | Value::Float { .. }
                             | Value::Duration { .. }
-                            | Value::Filesize { .. },
+                            | Value::Filesize { .. }
+                            | Value::Rate { .. },
                     )
                 },
             )),
@@ -3088,6 +3250,59 @@ impl Value {
                     Err(ShellError::DivisionByZero { span: op })
                 }
             }
+            (Value::Filesize { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
+                if *rhs == 0 {
+                    Err(ShellError::DivisionByZero { span: op })
+                } else if let Some(val) = Rate::from_filesize_per_nanos(*lhs, *rhs) {
+                    Ok(Value::rate(val, span))
+                } else {
+                    Err(ShellError::OperatorOverflow {
+                        msg: "division operation overflowed".into(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            (Value::Filesize { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                if *rhs == Rate::ZERO {
+                    Err(ShellError::DivisionByZero { span: op })
+                } else if let Some(val) = rhs.nanos_for_filesize(*lhs) {
+                    Ok(Value::duration(val, span))
+                } else {
+                    Err(ShellError::OperatorOverflow {
+                        msg: "division operation overflowed".into(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            (Value::Rate { val: lhs, .. }, Value::Rate { val: rhs, .. }) => {
+                if *rhs == Rate::ZERO {
+                    Err(ShellError::DivisionByZero { span: op })
+                } else {
+                    Ok(Value::float(lhs.get() as f64 / rhs.get() as f64, span))
+                }
+            }
+            (Value::Rate { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                if let Some(val) = lhs.get().checked_div(*rhs) {
+                    Ok(Value::rate(val, span))
+                } else if *rhs == 0 {
+                    Err(ShellError::DivisionByZero { span: op })
+                } else {
+                    Err(ShellError::OperatorOverflow {
+                        msg: "division operation overflowed".into(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            (Value::Rate { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                if *rhs != 0.0 {
+                    checked_rate_operation(*lhs * rhs.recip(), span)
+                } else {
+                    Err(ShellError::DivisionByZero { span: op })
+                }
+            }
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Divide), op, rhs)
             }
@@ -3102,7 +3317,8 @@ impl Value {
                         Value::Int { .. }
                             | Value::Float { .. }
                             | Value::Duration { .. }
-                            | Value::Filesize { .. },
+                            | Value::Filesize { .. }
+                            | Value::Rate { .. },
                     )
                 },
             )),
@@ -3570,6 +3786,7 @@ impl Value {
                             | Value::String { .. }
                             | Value::Filesize { .. }
                             | Value::Duration { .. }
+                            | Value::Rate { .. }
                             | Value::Date { .. }
                             | Value::Bool { .. }
                             | Value::Nothing { .. }
@@ -3612,6 +3829,7 @@ impl Value {
                             | Value::String { .. }
                             | Value::Filesize { .. }
                             | Value::Duration { .. }
+                            | Value::Rate { .. }
                             | Value::Date { .. }
                             | Value::Bool { .. }
                             | Value::Nothing { .. }
@@ -3657,6 +3875,7 @@ impl Value {
                             | Value::String { .. }
                             | Value::Filesize { .. }
                             | Value::Duration { .. }
+                            | Value::Rate { .. }
                             | Value::Date { .. }
                             | Value::Bool { .. }
                             | Value::Nothing { .. }
@@ -3699,6 +3918,7 @@ impl Value {
                             | Value::String { .. }
                             | Value::Filesize { .. }
                             | Value::Duration { .. }
+                            | Value::Rate { .. }
                             | Value::Date { .. }
                             | Value::Bool { .. }
                             | Value::Nothing { .. }
@@ -0,0 +1,250 @@
+use crate::{Filesize, FilesizeFormatter, FromValue, IntoValue, ShellError, Span, Type, Value};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+const BITS_PER_BYTE: i128 = 8;
+
+/// All the possible bit units, used for data rates and in file size literals like `100Mbit`.
+pub const SUPPORTED_BIT_UNITS: [&str; 13] = [
+    "bit", "kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Kibit", "Mibit", "Gibit", "Tibit",
+    "Pibit", "Eibit",
+];
+
+/// A signed data rate in bits per second.
+///
+/// [`Rate`] is a wrapper around [`i64`], like [`Filesize`] is for bytes. Bits are used instead of
+/// bytes so that network rates like `1bit/sec` can be represented exactly.
+///
+/// A [`Rate`] is what dividing a [`Filesize`] by a duration gives, and multiplying it by a
+/// duration gives a [`Filesize`] back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[repr(transparent)]
+#[serde(transparent)]
+pub struct Rate(i64);
+
+impl Rate {
+    /// A [`Rate`] of 0 bits per second.
+    pub const ZERO: Self = Self(0);
+
+    /// Create a new [`Rate`] from a [`i64`] number of bits per second.
+    pub const fn new(bits_per_second: i64) -> Self {
+        Self(bits_per_second)
+    }
+
+    /// Returns the underlying [`i64`] number of bits per second in a [`Rate`].
+    pub const fn get(&self) -> i64 {
+        self.0
+    }
+
+    /// The rate of transferring `filesize` in `nanos` nanoseconds.
+    ///
+    /// Returns `None` if `nanos` is zero or the rate overflows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nu_protocol::{Filesize, FilesizeUnit, Rate};
+    /// let filesize = Filesize::from_unit(10, FilesizeUnit::MB).unwrap();
+    /// let rate = Rate::from_filesize_per_nanos(filesize, 2_000_000_000);
+    /// assert_eq!(rate, Some(Rate::new(40_000_000)));
+    /// ```
+    pub fn from_filesize_per_nanos(filesize: Filesize, nanos: i64) -> Option<Self> {
+        if nanos == 0 {
+            return None;
+        }
+        let bits = filesize.get() as i128 * BITS_PER_BYTE * NANOS_PER_SEC / nanos as i128;
+        i64::try_from(bits).ok().map(Self)
+    }
+
+    /// The amount of data transferred at this rate in `nanos` nanoseconds.
+    ///
+    /// Partial bytes are rounded towards zero. Returns `None` if the file size overflows.
+    pub fn filesize_in_nanos(&self, nanos: i64) -> Option<Filesize> {
+        let bytes = self.0 as i128 * nanos as i128 / (BITS_PER_BYTE * NANOS_PER_SEC);
+        i64::try_from(bytes).ok().map(Filesize::new)
+    }
+
+    /// The number of nanoseconds it takes to transfer `filesize` at this rate.
+    ///
+    /// Returns `None` if the rate is zero or the duration overflows.
+    pub fn nanos_for_filesize(&self, filesize: Filesize) -> Option<i64> {
+        if self.0 == 0 {
+            return None;
+        }
+        let nanos = filesize.get() as i128 * BITS_PER_BYTE * NANOS_PER_SEC / self.0 as i128;
+        i64::try_from(nanos).ok()
+    }
+
+    /// Returns the equivalent number of bytes per second, if it is a whole number.
+    pub const fn bytes_per_second(&self) -> Option<Filesize> {
+        if self.0 % BITS_PER_BYTE as i64 == 0 {
+            Some(Filesize::new(self.0 / BITS_PER_BYTE as i64))
+        } else {
+            None
+        }
+    }
+
+    /// Format the [`Rate`] per second using a [`FilesizeFormatter`] for the bytes.
+    ///
+    /// Rates that aren't a whole number of bytes per second are shown in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nu_protocol::{FilesizeFormatter, Rate};
+    /// let formatter = FilesizeFormatter::new();
+    /// assert_eq!(Rate::new(100_000_000).format(formatter), "12.5 MB/sec");
+    /// assert_eq!(Rate::new(1).format(formatter), "1 bit/sec");
+    /// ```
+    pub fn format(&self, formatter: FilesizeFormatter) -> String {
+        match self.bytes_per_second() {
+            Some(bytes) => format!("{}/sec", formatter.format(bytes)),
+            None => format!("{} bit/sec", self.0),
+        }
+    }
+}
+
+impl From<i64> for Rate {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Rate> for i64 {
+    fn from(rate: Rate) -> Self {
+        rate.0
+    }
+}
+
+impl FromValue for Rate {
+    fn from_value(value: Value) -> Result<Self, ShellError> {
+        value.as_rate()
+    }
+
+    fn expected_type() -> Type {
+        Type::Rate
+    }
+}
+
+impl IntoValue for Rate {
+    fn into_value(self, span: Span) -> Value {
+        Value::rate(self.0, span)
+    }
+}
+
+impl Add for Rate {
+    type Output = Option<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+}
+
+impl Sub for Rate {
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+}
+
+impl Mul<i64> for Rate {
+    type Output = Option<Self>;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.0.checked_mul(rhs).map(Self)
+    }
+}
+
+impl Mul<f64> for Rate {
+    type Output = Option<Self>;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        let bits = ((self.0 as f64) * rhs).round();
+        if i64::MIN as f64 <= bits && bits <= i64::MAX as f64 {
+            Some(Self(bits as i64))
+        } else {
+            None
+        }
+    }
+}
+
+impl Neg for Rate {
+    type Output = Option<Self>;
+
+    fn neg(self) -> Self::Output {
+        self.0.checked_neg().map(Self)
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(FilesizeFormatter::new()))
+    }
+}
+
+/// Returns the number of bits in a bit unit from [`SUPPORTED_BIT_UNITS`].
+///
+/// The match ignores ASCII case, like file size units in literals do.
+pub fn bit_unit_as_bits(unit: &str) -> Option<i64> {
+    let bits = match unit.to_ascii_lowercase().as_str() {
+        "bit" => 1,
+        "kbit" => 10_i64.pow(3),
+        "mbit" => 10_i64.pow(6),
+        "gbit" => 10_i64.pow(9),
+        "tbit" => 10_i64.pow(12),
+        "pbit" => 10_i64.pow(15),
+        "ebit" => 10_i64.pow(18),
+        "kibit" => 2_i64.pow(10),
+        "mibit" => 2_i64.pow(20),
+        "gibit" => 2_i64.pow(30),
+        "tibit" => 2_i64.pow(40),
+        "pibit" => 2_i64.pow(50),
+        "eibit" => 2_i64.pow(60),
+        _ => return None,
+    };
+    Some(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1_000_000, 1_000_000_000, 8_000_000)]
+    #[case(1, 8_000_000_000, 1)]
+    #[case(-1000, 1_000_000_000, -8000)]
+    fn filesize_per_nanos(#[case] bytes: i64, #[case] nanos: i64, #[case] bits: i64) {
+        assert_eq!(
+            Rate::from_filesize_per_nanos(Filesize::new(bytes), nanos),
+            Some(Rate::new(bits))
+        );
+    }
+
+    #[test]
+    fn rate_over_zero_nanos() {
+        assert_eq!(Rate::from_filesize_per_nanos(Filesize::new(1), 0), None);
+    }
+
+    #[test]
+    fn rate_round_trip() {
+        let rate = Rate::new(100_000_000);
+        let filesize = rate.filesize_in_nanos(60_000_000_000);
+        assert_eq!(filesize, Some(Filesize::new(750_000_000)));
+        assert_eq!(
+            filesize.and_then(|filesize| rate.nanos_for_filesize(filesize)),
+            Some(60_000_000_000)
+        );
+    }
+
+    #[rstest]
+    #[case("bit", 1)]
+    #[case("Mbit", 1_000_000)]
+    #[case("KIBIT", 1024)]
+    fn bit_units(#[case] unit: &str, #[case] bits: i64) {
+        assert_eq!(bit_unit_as_bits(unit), Some(bits));
+    }
+}
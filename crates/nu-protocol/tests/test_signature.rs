@@ -50,6 +50,7 @@ fn test_signature_chained() {
             shape: SyntaxShape::String,
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         })
     );
@@ -61,6 +62,7 @@ fn test_signature_chained() {
             shape: SyntaxShape::String,
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         })
     );
@@ -72,6 +74,7 @@ fn test_signature_chained() {
             shape: SyntaxShape::String,
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         })
     );
@@ -86,6 +89,7 @@ fn test_signature_chained() {
             desc: "Required named description.".to_string(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         })
     );
@@ -100,6 +104,7 @@ fn test_signature_chained() {
             desc: "Required named description.".to_string(),
             var_id: None,
             default_value: None,
+            default_block: None,
             completion: None,
         })
     );
@@ -0,0 +1,302 @@
+use nu_engine::{command_prelude::*, eval_block, eval_block_with_early_return};
+use nu_parser::{TokenContents, lex, parse};
+use nu_protocol::{
+    BlockId, Completion, DeclId, PositionalArg, VarId,
+    debugger::WithoutDebug,
+    engine::{FileStack, StateWorkingSet},
+    report_parse_error,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// A prelude command whose module is only parsed once the command is called, so that it doesn't
+/// add to the startup time.
+///
+/// The signature is parsed from the definition in the module, without its body. The module is
+/// parsed on the first call, and the calls after it use the same parsed command.
+#[derive(Clone)]
+pub(crate) struct LazyCommand {
+    signature: Signature,
+    /// The virtual path of the module defining the command.
+    module: &'static str,
+    loaded: Arc<Mutex<Option<Loaded>>>,
+}
+
+/// The command as parsed from its module.
+#[derive(Clone)]
+struct Loaded {
+    /// The engine state of the first call, with the module merged into it
+    engine_state: EngineState,
+    decl_id: DeclId,
+}
+
+impl LazyCommand {
+    /// The command `name` defined in `source`, the contents of the std `module`. Reports a parse
+    /// error and returns `None` if its signature can't be parsed.
+    pub(crate) fn new(
+        engine_state: &EngineState,
+        name: &str,
+        module: &'static str,
+        source: &str,
+    ) -> Option<Self> {
+        let header = definition_header(source, name)?;
+        let mut working_set = StateWorkingSet::new(engine_state);
+        parse(
+            &mut working_set,
+            Some(module),
+            format!("{header} {{}}").as_bytes(),
+            false,
+        );
+        if let Some(err) = working_set.parse_errors.first() {
+            report_parse_error(None, &working_set, err);
+            return None;
+        }
+
+        let decl_id = working_set.find_decl(name.as_bytes())?;
+        let signature = working_set.get_decl(decl_id).signature();
+        Some(LazyCommand {
+            signature: without_ids(signature),
+            module,
+            loaded: Arc::default(),
+        })
+    }
+
+    /// Parses the module into a copy of `engine_state`, the first time it's needed.
+    fn load(&self, engine_state: &EngineState, span: Span) -> Result<Loaded, ShellError> {
+        let mut loaded = self.loaded.lock().map_err(|_| ShellError::GenericError {
+            error: format!("Failed to load {}", self.name()),
+            msg: "another call failed while loading it".into(),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        })?;
+        if let Some(loaded) = &*loaded {
+            return Ok(loaded.clone());
+        }
+
+        let name = self.name();
+        let mut engine_state = engine_state.clone();
+        let (decl_id, delta) = {
+            let mut working_set = StateWorkingSet::new(&engine_state);
+            // Resolve the module from the std virtual directory, like the prelude at startup
+            working_set.files = FileStack::with_file(PathBuf::from("load std/prelude"));
+            parse(
+                &mut working_set,
+                Some(&format!("loading {name}")),
+                format!("use {} [{name}]", self.module).as_bytes(),
+                false,
+            );
+            working_set.files.pop();
+
+            let failed = |msg: String| ShellError::GenericError {
+                error: format!("Failed to load {name}"),
+                msg,
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            };
+            if let Some(err) = working_set.parse_errors.first() {
+                return Err(failed(err.to_string()));
+            }
+            let decl_id = working_set
+                .find_decl(name.as_bytes())
+                .ok_or_else(|| failed(format!("{} doesn't define {name}", self.module)))?;
+            (decl_id, working_set.render())
+        };
+        engine_state.merge_delta(delta)?;
+
+        Ok(loaded
+            .insert(Loaded {
+                engine_state,
+                decl_id,
+            })
+            .clone())
+    }
+}
+
+impl Command for LazyCommand {
+    fn name(&self) -> &str {
+        &self.signature.name
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature.clone()
+    }
+
+    fn description(&self) -> &str {
+        &self.signature.description
+    }
+
+    fn extra_description(&self) -> &str {
+        &self.signature.extra_description
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        self.signature
+            .search_terms
+            .iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let Loaded {
+            engine_state: mut loaded_state,
+            decl_id,
+        } = self.load(engine_state, head)?;
+        // Only the definitions come from the first call, the rest is as of this one
+        loaded_state.env_vars = engine_state.env_vars.clone();
+        loaded_state.previous_env_vars = engine_state.previous_env_vars.clone();
+        loaded_state.config = engine_state.config.clone();
+        loaded_state.file = engine_state.file.clone();
+        loaded_state.current_job = engine_state.current_job.clone();
+        loaded_state.set_startup_time(engine_state.get_startup_time());
+
+        let decl = loaded_state.get_decl(decl_id);
+        let signature = decl.signature();
+        let block = decl
+            .block_id()
+            .map(|block_id| loaded_state.get_block(block_id))
+            .ok_or_else(|| ShellError::NushellFailedSpanned {
+                msg: format!("{} isn't a custom command", self.name()),
+                label: "lazily loaded here".into(),
+                span: head,
+            })?;
+
+        // The arguments are evaluated by the caller, and bound to the parameters of the command
+        let mut callee_stack = stack.gather_captures(&loaded_state, &block.captures);
+        let positionals = signature
+            .required_positional
+            .iter()
+            .chain(&signature.optional_positional);
+        for (index, param) in positionals.enumerate() {
+            let value = match call.opt(engine_state, stack, index)? {
+                Some(value) => value,
+                None => default_value(
+                    &loaded_state,
+                    &mut callee_stack,
+                    &param.default_value,
+                    param.default_block,
+                    head,
+                )?,
+            };
+            bind(&mut callee_stack, param.var_id, value);
+        }
+        if let Some(rest) = &signature.rest_positional {
+            let start = signature.required_positional.len() + signature.optional_positional.len();
+            let values = call.rest(engine_state, stack, start)?;
+            bind(&mut callee_stack, rest.var_id, Value::list(values, head));
+        }
+        for flag in &signature.named {
+            let value = if flag.arg.is_none() {
+                Value::bool(call.has_flag(engine_state, stack, &flag.long)?, head)
+            } else {
+                match call.get_flag(engine_state, stack, &flag.long)? {
+                    Some(value) => value,
+                    None => default_value(
+                        &loaded_state,
+                        &mut callee_stack,
+                        &flag.default_value,
+                        flag.default_block,
+                        head,
+                    )?,
+                }
+            };
+            bind(&mut callee_stack, flag.var_id, value);
+        }
+
+        eval_block_with_early_return::<WithoutDebug>(&loaded_state, &mut callee_stack, block, input)
+            .map(|data| data.body)
+    }
+}
+
+fn bind(stack: &mut Stack, var_id: Option<VarId>, value: Value) {
+    if let Some(var_id) = var_id {
+        stack.add_var(var_id, value);
+    }
+}
+
+/// The default value of a parameter that wasn't given, computing it if it isn't a constant.
+fn default_value(
+    engine_state: &EngineState,
+    callee_stack: &mut Stack,
+    value: &Option<Value>,
+    block_id: Option<BlockId>,
+    span: Span,
+) -> Result<Value, ShellError> {
+    match (block_id, value) {
+        (Some(block_id), _) => {
+            let block = engine_state.get_block(block_id);
+            eval_block::<WithoutDebug>(engine_state, callee_stack, block, PipelineData::empty())?
+                .body
+                .into_value(span)
+        }
+        (None, Some(value)) => Ok(value.clone()),
+        (None, None) => Ok(Value::nothing(span)),
+    }
+}
+
+/// The definition of the exported command `name` in `source` up to its body, with the comments
+/// and attributes before it, so its signature can be parsed without the rest of the module.
+fn definition_header(source: &str, name: &str) -> Option<String> {
+    let (tokens, _) = lex(source.as_bytes(), 0, &[], &[], true);
+    let text = |index: usize| {
+        tokens
+            .get(index)
+            .filter(|token| token.contents == TokenContents::Item)
+            .map(|token| &source[token.span.start..token.span.end])
+    };
+    let export = (0..tokens.len()).find(|&index| {
+        text(index) == Some("export")
+            && text(index + 1) == Some("def")
+            && text(index + 2) == Some(name)
+    })?;
+    let body = (export + 3..tokens.len())
+        .find(|&index| text(index).is_some_and(|text| text.starts_with('{')))?;
+
+    let before = source[..tokens[export].span.start].trim_end_matches([' ', '\t']);
+    let lines: Vec<&str> = before.lines().collect();
+    let attached = lines
+        .iter()
+        .rev()
+        .take_while(|line| line.trim_start().starts_with(['#', '@']))
+        .count();
+    let definition = &source[tokens[export + 1].span.start..tokens[body].span.start];
+    Some(format!(
+        "{}\n{definition}",
+        lines[lines.len() - attached..].join("\n")
+    ))
+}
+
+/// The signature without the ids it had in the working set it was parsed in.
+fn without_ids(mut signature: Signature) -> Signature {
+    signature
+        .required_positional
+        .iter_mut()
+        .chain(&mut signature.optional_positional)
+        .chain(&mut signature.rest_positional)
+        .for_each(|param| {
+            param.var_id = None;
+            param.default_block = None;
+            if matches!(param.completion, Some(Completion::Command(_))) {
+                param.completion = None;
+            }
+        });
+    for flag in &mut signature.named {
+        flag.var_id = None;
+        flag.default_block = None;
+        if matches!(flag.completion, Some(Completion::Command(_))) {
+            flag.completion = None;
+        }
+    }
+    signature
+}
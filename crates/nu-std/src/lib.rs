@@ -1,4 +1,5 @@
 #![doc = include_str!("../README.md")]
+use lazy::LazyCommand;
 use log::trace;
 use nu_parser::parse;
 use nu_protocol::{
@@ -8,6 +9,8 @@ use nu_protocol::{
 };
 use std::path::PathBuf;
 
+mod lazy;
+
 fn create_virt_file(working_set: &mut StateWorkingSet, name: &str, content: &str) -> VirtualPathId {
     let sanitized_name = PathBuf::from(name).to_string_lossy().to_string();
     let file_id = working_set.add_file(sanitized_name.clone(), content.as_bytes());
@@ -33,15 +36,23 @@ pub fn load_standard_library(
     );
     std_virt_paths.push(std_mod_virt_file_id);
 
+    // std/prelude, loaded at startup - Not technically part of std
+    // Split into files, so that only the commands needed right away are parsed at startup
+    let prelude_files = [
+        ("mod.nu", include_str!("../std/prelude/mod.nu")),
+        ("banner.nu", include_str!("../std/prelude/banner.nu")),
+        ("pwd.nu", include_str!("../std/prelude/pwd.nu")),
+    ]
+    .map(|(filename, content)| {
+        let name = PathBuf::from("std/prelude").join(filename);
+        create_virt_file(&mut working_set, &name.to_string_lossy(), content)
+    });
+    let prelude_dir = PathBuf::from("std/prelude").to_string_lossy().to_string();
+    std_virt_paths
+        .push(working_set.add_virtual_path(prelude_dir, VirtualPath::Dir(prelude_files.to_vec())));
+
     // Submodules/subdirectories ... std/<module>/mod.nu
     let mut std_submodules = vec![
-        // Loaded at startup - Not technically part of std
-        (
-            "mod.nu",
-            "std/prelude",
-            include_str!("../std/prelude/mod.nu"),
-        ),
-        // std submodules
         ("mod.nu", "std/assert", include_str!("../std/assert/mod.nu")),
         ("mod.nu", "std/bench", include_str!("../std/bench/mod.nu")),
         ("mod.nu", "std/dirs", include_str!("../std/dirs/mod.nu")),
@@ -155,11 +166,22 @@ pub fn load_standard_library(
     let std_rfc_dir = PathBuf::from("std-rfc").to_string_lossy().to_string();
     let _ = working_set.add_virtual_path(std_rfc_dir, VirtualPath::Dir(std_rfc_virt_paths));
 
+    // Commands of the prelude that are parsed only when they're called, as their modules take
+    // a while to parse
+    if let Some(banner) = LazyCommand::new(
+        engine_state,
+        "banner",
+        "std/prelude/banner.nu",
+        include_str!("../std/prelude/banner.nu"),
+    ) {
+        working_set.add_decl(Box::new(banner));
+    }
+
     // Load prelude
     let (_, delta) = {
         let source = r#"
 # Prelude
-use std/prelude *
+use std/prelude/pwd.nu *
 "#;
 
         // Add a placeholder file to the stack of files being evaluated.
@@ -1,7 +1,14 @@
-use crate::{TableOutput, TableTheme, clean_charset, colorize_space_str, string_wrap};
+use crate::{
+    TableOutput, TableTheme, clean_charset, colorize_space_str, string_truncate, string_width,
+    string_wrap,
+};
 use nu_color_config::{Alignment, StyleComputer, TextStyle};
-use nu_protocol::{Config, FooterMode, ShellError, Span, TableMode, TrimStrategy, Value};
-use nu_utils::terminal_size;
+use nu_protocol::{
+    Config, FooterMode, ShellError, Span, TableColumnAlignment, TableColumnConfig, TableMode,
+    TrimStrategy, Value,
+};
+use nu_utils::{get_system_locale, terminal_size};
+use std::sync::OnceLock;
 
 pub type NuText = (String, TextStyle);
 pub type TableResult = Result<Option<TableOutput>, ShellError>;
@@ -16,7 +23,7 @@ pub fn configure_table(
     mode: TableMode,
 ) {
     let with_footer = is_footer_needed(config, out);
-    let theme = load_theme(mode);
+    let theme = load_theme(mode, config);
 
     out.table.set_theme(theme);
     out.table
@@ -56,6 +63,10 @@ pub fn nu_value_to_string_colored(val: &Value, cfg: &Config, comp: &StyleCompute
 }
 
 pub fn nu_value_to_string(val: &Value, cfg: &Config, style: &StyleComputer) -> NuText {
+    if let Some(text) = format_number(val, None, cfg) {
+        return (text, style.style_primitive(val));
+    }
+
     let float_precision = cfg.float_precision as usize;
     let text = val.to_abbreviated_string(cfg);
     make_styled_value(text, val, float_precision, style)
@@ -83,6 +94,170 @@ pub fn wrap_text(text: &str, width: usize, config: &Config) -> String {
     string_wrap(text, width, keep_words)
 }
 
+/// The columns of a table that aren't hidden by `$env.config.table.columns`.
+///
+/// If all of them are hidden, they are all kept.
+pub fn visible_columns(headers: Vec<String>, config: &Config) -> Vec<String> {
+    let is_hidden = |header: &String| {
+        config
+            .table
+            .columns
+            .get(header)
+            .is_some_and(|column| column.hidden)
+    };
+
+    if headers.iter().all(is_hidden) {
+        return headers;
+    }
+
+    headers.into_iter().filter(|h| !is_hidden(h)).collect()
+}
+
+/// Applies the `$env.config.table.columns` width and alignment of a column to one of its cells.
+pub fn apply_column_config(text: NuText, header: &str, config: &Config) -> NuText {
+    let Some(column) = config.table.columns.get(header) else {
+        return text;
+    };
+
+    let (mut text, mut style) = text;
+    if let Some(width) = column.max_width
+        && string_width(&text) > width
+    {
+        text = match column.trim.as_ref().unwrap_or(&config.table.trim) {
+            TrimStrategy::Wrap { try_to_keep_words } => {
+                string_wrap(&text, width, *try_to_keep_words)
+            }
+            TrimStrategy::Truncate { suffix } => {
+                truncate_with_suffix(&text, width, suffix.as_deref())
+            }
+        };
+    }
+
+    if let Some(alignment) = column.alignment {
+        style = style.alignment(match alignment {
+            TableColumnAlignment::Left => Alignment::Left,
+            TableColumnAlignment::Center => Alignment::Center,
+            TableColumnAlignment::Right => Alignment::Right,
+        });
+    }
+
+    (text, style)
+}
+
+/// Formats an int or float by `$env.config.table.numbers`, and the decimal places and currency of
+/// its column.
+///
+/// Returns `None` when the number is shown the usual way.
+pub fn format_number(
+    val: &Value,
+    column: Option<&TableColumnConfig>,
+    config: &Config,
+) -> Option<String> {
+    let (number, int) = match *val {
+        Value::Int { val, .. } => (val as f64, Some(val)),
+        Value::Float { val, .. } if val.is_finite() => (val, None),
+        _ => return None,
+    };
+
+    let format = &config.table.numbers;
+    let decimals = column.and_then(|column| column.decimals);
+    let currency = column.and_then(|column| column.currency.as_deref());
+    let magnitude = number.abs();
+    let scientific = format
+        .scientific_above
+        .is_some_and(|above| magnitude >= above)
+        || format
+            .scientific_below
+            .is_some_and(|below| magnitude != 0.0 && magnitude < below);
+    let (thousands_separator, decimal_separator) = number_separators(config);
+
+    let is_default = !scientific
+        && decimals.is_none()
+        && currency.is_none()
+        && !format.group_digits
+        && (int.is_some() || decimal_separator == ".");
+    if is_default {
+        return None;
+    }
+
+    // Currencies are shown with cents, unless told otherwise
+    let decimals = decimals.or(currency.map(|_| 2));
+    let digits = if scientific {
+        let precision = decimals.unwrap_or(config.float_precision.max(0) as usize);
+        format!("{magnitude:.precision$e}").replacen('.', decimal_separator, 1)
+    } else {
+        let (integer, fraction) = match (int, decimals) {
+            (Some(int), None) => (int.unsigned_abs().to_string(), String::new()),
+            (Some(int), Some(decimals)) => (int.unsigned_abs().to_string(), "0".repeat(decimals)),
+            (None, decimals) => {
+                let precision = decimals.unwrap_or(config.float_precision.max(0) as usize);
+                let text = format!("{magnitude:.precision$}");
+                match text.split_once('.') {
+                    Some((integer, fraction)) => (integer.to_string(), fraction.to_string()),
+                    None => (text, String::new()),
+                }
+            }
+        };
+
+        let mut digits = if format.group_digits {
+            group_digits(&integer, thousands_separator)
+        } else {
+            integer
+        };
+        if !fraction.is_empty() {
+            digits.push_str(decimal_separator);
+            digits.push_str(&fraction);
+        }
+        digits
+    };
+
+    let sign = if number < 0.0 { "-" } else { "" };
+    Some(format!("{sign}{}{digits}", currency.unwrap_or_default()))
+}
+
+/// The thousands and decimal separators of `$env.config.table.numbers`.
+fn number_separators(config: &Config) -> (&str, &str) {
+    // Looking up the locale isn't cheap, and it's needed for every cell
+    static LOCALE_SEPARATORS: OnceLock<(&str, &str)> = OnceLock::new();
+
+    let format = &config.table.numbers;
+    let (thousands, decimal) = if format.locale {
+        *LOCALE_SEPARATORS.get_or_init(|| {
+            let locale = get_system_locale();
+            (locale.separator(), locale.decimal())
+        })
+    } else {
+        (",", ".")
+    };
+
+    (
+        format.thousands_separator.as_deref().unwrap_or(thousands),
+        format.decimal_separator.as_deref().unwrap_or(decimal),
+    )
+}
+
+fn group_digits(digits: &str, separator: &str) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn truncate_with_suffix(text: &str, width: usize, suffix: Option<&str>) -> String {
+    match suffix {
+        Some(suffix) if string_width(suffix) < width => {
+            let mut text = string_truncate(text, width - string_width(suffix));
+            text.push_str(suffix);
+            text
+        }
+        _ => string_truncate(text, width),
+    }
+}
+
 pub fn get_header_style(style_computer: &StyleComputer) -> TextStyle {
     TextStyle::with_style(
         Alignment::Center,
@@ -108,6 +283,10 @@ pub fn get_leading_trailing_space_style(style_computer: &StyleComputer) -> TextS
 }
 
 pub fn get_value_style(value: &Value, config: &Config, style_computer: &StyleComputer) -> NuText {
+    if let Some(text) = format_number(value, None, config) {
+        return (text, style_computer.style_primitive(value));
+    }
+
     match value {
         // Float precision is required here.
         Value::Float { val, .. } => (
@@ -168,7 +347,7 @@ fn convert_with_precision(val: &str, precision: usize) -> Result<String, ShellEr
     Ok(format!("{val_float:.precision$}"))
 }
 
-pub fn load_theme(mode: TableMode) -> TableTheme {
+pub fn load_theme(mode: TableMode, config: &Config) -> TableTheme {
     match mode {
         TableMode::Basic => TableTheme::basic(),
         TableMode::Thin => TableTheme::thin(),
@@ -188,6 +367,15 @@ pub fn load_theme(mode: TableMode) -> TableTheme {
         TableMode::BasicCompact => TableTheme::basic_compact(),
         TableMode::Single => TableTheme::single(),
         TableMode::Double => TableTheme::double(),
+        TableMode::Custom => {
+            let custom = &config.table.custom_theme;
+            // The config doesn't allow a custom base, but it'd never end if one got through
+            let base = match custom.base {
+                TableMode::Custom => TableMode::default(),
+                base => base,
+            };
+            TableTheme::custom(load_theme(base, config), custom)
+        }
     }
 }
 
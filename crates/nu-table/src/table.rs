@@ -807,17 +807,25 @@ fn load_theme(
 ) {
     let with_header = table.count_rows() > 1 && structure.with_header;
     let with_footer = with_header && structure.with_footer;
-    let mut theme = theme.as_base().clone();
+    let mut base = theme.as_base().clone();
 
     if !with_header {
-        let borders = *theme.get_borders();
-        theme.remove_horizontal_lines();
-        theme.set_borders(borders);
+        let borders = *base.get_borders();
+        base.remove_horizontal_lines();
+        base.set_borders(borders);
     } else if with_footer {
-        theme_copy_horizontal_line(&mut theme, 1, table.count_rows() - 1);
+        theme_copy_horizontal_line(&mut base, 1, table.count_rows() - 1);
     }
 
-    table.with(theme);
+    if let Some((every, line)) = theme.row_separator() {
+        let first_row = with_header as usize;
+        let last_row = table.count_rows() - with_footer as usize;
+        for row in (first_row + every..last_row).step_by(every) {
+            base.insert_horizontal_line(row, line);
+        }
+    }
+
+    table.with(base);
 
     if let Some(style) = sep_color {
         let color = convert_style(style);
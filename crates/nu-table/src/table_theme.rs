@@ -1,12 +1,18 @@
-use tabled::settings::{
-    style::{HorizontalLine, Style},
-    themes::Theme,
+use nu_protocol::TableCustomTheme;
+use tabled::{
+    grid::config::HorizontalLine as GridLine,
+    settings::{
+        style::{HorizontalLine, Style},
+        themes::Theme,
+    },
 };
 
 #[derive(Debug, Clone)]
 pub struct TableTheme {
     base: Theme,
     full: Theme,
+    /// A line drawn between the rows after every so many of them.
+    row_separator: Option<(usize, GridLine<char>)>,
 }
 
 impl TableTheme {
@@ -14,6 +20,69 @@ impl TableTheme {
         Self {
             base: base.into(),
             full: full.into(),
+            row_separator: None,
+        }
+    }
+
+    /// A theme of user-given characters, with the rest taken from `base`.
+    pub fn custom(base: TableTheme, custom: &TableCustomTheme) -> TableTheme {
+        let chars = &custom.chars;
+        let mut borders = *base.full.get_borders();
+        borders.top = chars.top.or(borders.top);
+        borders.bottom = chars.bottom.or(borders.bottom);
+        borders.left = chars.left.or(borders.left);
+        borders.right = chars.right.or(borders.right);
+        borders.horizontal = chars.horizontal.or(borders.horizontal);
+        borders.vertical = chars.vertical.or(borders.vertical);
+        borders.top_left = chars.corner_top_left.or(borders.top_left);
+        borders.top_right = chars.corner_top_right.or(borders.top_right);
+        borders.bottom_left = chars.corner_bottom_left.or(borders.bottom_left);
+        borders.bottom_right = chars.corner_bottom_right.or(borders.bottom_right);
+        borders.top_intersection = chars.intersection_top.or(borders.top_intersection);
+        borders.bottom_intersection = chars.intersection_bottom.or(borders.bottom_intersection);
+        borders.left_intersection = chars.intersection_left.or(borders.left_intersection);
+        borders.right_intersection = chars.intersection_right.or(borders.right_intersection);
+        borders.intersection = chars.intersection.or(borders.intersection);
+
+        if custom.outer_border_only {
+            borders.vertical = None;
+            borders.top_intersection = None;
+            borders.bottom_intersection = None;
+            borders.intersection = None;
+        }
+
+        let line = borders.horizontal.map(|main| {
+            GridLine::new(
+                Some(main),
+                borders.intersection,
+                borders.left_intersection,
+                borders.right_intersection,
+            )
+        });
+
+        let mut full = Theme::from_style(Style::empty());
+        full.set_borders(borders);
+
+        let mut base = full.clone();
+        let mut row_borders = borders;
+        row_borders.horizontal = None;
+        row_borders.intersection = None;
+        row_borders.left_intersection = None;
+        row_borders.right_intersection = None;
+        base.set_borders(row_borders);
+        if let Some(line) = line.filter(|_| custom.header_separator) {
+            base.insert_horizontal_line(1, line);
+        }
+
+        let row_separator = match (custom.row_separator_every, line) {
+            (Some(every), Some(line)) if !custom.outer_border_only => Some((every, line)),
+            _ => None,
+        };
+
+        Self {
+            base,
+            full,
+            row_separator,
         }
     }
 
@@ -186,4 +255,8 @@ impl TableTheme {
     pub fn as_base(&self) -> &Theme {
         &self.base
     }
+
+    pub(crate) fn row_separator(&self) -> Option<(usize, GridLine<char>)> {
+        self.row_separator
+    }
 }
@@ -21,7 +21,7 @@ fn collapsed_table(mut value: Value, opts: TableOpts<'_>) -> StringResult {
 
     let mut table = UnstructuredTable::new(value, opts.config);
 
-    let theme = load_theme(opts.mode);
+    let theme = load_theme(opts.mode, opts.config);
     let is_empty = table.truncate(&theme, opts.width);
     if is_empty {
         return Ok(None);
@@ -8,9 +8,10 @@ use tabled::grid::records::vec_records::Cell;
 use crate::{
     NuTable, TableOpts, TableOutput,
     common::{
-        INDEX_COLUMN_NAME, NuText, StringResult, TableResult, check_value, configure_table,
-        error_sign, get_header_style, get_index_style, load_theme, nu_value_to_string,
-        nu_value_to_string_clean, nu_value_to_string_colored, wrap_text,
+        INDEX_COLUMN_NAME, NuText, StringResult, TableResult, apply_column_config, check_value,
+        configure_table, error_sign, format_number, get_header_style, get_index_style, load_theme,
+        nu_value_to_string, nu_value_to_string_clean, nu_value_to_string_colored, visible_columns,
+        wrap_text,
     },
     string_width,
     types::has_index,
@@ -123,7 +124,7 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
         return Ok(None);
     }
 
-    let headers = get_columns(input);
+    let headers = visible_columns(get_columns(input), cfg.opts.config);
     let with_index = has_index(&cfg.opts, &headers);
 
     // The header with the INDEX is removed from the table headers since
@@ -271,6 +272,13 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
         }
 
         let mut available = available_width - extra_width;
+        let max_width = cfg
+            .opts
+            .config
+            .table
+            .columns
+            .get(&header)
+            .and_then(|column| column.max_width);
 
         // We want to reserver some space for next column
         // If we can't fit it in it will be popped anyhow.
@@ -287,6 +295,9 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
                 available -= need_width;
             }
         }
+        if let Some(max_width) = max_width {
+            available = available.min(max_width);
+        }
 
         let mut total_column_rows = 0usize;
         let mut column_width = 0;
@@ -386,7 +397,7 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
 }
 
 fn expanded_table_kv(record: &Record, cfg: Cfg<'_>) -> CellResult {
-    let theme = load_theme(cfg.opts.mode);
+    let theme = load_theme(cfg.opts.mode, cfg.opts.config);
     let theme = theme.as_base();
     let key_width = record
         .columns()
@@ -507,7 +518,19 @@ fn get_key_style(cfg: &Cfg<'_>) -> TextStyle {
 fn expand_entry_with_header(item: &Value, header: &str, cfg: Cfg<'_>) -> CellOutput {
     match item {
         Value::Record { val, .. } => match val.get(header) {
-            Some(val) => expand_entry(val, cfg),
+            Some(val @ (Value::Record { .. } | Value::List { .. })) => expand_entry(val, cfg),
+            Some(val) => {
+                let column = cfg.opts.config.table.columns.get(header);
+                let value = match format_number(val, column, cfg.opts.config) {
+                    Some(text) => (text, cfg.opts.style_computer.style_primitive(val)),
+                    None => {
+                        nu_value_to_string_clean(val, cfg.opts.config, &cfg.opts.style_computer)
+                    }
+                };
+                let value = apply_column_config(value, header, cfg.opts.config);
+                let value = nutext_wrap(value, &cfg);
+                CellOutput::styled(value)
+            }
             None => CellOutput::styled(error_sign(
                 cfg.opts.config.table.missing_value_symbol.clone(),
                 &cfg.opts.style_computer,
@@ -6,8 +6,9 @@ use crate::{
     NuRecordsValue, NuTable, StringResult, TableOpts, TableOutput, TableResult, clean_charset,
     colorize_space,
     common::{
-        INDEX_COLUMN_NAME, NuText, check_value, configure_table, get_empty_style, get_header_style,
-        get_index_style, get_value_style, nu_value_to_string_colored,
+        INDEX_COLUMN_NAME, NuText, apply_column_config, check_value, configure_table,
+        format_number, get_empty_style, get_header_style, get_index_style, get_value_style,
+        nu_value_to_string_colored, visible_columns,
     },
     types::has_index,
 };
@@ -70,7 +71,7 @@ fn create_table(input: Vec<Value>, opts: &TableOpts<'_>) -> TableResult {
         return Ok(None);
     }
 
-    let headers = get_columns(&input);
+    let headers = visible_columns(get_columns(&input), opts.config);
     let with_index = has_index(opts, &headers);
     let with_header = !headers.is_empty();
     let row_offset = opts.index_offset;
@@ -201,16 +202,23 @@ fn create_table_with_no_header_and_index(
 }
 
 fn get_string_value_with_header(item: &Value, header: &str, opts: &TableOpts) -> NuText {
-    match item {
-        Value::Record { val, .. } => match val.get(header) {
-            Some(value) => get_string_value(value, opts),
-            None => get_empty_style(
-                opts.config.table.missing_value_symbol.clone(),
-                &opts.style_computer,
-            ),
+    let value = match item {
+        Value::Record { val, .. } => val.get(header),
+        value => Some(value),
+    };
+    let column = opts.config.table.columns.get(header);
+    let text = match value {
+        Some(value) => match format_number(value, column, opts.config) {
+            Some(text) => (text, opts.style_computer.style_primitive(value)),
+            None => get_string_value(value, opts),
         },
-        value => get_string_value(value, opts),
-    }
+        None => get_empty_style(
+            opts.config.table.missing_value_symbol.clone(),
+            &opts.style_computer,
+        ),
+    };
+
+    apply_column_config(text, header, opts.config)
 }
 
 fn get_string_value(item: &Value, opts: &TableOpts) -> NuText {
@@ -0,0 +1,78 @@
+use nu_protocol::{Config, TableColumnConfig, Value};
+use nu_table::common::format_number;
+
+fn format(val: Value, column: Option<&TableColumnConfig>, config: &Config) -> Option<String> {
+    format_number(&val, column, config)
+}
+
+#[test]
+fn default_format_is_left_alone() {
+    let config = Config::default();
+    assert_eq!(format(Value::test_int(1234567), None, &config), None);
+    assert_eq!(format(Value::test_float(1234.5), None, &config), None);
+    assert_eq!(format(Value::test_string("1234"), None, &config), None);
+}
+
+#[test]
+fn groups_digits() {
+    let mut config = Config::default();
+    config.table.numbers.group_digits = true;
+    assert_eq!(
+        format(Value::test_int(-1234567), None, &config).as_deref(),
+        Some("-1,234,567")
+    );
+    assert_eq!(
+        format(Value::test_float(1234.5), None, &config).as_deref(),
+        Some("1,234.50")
+    );
+    assert_eq!(
+        format(Value::test_int(123), None, &config).as_deref(),
+        Some("123")
+    );
+
+    config.table.numbers.thousands_separator = Some("'".into());
+    config.table.numbers.decimal_separator = Some(",".into());
+    assert_eq!(
+        format(Value::test_float(1234567.126), None, &config).as_deref(),
+        Some("1'234'567,13")
+    );
+}
+
+#[test]
+fn formats_column_decimals_and_currency() {
+    let config = Config::default();
+    let column = TableColumnConfig {
+        decimals: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        format(Value::test_int(5), Some(&column), &config).as_deref(),
+        Some("5.000")
+    );
+
+    let column = TableColumnConfig {
+        currency: Some("$".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        format(Value::test_float(-12.5), Some(&column), &config).as_deref(),
+        Some("-$12.50")
+    );
+}
+
+#[test]
+fn uses_scientific_notation_past_thresholds() {
+    let mut config = Config::default();
+    config.table.numbers.scientific_above = Some(1e6);
+    config.table.numbers.scientific_below = Some(1e-3);
+    assert_eq!(
+        format(Value::test_int(12345678), None, &config).as_deref(),
+        Some("1.23e7")
+    );
+    assert_eq!(
+        format(Value::test_float(-0.00042), None, &config).as_deref(),
+        Some("-4.20e-4")
+    );
+    assert_eq!(format(Value::test_float(0.0), None, &config), None);
+    assert_eq!(format(Value::test_int(999), None, &config), None);
+}
@@ -157,6 +157,31 @@ impl CustomValue for CoolCustomValue {
         }
     }
 
+    fn reverse_operation(
+        &self,
+        left: &Value,
+        operator: ast::Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        match (operator, left) {
+            // Prepend a string to the string inside `cool`
+            (Operator::Math(Math::Concatenate), Value::String { val, .. }) => Ok(Value::custom(
+                Box::new(CoolCustomValue {
+                    cool: format!("{val}{}", self.cool),
+                }),
+                op,
+            )),
+            _ => Err(ShellError::OperatorUnsupportedType {
+                op: operator,
+                unsupported: Type::Custom(self.type_name().into()),
+                op_span: op,
+                unsupported_span: rhs_span,
+                help: None,
+            }),
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
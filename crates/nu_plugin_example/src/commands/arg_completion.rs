@@ -106,10 +106,13 @@ impl PluginCommand for ArgCompletion {
                             .collect(),
                     )
                 } else if index == 1 {
+                    // The text typed so far can be used to only generate matching values
                     Some(
                         (since_the_epoch..since_the_epoch + 10)
-                            .map(|s| DynamicSuggestion {
-                                value: format!("arg1:{s}"),
+                            .map(|s| format!("arg1:{s}"))
+                            .filter(|value| value.starts_with(&call.prefix))
+                            .map(|value| DynamicSuggestion {
+                                value,
                                 ..Default::default()
                             })
                             .collect(),
@@ -0,0 +1,95 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, LabeledError, PipelineData, ShellError, Signature,
+    SyntaxShape, Type, Value,
+};
+
+use crate::ExamplePlugin;
+
+/// `<list> | example map [--env] { |value| ... }`
+pub struct Map;
+
+impl PluginCommand for Map {
+    type Plugin = ExamplePlugin;
+
+    fn name(&self) -> &str {
+        "example map"
+    }
+
+    fn description(&self) -> &str {
+        "Example execution of a closure for each input value, like `each`"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The closure can use the caller's environment. With --env, changes the closure makes to \
+        the environment are kept after the command, like `do --env`."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(Type::list(Type::Any), Type::list(Type::Any))
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "The closure to run for each input value.",
+            )
+            .switch(
+                "env",
+                "Keep changes the closure makes to the environment.",
+                None,
+            )
+            .category(Category::Experimental)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "[1 2 3] | example map { |n| $n * 2 }",
+                description: "Double each number",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(2),
+                    Value::test_int(4),
+                    Value::test_int(6),
+                ])),
+            },
+            Example {
+                example: "[1 2 3] | example map --env { |n| $env.TOTAL = ($env.TOTAL? | default 0) + $n }; $env.TOTAL",
+                description: "Sum numbers into an environment variable",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &ExamplePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+        let closure = call.req(0)?;
+        let keep_env = call.has_flag("env")?;
+        // The results are collected before returning, because changes to the environment can only
+        // be kept while the command is running
+        let values = input
+            .into_iter()
+            .map(|value| {
+                let input = PipelineData::value(value.clone(), None);
+                let output = if keep_env {
+                    engine.eval_closure_with_env(&closure, vec![value], input, true, false)?
+                } else {
+                    engine.eval_closure_with_stream(&closure, vec![value], input, true, false)?
+                };
+                output.into_value(head)
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+        Ok(Value::list(values, head).into_pipeline_data())
+    }
+}
+
+#[test]
+fn test_examples() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+    PluginTest::new("example", ExamplePlugin.into())?.test_command_examples(&Map)
+}
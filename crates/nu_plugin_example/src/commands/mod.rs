@@ -32,6 +32,8 @@ mod collect_bytes;
 mod echo;
 mod for_each;
 mod generate;
+mod map;
+mod rot13;
 mod seq;
 mod sum;
 
@@ -39,6 +41,8 @@ pub use collect_bytes::CollectBytes;
 pub use echo::Echo;
 pub use for_each::ForEach;
 pub use generate::Generate;
+pub use map::Map;
+pub use rot13::Rot13;
 pub use seq::Seq;
 pub use sum::Sum;
 
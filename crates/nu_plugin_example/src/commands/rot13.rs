@@ -0,0 +1,131 @@
+use std::io::Read;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    ByteStream, Category, Example, LabeledError, PipelineData, ShellError, Signature, Type, Value,
+    shell_error::io::IoError,
+};
+
+use crate::ExamplePlugin;
+
+/// `<string> | example rot13`
+pub struct Rot13;
+
+impl PluginCommand for Rot13 {
+    type Plugin = ExamplePlugin;
+
+    fn name(&self) -> &str {
+        "example rot13"
+    }
+
+    fn description(&self) -> &str {
+        "Example byte stream transformer that applies ROT13 to its input as it arrives"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Byte streams are transformed a chunk at a time, without collecting them first, so this \
+        works on input of any size, and output is produced while the input is still being read."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["example", "stream", "cipher"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![
+                (Type::String, Type::String),
+                (Type::Binary, Type::Binary),
+            ])
+            .category(Category::Experimental)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'Hello, world!' | example rot13",
+                description: "apply ROT13 to a string",
+                result: Some(Value::test_string("Uryyb, jbeyq!")),
+            },
+            Example {
+                example: "open --raw secret.txt | example rot13 | save plain.txt",
+                description: "decode a file without reading all of it into memory",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &ExamplePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+        match input {
+            PipelineData::ByteStream(stream, metadata) => {
+                let type_ = stream.type_();
+                let Some(mut reader) = stream.reader() else {
+                    return Ok(PipelineData::empty());
+                };
+
+                let mut buf = vec![0; 8192];
+                let stream =
+                    ByteStream::from_fn(head, engine.signals().clone(), type_, move |out| {
+                        let len = reader
+                            .read(&mut buf)
+                            .map_err(|err| IoError::new(err, head, None))?;
+                        out.extend(buf[..len].iter().copied().map(rot13));
+                        Ok(len > 0)
+                    });
+                Ok(PipelineData::byte_stream(stream, metadata))
+            }
+            input => {
+                let metadata = input.metadata();
+                let value = match input.into_value(head)? {
+                    Value::String { val, .. } => Value::string(
+                        val.chars()
+                            .map(|c| {
+                                if c.is_ascii() {
+                                    rot13(c as u8) as char
+                                } else {
+                                    c
+                                }
+                            })
+                            .collect::<String>(),
+                        head,
+                    ),
+                    Value::Binary { val, .. } => {
+                        Value::binary(val.into_iter().map(rot13).collect::<Vec<u8>>(), head)
+                    }
+                    value => {
+                        return Err(ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "string or binary".into(),
+                            wrong_type: value.get_type().to_string(),
+                            dst_span: head,
+                            src_span: value.span(),
+                        }
+                        .into());
+                    }
+                };
+                Ok(PipelineData::value(value, metadata))
+            }
+        }
+    }
+}
+
+/// Rotate ASCII letters by 13 places, leaving other bytes as they are.
+fn rot13(byte: u8) -> u8 {
+    match byte {
+        b'a'..=b'z' => (byte - b'a' + 13) % 26 + b'a',
+        b'A'..=b'Z' => (byte - b'A' + 13) % 26 + b'A',
+        _ => byte,
+    }
+}
+
+#[test]
+fn test_examples() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+    PluginTest::new("example", ExamplePlugin.into())?.test_command_examples(&Rot13)
+}
@@ -34,6 +34,8 @@ impl Plugin for ExamplePlugin {
             Box::new(Echo),
             Box::new(ForEach),
             Box::new(Generate),
+            Box::new(Map),
+            Box::new(Rot13),
             Box::new(Seq),
             Box::new(Sum),
             // Auto completion demos
@@ -448,6 +448,8 @@ fn convert_to_value(
                         span: expr.span,
                     }),
                 },
+
+                Unit::BitPerSecond => Ok(Value::rate(size, span)),
             }
         }
         Expr::Var(..) => Err(ShellError::OutsideSpannedLabeledError {
@@ -170,6 +170,15 @@ mod tests {
         nuon_end_to_end("60000000000ns", Some(Value::test_duration(60_000_000_000)));
     }
 
+    #[test]
+    fn rate() {
+        nuon_end_to_end("100000000bit/sec", Some(Value::test_rate(100_000_000)));
+        assert_eq!(
+            from_nuon("12.5MB/sec", None).unwrap(),
+            Value::test_rate(100_000_000)
+        );
+    }
+
     #[test]
     fn to_nuon_datetime() {
         nuon_end_to_end(
@@ -218,6 +218,7 @@ fn value_to_string(
         Value::Filesize { val, .. } => Ok(format!("{}b", val.get())),
         Value::Float { val, .. } => Ok(ObviousFloat(*val).to_string()),
         Value::Int { val, .. } => Ok(val.to_string()),
+        Value::Rate { val, .. } => Ok(format!("{}bit/sec", val.get())),
         Value::List { vals, .. } => {
             let headers = get_columns(vals);
             let is_table =
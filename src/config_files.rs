@@ -11,6 +11,7 @@ use nu_protocol::{
 };
 use nu_utils::ConfigFileKind;
 use std::{
+    collections::HashMap,
     fs,
     fs::File,
     io::{Result, Write},
@@ -20,6 +21,7 @@ use std::{
 };
 
 const LOGINSHELL_FILE: &str = "login.nu";
+const AUTOLOAD_MODULE_DIRS_VAR: &str = "NU_AUTOLOAD_MODULE_DIRS";
 
 pub(crate) fn read_config_file(
     engine_state: &mut EngineState,
@@ -202,6 +204,56 @@ pub(crate) fn read_vendor_autoload_files(engine_state: &mut EngineState, stack:
         });
 }
 
+/// Register the modules in the autoload module directories, so the parser can import them when
+/// one of their commands is first used.
+///
+/// Every `*.nu` file and every directory with a `mod.nu` counts as a module, named after the
+/// file stem or the directory. If several directories have a module with the same name, the one
+/// in the later directory wins.
+pub(crate) fn read_autoload_module_dirs(engine_state: &mut EngineState, stack: &Stack) {
+    warn!(
+        "read_autoload_module_dirs() {}:{}:{}",
+        file!(),
+        line!(),
+        column!()
+    );
+
+    let Some(dirs) = engine_state.get_env_var(AUTOLOAD_MODULE_DIRS_VAR) else {
+        return;
+    };
+    let Ok(cwd) = engine_state.cwd(Some(stack)) else {
+        return;
+    };
+
+    let mut modules = HashMap::new();
+    for dir in dirs.clone().into_list().unwrap_or_default() {
+        let Ok(dir) = dir.coerce_into_string() else {
+            continue;
+        };
+        let Ok(autoload_dir) = absolute_with(nu_path::expand_tilde(dir), &cwd) else {
+            continue;
+        };
+        warn!("read_autoload_module_dirs: {}", autoload_dir.display());
+
+        let Ok(entries) = read_and_sort_directory(&autoload_dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = autoload_dir.join(&entry);
+            let name = if let Some(stem) = entry.strip_suffix(".nu") {
+                stem.to_string()
+            } else if path.join("mod.nu").is_file() {
+                entry
+            } else {
+                continue;
+            };
+            modules.insert(name, path);
+        }
+    }
+
+    engine_state.set_autoload_modules(modules);
+}
+
 fn eval_default_config(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -264,6 +316,7 @@ pub(crate) fn setup_config(
         }
         // read and auto load vendor autoload files
         read_vendor_autoload_files(engine_state, stack);
+        read_autoload_module_dirs(engine_state, stack);
     }));
     if result.is_err() {
         eprintln!(
@@ -79,6 +79,8 @@ pub(crate) fn run_commands(
         }
 
         perf!("read login.nu", start_time, use_color);
+
+        config_files::read_autoload_module_dirs(engine_state, &stack);
     }
 
     // Before running commands, set up the startup time
@@ -158,6 +160,8 @@ pub(crate) fn run_file(
             );
         }
         perf!("read config.nu", start_time, use_color);
+
+        config_files::read_autoload_module_dirs(engine_state, &stack);
     }
 
     // Regenerate the $nu constant to contain the startup time and any other potential updates
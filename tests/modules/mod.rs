@@ -943,3 +943,44 @@ fn use_nested_submodules() {
         assert_eq!(actual.out, "true");
     })
 }
+
+#[test]
+fn autoload_module_on_first_use() {
+    Playground::setup("autoload_module_on_first_use", |dirs, sandbox| {
+        sandbox.mkdir("modules/tools").with_files(&[
+            FileWithContent("tmp_env.nu", "$env.NU_AUTOLOAD_MODULE_DIRS = ['modules']"),
+            FileWithContent(
+                "modules/greet.nu",
+                "export def main [] { 'hello' }; export def loud [] { 'HELLO' }",
+            ),
+            FileWithContent("modules/tools/mod.nu", "export def version [] { '1.0' }"),
+        ]);
+
+        let actual = nu!(
+            env_config: "tmp_env.nu",
+            cwd: dirs.test(),
+            "[(greet) (greet loud) (tools version)] | str join ' '",
+        );
+        assert_eq!(actual.out, "hello HELLO 1.0");
+    })
+}
+
+#[test]
+fn autoload_module_does_not_shadow_commands() {
+    Playground::setup(
+        "autoload_module_does_not_shadow_commands",
+        |dirs, sandbox| {
+            sandbox.mkdir("modules").with_files(&[
+                FileWithContent("tmp_env.nu", "$env.NU_AUTOLOAD_MODULE_DIRS = ['modules']"),
+                FileWithContent("modules/greet.nu", "export def main [] { 'hello' }"),
+            ]);
+
+            let actual = nu!(
+                env_config: "tmp_env.nu",
+                cwd: dirs.test(),
+                "def greet [] { 'mine' }; greet",
+            );
+            assert_eq!(actual.out, "mine");
+        },
+    )
+}
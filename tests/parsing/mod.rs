@@ -67,6 +67,59 @@ fn source_const_in_bareword_interpolation() {
     });
 }
 
+#[test]
+fn source_with_arguments() {
+    Playground::setup("source_with_arguments_test", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContentToBeTrimmed(
+            "setup.nu",
+            "
+                $argv | to nuon
+            ",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "const profile = 'dev'; source setup.nu --profile $profile 1"
+        );
+
+        assert_eq!(actual.out, r#"["--profile", dev, 1]"#);
+    });
+}
+
+#[test]
+fn source_argv_is_constant_and_scoped_to_file() {
+    Playground::setup("source_argv_scoped_test", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContentToBeTrimmed(
+            "setup.nu",
+            "
+                const profile = $argv.0
+                def profile [] { $profile }
+            ",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "let argv = 'caller'; source setup.nu prod; [(profile) $argv] | to nuon"
+        );
+
+        assert_eq!(actual.out, "[prod, caller]");
+    });
+}
+
+#[test]
+fn source_arguments_must_be_constant() {
+    Playground::setup("source_arguments_constant_test", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContentToBeTrimmed("setup.nu", "$argv")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "let profile = 'dev'; source setup.nu $profile"
+        );
+
+        assert!(actual.err.contains("constant"));
+    });
+}
+
 // Test edge cases for paths with parentheses
 #[test]
 fn source_path_with_literal_parens() {
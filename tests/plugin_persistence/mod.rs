@@ -374,6 +374,82 @@ fn plugin_gc_can_be_configured_as_disabled() {
     assert_eq!("true", out.out, "with inc-specific config");
 }
 
+#[test]
+#[serial]
+fn plugin_gc_can_stop_plugins_using_too_much_memory() {
+    let out = nu_with_plugins!(
+        cwd: ".",
+        plugin: ("nu_plugin_inc"),
+        r#"
+            $env.config.plugin_gc = {
+                plugins: {
+                    inc: { stop_after: 1hr, max_idle_memory: 1B }
+                }
+            }
+            "2.3.0" | inc -M
+            let start = (date now)
+            mut cond = true
+            while $cond {
+                sleep 100ms
+                $cond = (
+                    (plugin list | where name == inc).0.status == running and
+                    ((date now) - $start) < 5sec
+                )
+            }
+            ((date now) - $start) | into int
+        "#
+    );
+    assert!(out.status.success());
+    let nanos = out.out.parse::<i64>().expect("not a number");
+    assert!(nanos < 5_000_000_000, "more than 5 seconds: {nanos} ns");
+}
+
+#[test]
+#[serial]
+fn plugin_list_shows_memory_and_gc_config() {
+    let out = nu_with_plugins!(
+        cwd: ".",
+        plugin: ("nu_plugin_inc"),
+        r#"
+            $env.config.plugin_gc = {
+                default: { enabled: false }
+                plugins: {
+                    inc: { stop_after: 1min, start: eager }
+                }
+            }
+            "2.3.0" | inc -M
+            let inc = (plugin list | where name == inc).0
+            [($inc.memory > 0B) $inc.gc.enabled $inc.gc.stop_after $inc.gc.start] | to nuon
+        "#
+    );
+    assert!(out.status.success());
+    assert_eq!("[true, true, 1min, eager]", out.out);
+}
+
+#[test]
+#[serial]
+fn plugin_list_shows_metrics() {
+    let out = nu_with_plugins!(
+        cwd: ".",
+        plugin: ("nu_plugin_inc"),
+        r#"
+            "2.3.0" | inc -M
+            "2.3.0" | inc -m
+            let inc = (plugin list --metrics | where name == inc).0
+            [
+                ($inc.metrics.calls >= 2)
+                ($inc.metrics.average_latency > 0sec)
+                ($inc.metrics.bytes_sent > 0B)
+                ($inc.metrics.bytes_received > 0B)
+                ($inc.metrics.uptime > 0sec)
+                ('metrics' in (plugin list | columns))
+            ] | to nuon
+        "#
+    );
+    assert!(out.status.success());
+    assert_eq!("[true, true, true, true, true, false]", out.out);
+}
+
 #[test]
 #[serial]
 fn plugin_gc_can_be_disabled_by_plugin() {
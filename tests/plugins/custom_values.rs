@@ -103,6 +103,17 @@ fn can_get_plugin_custom_value_int_cell_path() {
     assert_eq!(one_index_optional.out, "nothing");
 }
 
+#[test]
+fn can_follow_plugin_custom_value_cell_paths_concurrently() {
+    let actual = nu_with_plugins!(
+        cwd: "tests",
+        plugin: ("nu_plugin_custom_values"),
+        "1..20 | par-each { (custom-value generate).0 } | uniq | to nuon"
+    );
+    assert_eq!(actual.out, r#"["abc"]"#);
+    assert!(actual.status.success());
+}
+
 #[test]
 fn can_get_plugin_custom_value_string_cell_path() {
     let cool = nu_with_plugins!(
@@ -174,6 +185,32 @@ fn can_append_plugin_custom_values() {
     );
 }
 
+#[test]
+fn can_use_operators_with_plugin_custom_values_on_the_right() {
+    let actual = nu_with_plugins!(
+        cwd: "tests",
+        plugin: ("nu_plugin_custom_values"),
+        "'xyz' ++ (custom-value generate)"
+    );
+
+    assert_eq!(
+        actual.out,
+        "I used to be a custom value! My data was (xyzabc)"
+    );
+}
+
+#[test]
+fn unsupported_operators_with_plugin_custom_values_on_the_right_fail() {
+    let actual = nu_with_plugins!(
+        cwd: "tests",
+        plugin: ("nu_plugin_custom_values"),
+        "1 ++ (custom-value generate)"
+    );
+
+    // The error is still the one for the left-hand side
+    assert!(actual.err.contains("does not work on values of type 'int'"));
+}
+
 // There are currently no custom values defined by the engine that aren't hidden behind an extra
 // feature
 #[cfg(feature = "sqlite")]
@@ -63,3 +63,30 @@ fn set_env() {
     assert!(result.status.success());
     assert_eq!("rocks", result.out);
 }
+
+#[test]
+fn closure_sees_caller_env() {
+    let result = nu_with_plugins!(
+        cwd: ".",
+        plugin: ("nu_plugin_example"),
+        "$env.GREETING = 'hi'; [a b] | example map { |name| $'($env.GREETING) ($name)' } | str join ', '"
+    );
+    assert!(result.status.success());
+    assert_eq!("hi a, hi b", result.out);
+}
+
+#[test]
+fn closure_env_changes_are_only_kept_with_env_flag() {
+    let result = nu_with_plugins!(
+        cwd: ".",
+        plugin: ("nu_plugin_example"),
+        r#"
+            [1 2 3] | example map { |n| $env.TOTAL = ($env.TOTAL? | default 0) + $n } | ignore
+            print ($env.TOTAL? | default 'unset')
+            [1 2 3] | example map --env { |n| $env.TOTAL = ($env.TOTAL? | default 0) + $n } | ignore
+            print $env.TOTAL
+        "#
+    );
+    assert!(result.status.success());
+    assert_eq!("unset6", result.out);
+}
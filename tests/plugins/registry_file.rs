@@ -1,6 +1,6 @@
 use std::{fs::File, path::PathBuf};
 
-use nu_protocol::{PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData};
+use nu_protocol::{PluginMetadata, PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData};
 use nu_test_support::{fs::Stub, nu, nu_with_plugins, playground::Playground};
 
 fn example_plugin_path() -> PathBuf {
@@ -171,6 +171,77 @@ fn plugin_add_creates_missing_parent_directories() {
     })
 }
 
+fn minimal_wasm_plugin_path() -> PathBuf {
+    nu_test_support::fs::fixtures()
+        .join("plugins")
+        .join("nu_plugin_minimal.wasm")
+        .into_std_path_buf()
+}
+
+#[test]
+fn plugin_add_wasm_without_runtime() {
+    Playground::setup("plugin add wasm without runtime", |dirs, _| {
+        let result = nu!(
+            cwd: dirs.test(),
+            &format!(
+                "
+                    $env.NU_WASM_RUNTIME = 'nu-missing-wasm-runtime --threads'
+                    plugin add --plugin-config test-plugin-file.msgpackz '{}'
+                ",
+                minimal_wasm_plugin_path().display()
+            )
+        );
+
+        assert!(!result.status.success());
+        assert!(
+            result
+                .err
+                .contains("Can't find the WebAssembly runtime nu-missing-wasm-runtime")
+        );
+    })
+}
+
+#[test]
+fn plugin_add_wasm_rejects_other_files() {
+    Playground::setup("plugin add wasm rejects other files", |dirs, playground| {
+        playground.with_files(&[Stub::FileWithContent("nu_plugin_fake.wasm", "#!/bin/sh")]);
+
+        let result = nu!(
+            cwd: dirs.test(),
+            "plugin add --plugin-config test-plugin-file.msgpackz nu_plugin_fake.wasm"
+        );
+
+        assert!(!result.status.success());
+        assert!(result.err.contains("isn't a WebAssembly module"));
+    })
+}
+
+#[test]
+fn plugin_add_wasm_runs_the_module() {
+    // The module only has an empty `_start`, so it exits before the handshake
+    if std::process::Command::new("wasmtime")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping plugin_add_wasm_runs_the_module: wasmtime isn't in PATH");
+        return;
+    }
+
+    Playground::setup("plugin add wasm runs the module", |dirs, _| {
+        let result = nu!(
+            cwd: dirs.test(),
+            &format!(
+                "plugin add --plugin-config test-plugin-file.msgpackz '{}'",
+                minimal_wasm_plugin_path().display()
+            )
+        );
+
+        assert!(!result.status.success());
+        assert!(result.err.contains("unable to get encoding from plugin"));
+    })
+}
+
 #[test]
 fn plugin_rm_then_restart_nu() {
     let example_plugin_path = example_plugin_path();
@@ -188,6 +259,7 @@ fn plugin_rm_then_restart_nu() {
             name: "example".into(),
             filename: example_plugin_path,
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -196,6 +268,7 @@ fn plugin_rm_then_restart_nu() {
             // this doesn't exist, but it should be ok
             filename: dirs.test().join("nu_plugin_foo").into(),
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -264,6 +337,7 @@ fn plugin_rm_from_custom_path() {
             name: "example".into(),
             filename: example_plugin_path,
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -272,6 +346,7 @@ fn plugin_rm_from_custom_path() {
             // this doesn't exist, but it should be ok
             filename: dirs.test().join("nu_plugin_foo").into(),
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -312,6 +387,7 @@ fn plugin_rm_using_filename() {
             name: "example".into(),
             filename: example_plugin_path.clone(),
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -320,6 +396,7 @@ fn plugin_rm_using_filename() {
             // this doesn't exist, but it should be ok
             filename: dirs.test().join("nu_plugin_foo").into(),
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -370,6 +447,7 @@ fn warning_on_invalid_plugin_item() {
             name: "example".into(),
             filename: example_plugin_path,
             shell: None,
+            address: None,
             data: valid_plugin_item_data(),
         });
 
@@ -378,6 +456,7 @@ fn warning_on_invalid_plugin_item() {
             // this doesn't exist, but it should be ok
             filename: dirs.test().join("nu_plugin_badtest").into(),
             shell: None,
+            address: None,
             data: PluginRegistryItemData::Invalid,
         });
 
@@ -551,3 +630,202 @@ fn plugin_add_then_use_with_custom_path() {
         assert_eq!(r#"["example"]"#, result_use.out);
     })
 }
+
+#[test]
+fn plugin_update_finds_moved_plugin_and_refreshes_signatures() {
+    let example_plugin_path = example_plugin_path();
+    let dirname = example_plugin_path.parent().expect("no parent");
+    let file_name = example_plugin_path.file_name().expect("no file_name");
+
+    Playground::setup("plugin update", |dirs, _playground| {
+        let file = File::create(dirs.test().join("test-plugin-file.msgpackz"))
+            .expect("failed to create file");
+        let mut contents = PluginRegistryFile::new();
+
+        contents.upsert_plugin(PluginRegistryItem {
+            name: "example".into(),
+            // this was moved since it was added
+            filename: dirs.test().join("moved").join(file_name).into(),
+            shell: None,
+            address: None,
+            data: PluginRegistryItemData::Valid {
+                metadata: PluginMetadata::new().with_version("0.0.1"),
+                commands: vec![],
+            },
+        });
+
+        contents
+            .write_to(file, None)
+            .expect("failed to write plugin file");
+
+        let result = nu!(
+            cwd: dirs.test(),
+            &format!(
+                "
+                    $env.NU_PLUGIN_DIRS = ['{}']
+                    plugin update --plugin-config test-plugin-file.msgpackz example
+                    | select name old_version new_version
+                    | to json --raw
+                ",
+                dirname.display()
+            )
+        );
+
+        assert!(result.status.success());
+        assert_eq!(
+            format!(
+                r#"[{{"name":"example","old_version":"0.0.1","new_version":"{}"}}]"#,
+                env!("CARGO_PKG_VERSION")
+            ),
+            result.out
+        );
+
+        let contents = PluginRegistryFile::read_from(
+            File::open(dirs.test().join("test-plugin-file.msgpackz"))
+                .expect("failed to open plugin file"),
+            None,
+        )
+        .expect("failed to read plugin file");
+
+        assert_eq!(1, contents.plugins.len());
+        assert_eq!(example_plugin_path, contents.plugins[0].filename);
+        let PluginRegistryItemData::Valid { commands, .. } = &contents.plugins[0].data else {
+            panic!("plugin data is invalid");
+        };
+        assert!(!commands.is_empty());
+    })
+}
+
+#[test]
+fn plugin_requiring_newer_nu_version_is_not_loaded() {
+    let example_plugin_path = example_plugin_path();
+    Playground::setup("plugin min nu version", |dirs, playground| {
+        playground.with_files(&[
+            Stub::FileWithContent("config.nu", ""),
+            Stub::FileWithContent("env.nu", ""),
+        ]);
+
+        let file = File::create(dirs.test().join("test-plugin-file.msgpackz"))
+            .expect("failed to create file");
+        let mut contents = PluginRegistryFile::new();
+
+        contents.upsert_plugin(PluginRegistryItem {
+            name: "example".into(),
+            filename: example_plugin_path,
+            shell: None,
+            address: None,
+            data: PluginRegistryItemData::Valid {
+                metadata: PluginMetadata::new().with_min_nu_version("999.0.0"),
+                commands: vec![],
+            },
+        });
+
+        contents
+            .write_to(file, None)
+            .expect("failed to write plugin file");
+
+        let result = assert_cmd::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args([
+                "--no-std-lib",
+                "--config",
+                "config.nu",
+                "--env-config",
+                "env.nu",
+                "--plugin-config",
+                "test-plugin-file.msgpackz",
+                "--commands",
+                "plugin list --engine | get name | to json --raw",
+            ])
+            .assert()
+            .success()
+            .stdout("[]\n");
+
+        let stderr = String::from_utf8_lossy(&result.get_output().stderr);
+        assert!(stderr.contains("requires Nushell 999.0.0 or newer"));
+    })
+}
+
+/// Start the example plugin listening on a free loopback port with `secret`, and return it with
+/// its address once it's listening.
+fn start_remote_example_plugin(secret: &str) -> (std::process::Child, Option<String>) {
+    // Find a free port for the plugin to listen on
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("failed to find a free port")
+        .port();
+    let address = format!("tcp://127.0.0.1:{port}");
+
+    let plugin = std::process::Command::new(example_plugin_path())
+        .args(["--listen", &address])
+        .env("NU_PLUGIN_SECRET", secret)
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("failed to start plugin");
+
+    // Wait for the plugin to start listening
+    for _ in 0..100 {
+        if std::net::TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            return (plugin, Some(address));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    (plugin, None)
+}
+
+#[test]
+fn plugin_add_remote_then_restart_nu() {
+    let (mut plugin, address) = start_remote_example_plugin("test-secret");
+
+    let result = address.map(|address| {
+        nu_with_plugins!(
+            cwd: ".",
+            plugins: [],
+            &format!("
+                $env.NU_PLUGIN_SECRET = 'test-secret'
+                plugin add --address '{address}' nu_plugin_example
+                (
+                    ^$nu.current-exe
+                        --config $nu.config-path
+                        --env-config $nu.env-path
+                        --plugin-config $nu.plugin-path
+                        --commands '[1 2 3] | example sum'
+                )
+            ")
+        )
+    });
+
+    let _ = plugin.kill();
+    let _ = plugin.wait();
+
+    let result = result.expect("plugin didn't start listening");
+    assert!(result.status.success(), "{}", result.err);
+    assert_eq!("6", result.out);
+}
+
+#[test]
+fn plugin_add_remote_with_wrong_secret() {
+    let (mut plugin, address) = start_remote_example_plugin("test-secret");
+
+    let result = address.map(|address| {
+        nu_with_plugins!(
+            cwd: ".",
+            plugins: [],
+            &format!("
+                $env.NU_PLUGIN_SECRET = 'wrong-secret'
+                plugin add --address '{address}' nu_plugin_example
+            ")
+        )
+    });
+
+    let _ = plugin.kill();
+    let _ = plugin.wait();
+
+    let result = result.expect("plugin didn't start listening");
+    assert!(!result.status.success());
+    assert!(
+        result.err.contains("refused the connection"),
+        "{}",
+        result.err
+    );
+}
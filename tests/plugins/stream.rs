@@ -207,3 +207,57 @@ fn echo_interactivity_on_slow_pipelines() {
     );
     assert_eq!(actual.out, "0");
 }
+
+#[test]
+fn rot13_transforms_byte_stream() {
+    let actual = nu_with_plugins!(
+        cwd: "tests/fixtures/formats",
+        plugin: ("nu_plugin_example"),
+        "[Hello ', ' world] | example collect-bytes | example rot13 | describe"
+    );
+
+    assert_eq!(actual.out, "byte stream");
+
+    let actual = nu_with_plugins!(
+        cwd: "tests/fixtures/formats",
+        plugin: ("nu_plugin_example"),
+        "[Hello ', ' world] | example collect-bytes | example rot13 | collect"
+    );
+
+    assert_eq!(actual.out, "Uryyb, jbeyq");
+}
+
+#[test]
+fn rot13_big_stream() {
+    // The output stream is written while the input stream is still being read, which mustn't
+    // deadlock when both are bigger than the buffers in between
+    let actual = nu_with_plugins!(
+        cwd: "tests/fixtures/formats",
+        plugin: ("nu_plugin_example"),
+        r#"(
+            seq 1 10000 |
+                each {|i| ($i | into string) ++ (char newline) } |
+                example collect-bytes |
+                example rot13 |
+                example rot13 |
+                lines |
+                last
+        )"#
+    );
+
+    assert_eq!(actual.out, "10000");
+}
+
+#[rstest]
+#[serial]
+#[timeout(std::time::Duration::from_secs(6))]
+fn rot13_interactivity_on_slow_byte_streams() {
+    // Like `echo_interactivity_on_slow_pipelines`, but for a byte stream going through the plugin.
+    // The first byte has to come out of `example rot13` before the rest of its input exists.
+    let actual = nu_with_plugins!(
+        cwd: "tests/fixtures/formats",
+        plugin: ("nu_plugin_example"),
+        r#"[0x[42]] | each { |b| sleep 10sec; $b } | prepend 0x[4e] | example collect-bytes | example rot13 | first"#
+    );
+    assert_eq!(actual.out, "65");
+}
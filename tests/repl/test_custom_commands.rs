@@ -225,7 +225,7 @@ fn override_table_eval_file() {
 #[test]
 fn infinite_recursion_does_not_panic() {
     let actual = nu!(r#"
-            def bang [] { bang }; bang
+            def bang [] { 1 + (bang) }; bang
         "#);
     assert!(actual.err.contains("Recursion limit (50) reached"));
 }
@@ -236,12 +236,60 @@ fn infinite_recursion_does_not_panic() {
 #[cfg(not(target_os = "windows"))]
 #[test]
 fn infinite_mutual_recursion_does_not_panic() {
+    let actual = nu!(r#"
+            def bang [] { def boom [] { 1 + (bang) }; boom }; bang
+        "#);
+    assert!(actual.err.contains("Recursion limit (50) reached"));
+}
+
+#[test]
+fn infinite_tail_recursion_without_arguments_is_limited() {
+    let actual = nu!(r#"
+            def bang [] { bang }; bang
+        "#);
+    assert!(actual.err.contains("Recursion limit (50) reached"));
+}
+
+#[test]
+fn infinite_mutual_tail_recursion_without_arguments_is_limited() {
     let actual = nu!(r#"
             def bang [] { def boom [] { bang }; boom }; bang
         "#);
     assert!(actual.err.contains("Recursion limit (50) reached"));
 }
 
+#[test]
+fn tail_recursion_is_not_limited() -> TestResult {
+    run_test(
+        r#"def sum-to [n: int, acc: int] { if $n == 0 { $acc } else { sum-to ($n - 1) ($acc + $n) } }; sum-to 10000 0"#,
+        "50005000",
+    )
+}
+
+#[test]
+fn mutual_tail_recursion_is_not_limited() -> TestResult {
+    run_test(
+        r#"def is-even [n: int] { if $n == 0 { true } else { is-odd ($n - 1) } }; def is-odd [n: int] { if $n == 0 { false } else { is-even ($n - 1) } }; is-even 1001"#,
+        "false",
+    )
+}
+
+#[test]
+fn tail_called_command_can_return_early() -> TestResult {
+    run_test(
+        r#"def countdown [n: int] { if $n == 0 { return done }; countdown ($n - 1) }; countdown 1000"#,
+        "done",
+    )
+}
+
+#[test]
+fn tail_call_keeps_the_callers_environment() -> TestResult {
+    run_test(
+        r#"def show [n: int] { if $n == 0 { $env.DEPTH } else { $env.DEPTH = $n; show ($n - 1) } }; show 100"#,
+        "1",
+    )
+}
+
 #[test]
 fn type_check_for_during_eval() -> TestResult {
     fail_test(
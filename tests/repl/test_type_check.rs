@@ -189,21 +189,85 @@ fn collection_supertype_inference(
 fn pipeline_oneof() -> TestResult {
     // Empty is compatible with oneof<nothing, ..>
     run_test(
-        "def f []: [oneof<int, nothing> -> nothing] { describe }; f",
+        "def f []: [oneof<int, nothing> -> string] { describe }; f",
         "nothing",
     )?;
     // ByteStream is compatible with oneof<binary, ..>
     run_test(
-        "def f []: [oneof<int, binary> -> nothing] { describe }; [0x[01]] | bytes collect | f",
+        "def f []: [oneof<int, binary> -> string] { describe }; [0x[01]] | bytes collect | f",
         "binary (stream)",
     )?;
     // ListStream is compatible with oneof<list, ..>>
     run_test(
-        "def f []: [oneof<string, list<int>> -> nothing] { describe }; [1] | each {} | f",
+        "def f []: [oneof<string, list<int>> -> string] { describe }; [1] | each {} | f",
         "list<int> (stream)",
     )
 }
 
+#[test]
+fn custom_command_output_matches_declared_type() -> TestResult {
+    run_test(
+        "def f []: [int -> string, string -> int] { if $in == 'a' { 1 } else { 'b' } }; 1 | f",
+        "b",
+    )
+}
+
+#[test]
+fn custom_command_output_mismatch() -> TestResult {
+    fail_test(
+        "def f []: nothing -> int { $env.NOT_AN_INT? | default 'a' }; f",
+        "nu::shell::only_supports_this_output_type",
+    )
+}
+
+#[test]
+fn custom_command_output_mismatch_in_tail_call() -> TestResult {
+    fail_test(
+        "def g [] { 'a' }; def f []: nothing -> int { g }; f",
+        "command declares int output, but produced string",
+    )
+}
+
+#[test]
+fn custom_command_nothing_output_is_checked() -> TestResult {
+    fail_test(
+        "def f []: nothing -> nothing { $env.NOT_NOTHING? | default 1 }; f",
+        "command declares nothing output, but produced int",
+    )
+}
+
+#[test]
+fn custom_command_may_output_nothing_for_any_type() -> TestResult {
+    run_test(
+        "def f []: nothing -> string { if false { 'a' } }; f | describe",
+        "nothing",
+    )
+}
+
+#[test]
+fn custom_command_output_streams_stay_lazy() -> TestResult {
+    run_test(
+        "def f []: nothing -> list<int> { 1.. | each {|x| $x } }; f | first 3 | math sum",
+        "6",
+    )
+}
+
+#[test]
+fn custom_command_output_from_external_is_not_checked() -> TestResult {
+    run_test(
+        "def f []: nothing -> int { nu --testbin cococo hello }; f",
+        "hello",
+    )
+}
+
+#[test]
+fn input_mismatch_names_supported_types() -> TestResult {
+    fail_test(
+        "def f []: [int -> int, string -> int] { 3 }; {x: 2} | f",
+        "only supports int or string input",
+    )
+}
+
 #[test]
 fn transpose_into_load_env() -> TestResult {
     run_test(
@@ -346,3 +346,67 @@ fn example_results_have_valid_span() {
     let actual = nu!(&inp.join(" "));
     assert_eq!(actual.out, "scope commands");
 }
+
+#[test]
+fn scope_commands_show_definition_and_module() {
+    let module_setup = r#"
+        # nice spam
+        export def eggs [] { 'eggs' }
+    "#;
+
+    Playground::setup(
+        "scope_commands_show_definition_and_module",
+        |dirs, sandbox| {
+            sandbox.with_files(&[FileWithContent("spam.nu", module_setup)]);
+
+            let inp = &[
+                "use spam.nu",
+                "scope commands | where name == 'spam eggs' | get 0 | $'($in.module) ($in.line) ($in.file | path basename)'",
+            ];
+            let actual = nu!(cwd: dirs.test(), &inp.join("; "));
+            assert_eq!(actual.out, "spam 3 spam.nu");
+
+            let inp = &[
+                "use spam.nu",
+                "scope commands | where name == 'spam eggs' | get 0.span | view span $in.start $in.end",
+            ];
+            let actual = nu!(cwd: dirs.test(), &inp.join("; "));
+            assert_eq!(actual.out, "{ 'eggs' }");
+        },
+    )
+}
+
+#[test]
+fn scope_commands_builtin_has_no_definition() {
+    let actual = nu!(
+        "scope commands | where name == 'echo' | get 0 | [$in.file $in.span $in.module] | all { is-empty }"
+    );
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn scope_variables_show_doc_comments_and_definition() {
+    let inp = &[
+        "# the answer",
+        "#",
+        "# to everything",
+        "const x = 42",
+        "scope variables | where name == '$x' | get 0 | $'($in.description)|($in.extra_description)|($in.line)'",
+    ];
+    let actual = nu!(&inp.join("\n"));
+    assert_eq!(actual.out, "the answer|to everything|4");
+}
+
+#[test]
+fn scope_modules_constants_keep_doc_comments() {
+    let inp = &[
+        "module spam {",
+        "    # the number of eggs",
+        "    export const eggs = 3",
+        "}",
+        "use spam eggs",
+        "scope variables | where name == '$eggs' | get 0.description",
+    ];
+    let actual = nu!(&inp.join("\n"));
+    assert_eq!(actual.out, "the number of eggs");
+}
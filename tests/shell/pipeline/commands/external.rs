@@ -148,6 +148,26 @@ fn command_not_found_error_shows_not_found_1() {
     assert!(actual.err.contains("Command `foo` not found"));
 }
 
+#[test]
+fn command_not_found_hook_output_is_shown_as_help() {
+    let actual = nu!(r#"
+            $env.config.hooks.command_not_found = {|name| $"($name) is provided by the ferris package" }
+            ferris_is_not_here.exe
+        "#);
+    assert!(
+        actual
+            .err
+            .contains("ferris_is_not_here.exe is provided by the ferris package")
+    );
+
+    let actual = nu!(r#"
+            $env.config.hooks.command_not_found = {|name| ["Install it with:" "  apt install ferris"] }
+            ferris_is_not_here.exe
+        "#);
+    assert!(actual.err.contains("Install it with:"));
+    assert!(actual.err.contains("apt install ferris"));
+}
+
 #[test]
 fn command_substitution_wont_output_extra_newline() {
     let actual = nu!(r#"